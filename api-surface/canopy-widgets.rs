@@ -583,6 +583,157 @@ pub mod canopy_widgets {
         }
     }
 
+    pub mod term {
+        //! Embedded terminal widget running a command on a PTY.
+
+        /// Terminal widget backed by `itty`.
+        pub struct Terminal {}
+
+        impl Terminal {
+            /// Construct a new terminal widget with the provided configuration.
+            pub fn new(config: TerminalConfig) -> Self {}
+
+            /// Return the exit status of the child process, if it has exited.
+            pub fn exit_status(&self) -> Option<ExitStatus> {}
+
+            /// Return true if the child process is still running.
+            pub fn is_running(&self) -> bool {}
+
+            /// Return the most recent terminal title, if any.
+            pub fn title(&self) -> Option<String> {}
+
+            /// Return the attached `itty` driver handle for scripting integrations.
+            pub fn driver_handle(&self) -> Option<Arc<DriverHandle>> {}
+        }
+
+        impl CommandNode for Terminal {
+            fn commands() -> &'static [&'static canopy::commands::CommandSpec] {}
+        }
+
+        impl Widget for Terminal {
+            fn render(&mut self, rndr: &mut Render<'_>, ctx: &dyn ReadContext) -> Result<()> {}
+
+            fn on_event(
+                &mut self,
+                event: &event::Event,
+                ctx: &mut dyn Context,
+            ) -> Result<EventOutcome> {
+            }
+
+            fn measure(&self, c: MeasureConstraints) -> Measurement {}
+
+            fn canvas(&self, view: Size<u32>, _ctx: &CanvasContext<'_>) -> Size<u32> {}
+
+            fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {}
+
+            fn cursor(&self) -> Option<cursor::Cursor> {}
+
+            fn poll(&mut self, _ctx: &mut dyn Context) -> Option<Duration> {}
+
+            fn on_mount(&mut self, _ctx: &mut dyn Context) -> Result<()> {}
+
+            fn name(&self) -> NodeName {}
+        }
+
+        /// Terminal color palette.
+        #[derive(Clone, Copy, Debug, StructuralPartialEq, PartialEq, Eq, Default)]
+        pub struct TerminalColors {
+            /// ANSI black (0).
+            pub black: canopy::style::Color,
+            /// ANSI red (1).
+            pub red: canopy::style::Color,
+            /// ANSI green (2).
+            pub green: canopy::style::Color,
+            /// ANSI yellow (3).
+            pub yellow: canopy::style::Color,
+            /// ANSI blue (4).
+            pub blue: canopy::style::Color,
+            /// ANSI magenta (5).
+            pub magenta: canopy::style::Color,
+            /// ANSI cyan (6).
+            pub cyan: canopy::style::Color,
+            /// ANSI white (7).
+            pub white: canopy::style::Color,
+            /// ANSI bright black (8).
+            pub bright_black: canopy::style::Color,
+            /// ANSI bright red (9).
+            pub bright_red: canopy::style::Color,
+            /// ANSI bright green (10).
+            pub bright_green: canopy::style::Color,
+            /// ANSI bright yellow (11).
+            pub bright_yellow: canopy::style::Color,
+            /// ANSI bright blue (12).
+            pub bright_blue: canopy::style::Color,
+            /// ANSI bright magenta (13).
+            pub bright_magenta: canopy::style::Color,
+            /// ANSI bright cyan (14).
+            pub bright_cyan: canopy::style::Color,
+            /// ANSI bright white (15).
+            pub bright_white: canopy::style::Color,
+            /// Default foreground color.
+            pub foreground: canopy::style::Color,
+            /// Default background color.
+            pub background: canopy::style::Color,
+            /// Cursor color.
+            pub cursor: canopy::style::Color,
+        }
+
+        /// Terminal widget configuration.
+        #[derive(Default)]
+        pub struct TerminalConfig {}
+
+        impl TerminalConfig {
+            /// Construct a default terminal configuration.
+            pub fn new() -> Self {}
+
+            /// Configure the command argv to run instead of the default shell.
+            pub fn with_command<I, S>(self, command: I) -> Self
+            where
+                I: IntoIterator<Item = S>,
+                S: Into<String>, {
+            }
+
+            /// Configure the working directory for the terminal process.
+            pub fn with_cwd(self, cwd: impl Into<PathBuf>) -> Self {}
+
+            /// Add an environment variable for the terminal process.
+            pub fn with_env(self, key: impl Into<String>, value: impl Into<String>) -> Self {}
+
+            /// Configure the number of scrollback lines to keep.
+            pub fn with_scrollback_lines(self, scrollback_lines: usize) -> Self {}
+
+            /// Configure terminal mouse reporting.
+            pub fn with_mouse_reporting(self, mouse_reporting: bool) -> Self {}
+
+            /// Configure bracketed paste support.
+            pub fn with_bracketed_paste(self, bracketed_paste: bool) -> Self {}
+
+            /// Configure kitty keyboard protocol support.
+            pub fn with_kitty_keyboard(self, kitty_keyboard: bool) -> Self {}
+
+            /// Configure the terminal color palette.
+            pub fn with_colors(self, colors: TerminalColors) -> Self {}
+
+            /// Configure the clipboard store callback.
+            pub fn with_clipboard_store<F>(self, store: F) -> Self
+            where
+                F: Fn(String) + Send + Sync + 'static, {
+            }
+
+            /// Configure the clipboard load callback.
+            pub fn with_clipboard_load<F>(self, load: F) -> Self
+            where
+                F: Fn() -> String + Send + Sync + 'static, {
+            }
+
+            /// Configure the child exit callback.
+            pub fn with_on_exit<F>(self, on_exit: F) -> Self
+            where
+                F: Fn(ExitStatus) + Send + Sync + 'static, {
+            }
+        }
+    }
+
     /// A simple box container around its children.
     #[derive(Default)]
     pub struct Box {}
@@ -1137,47 +1288,51 @@ pub mod canopy_widgets {
         fn load(cnpy: &mut Canopy) -> canopy_error::Result<()> {}
     }
 
-    /// Single-line text input widget.
+    /// Single-line text input widget. Text is edited by an [`Editor`] configured with
+    /// [`EditorConfig::single_line`], so inputs share the editor's cursor movement, paste
+    /// handling, and horizontal scrolling.
     pub struct Input {}
 
     impl Input {
-        /// Construct a new input with initial text.
+        /// Construct a new input with initial text, with the cursor at its end.
         pub fn new(txt: impl Into<String>) -> Self {}
 
-        /// Return the current input text.
-        pub fn text(&self) -> &str {}
-
-        /// Return the raw input value without padding.
-        pub fn value(&self) -> &str {}
+        /// Build an input that underlines misspelled words with the `editor/misspelled` style
+        /// and offers corrections through `spell_suggest`.
+        pub fn with_spell_check(mut self, spell: SpellCheck) -> Self {}
 
-        /// Replace the input value and reset the cursor.
-        pub fn set_value(&mut self, value: impl Into<String>) {}
+        /// Install or remove the spell checker.
+        pub fn set_spell_check(&mut self, spell: Option<SpellCheck>) {}
 
-        /// Return a typed command reference for this command.
-        pub fn cmd_left() -> &'static canopy::commands::CommandSpec {}
+        /// Return the input text.
+        pub fn value(&self) -> String {}
 
-        /// Return a typed command reference for this command.
-        pub fn cmd_right() -> &'static canopy::commands::CommandSpec {}
+        /// Replace the input value, leaving the cursor at its end.
+        pub fn set_value(&mut self, value: impl Into<String>) {}
 
-        /// Return a typed command reference for this command.
-        pub fn cmd_backspace() -> &'static canopy::commands::CommandSpec {}
+        /// Offer spelling corrections for the word at the cursor in the completion popup.
+        pub fn spell_suggest(&mut self, c: &mut dyn Context) {}
     }
 
-    impl CommandNode for Input {
-        fn commands() -> &'static [&'static canopy::commands::CommandSpec] {}
-    }
+    impl Scrollable for Input {}
 
     impl Widget for Input {
+        fn scrollable(&mut self) -> Option<&mut dyn Scrollable> {}
+
         fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {}
 
+        fn accessible(&self, ctx: &dyn ReadContext) -> Option<Accessible> {}
+
         fn cursor(&self) -> Option<cursor::Cursor> {}
 
         fn render(&mut self, r: &mut Render<'_>, ctx: &dyn ReadContext) -> Result<()> {}
 
-        fn on_event(&mut self, event: &Event, _ctx: &mut dyn Context) -> Result<EventOutcome> {}
+        fn on_event(&mut self, event: &Event, ctx: &mut dyn Context) -> Result<EventOutcome> {}
 
         fn measure(&self, c: MeasureConstraints) -> Measurement {}
 
+        fn canvas(&self, view: Size<u32>, ctx: &CanvasContext) -> Size<u32> {}
+
         fn name(&self) -> NodeName {}
     }
 
@@ -1699,153 +1854,6 @@ pub mod canopy_widgets {
         fn label(&self) -> &str;
     }
 
-    /// Terminal widget backed by `itty`.
-    pub struct Terminal {}
-
-    impl Terminal {
-        /// Construct a new terminal widget with the provided configuration.
-        pub fn new(config: TerminalConfig) -> Self {}
-
-        /// Return the exit status of the child process, if it has exited.
-        pub fn exit_status(&self) -> Option<ExitStatus> {}
-
-        /// Return true if the child process is still running.
-        pub fn is_running(&self) -> bool {}
-
-        /// Return the most recent terminal title, if any.
-        pub fn title(&self) -> Option<String> {}
-
-        /// Return the attached `itty` driver handle for scripting integrations.
-        pub fn driver_handle(&self) -> Option<Arc<DriverHandle>> {}
-    }
-
-    impl CommandNode for Terminal {
-        fn commands() -> &'static [&'static canopy::commands::CommandSpec] {}
-    }
-
-    impl Widget for Terminal {
-        fn render(&mut self, rndr: &mut Render<'_>, ctx: &dyn ReadContext) -> Result<()> {}
-
-        fn on_event(
-            &mut self,
-            event: &event::Event,
-            ctx: &mut dyn Context,
-        ) -> Result<EventOutcome> {
-        }
-
-        fn measure(&self, c: MeasureConstraints) -> Measurement {}
-
-        fn canvas(&self, view: Size<u32>, _ctx: &CanvasContext<'_>) -> Size<u32> {}
-
-        fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {}
-
-        fn cursor(&self) -> Option<cursor::Cursor> {}
-
-        fn poll(&mut self, _ctx: &mut dyn Context) -> Option<Duration> {}
-
-        fn on_mount(&mut self, _ctx: &mut dyn Context) -> Result<()> {}
-
-        fn name(&self) -> NodeName {}
-    }
-
-    /// Terminal color palette.
-    #[derive(Clone, Copy, Debug, StructuralPartialEq, PartialEq, Eq, Default)]
-    pub struct TerminalColors {
-        /// ANSI black (0).
-        pub black: canopy::style::Color,
-        /// ANSI red (1).
-        pub red: canopy::style::Color,
-        /// ANSI green (2).
-        pub green: canopy::style::Color,
-        /// ANSI yellow (3).
-        pub yellow: canopy::style::Color,
-        /// ANSI blue (4).
-        pub blue: canopy::style::Color,
-        /// ANSI magenta (5).
-        pub magenta: canopy::style::Color,
-        /// ANSI cyan (6).
-        pub cyan: canopy::style::Color,
-        /// ANSI white (7).
-        pub white: canopy::style::Color,
-        /// ANSI bright black (8).
-        pub bright_black: canopy::style::Color,
-        /// ANSI bright red (9).
-        pub bright_red: canopy::style::Color,
-        /// ANSI bright green (10).
-        pub bright_green: canopy::style::Color,
-        /// ANSI bright yellow (11).
-        pub bright_yellow: canopy::style::Color,
-        /// ANSI bright blue (12).
-        pub bright_blue: canopy::style::Color,
-        /// ANSI bright magenta (13).
-        pub bright_magenta: canopy::style::Color,
-        /// ANSI bright cyan (14).
-        pub bright_cyan: canopy::style::Color,
-        /// ANSI bright white (15).
-        pub bright_white: canopy::style::Color,
-        /// Default foreground color.
-        pub foreground: canopy::style::Color,
-        /// Default background color.
-        pub background: canopy::style::Color,
-        /// Cursor color.
-        pub cursor: canopy::style::Color,
-    }
-
-    /// Terminal widget configuration.
-    #[derive(Default)]
-    pub struct TerminalConfig {}
-
-    impl TerminalConfig {
-        /// Construct a default terminal configuration.
-        pub fn new() -> Self {}
-
-        /// Configure the command argv to run instead of the default shell.
-        pub fn with_command<I, S>(self, command: I) -> Self
-        where
-            I: IntoIterator<Item = S>,
-            S: Into<String>, {
-        }
-
-        /// Configure the working directory for the terminal process.
-        pub fn with_cwd(self, cwd: impl Into<PathBuf>) -> Self {}
-
-        /// Add an environment variable for the terminal process.
-        pub fn with_env(self, key: impl Into<String>, value: impl Into<String>) -> Self {}
-
-        /// Configure the number of scrollback lines to keep.
-        pub fn with_scrollback_lines(self, scrollback_lines: usize) -> Self {}
-
-        /// Configure terminal mouse reporting.
-        pub fn with_mouse_reporting(self, mouse_reporting: bool) -> Self {}
-
-        /// Configure bracketed paste support.
-        pub fn with_bracketed_paste(self, bracketed_paste: bool) -> Self {}
-
-        /// Configure kitty keyboard protocol support.
-        pub fn with_kitty_keyboard(self, kitty_keyboard: bool) -> Self {}
-
-        /// Configure the terminal color palette.
-        pub fn with_colors(self, colors: TerminalColors) -> Self {}
-
-        /// Configure the clipboard store callback.
-        pub fn with_clipboard_store<F>(self, store: F) -> Self
-        where
-            F: Fn(String) + Send + Sync + 'static, {
-        }
-
-        /// Configure the clipboard load callback.
-        pub fn with_clipboard_load<F>(self, load: F) -> Self
-        where
-            F: Fn() -> String + Send + Sync + 'static, {
-        }
-
-        /// Configure the child exit callback.
-        pub fn with_on_exit<F>(self, on_exit: F) -> Self
-        where
-            F: Fn(ExitStatus) + Send + Sync + 'static, {
-        }
-    }
-
     /// Canvas width behavior for text widgets.
     #[derive(Debug, Clone, Copy, StructuralPartialEq, PartialEq, Eq)]
     pub enum CanvasWidth {
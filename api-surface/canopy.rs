@@ -430,8 +430,8 @@ pub mod canopy {
             /// Drain and return assertion outcomes from the most recent script evaluation.
             pub fn take_script_assertions(&self) -> Vec<script::ScriptAssertion> {}
 
-            /// Evaluate a Luau config file from disk.
-            pub fn run_config(&mut self, path: &FsPath) -> Result<()> {}
+            /// Evaluate a user Luau script file from disk, such as a config file.
+            pub fn load_script_file(&mut self, path: &FsPath) -> Result<()> {}
 
             /// Remove a binding by ID. Returns true if a binding was removed.
             pub fn unbind(&mut self, id: inputmap::BindingId) -> bool {}
@@ -2907,8 +2907,8 @@ pub mod canopy {
         /// Drain and return assertion outcomes from the most recent script evaluation.
         pub fn take_script_assertions(&self) -> Vec<script::ScriptAssertion> {}
 
-        /// Evaluate a Luau config file from disk.
-        pub fn run_config(&mut self, path: &FsPath) -> Result<()> {}
+        /// Evaluate a user Luau script file from disk, such as a config file.
+        pub fn load_script_file(&mut self, path: &FsPath) -> Result<()> {}
 
         /// Remove a binding by ID. Returns true if a binding was removed.
         pub fn unbind(&mut self, id: inputmap::BindingId) -> bool {}
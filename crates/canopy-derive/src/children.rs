@@ -0,0 +1,100 @@
+use proc_macro_error::{abort, abort_call_site};
+use quote::quote;
+use syn::{Attribute, DeriveInput, Fields, LitStr};
+
+/// Options parsed from `#[child(...)]` field attributes.
+#[derive(Default)]
+struct ChildOptions {
+    /// Key override from `key = "..."`.
+    key: Option<LitStr>,
+    /// Whether loading runs the child type's `Loader` instead of only registering its
+    /// commands.
+    load: bool,
+}
+
+/// Parse the `#[child(...)]` attributes of a field.
+fn child_options(attrs: &[Attribute]) -> ChildOptions {
+    let mut options = ChildOptions::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("child")) {
+        if matches!(attr.meta, syn::Meta::Path(_)) {
+            continue;
+        }
+        let parsed = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("key") {
+                options.key = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else if meta.path.is_ident("load") {
+                options.load = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported child option, expected `key` or `load`"))
+            }
+        });
+        if let Err(error) = parsed {
+            abort!(error.span(), "{}", error);
+        }
+    }
+    options
+}
+
+/// Expand `#[derive(ChildSet)]` into a `canopy::ChildSet` implementation.
+pub fn expand_derive_children(input: DeriveInput) -> proc_macro::TokenStream {
+    let ident = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match input.data {
+        syn::Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            _ => abort_call_site!("ChildSet can only be derived for structs with named fields"),
+        },
+        _ => abort_call_site!("ChildSet can only be derived for structs"),
+    };
+
+    let mut names = Vec::new();
+    let mut keys = Vec::new();
+    let mut loads = Vec::new();
+    for field in fields {
+        let Some(name) = field.ident else {
+            continue;
+        };
+        let options = child_options(&field.attrs);
+        let key = options
+            .key
+            .unwrap_or_else(|| LitStr::new(&name.to_string(), name.span()));
+        if keys
+            .iter()
+            .any(|existing: &LitStr| existing.value() == key.value())
+        {
+            abort!(key.span(), "duplicate child key `{}`", key.value());
+        }
+        let ty = field.ty;
+        loads.push(if options.load {
+            quote! { <#ty as canopy::Loader>::load(c)?; }
+        } else {
+            quote! { c.add_commands::<#ty>()?; }
+        });
+        names.push(name);
+        keys.push(key);
+    }
+
+    let expanded = quote! {
+        impl #impl_generics canopy::ChildSet for #ident #ty_generics #where_clause {
+            const KEYS: &'static [&'static str] = &[#(#keys),*];
+
+            fn mount(
+                self,
+                c: &mut dyn canopy::Context,
+                parent: canopy::NodeId,
+            ) -> canopy::error::Result<::std::vec::Vec<canopy::NodeId>> {
+                Ok(::std::vec![#(c.add_keyed_to(parent, #keys, self.#names)?.into()),*])
+            }
+
+            fn load(c: &mut canopy::Canopy) -> canopy::error::Result<()> {
+                #(#loads)*
+                Ok(())
+            }
+        }
+    };
+
+    expanded.into()
+}
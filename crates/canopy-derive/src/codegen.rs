@@ -248,15 +248,15 @@ impl DocMeta {
 }
 
 impl CommandMeta {
-    /// Render this command name as an identifier.
+    /// Render the command method name as an identifier.
     fn name_ident(&self) -> syn::Ident {
-        syn::Ident::new(&self.name, proc_macro2::Span::call_site())
+        syn::Ident::new(&self.method, proc_macro2::Span::call_site())
     }
 
     /// Identifier for the generated invoke shim.
     fn invoke_ident(&self) -> syn::Ident {
         syn::Ident::new(
-            &format!("__canopy_cmd_invoke_{}", self.name),
+            &format!("__canopy_cmd_invoke_{}", self.method),
             proc_macro2::Span::call_site(),
         )
     }
@@ -264,7 +264,7 @@ impl CommandMeta {
     /// Identifier for the generated parameter spec constant.
     fn params_const_ident(&self) -> syn::Ident {
         syn::Ident::new(
-            &format!("__CANOPY_CMD_{}_PARAMS", self.name.to_uppercase()),
+            &format!("__CANOPY_CMD_{}_PARAMS", self.method.to_uppercase()),
             proc_macro2::Span::call_site(),
         )
     }
@@ -272,7 +272,7 @@ impl CommandMeta {
     /// Identifier for the generated command spec constant.
     fn spec_const_ident(&self) -> syn::Ident {
         syn::Ident::new(
-            &format!("__CANOPY_CMD_{}_SPEC", self.name.to_uppercase()),
+            &format!("__CANOPY_CMD_{}_SPEC", self.method.to_uppercase()),
             proc_macro2::Span::call_site(),
         )
     }
@@ -280,7 +280,7 @@ impl CommandMeta {
     /// Identifier for the generated list of user parameter names.
     fn names_const_ident(&self) -> syn::Ident {
         syn::Ident::new(
-            &format!("__CANOPY_CMD_{}_USER_PARAMS", self.name.to_uppercase()),
+            &format!("__CANOPY_CMD_{}_USER_PARAMS", self.method.to_uppercase()),
             proc_macro2::Span::call_site(),
         )
     }
@@ -288,7 +288,7 @@ impl CommandMeta {
     /// Identifier for the typed command accessor.
     fn accessor_ident(&self) -> syn::Ident {
         syn::Ident::new(
-            &format!("cmd_{}", self.name),
+            &format!("cmd_{}", self.method),
             proc_macro2::Span::call_site(),
         )
     }
@@ -482,6 +482,7 @@ impl CommandMeta {
         let short = DocMeta::option_tokens(&self.doc.short);
         let long = DocMeta::option_tokens(&self.doc.long);
         let hidden = self.doc.hidden;
+        let keys = &self.keys;
 
         quote! {
             const #spec_const_ident: canopy::commands::CommandSpec = canopy::commands::CommandSpec {
@@ -495,6 +496,7 @@ impl CommandMeta {
                     long: #long,
                     hidden: #hidden,
                 },
+                keys: &[#(#keys),*],
                 invoke: Self::#invoke_ident,
             };
         }
@@ -1,13 +1,19 @@
 //! Proc-macro support for canopy commands and nodes.
 
+/// `ChildSet` derive expansion.
+mod children;
 /// Command metadata token emission.
 mod codegen;
 /// Local error type for derive parsing.
 mod error;
+/// `Loader` derive expansion.
+mod loader;
 /// Parsed command metadata model.
 mod model;
 /// Parsing support for `derive_commands`.
 mod parse;
+/// `view!` tree-building macro.
+mod view;
 
 use proc_macro_error::{abort, abort_call_site, proc_macro_error};
 use quote::quote;
@@ -34,6 +40,43 @@ pub fn command(
     input
 }
 
+/// Derive `canopy::ChildSet` for a struct whose named fields are child widgets. Each
+/// field is mounted as a keyed child in declaration order, keyed by its field name or by
+/// `#[child(key = "...")]`. The generated `ChildSet::load` registers each child type's
+/// commands, or runs its `Loader` for fields marked `#[child(load)]`.
+#[proc_macro_error]
+#[proc_macro_derive(ChildSet, attributes(child))]
+pub fn derive_children(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    children::expand_derive_children(input)
+}
+
+/// Derive `canopy::Loader` for a widget. The generated loader registers the widget's own
+/// commands, then loads the types named in `#[loader(...)]`: `children(...)` for
+/// `ChildSet` structs, `load(...)` for types with their own `Loader`, and `commands(...)`
+/// for widgets whose commands only need registering.
+#[proc_macro_error]
+#[proc_macro_derive(Loader, attributes(loader))]
+pub fn derive_loader(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    loader::expand_derive_loader(input)
+}
+
+/// Build a tree of widgets below the current node, declaratively.
+///
+/// The first argument is a `&mut dyn Context`. Each node is a widget expression, with
+/// optional `#[layout(...)]`, `#[key = "..."]` and repeatable `#[bind("key", "command")]`
+/// attributes, an optional `name =` prefix that binds its typed node ID to a local
+/// variable, and an optional braced list of children. A `bind` attribute binds a key to a
+/// command string while focus is inside the node. The macro expands to the equivalent
+/// `add_child_to`, `add_keyed_to`, `set_layout_of`, and `bind_key_in` calls and must be
+/// used where `?` can propagate a canopy error.
+#[proc_macro]
+pub fn view(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as view::ViewInput);
+    view::expand_view(input)
+}
+
 /// Derive the CommandArg marker trait for serde-backed types.
 #[proc_macro_derive(CommandArg)]
 pub fn derive_command_arg(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -0,0 +1,59 @@
+use proc_macro_error::abort;
+use quote::quote;
+use syn::{DeriveInput, Token, Type, meta::ParseNestedMeta, punctuated::Punctuated};
+
+/// Parse a parenthesized, comma-separated type list from a `#[loader(...)]` option.
+fn type_list(meta: &ParseNestedMeta<'_>) -> syn::Result<Vec<Type>> {
+    let content;
+    syn::parenthesized!(content in meta.input);
+    Ok(Punctuated::<Type, Token![,]>::parse_terminated(&content)?
+        .into_iter()
+        .collect())
+}
+
+/// Expand `#[derive(Loader)]` into a `canopy::Loader` implementation.
+pub fn expand_derive_loader(input: DeriveInput) -> proc_macro::TokenStream {
+    let ident = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut children = Vec::new();
+    let mut loaders = Vec::new();
+    let mut commands = Vec::new();
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("loader"))
+    {
+        let parsed = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("children") {
+                children.extend(type_list(&meta)?);
+            } else if meta.path.is_ident("load") {
+                loaders.extend(type_list(&meta)?);
+            } else if meta.path.is_ident("commands") {
+                commands.extend(type_list(&meta)?);
+            } else {
+                return Err(meta.error(
+                    "unsupported loader option, expected `children`, `load`, or `commands`",
+                ));
+            }
+            Ok(())
+        });
+        if let Err(error) = parsed {
+            abort!(error.span(), "{}", error);
+        }
+    }
+
+    let expanded = quote! {
+        impl #impl_generics canopy::Loader for #ident #ty_generics #where_clause {
+            fn load(c: &mut canopy::Canopy) -> canopy::error::Result<()> {
+                c.add_commands::<Self>()?;
+                #(<#children as canopy::ChildSet>::load(c)?;)*
+                #(<#loaders as canopy::Loader>::load(c)?;)*
+                #(c.add_commands::<#commands>()?;)*
+                Ok(())
+            }
+        }
+    };
+
+    expanded.into()
+}
@@ -9,6 +9,10 @@ pub struct MacroArgs {
     pub(crate) desc: Option<syn::LitStr>,
     /// Mark command as hidden from help.
     pub(crate) hidden: bool,
+    /// Override the command name.
+    pub(crate) name: Option<syn::LitStr>,
+    /// Default key bindings.
+    pub(crate) keys: Vec<syn::LitStr>,
 }
 
 /// Parsed default argument value.
@@ -92,6 +96,8 @@ pub struct DocMeta {
 pub struct CommandMeta {
     /// Command name (snake_case).
     pub(crate) name: String,
+    /// Rust method name, which differs from `name` when the command is renamed.
+    pub(crate) method: String,
     /// Owner type name.
     pub(crate) owner: String,
     /// Parameters in declaration order.
@@ -102,6 +108,8 @@ pub struct CommandMeta {
     pub(crate) ret: ReturnMeta,
     /// Documentation metadata.
     pub(crate) doc: DocMeta,
+    /// Default key bindings.
+    pub(crate) keys: Vec<String>,
 }
 
 /// The source used to bind a user-supplied command argument.
@@ -238,6 +238,12 @@ fn parse_command_macro_args(attrs: &[Attribute]) -> Result<Option<MacroArgs>> {
                     } else if meta.path.is_ident("desc") {
                         let value = meta.value()?;
                         args.desc = Some(value.parse()?);
+                    } else if meta.path.is_ident("name") {
+                        let value = meta.value()?;
+                        args.name = Some(value.parse()?);
+                    } else if meta.path.is_ident("key") {
+                        let value = meta.value()?;
+                        args.keys.push(value.parse()?);
                     } else {
                         return Err(syn::Error::new_spanned(
                             meta.path,
@@ -406,13 +412,37 @@ pub fn parse_command_method(owner: &str, method: &mut ImplItemFn) -> Result<Opti
 
     let ret = parse_return_type(&method.sig.output)?;
 
+    let name = match &macro_args.name {
+        Some(name) => {
+            let value = name.value();
+            if syn::parse_str::<syn::Ident>(&value).is_err() {
+                return Err(Error::Parse(format!(
+                    "command name {value:?} is not an identifier"
+                )));
+            }
+            value
+        }
+        None => method.sig.ident.to_string(),
+    };
+    if !macro_args.keys.is_empty()
+        && params.iter().any(|param| {
+            param.kind == ParamKind::User && !param.is_option && param.default.is_none()
+        })
+    {
+        return Err(Error::Unsupported(
+            "key bindings require a command without required arguments".into(),
+        ));
+    }
+
     Ok(Some(CommandMeta {
-        name: method.sig.ident.to_string(),
+        name,
+        method: method.sig.ident.to_string(),
         owner: owner.to_string(),
         params,
         ignore_result: macro_args.ignore_result,
         ret,
         doc,
+        keys: macro_args.keys.iter().map(syn::LitStr::value).collect(),
     }))
 }
 
@@ -453,6 +483,28 @@ mod tests {
         assert!(cmd.ret.is_result);
     }
 
+    #[test]
+    fn command_options_rename_and_bind() {
+        let mut method: syn::ImplItemFn = parse_quote! {
+            #[command(name = "sel_next", hidden, key = "j", key = "Down")]
+            fn select_next(&mut self, _core: &mut dyn canopy::Context) -> Result<()> {
+                Ok(())
+            }
+        };
+        let cmd = parse_command_method("foo", &mut method).unwrap().unwrap();
+        assert_eq!(cmd.name, "sel_next");
+        assert_eq!(cmd.method, "select_next");
+        assert!(cmd.doc.hidden);
+        assert_eq!(cmd.keys, ["j", "Down"]);
+
+        let mut method: syn::ImplItemFn = parse_quote! {
+            #[command(key = "g")]
+            fn goto(&mut self, _core: &mut dyn canopy::Context, line: usize) {}
+        };
+        let err = parse_command_method("foo", &mut method).unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)));
+    }
+
     #[test]
     fn rejects_unsupported_reference_args() {
         let mut method: syn::ImplItemFn = parse_quote! {
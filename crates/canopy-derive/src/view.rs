@@ -0,0 +1,156 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    Attribute, Expr, Ident, LitStr, Token, braced,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token,
+};
+
+/// Parsed `view!` invocation: a context and the nodes to add below the current node.
+pub struct ViewInput {
+    /// Context expression used to add nodes.
+    ctx: Ident,
+    /// Top-level nodes.
+    items: Vec<ViewItem>,
+}
+
+/// One node in a `view!` tree.
+struct ViewItem {
+    /// Variable the node's typed ID is bound to.
+    binding: Option<Ident>,
+    /// Widget construction expression.
+    widget: Expr,
+    /// Layout from `#[layout(...)]`.
+    layout: Option<Expr>,
+    /// Child key from `#[key = ...]`.
+    key: Option<Expr>,
+    /// Key bindings from `#[bind("key", "command")]`, scoped to the node.
+    bindings: Vec<ViewBinding>,
+    /// Nested children.
+    children: Vec<ViewItem>,
+}
+
+/// A key binding on a `view!` node.
+struct ViewBinding {
+    /// Key spec, such as `ctrl-s`.
+    key: LitStr,
+    /// Command string run when the key is pressed.
+    command: LitStr,
+}
+
+impl Parse for ViewBinding {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let key = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let command = input.parse()?;
+        Ok(Self { key, command })
+    }
+}
+
+/// Parse a comma-separated list of view items.
+fn parse_items(input: ParseStream<'_>) -> syn::Result<Vec<ViewItem>> {
+    Ok(Punctuated::<ViewItem, Token![,]>::parse_terminated(input)?
+        .into_iter()
+        .collect())
+}
+
+impl Parse for ViewInput {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let ctx = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let items = parse_items(input)?;
+        Ok(Self { ctx, items })
+    }
+}
+
+impl Parse for ViewItem {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let mut layout = None;
+        let mut key = None;
+        let mut bindings = Vec::new();
+        for attr in attrs {
+            if attr.path().is_ident("layout") {
+                layout = Some(attr.parse_args::<Expr>()?);
+            } else if attr.path().is_ident("key") {
+                key = Some(attr.meta.require_name_value()?.value.clone());
+            } else if attr.path().is_ident("bind") {
+                bindings.push(attr.parse_args::<ViewBinding>()?);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "unsupported view attribute, expected `layout`, `key` or `bind`",
+                ));
+            }
+        }
+
+        let binding = if input.peek(Ident) && input.peek2(Token![=]) && !input.peek2(Token![==]) {
+            let binding = input.parse()?;
+            input.parse::<Token![=]>()?;
+            Some(binding)
+        } else {
+            None
+        };
+        let widget = Expr::parse_without_eager_brace(input)?;
+        let children = if input.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+            parse_items(&content)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            binding,
+            widget,
+            layout,
+            key,
+            bindings,
+            children,
+        })
+    }
+}
+
+/// Emit the statements that add `item` and its children below `parent`.
+fn expand_item(
+    ctx: &Ident,
+    parent: &TokenStream,
+    item: &ViewItem,
+    next: &mut usize,
+    out: &mut TokenStream,
+) {
+    let id = Ident::new(&format!("__canopy_view_{next}"), Span::call_site());
+    *next += 1;
+
+    let widget = &item.widget;
+    out.extend(match &item.key {
+        Some(key) => quote! { let #id = #ctx.add_keyed_to(#parent, #key, #widget)?; },
+        None => quote! { let #id = #ctx.add_child_to(#parent, #widget)?; },
+    });
+    if let Some(layout) = &item.layout {
+        out.extend(quote! { #ctx.set_layout_of(#id, #layout)?; });
+    }
+    for ViewBinding { key, command } in &item.bindings {
+        out.extend(quote! { #ctx.bind_key_in(#id.into(), #key, #command)?; });
+    }
+    let parent = quote! { #id };
+    for child in &item.children {
+        expand_item(ctx, &parent, child, next, out);
+    }
+    if let Some(binding) = &item.binding {
+        out.extend(quote! { let #binding = #id; });
+    }
+}
+
+/// Expand a `view!` invocation into the statements that build its tree.
+pub fn expand_view(input: ViewInput) -> proc_macro::TokenStream {
+    let ctx = &input.ctx;
+    let parent = Ident::new("__canopy_view_parent", Span::call_site());
+    let mut out = quote! { let #parent = #ctx.node_id(); };
+    let mut next = 0;
+    for item in &input.items {
+        expand_item(ctx, &quote! { #parent }, item, &mut next, &mut out);
+    }
+    out.into()
+}
@@ -0,0 +1,109 @@
+/// Insets from each edge of a rectangle.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub struct Insets {
+    /// Top inset.
+    pub top: u32,
+    /// Right inset.
+    pub right: u32,
+    /// Bottom inset.
+    pub bottom: u32,
+    /// Left inset.
+    pub left: u32,
+}
+
+impl Insets {
+    /// Construct insets from each edge, in CSS order.
+    pub fn new(top: u32, right: u32, bottom: u32, left: u32) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// The same inset on every edge.
+    pub fn all(n: u32) -> Self {
+        Self::new(n, n, n, n)
+    }
+
+    /// Equal top and bottom insets, and equal left and right insets.
+    pub fn symmetric(vertical: u32, horizontal: u32) -> Self {
+        Self::new(vertical, horizontal, vertical, horizontal)
+    }
+
+    /// Total horizontal inset.
+    pub fn horizontal(&self) -> u32 {
+        self.left.saturating_add(self.right)
+    }
+
+    /// Total vertical inset.
+    pub fn vertical(&self) -> u32 {
+        self.top.saturating_add(self.bottom)
+    }
+}
+
+/// Placement of a child within a parent rectangle.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub enum Align {
+    /// Top-left corner.
+    #[default]
+    TopLeft,
+    /// Top edge, centered horizontally.
+    Top,
+    /// Top-right corner.
+    TopRight,
+    /// Left edge, centered vertically.
+    Left,
+    /// Centered on both axes.
+    Center,
+    /// Right edge, centered vertically.
+    Right,
+    /// Bottom-left corner.
+    BottomLeft,
+    /// Bottom edge, centered horizontally.
+    Bottom,
+    /// Bottom-right corner.
+    BottomRight,
+}
+
+/// Position along one axis: start, center, or end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AxisAlign {
+    /// Flush with the start.
+    Start,
+    /// Centered, rounding toward the start.
+    Center,
+    /// Flush with the end.
+    End,
+}
+
+impl AxisAlign {
+    /// Offset of a span of `len` placed within `space`. `len` must not exceed `space`.
+    pub(crate) fn offset(self, space: u32, len: u32) -> u32 {
+        let free = space - len;
+        match self {
+            Self::Start => 0,
+            Self::Center => free / 2,
+            Self::End => free,
+        }
+    }
+}
+
+impl Align {
+    /// Split into horizontal and vertical placements.
+    pub(crate) fn axes(self) -> (AxisAlign, AxisAlign) {
+        use AxisAlign::{Center, End, Start};
+        match self {
+            Self::TopLeft => (Start, Start),
+            Self::Top => (Center, Start),
+            Self::TopRight => (End, Start),
+            Self::Left => (Start, Center),
+            Self::Center => (Center, Center),
+            Self::Right => (End, Center),
+            Self::BottomLeft => (Start, End),
+            Self::Bottom => (Center, End),
+            Self::BottomRight => (End, End),
+        }
+    }
+}
@@ -2,6 +2,8 @@
 
 #![warn(missing_docs)]
 
+/// Alignment and inset helpers.
+mod align;
 /// Error types for geometry operations.
 mod error;
 /// Frame and padding helpers.
@@ -21,6 +23,7 @@ mod rect_i32;
 /// Width/height size type.
 mod size;
 
+pub use align::{Align, Insets};
 pub use error::{Error, Result};
 pub use frame::FrameRects;
 pub use line::Line;
@@ -147,11 +147,11 @@ impl LineSegment {
                     len: pre as u32,
                 },
                 Self {
-                    off: self.off + pre as u32,
+                    off: self.off.saturating_add(pre as u32),
                     len: active as u32,
                 },
                 Self {
-                    off: self.off + pre as u32 + active as u32,
+                    off: self.off.saturating_add(pre as u32 + active as u32),
                     len: post as u32,
                 },
             ))
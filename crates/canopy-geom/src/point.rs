@@ -37,8 +37,29 @@ impl Point {
     /// Clamp a point, constraining it to fall within `rect`.
     pub fn clamp(&self, rect: Rect) -> Self {
         Self {
-            x: self.x.clamp(rect.tl.x, rect.tl.x + rect.w),
-            y: self.y.clamp(rect.tl.y, rect.tl.y + rect.h),
+            x: self.x.clamp(rect.tl.x, rect.tl.x.saturating_add(rect.w)),
+            y: self.y.clamp(rect.tl.y, rect.tl.y.saturating_add(rect.h)),
+        }
+    }
+    /// Add two points, returning `None` if either coordinate overflows.
+    pub fn checked_add(&self, other: Self) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add(other.x)?,
+            y: self.y.checked_add(other.y)?,
+        })
+    }
+    /// Add two points, clamping each coordinate at `u32::MAX`.
+    pub fn saturating_add(&self, other: Self) -> Self {
+        Self {
+            x: self.x.saturating_add(other.x),
+            y: self.y.saturating_add(other.y),
+        }
+    }
+    /// Subtract a point, clamping each coordinate at zero.
+    pub fn saturating_sub(&self, other: Self) -> Self {
+        Self {
+            x: self.x.saturating_sub(other.x),
+            y: self.y.saturating_sub(other.y),
         }
     }
     /// Like scroll, but constrained within a rectangle.
@@ -57,14 +78,12 @@ impl Point {
     }
 }
 
+/// Addition saturates at `u32::MAX` rather than overflowing.
 impl Add for Point {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
-        Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
+        self.saturating_add(other)
     }
 }
 
@@ -87,4 +106,14 @@ mod tests {
         assert_eq!(Point::zero() + (0u32, 1u32).into(), (0u32, 1u32).into());
         Ok(())
     }
+
+    #[test]
+    fn overflow_saturates() {
+        let max: Point = (u32::MAX, 1).into();
+        assert_eq!(max + (1, 1).into(), (u32::MAX, 2).into());
+        assert_eq!(max.checked_add((1, 0).into()), None);
+        assert_eq!(Point::zero().saturating_sub((3, 4).into()), Point::zero());
+        let edge = Rect::new(u32::MAX - 1, 0, 10, 10);
+        assert_eq!(Point::zero().clamp(edge), (u32::MAX - 1, 0).into());
+    }
 }
@@ -1,4 +1,4 @@
-use super::{Direction, Error, Line, LineSegment, Point, Result, Size};
+use super::{Align, Direction, Error, Insets, Line, LineSegment, Point, Result, Size};
 
 /// A rectangle
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
@@ -128,8 +128,8 @@ impl Rect {
         } else {
             self.contains_point(other.tl)
                 && self.contains_point(Point {
-                    x: (other.tl.x + other.w).saturating_sub(1),
-                    y: (other.tl.y + other.h).saturating_sub(1),
+                    x: other.tl.x.saturating_add(other.w).saturating_sub(1),
+                    y: other.tl.y.saturating_add(other.h).saturating_sub(1),
                 })
         }
     }
@@ -152,6 +152,27 @@ impl Rect {
         }
     }
 
+    /// Shrink the rect by `insets`. Insets that exceed the size on an axis collapse that
+    /// axis to zero at its center, so the result never lies outside this rect.
+    pub fn inset(&self, insets: Insets) -> Self {
+        let (x, w) = inset_axis(self.tl.x, self.w, insets.left, insets.horizontal());
+        let (y, h) = inset_axis(self.tl.y, self.h, insets.top, insets.vertical());
+        Self::new(x, y, w, h)
+    }
+
+    /// Place a child of size `child` within this rect. A child larger than the rect is
+    /// clipped to the rect's size.
+    pub fn align(&self, child: Size, align: Align) -> Self {
+        let (w, h) = (child.w.min(self.w), child.h.min(self.h));
+        let (horizontal, vertical) = align.axes();
+        Self::new(
+            self.tl.x.saturating_add(horizontal.offset(self.w, w)),
+            self.tl.y.saturating_add(vertical.offset(self.h, h)),
+            w,
+            h,
+        )
+    }
+
     /// Extract a horizontal section of this rect based on an extent.
     pub fn hslice(&self, e: &LineSegment) -> Result<Self> {
         if !self.hextent().contains(e) {
@@ -265,6 +286,32 @@ impl Rect {
         Ok(ret)
     }
 
+    /// Splits the rectangle horizontally into sections with widths proportional to
+    /// `weights`. Widths are rounded so they sum to the original width.
+    pub fn split_weighted_horizontal(&self, weights: &[f32]) -> Result<Vec<Self>> {
+        let widths = split_weighted(self.w, weights)?;
+        let mut off = self.tl.x;
+        let mut ret = Vec::with_capacity(widths.len());
+        for w in widths {
+            ret.push(Self::new(off, self.tl.y, w, self.h));
+            off = off.saturating_add(w);
+        }
+        Ok(ret)
+    }
+
+    /// Splits the rectangle vertically into sections with heights proportional to
+    /// `weights`. Heights are rounded so they sum to the original height.
+    pub fn split_weighted_vertical(&self, weights: &[f32]) -> Result<Vec<Self>> {
+        let heights = split_weighted(self.h, weights)?;
+        let mut off = self.tl.y;
+        let mut ret = Vec::with_capacity(heights.len());
+        for h in heights {
+            ret.push(Self::new(self.tl.x, off, self.w, h));
+            off = off.saturating_add(h);
+        }
+        Ok(ret)
+    }
+
     /// Splits the rectangle into columns, with each column split into rows.
     /// Returns a Vec of rects per column.
     pub fn split_panes(&self, spec: &[u32]) -> Result<Vec<Vec<Self>>> {
@@ -365,14 +412,41 @@ impl Rect {
     }
 
     /// Return a line with a given offset in the rectangle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `off` is not less than the rectangle's height. See
+    /// [`Rect::checked_line`] for a fallible version.
     pub fn line(&self, off: u32) -> Line {
+        match self.checked_line(off) {
+            Ok(line) => line,
+            Err(_) => panic!("offset exceeds rectangle height"),
+        }
+    }
+
+    /// Return a line with a given offset in the rectangle, or an error if `off` is not
+    /// less than the rectangle's height.
+    pub fn checked_line(&self, off: u32) -> Result<Line> {
         if off >= self.h {
-            panic!("offset exceeds rectangle height")
+            return Err(Error::Geometry(format!(
+                "line offset {off} exceeds rectangle height {}",
+                self.h
+            )));
         }
-        Line {
+        Ok(Line {
             tl: (self.tl.x, self.tl.y.saturating_add(off)).into(),
             w: self.w,
-        }
+        })
+    }
+
+    /// Return the column one past the right edge, clamped at `u32::MAX`.
+    pub fn right(&self) -> u32 {
+        self.tl.x.saturating_add(self.w)
+    }
+
+    /// Return the row one past the bottom edge, clamped at `u32::MAX`.
+    pub fn bottom(&self) -> u32 {
+        self.tl.y.saturating_add(self.h)
     }
 
     /// Does this rect have a zero size?
@@ -386,6 +460,54 @@ impl Rect {
         (*self).into()
     }
 
+    /// Return the smallest rectangle covering this one and `other`. Zero-sized
+    /// rectangles contribute nothing.
+    pub fn union(&self, other: &Self) -> Self {
+        if other.is_zero() {
+            return *self;
+        }
+        if self.is_zero() {
+            return *other;
+        }
+        let left = self.tl.x.min(other.tl.x);
+        let top = self.tl.y.min(other.tl.y);
+        let right = self.right().max(other.right());
+        let bottom = self.bottom().max(other.bottom());
+        Self::new(left, top, right - left, bottom - top)
+    }
+
+    /// Do this rectangle and `other` share any cells?
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersect(other).is_some_and(|r| !r.is_zero())
+    }
+
+    /// Subtract every rectangle in `others` from this one, returning a set of
+    /// non-overlapping rectangles describing what remains.
+    pub fn sub_all(&self, others: &[Self]) -> Vec<Self> {
+        let mut remaining = vec![*self];
+        for other in others {
+            remaining = remaining.iter().flat_map(|r| r.sub(other)).collect();
+        }
+        remaining
+    }
+
+    /// Iterate over the rows of this rectangle, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = Line> {
+        let rect = *self;
+        (0..rect.h).map(move |off| rect.line(off))
+    }
+
+    /// Iterate over the cells of this rectangle in row-major order.
+    pub fn cells(&self) -> impl Iterator<Item = Point> {
+        let rect = *self;
+        (0..rect.h).flat_map(move |dy| {
+            (0..rect.w).map(move |dx| Point {
+                x: rect.tl.x.saturating_add(dx),
+                y: rect.tl.y.saturating_add(dy),
+            })
+        })
+    }
+
     /// Subtract a rectangle from this one, returning a set of rectangles
     /// describing what remains.
     pub fn sub(&self, other: &Self) -> Vec<Self> {
@@ -474,6 +596,50 @@ fn split(len: u32, n: u32) -> Result<Vec<u32>> {
     Ok(v)
 }
 
+/// Split a length into sections proportional to `weights`, giving leftover units to the
+/// sections with the largest fractional parts.
+fn split_weighted(len: u32, weights: &[f32]) -> Result<Vec<u32>> {
+    if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+        return Err(Error::Geometry(
+            "weights must be finite and non-negative".into(),
+        ));
+    }
+    let total: f64 = weights.iter().map(|w| f64::from(*w)).sum();
+    if total <= 0.0 {
+        return Err(Error::Geometry("weights must sum to more than zero".into()));
+    }
+    let exact: Vec<f64> = weights
+        .iter()
+        .map(|w| f64::from(len) * f64::from(*w) / total)
+        .collect();
+    let mut sizes: Vec<u32> = exact.iter().map(|e| e.floor() as u32).collect();
+    let assigned: u32 = sizes.iter().sum();
+    let mut order: Vec<usize> = (0..exact.len()).collect();
+    order.sort_by(|a, b| {
+        let fa = exact[*a] - exact[*a].floor();
+        let fb = exact[*b] - exact[*b].floor();
+        fb.total_cmp(&fa)
+    });
+    for i in order
+        .into_iter()
+        .take(len.saturating_sub(assigned) as usize)
+    {
+        sizes[i] += 1;
+    }
+    Ok(sizes)
+}
+
+/// Inset one axis starting at `start` with length `len` by `before` at its start and
+/// `total` overall. Returns the new start and length, collapsing to the axis center when
+/// the insets exceed the length.
+fn inset_axis(start: u32, len: u32, before: u32, total: u32) -> (u32, u32) {
+    if total > len {
+        (start.saturating_add(len / 2), 0)
+    } else {
+        (start.saturating_add(before), len - total)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
@@ -575,6 +741,59 @@ mod tests {
         }
     }
 
+    proptest! {
+        #[test]
+        fn split_weighted_covers_original_width(
+            rect in rect_strategy(),
+            weights in prop::collection::vec(0.1f32..10.0, 1..8),
+        ) {
+            let parts = rect.split_weighted_horizontal(&weights).expect("valid weights");
+            let total: u32 = parts.iter().map(|part| part.w).sum();
+            prop_assert_eq!(total, rect.w);
+            prop_assert_eq!(parts.len(), weights.len());
+        }
+    }
+
+    #[test]
+    fn split_weighted() -> Result<()> {
+        let r = Rect::new(2, 3, 10, 4);
+        assert_eq!(
+            r.split_weighted_horizontal(&[1.0, 2.0, 1.0])?,
+            vec![
+                Rect::new(2, 3, 3, 4),
+                Rect::new(5, 3, 5, 4),
+                Rect::new(10, 3, 2, 4),
+            ]
+        );
+        assert_eq!(
+            r.split_weighted_vertical(&[0.25, 0.75])?,
+            vec![Rect::new(2, 3, 10, 1), Rect::new(2, 4, 10, 3)]
+        );
+        assert!(r.split_weighted_horizontal(&[]).is_err());
+        assert!(r.split_weighted_horizontal(&[1.0, -1.0]).is_err());
+        assert!(r.split_weighted_horizontal(&[f32::NAN]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn inset_and_align() {
+        let r = Rect::new(10, 10, 20, 10);
+        assert_eq!(r.inset(Insets::new(1, 2, 3, 4)), Rect::new(14, 11, 14, 6));
+        assert_eq!(r.inset(Insets::all(20)), Rect::new(20, 15, 0, 0));
+        assert_eq!(r.inset(Insets::new(0, 0, 0, 25)), Rect::new(20, 10, 0, 10));
+        assert_eq!(r.inset(Insets::symmetric(5, 0)), Rect::new(10, 15, 20, 0));
+
+        let child = Size::new(4, 2);
+        assert_eq!(r.align(child, Align::TopLeft), Rect::new(10, 10, 4, 2));
+        assert_eq!(r.align(child, Align::Center), Rect::new(18, 14, 4, 2));
+        assert_eq!(r.align(child, Align::TopRight), Rect::new(26, 10, 4, 2));
+        assert_eq!(r.align(child, Align::Bottom), Rect::new(18, 18, 4, 2));
+        assert_eq!(
+            r.align(Size::new(30, 3), Align::BottomRight),
+            Rect::new(10, 17, 20, 3)
+        );
+    }
+
     #[test]
     fn rect_sub() -> Result<()> {
         assert_eq!(
@@ -629,6 +848,62 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn rect_union_and_sub_all() {
+        let a = Rect::new(2, 2, 3, 3);
+        assert_eq!(a.union(&Rect::new(6, 1, 2, 2)), Rect::new(2, 1, 6, 4));
+        assert_eq!(a.union(&Rect::new(50, 50, 0, 0)), a);
+        assert_eq!(Rect::zero().union(&a), a);
+        assert!(a.intersects(&Rect::new(4, 4, 5, 5)));
+        assert!(!a.intersects(&Rect::new(5, 2, 5, 5)));
+
+        let remains =
+            Rect::new(0, 0, 4, 4).sub_all(&[Rect::new(0, 0, 4, 2), Rect::new(0, 2, 2, 2)]);
+        assert_eq!(remains, vec![Rect::new(2, 2, 2, 2)]);
+        assert!(
+            Rect::new(0, 0, 4, 4)
+                .sub_all(&[Rect::new(0, 0, 9, 9)])
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn zero_sized_rects() {
+        let empty = Rect::new(3, 3, 0, 0);
+        assert!(empty.checked_line(0).is_err());
+        assert_eq!(
+            Rect::new(3, 3, 4, 1).checked_line(0).ok(),
+            Some(Line::new(3, 3, 4))
+        );
+        assert_eq!(empty.rows().count(), 0);
+        assert_eq!(empty.inner(1), Rect::default());
+        assert_eq!(empty.inset(Insets::all(1)), Rect::new(4, 4, 0, 0));
+        assert_eq!(empty.align(Size::new(2, 2), Align::Center), empty);
+        assert_eq!(Rect::new(u32::MAX - 1, 0, 5, 5).right(), u32::MAX);
+        assert_eq!(Rect::new(0, 0, 0, 5).split_horizontal(3).unwrap().len(), 3);
+        assert_eq!(Size::new(2, 1).saturating_sub(Size::new(3, 3)), Size::ZERO);
+        assert_eq!(Size::new(2, 1).checked_sub(Size::new(1, 3)), None);
+    }
+
+    #[test]
+    fn rect_rows_and_cells() {
+        let r = Rect::new(1, 2, 2, 2);
+        assert_eq!(
+            r.rows().collect::<Vec<_>>(),
+            vec![Line::new(1, 2, 2), Line::new(1, 3, 2)]
+        );
+        assert_eq!(
+            r.cells().collect::<Vec<_>>(),
+            vec![
+                Point { x: 1, y: 2 },
+                Point { x: 2, y: 2 },
+                Point { x: 1, y: 3 },
+                Point { x: 2, y: 3 },
+            ]
+        );
+        assert_eq!(Rect::new(0, 0, 0, 3).cells().count(), 0);
+    }
+
     #[test]
     fn tsearch() -> Result<()> {
         let bounds = Rect::new(0, 0, 6, 6);
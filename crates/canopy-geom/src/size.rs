@@ -33,6 +33,30 @@ impl Size<u32> {
             h: self.h,
         }
     }
+    /// Subtract another size, clamping each dimension at zero.
+    pub fn saturating_sub(&self, other: Self) -> Self {
+        Self {
+            w: self.w.saturating_sub(other.w),
+            h: self.h.saturating_sub(other.h),
+        }
+    }
+
+    /// Add another size, clamping each dimension at `u32::MAX`.
+    pub fn saturating_add(&self, other: Self) -> Self {
+        Self {
+            w: self.w.saturating_add(other.w),
+            h: self.h.saturating_add(other.h),
+        }
+    }
+
+    /// Subtract another size, returning `None` if either dimension would underflow.
+    pub fn checked_sub(&self, other: Self) -> Option<Self> {
+        Some(Self {
+            w: self.w.checked_sub(other.w)?,
+            h: self.h.checked_sub(other.h)?,
+        })
+    }
+
     /// True if this Size can completely enclose the target size in both dimensions.
     pub fn contains(&self, other: &Self) -> bool {
         self.w >= other.w && self.h >= other.h
@@ -7,7 +7,7 @@ use canopy::{
     render::Render, testing::harness::Harness,
 };
 use canopy_widgets::editor::{EditMode, Editor, EditorConfig, LineNumbers, WrapMode};
-use criterion::{Criterion, criterion_group, criterion_main};
+use criterion::{BatchSize, Criterion, Throughput, criterion_group, criterion_main};
 
 canopy::key!(EditorSlot: Editor);
 
@@ -84,9 +84,46 @@ fn benchmark_editor_rendering(c: &mut Criterion) {
     });
 }
 
+/// Build a focused, rendered editor harness.
+fn focused_editor(text: &str) -> Harness {
+    let mut harness = Harness::builder(BenchmarkEditorWrapper::new(text))
+        .size(80, 24)
+        .build()
+        .expect("Failed to create harness");
+    harness.render().expect("Failed to render");
+    harness
+        .with_root_context(|_: &mut BenchmarkEditorWrapper, ctx| {
+            ctx.focus_first();
+            Ok(())
+        })
+        .expect("Failed to focus editor");
+    harness
+}
+
+/// Benchmark typing into an editor, including key dispatch and a render afterwards.
+fn benchmark_editor_insert(c: &mut Criterion) {
+    let typed = "The quick brown fox jumps over the lazy dog. ".repeat(4);
+    let mut group = c.benchmark_group("editor_insert");
+    group.throughput(Throughput::Bytes(typed.len() as u64));
+    group.bench_function("type_text", |b| {
+        b.iter_batched(
+            || focused_editor("Lorem ipsum dolor sit amet.\n".repeat(200).as_str()),
+            |mut harness| {
+                harness
+                    .type_text(black_box(&typed))
+                    .expect("Failed to type");
+                harness.render().expect("Failed to render");
+                harness
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.finish();
+}
+
 criterion_group! {
     name = benches;
     config = Criterion::default().sample_size(10);
-    targets = benchmark_editor_rendering
+    targets = benchmark_editor_rendering, benchmark_editor_insert
 }
 criterion_main!(benches);
@@ -0,0 +1,83 @@
+//! List scrolling benchmarks for canopy-widgets.
+
+use std::hint::black_box;
+
+use canopy::{
+    Canopy, Context, Loader, ReadContext, Widget, derive_commands, error::Result, layout::Layout,
+    render::Render, testing::harness::Harness,
+};
+use canopy_widgets::{List, Text};
+use criterion::{Criterion, criterion_group, criterion_main};
+
+canopy::key!(ListSlot: List<Text>);
+
+/// Number of items in the benchmark list.
+const ITEMS: usize = 10_000;
+
+/// Wrapper node that mounts a large list of text items.
+struct BenchmarkListWrapper;
+
+#[derive_commands]
+impl BenchmarkListWrapper {}
+
+impl Widget for BenchmarkListWrapper {
+    fn render(&mut self, _r: &mut Render, _ctx: &dyn ReadContext) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_mount(&mut self, c: &mut dyn Context) -> Result<()> {
+        let list_id = c.add_keyed::<ListSlot>(List::new())?;
+        c.set_layout(Layout::fill())?;
+        c.set_layout_of(list_id, Layout::fill())?;
+        c.with_child::<ListSlot, _>(|list, ctx| {
+            for i in 0..ITEMS {
+                list.append(
+                    ctx,
+                    Text::new(format!("item {i}: lorem ipsum dolor sit amet")),
+                )?;
+            }
+            list.select_first(ctx)
+        })
+    }
+}
+
+impl Loader for BenchmarkListWrapper {
+    fn load(c: &mut Canopy) -> Result<()> {
+        c.add_commands::<List<Text>>()?;
+        Ok(())
+    }
+}
+
+/// Benchmark moving the selection through a large list, rendering after each step.
+fn benchmark_list_scroll(c: &mut Criterion) {
+    let mut harness = Harness::builder(BenchmarkListWrapper)
+        .size(80, 24)
+        .build()
+        .expect("Failed to create harness");
+    harness.render().expect("Failed to render");
+
+    c.bench_function("list_scroll", |b| {
+        b.iter(|| {
+            harness
+                .with_root_context(|_: &mut BenchmarkListWrapper, ctx| {
+                    ctx.with_child::<ListSlot, _>(|list, ctx| {
+                        if list.selected_index() == Some(ITEMS - 1) {
+                            list.select_first(ctx)
+                        } else {
+                            list.select_by(ctx, 1)
+                        }
+                    })
+                })
+                .expect("Failed to scroll");
+            harness.render().expect("Failed to render");
+            black_box(harness.buf());
+        });
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = benchmark_list_scroll
+}
+criterion_main!(benches);
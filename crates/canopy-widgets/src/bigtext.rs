@@ -0,0 +1,272 @@
+//! Lazily loaded text view for files too large to hold in memory.
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Seek, SeekFrom},
+    ops::Range,
+    path::Path,
+};
+
+use canopy::{
+    Canopy, Loader, ReadContext, ScrollCommands, Scrollable, Widget, derive_commands,
+    error::Result,
+    geom::Line,
+    layout::{CanvasContext, Size},
+    render::Render,
+    state::NodeName,
+    text,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Number of lines between entries in a file's offset index.
+const INDEX_STRIDE: usize = 64;
+
+/// Tab stop used when expanding tabs for display.
+const TAB_STOP: usize = 4;
+
+/// A random-access source of text lines.
+pub trait LineSource {
+    /// Return the total number of lines.
+    fn line_count(&self) -> usize;
+
+    /// Return up to `count` lines starting at line `start`, without line terminators.
+    fn lines(&mut self, start: usize, count: usize) -> Result<Vec<String>>;
+}
+
+impl LineSource for Vec<String> {
+    fn line_count(&self) -> usize {
+        self.len()
+    }
+
+    fn lines(&mut self, start: usize, count: usize) -> Result<Vec<String>> {
+        Ok(self.iter().skip(start).take(count).cloned().collect())
+    }
+}
+
+/// Lines read on demand from a file through a sparse offset index.
+///
+/// Opening the file scans it once to record the byte offset of every `INDEX_STRIDE`th
+/// line. Reads seek to the nearest indexed line and skip forward, so memory use is
+/// independent of the file size.
+pub struct FileLines {
+    /// Buffered reader over the file.
+    reader: BufReader<File>,
+    /// Byte offset of every `INDEX_STRIDE`th line.
+    index: Vec<u64>,
+    /// Total number of lines.
+    count: usize,
+}
+
+impl FileLines {
+    /// Open and index a file.
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut index = Vec::new();
+        let mut count = 0;
+        let mut offset = 0;
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            let read = reader.read_until(b'\n', &mut buf)?;
+            if read == 0 {
+                break;
+            }
+            if count % INDEX_STRIDE == 0 {
+                index.push(offset);
+            }
+            offset += read as u64;
+            count += 1;
+        }
+        Ok(Self {
+            reader,
+            index,
+            count,
+        })
+    }
+}
+
+impl LineSource for FileLines {
+    fn line_count(&self) -> usize {
+        self.count
+    }
+
+    fn lines(&mut self, start: usize, count: usize) -> Result<Vec<String>> {
+        let end = start.saturating_add(count).min(self.count);
+        if start >= end {
+            return Ok(Vec::new());
+        }
+        let block = start / INDEX_STRIDE;
+        self.reader.seek(SeekFrom::Start(self.index[block]))?;
+        let mut out = Vec::with_capacity(end - start);
+        let mut buf = Vec::new();
+        for line in block * INDEX_STRIDE..end {
+            buf.clear();
+            self.reader.read_until(b'\n', &mut buf)?;
+            if line >= start {
+                let text = String::from_utf8_lossy(&buf);
+                out.push(text.trim_end_matches(['\n', '\r']).to_string());
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// A scrolling, unwrapped view over a [`LineSource`].
+///
+/// Only the visible lines are read on each render, so multi-hundred-megabyte files can be
+/// paged without loading them. Lines are not wrapped; the canvas widens to the longest
+/// line seen so far.
+pub struct BigText {
+    /// Line source.
+    source: Box<dyn LineSource>,
+    /// Most recently read lines and the line range they cover.
+    window: (Range<usize>, Vec<String>),
+    /// Display width of the widest line read so far.
+    max_width: u32,
+}
+
+#[derive_commands]
+impl BigText {
+    /// Construct a view over a line source.
+    pub fn new(source: impl LineSource + 'static) -> Self {
+        Self {
+            source: Box::new(source),
+            window: (0..0, Vec::new()),
+            max_width: 0,
+        }
+    }
+
+    /// Construct a view over a file.
+    pub fn open(path: &Path) -> Result<Self> {
+        Ok(Self::new(FileLines::open(path)?))
+    }
+
+    /// Return the total number of lines.
+    pub fn line_count(&self) -> usize {
+        self.source.line_count()
+    }
+
+    /// Return the lines in `range`, reading them from the source unless cached.
+    fn read(&mut self, range: Range<usize>) -> Result<&[String]> {
+        if self.window.0 != range {
+            let lines = self.source.lines(range.start, range.len())?;
+            for line in &lines {
+                let width = UnicodeWidthStr::width(text::expand_tabs(line, TAB_STOP).as_str());
+                self.max_width = self.max_width.max(width as u32);
+            }
+            self.window = (range, lines);
+        }
+        Ok(&self.window.1)
+    }
+}
+
+impl Scrollable for BigText {}
+
+impl Widget for BigText {
+    fn scrollable(&mut self) -> Option<&mut dyn Scrollable> {
+        Some(self)
+    }
+
+    fn render(&mut self, rndr: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+        let view = ctx.view();
+        let view_rect = view.view_rect();
+        let content_origin = view.content_origin();
+        let start = view_rect.tl.y as usize;
+        let lines = self.read(start..start + view_rect.h as usize)?;
+        for (i, line) in lines.iter().enumerate() {
+            let expanded = text::expand_tabs(line, TAB_STOP);
+            let (out, _) =
+                text::slice_by_columns(&expanded, view_rect.tl.x as usize, view_rect.w as usize);
+            let line_rect = Line::new(
+                content_origin.x,
+                content_origin.y.saturating_add(i as u32),
+                view_rect.w,
+            );
+            rndr.text("text", line_rect, out)?;
+        }
+        Ok(())
+    }
+
+    fn canvas(&self, view: Size<u32>, _ctx: &CanvasContext) -> Size<u32> {
+        Size::new(
+            self.max_width.max(view.w).max(1),
+            self.source.line_count() as u32,
+        )
+    }
+
+    fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+        true
+    }
+
+    fn name(&self) -> NodeName {
+        NodeName::convert("big_text")
+    }
+}
+
+impl Loader for BigText {
+    fn load(c: &mut Canopy) -> Result<()> {
+        c.add_commands::<Self>()?;
+        ScrollCommands::load(c)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use canopy::testing::harness::Harness;
+
+    use super::*;
+
+    #[test]
+    fn file_lines_reads_through_sparse_index() -> Result<()> {
+        let path = env::temp_dir().join(format!("canopy-bigtext-{}.txt", process::id()));
+        let contents: String = (0..200).map(|i| format!("line {i}\r\n")).collect();
+        fs::write(&path, contents).unwrap();
+
+        let mut lines = FileLines::open(&path)?;
+        assert_eq!(lines.line_count(), 200);
+        assert_eq!(lines.index.len(), 200usize.div_ceil(INDEX_STRIDE));
+        assert_eq!(lines.lines(130, 2)?, ["line 130", "line 131"]);
+        assert_eq!(lines.lines(0, 1)?, ["line 0"]);
+        assert_eq!(lines.lines(199, 5)?, ["line 199"]);
+        assert!(lines.lines(300, 5)?.is_empty());
+        fs::remove_file(&path).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn renders_only_the_visible_window() -> Result<()> {
+        let source: Vec<String> = ["one", "two", "three"].map(String::from).into();
+        let mut harness = Harness::builder(BigText::new(source)).size(5, 2).build()?;
+        harness.render()?;
+        assert_eq!(harness.buf().lines(), ["one  ", "two  "]);
+
+        harness.with_root_context(|text: &mut BigText, ctx| {
+            text.scroll_to(ctx, 0, 1);
+            Ok(())
+        })?;
+        harness.render()?;
+        assert_eq!(harness.buf().lines(), ["two  ", "three"]);
+        Ok(())
+    }
+
+    #[test]
+    fn shared_scroll_commands_move_the_view() -> Result<()> {
+        let source: Vec<String> = (0..10).map(|i| format!("l{i}")).collect();
+        let mut harness = Harness::builder(BigText::new(source)).size(2, 2).build()?;
+        harness.script("scrollable.down()")?;
+        harness.render()?;
+        assert_eq!(harness.buf().lines(), ["l1", "l2"]);
+
+        harness.script("scrollable.bottom()")?;
+        harness.render()?;
+        assert_eq!(harness.buf().lines(), ["l8", "l9"]);
+
+        harness.script("scrollable.top()")?;
+        harness.render()?;
+        assert_eq!(harness.buf().lines(), ["l0", "l1"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+//! Breadcrumb widget showing a slash-separated path.
+
+use canopy::{
+    Canopy, Context, EventOutcome, Loader, ReadContext, Widget, command,
+    commands::{CommandArgs, CommandCall, CommandInvocation, ToArgValue},
+    derive_commands,
+    error::{Error, Result},
+    event::{Event, mouse},
+    geom::Line,
+    layout::Layout,
+    render::Render,
+    state::NodeName,
+    text,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Separator drawn between segments.
+const SEPARATOR: &str = "/";
+
+/// Marker drawn in place of segments elided from the middle.
+const ELLIPSIS: &str = "\u{2026}";
+
+/// Choose which segments to show in `width` columns.
+///
+/// Returns segment indexes in display order, with `None` marking the elided middle. The
+/// first and last segments are kept for as long as they fit; the last segment is kept
+/// in any case and clipped on render.
+fn fit(widths: &[usize], absolute: bool, width: usize) -> Vec<Option<usize>> {
+    let n = widths.len();
+    let lead = usize::from(absolute);
+    let full = lead + widths.iter().sum::<usize>() + n.saturating_sub(1);
+    if full <= width || n <= 2 {
+        return (0..n).map(Some).collect();
+    }
+    // Head and tail around two separators and the one-column ellipsis.
+    let mut used = lead + widths[0] + 3 + widths[n - 1];
+    if used > width {
+        return vec![None, Some(n - 1)];
+    }
+    let mut start = n - 1;
+    while start > 1 && used + widths[start - 1] + 1 <= width {
+        start -= 1;
+        used += widths[start] + 1;
+    }
+    [Some(0), None]
+        .into_iter()
+        .chain((start..n).map(Some))
+        .collect()
+}
+
+/// A one-line path display with middle truncation and clickable segments.
+///
+/// Clicking a segment dispatches the navigation command with the path up to and
+/// including that segment appended as a `path` argument.
+pub struct Breadcrumb {
+    /// Displayed path.
+    path: String,
+    /// Whether the path tracks the focus path on every render.
+    follow_focus: bool,
+    /// Command dispatched when a segment is activated.
+    command: Option<CommandInvocation>,
+    /// Column ranges of the segments drawn by the last render, as `(start, end, index)`.
+    spans: Vec<(u32, u32, usize)>,
+}
+
+#[derive_commands]
+impl Breadcrumb {
+    /// Construct a breadcrumb showing `path`.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            follow_focus: false,
+            command: None,
+            spans: Vec::new(),
+        }
+    }
+
+    /// Build a breadcrumb that shows the current focus path.
+    pub fn with_focus_path(mut self) -> Self {
+        self.follow_focus = true;
+        self
+    }
+
+    /// Build a breadcrumb that dispatches `command` when a segment is activated.
+    pub fn with_command(mut self, command: CommandCall) -> Self {
+        self.command = Some(command.invocation());
+        self
+    }
+
+    /// Return the displayed path.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Replace the displayed path.
+    pub fn set_path(&mut self, path: impl Into<String>) {
+        self.path = path.into();
+    }
+
+    /// Return the non-empty path segments.
+    fn segments(&self) -> Vec<&str> {
+        self.path.split('/').filter(|s| !s.is_empty()).collect()
+    }
+
+    /// Return the path up to and including segment `index`.
+    fn prefix(&self, index: usize) -> String {
+        let joined = self.segments()[..=index].join(SEPARATOR);
+        if self.path.starts_with('/') {
+            format!("{SEPARATOR}{joined}")
+        } else {
+            joined
+        }
+    }
+
+    /// Dispatch the navigation command for a segment.
+    /// @param index Zero-based segment index.
+    #[command]
+    pub fn activate(&mut self, c: &mut dyn Context, index: usize) -> Result<()> {
+        if index >= self.segments().len() {
+            return Err(Error::Invalid(format!("no path segment {index}")));
+        }
+        let Some(command) = &self.command else {
+            return Ok(());
+        };
+        let path = self.prefix(index).to_arg_value();
+        let args = match &command.args {
+            CommandArgs::Positional(values) => {
+                let mut out = values.clone();
+                out.push(path);
+                CommandArgs::Positional(out)
+            }
+            CommandArgs::Named(values) => {
+                let mut out = values.clone();
+                out.insert("path".to_string(), path);
+                CommandArgs::Named(out)
+            }
+        };
+        c.dispatch_command(&CommandInvocation {
+            id: command.id,
+            args,
+        })?;
+        Ok(())
+    }
+
+    /// Activate the parent of the last segment.
+    #[command]
+    pub fn parent(&mut self, c: &mut dyn Context) -> Result<()> {
+        match self.segments().len() {
+            0 | 1 => Ok(()),
+            n => self.activate(c, n - 2),
+        }
+    }
+
+    /// Return the segment drawn at column `x`, if any.
+    fn segment_at(&self, x: u32) -> Option<usize> {
+        self.spans
+            .iter()
+            .find(|(start, end, _)| (*start..*end).contains(&x))
+            .map(|(_, _, index)| *index)
+    }
+}
+
+impl Widget for Breadcrumb {
+    fn layout(&self) -> Layout {
+        Layout::fill().fixed_height(1)
+    }
+
+    fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+        if self.follow_focus {
+            self.path = ctx.focus_path(ctx.root_id()).to_string();
+        }
+        self.spans.clear();
+        let area = ctx.view().view_rect_local();
+        if area.w == 0 || area.h == 0 {
+            return Ok(());
+        }
+        r.fill("breadcrumb", area.line(0).into(), ' ')?;
+
+        let segments: Vec<&str> = self.path.split('/').filter(|s| !s.is_empty()).collect();
+        let widths: Vec<usize> = segments
+            .iter()
+            .map(|s| UnicodeWidthStr::width(*s))
+            .collect();
+        let absolute = self.path.starts_with('/');
+        let pieces = fit(&widths, absolute, area.w as usize);
+        let mut x = 0;
+        for (i, piece) in pieces.iter().enumerate() {
+            if (i > 0 || (absolute && *piece == Some(0))) && x < area.w {
+                r.text("breadcrumb/separator", Line::new(x, 0, 1), SEPARATOR)?;
+                x += 1;
+            }
+            let remaining = area.w.saturating_sub(x) as usize;
+            let (style, label) = match piece {
+                None => ("breadcrumb/separator", ELLIPSIS),
+                Some(index) if *index + 1 == segments.len() => {
+                    ("breadcrumb/current", segments[*index])
+                }
+                Some(index) => ("breadcrumb/segment", segments[*index]),
+            };
+            let (label, used) = text::slice_by_columns(label, 0, remaining);
+            if used == 0 {
+                break;
+            }
+            r.text(style, Line::new(x, 0, used as u32), label)?;
+            if let Some(index) = piece {
+                self.spans.push((x, x + used as u32, *index));
+            }
+            x += used as u32;
+        }
+        Ok(())
+    }
+
+    fn on_event(&mut self, event: &Event, ctx: &mut dyn Context) -> Result<EventOutcome> {
+        if let Event::Mouse(m) = event
+            && m.button == mouse::Button::Left
+            && m.action == mouse::Action::Down
+            && m.location.y == 0
+            && let Some(index) = self.segment_at(m.location.x)
+        {
+            self.activate(ctx, index)?;
+            return Ok(EventOutcome::Handle);
+        }
+        Ok(EventOutcome::Ignore)
+    }
+
+    fn name(&self) -> NodeName {
+        NodeName::convert("breadcrumb")
+    }
+}
+
+impl Loader for Breadcrumb {
+    fn load(c: &mut Canopy) -> Result<()> {
+        c.add_commands::<Self>()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_elides_the_middle() {
+        let widths = [4, 3, 6, 3];
+        // "/home/src/canopy/lib" is 20 columns.
+        assert_eq!(fit(&widths, true, 20).len(), 4);
+        // "/home/…/canopy/lib" is 18 columns.
+        assert_eq!(fit(&widths, true, 18), [Some(0), None, Some(2), Some(3)]);
+        assert_eq!(fit(&widths, true, 12), [Some(0), None, Some(3)]);
+        assert_eq!(fit(&widths, true, 5), [None, Some(3)]);
+    }
+
+    #[test]
+    fn prefix_keeps_leading_separator() {
+        let crumb = Breadcrumb::new("/home/src//canopy");
+        assert_eq!(crumb.segments(), ["home", "src", "canopy"]);
+        assert_eq!(crumb.prefix(1), "/home/src");
+        assert_eq!(Breadcrumb::new("a/b").prefix(0), "a");
+    }
+}
@@ -1,7 +1,7 @@
 //! Button widget.
 
 use canopy::{
-    Context, EventOutcome, ReadContext, Slot, Widget, command,
+    Accessible, Context, EventOutcome, ReadContext, Role, Slot, Widget, command,
     commands::{CommandCall, CommandInvocation},
     derive_commands,
     error::Result,
@@ -160,6 +160,15 @@ impl Widget for Button {
         self.sync_label(ctx)
     }
 
+    fn accessible(&self, _ctx: &dyn ReadContext) -> Option<Accessible> {
+        let button = Accessible::new(Role::Button, self.label.clone());
+        Some(if self.active {
+            button.with_state("active")
+        } else {
+            button
+        })
+    }
+
     fn render(&mut self, rndr: &mut Render, _ctx: &dyn ReadContext) -> Result<()> {
         rndr.push_layer("button");
         if self.selected {
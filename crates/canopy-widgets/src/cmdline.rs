@@ -0,0 +1,276 @@
+//! Ex-style command line.
+//!
+//! A one-line prompt that parses and runs command strings such as `list::select(3)` or
+//! registered aliases. Lines starting with `lua ` are evaluated as Luau instead. Commands and
+//! scripts run relative to the node that was focused before the prompt opened.
+
+use canopy::{
+    Canopy, Context, EventOutcome, Loader, ReadContext, Scrollable, Widget, command, cursor,
+    derive_commands,
+    error::Result,
+    event::Event,
+    layout::{CanvasContext, Edges, Layout, Size},
+    render::Render,
+    state::NodeName,
+};
+
+use crate::{
+    editor::{Editor, EditorConfig},
+    input::set_line,
+};
+
+/// Default command line bindings exposed through `command_line.default_bindings()`.
+const DEFAULT_BINDINGS: &str = r#"
+canopy.bind_with("Enter", { path = "command_line", desc = "Run command" }, function()
+    command_line.accept()
+end)
+canopy.bind_with("Esc", { path = "command_line", desc = "Close command line" }, function()
+    command_line.cancel()
+end)
+canopy.bind_with("Tab", { path = "command_line", desc = "Complete" }, function()
+    command_line.complete()
+end)
+canopy.bind_with("Up", { path = "command_line", desc = "Previous history entry" }, function()
+    command_line.history_prev()
+end)
+canopy.bind_with("Down", { path = "command_line", desc = "Next history entry" }, function()
+    command_line.history_next()
+end)
+"#;
+
+/// Prefix that marks a command line entry as Luau source.
+const SCRIPT_PREFIX: &str = "lua ";
+
+/// Prompt glyph drawn before the input.
+const PROMPT: &str = ":";
+
+/// One-line command prompt with completion and history. The entry is edited by an
+/// [`Editor`] configured with [`EditorConfig::single_line`], drawn after the prompt.
+pub struct CommandLine {
+    /// Single-line editor holding the entry.
+    editor: Editor,
+    /// Previously accepted entries, oldest first.
+    history: Vec<String>,
+    /// Index into `history` while browsing it.
+    history_index: Option<usize>,
+    /// Completion candidates or the last error, shown in place of the input.
+    message: Option<String>,
+}
+
+#[derive_commands]
+impl CommandLine {
+    /// Construct an empty command line.
+    pub fn new() -> Self {
+        Self {
+            editor: Editor::with_config("", EditorConfig::single_line()),
+            history: Vec::new(),
+            history_index: None,
+            message: None,
+        }
+    }
+
+    /// Return the text currently being edited.
+    pub fn value(&self) -> String {
+        self.editor.text()
+    }
+
+    /// Return the accepted entries, oldest first.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Return the message displayed in place of the input, if any.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// Show the prompt and move focus into it, saving the current focus for `close`.
+    pub fn open(&mut self, c: &mut dyn Context) {
+        let node = c.node_id();
+        self.set_entry(c, "");
+        self.history_index = None;
+        self.message = None;
+        c.show();
+        c.focus_push();
+        c.push_focus_scope(node);
+        c.set_focus(node);
+    }
+
+    /// Hide the prompt and return focus to where it was when the prompt opened.
+    pub fn close(&mut self, c: &mut dyn Context) {
+        let node = c.node_id();
+        c.pop_focus_scope(node);
+        c.hide();
+        if !c.focus_pop() {
+            c.focus_first_global();
+        }
+    }
+
+    /// Run the entered command and close the prompt. On failure the prompt reopens with the
+    /// entry and the error.
+    #[command]
+    pub fn accept(&mut self, c: &mut dyn Context) {
+        let entry = self.editor.text().trim().to_string();
+        self.close(c);
+        if entry.is_empty() {
+            return;
+        }
+        self.history.retain(|item| *item != entry);
+        self.history.push(entry.clone());
+
+        let target = c.focused_leaf(c.root_id()).unwrap_or_else(|| c.root_id());
+        if let Some(source) = entry.strip_prefix(SCRIPT_PREFIX) {
+            c.eval_script(target, source);
+            return;
+        }
+        if let Err(err) = c.dispatch_str_from(target, &entry) {
+            self.open(c);
+            self.set_entry(c, entry);
+            self.message = Some(err.to_string());
+        }
+    }
+
+    /// Close the prompt without running anything.
+    #[command]
+    pub fn cancel(&mut self, c: &mut dyn Context) {
+        self.close(c);
+    }
+
+    /// Complete the entry from history and registered command names. A unique match is
+    /// filled in; otherwise the entry is extended to the longest shared prefix and the
+    /// candidates are listed.
+    #[command]
+    pub fn complete(&mut self, c: &mut dyn Context) {
+        let prefix = self.editor.text();
+        let mut candidates: Vec<String> = Vec::new();
+        for name in self.history.iter().rev().cloned().chain(c.command_names()) {
+            if name.starts_with(&prefix) && name != prefix && !candidates.contains(&name) {
+                candidates.push(name);
+            }
+        }
+        match candidates.as_slice() {
+            [] => self.message = Some(format!("no completions for {prefix:?}")),
+            [only] => self.set_entry(c, only.clone()),
+            _ => {
+                let shared = common_prefix(&candidates);
+                if shared.len() > prefix.len() {
+                    self.set_entry(c, shared);
+                }
+                self.message = Some(candidates.join("  "));
+            }
+        }
+    }
+
+    /// Replace the entry with the previous history item.
+    #[command]
+    pub fn history_prev(&mut self, c: &mut dyn Context) {
+        let index = match self.history_index {
+            _ if self.history.is_empty() => return,
+            None => self.history.len() - 1,
+            Some(index) => index.saturating_sub(1),
+        };
+        self.history_index = Some(index);
+        self.set_entry(c, self.history[index].clone());
+        self.message = None;
+    }
+
+    /// Replace the entry with the next history item, clearing it past the newest.
+    #[command]
+    pub fn history_next(&mut self, c: &mut dyn Context) {
+        let Some(index) = self.history_index else {
+            return;
+        };
+        if index + 1 < self.history.len() {
+            self.history_index = Some(index + 1);
+            self.set_entry(c, self.history[index + 1].clone());
+        } else {
+            self.history_index = None;
+            self.set_entry(c, "");
+        }
+        self.message = None;
+    }
+
+    /// Replace the entry, leaving the cursor at its end and in view.
+    fn set_entry(&mut self, c: &mut dyn Context, entry: impl Into<String>) {
+        set_line(&mut self.editor, entry);
+        self.editor.ensure_cursor_visible(c);
+    }
+}
+
+impl Default for CommandLine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scrollable for CommandLine {}
+
+impl Widget for CommandLine {
+    fn scrollable(&mut self) -> Option<&mut dyn Scrollable> {
+        Some(self)
+    }
+
+    fn layout(&self) -> Layout {
+        Layout::fill().padding(Edges::new(0, 0, 0, PROMPT.len() as u32))
+    }
+
+    fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+        true
+    }
+
+    fn cursor(&self) -> Option<cursor::Cursor> {
+        if self.message.is_some() {
+            return None;
+        }
+        self.editor.cursor()
+    }
+
+    fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+        let line = ctx.view().outer_rect_local().line(0);
+        if let Some(message) = &self.message {
+            return r.text("command_line/message", line, message);
+        }
+        r.text("command_line", line, PROMPT)?;
+        self.editor.render(r, ctx)
+    }
+
+    fn on_event(&mut self, event: &Event, ctx: &mut dyn Context) -> Result<EventOutcome> {
+        if matches!(event, Event::Key(_) | Event::Paste(_)) {
+            self.message = None;
+        }
+        Ok(self.editor.handle_event(event, ctx))
+    }
+
+    fn canvas(&self, view: Size<u32>, ctx: &CanvasContext) -> Size<u32> {
+        self.editor.canvas(view, ctx)
+    }
+
+    fn name(&self) -> NodeName {
+        NodeName::convert("command_line")
+    }
+}
+
+impl Loader for CommandLine {
+    fn load(c: &mut Canopy) -> Result<()> {
+        c.add_commands::<Self>()?;
+        c.register_default_bindings("command_line", DEFAULT_BINDINGS)?;
+        Ok(())
+    }
+}
+
+/// Return the longest prefix shared by every candidate.
+fn common_prefix(candidates: &[String]) -> String {
+    let Some((first, rest)) = candidates.split_first() else {
+        return String::new();
+    };
+    let mut shared = first.as_str();
+    for candidate in rest {
+        let len = shared
+            .char_indices()
+            .zip(candidate.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(shared.len().min(candidate.len()), |((idx, _), _)| idx);
+        shared = &shared[..len];
+    }
+    shared.to_string()
+}
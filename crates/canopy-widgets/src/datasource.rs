@@ -0,0 +1,219 @@
+//! Row data that lives outside the widget tree.
+//!
+//! A [`DataSource`] exposes indexed rows and a log of changes. Widgets such as
+//! [`List`](crate::List) and [`Table`](crate::Table) render rows from a source and,
+//! when the source changes, rebuild only the rows that were inserted, removed, or
+//! updated.
+
+use std::collections::VecDeque;
+
+use canopy::{ChangeCounter, Observable};
+
+/// Number of changes a [`VecSource`] remembers before readers fall back to a reset.
+const CHANGE_LOG_LIMIT: usize = 256;
+
+/// A change to a data source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataChange {
+    /// A row was inserted at the index.
+    Insert(usize),
+    /// The row at the index was removed.
+    Remove(usize),
+    /// The row at the index changed in place.
+    Update(usize),
+    /// Every row may have changed.
+    Reset,
+}
+
+/// Indexed rows with change tracking.
+pub trait DataSource<T>: Send {
+    /// Number of rows.
+    fn len(&self) -> usize;
+
+    /// Whether the source has no rows.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the row at `index`.
+    fn get(&self, index: usize) -> Option<T>;
+
+    /// Number of changes made so far.
+    fn version(&self) -> u64;
+
+    /// Changes made after `version`, in order. Sources that no longer remember that far
+    /// back return a single [`DataChange::Reset`].
+    fn changes_since(&self, version: u64) -> Vec<DataChange>;
+
+    /// Change counter that widgets subscribe to with `Context::observe_changes`.
+    fn changes(&self) -> ChangeCounter;
+}
+
+/// Rows and recent changes of a [`VecSource`].
+struct VecState<T> {
+    /// Current rows.
+    items: Vec<T>,
+    /// Number of changes made so far.
+    version: u64,
+    /// Recent changes, tagged with the version they produced.
+    log: VecDeque<(u64, DataChange)>,
+}
+
+/// A shared, growable data source backed by a `Vec`.
+///
+/// Clones share the same rows, so the app keeps one handle and gives another to the
+/// widget.
+pub struct VecSource<T> {
+    /// Shared rows and change log.
+    state: Observable<VecState<T>>,
+}
+
+impl<T> Clone for VecSource<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T> Default for VecSource<T> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<T> VecSource<T> {
+    /// Create a source holding `items`.
+    pub fn new(items: Vec<T>) -> Self {
+        Self {
+            state: Observable::new(VecState {
+                items,
+                version: 0,
+                log: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Apply an edit under the source's lock and record the change it reports. An edit
+    /// that reports no change, such as one with an out-of-range index, records nothing.
+    /// Returns whether a change was recorded.
+    fn edit(&self, f: impl FnOnce(&mut Vec<T>) -> Option<DataChange>) -> bool {
+        let mut changed = false;
+        self.state.update(|state| {
+            let Some(change) = f(&mut state.items) else {
+                return;
+            };
+            changed = true;
+            state.version += 1;
+            if state.log.len() == CHANGE_LOG_LIMIT {
+                state.log.pop_front();
+            }
+            state.log.push_back((state.version, change));
+        });
+        changed
+    }
+
+    /// Append a row.
+    pub fn push(&self, item: T) {
+        self.edit(|items| {
+            items.push(item);
+            Some(DataChange::Insert(items.len() - 1))
+        });
+    }
+
+    /// Insert a row at `index`, clamped to the end.
+    pub fn insert(&self, index: usize, item: T) {
+        self.edit(|items| {
+            let index = index.min(items.len());
+            items.insert(index, item);
+            Some(DataChange::Insert(index))
+        });
+    }
+
+    /// Remove the row at `index`. Returns `false` if the index is out of range.
+    pub fn remove(&self, index: usize) -> bool {
+        self.edit(|items| {
+            (index < items.len()).then(|| {
+                items.remove(index);
+                DataChange::Remove(index)
+            })
+        })
+    }
+
+    /// Replace the row at `index`. Returns `false` if the index is out of range.
+    pub fn set(&self, index: usize, item: T) -> bool {
+        self.edit(|items| {
+            let slot = items.get_mut(index)?;
+            *slot = item;
+            Some(DataChange::Update(index))
+        })
+    }
+
+    /// Replace every row.
+    pub fn replace_all(&self, items: Vec<T>) {
+        self.edit(|current| {
+            *current = items;
+            Some(DataChange::Reset)
+        });
+    }
+}
+
+impl<T: Clone + Send> DataSource<T> for VecSource<T> {
+    fn len(&self) -> usize {
+        self.state.with(|state| state.items.len())
+    }
+
+    fn get(&self, index: usize) -> Option<T> {
+        self.state.with(|state| state.items.get(index).cloned())
+    }
+
+    fn version(&self) -> u64 {
+        self.state.with(|state| state.version)
+    }
+
+    fn changes_since(&self, version: u64) -> Vec<DataChange> {
+        self.state.with(|state| {
+            if version >= state.version {
+                return Vec::new();
+            }
+            match state.log.front() {
+                Some((first, _)) if *first <= version + 1 => state
+                    .log
+                    .iter()
+                    .filter(|(v, _)| *v > version)
+                    .map(|(_, change)| *change)
+                    .collect(),
+                _ => vec![DataChange::Reset],
+            }
+        })
+    }
+
+    fn changes(&self) -> ChangeCounter {
+        self.state.changes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_since_replays_or_resets() {
+        let source = VecSource::new(vec![1, 2]);
+        source.push(3);
+        source.set(0, 10);
+        assert!(source.remove(1));
+        assert!(!source.remove(5));
+        assert_eq!(
+            source.changes_since(1),
+            [DataChange::Update(0), DataChange::Remove(1)]
+        );
+        assert!(source.changes_since(3).is_empty());
+        assert_eq!(source.get(1), Some(3));
+
+        for i in 0..CHANGE_LOG_LIMIT {
+            source.push(i as i32);
+        }
+        assert_eq!(source.changes_since(0), [DataChange::Reset]);
+    }
+}
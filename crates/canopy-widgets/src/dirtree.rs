@@ -0,0 +1,580 @@
+//! Lazy-loading filesystem tree that follows changes on disk.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs, iter,
+    path::{Component, Path, PathBuf},
+    sync::mpsc,
+    time::Duration,
+};
+
+use canopy::{
+    Canopy, Context, Loader, PollWaker, ReadContext, Widget, command, derive_commands,
+    error::{Error, Result},
+    geom::{Line, Rect},
+    layout::{CanvasContext, Layout, MeasureConstraints, Measurement, Size},
+    render::Render,
+    state::NodeName,
+};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+/// Default bindings for the directory tree.
+const DEFAULT_BINDINGS: &str = r#"
+canopy.bind_with("j", { path = "dir_tree", desc = "Next entry" }, function()
+    dir_tree.select_by(1)
+end)
+canopy.bind_with("Down", { path = "dir_tree", desc = "Next entry" }, function()
+    dir_tree.select_by(1)
+end)
+canopy.bind_with("k", { path = "dir_tree", desc = "Previous entry" }, function()
+    dir_tree.select_by(-1)
+end)
+canopy.bind_with("Up", { path = "dir_tree", desc = "Previous entry" }, function()
+    dir_tree.select_by(-1)
+end)
+canopy.bind_with("l", { path = "dir_tree", desc = "Expand directory" }, function()
+    dir_tree.expand()
+end)
+canopy.bind_with("Right", { path = "dir_tree", desc = "Expand directory" }, function()
+    dir_tree.expand()
+end)
+canopy.bind_with("h", { path = "dir_tree", desc = "Collapse directory" }, function()
+    dir_tree.collapse()
+end)
+canopy.bind_with("Left", { path = "dir_tree", desc = "Collapse directory" }, function()
+    dir_tree.collapse()
+end)
+canopy.bind_with("Enter", { path = "dir_tree", desc = "Toggle directory" }, function()
+    dir_tree.toggle()
+end)
+"#;
+
+/// A directory entry as listed on disk.
+struct Entry {
+    /// File name.
+    name: String,
+    /// Full path.
+    path: PathBuf,
+    /// Whether the entry is a directory.
+    is_dir: bool,
+}
+
+/// A visible row in the flattened tree.
+struct Row {
+    /// Full path of the entry.
+    path: PathBuf,
+    /// Display name.
+    name: String,
+    /// Nesting depth below the root.
+    depth: usize,
+    /// Whether the entry is a directory.
+    is_dir: bool,
+}
+
+/// Change events delivered by the watcher thread.
+type WatchEvents = mpsc::Receiver<notify::Result<notify::Event>>;
+
+/// A filesystem tree rooted at a directory.
+///
+/// Directories are read when they are expanded. Once mounted, the tree watches every
+/// expanded directory and relists it when its contents change on disk. The watcher
+/// thread wakes the tree with a poll when events arrive, so nothing runs while the
+/// filesystem is quiet.
+pub struct DirTree {
+    /// Root directory.
+    root: PathBuf,
+    /// Listings of loaded directories, keyed by path.
+    listings: HashMap<PathBuf, Vec<Entry>>,
+    /// Expanded directories below the root.
+    expanded: BTreeSet<PathBuf>,
+    /// Visible rows in display order.
+    rows: Vec<Row>,
+    /// Selected row index.
+    selected: usize,
+    /// Filesystem watcher for the root and every expanded directory, present once mounted.
+    watcher: Option<RecommendedWatcher>,
+    /// Change events from the watcher.
+    events: Option<WatchEvents>,
+}
+
+#[derive_commands]
+impl DirTree {
+    /// Construct a tree rooted at `root`, listing its top level.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        let mut tree = Self {
+            listings: HashMap::new(),
+            expanded: BTreeSet::new(),
+            rows: Vec::new(),
+            selected: 0,
+            watcher: None,
+            events: None,
+            root: root.clone(),
+        };
+        tree.load(&root);
+        tree.rebuild_rows(None);
+        tree
+    }
+
+    /// Return the root directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Return the path of the selected entry.
+    pub fn selected_path(&self) -> Option<&Path> {
+        self.rows.get(self.selected).map(|row| row.path.as_path())
+    }
+
+    /// Return the number of visible entries.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Return true when the root directory has no visible entries.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Move the selection by a signed offset.
+    /// @param delta Rows to move, negative for up.
+    #[command]
+    pub fn select_by(&mut self, c: &mut dyn Context, delta: i32) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let next = if delta.is_negative() {
+            self.selected.saturating_sub(delta.unsigned_abs() as usize)
+        } else {
+            self.selected.saturating_add(delta as usize)
+        };
+        self.selected = next.min(self.rows.len() - 1);
+        self.ensure_selected_visible(c);
+    }
+
+    /// Expand the selected directory.
+    #[command]
+    pub fn expand(&mut self, c: &mut dyn Context) {
+        let Some(row) = self.rows.get(self.selected) else {
+            return;
+        };
+        if !row.is_dir || self.expanded.contains(&row.path) {
+            return;
+        }
+        let path = row.path.clone();
+        self.open_dir(&path);
+        self.rebuild_rows(Some(&path));
+        self.ensure_selected_visible(c);
+    }
+
+    /// Collapse the selected directory, or select the parent of a collapsed entry.
+    #[command]
+    pub fn collapse(&mut self, c: &mut dyn Context) {
+        let Some(row) = self.rows.get(self.selected) else {
+            return;
+        };
+        let path = row.path.clone();
+        if self.expanded.contains(&path) {
+            self.close_dir(&path);
+            self.rebuild_rows(Some(&path));
+        } else if let Some(parent) = path.parent()
+            && parent != self.root
+        {
+            let parent = parent.to_path_buf();
+            self.rebuild_rows(Some(&parent));
+        }
+        self.ensure_selected_visible(c);
+    }
+
+    /// Expand or collapse the selected directory.
+    #[command]
+    pub fn toggle(&mut self, c: &mut dyn Context) {
+        let expanded = self
+            .rows
+            .get(self.selected)
+            .is_some_and(|row| self.expanded.contains(&row.path));
+        if expanded {
+            self.collapse(c);
+        } else {
+            self.expand(c);
+        }
+    }
+
+    /// Expand every directory leading to `path` and select it.
+    /// @param path Absolute path, or a path relative to the tree root.
+    #[command]
+    pub fn reveal(&mut self, c: &mut dyn Context, path: String) -> Result<()> {
+        let path = self.root.join(path);
+        let relative = path
+            .strip_prefix(&self.root)
+            .ok()
+            .filter(|rel| rel.components().all(|c| matches!(c, Component::Normal(_))))
+            .ok_or_else(|| Error::Invalid(format!("{} is outside the tree", path.display())))?
+            .to_path_buf();
+        let mut dir = self.root.clone();
+        let mut parts = relative.components().peekable();
+        while let Some(part) = parts.next() {
+            if parts.peek().is_none() {
+                break;
+            }
+            dir.push(part);
+            if !self.expanded.contains(&dir) {
+                self.open_dir(&dir);
+            }
+        }
+        self.rebuild_rows(None);
+        let index = self
+            .rows
+            .iter()
+            .position(|row| row.path == path)
+            .ok_or_else(|| Error::NotFound(path.display().to_string()))?;
+        self.selected = index;
+        self.ensure_selected_visible(c);
+        Ok(())
+    }
+
+    /// Read a directory listing, sorting directories first. Unreadable directories list
+    /// as empty.
+    fn load(&mut self, dir: &Path) {
+        let mut entries: Vec<Entry> = match fs::read_dir(dir) {
+            Ok(read) => read
+                .filter_map(|entry| entry.ok())
+                .map(|entry| Entry {
+                    name: entry.file_name().to_string_lossy().into_owned(),
+                    is_dir: entry.file_type().is_ok_and(|t| t.is_dir()),
+                    path: entry.path(),
+                })
+                .collect(),
+            Err(err) => {
+                tracing::warn!("failed to list {}: {err}", dir.display());
+                Vec::new()
+            }
+        };
+        entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+        self.listings.insert(dir.to_path_buf(), entries);
+    }
+
+    /// Load and watch a directory, marking it expanded.
+    fn open_dir(&mut self, dir: &Path) {
+        self.load(dir);
+        self.expanded.insert(dir.to_path_buf());
+        self.watch_dir(dir);
+    }
+
+    /// Forget a directory and everything expanded below it.
+    fn close_dir(&mut self, dir: &Path) {
+        let closed: Vec<PathBuf> = self
+            .expanded
+            .iter()
+            .filter(|path| path.starts_with(dir))
+            .cloned()
+            .collect();
+        for path in closed {
+            self.expanded.remove(&path);
+            self.listings.remove(&path);
+            if let Some(watcher) = self.watcher.as_mut() {
+                let _ = watcher.unwatch(&path);
+            }
+        }
+    }
+
+    /// Register a directory with the watcher, if one is running.
+    fn watch_dir(&mut self, dir: &Path) {
+        if let Some(watcher) = self.watcher.as_mut()
+            && let Err(err) = watcher.watch(dir, RecursiveMode::NonRecursive)
+        {
+            tracing::warn!("failed to watch {}: {err}", dir.display());
+        }
+    }
+
+    /// Start watching the root and every expanded directory, waking the tree through
+    /// `waker` as change events arrive.
+    fn start_watching(&mut self, waker: PollWaker) {
+        let (tx, events) = mpsc::channel();
+        let watcher = notify::recommended_watcher(move |event| {
+            if tx.send(event).is_ok() {
+                waker.wake();
+            }
+        });
+        match watcher {
+            Ok(watcher) => {
+                self.watcher = Some(watcher);
+                self.events = Some(events);
+            }
+            Err(err) => {
+                tracing::warn!("failed to start filesystem watcher: {err}");
+                return;
+            }
+        }
+        let dirs: Vec<PathBuf> = iter::once(self.root.clone())
+            .chain(self.expanded.iter().cloned())
+            .collect();
+        for dir in dirs {
+            self.watch_dir(&dir);
+        }
+    }
+
+    /// Relist loaded directories touched by pending change events. Returns true if any
+    /// listing was refreshed.
+    fn apply_changes(&mut self) -> bool {
+        let Some(events) = &self.events else {
+            return false;
+        };
+        let mut touched = BTreeSet::new();
+        for event in events.try_iter() {
+            match event {
+                Ok(event) => touched.extend(event.paths),
+                Err(err) => tracing::warn!("filesystem watch error: {err}"),
+            }
+        }
+        let mut stale: BTreeSet<PathBuf> = touched
+            .iter()
+            .filter_map(|path| path.parent().map(Path::to_path_buf))
+            .chain(touched.iter().cloned())
+            .collect();
+        stale.retain(|dir| self.listings.contains_key(dir));
+        for dir in &stale {
+            self.load(dir);
+        }
+        let expanded: Vec<PathBuf> = self
+            .expanded
+            .iter()
+            .filter(|dir| touched.contains(*dir) && !dir.is_dir())
+            .cloned()
+            .collect();
+        for dir in &expanded {
+            self.close_dir(dir);
+        }
+        if stale.is_empty() && expanded.is_empty() {
+            return false;
+        }
+        let selected = self.selected_path().map(Path::to_path_buf);
+        self.rebuild_rows(selected.as_deref());
+        true
+    }
+
+    /// Flatten the expanded tree into rows, selecting `select` if it is visible and
+    /// clamping the current selection otherwise.
+    fn rebuild_rows(&mut self, select: Option<&Path>) {
+        let mut rows = Vec::new();
+        self.push_rows(&self.root, 0, &mut rows);
+        self.rows = rows;
+        if let Some(index) = select.and_then(|path| self.rows.iter().position(|r| r.path == path)) {
+            self.selected = index;
+        }
+        self.selected = self.selected.min(self.rows.len().saturating_sub(1));
+    }
+
+    /// Append rows for a directory's listing, recursing into expanded children.
+    fn push_rows(&self, dir: &Path, depth: usize, rows: &mut Vec<Row>) {
+        let Some(entries) = self.listings.get(dir) else {
+            return;
+        };
+        for entry in entries {
+            rows.push(Row {
+                path: entry.path.clone(),
+                name: entry.name.clone(),
+                depth,
+                is_dir: entry.is_dir,
+            });
+            if entry.is_dir && self.expanded.contains(&entry.path) {
+                self.push_rows(&entry.path, depth + 1, rows);
+            }
+        }
+    }
+
+    /// Scroll so the selected row is visible.
+    fn ensure_selected_visible(&self, c: &mut dyn Context) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let view_rect = c.view().view_rect();
+        let _ = c.scroll_rect_into_view(Rect::new(view_rect.tl.x, self.selected as u32, 1, 1));
+    }
+
+    /// Return the display text for a row.
+    fn row_text(&self, row: &Row) -> String {
+        let marker = match (row.is_dir, self.expanded.contains(&row.path)) {
+            (true, true) => "▾ ",
+            (true, false) => "▸ ",
+            (false, _) => "  ",
+        };
+        format!("{}{marker}{}", "  ".repeat(row.depth), row.name)
+    }
+
+    /// Return the unclamped size required to render every visible row.
+    fn content_size(&self) -> Size<u32> {
+        let width = self
+            .rows
+            .iter()
+            .map(|row| unicode_width::UnicodeWidthStr::width(self.row_text(row).as_str()))
+            .max()
+            .unwrap_or(0);
+        Size::new(width as u32, self.rows.len() as u32)
+    }
+}
+
+impl Widget for DirTree {
+    fn layout(&self) -> Layout {
+        Layout::fill()
+    }
+
+    fn render(&mut self, rndr: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+        let view = ctx.view();
+        let view_rect = view.view_rect();
+        let content_origin = view.content_origin();
+        rndr.fill("dirtree", view.view_rect_local(), ' ')?;
+        let focused = ctx.is_focused();
+
+        for i in 0..view_rect.h {
+            let index = (view_rect.tl.y + i) as usize;
+            let Some(row) = self.rows.get(index) else {
+                break;
+            };
+            let text = self.row_text(row);
+            let (text, _) = canopy::text::slice_by_columns(
+                &text,
+                view_rect.tl.x as usize,
+                view_rect.w as usize,
+            );
+            let line = Line::new(
+                content_origin.x,
+                content_origin.y.saturating_add(i),
+                view_rect.w,
+            );
+            let style = if focused && index == self.selected {
+                rndr.fill("dirtree/focus", line.into(), ' ')?;
+                "dirtree/focus"
+            } else if row.is_dir {
+                "dirtree/dir"
+            } else {
+                "dirtree"
+            };
+            rndr.text(style, line, text)?;
+        }
+        Ok(())
+    }
+
+    fn measure(&self, c: MeasureConstraints) -> Measurement {
+        c.clamp(self.content_size())
+    }
+
+    fn canvas(&self, view: Size<u32>, _ctx: &CanvasContext) -> Size<u32> {
+        let content = self.content_size();
+        Size::new(content.w.max(view.w), content.h)
+    }
+
+    fn poll(&mut self, c: &mut dyn Context) -> Option<Duration> {
+        if self.apply_changes() {
+            c.invalidate_layout();
+        }
+        None
+    }
+
+    fn on_mount(&mut self, ctx: &mut dyn Context) -> Result<()> {
+        self.start_watching(ctx.poll_waker());
+        Ok(())
+    }
+
+    fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+        true
+    }
+
+    fn name(&self) -> NodeName {
+        NodeName::convert("dir_tree")
+    }
+}
+
+impl Loader for DirTree {
+    fn load(c: &mut Canopy) -> Result<()> {
+        c.add_commands::<Self>()?;
+        c.register_default_bindings("dir_tree", DEFAULT_BINDINGS)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use canopy::testing::dummyctx::DummyContext;
+    use notify::{Event as FsEvent, EventKind};
+
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("canopy-dirtree-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/core")).unwrap();
+        fs::write(root.join("README.md"), "").unwrap();
+        fs::write(root.join("src/lib.rs"), "").unwrap();
+        fs::write(root.join("src/core/mod.rs"), "").unwrap();
+        root
+    }
+
+    fn rows(tree: &DirTree) -> Vec<String> {
+        tree.rows.iter().map(|row| tree.row_text(row)).collect()
+    }
+
+    #[test]
+    fn expand_collapse_and_reveal() -> Result<()> {
+        let root = fixture("expand");
+        let mut tree = DirTree::new(&root);
+        let mut ctx = DummyContext::default();
+        assert_eq!(rows(&tree), ["▸ src", "  README.md"]);
+
+        tree.expand(&mut ctx);
+        assert_eq!(
+            rows(&tree),
+            ["▾ src", "  ▸ core", "    lib.rs", "  README.md"]
+        );
+
+        tree.reveal(&mut ctx, "src/core/mod.rs".into())?;
+        assert_eq!(
+            tree.selected_path(),
+            Some(root.join("src/core/mod.rs").as_path())
+        );
+        assert_eq!(tree.len(), 5);
+
+        tree.collapse(&mut ctx);
+        assert_eq!(tree.selected_path(), Some(root.join("src/core").as_path()));
+        tree.select_by(&mut ctx, -1);
+        tree.collapse(&mut ctx);
+        assert_eq!(rows(&tree), ["▸ src", "  README.md"]);
+        assert!(tree.listings.keys().all(|dir| *dir == root));
+
+        assert!(matches!(
+            tree.reveal(&mut ctx, "missing".into()),
+            Err(Error::NotFound(_))
+        ));
+        assert!(matches!(
+            tree.reveal(&mut ctx, "../elsewhere".into()),
+            Err(Error::Invalid(_))
+        ));
+        fs::remove_dir_all(root).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn change_events_relist_loaded_directories() {
+        let root = fixture("changes");
+        let mut tree = DirTree::new(&root);
+        tree.expand(&mut DummyContext::default());
+        let (tx, events) = mpsc::channel();
+        tree.events = Some(events);
+        assert!(!tree.apply_changes());
+
+        fs::write(root.join("NEW.md"), "").unwrap();
+        fs::remove_dir_all(root.join("src")).unwrap();
+        tx.send(Ok(
+            FsEvent::new(EventKind::Any).add_path(root.join("NEW.md"))
+        ))
+        .unwrap();
+        tx.send(Ok(FsEvent::new(EventKind::Any).add_path(root.join("src"))))
+            .unwrap();
+        assert!(tree.apply_changes());
+        assert_eq!(rows(&tree), ["  NEW.md", "  README.md"]);
+        assert!(tree.expanded.is_empty());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
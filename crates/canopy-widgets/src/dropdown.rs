@@ -1,7 +1,7 @@
 //! Dropdown widget for single-value selection with expand/collapse behavior.
 
 use canopy::{
-    Context, EventOutcome, ReadContext, Widget, command, derive_commands,
+    Context, EventOutcome, ReadContext, UndoEntry, Widget, command, derive_commands,
     error::Result,
     event::{Event, mouse},
     layout::{MeasureConstraints, Measurement, Size},
@@ -122,14 +122,40 @@ where
     #[command]
     pub fn confirm(&mut self, c: &mut dyn Context) -> Result<()> {
         if self.expanded {
-            self.selected = self.highlighted;
             self.expanded = false;
+            self.commit_selection(c, self.highlighted);
             c.invalidate_layout();
         }
         debug_assert!(self.selection_invariant_holds());
         Ok(())
     }
 
+    /// Select an item and collapse. The change is registered for undo.
+    /// @param index Zero-based item index, clamped to the last item.
+    #[command]
+    pub fn select(&mut self, c: &mut dyn Context, index: usize) -> Result<()> {
+        self.expanded = false;
+        self.commit_selection(c, index.min(self.items.len() - 1));
+        c.invalidate_layout();
+        debug_assert!(self.selection_invariant_holds());
+        Ok(())
+    }
+
+    /// Make `index` the selection, registering the change for undo.
+    fn commit_selection(&mut self, c: &mut dyn Context, index: usize) {
+        let previous = self.selected;
+        self.selected = index;
+        self.highlighted = index;
+        if previous != index {
+            c.push_undo(UndoEntry::new(
+                c.node_id(),
+                "select option",
+                Self::cmd_select().call_with([previous]).invocation(),
+                Self::cmd_select().call_with([index]).invocation(),
+            ));
+        }
+    }
+
     /// Handle a click inside the dropdown.
     fn handle_click(&mut self, c: &mut dyn Context, event: mouse::MouseEvent) -> Result<bool> {
         if event.action != mouse::Action::Down || event.button != mouse::Button::Left {
@@ -139,9 +165,8 @@ where
         if self.expanded {
             // When expanded, click selects and confirms.
             if clicked_row < self.items.len() {
-                self.highlighted = clicked_row;
-                self.selected = self.highlighted;
                 self.expanded = false;
+                self.commit_selection(c, clicked_row);
                 c.invalidate_layout();
                 debug_assert!(self.selection_invariant_holds());
                 return Ok(true);
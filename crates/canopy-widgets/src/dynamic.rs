@@ -0,0 +1,288 @@
+//! Keyed dynamic children.
+//!
+//! [`Children`] owns a keyed, ordered set of child widgets under the node that holds it.
+//! Adding mounts the new subtree, removing runs `pre_remove` and `on_unmount` and frees the
+//! nodes, and pending polls or timers on removed nodes are dropped when they come due. The
+//! owning widget should not manage its children by any other means.
+
+use std::{fmt::Debug, hash::Hash, marker::PhantomData};
+
+use canopy::{
+    Context, KeyedChildren, NodeId, RemovePolicy, TypedId, Widget,
+    error::{Error, Result},
+};
+
+/// Keyed, ordered children of the owning widget's node.
+pub struct Children<W, K = String> {
+    /// Keyed child nodes in order.
+    items: KeyedChildren<K>,
+    /// Marker for the widget type.
+    _marker: PhantomData<W>,
+}
+
+impl<W, K> Default for Children<W, K> {
+    fn default() -> Self {
+        Self {
+            items: KeyedChildren::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<W, K> Children<W, K>
+where
+    W: Widget + 'static,
+    K: Eq + Hash + Clone + Debug,
+{
+    /// Construct an empty collection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the number of children.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Return true if there are no children.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Return the keys in order.
+    pub fn keys(&self) -> &[K] {
+        self.items.keys()
+    }
+
+    /// Return the child for a key.
+    pub fn get(&self, key: &K) -> Option<TypedId<W>> {
+        self.items.id_for(key).map(TypedId::new)
+    }
+
+    /// Return the index of a key.
+    pub fn index_of(&self, key: &K) -> Option<usize> {
+        self.items.keys().iter().position(|k| k == key)
+    }
+
+    /// Iterate children in order.
+    pub fn iter(&self) -> impl Iterator<Item = TypedId<W>> + '_ {
+        self.items.iter_ids().map(TypedId::new)
+    }
+
+    /// Append a child under a new key.
+    pub fn add(&mut self, ctx: &mut dyn Context, key: K, widget: W) -> Result<TypedId<W>> {
+        self.insert(ctx, self.len(), key, widget)
+    }
+
+    /// Insert a child under a new key at `index`, clamped to the end.
+    pub fn insert(
+        &mut self,
+        ctx: &mut dyn Context,
+        index: usize,
+        key: K,
+        widget: W,
+    ) -> Result<TypedId<W>> {
+        if self.items.id_for(&key).is_some() {
+            return Err(Error::DuplicateChildKey(format!("{key:?}")));
+        }
+        let mut desired = self.items.keys().to_vec();
+        desired.insert(index.min(desired.len()), key.clone());
+        let mut widget = Some(widget);
+        self.items.try_reconcile(
+            ctx,
+            desired,
+            |requested| {
+                if *requested != key {
+                    return Err(Error::Internal(format!(
+                        "children reconcile requested unexpected key {requested:?}"
+                    )));
+                }
+                widget
+                    .take()
+                    .ok_or_else(|| Error::Internal("child widget already consumed".into()))
+            },
+            |_, _, _| Ok(()),
+            RemovePolicy::RemoveSubtree,
+        )?;
+        self.get(&key)
+            .ok_or_else(|| Error::Internal("inserted child is missing".into()))
+    }
+
+    /// Remove the child for a key and free its subtree. Focus inside the subtree moves
+    /// to a neighbouring node. Returns `false` if the key is absent.
+    pub fn remove(&mut self, ctx: &mut dyn Context, key: &K) -> Result<bool> {
+        let Some(index) = self.index_of(key) else {
+            return Ok(false);
+        };
+        let mut desired = self.items.keys().to_vec();
+        desired.remove(index);
+        self.reorder(ctx, desired)?;
+        Ok(true)
+    }
+
+    /// Replace the widget for a key, keeping its position. If focus was inside the old
+    /// subtree, it moves into the replacement.
+    pub fn replace(&mut self, ctx: &mut dyn Context, key: &K, widget: W) -> Result<TypedId<W>> {
+        let (index, old) = self
+            .index_of(key)
+            .zip(self.items.id_for(key))
+            .ok_or_else(|| Error::NotFound(format!("child key {key:?}")))?;
+        let had_focus = ctx.node_is_on_focus_path(old);
+        self.remove(ctx, key)?;
+        let id = self.insert(ctx, index, key.clone(), widget)?;
+        if had_focus {
+            ctx.focus_first_in(id.into());
+        }
+        Ok(id)
+    }
+
+    /// Move the child for a key to `index`, clamped to the end. Returns `false` if the key
+    /// is absent.
+    pub fn move_to(&mut self, ctx: &mut dyn Context, key: &K, index: usize) -> Result<bool> {
+        let Some(from) = self.index_of(key) else {
+            return Ok(false);
+        };
+        let mut desired = self.items.keys().to_vec();
+        let key = desired.remove(from);
+        desired.insert(index.min(desired.len()), key);
+        self.reorder(ctx, desired)?;
+        Ok(true)
+    }
+
+    /// Remove all children.
+    pub fn clear(&mut self, ctx: &mut dyn Context) -> Result<()> {
+        self.reorder(ctx, Vec::new())
+    }
+
+    /// Reconcile to `desired`, which must only contain existing keys.
+    fn reorder(&mut self, ctx: &mut dyn Context, desired: Vec<K>) -> Result<()> {
+        self.items.try_reconcile(
+            ctx,
+            desired,
+            |requested| {
+                Err::<W, _>(Error::Internal(format!(
+                    "children reconcile requested missing widget for key {requested:?}"
+                )))
+            },
+            |_, _, _| Ok(()),
+            RemovePolicy::RemoveSubtree,
+        )?;
+        Ok(())
+    }
+
+    /// Return node IDs in order.
+    pub fn ids(&self) -> Vec<NodeId> {
+        self.items.iter_ids().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use canopy::{
+        Canopy, Loader, ReadContext, derive_commands, state::NodeName, testing::harness::Harness,
+    };
+
+    use super::*;
+    use crate::Text;
+
+    struct Holder {
+        children: Children<Text>,
+    }
+
+    #[derive_commands]
+    impl Holder {}
+
+    impl Widget for Holder {
+        fn name(&self) -> NodeName {
+            NodeName::convert("holder")
+        }
+    }
+
+    impl Loader for Holder {
+        fn load(c: &mut Canopy) -> Result<()> {
+            c.add_commands::<Self>()?;
+            Ok(())
+        }
+    }
+
+    struct Focusable;
+
+    impl Widget for Focusable {
+        fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn add_remove_replace_and_move_keep_order() -> Result<()> {
+        let mut harness = Harness::builder(Holder {
+            children: Children::new(),
+        })
+        .size(20, 5)
+        .build()?;
+
+        let ids = harness.with_root_context(|holder: &mut Holder, ctx| {
+            let children = &mut holder.children;
+            children.add(ctx, "a".into(), Text::new("a"))?;
+            children.add(ctx, "b".into(), Text::new("b"))?;
+            children.insert(ctx, 0, "c".into(), Text::new("c"))?;
+            assert!(children.add(ctx, "a".into(), Text::new("dup")).is_err());
+            let old_b = children.get(&"b".to_string()).expect("b");
+            assert!(children.remove(ctx, &"a".to_string())?);
+            assert!(!children.remove(ctx, &"a".to_string())?);
+            let new_b = children.replace(ctx, &"b".to_string(), Text::new("b2"))?;
+            assert_ne!(NodeId::from(old_b), NodeId::from(new_b));
+            assert!(children.move_to(ctx, &"b".to_string(), 0)?);
+            assert_eq!(children.keys(), ["b", "c"]);
+            Ok(children.ids())
+        })?;
+
+        let root = harness.root;
+        assert_eq!(
+            harness
+                .canopy
+                .core()
+                .node(root)
+                .map(|n| n.children().to_vec()),
+            Some(ids)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn replace_moves_focus_into_the_replacement() -> Result<()> {
+        struct FocusHolder {
+            children: Children<Focusable, u32>,
+        }
+
+        #[derive_commands]
+        impl FocusHolder {}
+
+        impl Widget for FocusHolder {}
+
+        impl Loader for FocusHolder {
+            fn load(c: &mut Canopy) -> Result<()> {
+                c.add_commands::<Self>()?;
+                Ok(())
+            }
+        }
+
+        let mut harness = Harness::builder(FocusHolder {
+            children: Children::new(),
+        })
+        .size(20, 5)
+        .build()?;
+
+        let replacement = harness.with_root_context(|holder: &mut FocusHolder, ctx| {
+            let first = holder.children.add(ctx, 1, Focusable)?;
+            holder.children.add(ctx, 2, Focusable)?;
+            ctx.set_focus(first.into());
+            holder.children.replace(ctx, &1, Focusable)
+        })?;
+        assert_eq!(
+            harness.canopy.core().focus_id(),
+            Some(NodeId::from(replacement))
+        );
+        Ok(())
+    }
+}
@@ -1,4 +1,7 @@
-use std::ops::{Deref, DerefMut};
+use std::{
+    mem,
+    ops::{Deref, DerefMut},
+};
 
 use canopy::text;
 use ropey::Rope;
@@ -21,6 +24,17 @@ pub struct LineChange {
     pub new_line_count: usize,
 }
 
+/// A single edit applied to the buffer, including edits made by undo and redo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextChange {
+    /// Range that was replaced, in positions from before the edit.
+    pub range: TextRange,
+    /// Text inserted in place of the range. Empty for deletions.
+    pub text: String,
+    /// Buffer revision produced by the edit.
+    pub revision: u64,
+}
+
 /// Rope-backed text buffer with selection and undo/redo support.
 #[derive(Debug, Clone)]
 pub struct TextBuffer {
@@ -32,12 +46,18 @@ pub struct TextBuffer {
     revision: u64,
     /// Latest line change since the last sync.
     pending_change: Option<LineChange>,
+    /// Edits not yet taken by the owner.
+    changes: Vec<TextChange>,
+    /// Revision at which the contents were last marked clean.
+    clean_revision: u64,
     /// Undo history.
     undo: Vec<Transaction>,
     /// Redo history.
     redo: Vec<Transaction>,
     /// Active transaction for grouped edits.
     transaction: Option<Transaction>,
+    /// Number of transactions committed to the undo history.
+    commits: u64,
 }
 
 impl TextBuffer {
@@ -57,9 +77,12 @@ impl TextBuffer {
             selection,
             revision: 0,
             pending_change: None,
+            changes: Vec::new(),
+            clean_revision: 0,
             undo: Vec::new(),
             redo: Vec::new(),
             transaction: None,
+            commits: 0,
         }
     }
 
@@ -73,10 +96,10 @@ impl TextBuffer {
         self.selection
     }
 
-    /// Replace the selection, clamping to bounds.
+    /// Replace the selection, clamping to bounds and grapheme boundaries.
     pub fn set_selection(&mut self, selection: Selection) {
-        let anchor = self.clamp_position(selection.anchor());
-        let head = self.clamp_position(selection.head());
+        let anchor = self.snap_position(selection.anchor());
+        let head = self.snap_position(selection.head());
         self.selection = Selection::new(anchor, head);
     }
 
@@ -85,9 +108,10 @@ impl TextBuffer {
         self.selection.head()
     }
 
-    /// Replace the cursor and collapse the selection.
+    /// Replace the cursor and collapse the selection. Positions inside a grapheme cluster
+    /// move back to the start of the cluster.
     pub fn set_cursor(&mut self, pos: TextPosition) {
-        let pos = self.clamp_position(pos);
+        let pos = self.snap_position(pos);
         self.selection = Selection::caret(pos);
     }
 
@@ -149,6 +173,21 @@ impl TextBuffer {
         text
     }
 
+    /// Take the edits made since the last call, in order.
+    pub fn take_changes(&mut self) -> Vec<TextChange> {
+        mem::take(&mut self.changes)
+    }
+
+    /// Whether the buffer was edited since it was created or last marked clean.
+    pub fn is_dirty(&self) -> bool {
+        self.revision != self.clean_revision
+    }
+
+    /// Mark the current contents as clean, for example after saving them.
+    pub fn mark_clean(&mut self) {
+        self.clean_revision = self.revision;
+    }
+
     /// Take the pending line change, if any.
     pub fn take_change(&mut self) -> Option<LineChange> {
         self.pending_change.take()
@@ -172,6 +211,13 @@ impl TextBuffer {
         transaction.finish(self.selection);
         self.undo.push(transaction);
         self.redo.clear();
+        self.commits += 1;
+    }
+
+    /// Return the number of transactions committed to the undo history. Undo and redo do
+    /// not change the count.
+    pub fn commit_count(&self) -> u64 {
+        self.commits
     }
 
     /// Begin a grouped transaction that commits when the guard is dropped.
@@ -235,6 +281,11 @@ impl TextBuffer {
         let new_cursor = advance_position(range.start, text);
         self.selection = Selection::caret(new_cursor);
         self.bump_revision(range, text);
+        self.changes.push(TextChange {
+            range,
+            text: text.to_string(),
+            revision: self.revision,
+        });
     }
 
     /// Delete the selection or the grapheme before the cursor.
@@ -347,11 +398,11 @@ impl TextBuffer {
         let cursor = self.selection.head();
         let line_text = self.line_text(cursor.line);
         let mut column = 0usize;
-        for ch in line_text.chars() {
-            if !ch.is_whitespace() {
+        for grapheme in line_text.graphemes(true) {
+            if !grapheme.chars().all(char::is_whitespace) {
                 break;
             }
-            column = column.saturating_add(1);
+            column = column.saturating_add(grapheme.chars().count());
         }
         self.selection = Selection::caret(TextPosition::new(cursor.line, column));
     }
@@ -416,6 +467,7 @@ impl TextBuffer {
             transaction.finish(self.selection);
             self.undo.push(transaction);
             self.redo.clear();
+            self.commits += 1;
         }
     }
 
@@ -434,6 +486,17 @@ impl TextBuffer {
         TextPosition::new(line, column)
     }
 
+    /// Clamp a position to buffer bounds and the start of its grapheme cluster.
+    fn snap_position(&self, pos: TextPosition) -> TextPosition {
+        let pos = self.clamp_position(pos);
+        let boundaries = grapheme_boundaries(&self.line_text_at(pos.line));
+        let column = match boundaries.binary_search(&pos.column) {
+            Ok(_) => pos.column,
+            Err(idx) => boundaries.get(idx.saturating_sub(1)).copied().unwrap_or(0),
+        };
+        TextPosition::new(pos.line, column)
+    }
+
     /// Convert a text position to a rope char index.
     fn position_to_char(&self, pos: TextPosition) -> usize {
         let pos = self.clamp_position(pos);
@@ -485,6 +548,11 @@ impl TextBuffer {
         self.rope.insert(start_char, insert_text);
         self.revision = self.revision.saturating_add(1);
         self.pending_change = None;
+        self.changes.push(TextChange {
+            range: TextRange::new(start, end),
+            text: insert_text.to_string(),
+            revision: self.revision,
+        });
     }
 }
 
@@ -646,6 +714,24 @@ mod tests {
         assert_eq!(buf.cursor(), TextPosition::new(0, 1));
     }
 
+    #[test]
+    fn cursor_positions_snap_to_grapheme_starts() {
+        let mut buf = TextBuffer::new("ae\u{301}👩‍💻\n \u{301}x");
+        buf.set_cursor(TextPosition::new(0, 2));
+        assert_eq!(buf.cursor(), TextPosition::new(0, 1));
+        buf.set_cursor(TextPosition::new(0, 5));
+        assert_eq!(buf.cursor(), TextPosition::new(0, 3));
+        buf.set_selection(Selection::new(
+            TextPosition::new(0, 0),
+            TextPosition::new(0, 4),
+        ));
+        assert_eq!(buf.selection().head(), TextPosition::new(0, 3));
+
+        buf.set_cursor(TextPosition::new(1, 0));
+        buf.move_line_first_non_ws();
+        assert_eq!(buf.cursor(), TextPosition::new(1, 0));
+    }
+
     #[test]
     fn column_mapping_respects_tabs() {
         let buf = TextBuffer::new("a\tb");
@@ -686,6 +772,38 @@ mod tests {
         assert_eq!(buf.text(), "abcd");
     }
 
+    #[test]
+    fn changes_record_edits_and_dirty_state() {
+        let mut buf = TextBuffer::new("abc");
+        assert!(!buf.is_dirty());
+        buf.replace_range(
+            TextRange::new(TextPosition::new(0, 1), TextPosition::new(0, 2)),
+            "xy",
+        );
+        assert!(buf.undo());
+        assert_eq!(
+            buf.take_changes(),
+            [
+                TextChange {
+                    range: TextRange::new(TextPosition::new(0, 1), TextPosition::new(0, 2)),
+                    text: "xy".into(),
+                    revision: 1,
+                },
+                TextChange {
+                    range: TextRange::new(TextPosition::new(0, 1), TextPosition::new(0, 3)),
+                    text: "b".into(),
+                    revision: 2,
+                },
+            ]
+        );
+        assert!(buf.take_changes().is_empty());
+        assert!(buf.is_dirty());
+        buf.mark_clean();
+        assert!(!buf.is_dirty());
+        buf.insert_text("z");
+        assert!(buf.is_dirty());
+    }
+
     #[test]
     fn strict_accessors_reject_out_of_bounds_positions() {
         let buf = TextBuffer::new("ab\ncd");
@@ -0,0 +1,88 @@
+use super::TextPosition;
+
+/// Maximum number of candidates shown in the completion popup at once.
+pub const MAX_VISIBLE: usize = 8;
+
+/// A completion candidate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    /// Text that replaces the word before the cursor.
+    pub text: String,
+    /// Optional detail shown after the text in the popup.
+    pub detail: Option<String>,
+}
+
+impl Completion {
+    /// Construct a candidate that inserts `text`.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            detail: None,
+        }
+    }
+
+    /// Attach detail text shown in the popup.
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// Text shown for this candidate in the popup.
+    pub fn label(&self) -> String {
+        match &self.detail {
+            Some(detail) => format!("{}  {detail}", self.text),
+            None => self.text.clone(),
+        }
+    }
+}
+
+/// Supplies completion candidates at the editor cursor.
+///
+/// Tab asks the provider for candidates. A single candidate is inserted immediately;
+/// several open a popup that follows further typing, moves with Up and Down, accepts with
+/// Tab, and closes with Esc.
+pub trait CompletionProvider: Send {
+    /// Return candidates for `prefix`, the word that ends at `cursor` on `line`. An empty
+    /// prefix means the cursor is not directly after a word.
+    fn complete(&mut self, prefix: &str, line: &str, cursor: TextPosition) -> Vec<Completion>;
+}
+
+/// Open completion popup state.
+#[derive(Debug, Clone)]
+pub struct CompletionMenu {
+    /// Start of the text replaced by the accepted candidate.
+    pub start: TextPosition,
+    /// Candidates in display order.
+    pub items: Vec<Completion>,
+    /// Index of the highlighted candidate.
+    pub selected: usize,
+}
+
+impl CompletionMenu {
+    /// Construct a menu with the first candidate highlighted.
+    pub fn new(start: TextPosition, items: Vec<Completion>) -> Self {
+        Self {
+            start,
+            items,
+            selected: 0,
+        }
+    }
+
+    /// Move the highlight by `delta`, wrapping at either end.
+    pub fn shift(&mut self, delta: isize) {
+        let len = self.items.len() as isize;
+        if len > 0 {
+            self.selected = (self.selected as isize + delta).rem_euclid(len) as usize;
+        }
+    }
+
+    /// Return the highlighted candidate.
+    pub fn current(&self) -> Option<&Completion> {
+        self.items.get(self.selected)
+    }
+
+    /// Index of the first candidate shown when `rows` fit on screen.
+    pub fn first_visible(&self, rows: usize) -> usize {
+        self.selected.saturating_sub(rows.saturating_sub(1))
+    }
+}
@@ -0,0 +1,111 @@
+use super::TextRange;
+
+/// Severity of a diagnostic, ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// A hint, such as a suggested refactoring.
+    Hint,
+    /// An informational message.
+    Info,
+    /// A warning.
+    Warning,
+    /// An error.
+    Error,
+}
+
+impl Severity {
+    /// Style used to underline text covered by a diagnostic of this severity.
+    pub fn style(self) -> &'static str {
+        match self {
+            Self::Hint => "editor/diagnostic/hint",
+            Self::Info => "editor/diagnostic/info",
+            Self::Warning => "editor/diagnostic/warning",
+            Self::Error => "editor/diagnostic/error",
+        }
+    }
+
+    /// Style used for the gutter sign of this severity.
+    pub fn sign_style(self) -> &'static str {
+        match self {
+            Self::Hint => "editor/sign/hint",
+            Self::Info => "editor/sign/info",
+            Self::Warning => "editor/sign/warning",
+            Self::Error => "editor/sign/error",
+        }
+    }
+
+    /// Glyph drawn in the gutter next to lines with a diagnostic of this severity.
+    pub fn sign(self) -> char {
+        match self {
+            Self::Hint => 'H',
+            Self::Info => 'I',
+            Self::Warning => 'W',
+            Self::Error => 'E',
+        }
+    }
+}
+
+/// A message attached to a range of editor text, such as a compiler error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Text the diagnostic applies to.
+    pub range: TextRange,
+    /// Diagnostic severity.
+    pub severity: Severity,
+    /// Message describing the problem.
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Construct a diagnostic covering `range`.
+    pub fn new(range: TextRange, severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            range,
+            severity,
+            message: message.into(),
+        }
+    }
+
+    /// Whether the diagnostic touches `line`.
+    fn covers_line(&self, line: usize) -> bool {
+        let range = self.range.normalized();
+        range.start.line <= line && line <= range.end.line
+    }
+}
+
+/// Most severe diagnostic on `line`.
+pub fn line_severity(diagnostics: &[Diagnostic], line: usize) -> Option<Severity> {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.covers_line(line))
+        .map(|diagnostic| diagnostic.severity)
+        .max()
+}
+
+/// Most severe diagnostic overlapping characters `start..end` of `line`. Empty ranges
+/// mark the character they start at.
+pub fn severity_at(
+    diagnostics: &[Diagnostic],
+    line: usize,
+    start: usize,
+    end: usize,
+) -> Option<Severity> {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| {
+            let range = diagnostic.range.normalized();
+            let from = if range.start.line == line {
+                range.start.column
+            } else {
+                0
+            };
+            let to = if range.end.line == line {
+                range.end.column.max(from.saturating_add(1))
+            } else {
+                usize::MAX
+            };
+            diagnostic.covers_line(line) && from < end && to > start
+        })
+        .map(|diagnostic| diagnostic.severity)
+        .max()
+}
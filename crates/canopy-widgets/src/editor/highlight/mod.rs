@@ -0,0 +1,48 @@
+use std::ops::Range;
+
+use canopy::style::Style;
+
+/// Highlighter backed by syntect.
+#[cfg(feature = "syntect")]
+mod syntect;
+
+#[cfg(feature = "syntect")]
+pub use self::syntect::SyntectHighlighter;
+
+/// A highlighted span for a single line.
+#[derive(Debug, Clone)]
+pub struct HighlightSpan {
+    /// Character range covered by the span.
+    pub range: Range<usize>,
+    /// Style to apply to the span.
+    pub style: Style,
+}
+
+/// Trait for providing syntax highlighting spans.
+///
+/// The editor highlights the buffer from the top down. Each render passes the lines from
+/// the first one not yet highlighted through the last visible line to
+/// [`Highlighter::highlight_chunk`], so every chunk directly follows the previous one.
+/// After an edit the editor keeps the spans of lines above the first changed line and
+/// requests the rest again, so highlighters whose state flows from one line to the next
+/// stay correct if they carry that state from the end of one chunk to the next.
+pub trait Highlighter: Send {
+    /// Return highlight spans for a line of text.
+    fn highlight_line(&self, line: usize, text: &str) -> Vec<HighlightSpan>;
+
+    /// Return highlight spans for each line of `chunk`, the first of which is line
+    /// `start` of the buffer at `revision`. A chunk starting at line 0 begins a new pass
+    /// over the buffer. The default highlights every line on its own.
+    fn highlight_chunk(
+        &mut self,
+        start: usize,
+        chunk: &[String],
+        _revision: u64,
+    ) -> Vec<Vec<HighlightSpan>> {
+        chunk
+            .iter()
+            .enumerate()
+            .map(|(offset, text)| self.highlight_line(start + offset, text))
+            .collect()
+    }
+}
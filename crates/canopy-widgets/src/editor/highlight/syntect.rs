@@ -0,0 +1,250 @@
+use canopy::style::{Attr, AttrSet, Color, Paint, Style};
+use syntect::{
+    highlighting,
+    highlighting::{
+        FontStyle, HighlightIterator, HighlightState, Style as SyntectStyle, Theme, ThemeSet,
+    },
+    parsing::{ParseState, ScopeStack, SyntaxReference, SyntaxSet},
+};
+
+use super::{HighlightSpan, Highlighter};
+
+/// A basic syntect-backed highlighter.
+#[derive(Debug, Clone)]
+pub struct SyntectHighlighter {
+    /// Loaded syntax set.
+    syntax_set: SyntaxSet,
+    /// Theme used for highlighting.
+    theme: Theme,
+    /// File extension hint for syntax selection.
+    extension: String,
+    /// Highlight and parse state at the start of each line highlighted by
+    /// `highlight_chunk`, so the next chunk resumes where the lines before it left off.
+    states: Vec<(HighlightState, ParseState)>,
+}
+
+impl SyntectHighlighter {
+    /// Construct a new syntect highlighter for the provided extension.
+    pub fn new(extension: impl Into<String>) -> Self {
+        let syntax_set = SyntaxSet::load_defaults_newlines();
+        let themes = ThemeSet::load_defaults();
+        let theme = default_theme(&themes);
+        Self {
+            syntax_set,
+            theme,
+            extension: extension.into(),
+            states: Vec::new(),
+        }
+    }
+
+    /// Construct a syntect highlighter with a named theme.
+    pub fn with_theme_name(extension: impl Into<String>, theme_name: impl AsRef<str>) -> Self {
+        let syntax_set = SyntaxSet::load_defaults_newlines();
+        let themes = ThemeSet::load_defaults();
+        let theme = themes
+            .themes
+            .get(theme_name.as_ref())
+            .cloned()
+            .unwrap_or_else(|| default_theme(&themes));
+        Self {
+            syntax_set,
+            theme,
+            extension: extension.into(),
+            states: Vec::new(),
+        }
+    }
+
+    /// Construct a syntect highlighter using a specific theme.
+    pub fn with_theme(extension: impl Into<String>, theme: Theme) -> Self {
+        let syntax_set = SyntaxSet::load_defaults_newlines();
+        Self {
+            syntax_set,
+            theme,
+            extension: extension.into(),
+            states: Vec::new(),
+        }
+    }
+
+    /// Construct a highlighter using the plain text syntax.
+    pub fn plain() -> Self {
+        Self::new("txt")
+    }
+
+    /// Resolve the syntax definition for the configured extension.
+    fn syntax(&self) -> &SyntaxReference {
+        self.syntax_set
+            .find_syntax_by_extension(&self.extension)
+            .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text())
+    }
+
+    /// Return the state at the start of the file.
+    fn initial_state(
+        &self,
+        highlighter: &highlighting::Highlighter<'_>,
+    ) -> (HighlightState, ParseState) {
+        (
+            HighlightState::new(highlighter, ScopeStack::new()),
+            ParseState::new(self.syntax()),
+        )
+    }
+}
+
+impl Highlighter for SyntectHighlighter {
+    fn highlight_line(&self, _line: usize, text: &str) -> Vec<HighlightSpan> {
+        let highlighter = highlighting::Highlighter::new(&self.theme);
+        let (mut highlight, mut parse) = self.initial_state(&highlighter);
+        highlight_text(
+            &highlighter,
+            &self.syntax_set,
+            &mut highlight,
+            &mut parse,
+            text,
+        )
+    }
+
+    fn highlight_chunk(
+        &mut self,
+        start: usize,
+        chunk: &[String],
+        _revision: u64,
+    ) -> Vec<Vec<HighlightSpan>> {
+        let highlighter = highlighting::Highlighter::new(&self.theme);
+        if start == 0 {
+            self.states = vec![self.initial_state(&highlighter)];
+        }
+        self.states.truncate(start + 1);
+        let Some((mut highlight, mut parse)) = self.states.get(start).cloned() else {
+            // Without the state of the line before the chunk, each line starts afresh.
+            return chunk
+                .iter()
+                .enumerate()
+                .map(|(offset, text)| self.highlight_line(start + offset, text))
+                .collect();
+        };
+        let mut spans = Vec::with_capacity(chunk.len());
+        for text in chunk {
+            spans.push(highlight_text(
+                &highlighter,
+                &self.syntax_set,
+                &mut highlight,
+                &mut parse,
+                text,
+            ));
+            self.states.push((highlight.clone(), parse.clone()));
+        }
+        spans
+    }
+}
+
+/// Highlight one line, given without its newline, advancing `highlight` and `parse` past
+/// it.
+fn highlight_text(
+    highlighter: &highlighting::Highlighter<'_>,
+    syntax_set: &SyntaxSet,
+    highlight: &mut HighlightState,
+    parse: &mut ParseState,
+    text: &str,
+) -> Vec<HighlightSpan> {
+    // The default syntaxes match line ends against the newline.
+    let line = format!("{text}\n");
+    let Ok(ops) = parse.parse_line(&line, syntax_set) else {
+        return Vec::new();
+    };
+    let len = text.chars().count();
+    let mut spans = Vec::new();
+    let mut offset = 0usize;
+    for (style, slice) in HighlightIterator::new(highlight, &ops, &line, highlighter) {
+        let end = offset.saturating_add(slice.chars().count()).min(len);
+        if offset < end {
+            spans.push(HighlightSpan {
+                range: offset..end,
+                style: map_style(style),
+            });
+        }
+        offset = end;
+    }
+    spans
+}
+
+impl Default for SyntectHighlighter {
+    fn default() -> Self {
+        Self::plain()
+    }
+}
+
+/// Return the default theme from the provided theme set.
+fn default_theme(themes: &ThemeSet) -> Theme {
+    themes
+        .themes
+        .get("Solarized (dark)")
+        .cloned()
+        .or_else(|| themes.themes.values().next().cloned())
+        .unwrap_or_default()
+}
+
+/// Convert a syntect style to a canopy style.
+fn map_style(style: SyntectStyle) -> Style {
+    let attrs = map_attrs(style.font_style);
+    Style {
+        fg: Paint::solid(map_color(style.foreground)),
+        bg: Paint::solid(map_color(style.background)),
+        attrs,
+    }
+}
+
+/// Convert a syntect color to a canopy color.
+fn map_color(color: highlighting::Color) -> Color {
+    Color::Rgb {
+        r: color.r,
+        g: color.g,
+        b: color.b,
+    }
+}
+
+/// Convert syntect font styles to canopy attributes.
+fn map_attrs(style: FontStyle) -> AttrSet {
+    let mut attrs = AttrSet::default();
+    if style.contains(FontStyle::BOLD) {
+        attrs = attrs.with(Attr::Bold);
+    }
+    if style.contains(FontStyle::ITALIC) {
+        attrs = attrs.with(Attr::Italic);
+    }
+    if style.contains(FontStyle::UNDERLINE) {
+        attrs = attrs.with(Attr::Underline);
+    }
+    attrs
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Range;
+
+    use super::*;
+
+    /// Reduce spans to comparable ranges and styles.
+    fn parts(spans: &[HighlightSpan]) -> Vec<(Range<usize>, Style)> {
+        spans
+            .iter()
+            .map(|span| (span.range.clone(), span.style.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn chunks_carry_parse_state_across_lines() {
+        let lines = ["/* open", "still comment */", "let x = 1;"].map(String::from);
+        let mut whole = SyntectHighlighter::new("rs");
+        let expected = whole.highlight_chunk(0, &lines, 0);
+
+        let mut split = SyntectHighlighter::new("rs");
+        let mut actual = split.highlight_chunk(0, &lines[..1], 0);
+        actual.extend(split.highlight_chunk(1, &lines[1..], 0));
+        for (actual, expected) in actual.iter().zip(&expected) {
+            assert_eq!(parts(actual), parts(expected));
+        }
+        assert_ne!(
+            parts(&expected[1]),
+            parts(&whole.highlight_line(1, &lines[1]))
+        );
+    }
+}
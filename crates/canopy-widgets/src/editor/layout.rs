@@ -2,6 +2,7 @@ use canopy::{geom::Point, text};
 use unicode_segmentation::UnicodeSegmentation;
 
 use super::{LineChange, TextBuffer, TextPosition, WrapMode, tab_width};
+use crate::text::WordBreaker;
 
 /// A wrapped segment of a logical line.
 #[derive(Debug, Clone)]
@@ -71,6 +72,8 @@ pub struct LayoutCache {
     wrap_mode: WrapMode,
     /// Cached tab stop.
     tab_stop: usize,
+    /// Cached word breaker.
+    breaker: Option<WordBreaker>,
     /// Cached buffer revision.
     revision: u64,
 }
@@ -86,6 +89,7 @@ impl LayoutCache {
             wrap_width: 0,
             wrap_mode: WrapMode::None,
             tab_stop: 4,
+            breaker: None,
             revision: 0,
         }
     }
@@ -97,13 +101,16 @@ impl LayoutCache {
         wrap_width: usize,
         wrap_mode: WrapMode,
         tab_stop: usize,
+        breaker: Option<&WordBreaker>,
     ) {
         let wrap_width = wrap_width.max(1);
         let needs_rebuild = self.wrap_width != wrap_width
             || self.wrap_mode != wrap_mode
-            || self.tab_stop != tab_stop;
+            || self.tab_stop != tab_stop
+            || self.breaker.as_ref() != breaker;
 
         if needs_rebuild {
+            self.breaker = breaker.cloned();
             self.rebuild_all(buffer, wrap_width, wrap_mode, tab_stop);
             return;
         }
@@ -230,7 +237,13 @@ impl LayoutCache {
         let line_count = buffer.line_count().max(1);
         for line in 0..line_count {
             let text = buffer.line_text(line);
-            let layout = layout_line(&text, wrap_mode, wrap_width, tab_stop);
+            let layout = layout_line(
+                &text,
+                wrap_mode,
+                wrap_width,
+                tab_stop,
+                self.breaker.as_ref(),
+            );
             self.lines.push(layout);
         }
         self.rebuild_offsets();
@@ -257,13 +270,19 @@ impl LayoutCache {
         let new_end = start.saturating_add(change.new_line_count);
         for line in start..new_end {
             let text = buffer.line_text(line);
-            let layout = layout_line(&text, wrap_mode, wrap_width, tab_stop);
+            let layout = layout_line(
+                &text,
+                wrap_mode,
+                wrap_width,
+                tab_stop,
+                self.breaker.as_ref(),
+            );
             replacement.push(layout);
         }
         self.lines.splice(start..end, replacement);
         if self.lines.is_empty() {
             self.lines
-                .push(layout_line("", wrap_mode, wrap_width, tab_stop));
+                .push(layout_line("", wrap_mode, wrap_width, tab_stop, None));
         }
         self.rebuild_offsets();
     }
@@ -291,12 +310,14 @@ impl Default for LayoutCache {
     }
 }
 
-/// Build layout segments for a single logical line.
+/// Build layout segments for a single logical line. Soft-wrapped segments end at the
+/// last break point allowed by `breaker`, or at any character without one.
 pub fn layout_line(
     text: &str,
     wrap_mode: WrapMode,
     wrap_width: usize,
     tab_stop: usize,
+    breaker: Option<&WordBreaker>,
 ) -> LineLayout {
     let wrap_width = wrap_width.max(1);
     let mut segments = Vec::new();
@@ -304,6 +325,7 @@ pub fn layout_line(
     let mut char_index = 0usize;
     let mut seg_start_char = 0usize;
     let mut seg_start_col = 0usize;
+    let mut last_break: Option<(usize, usize)> = None;
 
     if text.is_empty() {
         segments.push(WrapSegment {
@@ -329,19 +351,35 @@ pub fn layout_line(
         if wrap_mode == WrapMode::Soft {
             let seg_width = col.saturating_sub(seg_start_col);
             if seg_width > 0 && seg_width.saturating_add(width) > wrap_width {
-                segments.push(WrapSegment {
-                    start_char: seg_start_char,
-                    end_char: char_index,
-                    start_col: seg_start_col,
-                    end_col: col,
-                });
-                seg_start_char = char_index;
-                seg_start_col = col;
+                if let Some((break_char, break_col)) = last_break.take() {
+                    segments.push(WrapSegment {
+                        start_char: seg_start_char,
+                        end_char: break_char,
+                        start_col: seg_start_col,
+                        end_col: break_col,
+                    });
+                    seg_start_char = break_char;
+                    seg_start_col = break_col;
+                }
+                let seg_width = col.saturating_sub(seg_start_col);
+                if seg_width > 0 && seg_width.saturating_add(width) > wrap_width {
+                    segments.push(WrapSegment {
+                        start_char: seg_start_char,
+                        end_char: char_index,
+                        start_col: seg_start_col,
+                        end_col: col,
+                    });
+                    seg_start_char = char_index;
+                    seg_start_col = col;
+                }
             }
         }
 
         col = col.saturating_add(width);
         char_index = char_index.saturating_add(grapheme_chars);
+        if breaker.is_some_and(|b| b.breaks_after(grapheme)) {
+            last_break = Some((char_index, col));
+        }
     }
 
     segments.push(WrapSegment {
@@ -364,16 +402,31 @@ mod tests {
     #[test]
     fn wrap_layout_splits_lines() {
         let line = "hello";
-        let layout = layout_line(line, WrapMode::Soft, 2, 4);
+        let layout = layout_line(line, WrapMode::Soft, 2, 4, None);
         assert_eq!(layout.segments.len(), 3);
         assert_eq!(layout.display_width, 5);
     }
 
+    #[test]
+    fn word_breaker_wraps_at_break_points() {
+        let breaker = WordBreaker::after("/");
+        let layout = layout_line("a/bcd efgh", WrapMode::Soft, 5, 4, Some(&breaker));
+        let ends: Vec<_> = layout.segments.iter().map(|s| s.end_char).collect();
+        assert_eq!(ends, [2, 6, 10]);
+
+        let mut buffer = TextBuffer::new("a/bcd efgh");
+        let mut cache = LayoutCache::new();
+        cache.sync(&mut buffer, 5, WrapMode::Soft, 4, None);
+        assert_eq!(cache.total_lines(), 2);
+        cache.sync(&mut buffer, 5, WrapMode::Soft, 4, Some(&breaker));
+        assert_eq!(cache.total_lines(), 3);
+    }
+
     #[test]
     fn mapping_roundtrip() {
         let mut buffer = TextBuffer::new("a\tb");
         let mut cache = LayoutCache::new();
-        cache.sync(&mut buffer, 10, WrapMode::Soft, 4);
+        cache.sync(&mut buffer, 10, WrapMode::Soft, 4, None);
         let pos = TextPosition::new(0, 2);
         let point = cache.point_for_position(&buffer, pos, 4);
         let back = cache.position_for_point(&buffer, point, 4);
@@ -384,18 +437,36 @@ mod tests {
     fn line_for_display_accounts_for_wrapping() {
         let mut buffer = TextBuffer::new("aa\naaa");
         let mut cache = LayoutCache::new();
-        cache.sync(&mut buffer, 2, WrapMode::Soft, 4);
+        cache.sync(&mut buffer, 2, WrapMode::Soft, 4, None);
         assert_eq!(cache.total_lines(), 3);
         assert_eq!(cache.line_for_display(0), 0);
         assert_eq!(cache.line_for_display(1), 1);
         assert_eq!(cache.line_for_display(2), 1);
     }
 
+    #[test]
+    fn wide_graphemes_wrap_whole_and_map_back() {
+        let mut buffer = TextBuffer::new("a界b");
+        let mut cache = LayoutCache::new();
+        cache.sync(&mut buffer, 2, WrapMode::Soft, 4, None);
+        let layout = cache.line(0).expect("line layout");
+        assert_eq!(layout.segments.len(), 3);
+        assert_eq!(layout.segments[1].start_char, 1);
+        assert_eq!(layout.segments[1].end_char, 2);
+
+        let pos = TextPosition::new(0, 1);
+        let point = cache.point_for_position(&buffer, pos, 4);
+        assert_eq!(point, Point { x: 0, y: 1 });
+        assert_eq!(cache.position_for_point(&buffer, point, 4), pos);
+        let inside_wide = cache.position_for_point(&buffer, Point { x: 1, y: 1 }, 4);
+        assert_eq!(inside_wide, pos);
+    }
+
     #[test]
     fn position_for_point_clamps_to_segment() {
         let mut buffer = TextBuffer::new("hello");
         let mut cache = LayoutCache::new();
-        cache.sync(&mut buffer, 3, WrapMode::Soft, 4);
+        cache.sync(&mut buffer, 3, WrapMode::Soft, 4, None);
         let point = Point { x: 10, y: 0 };
         let pos = cache.position_for_point(&buffer, point, 4);
         assert_eq!(pos, TextPosition::new(0, 3));
@@ -0,0 +1,759 @@
+//! A [`LspClient`] runs a language server as a child process. What the server reports
+//! about a document is sent as an [`LspEvent`] message to the editor node registered for
+//! it with [`LspClient::did_open`], and the [`Editor`](super::Editor) shows it:
+//! diagnostics become gutter signs and underlines, hover text is shown next to the
+//! cursor, and completions open the completion popup.
+//!
+//! Messages are framed by `lsp-server` and typed by `lsp-types`. A writer thread owns
+//! the server's standard input and a reader thread its standard output, so a server that
+//! stops reading never blocks the reader or the app.
+//!
+//! The client asks for UTF-32 positions, which match editor character columns. When the
+//! server answers that it counts UTF-16 code units instead, columns are converted using
+//! the document text sent with [`LspClient::did_open`] and [`LspClient::did_change`].
+
+use std::{
+    collections::HashMap,
+    io::{self, BufReader},
+    process::{self, Child, ChildStdin, ChildStdout, Command, Stdio},
+    sync::{
+        Arc, Mutex, OnceLock,
+        atomic::{AtomicI32, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+use canopy::{MessageSender, error::Result};
+use lsp_server::{
+    Message, Notification as RpcNotification, Request as RpcRequest, RequestId, Response,
+};
+pub use lsp_types::Uri;
+use lsp_types::{
+    ClientCapabilities, CompletionClientCapabilities, CompletionItemCapability, CompletionParams,
+    CompletionResponse, CompletionTextEdit, DiagnosticSeverity, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, GeneralClientCapabilities, Hover,
+    HoverClientCapabilities, HoverContents, HoverParams, InitializeParams, InitializeResult,
+    InitializedParams, MarkedString, MarkupKind, Position, PositionEncodingKind,
+    PublishDiagnosticsParams, TextDocumentClientCapabilities, TextDocumentContentChangeEvent,
+    TextDocumentIdentifier, TextDocumentItem, TextDocumentPositionParams,
+    VersionedTextDocumentIdentifier, WorkspaceFolder,
+    notification::{self, Notification},
+    request::{self, Request},
+};
+
+use super::{Completion, Diagnostic, Severity, TextPosition, TextRange};
+
+/// Language server output about a document, sent to the editor node showing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LspEvent {
+    /// The server published diagnostics for the document, replacing earlier ones.
+    Diagnostics(Vec<Diagnostic>),
+    /// Response to [`LspClient::hover`]. `None` when the server has nothing to show.
+    Hover(Option<String>),
+    /// Response to [`LspClient::completion`].
+    Completions(Vec<Completion>),
+}
+
+/// A request awaiting its response.
+enum Pending {
+    /// `initialize`, answered with the server's capabilities.
+    Initialize(mpsc::Sender<InitializeResult>),
+    /// `textDocument/hover` for a document.
+    Hover(Uri),
+    /// `textDocument/completion` for a document.
+    Completion(Uri),
+    /// `shutdown`, signalled once the server answers.
+    Shutdown(mpsc::Sender<()>),
+}
+
+/// How the server counts position columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    /// UTF-16 code units, the protocol default.
+    Utf16,
+    /// Characters, matching editor columns.
+    Utf32,
+}
+
+impl Encoding {
+    /// Convert an editor character column on `line` to a protocol column. Without the
+    /// line text the column is passed through.
+    fn protocol_column(self, line: Option<&str>, column: usize) -> usize {
+        match (self, line) {
+            (Self::Utf16, Some(line)) => line.chars().take(column).map(char::len_utf16).sum(),
+            _ => column,
+        }
+    }
+
+    /// Convert a protocol column on `line` to an editor character column. A column inside
+    /// a surrogate pair rounds up to the next character.
+    fn editor_column(self, line: Option<&str>, column: usize) -> usize {
+        let (Self::Utf16, Some(line)) = (self, line) else {
+            return column;
+        };
+        let mut units = 0;
+        line.chars()
+            .take_while(|c| {
+                let inside = units < column;
+                units += c.len_utf16();
+                inside
+            })
+            .count()
+    }
+}
+
+/// A document open in an editor.
+struct Document {
+    /// Editor node showing the document.
+    editor: MessageSender,
+    /// Lines of the latest text sent to the server, for converting columns.
+    lines: Vec<String>,
+}
+
+impl Document {
+    /// Replace the document's text.
+    fn set_text(&mut self, text: &str) {
+        self.lines = text.lines().map(str::to_string).collect();
+    }
+}
+
+/// Connection state shared by clones of a client and the reader thread.
+struct Connection {
+    /// Messages queued for the writer thread.
+    outgoing: mpsc::Sender<Message>,
+    /// Identifier of the next request.
+    next_id: AtomicI32,
+    /// Requests whose responses the reader handles.
+    pending: Mutex<HashMap<RequestId, Pending>>,
+    /// Open documents.
+    documents: Mutex<HashMap<Uri, Document>>,
+    /// Column encoding the server chose when it initialized.
+    encoding: OnceLock<Encoding>,
+}
+
+impl Connection {
+    /// Queue a message for the writer thread.
+    fn send(&self, message: Message) -> io::Result<()> {
+        self.outgoing
+            .send(message)
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))
+    }
+
+    /// Send a request, remembering `pending` so its response can be handled.
+    fn request<R: Request>(&self, params: R::Params, pending: Option<Pending>) -> io::Result<()> {
+        let id = RequestId::from(self.next_id.fetch_add(1, Ordering::Relaxed));
+        if let Some(pending) = pending {
+            self.pending.lock().unwrap().insert(id.clone(), pending);
+        }
+        self.send(RpcRequest::new(id, R::METHOD.to_string(), params).into())
+    }
+
+    /// Send a notification.
+    fn notify<N: Notification>(&self, params: N::Params) -> io::Result<()> {
+        self.send(RpcNotification::new(N::METHOD.to_string(), params).into())
+    }
+
+    /// Return the column encoding, UTF-16 until the server has initialized.
+    fn encoding(&self) -> Encoding {
+        self.encoding.get().copied().unwrap_or(Encoding::Utf16)
+    }
+
+    /// Convert an editor position in `uri` to a protocol position.
+    fn protocol_position(&self, uri: &Uri, position: TextPosition) -> Position {
+        let documents = self.documents.lock().unwrap();
+        let line = documents
+            .get(uri)
+            .and_then(|document| document.lines.get(position.line));
+        let column = self
+            .encoding()
+            .protocol_column(line.map(String::as_str), position.column);
+        Position::new(
+            u32::try_from(position.line).unwrap_or(u32::MAX),
+            u32::try_from(column).unwrap_or(u32::MAX),
+        )
+    }
+
+    /// Convert a protocol position in `uri` to an editor position.
+    fn text_position(&self, uri: &Uri, position: Position) -> TextPosition {
+        let documents = self.documents.lock().unwrap();
+        let line = documents
+            .get(uri)
+            .and_then(|document| document.lines.get(position.line as usize));
+        let column = self
+            .encoding()
+            .editor_column(line.map(String::as_str), position.character as usize);
+        TextPosition::new(position.line as usize, column)
+    }
+
+    /// Parameters naming a document and a position in it.
+    fn position_params(&self, uri: &Uri, position: TextPosition) -> TextDocumentPositionParams {
+        TextDocumentPositionParams::new(
+            TextDocumentIdentifier::new(uri.clone()),
+            self.protocol_position(uri, position),
+        )
+    }
+
+    /// Handle server output until it closes. Pending requests are dropped when it does,
+    /// which releases a caller waiting in [`LspClient::shutdown`].
+    fn read_loop(&self, stdout: ChildStdout) {
+        let mut stdout = BufReader::new(stdout);
+        while let Ok(Some(message)) = Message::read(&mut stdout) {
+            if self.handle(message).is_err() {
+                break;
+            }
+        }
+        self.pending.lock().unwrap().clear();
+    }
+
+    /// Handle one message from the server. Requests from the server get an empty result
+    /// so it does not wait on features the client lacks. Fails once the app or the
+    /// writer has gone away.
+    fn handle(&self, message: Message) -> Result<()> {
+        match message {
+            Message::Request(request) => {
+                let reply = Response::new_ok(request.id, ());
+                Ok(self.send(reply.into())?)
+            }
+            Message::Notification(notice) => {
+                let Ok(params) = notice
+                    .extract::<PublishDiagnosticsParams>(notification::PublishDiagnostics::METHOD)
+                else {
+                    return Ok(());
+                };
+                let diagnostics = params
+                    .diagnostics
+                    .iter()
+                    .map(|value| {
+                        diagnostic(value, |position| self.text_position(&params.uri, position))
+                    })
+                    .collect();
+                self.deliver(&params.uri, LspEvent::Diagnostics(diagnostics))
+            }
+            Message::Response(response) => {
+                let Some(pending) = self.pending.lock().unwrap().remove(&response.id) else {
+                    return Ok(());
+                };
+                let result = response.result.unwrap_or_default();
+                match pending {
+                    Pending::Initialize(done) => {
+                        if let Ok(result) = serde_json::from_value::<InitializeResult>(result) {
+                            let _ignored = done.send(result);
+                        }
+                        Ok(())
+                    }
+                    Pending::Hover(uri) => {
+                        let hover = serde_json::from_value::<Option<Hover>>(result).ok();
+                        let text = hover.flatten().and_then(|hover| hover_text(&hover));
+                        self.deliver(&uri, LspEvent::Hover(text))
+                    }
+                    Pending::Completion(uri) => {
+                        let items =
+                            serde_json::from_value::<Option<CompletionResponse>>(result).ok();
+                        let items = items.flatten().map(completion_items).unwrap_or_default();
+                        self.deliver(&uri, LspEvent::Completions(items))
+                    }
+                    Pending::Shutdown(done) => {
+                        let _ignored = done.send(());
+                        Ok(())
+                    }
+                }
+            }
+        }
+    }
+
+    /// Send `event` to the editor showing `uri`, if the document is open.
+    fn deliver(&self, uri: &Uri, event: LspEvent) -> Result<()> {
+        let editor = self
+            .documents
+            .lock()
+            .unwrap()
+            .get(uri)
+            .map(|document| document.editor.clone());
+        match editor {
+            Some(editor) => editor.send(event),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Write queued messages to the server until the client is dropped or the pipe closes.
+fn write_loop(mut stdin: ChildStdin, outgoing: &mpsc::Receiver<Message>) {
+    for message in outgoing {
+        if message.write(&mut stdin).is_err() {
+            return;
+        }
+    }
+}
+
+/// The server process, killed when the last client clone is dropped unless it has
+/// already exited.
+struct Server {
+    /// Child process.
+    child: Mutex<Child>,
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let Ok(child) = self.child.get_mut() else {
+            return;
+        };
+        if matches!(child.try_wait(), Ok(None)) {
+            let _ignored = child.kill();
+            let _ignored = child.wait();
+        }
+    }
+}
+
+/// A connection to a language server process. Clones share the connection, and the
+/// server is killed when the last clone is dropped without [`LspClient::shutdown`].
+#[derive(Clone)]
+pub struct LspClient {
+    /// Shared connection state.
+    connection: Arc<Connection>,
+    /// Server process.
+    server: Arc<Server>,
+}
+
+impl LspClient {
+    /// Start a server with `command` and initialize it for the workspace at `root`.
+    ///
+    /// Blocks until the server answers the `initialize` request, since nothing else may
+    /// be sent before then. Fails if the server exits or rejects initialization.
+    pub fn spawn(mut command: Command, root: Uri) -> io::Result<Self> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(io::Error::other("language server pipes unavailable"));
+        };
+        let server = Arc::new(Server {
+            child: Mutex::new(child),
+        });
+        let (outgoing, queue) = mpsc::channel();
+        let connection = Arc::new(Connection {
+            outgoing,
+            next_id: AtomicI32::new(1),
+            pending: Mutex::new(HashMap::new()),
+            documents: Mutex::new(HashMap::new()),
+            encoding: OnceLock::new(),
+        });
+        thread::spawn(move || write_loop(stdin, &queue));
+        let reader = Arc::clone(&connection);
+        thread::spawn(move || reader.read_loop(stdout));
+
+        let name = root
+            .as_str()
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let (done, answered) = mpsc::channel();
+        connection.request::<request::Initialize>(
+            InitializeParams {
+                process_id: Some(process::id()),
+                workspace_folders: Some(vec![WorkspaceFolder { uri: root, name }]),
+                capabilities: capabilities(),
+                ..InitializeParams::default()
+            },
+            Some(Pending::Initialize(done)),
+        )?;
+        let initialized = answered
+            .recv()
+            .map_err(|_| io::Error::other("language server did not initialize"))?;
+        let encoding = match initialized.capabilities.position_encoding {
+            Some(kind) if kind == PositionEncodingKind::UTF32 => Encoding::Utf32,
+            _ => Encoding::Utf16,
+        };
+        let _ignored = connection.encoding.set(encoding);
+        connection.notify::<notification::Initialized>(InitializedParams {})?;
+        Ok(Self { connection, server })
+    }
+
+    /// Tell the server that `uri` is open with `text`, and send what it reports about
+    /// the document to `editor`, usually from `Canopy::message_sender(editor_id)`.
+    pub fn did_open(
+        &self,
+        uri: &Uri,
+        language_id: &str,
+        version: u64,
+        text: &str,
+        editor: MessageSender,
+    ) -> io::Result<()> {
+        let mut document = Document {
+            editor,
+            lines: Vec::new(),
+        };
+        document.set_text(text);
+        self.connection
+            .documents
+            .lock()
+            .unwrap()
+            .insert(uri.clone(), document);
+        self.connection
+            .notify::<notification::DidOpenTextDocument>(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem::new(
+                    uri.clone(),
+                    language_id.to_string(),
+                    protocol_version(version),
+                    text.to_string(),
+                ),
+            })
+    }
+
+    /// Send the full text of `uri` after an edit. `Editor::revision` is a suitable
+    /// version.
+    pub fn did_change(&self, uri: &Uri, version: u64, text: &str) -> io::Result<()> {
+        if let Some(document) = self.connection.documents.lock().unwrap().get_mut(uri) {
+            document.set_text(text);
+        }
+        self.connection
+            .notify::<notification::DidChangeTextDocument>(DidChangeTextDocumentParams {
+                text_document: VersionedTextDocumentIdentifier::new(
+                    uri.clone(),
+                    protocol_version(version),
+                ),
+                content_changes: vec![TextDocumentContentChangeEvent {
+                    range: None,
+                    range_length: None,
+                    text: text.to_string(),
+                }],
+            })
+    }
+
+    /// Tell the server that `uri` was closed, and stop sending its reports to the editor.
+    pub fn did_close(&self, uri: &Uri) -> io::Result<()> {
+        self.connection.documents.lock().unwrap().remove(uri);
+        self.connection
+            .notify::<notification::DidCloseTextDocument>(DidCloseTextDocumentParams {
+                text_document: TextDocumentIdentifier::new(uri.clone()),
+            })
+    }
+
+    /// Ask for hover text at `position`. The answer arrives as [`LspEvent::Hover`].
+    pub fn hover(&self, uri: &Uri, position: TextPosition) -> io::Result<()> {
+        self.connection.request::<request::HoverRequest>(
+            HoverParams {
+                text_document_position_params: self.connection.position_params(uri, position),
+                work_done_progress_params: Default::default(),
+            },
+            Some(Pending::Hover(uri.clone())),
+        )
+    }
+
+    /// Ask for completions at `position`. The answer arrives as
+    /// [`LspEvent::Completions`].
+    pub fn completion(&self, uri: &Uri, position: TextPosition) -> io::Result<()> {
+        self.connection.request::<request::Completion>(
+            CompletionParams {
+                text_document_position: self.connection.position_params(uri, position),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+                context: None,
+            },
+            Some(Pending::Completion(uri.clone())),
+        )
+    }
+
+    /// Ask the server to shut down, wait for its answer, then tell it to exit and wait
+    /// for the process to end.
+    ///
+    /// This blocks until the server answers and exits, so a hung server blocks the
+    /// caller. To stop a server without waiting, drop every clone of the client instead,
+    /// which kills the process.
+    pub fn shutdown(&self) -> io::Result<()> {
+        let (done, answered) = mpsc::channel();
+        self.connection
+            .request::<request::Shutdown>((), Some(Pending::Shutdown(done)))?;
+        answered
+            .recv()
+            .map_err(|_| io::Error::from(io::ErrorKind::UnexpectedEof))?;
+        self.connection.notify::<notification::Exit>(())?;
+        self.server.child.lock().unwrap().wait()?;
+        Ok(())
+    }
+}
+
+/// Capabilities announced to the server.
+fn capabilities() -> ClientCapabilities {
+    ClientCapabilities {
+        general: Some(GeneralClientCapabilities {
+            position_encodings: Some(vec![PositionEncodingKind::UTF32]),
+            ..GeneralClientCapabilities::default()
+        }),
+        text_document: Some(TextDocumentClientCapabilities {
+            hover: Some(HoverClientCapabilities {
+                content_format: Some(vec![MarkupKind::PlainText, MarkupKind::Markdown]),
+                ..HoverClientCapabilities::default()
+            }),
+            completion: Some(CompletionClientCapabilities {
+                completion_item: Some(CompletionItemCapability {
+                    snippet_support: Some(false),
+                    ..CompletionItemCapability::default()
+                }),
+                ..CompletionClientCapabilities::default()
+            }),
+            publish_diagnostics: Some(Default::default()),
+            ..TextDocumentClientCapabilities::default()
+        }),
+        ..ClientCapabilities::default()
+    }
+}
+
+/// Convert an editor revision to a protocol document version, saturating at the
+/// largest version the protocol allows.
+fn protocol_version(version: u64) -> i32 {
+    i32::try_from(version).unwrap_or(i32::MAX)
+}
+
+/// Convert a protocol diagnostic, mapping positions with `position`. A missing severity
+/// counts as an error.
+fn diagnostic(
+    value: &lsp_types::Diagnostic,
+    position: impl Fn(Position) -> TextPosition,
+) -> Diagnostic {
+    let range = TextRange::new(position(value.range.start), position(value.range.end));
+    let severity = match value.severity {
+        Some(DiagnosticSeverity::WARNING) => Severity::Warning,
+        Some(DiagnosticSeverity::INFORMATION) => Severity::Info,
+        Some(DiagnosticSeverity::HINT) => Severity::Hint,
+        _ => Severity::Error,
+    };
+    Diagnostic::new(range, severity, &value.message)
+}
+
+/// Text of a marked string.
+fn marked_text(marked: &MarkedString) -> &str {
+    match marked {
+        MarkedString::String(text) => text,
+        MarkedString::LanguageString(code) => &code.value,
+    }
+}
+
+/// Plain text of a hover result, without code fences or blank lines.
+fn hover_text(hover: &Hover) -> Option<String> {
+    let parts: Vec<&str> = match &hover.contents {
+        HoverContents::Scalar(marked) => vec![marked_text(marked)],
+        HoverContents::Array(items) => items.iter().map(marked_text).collect(),
+        HoverContents::Markup(markup) => vec![&markup.value],
+    };
+    let lines: Vec<&str> = parts
+        .iter()
+        .flat_map(|part| part.lines())
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty() && !line.starts_with("```"))
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// Candidates in a completion result.
+fn completion_items(response: CompletionResponse) -> Vec<Completion> {
+    let items = match response {
+        CompletionResponse::Array(items) => items,
+        CompletionResponse::List(list) => list.items,
+    };
+    items
+        .into_iter()
+        .map(|item| {
+            let text = item
+                .insert_text
+                .or_else(|| {
+                    item.text_edit.map(|edit| match edit {
+                        CompletionTextEdit::Edit(edit) => edit.new_text,
+                        CompletionTextEdit::InsertAndReplace(edit) => edit.new_text,
+                    })
+                })
+                .unwrap_or(item.label);
+            let completion = Completion::new(text);
+            match item.detail {
+                Some(detail) => completion.with_detail(detail),
+                None => completion,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use canopy::Canopy;
+    use lsp_types::{CompletionItem, CompletionList, MarkupContent, Range, ServerCapabilities};
+
+    use super::*;
+
+    fn connection() -> (Connection, mpsc::Receiver<Message>) {
+        let (outgoing, queue) = mpsc::channel();
+        let connection = Connection {
+            outgoing,
+            next_id: AtomicI32::new(1),
+            pending: Mutex::new(HashMap::new()),
+            documents: Mutex::new(HashMap::new()),
+            encoding: OnceLock::new(),
+        };
+        (connection, queue)
+    }
+
+    #[test]
+    fn server_requests_are_answered_through_the_writer() {
+        let (connection, queue) = connection();
+        let query = RpcRequest::new(7.into(), "workspace/configuration".to_string(), ());
+        connection.handle(query.into()).unwrap();
+        let Ok(Message::Response(reply)) = queue.try_recv() else {
+            panic!("expected a reply");
+        };
+        assert_eq!(reply.id, RequestId::from(7));
+
+        let (done, answered) = mpsc::channel();
+        connection
+            .request::<request::Shutdown>((), Some(Pending::Shutdown(done)))
+            .unwrap();
+        let Ok(Message::Request(shutdown)) = queue.try_recv() else {
+            panic!("expected the shutdown request");
+        };
+        assert!(answered.try_recv().is_err());
+        connection
+            .handle(Response::new_ok(shutdown.id, ()).into())
+            .unwrap();
+        assert!(answered.try_recv().is_ok());
+    }
+
+    #[test]
+    fn initialize_results_are_kept() {
+        let (connection, queue) = connection();
+        let (done, answered) = mpsc::channel();
+        connection
+            .request::<request::Initialize>(
+                InitializeParams::default(),
+                Some(Pending::Initialize(done)),
+            )
+            .unwrap();
+        let Ok(Message::Request(initialize)) = queue.try_recv() else {
+            panic!("expected the initialize request");
+        };
+        let result = InitializeResult {
+            capabilities: ServerCapabilities {
+                position_encoding: Some(PositionEncodingKind::UTF16),
+                ..ServerCapabilities::default()
+            },
+            ..InitializeResult::default()
+        };
+        connection
+            .handle(Response::new_ok(initialize.id, result).into())
+            .unwrap();
+        let kept = answered.try_recv().unwrap();
+        assert_eq!(
+            kept.capabilities.position_encoding,
+            Some(PositionEncodingKind::UTF16)
+        );
+    }
+
+    #[test]
+    fn utf16_columns_convert_through_document_text() {
+        let (connection, _queue) = connection();
+        let uri = Uri::from_str("file:///a.rs").unwrap();
+        let canopy = Canopy::new();
+        let mut document = Document {
+            editor: canopy.message_sender(canopy.root_id()),
+            lines: Vec::new(),
+        };
+        document.set_text("x\na\u{1F600}b\n");
+        connection
+            .documents
+            .lock()
+            .unwrap()
+            .insert(uri.clone(), document);
+
+        let emoji_end = TextPosition::new(1, 2);
+        assert_eq!(Encoding::Utf32.protocol_column(Some("a\u{1F600}b"), 2), 2);
+        assert_eq!(
+            connection.protocol_position(&uri, emoji_end),
+            Position::new(1, 3)
+        );
+        assert_eq!(
+            connection.text_position(&uri, Position::new(1, 3)),
+            emoji_end
+        );
+        assert_eq!(
+            connection.text_position(&uri, Position::new(1, 2)),
+            emoji_end
+        );
+        assert_eq!(
+            connection.text_position(&uri, Position::new(1, 9)),
+            TextPosition::new(1, 3)
+        );
+        let unknown = Uri::from_str("file:///b.rs").unwrap();
+        assert_eq!(
+            connection.text_position(&unknown, Position::new(1, 3)),
+            TextPosition::new(1, 3)
+        );
+    }
+
+    #[test]
+    fn results_translate_to_events() {
+        let uri = Uri::from_str("file:///a.rs").unwrap();
+        let published: PublishDiagnosticsParams = serde_json::from_value(serde_json::json!({
+            "uri": uri,
+            "diagnostics": [{
+                "range": {
+                    "start": { "line": 1, "character": 2 },
+                    "end": { "line": 1, "character": 5 },
+                },
+                "severity": 2,
+                "message": "unused",
+            }],
+        }))
+        .unwrap();
+        let position = |p: Position| TextPosition::new(p.line as usize, p.character as usize);
+        assert_eq!(
+            diagnostic(&published.diagnostics[0], position),
+            Diagnostic::new(
+                TextRange::new(TextPosition::new(1, 2), TextPosition::new(1, 5)),
+                Severity::Warning,
+                "unused",
+            )
+        );
+        let unrated = lsp_types::Diagnostic::new_simple(Range::default(), "bad".to_string());
+        assert_eq!(diagnostic(&unrated, position).severity, Severity::Error);
+
+        let hover = Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: "```rust\nfn main()\n```".to_string(),
+            }),
+            range: None,
+        };
+        assert_eq!(hover_text(&hover), Some("fn main()".to_string()));
+        let empty = Hover {
+            contents: HoverContents::Array(Vec::new()),
+            range: None,
+        };
+        assert_eq!(hover_text(&empty), None);
+
+        let completions = CompletionResponse::List(CompletionList {
+            is_incomplete: false,
+            items: vec![
+                CompletionItem {
+                    label: "push".to_string(),
+                    detail: Some("fn(&mut self, T)".to_string()),
+                    ..CompletionItem::default()
+                },
+                CompletionItem {
+                    label: "pop()".to_string(),
+                    insert_text: Some("pop".to_string()),
+                    ..CompletionItem::default()
+                },
+            ],
+        });
+        assert_eq!(
+            completion_items(completions),
+            [
+                Completion::new("push").with_detail("fn(&mut self, T)"),
+                Completion::new("pop"),
+            ]
+        );
+    }
+}
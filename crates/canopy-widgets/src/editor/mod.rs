@@ -2,14 +2,22 @@
 
 /// Text buffer implementation backed by a rope.
 pub(crate) mod buffer;
+/// Completion providers and popup state.
+mod complete;
 /// Editor movement and edit-session control state.
 mod controller;
+/// Diagnostics shown in the gutter and underlined in the text.
+mod diagnostic;
 /// Undo/redo edit definitions.
 mod edit;
 /// Syntax highlighting helpers.
 pub mod highlight;
 /// Layout and wrapping cache.
 mod layout;
+/// Language Server Protocol client that feeds diagnostics, hover text, and completions
+/// to an editor.
+#[cfg(feature = "lsp")]
+pub mod lsp;
 /// Text position and range types.
 pub(crate) mod position;
 /// Search state and match helpers.
@@ -25,12 +33,16 @@ mod view;
 /// Editor widget implementation.
 mod widget;
 
-pub use buffer::{LineChange, TextBuffer};
+pub use buffer::{LineChange, TextBuffer, TextChange};
+pub use complete::{Completion, CompletionProvider};
+pub use diagnostic::{Diagnostic, Severity};
 pub use position::{TextPosition, TextRange};
 pub use selection::Selection;
 pub use util::tab_width;
 pub use widget::Editor;
 
+use crate::text::WordBreaker;
+
 #[cfg(test)]
 mod tests;
 
@@ -66,7 +78,7 @@ pub enum LineNumbers {
 /// Configuration for the editor widget.
 #[derive(Debug, Clone)]
 pub struct EditorConfig {
-    /// Allow multi-line content.
+    /// Allow multi-line content. Single-line editors replace newlines with spaces.
     pub multiline: bool,
     /// Wrapping mode.
     pub wrap: WrapMode,
@@ -78,12 +90,17 @@ pub struct EditorConfig {
     pub max_height: Option<u32>,
     /// Edit mode behavior.
     pub mode: EditMode,
-    /// Whether the editor is read-only.
+    /// Whether the editor is read-only. Read-only editors still navigate, select, and
+    /// yank, but ignore every edit, including undo and redo.
     pub read_only: bool,
     /// Line number rendering mode.
     pub line_numbers: LineNumbers,
     /// Tab stop width in columns.
     pub tab_stop: usize,
+    /// Draw tabs and spaces with visible glyphs.
+    pub show_whitespace: bool,
+    /// Break points for soft wrapping. Without one, lines wrap at any character.
+    pub word_breaker: Option<WordBreaker>,
 }
 
 impl Default for EditorConfig {
@@ -98,6 +115,8 @@ impl Default for EditorConfig {
             read_only: false,
             line_numbers: LineNumbers::None,
             tab_stop: 4,
+            show_whitespace: false,
+            word_breaker: None,
         }
     }
 }
@@ -108,6 +127,13 @@ impl EditorConfig {
         Self::default()
     }
 
+    /// Construct a configuration for one-line text entry, as used by [`Input`](crate::Input)
+    /// and the command line. Newlines in inserted or pasted text become spaces, Enter, Up,
+    /// and Down are left to the host, and the line scrolls horizontally instead of wrapping.
+    pub fn single_line() -> Self {
+        Self::new().with_multiline(false).with_wrap(WrapMode::None)
+    }
+
     /// Configure multiline behavior.
     pub fn with_multiline(mut self, multiline: bool) -> Self {
         self.multiline = multiline;
@@ -161,4 +187,17 @@ impl EditorConfig {
         self.tab_stop = tab_stop.max(1);
         self
     }
+
+    /// Configure whitespace visualization.
+    pub fn with_show_whitespace(mut self, show_whitespace: bool) -> Self {
+        self.show_whitespace = show_whitespace;
+        self
+    }
+
+    /// Soft-wrap lines at the last break point `breaker` allows, falling back to any
+    /// character when a segment has none.
+    pub fn with_word_breaker(mut self, breaker: WordBreaker) -> Self {
+        self.word_breaker = Some(breaker);
+        self
+    }
 }
@@ -5,7 +5,7 @@ use std::cmp::Ordering;
 pub struct TextPosition {
     /// Logical line index (0-based).
     pub line: usize,
-    /// Char index within the line (0-based).
+    /// Char index within the line (0-based), on a grapheme cluster boundary for cursors.
     pub column: usize,
 }
 
@@ -1,6 +1,6 @@
 use canopy::geom::{Point, Rect};
 
-use super::{TextBuffer, WrapMode, layout::LayoutCache};
+use super::{EditorConfig, TextBuffer, TextPosition, layout::LayoutCache};
 
 /// Cached editor view state derived from layout and cursor position.
 #[derive(Debug, Clone)]
@@ -11,6 +11,8 @@ pub struct EditorView {
     pub(crate) cursor_point: Option<Point>,
     /// Cached cursor position in view coordinates.
     pub(crate) cursor_view_point: Option<Point>,
+    /// Cached position of the anchor of a non-empty selection in view coordinates.
+    pub(crate) anchor_view_point: Option<Point>,
 }
 
 impl EditorView {
@@ -20,6 +22,7 @@ impl EditorView {
             layout: LayoutCache::new(),
             cursor_point: None,
             cursor_view_point: None,
+            anchor_view_point: None,
         }
     }
 
@@ -30,24 +33,48 @@ impl EditorView {
         view_rect: Rect,
         gutter_width: u32,
         wrap_width: usize,
-        wrap: WrapMode,
-        tab_stop: usize,
+        config: &EditorConfig,
     ) {
-        self.layout.sync(buffer, wrap_width, wrap, tab_stop);
-        let cursor = buffer.cursor();
-        let point = self.layout.point_for_position(buffer, cursor, tab_stop);
-        let cursor_point = Point {
-            x: point.x.saturating_add(gutter_width),
-            y: point.y,
-        };
+        let tab_stop = config.tab_stop;
+        self.layout.sync(
+            buffer,
+            wrap_width,
+            config.wrap,
+            tab_stop,
+            config.word_breaker.as_ref(),
+        );
+        let selection = buffer.selection();
+        let cursor_point = self.content_point(buffer, selection.head(), gutter_width, tab_stop);
         self.cursor_point = Some(cursor_point);
-        self.cursor_view_point = if view_rect.contains_point(cursor_point) {
-            Some(Point {
-                x: cursor_point.x - view_rect.tl.x,
-                y: cursor_point.y - view_rect.tl.y,
-            })
-        } else {
+        self.cursor_view_point = view_point(view_rect, cursor_point);
+        self.anchor_view_point = if selection.is_empty() {
             None
+        } else {
+            let anchor = self.content_point(buffer, selection.anchor(), gutter_width, tab_stop);
+            view_point(view_rect, anchor)
         };
     }
+
+    /// Return the content point of a text position, offset past the gutter.
+    fn content_point(
+        &self,
+        buffer: &TextBuffer,
+        pos: TextPosition,
+        gutter_width: u32,
+        tab_stop: usize,
+    ) -> Point {
+        let point = self.layout.point_for_position(buffer, pos, tab_stop);
+        Point {
+            x: point.x.saturating_add(gutter_width),
+            y: point.y,
+        }
+    }
+}
+
+/// Translate a content point into view coordinates, if it is visible.
+fn view_point(view_rect: Rect, point: Point) -> Option<Point> {
+    view_rect.contains_point(point).then(|| Point {
+        x: point.x - view_rect.tl.x,
+        y: point.y - view_rect.tl.y,
+    })
 }
@@ -1,23 +1,34 @@
+#[cfg(feature = "lsp")]
+use std::any::Any;
 use std::{
-    collections::HashMap,
+    collections::BTreeMap,
+    ops::Range,
     time::{Duration, Instant},
 };
 
 use canopy::{
-    Context, EventOutcome, ReadContext, Widget, command, cursor, derive_commands,
+    Accessible, Context, EventOutcome, PersistentState, ReadContext, Role, Scrollable, UndoEntry,
+    Widget, command, cursor, derive_commands,
     error::Result,
     event::{Event, key, mouse},
     geom::{Direction, Line, Point, Rect},
     layout::{CanvasContext, Constraint, MeasureConstraints, Measurement, Size},
+    persist::{Value, from_state, to_state},
     render::Render,
     state::NodeName,
+    style::{ResolvedStyle, Style},
     text,
 };
 use unicode_segmentation::UnicodeSegmentation;
 
+#[cfg(feature = "lsp")]
+use super::lsp::LspEvent;
 use super::{
-    EditMode, EditorConfig, LineNumbers, Selection, TextBuffer, TextPosition, TextRange, WrapMode,
+    Diagnostic, EditMode, EditorConfig, LineNumbers, Selection, Severity, TextBuffer, TextChange,
+    TextPosition, TextRange, WrapMode,
+    complete::{Completion, CompletionMenu, CompletionProvider, MAX_VISIBLE},
     controller::EditorController,
+    diagnostic::{line_severity, severity_at},
     highlight::{HighlightSpan, Highlighter},
     layout::{WrapSegment, layout_line},
     search::{SearchDirection, SearchState, find_matches},
@@ -25,11 +36,16 @@ use super::{
     vi::{PendingKey, RepeatableEdit, ViMode, ViState, VisualMode},
     view::EditorView,
 };
+use crate::{spell::SpellCheck, text::WordBreaker};
 
 /// Maximum delay between clicks to count as multi-click selection.
 const DOUBLE_CLICK_MS: u64 = 500;
 /// Lines to scroll per mouse wheel tick within the editor.
 const WHEEL_SCROLL_LINES: i32 = 3;
+/// Glyph drawn in the first cell of a tab when whitespace is shown.
+const TAB_MARK: char = '\u{2192}';
+/// Glyph drawn for spaces and tab padding when whitespace is shown.
+const SPACE_MARK: char = '\u{b7}';
 
 /// Editor widget implementation.
 pub struct Editor {
@@ -57,8 +73,27 @@ pub struct Editor {
     highlighter: Option<Box<dyn Highlighter>>,
     /// Cached syntax highlight spans.
     highlight_cache: HighlightCache,
+    /// Buffer commit count already registered with the app undo history.
+    undo_mark: u64,
+    /// Callback run for every edit.
+    on_change: Option<ChangeHandler>,
+    /// Optional completion provider.
+    completer: Option<Box<dyn CompletionProvider>>,
+    /// Open completion popup.
+    completion: Option<CompletionMenu>,
+    /// Diagnostics marked in the gutter and underlined in the text.
+    diagnostics: Vec<Diagnostic>,
+    /// Hover text shown next to the cursor until the next key or mouse event.
+    hover: Option<String>,
+    /// Optional spell checker.
+    spell: Option<SpellCheck>,
+    /// Cached misspelled word ranges.
+    spell_cache: SpellCache,
 }
 
+/// Callback run with each edit made to the editor buffer.
+type ChangeHandler = Box<dyn FnMut(&TextChange) + Send>;
+
 /// Prompt modes for search and replace interactions.
 #[derive(Debug, Clone)]
 enum PromptState {
@@ -142,13 +177,13 @@ impl<'a, 'b> RenderLineContext<'a, 'b> {
     }
 }
 
-/// Cache of syntax highlight spans keyed by buffer revision and line index.
+/// Cache of syntax highlight spans for the first lines of the buffer at a revision.
 #[derive(Debug, Clone)]
 struct HighlightCache {
     /// Buffer revision the cache corresponds to.
     revision: u64,
-    /// Cached spans per line.
-    lines: HashMap<usize, Vec<HighlightSpan>>,
+    /// Cached spans for lines `0..lines.len()`.
+    lines: Vec<Vec<HighlightSpan>>,
 }
 
 impl HighlightCache {
@@ -156,7 +191,7 @@ impl HighlightCache {
     fn new() -> Self {
         Self {
             revision: 0,
-            lines: HashMap::new(),
+            lines: Vec::new(),
         }
     }
 
@@ -165,7 +200,61 @@ impl HighlightCache {
         self.lines.clear();
     }
 
-    /// Reset the cache when the buffer revision changes.
+    /// Drop spans from `line` onward after an edit that produced `revision`.
+    fn invalidate_from(&mut self, line: usize, revision: u64) {
+        self.lines.truncate(line);
+        self.revision = revision;
+    }
+
+    /// Reset the cache when the buffer changed without an invalidation.
+    fn sync_revision(&mut self, revision: u64) {
+        if self.revision != revision {
+            self.revision = revision;
+            self.lines.clear();
+        }
+    }
+
+    /// Highlight the uncached lines up to and including `last` as one chunk. The chunk
+    /// starts at the first uncached line, so it always follows lines already highlighted.
+    fn fill(&mut self, highlighter: &mut dyn Highlighter, buffer: &TextBuffer, last: usize) {
+        let start = self.lines.len();
+        if start > last {
+            return;
+        }
+        let chunk: Vec<String> = (start..=last).map(|line| buffer.line_text(line)).collect();
+        let mut spans = highlighter.highlight_chunk(start, &chunk, self.revision);
+        spans.resize_with(chunk.len(), Vec::new);
+        self.lines.extend(spans);
+    }
+
+    /// Return cached spans for a line.
+    fn spans(&self, line: usize) -> Vec<HighlightSpan> {
+        self.lines.get(line).cloned().unwrap_or_default()
+    }
+}
+
+/// Cache of misspelled word ranges by line at a buffer revision.
+#[derive(Debug, Clone, Default)]
+struct SpellCache {
+    /// Buffer revision the cache corresponds to.
+    revision: u64,
+    /// Misspelled character ranges of the lines checked so far.
+    lines: BTreeMap<usize, Vec<Range<usize>>>,
+}
+
+impl SpellCache {
+    /// Clear cached ranges.
+    fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    /// Drop ranges from `line` onward after an edit that produced `revision`.
+    fn invalidate_from(&mut self, line: usize, revision: u64) {
+        self.lines.split_off(&line);
+        self.revision = revision;
+    }
+
+    /// Reset the cache when the buffer changed without an invalidation.
     fn sync_revision(&mut self, revision: u64) {
         if self.revision != revision {
             self.revision = revision;
@@ -173,18 +262,17 @@ impl HighlightCache {
         }
     }
 
-    /// Return cached spans for a line or compute and store them.
-    fn spans_for_line(
+    /// Return the misspelled ranges of `line`, checking it if it is not cached.
+    fn misspelled(
         &mut self,
+        spell: &SpellCheck,
+        buffer: &TextBuffer,
         line: usize,
-        compute: impl FnOnce() -> Vec<HighlightSpan>,
-    ) -> Vec<HighlightSpan> {
-        if let Some(spans) = self.lines.get(&line) {
-            return spans.clone();
-        }
-        let spans = compute();
-        self.lines.insert(line, spans.clone());
-        spans
+    ) -> Vec<Range<usize>> {
+        self.lines
+            .entry(line)
+            .or_insert_with(|| spell.misspelled(&buffer.line_text(line)))
+            .clone()
     }
 }
 
@@ -197,7 +285,7 @@ impl Editor {
 
     /// Construct an editor with a configuration.
     pub fn with_config(text: impl Into<String>, config: EditorConfig) -> Self {
-        let mut buffer = TextBuffer::new(text);
+        let mut buffer = TextBuffer::new(flatten_lines(text.into(), config.multiline));
         buffer.set_cursor(TextPosition::new(0, 0));
         let controller = EditorController::new(&buffer, config.tab_stop);
         Self {
@@ -213,9 +301,43 @@ impl Editor {
             mouse: MouseState::new(),
             highlighter: None,
             highlight_cache: HighlightCache::new(),
+            undo_mark: 0,
+            on_change: None,
+            completer: None,
+            completion: None,
+            diagnostics: Vec::new(),
+            hover: None,
+            spell: None,
+            spell_cache: SpellCache::default(),
         }
     }
 
+    /// Build an editor that calls `f` with every edit, including undo and redo.
+    pub fn with_on_change(mut self, f: impl FnMut(&TextChange) + Send + 'static) -> Self {
+        self.set_on_change(f);
+        self
+    }
+
+    /// Call `f` with every edit, including undo and redo.
+    pub fn set_on_change(&mut self, f: impl FnMut(&TextChange) + Send + 'static) {
+        self.on_change = Some(Box::new(f));
+    }
+
+    /// Whether the text was edited since it was set or last marked clean.
+    pub fn is_dirty(&self) -> bool {
+        self.buffer.is_dirty()
+    }
+
+    /// Mark the current text as clean, for example after saving it.
+    pub fn mark_clean(&mut self) {
+        self.buffer.mark_clean();
+    }
+
+    /// Return the buffer revision, which increases with every edit.
+    pub fn revision(&self) -> u64 {
+        self.buffer.revision()
+    }
+
     /// Return the current editor configuration.
     pub fn config(&self) -> &EditorConfig {
         &self.config
@@ -223,7 +345,11 @@ impl Editor {
 
     /// Replace the editor configuration.
     pub fn set_config(&mut self, config: EditorConfig) {
+        let flatten = self.config.multiline && !config.multiline;
         self.config = config;
+        if flatten && self.buffer.line_count() > 1 {
+            self.set_text(self.buffer.text());
+        }
         self.update_preferred_column();
     }
 
@@ -232,12 +358,53 @@ impl Editor {
         self.buffer.text()
     }
 
-    /// Replace the buffer contents.
+    /// Replace the buffer contents. Single-line editors replace newlines with spaces.
     pub fn set_text(&mut self, text: impl Into<String>) {
-        self.buffer = TextBuffer::new(text);
+        self.buffer = TextBuffer::new(flatten_lines(text.into(), self.config.multiline));
         self.buffer.set_cursor(TextPosition::new(0, 0));
         self.update_preferred_column();
         self.highlight_cache.clear();
+        self.spell_cache.clear();
+        self.undo_mark = 0;
+    }
+
+    /// Return the number of logical lines.
+    pub fn line_count(&self) -> usize {
+        self.buffer.line_count()
+    }
+
+    /// Return the text of line `n` without its newline, or `None` past the last line.
+    pub fn line(&self, n: usize) -> Option<String> {
+        self.buffer.try_line_text(n)
+    }
+
+    /// Insert `text` at `pos`, leaving the cursor after it. Positions are clamped to the
+    /// buffer, and single-line editors replace newlines with spaces.
+    pub fn insert_at(&mut self, pos: TextPosition, text: &str) {
+        let text = self.normalize_insert_text(text);
+        self.buffer.replace_range(TextRange::new(pos, pos), &text);
+        self.update_preferred_column();
+        self.notify_changes();
+    }
+
+    /// Delete the text in `range`, leaving the cursor at its start. The range is clamped
+    /// to the buffer.
+    pub fn delete_range(&mut self, range: TextRange) {
+        self.buffer.replace_range(range, "");
+        self.update_preferred_column();
+        self.notify_changes();
+    }
+
+    /// Return the cursor position.
+    pub fn cursor_position(&self) -> TextPosition {
+        self.buffer.cursor()
+    }
+
+    /// Move the cursor to `pos`, clearing any selection. The position is clamped to the
+    /// buffer and snapped to a grapheme boundary.
+    pub fn set_cursor_position(&mut self, pos: TextPosition) {
+        self.buffer.set_selection(Selection::caret(pos));
+        self.update_preferred_column();
     }
 
     /// Return the current selection.
@@ -251,6 +418,57 @@ impl Editor {
         self.highlight_cache.clear();
     }
 
+    /// Install a completion provider, consulted when Tab is pressed while entering text.
+    pub fn set_completer(&mut self, completer: Option<Box<dyn CompletionProvider>>) {
+        self.completer = completer;
+        self.completion = None;
+    }
+
+    /// Install a spell checker. Misspelled words are underlined with the
+    /// `editor/misspelled` style over their text style, and `spell_suggest` offers
+    /// corrections in the completion popup.
+    pub fn set_spell_check(&mut self, spell: Option<SpellCheck>) {
+        self.spell = spell;
+        self.spell_cache.clear();
+    }
+
+    /// Return the candidates in the open completion popup.
+    pub fn completions(&self) -> &[Completion] {
+        self.completion
+            .as_ref()
+            .map_or(&[], |menu| menu.items.as_slice())
+    }
+
+    /// Open the completion popup with `items`, replacing the word before the cursor when
+    /// one is accepted. Candidates computed elsewhere, such as by a language server, are
+    /// shown this way; typing filters them by prefix.
+    pub fn show_completions(&mut self, items: Vec<Completion>) {
+        let (start, _, _) = self.completion_prefix();
+        self.completion =
+            (self.entering_text() && !items.is_empty()).then(|| CompletionMenu::new(start, items));
+    }
+
+    /// Replace the diagnostics shown by the editor. Lines with a diagnostic get a sign in
+    /// the gutter, and the text each one covers is underlined.
+    pub fn set_diagnostics(&mut self, diagnostics: Vec<Diagnostic>) {
+        self.diagnostics = diagnostics;
+    }
+
+    /// Return the diagnostics shown by the editor.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Show `text` next to the cursor until the next key or mouse event.
+    pub fn set_hover(&mut self, text: Option<String>) {
+        self.hover = text.filter(|text| !text.is_empty());
+    }
+
+    /// Return the hover text currently shown.
+    pub fn hover(&self) -> Option<&str> {
+        self.hover.as_deref()
+    }
+
     /// Return a reference to the internal buffer.
     #[cfg(test)]
     pub(crate) fn buffer(&self) -> &TextBuffer {
@@ -263,15 +481,21 @@ impl Editor {
         available as usize
     }
 
-    /// Compute the line-number gutter width.
+    /// Width of the diagnostic sign column, shown while there are diagnostics.
+    fn sign_width(&self) -> u32 {
+        u32::from(!self.diagnostics.is_empty())
+    }
+
+    /// Compute the gutter width, covering the sign column and line numbers.
     fn gutter_width(&self) -> u32 {
-        match self.config.line_numbers {
+        let numbers = match self.config.line_numbers {
             LineNumbers::None => 0,
             LineNumbers::Absolute | LineNumbers::Relative => {
                 let digits = self.buffer.line_count().max(1).to_string().len() as u32;
                 digits.saturating_add(1)
             }
-        }
+        };
+        self.sign_width().saturating_add(numbers)
     }
 
     /// Synchronize layout and cached cursor position.
@@ -282,13 +506,12 @@ impl Editor {
             view_rect,
             gutter_width,
             wrap_width,
-            self.config.wrap,
-            self.config.tab_stop,
+            &self.config,
         );
     }
 
     /// Ensure the cursor is visible within the current scroll view.
-    fn ensure_cursor_visible(&mut self, ctx: &mut dyn Context) {
+    pub(crate) fn ensure_cursor_visible(&mut self, ctx: &mut dyn Context) {
         let view = ctx.view();
         let view_rect = view.view_rect();
         let gutter_width = self.gutter_width();
@@ -297,29 +520,13 @@ impl Editor {
         let Some(cursor) = self.view.cursor_point else {
             return;
         };
-        let cursor_x = cursor.x;
-        let cursor_y = cursor.y;
-
-        let mut target_x = view_rect.tl.x;
-        let mut target_y = view_rect.tl.y;
-
-        if cursor_x < view_rect.tl.x {
-            target_x = cursor_x;
-        } else if cursor_x >= view_rect.tl.x.saturating_add(view_rect.w.saturating_sub(1)) {
-            target_x = cursor_x.saturating_sub(view_rect.w.saturating_sub(1));
-        }
-
-        if cursor_y < view_rect.tl.y {
-            target_y = cursor_y;
-        } else if cursor_y >= view_rect.tl.y.saturating_add(view_rect.h.saturating_sub(1)) {
-            target_y = cursor_y.saturating_sub(view_rect.h.saturating_sub(1));
-        }
-
-        if self.config.wrap == WrapMode::Soft {
-            target_x = 0;
-        }
-
-        let _ = ctx.scroll_to(target_x, target_y);
+        // Soft wrap never scrolls horizontally.
+        let x = if self.config.wrap == WrapMode::Soft {
+            0
+        } else {
+            cursor.x
+        };
+        let _ = ctx.scroll_rect_into_view(Rect::new(x, cursor.y, 1, 1));
     }
 
     /// Refresh the preferred display column from the cursor position.
@@ -407,11 +614,7 @@ impl Editor {
 
     /// Normalize inserted text for single-line editors.
     fn normalize_insert_text(&self, text: &str) -> String {
-        if self.config.multiline {
-            text.to_string()
-        } else {
-            text.replace(['\n', '\r'], " ")
-        }
+        flatten_lines(text.to_string(), self.config.multiline)
     }
 
     /// Delete backward respecting selection and multiline rules.
@@ -757,6 +960,155 @@ impl Editor {
         (updated, next_index)
     }
 
+    /// Whether typed text currently goes into the buffer.
+    fn entering_text(&self) -> bool {
+        if self.config.read_only || self.prompt.is_some() {
+            return false;
+        }
+        match self.config.mode {
+            EditMode::Text => true,
+            EditMode::Vi => self.vi.mode() == ViMode::Insert,
+        }
+    }
+
+    /// Return the start of the word ending at the cursor, the word, and the cursor line.
+    fn completion_prefix(&self) -> (TextPosition, String, String) {
+        let cursor = self.buffer.cursor();
+        let line = self.buffer.line_text(cursor.line);
+        let chars: Vec<char> = line.chars().collect();
+        let end = cursor.column.min(chars.len());
+        let mut start = end;
+        while start > 0 && is_word_char(chars[start - 1]) {
+            start -= 1;
+        }
+        let prefix = chars[start..end].iter().collect();
+        (TextPosition::new(cursor.line, start), prefix, line)
+    }
+
+    /// Ask the completion provider for candidates at the cursor.
+    fn request_completions(&mut self) -> Option<CompletionMenu> {
+        let (start, prefix, line) = self.completion_prefix();
+        let cursor = self.buffer.cursor();
+        let items = self.completer.as_mut()?.complete(&prefix, &line, cursor);
+        (!items.is_empty()).then(|| CompletionMenu::new(start, items))
+    }
+
+    /// Open the completion popup, inserting a lone candidate directly. Returns `false` if
+    /// the provider had no candidates.
+    fn open_completion(&mut self) -> bool {
+        self.completion = self.request_completions();
+        match &self.completion {
+            None => false,
+            Some(menu) if menu.items.len() == 1 => {
+                self.accept_completion();
+                true
+            }
+            Some(_) => true,
+        }
+    }
+
+    /// Replace the word before the cursor with the highlighted candidate and close the
+    /// popup.
+    fn accept_completion(&mut self) {
+        let Some(menu) = self.completion.take() else {
+            return;
+        };
+        let Some(item) = menu.current() else {
+            return;
+        };
+        let cursor = self.buffer.cursor();
+        self.buffer
+            .set_selection(Selection::new(menu.start, cursor));
+        self.handle_insert_text(&item.text);
+    }
+
+    /// Follow an edit made while the popup is open, closing it when the word before the
+    /// cursor no longer matches anything.
+    fn refresh_completion(&mut self) {
+        let Some(menu) = &self.completion else {
+            return;
+        };
+        let (start, prefix, _) = self.completion_prefix();
+        self.completion = if prefix.is_empty() || start != menu.start {
+            None
+        } else if self.completer.is_some() {
+            self.request_completions()
+        } else {
+            let items: Vec<Completion> = menu
+                .items
+                .iter()
+                .filter(|item| item.text.starts_with(&prefix))
+                .cloned()
+                .collect();
+            (!items.is_empty()).then(|| CompletionMenu::new(start, items))
+        };
+    }
+
+    /// Handle keys that drive completion. Returns `None` for events left to the editor.
+    fn handle_completion_event(&mut self, event: &Event) -> Option<EventOutcome> {
+        if !self.entering_text() || (self.completer.is_none() && self.completion.is_none()) {
+            self.completion = None;
+            return None;
+        }
+        let Event::Key(key) = event else {
+            return None;
+        };
+        if key.mods.ctrl || key.mods.alt {
+            return None;
+        }
+        let open = self.completion.is_some();
+        match key.key {
+            key::KeyCode::Tab if open => self.accept_completion(),
+            key::KeyCode::Tab => {
+                if !self.open_completion() {
+                    return None;
+                }
+            }
+            key::KeyCode::Esc if open => self.completion = None,
+            key::KeyCode::Down | key::KeyCode::Up if open => {
+                let delta = if key.key == key::KeyCode::Down { 1 } else { -1 };
+                if let Some(menu) = &mut self.completion {
+                    menu.shift(delta);
+                }
+            }
+            _ => return None,
+        }
+        Some(EventOutcome::Handle)
+    }
+
+    /// Offer spelling corrections for the word at the cursor in the completion popup.
+    #[command]
+    pub fn spell_suggest(&mut self, ctx: &mut dyn Context) {
+        let Some(spell) = &self.spell else {
+            return;
+        };
+        let range = word_range(&self.buffer, self.buffer.cursor());
+        let word = self.buffer.range_text(range);
+        if !self.entering_text() || !spell.is_misspelled(&word) {
+            return;
+        }
+        let items = spell
+            .suggest(&word)
+            .into_iter()
+            .map(Completion::new)
+            .collect();
+        self.buffer.set_cursor(range.end);
+        self.show_completions(items);
+        self.ensure_cursor_visible(ctx);
+    }
+
+    /// Complete the word before the cursor from the completion provider.
+    #[command]
+    pub fn complete(&mut self, ctx: &mut dyn Context) {
+        if self.completer.is_none() || !self.entering_text() {
+            return;
+        }
+        self.open_completion();
+        self.ensure_cursor_visible(ctx);
+        self.register_undo(ctx);
+        self.notify_changes();
+    }
+
     /// Handle events in text-entry mode.
     fn handle_text_entry_event(&mut self, event: &Event, ctx: &mut dyn Context) -> EventOutcome {
         match event {
@@ -816,7 +1168,7 @@ impl Editor {
             Event::Key(key::Key {
                 key: key::KeyCode::Up,
                 ..
-            }) => {
+            }) if self.config.multiline => {
                 self.commit_text_entry_transaction();
                 self.move_vertical(-1);
                 self.ensure_cursor_visible(ctx);
@@ -825,7 +1177,7 @@ impl Editor {
             Event::Key(key::Key {
                 key: key::KeyCode::Down,
                 ..
-            }) => {
+            }) if self.config.multiline => {
                 self.commit_text_entry_transaction();
                 self.move_vertical(1);
                 self.ensure_cursor_visible(ctx);
@@ -1045,7 +1397,9 @@ impl Editor {
                 key: key::KeyCode::Char('u'),
                 ..
             }) => {
-                self.buffer.undo();
+                if !self.config.read_only {
+                    self.buffer.undo();
+                }
                 self.update_preferred_column();
                 self.ensure_cursor_visible(ctx);
                 EventOutcome::Handle
@@ -1054,7 +1408,9 @@ impl Editor {
                 key: key::KeyCode::Char('r'),
                 mods,
             }) if mods.ctrl => {
-                self.buffer.redo();
+                if !self.config.read_only {
+                    self.buffer.redo();
+                }
                 self.update_preferred_column();
                 self.ensure_cursor_visible(ctx);
                 EventOutcome::Handle
@@ -2071,6 +2427,92 @@ impl Editor {
         r.text("editor/prompt", line, &prompt_text(prompt))
     }
 
+    /// Render the completion popup below the cursor, or above it when there is no room.
+    /// Single-line editors always draw it below, past their rect, which shows when the
+    /// host node is unclipped as `Input` is.
+    fn render_completion(&self, r: &mut Render, view_rect: Rect, origin: Point) -> Result<()> {
+        let (Some(menu), Some(cursor)) = (&self.completion, self.view.cursor_view_point) else {
+            return Ok(());
+        };
+        let labels: Vec<String> = menu.items.iter().map(Completion::label).collect();
+        let width = labels
+            .iter()
+            .map(|label| text::display_width(label) as u32)
+            .max()
+            .unwrap_or(0)
+            .saturating_add(2)
+            .min(view_rect.w);
+        let wanted = labels.len().min(MAX_VISIBLE) as u32;
+        let below = view_rect.h.saturating_sub(cursor.y.saturating_add(1));
+        let (top, rows) = if !self.config.multiline {
+            (cursor.y.saturating_add(1), wanted)
+        } else if below >= wanted || below >= cursor.y {
+            (cursor.y.saturating_add(1), wanted.min(below))
+        } else {
+            let rows = wanted.min(cursor.y);
+            (cursor.y - rows, rows)
+        };
+        let x = cursor.x.min(view_rect.w.saturating_sub(width));
+        let first = menu.first_visible(rows as usize);
+        let visible = labels.iter().enumerate().skip(first).take(rows as usize);
+        for (row, (index, label)) in visible.enumerate() {
+            let style = if index == menu.selected {
+                "editor/completion/selected"
+            } else {
+                "editor/completion"
+            };
+            let line = Line::new(
+                origin.x.saturating_add(x),
+                origin.y.saturating_add(top).saturating_add(row as u32),
+                width,
+            );
+            r.fill(style, line.into(), ' ')?;
+            let (label, _) = text::slice_by_columns(label, 0, width.saturating_sub(2) as usize);
+            r.text(
+                style,
+                Line::new(
+                    line.tl.x.saturating_add(1),
+                    line.tl.y,
+                    width.saturating_sub(1),
+                ),
+                label,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Render the hover text above the cursor, or below it on the first row.
+    fn render_hover(&self, r: &mut Render, view_rect: Rect, origin: Point) -> Result<()> {
+        let (Some(hover), Some(cursor)) = (&self.hover, self.view.cursor_view_point) else {
+            return Ok(());
+        };
+        let first = hover.lines().next().unwrap_or_default();
+        let width = (text::display_width(first) as u32)
+            .saturating_add(2)
+            .min(view_rect.w);
+        let y = if cursor.y > 0 {
+            cursor.y - 1
+        } else {
+            cursor.y.saturating_add(1)
+        };
+        if y >= view_rect.h || width == 0 {
+            return Ok(());
+        }
+        let x = cursor.x.min(view_rect.w.saturating_sub(width));
+        let line = Line::new(
+            origin.x.saturating_add(x),
+            origin.y.saturating_add(y),
+            width,
+        );
+        r.fill("editor/hover", line.into(), ' ')?;
+        let (first, used) = text::slice_by_columns(first, 0, width.saturating_sub(2) as usize);
+        r.text(
+            "editor/hover",
+            Line::new(line.tl.x.saturating_add(1), line.tl.y, used as u32),
+            first,
+        )
+    }
+
     /// Render a single display line of text and gutter content.
     fn render_line(
         &mut self,
@@ -2085,13 +2527,29 @@ impl Editor {
 
         let base_text_style = ctx.r.resolve_style_name_raw("editor/text");
 
-        if ctx.gutter_width > 0 {
-            let gutter_line = Line::new(ctx.origin.x, line_y, ctx.gutter_width);
+        let sign_width = self.sign_width();
+        if sign_width > 0 {
+            let sign_line = Line::new(ctx.origin.x, line_y, sign_width);
+            match line_severity(&self.diagnostics, line_idx) {
+                Some(severity) if segment.start_char == 0 => {
+                    let sign = severity.sign().to_string();
+                    ctx.r.text(severity.sign_style(), sign_line, &sign)?;
+                }
+                _ => ctx.r.text("editor/line-number", sign_line, " ")?,
+            }
+        }
+        let numbers_width = ctx.gutter_width.saturating_sub(sign_width);
+        if numbers_width > 0 {
+            let gutter_line = Line::new(
+                ctx.origin.x.saturating_add(sign_width),
+                line_y,
+                numbers_width,
+            );
             let number_text = line_number_text(
                 self.config.line_numbers,
                 line_idx,
                 self.buffer.cursor().line,
-                ctx.gutter_width,
+                numbers_width,
             );
             let style = if line_idx == self.buffer.cursor().line {
                 "editor/line-number/current"
@@ -2119,12 +2577,11 @@ impl Editor {
             self.buffer.line_char_len(line_idx)
         };
 
-        let mut highlight_spans = Vec::new();
-        if let Some(highlighter) = &self.highlighter {
-            highlight_spans = self.highlight_cache.spans_for_line(line_idx, || {
-                highlighter.highlight_line(line_idx, &line_text)
-            });
-        }
+        let highlight_spans = self.highlight_cache.spans(line_idx);
+        let misspelled = match &self.spell {
+            Some(spell) => self.spell_cache.misspelled(spell, &self.buffer, line_idx),
+            None => Vec::new(),
+        };
 
         let mut span_idx = 0usize;
         let search_ranges = self.search.matches_for_line(line_idx);
@@ -2171,6 +2628,7 @@ impl Editor {
 
             let mut style_name = "editor/text";
             let mut style = None;
+            let mut underline = None;
 
             if selection_on_line && g_start < line_end_sel && g_end > line_start_sel {
                 style_name = "editor/selection";
@@ -2184,6 +2642,14 @@ impl Editor {
             {
                 style_name = "editor/search/match";
             } else {
+                underline = severity_at(&self.diagnostics, line_idx, g_start, g_end)
+                    .map(Underline::Diagnostic)
+                    .or_else(|| {
+                        misspelled
+                            .iter()
+                            .any(|range| g_start < range.end && g_end > range.start)
+                            .then_some(Underline::Misspelled)
+                    });
                 while let Some(span) = highlight_spans.get(span_idx) {
                     if span.range.end <= g_start {
                         span_idx = span_idx.saturating_add(1);
@@ -2198,6 +2664,12 @@ impl Editor {
                 }
             }
 
+            let whitespace = self.config.show_whitespace && (grapheme == "\t" || grapheme == " ");
+            if whitespace && style_name == "editor/text" {
+                style_name = "editor/whitespace";
+                style = None;
+            }
+
             if grapheme == "\t" {
                 let start = draw_col;
                 let end = draw_col.saturating_add(width);
@@ -2207,22 +2679,31 @@ impl Editor {
                         x: ctx.origin.x.saturating_add(x),
                         y: line_y,
                     };
-                    let resolved = match style.as_ref() {
-                        Some(custom) => ctx.r.resolve_style_at(custom.clone(), line_rect, p),
-                        None => ctx.r.resolve_style_name_at(style_name, line_rect, p),
+                    let resolved =
+                        resolve_cell(ctx.r, style.as_ref(), style_name, underline, line_rect, p);
+                    let glyph = match (whitespace, offset == start) {
+                        (false, _) => ' ',
+                        (true, true) => TAB_MARK,
+                        (true, false) => SPACE_MARK,
                     };
-                    ctx.r.put_cell(resolved, p, ' ')?;
+                    ctx.r.put_cell(resolved, p, glyph)?;
                 }
-            } else {
+            } else if whitespace {
                 let x = draw_col.saturating_sub(view_start) as u32;
                 let p = Point {
                     x: ctx.origin.x.saturating_add(x),
                     y: line_y,
                 };
-                let resolved = match style.as_ref() {
-                    Some(custom) => ctx.r.resolve_style_at(custom.clone(), line_rect, p),
-                    None => ctx.r.resolve_style_name_at(style_name, line_rect, p),
+                let resolved = resolve_cell(ctx.r, None, style_name, underline, line_rect, p);
+                ctx.r.put_cell(resolved, p, SPACE_MARK)?;
+            } else {
+                let x = draw_col.saturating_sub(view_start) as u32;
+                let p = Point {
+                    x: ctx.origin.x.saturating_add(x),
+                    y: line_y,
                 };
+                let resolved =
+                    resolve_cell(ctx.r, style.as_ref(), style_name, underline, line_rect, p);
                 ctx.r.put_grapheme(resolved, p, grapheme)?;
             }
 
@@ -2256,26 +2737,184 @@ impl Editor {
         self.ensure_cursor_visible(ctx);
     }
 
+    /// Toggle soft wrapping. With wrapping off, long lines scroll horizontally.
+    #[command]
+    pub fn toggle_wrap(&mut self, ctx: &mut dyn Context) {
+        self.config.wrap = match self.config.wrap {
+            WrapMode::Soft => WrapMode::None,
+            WrapMode::None => WrapMode::Soft,
+        };
+        if self.config.wrap == WrapMode::Soft {
+            ctx.scroll_to(0, ctx.view().tl.y);
+        }
+        self.ensure_cursor_visible(ctx);
+    }
+
+    /// Set the tab stop width.
+    /// @param tab_stop Tab stop width in columns.
+    #[command]
+    pub fn set_tab_stop(&mut self, ctx: &mut dyn Context, tab_stop: usize) {
+        self.config.tab_stop = tab_stop.max(1);
+        self.update_preferred_column();
+        self.ensure_cursor_visible(ctx);
+    }
+
+    /// Toggle drawing tabs and spaces with visible glyphs.
+    #[command]
+    pub fn toggle_whitespace(&mut self, _ctx: &mut dyn Context) {
+        self.config.show_whitespace = !self.config.show_whitespace;
+    }
+
     /// Undo the last edit.
     #[command]
     pub fn undo(&mut self, _ctx: &mut dyn Context) {
+        if self.config.read_only {
+            return;
+        }
         self.buffer.undo();
         self.update_preferred_column();
+        self.notify_changes();
     }
 
     /// Redo the last undone edit.
     #[command]
     pub fn redo(&mut self, _ctx: &mut dyn Context) {
+        if self.config.read_only {
+            return;
+        }
         self.buffer.redo();
         self.update_preferred_column();
+        self.notify_changes();
+    }
+
+    /// Insert text at the cursor, replacing any selection.
+    /// @param text The text to insert.
+    #[command]
+    pub fn insert(&mut self, ctx: &mut dyn Context, text: String) {
+        self.handle_insert_text(&text);
+        self.ensure_cursor_visible(ctx);
+        self.register_undo(ctx);
+        self.notify_changes();
+    }
+
+    /// Pass edits made since the last call to the change callback.
+    fn notify_changes(&mut self) {
+        let changes = self.buffer.take_changes();
+        if let Some(line) = changes.iter().map(|change| change.range.start.line).min() {
+            self.highlight_cache
+                .invalidate_from(line, self.buffer.revision());
+            self.spell_cache
+                .invalidate_from(line, self.buffer.revision());
+        }
+        if let Some(on_change) = &mut self.on_change {
+            for change in &changes {
+                on_change(change);
+            }
+        }
+    }
+
+    /// Handle an input event without registering edits with the app undo history, whose
+    /// entries run the editor's commands on its own node. Widgets that embed an editor
+    /// use this to edit through it.
+    pub(crate) fn handle_event(&mut self, event: &Event, ctx: &mut dyn Context) -> EventOutcome {
+        if matches!(event, Event::Key(_) | Event::Mouse(_)) {
+            self.hover = None;
+        }
+        if let Event::Mouse(mouse_event) = event {
+            self.completion = None;
+            match mouse_event.action {
+                mouse::Action::ScrollUp if ctx.scroll_by(0, -WHEEL_SCROLL_LINES) => {
+                    return EventOutcome::Handle;
+                }
+                mouse::Action::ScrollDown if ctx.scroll_by(0, WHEEL_SCROLL_LINES) => {
+                    return EventOutcome::Handle;
+                }
+                mouse::Action::ScrollLeft if ctx.scroll_by(-WHEEL_SCROLL_LINES, 0) => {
+                    return EventOutcome::Handle;
+                }
+                mouse::Action::ScrollRight if ctx.scroll_by(WHEEL_SCROLL_LINES, 0) => {
+                    return EventOutcome::Handle;
+                }
+                _ => {}
+            }
+
+            let handled = self.handle_mouse_event(mouse_event, ctx);
+            if handled {
+                self.ensure_cursor_visible(ctx);
+                return EventOutcome::Handle;
+            }
+        }
+
+        if let Some(outcome) = self.handle_completion_event(event) {
+            self.ensure_cursor_visible(ctx);
+            self.notify_changes();
+            return outcome;
+        }
+
+        let revision = self.buffer.revision();
+        let outcome = match self.config.mode {
+            EditMode::Text => self.handle_text_entry_event(event, ctx),
+            EditMode::Vi => self.handle_vi_event(event, ctx),
+        };
+        if self.buffer.revision() == revision {
+            self.completion = None;
+        } else {
+            self.refresh_completion();
+        }
+        if self.config.read_only && self.vi.mode() == ViMode::Insert {
+            let _ = self.vi.end_insert();
+        }
+        self.notify_changes();
+        outcome
+    }
+
+    /// Register newly committed buffer transactions with the app undo history, each undone
+    /// through the editor's own `undo` and `redo` commands.
+    fn register_undo(&mut self, ctx: &mut dyn Context) {
+        let commits = self.buffer.commit_count();
+        for _ in self.undo_mark..commits {
+            ctx.push_undo(UndoEntry::new(
+                ctx.node_id(),
+                "edit text",
+                Self::cmd_undo().call().invocation(),
+                Self::cmd_redo().call().invocation(),
+            ));
+        }
+        self.undo_mark = commits;
     }
 }
 
+impl Scrollable for Editor {}
+
 impl Widget for Editor {
+    fn scrollable(&mut self) -> Option<&mut dyn Scrollable> {
+        Some(self)
+    }
+
     fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
         true
     }
 
+    fn accessible(&self, _ctx: &dyn ReadContext) -> Option<Accessible> {
+        let role = if self.config.read_only {
+            Role::Text
+        } else {
+            Role::TextInput
+        };
+        let state = if self.config.multiline {
+            let line = self.buffer.cursor().line;
+            format!("line {} of {}", line + 1, self.buffer.line_count())
+        } else {
+            let text = self.buffer.text();
+            if text.is_empty() {
+                "blank".to_string()
+            } else {
+                text
+            }
+        };
+        Some(Accessible::new(role, "").with_state(state))
+    }
+
     fn cursor(&self) -> Option<cursor::Cursor> {
         let location = self.view.cursor_view_point?;
         let shape = match self.config.mode {
@@ -2289,9 +2928,23 @@ impl Widget for Editor {
             location,
             shape,
             blink: true,
+            color: None,
         })
     }
 
+    fn extra_cursors(&self) -> Vec<cursor::Cursor> {
+        self.view
+            .anchor_view_point
+            .map(|location| cursor::Cursor {
+                location,
+                shape: cursor::CursorShape::Block,
+                blink: false,
+                color: None,
+            })
+            .into_iter()
+            .collect()
+    }
+
     fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
         let view = ctx.view();
         let view_rect = view.view_rect();
@@ -2299,6 +2952,17 @@ impl Widget for Editor {
         let gutter_width = self.gutter_width();
         self.update_layout(view_rect, gutter_width);
         self.highlight_cache.sync_revision(self.buffer.revision());
+        self.spell_cache.sync_revision(self.buffer.revision());
+        if let Some(highlighter) = &mut self.highlighter {
+            let first = view_rect.tl.y as usize;
+            let last = first.saturating_add(view_rect.h.saturating_sub(1) as usize);
+            let total = self.view.layout.total_lines();
+            if view_rect.h > 0 && first < total {
+                let last = self.view.layout.line_for_display(last.min(total - 1));
+                self.highlight_cache
+                    .fill(highlighter.as_mut(), &self.buffer, last);
+            }
+        }
 
         self.search.update(&self.buffer);
 
@@ -2323,6 +2987,8 @@ impl Widget for Editor {
             }
         }
 
+        self.render_hover(r, view_rect, origin)?;
+        self.render_completion(r, view_rect, origin)?;
         self.render_prompt(r, view_rect, origin)?;
         Ok(())
     }
@@ -2343,6 +3009,7 @@ impl Widget for Editor {
                 self.config.wrap,
                 wrap_width,
                 self.config.tab_stop,
+                self.config.word_breaker.as_ref(),
             ) as u32
         } else {
             self.config.min_height.max(1)
@@ -2362,6 +3029,7 @@ impl Widget for Editor {
             self.config.wrap,
             wrap_width,
             self.config.tab_stop,
+            self.config.word_breaker.as_ref(),
         ) as u32;
         let width = match self.config.wrap {
             WrapMode::None => {
@@ -2374,34 +3042,25 @@ impl Widget for Editor {
     }
 
     fn on_event(&mut self, event: &Event, ctx: &mut dyn Context) -> Result<EventOutcome> {
-        if let Event::Mouse(mouse_event) = event {
-            match mouse_event.action {
-                mouse::Action::ScrollUp if ctx.scroll_by(0, -WHEEL_SCROLL_LINES) => {
-                    return Ok(EventOutcome::Handle);
-                }
-                mouse::Action::ScrollDown if ctx.scroll_by(0, WHEEL_SCROLL_LINES) => {
-                    return Ok(EventOutcome::Handle);
-                }
-                mouse::Action::ScrollLeft if ctx.scroll_by(-WHEEL_SCROLL_LINES, 0) => {
-                    return Ok(EventOutcome::Handle);
-                }
-                mouse::Action::ScrollRight if ctx.scroll_by(WHEEL_SCROLL_LINES, 0) => {
-                    return Ok(EventOutcome::Handle);
-                }
-                _ => {}
-            }
+        let outcome = self.handle_event(event, ctx);
+        self.register_undo(ctx);
+        Ok(outcome)
+    }
 
-            let handled = self.handle_mouse_event(mouse_event, ctx);
-            if handled {
-                self.ensure_cursor_visible(ctx);
-                return Ok(EventOutcome::Handle);
+    #[cfg(feature = "lsp")]
+    fn handle_message(&mut self, msg: Box<dyn Any>, _ctx: &mut dyn Context) -> Result<()> {
+        if let Ok(event) = msg.downcast::<LspEvent>() {
+            match *event {
+                LspEvent::Diagnostics(diagnostics) => self.set_diagnostics(diagnostics),
+                LspEvent::Hover(text) => self.set_hover(text),
+                LspEvent::Completions(items) => self.show_completions(items),
             }
         }
+        Ok(())
+    }
 
-        Ok(match self.config.mode {
-            EditMode::Text => self.handle_text_entry_event(event, ctx),
-            EditMode::Vi => self.handle_vi_event(event, ctx),
-        })
+    fn persistent(&mut self) -> Option<&mut dyn PersistentState> {
+        Some(self)
     }
 
     fn name(&self) -> NodeName {
@@ -2409,6 +3068,28 @@ impl Widget for Editor {
     }
 }
 
+/// Editors persist their contents, cursor, and scroll offset. Restoring replaces the
+/// buffer without an undo entry, like [`Editor::set_text`].
+impl PersistentState for Editor {
+    fn save_state(&self, ctx: &dyn ReadContext) -> Result<Value> {
+        let cursor = self.cursor_position();
+        let offset = ctx.view().tl;
+        to_state(&(
+            self.text(),
+            [cursor.line, cursor.column],
+            [offset.x, offset.y],
+        ))
+    }
+
+    fn restore_state(&mut self, state: Value, ctx: &mut dyn Context) -> Result<()> {
+        let (text, [line, column], [x, y]): (String, [usize; 2], [u32; 2]) = from_state(state)?;
+        self.set_text(text);
+        self.set_cursor_position(TextPosition::new(line, column));
+        ctx.scroll_to(x, y);
+        Ok(())
+    }
+}
+
 impl MouseState {
     /// Construct a new mouse state.
     fn new() -> Self {
@@ -2462,6 +3143,42 @@ enum ClickType {
     Triple,
 }
 
+/// Underline layered over the text style of a grapheme.
+#[derive(Debug, Clone, Copy)]
+enum Underline {
+    /// Text covered by a diagnostic, underlined in the severity's color.
+    Diagnostic(Severity),
+    /// A misspelled word, underlined in its text color.
+    Misspelled,
+}
+
+/// Resolve the style of a text cell from its highlight style or style name, then layer
+/// any underline over it.
+fn resolve_cell(
+    r: &Render,
+    style: Option<&Style>,
+    style_name: &str,
+    underline: Option<Underline>,
+    bounds: Rect,
+    p: Point,
+) -> ResolvedStyle {
+    let mut resolved = match style {
+        Some(custom) => r.resolve_style_at(custom.clone(), bounds, p),
+        None => r.resolve_style_name_at(style_name, bounds, p),
+    };
+    let layer = match underline {
+        Some(Underline::Diagnostic(severity)) => severity.style(),
+        Some(Underline::Misspelled) => "editor/misspelled",
+        None => return resolved,
+    };
+    let over = r.resolve_style_name_at(layer, bounds, p);
+    resolved.attrs.underline |= over.attrs.underline;
+    if matches!(underline, Some(Underline::Diagnostic(_))) {
+        resolved.fg = over.fg;
+    }
+    resolved
+}
+
 /// Build prompt text for search and replace overlays.
 fn prompt_text(prompt: &PromptState) -> String {
     match prompt {
@@ -2502,17 +3219,27 @@ fn line_number_text(mode: LineNumbers, line: usize, cursor_line: usize, width: u
     )
 }
 
+/// Replace newlines with spaces unless the editor is multiline.
+fn flatten_lines(text: String, multiline: bool) -> String {
+    if multiline {
+        text
+    } else {
+        text.replace("\r\n", " ").replace(['\n', '\r'], " ")
+    }
+}
+
 /// Compute the total display line count for a buffer.
 fn display_line_count(
     buffer: &TextBuffer,
     wrap_mode: WrapMode,
     wrap_width: usize,
     tab_stop: usize,
+    breaker: Option<&WordBreaker>,
 ) -> usize {
     let mut total = 0usize;
     for line in 0..buffer.line_count().max(1) {
         let text = buffer.line_text(line);
-        let layout = layout_line(&text, wrap_mode, wrap_width, tab_stop);
+        let layout = layout_line(&text, wrap_mode, wrap_width, tab_stop, breaker);
         total = total.saturating_add(layout.display_lines());
     }
     total.max(1)
@@ -2523,7 +3250,7 @@ fn display_line_width(buffer: &TextBuffer, tab_stop: usize) -> usize {
     let mut max_width = 1usize;
     for line in 0..buffer.line_count().max(1) {
         let text = buffer.line_text(line);
-        let layout = layout_line(&text, WrapMode::None, 1, tab_stop);
+        let layout = layout_line(&text, WrapMode::None, 1, tab_stop, None);
         max_width = max_width.max(layout.display_width);
     }
     max_width
@@ -116,6 +116,11 @@ impl Frame {
         self.title.as_deref()
     }
 
+    /// Replace the title, or remove it with `None`.
+    pub fn set_title(&mut self, title: Option<String>) {
+        self.title = title;
+    }
+
     /// Wrap an existing child node in a new frame and return the frame node ID.
     pub fn wrap(c: &mut dyn Context, child: impl Into<NodeId>) -> Result<NodeId> {
         Self::wrap_with(c, child, Self::new())
@@ -1,6 +1,10 @@
 //! Contextual help modal widget.
 //!
 //! Displays bindings and commands available from the current focus context.
+//! [`HelpOverlay`] shows a cheat sheet of every binding instead.
+
+/// Binding cheat-sheet overlay widget.
+mod overlay;
 
 use std::cell::RefCell;
 
@@ -22,6 +26,8 @@ use canopy::{
 };
 use unicode_width::UnicodeWidthStr;
 
+pub use overlay::HelpOverlay;
+
 use crate::{frame, modal::Modal};
 
 /// Default help bindings exposed through `help.default_bindings()`.
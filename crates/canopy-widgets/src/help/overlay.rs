@@ -0,0 +1,326 @@
+//! Cheat-sheet overlay generated from every registered binding.
+
+use std::collections::BTreeMap;
+
+use canopy::{
+    Canopy, Context, Loader, ReadContext, ScrollCommands, Scrollable, Widget,
+    commands::CommandSpec,
+    derive_commands,
+    error::Result,
+    geom::Line,
+    help::CatalogBinding,
+    inputmap::BindingTarget,
+    layout::{CanvasContext, Layout, Size},
+    render::Render,
+    state::NodeName,
+    text,
+};
+use unicode_width::UnicodeWidthStr;
+
+use super::binding_sort_key;
+
+/// Columns between the key column and the label.
+const KEY_GAP: usize = 2;
+
+/// Default bindings for the help overlay.
+const DEFAULT_BINDINGS: &str = r#"
+canopy.bind_with("j", { path = "help_overlay", desc = "Scroll down" }, function()
+    scrollable.down()
+end)
+canopy.bind_with("Down", { path = "help_overlay", desc = "Scroll down" }, function()
+    scrollable.down()
+end)
+canopy.bind_with("k", { path = "help_overlay", desc = "Scroll up" }, function()
+    scrollable.up()
+end)
+canopy.bind_with("Up", { path = "help_overlay", desc = "Scroll up" }, function()
+    scrollable.up()
+end)
+canopy.bind_with("Space", { path = "help_overlay", desc = "Page down" }, function()
+    scrollable.page_down()
+end)
+canopy.bind_with("g", { path = "help_overlay", desc = "Top" }, function()
+    scrollable.top()
+end)
+canopy.bind_with("G", { path = "help_overlay", desc = "Bottom" }, function()
+    scrollable.bottom()
+end)
+"#;
+
+/// One display line of the cheat sheet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SheetLine {
+    /// A section heading naming the path filter and mode.
+    Section(String),
+    /// A binding within the preceding section.
+    Entry {
+        /// Formatted input.
+        key: String,
+        /// Binding description.
+        label: String,
+        /// Doc text of the bound command, if the binding runs a single documented command.
+        doc: Option<&'static str>,
+    },
+    /// Spacing between sections.
+    Blank,
+}
+
+/// Build the cheat sheet from a binding catalog, grouped by mode and path filter.
+///
+/// Bindings that run a hidden command are left out.
+fn build_sheet(catalog: &[CatalogBinding], specs: &[&'static CommandSpec]) -> Vec<SheetLine> {
+    let spec = |id: &str| specs.iter().find(|spec| spec.id.0 == id);
+    let mut sections: BTreeMap<(&str, &str), Vec<&CatalogBinding>> = BTreeMap::new();
+    for binding in catalog {
+        if let BindingTarget::Command(inv) = &binding.target
+            && spec(inv.id.0).is_some_and(|spec| spec.doc.hidden)
+        {
+            continue;
+        }
+        sections
+            .entry((binding.mode.as_str(), binding.path_filter.as_str()))
+            .or_default()
+            .push(binding);
+    }
+
+    let mut lines = Vec::new();
+    for ((mode, path_filter), mut bindings) in sections {
+        if !lines.is_empty() {
+            lines.push(SheetLine::Blank);
+        }
+        let mut heading = if path_filter.is_empty() {
+            "global".to_string()
+        } else {
+            path_filter.to_string()
+        };
+        if !mode.is_empty() {
+            heading.push_str(&format!(" [{mode}]"));
+        }
+        lines.push(SheetLine::Section(heading));
+        bindings.sort_by_key(|binding| binding_sort_key(&binding.input));
+        for binding in bindings {
+            let doc = match &binding.target {
+                BindingTarget::Command(inv) => spec(inv.id.0)
+                    .and_then(|spec| spec.doc.short.or(spec.doc.long))
+                    .filter(|doc| *doc != binding.label),
+                _ => None,
+            };
+            lines.push(SheetLine::Entry {
+                key: binding.input.to_string(),
+                label: binding.label.clone(),
+                doc,
+            });
+        }
+    }
+    lines
+}
+
+/// Categorized cheat sheet of every binding in every mode.
+///
+/// The overlay observes the binding catalog and rebuilds the sheet only when the
+/// catalog is recaptured, so bindings added at runtime show up without reinstalling it.
+/// Apps typically mount it in a modal and toggle it from a `?` binding.
+pub struct HelpOverlay {
+    /// Current sheet lines.
+    lines: Vec<SheetLine>,
+    /// Width of the widest key in the sheet.
+    key_width: usize,
+}
+
+#[derive_commands]
+impl HelpOverlay {
+    /// Construct an empty overlay. The sheet fills in once the catalog is captured.
+    pub fn new() -> Self {
+        Self {
+            lines: Vec::new(),
+            key_width: 0,
+        }
+    }
+
+    /// Rebuild the sheet from the captured catalog and the command registry.
+    fn rebuild(&mut self, ctx: &dyn ReadContext) {
+        let specs = ctx.command_specs();
+        self.lines = ctx
+            .binding_catalog()
+            .with(|catalog| build_sheet(catalog, &specs));
+        self.key_width = self
+            .lines
+            .iter()
+            .filter_map(|line| match line {
+                SheetLine::Entry { key, .. } => Some(UnicodeWidthStr::width(key.as_str())),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0);
+    }
+}
+
+impl Default for HelpOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scrollable for HelpOverlay {}
+
+impl Widget for HelpOverlay {
+    fn layout(&self) -> Layout {
+        Layout::fill()
+    }
+
+    fn canvas(&self, view: Size<u32>, _ctx: &CanvasContext) -> Size<u32> {
+        Size::new(view.w, self.lines.len() as u32)
+    }
+
+    fn scrollable(&mut self) -> Option<&mut dyn Scrollable> {
+        Some(self)
+    }
+
+    fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+        let view = ctx.view();
+        let view_rect = view.view_rect();
+        let origin = view.content_origin();
+        if view_rect.w == 0 || view_rect.h == 0 {
+            return Ok(());
+        }
+        r.fill("help/content", view.outer_rect_local(), ' ')?;
+
+        let width = view_rect.w;
+        let label_x = (self.key_width + KEY_GAP) as u32;
+        for (row, line) in self
+            .lines
+            .iter()
+            .skip(view_rect.tl.y as usize)
+            .take(view_rect.h as usize)
+            .enumerate()
+        {
+            let y = origin.y + row as u32;
+            match line {
+                SheetLine::Section(heading) => {
+                    r.text("help/section", Line::new(origin.x, y, width), heading)?;
+                }
+                SheetLine::Entry { key, label, doc } => {
+                    let padded = format!("{key:>width$}", width = self.key_width);
+                    r.text(
+                        "help/key",
+                        Line::new(origin.x, y, label_x.min(width)),
+                        &padded,
+                    )?;
+                    let label_width = width.saturating_sub(label_x);
+                    let (label, used) = text::slice_by_columns(label, 0, label_width as usize);
+                    r.text(
+                        "help/label",
+                        Line::new(origin.x + label_x, y, label_width),
+                        label,
+                    )?;
+                    if let Some(doc) = doc {
+                        let x = label_x + used as u32 + KEY_GAP as u32;
+                        let doc_width = width.saturating_sub(x);
+                        let (doc, _) = text::slice_by_columns(doc, 0, doc_width as usize);
+                        r.text("help/doc", Line::new(origin.x + x, y, doc_width), doc)?;
+                    }
+                }
+                SheetLine::Blank => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn on_mount(&mut self, ctx: &mut dyn Context) -> Result<()> {
+        let catalog = ctx.binding_catalog();
+        ctx.observe(&catalog);
+        ctx.request_binding_catalog();
+        Ok(())
+    }
+
+    fn on_observed(&mut self, ctx: &mut dyn Context) -> Result<()> {
+        self.rebuild(ctx);
+        Ok(())
+    }
+
+    fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+        true
+    }
+
+    fn name(&self) -> NodeName {
+        NodeName::convert("help_overlay")
+    }
+}
+
+impl Loader for HelpOverlay {
+    fn load(c: &mut Canopy) -> Result<()> {
+        c.add_commands::<Self>()?;
+        ScrollCommands::load(c)?;
+        c.register_default_bindings("help_overlay", DEFAULT_BINDINGS)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use canopy::{
+        commands::{CommandArgs, CommandId, CommandInvocation, CommandNode},
+        inputmap::InputSpec,
+    };
+
+    use super::*;
+
+    struct Counter;
+
+    #[derive_commands]
+    impl Counter {
+        /// Count a hit.
+        #[command]
+        fn bump(&mut self, _c: &mut dyn Context) {}
+
+        /// Internal reset.
+        #[command(hidden)]
+        fn reset(&mut self, _c: &mut dyn Context) {}
+    }
+
+    impl Widget for Counter {}
+
+    fn binding(key: char, mode: &str, path_filter: &str, command: &'static str) -> CatalogBinding {
+        CatalogBinding {
+            input: InputSpec::Key(key.into()),
+            mode: mode.into(),
+            path_filter: path_filter.into(),
+            label: format!("run {command}"),
+            target: BindingTarget::Command(CommandInvocation {
+                id: CommandId(command),
+                args: CommandArgs::default(),
+            }),
+        }
+    }
+
+    #[test]
+    fn sheet_groups_by_mode_and_path_filter() {
+        let catalog = [
+            binding('k', "", "counter", "counter::bump"),
+            binding('a', "", "counter", "counter::bump"),
+            binding('r', "", "counter", "counter::reset"),
+            binding('q', "", "", "root::quit"),
+            binding('i', "insert", "", "counter::bump"),
+        ];
+        let sheet = build_sheet(&catalog, Counter::commands());
+        let entry = |key: &str, command: &str, doc: Option<&'static str>| SheetLine::Entry {
+            key: key.into(),
+            label: format!("run {command}"),
+            doc,
+        };
+        assert_eq!(
+            sheet,
+            [
+                SheetLine::Section("global".into()),
+                entry("q", "root::quit", None),
+                SheetLine::Blank,
+                SheetLine::Section("counter".into()),
+                entry("a", "counter::bump", Some("Count a hit.")),
+                entry("k", "counter::bump", Some("Count a hit.")),
+                SheetLine::Blank,
+                SheetLine::Section("global [insert]".into()),
+                entry("i", "counter::bump", Some("Count a hit.")),
+            ]
+        );
+    }
+}
@@ -1,290 +1,102 @@
 use canopy::{
-    Context, EventOutcome, ReadContext, Widget, command, cursor, derive_commands,
+    Accessible, Context, EventOutcome, ReadContext, Scrollable, Widget, command, cursor,
+    derive_commands,
     error::Result,
-    event::{Event, key},
-    geom::{Line, Point},
-    layout::{MeasureConstraints, Measurement, Size},
+    event::Event,
+    layout::{CanvasContext, MeasureConstraints, Measurement, Size},
     render::Render,
     state::NodeName,
-    text,
 };
 
-use crate::editor::{TextBuffer, TextPosition};
-
-/// Default tab stop width for single-line inputs.
-const DEFAULT_TAB_STOP: usize = 4;
-
-/// A single-line text buffer with horizontal scrolling.
-#[derive(Debug, Clone)]
-struct InputBuffer {
-    /// Rope-backed text storage.
-    buffer: TextBuffer,
-    /// Cached buffer contents for easy slicing.
-    value: String,
-    /// Column offset of the visible window.
-    scroll: usize,
-    /// Visible window width in columns.
-    view_width: usize,
-    /// Tab stop width in columns.
-    tab_stop: usize,
-}
-
-impl InputBuffer {
-    /// Construct a new input buffer with initial content.
-    fn new(start: impl Into<String>) -> Self {
-        let raw = start.into();
-        let value = sanitize_single_line(&raw);
-        let buffer = TextBuffer::new(value.clone());
-        let mut out = Self {
-            buffer,
-            value,
-            scroll: 0,
-            view_width: 0,
-            tab_stop: DEFAULT_TAB_STOP,
-        };
-        out.ensure_cursor_visible();
-        out
-    }
-
-    /// Set the visible window width.
-    fn set_display_width(&mut self, width: usize) {
-        self.view_width = width;
-        self.ensure_cursor_visible();
-    }
-
-    /// The location of the displayed cursor along the x axis.
-    fn cursor_display(&self) -> u32 {
-        let cursor_col = self.cursor_column();
-        cursor_col.saturating_sub(self.scroll) as u32
-    }
-
-    /// Return the visible text slice.
-    fn text(&self) -> &str {
-        if self.view_width == 0 {
-            return "";
-        }
-        let (start, end) = self.visible_range();
-        &self.value[start..end]
-    }
-
-    /// Return the raw input value.
-    fn value(&self) -> &str {
-        &self.value
-    }
-
-    /// Return the visible text for rendering.
-    fn render_text(&self) -> String {
-        if self.view_width == 0 {
-            return String::new();
-        }
-        let expanded = text::expand_tabs(&self.value, self.tab_stop);
-        let (out, _) = text::slice_by_columns(&expanded, self.scroll, self.view_width);
-        out.to_string()
-    }
-
-    /// Insert a character at the cursor position.
-    fn insert(&mut self, c: char) -> bool {
-        let insert = match c {
-            '\n' | '\r' => ' ',
-            _ => c,
-        };
-        self.buffer.insert_text(&insert.to_string());
-        self.sync_value();
-        self.ensure_cursor_visible();
-        true
-    }
-
-    /// Delete the character before the cursor.
-    fn backspace(&mut self) -> bool {
-        if self.buffer.delete_backward(false) {
-            self.sync_value();
-            self.ensure_cursor_visible();
-            true
-        } else {
-            false
-        }
-    }
-
-    /// Move the cursor left by one character.
-    fn left(&mut self) -> bool {
-        if self.buffer.move_left(false) {
-            self.ensure_cursor_visible();
-            true
-        } else {
-            false
-        }
-    }
-
-    /// Move the cursor right by one character.
-    fn right(&mut self) -> bool {
-        if self.buffer.move_right(false) {
-            self.ensure_cursor_visible();
-            true
-        } else {
-            false
-        }
-    }
-
-    /// Return the display width of the full buffer.
-    fn display_width(&self) -> u32 {
-        self.line_width() as u32
-    }
-
-    /// Update the cached value string from the rope.
-    fn sync_value(&mut self) {
-        self.value = self.buffer.line_text(0);
-    }
-
-    /// Compute the cursor column in display coordinates.
-    fn cursor_column(&self) -> usize {
-        self.buffer
-            .column_for_position(self.buffer.cursor(), self.tab_stop)
-    }
-
-    /// Compute the display width of the line.
-    fn line_width(&self) -> usize {
-        let len = self.buffer.line_char_len(0);
-        self.buffer
-            .column_for_position(TextPosition::new(0, len), self.tab_stop)
-    }
-
-    /// Compute the visible byte range for the current scroll state.
-    fn visible_range(&self) -> (usize, usize) {
-        if self.value.is_empty() || self.view_width == 0 {
-            return (0, 0);
-        }
-        let start_pos = self
-            .buffer
-            .position_for_column(0, self.scroll, self.tab_stop);
-        let end_col = self.scroll.saturating_add(self.view_width);
-        let end_pos = self.buffer.position_for_column(0, end_col, self.tab_stop);
-        let start = byte_index_for_char(&self.value, start_pos.column);
-        let end = byte_index_for_char(&self.value, end_pos.column);
-        if start >= end {
-            (start, start)
-        } else {
-            (start, end)
-        }
-    }
-
-    /// Ensure the cursor stays within the visible window.
-    fn ensure_cursor_visible(&mut self) {
-        if self.view_width == 0 {
-            self.scroll = 0;
-            return;
-        }
-        let cursor_col = self.cursor_column();
-        if cursor_col < self.scroll {
-            self.scroll = cursor_col;
-        } else {
-            let window_end = self.scroll.saturating_add(self.view_width);
-            if cursor_col >= window_end {
-                let delta = cursor_col.saturating_sub(window_end).saturating_add(1);
-                self.scroll = self.scroll.saturating_add(delta);
-            }
-        }
-
-        let text_width = self.line_width();
-        if text_width <= self.view_width {
-            self.scroll = 0;
-        } else if self.scroll > text_width.saturating_sub(1) {
-            self.scroll = text_width.saturating_sub(1);
-        }
-    }
-}
+use crate::{
+    editor::{Editor, EditorConfig, TextPosition},
+    spell::SpellCheck,
+};
 
-/// Single-line text input widget.
+/// Single-line text input widget. Text is edited by an [`Editor`] configured with
+/// [`EditorConfig::single_line`], so inputs share the editor's cursor movement, paste
+/// handling, horizontal scrolling, and completion popup, which drops below the input.
 pub struct Input {
-    /// Text buffer for the input.
-    buffer: InputBuffer,
+    /// Single-line editor holding the text.
+    editor: Editor,
 }
 
 #[derive_commands]
 impl Input {
-    /// Construct a new input with initial text.
+    /// Construct a new input with initial text, with the cursor at its end.
     pub fn new(txt: impl Into<String>) -> Self {
-        Self {
-            buffer: InputBuffer::new(txt),
-        }
+        let mut editor = Editor::with_config("", EditorConfig::single_line());
+        set_line(&mut editor, txt);
+        Self { editor }
     }
 
-    /// Return the current input text.
-    pub fn text(&self) -> &str {
-        self.buffer.text()
+    /// Build an input that underlines misspelled words with the `editor/misspelled` style
+    /// and offers corrections through `spell_suggest`.
+    pub fn with_spell_check(mut self, spell: SpellCheck) -> Self {
+        self.editor.set_spell_check(Some(spell));
+        self
     }
 
-    /// Return the raw input value without padding.
-    pub fn value(&self) -> &str {
-        self.buffer.value()
+    /// Install or remove the spell checker.
+    pub fn set_spell_check(&mut self, spell: Option<SpellCheck>) {
+        self.editor.set_spell_check(spell);
     }
 
-    /// Replace the input value and reset the cursor.
-    pub fn set_value(&mut self, value: impl Into<String>) {
-        self.buffer = InputBuffer::new(value);
+    /// Return the input text.
+    pub fn value(&self) -> String {
+        self.editor.text()
     }
 
-    /// Move the cursor left.
-    #[command]
-    fn left(&mut self, _c: &mut dyn Context) {
-        let _ = self.buffer.left();
-    }
-
-    /// Move the cursor right.
-    #[command]
-    fn right(&mut self, _c: &mut dyn Context) {
-        let _ = self.buffer.right();
+    /// Replace the input value, leaving the cursor at its end.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        set_line(&mut self.editor, value);
     }
 
-    /// Delete a character at the input location.
+    /// Offer spelling corrections for the word at the cursor in the completion popup.
     #[command]
-    fn backspace(&mut self, _c: &mut dyn Context) {
-        let _ = self.buffer.backspace();
+    pub fn spell_suggest(&mut self, c: &mut dyn Context) {
+        self.editor.spell_suggest(c);
     }
 }
 
+impl Scrollable for Input {}
+
 impl Widget for Input {
+    fn scrollable(&mut self) -> Option<&mut dyn Scrollable> {
+        Some(self)
+    }
+
     fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
         true
     }
 
+    fn on_mount(&mut self, c: &mut dyn Context) -> Result<()> {
+        let node = c.node_id();
+        c.set_unclipped(node, true)
+    }
+
+    fn accessible(&self, ctx: &dyn ReadContext) -> Option<Accessible> {
+        self.editor.accessible(ctx)
+    }
+
     fn cursor(&self) -> Option<cursor::Cursor> {
-        Some(cursor::Cursor {
-            location: Point {
-                x: self.buffer.cursor_display(),
-                y: 0,
-            },
-            shape: cursor::CursorShape::Block,
-            blink: true,
-        })
+        self.editor.cursor()
     }
 
     fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
-        let view = ctx.view();
-        let view_rect = view.view_rect();
-        let content_origin = view.content_origin();
-        self.buffer.set_display_width(view_rect.w as usize);
-        let line = Line::new(content_origin.x, content_origin.y, view_rect.w);
-        let content = self.buffer.render_text();
-        r.text("text", line, &content)
+        self.editor.render(r, ctx)
     }
 
-    fn on_event(&mut self, event: &Event, _ctx: &mut dyn Context) -> Result<EventOutcome> {
-        let outcome = match event {
-            Event::Key(key::Key {
-                key: key::KeyCode::Char(c),
-                ..
-            }) => {
-                self.buffer.insert(*c);
-                EventOutcome::Handle
-            }
-            _ => EventOutcome::Ignore,
-        };
-        Ok(outcome)
+    fn on_event(&mut self, event: &Event, ctx: &mut dyn Context) -> Result<EventOutcome> {
+        Ok(self.editor.handle_event(event, ctx))
     }
 
     fn measure(&self, c: MeasureConstraints) -> Measurement {
-        let text_len = self.buffer.display_width().max(1);
-        c.clamp(Size::new(text_len, 1))
+        self.editor.measure(c)
+    }
+
+    fn canvas(&self, view: Size<u32>, ctx: &CanvasContext) -> Size<u32> {
+        self.editor.canvas(view, ctx)
     }
 
     fn name(&self) -> NodeName {
@@ -292,66 +104,67 @@ impl Widget for Input {
     }
 }
 
-/// Replace newlines in single-line input values.
-fn sanitize_single_line(value: &str) -> String {
-    value.replace(['\n', '\r'], " ")
-}
-
-/// Convert a char index to a byte index in a string.
-fn byte_index_for_char(text: &str, char_index: usize) -> usize {
-    if char_index == 0 {
-        return 0;
-    }
-    if char_index >= text.chars().count() {
-        return text.len();
-    }
-    text.char_indices()
-        .nth(char_index)
-        .map(|(idx, _)| idx)
-        .unwrap_or(text.len())
+/// Replace the text of a single-line editor, leaving the cursor at its end.
+pub(crate) fn set_line(editor: &mut Editor, text: impl Into<String>) {
+    editor.set_text(text);
+    editor.set_cursor_position(TextPosition::new(0, usize::MAX));
 }
 
 #[cfg(test)]
 mod tests {
-    use unicode_width::UnicodeWidthStr;
+    use canopy::{Canopy, Loader, error::Result, event::key::KeyCode, testing::harness::Harness};
 
-    use super::InputBuffer;
+    use super::Input;
+    use crate::{SpellCheck, WordList};
 
-    #[test]
-    fn input_buffer_handles_multibyte_chars() {
-        let mut buf = InputBuffer::new("a");
-        buf.set_display_width(10);
-        let accent = '\u{00e9}';
-        buf.insert(accent);
-        let expected = format!("a{accent}");
-        assert_eq!(buf.value(), expected);
-        assert_eq!(
-            buf.cursor_display(),
-            UnicodeWidthStr::width(expected.as_str()) as u32
-        );
-        buf.left();
-        assert_eq!(buf.cursor_display(), UnicodeWidthStr::width("a") as u32);
-        buf.backspace();
-        assert_eq!(buf.value(), accent.to_string());
+    impl Loader for Input {
+        fn load(c: &mut Canopy) -> Result<()> {
+            c.add_commands::<Self>()
+        }
     }
 
     #[test]
-    fn input_buffer_handles_grapheme_clusters() {
+    fn input_edits_multibyte_chars_and_grapheme_clusters() -> Result<()> {
         let astronaut = "\u{1f469}\u{200d}\u{1f680}";
-        let mut buf = InputBuffer::new(format!("a{astronaut}b"));
-        buf.set_display_width(10);
-        let expected = format!("a{astronaut}b");
+        let mut h = Harness::builder(Input::new(format!("a{astronaut}b")))
+            .size(10, 1)
+            .build()?;
+        h.key(KeyCode::Left)?;
+        h.key(KeyCode::Backspace)?;
+        h.type_text("\u{00e9}")?;
         assert_eq!(
-            buf.cursor_display(),
-            UnicodeWidthStr::width(expected.as_str()) as u32
+            h.with_root_widget(|input: &mut Input| input.value()),
+            "a\u{00e9}b"
         );
-        buf.left();
-        let expected = format!("a{astronaut}");
+        Ok(())
+    }
+
+    #[test]
+    fn input_leaves_enter_and_vertical_keys_to_the_host() -> Result<()> {
+        let mut h = Harness::builder(Input::new("one")).size(10, 1).build()?;
+        h.key(KeyCode::Enter)?;
+        h.key(KeyCode::Up)?;
+        h.type_text(" two")?;
         assert_eq!(
-            buf.cursor_display(),
-            UnicodeWidthStr::width(expected.as_str()) as u32
+            h.with_root_widget(|input: &mut Input| input.value()),
+            "one two"
         );
-        buf.backspace();
-        assert_eq!(buf.value(), "ab");
+        Ok(())
+    }
+
+    #[test]
+    fn input_offers_spelling_corrections_below_the_line() -> Result<()> {
+        let spell = SpellCheck::new(WordList::new(["the", "cat"]));
+        let mut h = Harness::builder(Input::new("teh").with_spell_check(spell))
+            .size(10, 3)
+            .build()?;
+        h.key(KeyCode::Left)?;
+        h.script("input.spell_suggest()")?;
+        h.render()?;
+        assert_eq!(h.buf().lines()[1].trim(), "the");
+        assert!(h.overflows().is_empty());
+        h.key(KeyCode::Tab)?;
+        assert_eq!(h.with_root_widget(|input: &mut Input| input.value()), "the");
+        Ok(())
     }
 }
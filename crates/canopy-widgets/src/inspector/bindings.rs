@@ -0,0 +1,323 @@
+//! Command and binding browser for the inspector widget.
+
+use std::time::Duration;
+
+use canopy::{
+    Canopy, Context, EventOutcome, Loader, ReadContext, ScrollCommands, Scrollable, Widget,
+    command,
+    commands::{CommandArgs, CommandInvocation, CommandParamKind, CommandSpec},
+    derive_commands,
+    error::Result,
+    event::{Event, key},
+    help::CatalogBinding,
+    inputmap::BindingTarget,
+    layout::{CanvasContext, Layout, Size},
+    render::Render,
+    state::NodeName,
+};
+
+use super::panel::{self, PanelView};
+
+/// Number of header lines above the rows.
+const HEADER: u32 = 1;
+
+/// One entry in the browser.
+enum Row {
+    /// A registered command.
+    Command(&'static CommandSpec),
+    /// A binding in some input mode.
+    Binding(CatalogBinding),
+}
+
+impl Row {
+    /// Describe the row for display and searching.
+    fn text(&self) -> String {
+        match self {
+            Self::Command(spec) => format!(
+                "cmd  {:<40} {}",
+                spec.signature(),
+                spec.doc.short.unwrap_or_default()
+            ),
+            Self::Binding(binding) => format!(
+                "key  {:<9} {:<14} {:<20} {}",
+                if binding.mode.is_empty() {
+                    "default"
+                } else {
+                    &binding.mode
+                },
+                binding.input.to_string(),
+                binding.path_filter,
+                binding.label
+            ),
+        }
+    }
+}
+
+/// Inspector panel listing every registered command and every binding.
+///
+/// Typing filters the list. Simulating a row dispatches its command from the root.
+pub struct Bindings {
+    /// Rows passing the search filter.
+    rows: Vec<Row>,
+    /// Case-insensitive search text.
+    query: String,
+    /// Selected row index.
+    selected: usize,
+    /// Outcome of the last simulation, shown in the header.
+    status: String,
+}
+
+#[derive_commands]
+impl Bindings {
+    /// Construct a command and binding browser.
+    pub fn new() -> Self {
+        Self {
+            rows: Vec::new(),
+            query: String::new(),
+            selected: 0,
+            status: String::new(),
+        }
+    }
+
+    /// Rebuild the rows from the registered commands and the captured binding catalog.
+    fn refresh(&mut self, ctx: &dyn ReadContext) {
+        let query = self.query.to_lowercase();
+        self.rows = ctx
+            .command_specs()
+            .into_iter()
+            .filter(|spec| !spec.doc.hidden)
+            .map(Row::Command)
+            .chain(ctx.binding_catalog().get().into_iter().map(Row::Binding))
+            .filter(|row| row.text().to_lowercase().contains(&query))
+            .collect();
+        self.selected = self.selected.min(self.rows.len().saturating_sub(1));
+    }
+
+    /// Move selection by a signed offset.
+    /// @param delta Signed row delta. Positive moves down and negative moves up.
+    #[command]
+    pub fn select_by(&mut self, c: &mut dyn Context, delta: i32) {
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta as isize)
+            .min(self.rows.len().saturating_sub(1));
+        panel::keep_visible(c, HEADER, self.selected);
+    }
+
+    /// Move selection by a signed number of pages. Returns `true` if the selection moved.
+    fn page(&mut self, c: &mut dyn Context, pages: i32) -> bool {
+        let before = self.selected;
+        let height = c.view().content_size().h.saturating_sub(HEADER).max(1) as i32;
+        self.select_by(c, pages.saturating_mul(height));
+        self.selected != before
+    }
+
+    /// Delete the last character of the search text.
+    #[command]
+    pub fn backspace(&mut self, _c: &mut dyn Context) {
+        self.query.pop();
+    }
+
+    /// Clear the search text.
+    #[command]
+    pub fn clear_search(&mut self, _c: &mut dyn Context) {
+        self.query.clear();
+    }
+
+    /// Dispatch the selected command, or the commands run by the selected binding, from
+    /// the root node.
+    #[command]
+    pub fn simulate(&mut self, c: &mut dyn Context) {
+        let invocations = match self.rows.get(self.selected) {
+            None => return,
+            Some(Row::Command(spec)) => {
+                if spec
+                    .params
+                    .iter()
+                    .any(|p| p.kind == CommandParamKind::User && !p.optional)
+                {
+                    self.status = format!("{} needs arguments", spec.signature());
+                    return;
+                }
+                vec![CommandInvocation {
+                    id: spec.id,
+                    args: CommandArgs::default(),
+                }]
+            }
+            Some(Row::Binding(binding)) => match &binding.target {
+                BindingTarget::Command(inv) => vec![inv.clone()],
+                BindingTarget::CommandSequence(invs) => invs.clone(),
+                _ => {
+                    self.status = "only command bindings can be simulated".into();
+                    return;
+                }
+            },
+        };
+        let root = c.root_id();
+        for inv in &invocations {
+            if let Err(err) = c.dispatch_command_from(root, inv) {
+                self.status = format!("{} -> error: {err}", inv.id);
+                return;
+            }
+        }
+        self.status = invocations
+            .iter()
+            .map(|inv| format!("{} -> ok", inv.id))
+            .collect::<Vec<_>>()
+            .join(", ");
+    }
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scrollable for Bindings {
+    fn page_up(&mut self, c: &mut dyn Context) -> bool {
+        self.page(c, -1)
+    }
+
+    fn page_down(&mut self, c: &mut dyn Context) -> bool {
+        self.page(c, 1)
+    }
+}
+
+impl Widget for Bindings {
+    fn scrollable(&mut self) -> Option<&mut dyn Scrollable> {
+        Some(self)
+    }
+
+    fn layout(&self) -> Layout {
+        Layout::fill()
+    }
+
+    fn canvas(&self, view: Size<u32>, _ctx: &CanvasContext) -> Size<u32> {
+        panel::canvas(view, HEADER, self.rows.len())
+    }
+
+    fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+        self.refresh(ctx);
+        let Some(view) = PanelView::clear(r, ctx, HEADER)? else {
+            return Ok(());
+        };
+
+        let mut header = format!("search: {}_ | {} match(es)", self.query, self.rows.len());
+        if !self.status.is_empty() {
+            header.push_str(&format!(" | {}", self.status));
+        }
+        r.text("text", view.header_line(0), &header)?;
+
+        let rows = view.rows();
+        for (index, row) in self.rows.iter().enumerate().take(rows.end).skip(rows.start) {
+            if index == self.selected {
+                view.mark_selected(r, index)?;
+            }
+            r.text("text", view.row(index, 2).line(0), &row.text())?;
+        }
+        Ok(())
+    }
+
+    fn poll(&mut self, c: &mut dyn Context) -> Option<Duration> {
+        if !c.view().is_zero() {
+            c.request_binding_catalog();
+        }
+        panel::refresh(c)
+    }
+
+    fn on_event(&mut self, event: &Event, ctx: &mut dyn Context) -> Result<EventOutcome> {
+        let outcome = match event {
+            Event::Key(key::Key {
+                key: key::KeyCode::Char(c),
+                mods,
+            }) if !mods.ctrl && !mods.alt => {
+                self.query.push(*c);
+                self.selected = 0;
+                ctx.scroll_to(0, 0);
+                EventOutcome::Handle
+            }
+            _ => EventOutcome::Ignore,
+        };
+        Ok(outcome)
+    }
+
+    fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+        true
+    }
+
+    fn name(&self) -> NodeName {
+        NodeName::convert("bindings")
+    }
+}
+
+impl Loader for Bindings {
+    fn load(c: &mut Canopy) -> Result<()> {
+        c.add_commands::<Self>()?;
+        ScrollCommands::load(c)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use canopy::testing::harness::Harness;
+
+    use super::*;
+
+    struct Counter {
+        hits: usize,
+    }
+
+    #[derive_commands]
+    impl Counter {
+        /// Count a hit.
+        #[command]
+        fn bump(&mut self, _c: &mut dyn Context) {
+            self.hits += 1;
+        }
+    }
+
+    impl Widget for Counter {
+        fn name(&self) -> NodeName {
+            NodeName::convert("counter")
+        }
+    }
+
+    #[test]
+    fn search_filters_and_simulate_dispatches() -> Result<()> {
+        let mut harness = Harness::builder(Bindings::new()).size(100, 6).build()?;
+        harness.canopy.add_commands::<Counter>()?;
+        let counter = harness
+            .with_root_context(|_: &mut Bindings, ctx| ctx.add_child(Counter { hits: 0 }))?;
+
+        harness.type_text("bump")?;
+        harness.render()?;
+        let screen = harness.buf().screen_text();
+        assert!(screen.contains("search: bump_ | 1 match(es)"));
+        assert!(screen.contains("counter::bump()"));
+
+        harness.with_root_context(|bindings: &mut Bindings, ctx| {
+            bindings.simulate(ctx);
+            assert_eq!(bindings.status, "counter::bump -> ok");
+            bindings.clear_search(ctx);
+            Ok(())
+        })?;
+        assert_eq!(harness.with_widget(counter, |c: &mut Counter| c.hits), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn paging_scrolls_the_selection_into_view() -> Result<()> {
+        let mut harness = Harness::builder(Bindings::new()).size(100, 4).build()?;
+        harness.render()?;
+        harness.script("scrollable.page_down()")?;
+        harness.render()?;
+        let (selected, top) = harness.with_root_context(|bindings: &mut Bindings, ctx| {
+            Ok((bindings.selected, ctx.view().tl.y))
+        })?;
+        assert_eq!((selected, top), (3, 1));
+        assert!(harness.buf().lines()[3].starts_with('\u{2588}'));
+        Ok(())
+    }
+}
@@ -0,0 +1,367 @@
+//! Event and command log for the inspector widget.
+
+use std::{
+    collections::VecDeque,
+    result::Result as StdResult,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use canopy::{
+    Canopy, Context, Core, HookHandle, Loader, NodeId, ReadContext, RoutePhase, RouteTraceEntry,
+    ScrollCommands, Scrollable, Widget, command,
+    commands::{ArgValue, CommandError, CommandInvocation},
+    derive_commands,
+    error::Result,
+    event::Event,
+    layout::{CanvasContext, Layout, Size},
+    render::Render,
+    state::NodeName,
+};
+
+use super::panel::{self, PanelView};
+
+/// Maximum number of records retained.
+const CAPACITY: usize = 1000;
+/// Number of header lines above the records.
+const HEADER: u32 = 1;
+
+/// Category of a recorded dispatch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// Key input.
+    Key,
+    /// Mouse input.
+    Mouse,
+    /// Poll and timer wakeups.
+    Poll,
+    /// Command dispatch.
+    Command,
+    /// Any other event.
+    Other,
+}
+
+impl Kind {
+    /// Kinds in filter cycle order.
+    const ALL: [Self; 5] = [
+        Self::Key,
+        Self::Mouse,
+        Self::Poll,
+        Self::Command,
+        Self::Other,
+    ];
+
+    /// Return a short label for display.
+    fn label(self) -> &'static str {
+        match self {
+            Self::Key => "key",
+            Self::Mouse => "mouse",
+            Self::Poll => "poll",
+            Self::Command => "command",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// One recorded dispatch. Identical consecutive dispatches share a record.
+struct Record {
+    /// Dispatch category.
+    kind: Kind,
+    /// Description of the dispatch and its outcome.
+    text: String,
+    /// Number of identical consecutive dispatches.
+    count: usize,
+}
+
+/// Records shared between the dispatch hooks and the panel.
+#[derive(Default)]
+struct Feed {
+    /// Retained records, oldest first.
+    records: VecDeque<Record>,
+    /// Whether recording is paused.
+    paused: bool,
+    /// Whether the panel was visible when it last polled.
+    visible: bool,
+}
+
+impl Feed {
+    /// Whether dispatches should be recorded: the panel is visible and not paused.
+    fn recording(&self) -> bool {
+        self.visible && !self.paused
+    }
+
+    /// Append a record, folding it into the newest record if identical.
+    fn push(&mut self, kind: Kind, text: String) {
+        if let Some(last) = self.records.back_mut()
+            && last.kind == kind
+            && last.text == text
+        {
+            last.count += 1;
+            return;
+        }
+        self.records.push_back(Record {
+            kind,
+            text,
+            count: 1,
+        });
+        if self.records.len() > CAPACITY {
+            self.records.pop_front();
+        }
+    }
+}
+
+/// Inspector panel logging input events and command dispatches, newest first.
+pub struct Events {
+    /// Records fed by the dispatch hooks.
+    feed: Arc<Mutex<Feed>>,
+    /// Only show records of this kind, if set.
+    filter: Option<Kind>,
+    /// Registrations of the hooks feeding the panel, removed when the panel is dropped.
+    hooks: Vec<HookHandle>,
+}
+
+#[derive_commands]
+impl Events {
+    /// Construct an event log panel. It records nothing until connected with `install`.
+    pub fn new() -> Self {
+        Self {
+            feed: Arc::new(Mutex::new(Feed::default())),
+            filter: None,
+            hooks: Vec::new(),
+        }
+    }
+
+    /// Create the panel and register the hooks that feed it.
+    pub fn install(core: &mut Core) -> NodeId {
+        let mut events = Self::new();
+        events.hooks = connect(&events.feed, core);
+        core.create_detached(events)
+    }
+
+    /// Return the number of records passing the current filter.
+    fn visible_len(&self, feed: &Feed) -> usize {
+        feed.records
+            .iter()
+            .filter(|r| self.filter.is_none_or(|kind| r.kind == kind))
+            .count()
+    }
+
+    /// Pause or resume recording.
+    #[command]
+    pub fn toggle_pause(&mut self, _c: &mut dyn Context) {
+        let mut feed = self.feed.lock().unwrap();
+        feed.paused = !feed.paused;
+    }
+
+    /// Cycle the filter through each kind of dispatch and back to showing everything.
+    #[command]
+    pub fn cycle_filter(&mut self, c: &mut dyn Context) {
+        self.filter = match self.filter {
+            None => Some(Kind::ALL[0]),
+            Some(kind) => Kind::ALL
+                .iter()
+                .position(|k| *k == kind)
+                .and_then(|i| Kind::ALL.get(i + 1).copied()),
+        };
+        c.scroll_to(0, 0);
+    }
+
+    /// Discard all records.
+    #[command]
+    pub fn clear(&mut self, c: &mut dyn Context) {
+        self.feed.lock().unwrap().records.clear();
+        c.scroll_to(0, 0);
+    }
+}
+
+/// Register dispatch hooks that append to `feed` while it is recording.
+fn connect(feed: &Arc<Mutex<Feed>>, core: &mut Core) -> Vec<HookHandle> {
+    let events = Arc::clone(feed);
+    let after_event = core.after_event(move |event, trace| {
+        let mut feed = events.lock().unwrap();
+        if !feed.recording() {
+            return;
+        }
+        if let Some((kind, text)) = describe_event(event, trace) {
+            feed.push(kind, text);
+        }
+    });
+    let commands = Arc::clone(feed);
+    let after_command = core.after_command(move |cmd, result| {
+        let mut feed = commands.lock().unwrap();
+        if feed.recording() {
+            feed.push(Kind::Command, describe_command(cmd, result));
+        }
+    });
+    vec![after_event, after_command]
+}
+
+/// Describe an event and, for input, the node that handled it.
+fn describe_event(event: &Event, trace: &[RouteTraceEntry]) -> Option<(Kind, String)> {
+    let described = match event {
+        Event::Key(key) => (Kind::Key, format!("{key} {}", route_outcome(trace))),
+        Event::Mouse(m) => (
+            Kind::Mouse,
+            format!(
+                "{:?} {:?} {},{} {}",
+                m.action,
+                m.button,
+                m.location.x,
+                m.location.y,
+                route_outcome(trace)
+            ),
+        ),
+        Event::Poll(ids) => (Kind::Poll, format!("poll {} node(s)", ids.len())),
+        Event::Timers(due) => (Kind::Poll, format!("timers {} due", due.len())),
+        Event::Resize(size) => (Kind::Other, format!("resize {}x{}", size.w, size.h)),
+        Event::Paste(content) => (
+            Kind::Other,
+            format!("paste {} char(s)", content.chars().count()),
+        ),
+        Event::FocusGained => (Kind::Other, "terminal focus gained".into()),
+        Event::FocusLost => (Kind::Other, "terminal focus lost".into()),
+        Event::App(app) => (Kind::Other, format!("app event from {}", app.source)),
+        Event::Timer(_) | Event::Scroll(_) | Event::Wake => return None,
+    };
+    Some(described)
+}
+
+/// Describe where routing ended according to the route trace.
+fn route_outcome(trace: &[RouteTraceEntry]) -> String {
+    trace
+        .iter()
+        .rev()
+        .find_map(|entry| match entry.phase {
+            RoutePhase::Handled => Some(format!("-> {} ({})", entry.path, entry.detail)),
+            RoutePhase::Unhandled => Some("-> unhandled".to_string()),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Describe a command dispatch and its result.
+fn describe_command(cmd: &CommandInvocation, result: &StdResult<ArgValue, CommandError>) -> String {
+    match result {
+        Ok(_) => format!("{} -> ok", cmd.id),
+        Err(err) => format!("{} -> error: {err}", cmd.id),
+    }
+}
+
+impl Default for Events {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scrollable for Events {}
+
+impl Widget for Events {
+    fn scrollable(&mut self) -> Option<&mut dyn Scrollable> {
+        Some(self)
+    }
+
+    fn layout(&self) -> Layout {
+        Layout::fill()
+    }
+
+    fn canvas(&self, view: Size<u32>, _ctx: &CanvasContext) -> Size<u32> {
+        let rows = self.visible_len(&self.feed.lock().unwrap());
+        panel::canvas(view, HEADER, rows)
+    }
+
+    fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+        let Some(view) = PanelView::clear(r, ctx, HEADER)? else {
+            return Ok(());
+        };
+        let feed = self.feed.lock().unwrap();
+        let header = format!(
+            "{} | filter: {} | {} record(s)",
+            if feed.paused { "paused" } else { "recording" },
+            self.filter.map_or("all", Kind::label),
+            self.visible_len(&feed)
+        );
+        r.text("text", view.header_line(0), &header)?;
+
+        let rows = view.rows();
+        let records = feed
+            .records
+            .iter()
+            .rev()
+            .filter(|r| self.filter.is_none_or(|kind| r.kind == kind));
+        for (index, record) in records.enumerate().take(rows.end).skip(rows.start) {
+            let mut text = format!("{:<7} {}", record.kind.label(), record.text);
+            if record.count > 1 {
+                text.push_str(&format!(" (x{})", record.count));
+            }
+            r.text("text", view.row(index, 0).line(0), &text)?;
+        }
+        Ok(())
+    }
+
+    fn poll(&mut self, c: &mut dyn Context) -> Option<Duration> {
+        self.feed.lock().unwrap().visible = !c.view().is_zero();
+        panel::refresh(c)
+    }
+
+    fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+        true
+    }
+
+    fn name(&self) -> NodeName {
+        NodeName::convert("events")
+    }
+}
+
+impl Loader for Events {
+    fn load(c: &mut Canopy) -> Result<()> {
+        c.add_commands::<Self>()?;
+        ScrollCommands::load(c)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use canopy::testing::harness::Harness;
+
+    use super::*;
+
+    #[test]
+    fn records_routed_keys_until_paused() -> Result<()> {
+        let mut harness = Harness::builder(Events::new()).size(60, 5).build()?;
+        let feed = harness.with_root_widget(|events: &mut Events| Arc::clone(&events.feed));
+        let _hooks = connect(&feed, harness.canopy.core_mut());
+        // The panel records only once a poll has seen it visible.
+        harness.render()?;
+
+        harness.key('x')?;
+        harness.key('x')?;
+        {
+            let feed = feed.lock().unwrap();
+            let keys: Vec<_> = feed
+                .records
+                .iter()
+                .filter(|r| r.kind == Kind::Key)
+                .collect();
+            assert_eq!(keys.len(), 1);
+            assert_eq!(keys[0].count, 2);
+            assert!(keys[0].text.starts_with("x -> "));
+        }
+
+        harness.with_root_context(|events: &mut Events, ctx| {
+            events.toggle_pause(ctx);
+            events.cycle_filter(ctx);
+            Ok(())
+        })?;
+        harness.key('y')?;
+        assert!(
+            feed.lock()
+                .unwrap()
+                .records
+                .iter()
+                .all(|r| !r.text.starts_with('y'))
+        );
+        assert!(harness.buf().screen_text().contains("paused | filter: key"));
+        Ok(())
+    }
+}
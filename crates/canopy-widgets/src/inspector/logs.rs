@@ -1,20 +1,16 @@
 //! Log panel for the inspector widget.
 
-use std::{
-    io::{Result as IoResult, Write},
-    sync::{Arc, Mutex},
-    time::Duration,
-};
+use std::time::Duration;
 
 use canopy::{
     Canopy, Context, Loader, ReadContext, Widget, command, derive_commands,
     error::{Error, Result},
     geom::{Direction, Rect},
     layout::{CanvasContext, Constraint, Layout, MeasureConstraints, Measurement, Size},
+    logging::{self, LogBuffer},
     render::Render,
     state::NodeName,
 };
-use tracing_subscriber::fmt;
 
 use crate::{List, Selectable};
 
@@ -101,32 +97,15 @@ impl Widget for LogEntry {
     }
 }
 
-/// Log writer that appends to a shared buffer.
-struct LogWriter {
-    /// Shared log buffer.
-    buf: Arc<Mutex<Vec<String>>>,
-}
-
-impl Write for LogWriter {
-    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
-        self.buf
-            .lock()
-            .unwrap()
-            .push(String::from_utf8_lossy(buf).to_string().trim().to_string());
-        Ok(buf.len())
-    }
-
-    fn flush(&mut self) -> IoResult<()> {
-        Ok(())
-    }
-}
-
 /// Inspector log panel.
+///
+/// Shows records from the shared log buffer, which the application feeds by adding
+/// [`canopy::tracing_layer`] to its subscriber.
 pub struct Logs {
-    /// Whether logging is initialized.
-    started: bool,
     /// Shared log buffer.
-    buf: Arc<Mutex<Vec<String>>>,
+    buffer: LogBuffer,
+    /// Sequence number of the next unread record.
+    cursor: u64,
 }
 
 impl Widget for Logs {
@@ -153,23 +132,6 @@ impl Widget for Logs {
 
     fn poll(&mut self, c: &mut dyn Context) -> Option<Duration> {
         self.ensure_tree(c).ok()?;
-
-        if !self.started {
-            let format = fmt::format()
-                .with_level(true)
-                .with_line_number(true)
-                .with_ansi(false)
-                .without_time()
-                .compact();
-
-            let buf = self.buf.clone();
-            tracing_subscriber::fmt()
-                .with_writer(move || -> LogWriter { LogWriter { buf: buf.clone() } })
-                .event_format(format)
-                .init();
-            self.started = true;
-        }
-
         self.flush_buffer(c).ok();
         Some(Duration::from_millis(100))
     }
@@ -184,8 +146,8 @@ impl Logs {
     /// Construct a log panel.
     pub fn new() -> Self {
         Self {
-            started: false,
-            buf: Arc::new(Mutex::new(vec![])),
+            buffer: logging::log_buffer().clone(),
+            cursor: 0,
         }
     }
 
@@ -211,19 +173,14 @@ impl Logs {
         c.with_child::<ListSlot, _>(f)
     }
 
-    /// Drain buffered log lines into the list.
-    fn flush_buffer(&self, c: &mut dyn Context) -> Result<()> {
-        let buf = self.buf.clone();
-        let mut b = buf.lock().unwrap();
-        let vals: Vec<String> = b.drain(..).collect();
-        drop(b);
-
+    /// Append unread log records to the list.
+    fn flush_buffer(&mut self, c: &mut dyn Context) -> Result<()> {
         if !c.has_child::<ListSlot>() {
             return Ok(());
         }
 
-        for line in vals {
-            let mut entry = Some(LogEntry::new(line));
+        for record in self.buffer.read(&mut self.cursor) {
+            let mut entry = Some(LogEntry::new(record.to_string()));
             c.with_child::<ListSlot, _>(|list, ctx| {
                 if let Some(e) = entry.take() {
                     list.append(ctx, e)?;
@@ -1,13 +1,30 @@
+/// Command and binding browser panel widget.
+mod bindings;
+/// Event and command log panel widget.
+mod events;
 /// Log panel widget.
 mod logs;
+/// Node tree panel widget.
+mod nodes;
+/// Helpers shared by the panels.
+mod panel;
+/// Frame profiler panel widget.
+mod perf;
+/// Style editor panel widget.
+mod styles;
 /// Inspector view layout.
 mod view;
 
+use bindings::Bindings;
 use canopy::{
     Canopy, Core, Loader, NodeId, ReadContext, Widget, derive_commands, error::Result,
     layout::Layout, render::Render, state::NodeName,
 };
+use events::Events;
 use logs::Logs;
+use nodes::Nodes;
+use perf::Perf;
+use styles::Styles;
 
 use crate::{frame, tabs};
 
@@ -17,6 +34,143 @@ canopy.bind_with("Tab", { path = "inspector/", desc = "Next tab" }, function()
     tabs.select_by(1)
 end)
 
+canopy.bind_with("j", { path = "nodes", desc = "Next node" }, function()
+    nodes.select_by(1)
+end)
+canopy.bind_with("k", { path = "nodes", desc = "Previous node" }, function()
+    nodes.select_by(-1)
+end)
+canopy.bind_with("Down", { path = "nodes", desc = "Next node" }, function()
+    nodes.select_by(1)
+end)
+canopy.bind_with("Up", { path = "nodes", desc = "Previous node" }, function()
+    nodes.select_by(-1)
+end)
+canopy.bind_with("g", { path = "nodes", desc = "First node" }, function()
+    nodes.select_first()
+end)
+canopy.bind_with("G", { path = "nodes", desc = "Last node" }, function()
+    nodes.select_last()
+end)
+canopy.bind_with("h", { path = "nodes", desc = "Toggle on-screen highlight" }, function()
+    nodes.toggle_highlight()
+end)
+canopy.bind_with("PageDown", { path = "nodes", desc = "Page down" }, function()
+    scrollable.page_down()
+end)
+canopy.bind_with("PageUp", { path = "nodes", desc = "Page up" }, function()
+    scrollable.page_up()
+end)
+
+canopy.bind_with("p", { path = "events", desc = "Pause or resume recording" }, function()
+    events.toggle_pause()
+end)
+canopy.bind_with("f", { path = "events", desc = "Cycle event filter" }, function()
+    events.cycle_filter()
+end)
+canopy.bind_with("C", { path = "events", desc = "Clear event log" }, function()
+    events.clear()
+end)
+canopy.bind_with("j", { path = "events", desc = "Scroll toward older events" }, function()
+    scrollable.down()
+end)
+canopy.bind_with("k", { path = "events", desc = "Scroll toward newer events" }, function()
+    scrollable.up()
+end)
+canopy.bind_with("Down", { path = "events", desc = "Scroll toward older events" }, function()
+    scrollable.down()
+end)
+canopy.bind_with("Up", { path = "events", desc = "Scroll toward newer events" }, function()
+    scrollable.up()
+end)
+canopy.bind_with("PageDown", { path = "events", desc = "Page toward older events" }, function()
+    scrollable.page_down()
+end)
+canopy.bind_with("PageUp", { path = "events", desc = "Page toward newer events" }, function()
+    scrollable.page_up()
+end)
+canopy.bind_with("g", { path = "events", desc = "Jump to newest events" }, function()
+    scrollable.top()
+end)
+
+canopy.bind_with("r", { path = "perf", desc = "Pause or resume profiling" }, function()
+    perf.toggle_recording()
+end)
+canopy.bind_with("s", { path = "perf", desc = "Cycle sort column" }, function()
+    perf.cycle_sort()
+end)
+canopy.bind_with("j", { path = "perf", desc = "Scroll node table down" }, function()
+    scrollable.down()
+end)
+canopy.bind_with("k", { path = "perf", desc = "Scroll node table up" }, function()
+    scrollable.up()
+end)
+canopy.bind_with("PageDown", { path = "perf", desc = "Page node table down" }, function()
+    scrollable.page_down()
+end)
+canopy.bind_with("PageUp", { path = "perf", desc = "Page node table up" }, function()
+    scrollable.page_up()
+end)
+
+canopy.bind_with("j", { path = "styles", desc = "Next style layer" }, function()
+    styles.select_by(1)
+end)
+canopy.bind_with("k", { path = "styles", desc = "Previous style layer" }, function()
+    styles.select_by(-1)
+end)
+canopy.bind_with("t", { path = "styles", desc = "Edit foreground or background" }, function()
+    styles.toggle_target()
+end)
+canopy.bind_with("c", { path = "styles", desc = "Cycle color channel" }, function()
+    styles.cycle_channel()
+end)
+canopy.bind_with("l", { path = "styles", desc = "Increase channel" }, function()
+    styles.adjust(8)
+end)
+canopy.bind_with("h", { path = "styles", desc = "Decrease channel" }, function()
+    styles.adjust(-8)
+end)
+canopy.bind_with("L", { path = "styles", desc = "Increase channel by one" }, function()
+    styles.adjust(1)
+end)
+canopy.bind_with("H", { path = "styles", desc = "Decrease channel by one" }, function()
+    styles.adjust(-1)
+end)
+canopy.bind_with("e", { path = "styles", desc = "Export theme.toml" }, function()
+    styles.export("theme.toml")
+end)
+canopy.bind_with("i", { path = "styles", desc = "Import theme.toml" }, function()
+    styles.import("theme.toml")
+end)
+canopy.bind_with("PageDown", { path = "styles", desc = "Page down" }, function()
+    scrollable.page_down()
+end)
+canopy.bind_with("PageUp", { path = "styles", desc = "Page up" }, function()
+    scrollable.page_up()
+end)
+
+canopy.bind_with("Down", { path = "bindings", desc = "Next entry" }, function()
+    bindings.select_by(1)
+end)
+canopy.bind_with("Up", { path = "bindings", desc = "Previous entry" }, function()
+    bindings.select_by(-1)
+end)
+canopy.bind_with("PageDown", { path = "bindings", desc = "Page down" }, function()
+    scrollable.page_down()
+end)
+canopy.bind_with("PageUp", { path = "bindings", desc = "Page up" }, function()
+    scrollable.page_up()
+end)
+canopy.bind_with("Enter", { path = "bindings", desc = "Simulate selected entry" }, function()
+    bindings.simulate()
+end)
+canopy.bind_with("Backspace", { path = "bindings", desc = "Delete search character" }, function()
+    bindings.backspace()
+end)
+canopy.bind_with("Esc", { path = "bindings", desc = "Clear search" }, function()
+    bindings.clear_search()
+end)
+
 canopy.bind_with("C", { path = "logs", desc = "Clear log entry" }, function()
     logs.clear()
 end)
@@ -64,7 +218,7 @@ impl Inspector {
 
     /// Build the inspector subtree and return its node id.
     pub fn install(core: &mut Core) -> Result<NodeId> {
-        let (view_id, _tabs, _logs) = view::View::install(core)?;
+        let view_id = view::View::install(core)?;
         let frame_id = core.create_detached(frame::Frame::new());
         core.set_children(frame_id, vec![view_id])?;
         core.set_layout_of(frame_id, Layout::fill())?;
@@ -99,7 +253,12 @@ impl Loader for Inspector {
         c.add_commands::<Self>()?;
         c.add_commands::<tabs::Tabs>()?;
         c.register_default_bindings("inspector", DEFAULT_BINDINGS)?;
+        Bindings::load(c)?;
+        Events::load(c)?;
         Logs::load(c)?;
+        Nodes::load(c)?;
+        Perf::load(c)?;
+        Styles::load(c)?;
         Ok(())
     }
 }
@@ -0,0 +1,276 @@
+//! Node tree browser for the inspector widget.
+
+use std::time::Duration;
+
+use canopy::{
+    Canopy, Context, EventOutcome, Loader, Node, NodeId, ReadContext, ScrollCommands, Scrollable,
+    Widget, command, derive_commands,
+    error::Result,
+    event::{Event, TimerId},
+    layout::{CanvasContext, Layout, Size},
+    render::Render,
+    state::NodeName,
+    style::{Effect, effects},
+};
+
+use super::panel::{self, PanelView};
+
+/// Timer that toggles the highlight effect on the selected node.
+const FLASH_TIMER: TimerId = 1;
+/// Delay between highlight toggles.
+const FLASH_INTERVAL: Duration = Duration::from_millis(300);
+
+/// One line of the node tree.
+struct Row {
+    /// Node shown on this line.
+    id: NodeId,
+    /// Rendered description, indented by depth.
+    label: String,
+}
+
+/// Inspector panel listing every node in the running app.
+pub struct Nodes {
+    /// Tree rows in pre-order.
+    rows: Vec<Row>,
+    /// Selected row index.
+    selected: usize,
+    /// Selected node, used to keep the selection stable as the tree changes.
+    selected_id: Option<NodeId>,
+    /// Whether the selected node is flashed on screen.
+    highlight: bool,
+    /// Node currently carrying the flash effect, with the effect instance.
+    flash: Option<(NodeId, Effect)>,
+}
+
+#[derive_commands]
+impl Nodes {
+    /// Construct a node tree panel.
+    pub fn new() -> Self {
+        Self {
+            rows: Vec::new(),
+            selected: 0,
+            selected_id: None,
+            highlight: false,
+            flash: None,
+        }
+    }
+
+    /// Rebuild the rows from the live tree, keeping the selected node if it still exists.
+    fn refresh(&mut self, ctx: &(impl ReadContext + ?Sized)) {
+        self.rows.clear();
+        let mut stack = vec![(ctx.root_id(), 0)];
+        while let Some((id, depth)) = stack.pop() {
+            let Some(node) = ctx.node(id) else {
+                continue;
+            };
+            self.rows.push(Row {
+                id,
+                label: describe(ctx, id, node, depth),
+            });
+            stack.extend(
+                node.children()
+                    .iter()
+                    .rev()
+                    .map(|child| (*child, depth + 1)),
+            );
+        }
+
+        if let Some(index) = self
+            .selected_id
+            .and_then(|id| self.rows.iter().position(|row| row.id == id))
+        {
+            self.selected = index;
+        } else {
+            self.selected = self.selected.min(self.rows.len().saturating_sub(1));
+            self.selected_id = self.rows.get(self.selected).map(|row| row.id);
+        }
+    }
+
+    /// Select a row by index, moving any active flash off the previous node.
+    fn select(&mut self, c: &mut dyn Context, index: usize) {
+        self.refresh(c);
+        if self.rows.is_empty() {
+            return;
+        }
+        self.selected = index.min(self.rows.len() - 1);
+        self.selected_id = Some(self.rows[self.selected].id);
+        panel::keep_visible(c, 0, self.selected);
+        self.clear_flash(c);
+    }
+
+    /// Remove the flash effect from the node carrying it.
+    fn clear_flash(&mut self, c: &mut dyn Context) {
+        if let Some((node, effect)) = self.flash.take() {
+            let _ignored = c.remove_effect(node, &effect);
+        }
+    }
+
+    /// Move selection by a signed offset.
+    /// @param delta Signed row delta. Positive moves down and negative moves up.
+    #[command]
+    pub fn select_by(&mut self, c: &mut dyn Context, delta: i32) {
+        let index = self.selected.saturating_add_signed(delta as isize);
+        self.select(c, index);
+    }
+
+    /// Move selection to the first node.
+    #[command]
+    pub fn select_first(&mut self, c: &mut dyn Context) {
+        self.select(c, 0);
+    }
+
+    /// Move selection to the last node.
+    #[command]
+    pub fn select_last(&mut self, c: &mut dyn Context) {
+        self.select(c, usize::MAX);
+    }
+
+    /// Toggle flashing the selected node on screen.
+    #[command]
+    pub fn toggle_highlight(&mut self, c: &mut dyn Context) {
+        self.highlight = !self.highlight;
+        if self.highlight {
+            c.set_timer(FLASH_TIMER, FLASH_INTERVAL, true);
+        } else {
+            c.cancel_timer(FLASH_TIMER);
+            self.clear_flash(c);
+        }
+    }
+}
+
+/// Describe a node as its name, id, screen viewport, and state flags.
+fn describe(ctx: &(impl ReadContext + ?Sized), id: NodeId, node: &Node, depth: usize) -> String {
+    let outer = node.view().outer;
+    let mut flags = Vec::new();
+    if ctx.node_is_focused(id) {
+        flags.push("focus");
+    } else if ctx.node_is_on_focus_path(id) {
+        flags.push("focus-path");
+    }
+    if node.hidden() {
+        flags.push("hidden");
+    }
+    if node.layout_dirty() {
+        flags.push("layout");
+    }
+    if node.damage().is_some() {
+        flags.push("damage");
+    }
+
+    let mut label = format!(
+        "{}{} {id:?} {},{} {}x{}",
+        "  ".repeat(depth),
+        node.name(),
+        outer.tl.x,
+        outer.tl.y,
+        outer.w,
+        outer.h
+    );
+    if !flags.is_empty() {
+        label.push_str(&format!(" [{}]", flags.join(" ")));
+    }
+    label
+}
+
+impl Default for Nodes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scrollable for Nodes {}
+
+impl Widget for Nodes {
+    fn scrollable(&mut self) -> Option<&mut dyn Scrollable> {
+        Some(self)
+    }
+
+    fn layout(&self) -> Layout {
+        Layout::fill()
+    }
+
+    fn canvas(&self, view: Size<u32>, _ctx: &CanvasContext) -> Size<u32> {
+        panel::canvas(view, 0, self.rows.len())
+    }
+
+    fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+        self.refresh(ctx);
+        let Some(view) = PanelView::clear(r, ctx, 0)? else {
+            return Ok(());
+        };
+        let rows = view.rows();
+        for (index, row) in self.rows.iter().enumerate().take(rows.end).skip(rows.start) {
+            if index == self.selected {
+                view.mark_selected(r, index)?;
+            }
+            r.text("text", view.row(index, 2).line(0), &row.label)?;
+        }
+        Ok(())
+    }
+
+    fn poll(&mut self, c: &mut dyn Context) -> Option<Duration> {
+        panel::refresh(c)
+    }
+
+    fn on_event(&mut self, event: &Event, ctx: &mut dyn Context) -> Result<EventOutcome> {
+        let Event::Timer(FLASH_TIMER) = event else {
+            return Ok(EventOutcome::Ignore);
+        };
+        if self.flash.is_some() || ctx.view().is_zero() {
+            self.clear_flash(ctx);
+        } else if let Some(id) = self.selected_id {
+            let effect = effects::swap_fg_bg();
+            ctx.push_effect(id, effect.clone())?;
+            self.flash = Some((id, effect));
+        }
+        Ok(EventOutcome::Handle)
+    }
+
+    fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+        true
+    }
+
+    fn name(&self) -> NodeName {
+        NodeName::convert("nodes")
+    }
+}
+
+impl Loader for Nodes {
+    fn load(c: &mut Canopy) -> Result<()> {
+        c.add_commands::<Self>()?;
+        ScrollCommands::load(c)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use canopy::testing::harness::Harness;
+
+    use super::*;
+
+    #[test]
+    fn lists_nodes_and_flashes_selection() -> Result<()> {
+        let mut harness = Harness::builder(Nodes::new()).size(60, 4).build()?;
+        harness.render()?;
+        let root = harness.root;
+        assert!(
+            harness
+                .buf()
+                .screen_text()
+                .contains(&format!("nodes {root:?} 0,0 60x4"))
+        );
+
+        harness.with_root_context(|nodes: &mut Nodes, ctx| {
+            nodes.toggle_highlight(ctx);
+            nodes.on_event(&Event::Timer(FLASH_TIMER), ctx)?;
+            assert_eq!(nodes.flash.as_ref().map(|(id, _)| *id), Some(root));
+            nodes.on_event(&Event::Timer(FLASH_TIMER), ctx)?;
+            assert!(nodes.flash.is_none());
+            nodes.on_event(&Event::Timer(FLASH_TIMER), ctx)?;
+            nodes.toggle_highlight(ctx);
+            assert!(nodes.flash.is_none());
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,95 @@
+//! Refresh, scrolling, and drawing helpers shared by the inspector panels.
+//!
+//! A panel draws a few header lines fixed to the top of its view, over rows laid out one
+//! per canvas line below them. Scrolling moves the rows under the header, so panels get
+//! `scrollable::*` commands from a plain [`Scrollable`](canopy::Scrollable) impl.
+
+use std::{ops::Range, time::Duration};
+
+use canopy::{
+    Context, ReadContext,
+    error::Result,
+    geom::{Line, Rect},
+    layout::Size,
+    render::Render,
+};
+
+/// Delay between refreshes of a panel while it is visible.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Damage the visible part of a panel and return the delay until its next refresh.
+/// Hidden panels have an empty view, so they damage nothing and are not redrawn.
+pub(super) fn refresh(c: &mut dyn Context) -> Option<Duration> {
+    let view = c.view().view_rect();
+    c.damage(view);
+    Some(REFRESH_INTERVAL)
+}
+
+/// Canvas for a panel with `header` fixed lines above `rows` rows.
+pub(super) fn canvas(view: Size<u32>, header: u32, rows: usize) -> Size<u32> {
+    Size::new(view.w, header.saturating_add(rows as u32))
+}
+
+/// Scroll a panel with `header` fixed lines so that row `index` is in view.
+pub(super) fn keep_visible(c: &mut dyn Context, header: u32, index: usize) {
+    let view = c.view().view_rect();
+    let rows = view.h.saturating_sub(header).max(1) as usize;
+    let top = view.tl.y as usize;
+    if index < top {
+        c.scroll_to(view.tl.x, index as u32);
+    } else if index >= top + rows {
+        c.scroll_to(view.tl.x, (index + 1 - rows) as u32);
+    }
+}
+
+/// The visible part of a panel being drawn.
+pub(super) struct PanelView {
+    /// Visible rect in canvas coordinates.
+    view: Rect,
+    /// Number of header lines fixed to the top of the view.
+    header: u32,
+}
+
+impl PanelView {
+    /// Clear the visible part of a panel with `header` fixed lines. Returns `None` if the
+    /// panel is hidden.
+    pub(super) fn clear(
+        r: &mut Render,
+        ctx: &dyn ReadContext,
+        header: u32,
+    ) -> Result<Option<Self>> {
+        let view = ctx.view().view_rect();
+        if view.w == 0 || view.h == 0 {
+            return Ok(None);
+        }
+        r.fill("", view, ' ')?;
+        Ok(Some(Self { view, header }))
+    }
+
+    /// Return header line `index`.
+    pub(super) fn header_line(&self, index: u32) -> Line {
+        Rect::new(self.view.tl.x, self.view.tl.y + index, self.view.w, 1).line(0)
+    }
+
+    /// Return the indices of the rows in view, which may extend past the last row.
+    pub(super) fn rows(&self) -> Range<usize> {
+        let top = self.view.tl.y as usize;
+        top..top + self.view.h.saturating_sub(self.header) as usize
+    }
+
+    /// Return the rect of row `index` from column `x` to the right edge.
+    pub(super) fn row(&self, index: usize, x: u32) -> Rect {
+        Rect::new(
+            x,
+            self.header + index as u32,
+            self.view.w.saturating_sub(x),
+            1,
+        )
+    }
+
+    /// Draw the selection marker in the first column of row `index`.
+    pub(super) fn mark_selected(&self, r: &mut Render, index: usize) -> Result<()> {
+        let marker = Rect::new(0, self.header + index as u32, 1, 1);
+        r.fill("list/selected", marker, '\u{2588}')
+    }
+}
@@ -0,0 +1,244 @@
+//! Frame profiler panel for the inspector widget.
+
+use std::time::Duration;
+
+use canopy::{
+    Canopy, Context, Loader, NodeId, NodeTiming, ReadContext, ScrollCommands, Scrollable, Widget,
+    command, derive_commands,
+    error::Result,
+    layout::{CanvasContext, Layout, Size},
+    render::Render,
+    state::NodeName,
+};
+
+use super::panel::{self, PanelView};
+
+/// Number of header lines above the node table: the summary, the sparkline, and the
+/// column titles.
+const HEADER: u32 = 3;
+/// Glyphs used for the frame time sparkline, lowest first.
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Width of each timing column in the node table.
+const COLUMN: usize = 9;
+
+/// Column the node table is sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortKey {
+    /// Layout and render time combined.
+    Total,
+    /// Layout time.
+    Layout,
+    /// Render time.
+    Render,
+}
+
+impl SortKey {
+    /// Return the next key in the sort cycle.
+    fn next(self) -> Self {
+        match self {
+            Self::Total => Self::Layout,
+            Self::Layout => Self::Render,
+            Self::Render => Self::Total,
+        }
+    }
+
+    /// Return the sort value for a timing.
+    fn value(self, timing: &NodeTiming) -> Duration {
+        match self {
+            Self::Total => timing.total(),
+            Self::Layout => timing.layout,
+            Self::Render => timing.render,
+        }
+    }
+
+    /// Return a short label for display.
+    fn label(self) -> &'static str {
+        match self {
+            Self::Total => "total",
+            Self::Layout => "layout",
+            Self::Render => "render",
+        }
+    }
+}
+
+/// Inspector panel showing frame times and the slowest nodes of the last frame.
+///
+/// Profiling runs only while the panel is visible and recording.
+pub struct Perf {
+    /// Whether profiling should run while the panel is visible.
+    recording: bool,
+    /// Column the node table is sorted by.
+    sort: SortKey,
+    /// Number of rows in the node table when it was last drawn.
+    rows: usize,
+}
+
+#[derive_commands]
+impl Perf {
+    /// Construct a profiler panel.
+    pub fn new() -> Self {
+        Self {
+            recording: true,
+            sort: SortKey::Total,
+            rows: 0,
+        }
+    }
+
+    /// Pause or resume profiling.
+    #[command]
+    pub fn toggle_recording(&mut self, c: &mut dyn Context) {
+        self.recording = !self.recording;
+        c.set_profiling(self.recording);
+    }
+
+    /// Cycle the column the node table is sorted by.
+    #[command]
+    pub fn cycle_sort(&mut self, c: &mut dyn Context) {
+        self.sort = self.sort.next();
+        c.scroll_to(0, 0);
+    }
+}
+
+/// Format a duration in milliseconds.
+fn millis(d: Duration) -> String {
+    format!("{:.2}ms", d.as_secs_f64() * 1000.0)
+}
+
+/// Render durations as a sparkline scaled to the largest value.
+fn sparkline(durations: &[Duration]) -> String {
+    let max = durations.iter().max().copied().unwrap_or_default();
+    if max.is_zero() {
+        return String::new();
+    }
+    durations
+        .iter()
+        .map(|d| {
+            let level = (d.as_secs_f64() / max.as_secs_f64() * (SPARKS.len() - 1) as f64).round();
+            SPARKS[level as usize]
+        })
+        .collect()
+}
+
+impl Default for Perf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scrollable for Perf {}
+
+impl Widget for Perf {
+    fn scrollable(&mut self) -> Option<&mut dyn Scrollable> {
+        Some(self)
+    }
+
+    fn layout(&self) -> Layout {
+        Layout::fill()
+    }
+
+    fn canvas(&self, view: Size<u32>, _ctx: &CanvasContext) -> Size<u32> {
+        panel::canvas(view, HEADER, self.rows)
+    }
+
+    fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+        let Some(view) = PanelView::clear(r, ctx, HEADER)? else {
+            return Ok(());
+        };
+        let Some(profiler) = ctx.profiler() else {
+            self.rows = 0;
+            r.text("text", view.header_line(0), "paused")?;
+            return Ok(());
+        };
+        let durations = profiler.durations();
+        let frame = profiler.last();
+        let (avg, max) = if durations.is_empty() {
+            (Duration::ZERO, Duration::ZERO)
+        } else {
+            (
+                durations.iter().sum::<Duration>() / durations.len() as u32,
+                durations.iter().max().copied().unwrap_or_default(),
+            )
+        };
+        let header = format!(
+            "last {} | avg {} | max {} | layout-dirty {} | damaged {}",
+            millis(frame.duration),
+            millis(avg),
+            millis(max),
+            frame.layout_dirty,
+            frame.damaged
+        );
+        r.text("text", view.header_line(0), &header)?;
+        let width = ctx.view().content_size().w as usize;
+        let skip = durations.len().saturating_sub(width);
+        let recent: Vec<Duration> = durations.iter().skip(skip).copied().collect();
+        r.text("text", view.header_line(1), &sparkline(&recent))?;
+        r.text(
+            "text",
+            view.header_line(2),
+            &format!(
+                "{:>COLUMN$}{:>COLUMN$}{:>COLUMN$}  node (by {})",
+                "layout",
+                "render",
+                "total",
+                self.sort.label()
+            ),
+        )?;
+        let mut rows: Vec<(NodeId, NodeTiming)> =
+            frame.nodes.iter().map(|(id, t)| (*id, *t)).collect();
+        rows.sort_by(|a, b| self.sort.value(&b.1).cmp(&self.sort.value(&a.1)));
+        self.rows = rows.len();
+        let visible = view.rows();
+        for (index, (id, timing)) in rows
+            .iter()
+            .enumerate()
+            .take(visible.end)
+            .skip(visible.start)
+        {
+            let name = ctx
+                .node(*id)
+                .map_or_else(|| "<removed>".to_string(), |n| n.name().to_string());
+            let text = format!(
+                "{:>COLUMN$}{:>COLUMN$}{:>COLUMN$}  {name} {id:?}",
+                millis(timing.layout),
+                millis(timing.render),
+                millis(timing.total())
+            );
+            r.text("text", view.row(index, 0).line(0), &text)?;
+        }
+        Ok(())
+    }
+
+    fn poll(&mut self, c: &mut dyn Context) -> Option<Duration> {
+        c.set_profiling(self.recording && !c.view().is_zero());
+        panel::refresh(c)
+    }
+
+    fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+        true
+    }
+
+    fn name(&self) -> NodeName {
+        NodeName::convert("perf")
+    }
+}
+
+impl Loader for Perf {
+    fn load(c: &mut Canopy) -> Result<()> {
+        c.add_commands::<Self>()?;
+        ScrollCommands::load(c)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparkline_scales_to_the_slowest_frame() {
+        let ms = Duration::from_millis;
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[ms(0), ms(0)]), "");
+        assert_eq!(sparkline(&[ms(0), ms(4), ms(8)]), "▁▅█");
+    }
+}
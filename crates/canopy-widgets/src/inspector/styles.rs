@@ -0,0 +1,333 @@
+//! Live style editor for the inspector widget.
+
+use std::{fs, time::Duration};
+
+use canopy::{
+    Canopy, Context, Loader, ReadContext, ScrollCommands, Scrollable, Widget, command,
+    derive_commands,
+    error::{Error, Result},
+    geom::{Point, Rect},
+    layout::{CanvasContext, Layout, Size},
+    render::Render,
+    state::NodeName,
+    style::{Color, Paint, PartialStyle, StyleMap},
+};
+
+use super::panel::{self, PanelView};
+
+/// Number of header lines above the layers.
+const HEADER: u32 = 1;
+/// Width of the swatch drawn for each layer.
+const SWATCH: u32 = 4;
+
+/// Which paint of the selected layer is being edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    /// Foreground paint.
+    Fg,
+    /// Background paint.
+    Bg,
+}
+
+impl Target {
+    /// Return a short label for display.
+    fn label(self) -> &'static str {
+        match self {
+            Self::Fg => "fg",
+            Self::Bg => "bg",
+        }
+    }
+}
+
+/// Inspector panel listing style layers with an RGB picker for the selected layer.
+///
+/// Edits are applied to the running app immediately and can be exported as a TOML theme.
+pub struct Styles {
+    /// Copy of the app's style map, refreshed on render when the app's map changes.
+    map: StyleMap,
+    /// Selected layer index.
+    selected: usize,
+    /// Paint being edited.
+    target: Target,
+    /// Channel being edited: 0 red, 1 green, 2 blue.
+    channel: usize,
+    /// Result of the last export, shown in the header.
+    status: String,
+}
+
+#[derive_commands]
+impl Styles {
+    /// Construct a style editor panel.
+    pub fn new() -> Self {
+        Self {
+            map: StyleMap::new(),
+            selected: 0,
+            target: Target::Fg,
+            channel: 0,
+            status: String::new(),
+        }
+    }
+
+    /// Return the path of the selected layer.
+    fn selected_path(&self) -> Option<String> {
+        self.map
+            .entries()
+            .get(self.selected)
+            .map(|(path, _)| path.clone())
+    }
+
+    /// Move selection by a signed offset.
+    /// @param delta Signed row delta. Positive moves down and negative moves up.
+    #[command]
+    pub fn select_by(&mut self, c: &mut dyn Context, delta: i32) {
+        let last = self.map.entries().len().saturating_sub(1);
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta as isize)
+            .min(last);
+        panel::keep_visible(c, HEADER, self.selected);
+    }
+
+    /// Switch the picker between the foreground and background paint.
+    #[command]
+    pub fn toggle_target(&mut self, _c: &mut dyn Context) {
+        self.target = match self.target {
+            Target::Fg => Target::Bg,
+            Target::Bg => Target::Fg,
+        };
+    }
+
+    /// Cycle the picker through the red, green, and blue channels.
+    #[command]
+    pub fn cycle_channel(&mut self, _c: &mut dyn Context) {
+        self.channel = (self.channel + 1) % 3;
+    }
+
+    /// Adjust the selected channel of the selected layer and apply the result.
+    ///
+    /// Layers without their own paint start from the color they currently inherit, and
+    /// gradients are replaced by a solid color.
+    /// @param delta Signed channel delta.
+    #[command]
+    pub fn adjust(&mut self, c: &mut dyn Context, delta: i32) {
+        let Some(path) = self.selected_path() else {
+            return;
+        };
+        let mut style = self.map.get(&path).cloned().unwrap_or_default();
+        let current = match self.target {
+            Target::Fg => style.fg.as_ref(),
+            Target::Bg => style.bg.as_ref(),
+        }
+        .and_then(|paint| self.paint_color(paint))
+        .unwrap_or_else(|| self.inherited(&path));
+        let updated = Paint::Solid(shift_channel(current, self.channel, delta));
+        match self.target {
+            Target::Fg => style.fg = Some(updated),
+            Target::Bg => style.bg = Some(updated),
+        }
+        self.map.set(&path, style);
+        c.set_style(self.map.clone());
+    }
+
+    /// Write the current styles to a TOML theme file.
+    /// @param path Destination file path.
+    #[command]
+    pub fn export(&mut self, _c: &mut dyn Context, path: String) -> Result<()> {
+        let written = fs::write(&path, self.map.to_toml()).map_err(Error::from);
+        self.status = match &written {
+            Ok(()) => format!("exported to {path}"),
+            Err(err) => err.to_string(),
+        };
+        written
+    }
+
+    /// Load a TOML theme file written by `export` and apply it.
+    /// @param path Source file path.
+    #[command]
+    pub fn import(&mut self, c: &mut dyn Context, path: String) -> Result<()> {
+        let loaded = fs::read_to_string(&path)
+            .map_err(Error::from)
+            .and_then(|text| StyleMap::from_toml(&text));
+        self.status = match &loaded {
+            Ok(_) => format!("imported from {path}"),
+            Err(err) => err.to_string(),
+        };
+        self.map = loaded?;
+        c.set_style(self.map.clone());
+        Ok(())
+    }
+
+    /// Return the color of a paint, using the first stop of a gradient.
+    fn paint_color(&self, paint: &Paint) -> Option<Color> {
+        match paint {
+            Paint::Solid(color) => Some(*color),
+            Paint::Gradient(spec) => spec.stops.first().map(|stop| stop.color),
+        }
+    }
+
+    /// Return the color the target paint of a layer inherits from its ancestors.
+    fn inherited(&self, path: &str) -> Color {
+        let mut parent = path.trim_end_matches('/');
+        while let Some(index) = parent.rfind('/') {
+            parent = &parent[..index];
+            let paint = self.map.get(parent).and_then(|style| match self.target {
+                Target::Fg => style.fg.as_ref(),
+                Target::Bg => style.bg.as_ref(),
+            });
+            if let Some(color) = paint.and_then(|p| self.paint_color(p)) {
+                return color;
+            }
+        }
+        match self.target {
+            Target::Fg => Color::White,
+            Target::Bg => Color::Black,
+        }
+    }
+}
+
+/// Add `delta` to one RGB channel of a color, saturating at the channel bounds.
+fn shift_channel(color: Color, channel: usize, delta: i32) -> Color {
+    let Color::Rgb { r, g, b } = color.to_rgb() else {
+        return color;
+    };
+    let mut channels = [r, g, b];
+    channels[channel] = (channels[channel] as i32 + delta).clamp(0, 255) as u8;
+    Color::Rgb {
+        r: channels[0],
+        g: channels[1],
+        b: channels[2],
+    }
+}
+
+/// Describe one paint of a layer, or "-" if the layer inherits it.
+fn describe_paint(paint: Option<&Paint>) -> String {
+    match paint {
+        Some(Paint::Solid(color)) => color.to_hex(),
+        Some(Paint::Gradient(spec)) => format!("gradient({})", spec.stops.len()),
+        None => "-".into(),
+    }
+}
+
+/// Describe a layer's own colors and attributes.
+fn describe(path: &str, style: &PartialStyle) -> String {
+    let attrs = style
+        .attrs
+        .map_or_else(|| "-".to_string(), |a| a.names().join(","));
+    format!(
+        "{path:<24} fg {:<9} bg {:<9} {attrs}",
+        describe_paint(style.fg.as_ref()),
+        describe_paint(style.bg.as_ref())
+    )
+}
+
+impl Default for Styles {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scrollable for Styles {}
+
+impl Widget for Styles {
+    fn scrollable(&mut self) -> Option<&mut dyn Scrollable> {
+        Some(self)
+    }
+
+    fn layout(&self) -> Layout {
+        Layout::fill()
+    }
+
+    fn canvas(&self, view: Size<u32>, _ctx: &CanvasContext) -> Size<u32> {
+        panel::canvas(view, HEADER, self.map.entries().len())
+    }
+
+    fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+        if r.stylemap().version() != self.map.version() {
+            self.map = r.stylemap().clone();
+        }
+        let Some(view) = PanelView::clear(r, ctx, HEADER)? else {
+            return Ok(());
+        };
+
+        let entries = self.map.entries();
+        self.selected = self.selected.min(entries.len().saturating_sub(1));
+        let mut header = format!(
+            "{} layer(s) | editing {} {}",
+            entries.len(),
+            self.target.label(),
+            ["red", "green", "blue"][self.channel]
+        );
+        if !self.status.is_empty() {
+            header.push_str(&format!(" | {}", self.status));
+        }
+        r.text("text", view.header_line(0), &header)?;
+
+        let rows = view.rows();
+        for (index, (path, style)) in entries.iter().enumerate().take(rows.end).skip(rows.start) {
+            if index == self.selected {
+                view.mark_selected(r, index)?;
+            }
+            // The swatch shows the layer as resolved, including inherited values.
+            let y = view.row(index, 0).tl.y;
+            let swatch = Rect::new(2, y, SWATCH, 1);
+            let resolved = r.resolve_style_name_raw(path).resolve_at(swatch, swatch.tl);
+            for x in 0..SWATCH {
+                r.put_cell(resolved, Point { x: 2 + x, y }, 'A')?;
+            }
+            let text = view.row(index, SWATCH + 3);
+            r.text("text", text.line(0), &describe(path, style))?;
+        }
+        Ok(())
+    }
+
+    fn poll(&mut self, c: &mut dyn Context) -> Option<Duration> {
+        panel::refresh(c)
+    }
+
+    fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+        true
+    }
+
+    fn name(&self) -> NodeName {
+        NodeName::convert("styles")
+    }
+}
+
+impl Loader for Styles {
+    fn load(c: &mut Canopy) -> Result<()> {
+        c.add_commands::<Self>()?;
+        ScrollCommands::load(c)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use canopy::testing::harness::Harness;
+
+    use super::*;
+
+    #[test]
+    fn adjusting_a_channel_applies_a_solid_color() -> Result<()> {
+        let mut harness = Harness::builder(Styles::new()).size(60, 4).build()?;
+        harness.render()?;
+        assert!(harness.buf().screen_text().contains("fg #ffffff"));
+
+        harness.with_root_context(|styles: &mut Styles, ctx| {
+            styles.toggle_target(ctx);
+            styles.cycle_channel(ctx);
+            styles.adjust(ctx, 16);
+            Ok(())
+        })?;
+        harness.render()?;
+        assert!(harness.buf().screen_text().contains("bg #001000"));
+        Ok(())
+    }
+
+    #[test]
+    fn shift_channel_saturates() {
+        let color = Color::rgb("#f00010");
+        assert_eq!(shift_channel(color, 0, 32), Color::rgb("#ff0010"));
+        assert_eq!(shift_channel(color, 2, -32), Color::rgb("#f00000"));
+    }
+}
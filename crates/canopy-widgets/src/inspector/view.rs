@@ -25,15 +25,31 @@ impl View {
         Self
     }
 
-    /// Construct a new inspector view.
-    pub fn install(core: &mut Core) -> Result<(NodeId, NodeId, NodeId)> {
-        let tabs = core.create_detached(Tabs::new(vec!["Stats", "Logs"]));
+    /// Construct a new inspector view with a tab bar over one pane per tab.
+    pub fn install(core: &mut Core) -> Result<NodeId> {
+        let nodes = core.create_detached(super::nodes::Nodes::new());
+        let events = super::events::Events::install(core);
+        let perf = core.create_detached(super::perf::Perf::new());
+        let styles = core.create_detached(super::styles::Styles::new());
+        let bindings = core.create_detached(super::bindings::Bindings::new());
         let logs = core.create_detached(super::logs::Logs::new());
+        let panes = vec![nodes, events, perf, styles, bindings, logs];
+        let tabs = core.create_detached(
+            Tabs::new(vec![
+                "Nodes", "Events", "Perf", "Styles", "Bindings", "Logs",
+            ])
+            .with_panes(panes.clone()),
+        );
         let view_id = core.create_detached(Self::new());
-        core.set_children(view_id, vec![tabs, logs])?;
+        let mut children = vec![tabs];
+        children.extend(&panes);
+        core.set_children(view_id, children)?;
         core.set_layout_of(view_id, Layout::fill())?;
         core.set_layout_of(tabs, Layout::column().flex_horizontal(1).fixed_height(1))?;
-        core.set_layout_of(logs, Layout::fill())?;
-        Ok((view_id, tabs, logs))
+        for (i, pane) in panes.into_iter().enumerate() {
+            core.set_layout_of(pane, Layout::fill())?;
+            core.set_hidden(pane, i != 0);
+        }
+        Ok(view_id)
     }
 }
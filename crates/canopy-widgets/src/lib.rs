@@ -7,12 +7,24 @@
 
 /// Box border widget with customizable glyphs.
 mod boxed;
+/// Lazily loaded line viewer for very large files.
+pub mod bigtext;
+/// Path breadcrumb with middle truncation and clickable segments.
+pub mod breadcrumb;
 /// Button widget with command dispatch.
 mod button;
 /// Content centering container.
 mod center;
+/// Ex-style command line prompt.
+pub mod cmdline;
+/// Row data sources for list and table widgets.
+pub mod datasource;
+/// Lazy-loading filesystem tree widget.
+pub mod dirtree;
 /// Dropdown selection widget.
 mod dropdown;
+/// Keyed dynamic child collections.
+pub mod dynamic;
 /// Experimental editor API with syntax highlighting and vi mode.
 pub mod editor;
 /// Widget-specific error types.
@@ -33,20 +45,28 @@ mod input;
 pub mod inspector;
 /// Typed list container with selection.
 mod list;
+/// Log viewer with filtering, search, and follow mode.
+pub mod logview;
 /// Modal overlay container.
 mod modal;
 /// Padding container widget.
 mod pad;
+/// Page navigation for paged data sources.
+pub mod paginate;
 /// 2D grid layout of panes.
 mod panes;
 /// Application root widget.
 mod root;
 /// Selection widget.
 mod selector;
+/// Spell checking for text entry widgets.
+mod spell;
 /// Experimental tab container API.
 pub mod tabs;
-/// Terminal emulation widget.
-mod terminal;
+/// Table widget rendering rows from a data source.
+mod table;
+/// Embedded terminal widget running a command on a PTY.
+pub mod term;
 /// Multiline text widget.
 mod text;
 /// Vertical stack container.
@@ -72,8 +92,9 @@ pub use pad::Pad;
 pub use panes::Panes;
 pub use root::Root;
 pub use selector::{Selector, SelectorItem};
-pub use terminal::{Terminal, TerminalColors, TerminalConfig};
-pub use text::{CanvasWidth, Text};
+pub use spell::{Dictionary, SpellCheck, WordList};
+pub use table::{Column, Table};
+pub use text::{CanvasWidth, Text, WordBreaker, WrapAlgorithm, WrapOptions};
 pub use vstack::VStack;
 
 #[cfg(test)]
@@ -6,21 +6,24 @@
 use std::marker::PhantomData;
 
 use canopy::{
-    Context, EventOutcome, KeyedChildren, NodeId, ReadContext, RemovePolicy, TypedId, Widget,
-    command,
+    Accessible, Context, EventOutcome, KeyedChildren, NodeId, PersistentState, ReadContext,
+    RemovePolicy, Role, Scrollable, TypedId, UndoEntry, Widget, command,
     commands::{
         CommandArgs, CommandCall, CommandInvocation, CommandScopeFrame, ListRowContext, ToArgValue,
     },
     derive_commands,
     error::{Error, Result},
     event::{Event, mouse},
-    geom::{Direction, Line, Point},
+    geom::{Direction, Line, Point, Rect},
     layout::{CanvasContext, Constraint, Edges, Layout, MeasureConstraints, Measurement, Size},
+    persist::{Value, from_state, to_state},
     render::Render,
     state::NodeName,
 };
 use unicode_width::UnicodeWidthStr;
 
+use crate::datasource::{DataChange, DataSource};
+
 /// List selection indicator configuration.
 struct SelectionIndicator {
     /// Style path for the indicator.
@@ -126,6 +129,10 @@ pub struct List<W: Selectable> {
     on_activate: Option<ListActivateConfig>,
     /// Pending activation state while handling clicks.
     pending_activate: Option<PendingActivate>,
+    /// Data source the items are built from, if any.
+    source: Option<Box<dyn RowSource<W>>>,
+    /// Source version the items reflect.
+    source_version: u64,
     /// Marker for the widget type.
     _marker: PhantomData<W>,
 }
@@ -147,6 +154,8 @@ impl<W: Selectable> List<W> {
             selection_indicator: None,
             on_activate: None,
             pending_activate: None,
+            source: None,
+            source_version: 0,
             _marker: PhantomData,
         }
     }
@@ -338,7 +347,42 @@ impl<W: Selectable> List<W> {
         }
     }
 
+    /// Move an item to a new position. Selection follows the moved item, and the move is
+    /// registered for undo.
+    /// @param from Zero-based index of the item to move.
+    /// @param to Zero-based destination index, clamped to the last item.
+    #[command]
+    pub fn move_item(&mut self, ctx: &mut dyn Context, from: usize, to: usize) -> Result<()> {
+        let mut desired = self.items.keys().to_vec();
+        if from >= desired.len() {
+            return Ok(());
+        }
+        let to = to.min(desired.len() - 1);
+        if from == to {
+            return Ok(());
+        }
+        let key = desired.remove(from);
+        desired.insert(to, key);
+        self.reconcile_order(ctx, desired, RemovePolicy::RemoveSubtree)?;
+        self.selected = self.selected.map(|sel| match sel {
+            sel if sel == from => to,
+            sel if from < sel && sel <= to => sel - 1,
+            sel if to <= sel && sel < from => sel + 1,
+            sel => sel,
+        });
+        debug_assert!(self.selection_invariant_holds());
+        ctx.push_undo(UndoEntry::new(
+            ctx.node_id(),
+            "move item",
+            Self::cmd_move_item().call_with([to, from]).invocation(),
+            Self::cmd_move_item().call_with([from, to]).invocation(),
+        ));
+        Ok(())
+    }
+
     /// Select an item at the given index.
+    /// @param index Zero-based item index, clamped to the last item.
+    #[command]
     pub fn select(&mut self, ctx: &mut dyn Context, index: usize) -> Result<()> {
         if self.items.is_empty() {
             return Ok(());
@@ -587,13 +631,7 @@ impl<W: Selectable> List<W> {
             return;
         };
 
-        if start < view_rect.tl.y {
-            let delta = view_rect.tl.y - start;
-            let _ = c.scroll_by(0, -(delta as i32));
-        } else if start.saturating_add(height) > view_rect.tl.y.saturating_add(view_rect.h) {
-            let delta = start.saturating_add(height) - (view_rect.tl.y + view_rect.h);
-            let _ = c.scroll_by(0, delta as i32);
-        }
+        let _ = c.scroll_rect_into_view(Rect::new(view_rect.tl.x, start, view_rect.w, height));
     }
 
     /// Move selection by one page and keep it visible.
@@ -675,6 +713,99 @@ impl<W: Selectable> List<W> {
             Some(metrics.len() - 1)
         }
     }
+    /// Build the list items from a data source, one item per row, and keep them in sync.
+    ///
+    /// The list subscribes to the source's changes. Inserted, removed, and updated rows
+    /// rebuild only the affected items; other items keep their nodes and state.
+    pub fn set_source<T, S, F>(&mut self, ctx: &mut dyn Context, source: S, build: F) -> Result<()>
+    where
+        T: 'static,
+        S: DataSource<T> + 'static,
+        F: Fn(&T) -> W + Send + 'static,
+        W: Send + 'static,
+    {
+        let node = ctx.node_id();
+        ctx.observe_changes(node, source.changes());
+        self.source = Some(Box::new(SourceRows {
+            source,
+            build,
+            _marker: PhantomData,
+        }));
+        self.source_version = 0;
+        self.apply_source_changes(ctx, &[DataChange::Reset])
+    }
+
+    /// Apply changes made to the data source since the items were last built.
+    fn sync_source(&mut self, ctx: &mut dyn Context) -> Result<()>
+    where
+        W: 'static,
+    {
+        let Some(source) = &self.source else {
+            return Ok(());
+        };
+        let changes = source.changes_since(self.source_version);
+        self.apply_source_changes(ctx, &changes)
+    }
+
+    /// Rebuild the items affected by `changes`.
+    fn apply_source_changes(&mut self, ctx: &mut dyn Context, changes: &[DataChange]) -> Result<()>
+    where
+        W: 'static,
+    {
+        let Some(source) = self.source.take() else {
+            return Ok(());
+        };
+        let result = self.apply_row_changes(ctx, source.as_ref(), changes);
+        self.source_version = source.version();
+        self.source = Some(source);
+        result
+    }
+
+    /// Rebuild items from `source` for each change in order.
+    fn apply_row_changes(
+        &mut self,
+        ctx: &mut dyn Context,
+        source: &dyn RowSource<W>,
+        changes: &[DataChange],
+    ) -> Result<()>
+    where
+        W: 'static,
+    {
+        for change in changes {
+            match *change {
+                DataChange::Insert(index) => {
+                    if let Some(widget) = source.build(index) {
+                        self.insert(ctx, index, widget)?;
+                    }
+                }
+                DataChange::Remove(index) => {
+                    self.remove(ctx, index)?;
+                }
+                DataChange::Update(index) => {
+                    let (Some(id), Some(widget)) = (self.item(index), source.build(index)) else {
+                        continue;
+                    };
+                    let selected = self.selected == Some(index);
+                    ctx.with_typed(id, |item, c| {
+                        *item = widget;
+                        item.set_selected(selected);
+                        c.invalidate_layout();
+                        Ok(())
+                    })?;
+                }
+                DataChange::Reset => {
+                    self.clear(ctx)?;
+                    for index in 0..source.len() {
+                        if let Some(widget) = source.build(index) {
+                            self.append(ctx, widget)?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Allocate the next list key.
     fn next_key(&mut self) -> ListKey {
         let key = ListKey(self.next_key);
@@ -737,7 +868,67 @@ impl<W: Selectable> List<W> {
     }
 }
 
+/// Type-erased view of a data source that builds list items.
+trait RowSource<W>: Send {
+    /// Number of rows.
+    fn len(&self) -> usize;
+    /// Build the item for the row at `index`.
+    fn build(&self, index: usize) -> Option<W>;
+    /// Number of changes made to the source so far.
+    fn version(&self) -> u64;
+    /// Changes made after `version`.
+    fn changes_since(&self, version: u64) -> Vec<DataChange>;
+}
+
+/// A data source paired with the function that turns its rows into items.
+struct SourceRows<T, S, F> {
+    /// Row data.
+    source: S,
+    /// Item builder.
+    build: F,
+    /// Marker for the row type.
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T, S, F, W> RowSource<W> for SourceRows<T, S, F>
+where
+    S: DataSource<T>,
+    F: Fn(&T) -> W + Send,
+{
+    fn len(&self) -> usize {
+        self.source.len()
+    }
+
+    fn build(&self, index: usize) -> Option<W> {
+        self.source.get(index).map(|row| (self.build)(&row))
+    }
+
+    fn version(&self) -> u64 {
+        self.source.version()
+    }
+
+    fn changes_since(&self, version: u64) -> Vec<DataChange> {
+        self.source.changes_since(version)
+    }
+}
+
+impl<W: Selectable + Send + 'static> Scrollable for List<W> {
+    fn page_up(&mut self, c: &mut dyn Context) -> bool {
+        let before = self.selected;
+        self.page_shift(c, false).is_ok() && self.selected != before
+    }
+
+    fn page_down(&mut self, c: &mut dyn Context) -> bool {
+        let before = self.selected;
+        self.page_shift(c, true).is_ok() && self.selected != before
+    }
+}
+
 impl<W: Selectable + Send + 'static> Widget for List<W> {
+    fn scrollable(&mut self) -> Option<&mut dyn Scrollable> {
+        Some(self)
+    }
+
     fn layout(&self) -> Layout {
         let mut layout = Layout::fill().overflow_x();
         if let Some(indicator) = &self.selection_indicator
@@ -748,6 +939,10 @@ impl<W: Selectable + Send + 'static> Widget for List<W> {
         layout
     }
 
+    fn on_observed(&mut self, ctx: &mut dyn Context) -> Result<()> {
+        self.sync_source(ctx)
+    }
+
     fn on_event(&mut self, event: &Event, ctx: &mut dyn Context) -> Result<EventOutcome> {
         if let Event::Mouse(mouse_event) = event
             && self.handle_click(ctx, *mouse_event)?
@@ -833,11 +1028,40 @@ impl<W: Selectable + Send + 'static> Widget for List<W> {
         false
     }
 
+    fn accessible(&self, _ctx: &dyn ReadContext) -> Option<Accessible> {
+        let state = match self.selected {
+            Some(index) => format!("{} of {}", index + 1, self.len()),
+            None if self.is_empty() => "empty".to_string(),
+            None => format!("{} items", self.len()),
+        };
+        Some(Accessible::new(Role::List, "").with_state(state))
+    }
+
+    fn persistent(&mut self) -> Option<&mut dyn PersistentState> {
+        Some(self)
+    }
+
     fn name(&self) -> NodeName {
         NodeName::convert("list")
     }
 }
 
+impl<W: Selectable + Send + 'static> PersistentState for List<W> {
+    fn save_state(&self, _ctx: &dyn ReadContext) -> Result<Value> {
+        to_state(&self.selected)
+    }
+
+    fn restore_state(&mut self, state: Value, ctx: &mut dyn Context) -> Result<()> {
+        let selected: Option<usize> = from_state(state)?;
+        let selected = selected
+            .filter(|_| !self.items.is_empty())
+            .map(|index| index.min(self.items.len() - 1));
+        self.update_selection(ctx, selected)?;
+        self.ensure_selected_visible(ctx);
+        Ok(())
+    }
+}
+
 /// Compute the indicator width in cells from a multi-line string.
 fn indicator_width(text: &str) -> u32 {
     text.lines()
@@ -863,7 +1087,7 @@ mod tests {
     };
 
     use super::*;
-    use crate::Text;
+    use crate::{Text, datasource::VecSource};
 
     struct Row {
         selected: bool,
@@ -969,6 +1193,54 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_list_select_command() -> Result<()> {
+        let root = List::<Text>::new();
+        let mut harness = Harness::builder(root).size(20, 10).build()?;
+
+        harness.with_root_context(|list: &mut List<Text>, ctx| {
+            list.append(ctx, Text::new("Item 1"))?;
+            list.append(ctx, Text::new("Item 2"))?;
+            list.append(ctx, Text::new("Item 3"))?;
+            Ok(())
+        })?;
+
+        harness.render()?;
+        harness.script(include_str!("../tests/luau/list_select.luau"))?;
+        harness.with_root_widget::<List<Text>, _>(|list| {
+            assert_eq!(list.selected_index(), Some(1));
+        });
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_state_restores_selection() -> Result<()> {
+        fn fill(list: &mut List<Text>, ctx: &mut dyn Context) -> Result<()> {
+            for item in ["Item 1", "Item 2", "Item 3"] {
+                list.append(ctx, Text::new(item))?;
+            }
+            Ok(())
+        }
+
+        let mut saved = Harness::builder(List::<Text>::new()).size(20, 10).build()?;
+        let state = saved.with_root_context(|list: &mut List<Text>, ctx| {
+            fill(list, ctx)?;
+            list.select(ctx, 2)?;
+            list.save_state(ctx)
+        })?;
+
+        let mut restored = Harness::builder(List::<Text>::new()).size(20, 10).build()?;
+        restored.with_root_context(|list: &mut List<Text>, ctx| {
+            fill(list, ctx)?;
+            list.restore_state(state.clone(), ctx)
+        })?;
+        restored.with_root_widget::<List<Text>, _>(|list| {
+            assert_eq!(list.selected_index(), Some(2));
+        });
+        Ok(())
+    }
+
     #[test]
     fn test_list_remove() -> Result<()> {
         let root = List::<Text>::new();
@@ -1059,4 +1331,89 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn focused_rows_are_described_by_the_list() -> Result<()> {
+        let mut harness = Harness::builder(List::<Row>::new()).size(20, 10).build()?;
+        harness.with_root_context(|list: &mut List<Row>, ctx| {
+            for _ in 0..3 {
+                list.append(ctx, Row::new())?;
+            }
+            list.select_by(ctx, 1)
+        })?;
+        assert_eq!(
+            harness.canopy.focused_description()?.as_deref(),
+            Some("list, 2 of 3")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn move_item_reorders_and_tracks_selection() -> Result<()> {
+        let root = List::<Text>::new();
+        let mut harness = Harness::builder(root).size(20, 10).build()?;
+        harness.canopy.enable_undo(8);
+
+        harness.with_root_context(|list: &mut List<Text>, ctx| {
+            list.append(ctx, Text::new("Item 1"))?;
+            list.append(ctx, Text::new("Item 2"))?;
+            list.append(ctx, Text::new("Item 3"))?;
+            Ok(())
+        })?;
+
+        harness.script("list.move_item(0, 2)")?;
+        let ids = harness.with_root_widget::<List<Text>, _>(|list| {
+            assert_eq!(list.selected_index(), Some(2));
+            (0..list.len())
+                .map(|index| list.item(index).expect("item id"))
+                .collect::<Vec<_>>()
+        });
+        let labels: Vec<String> = ids
+            .into_iter()
+            .map(|id| harness.with_widget::<Text, _>(id, |text| text.raw().to_string()))
+            .collect();
+        assert_eq!(labels, ["Item 2", "Item 3", "Item 1"]);
+        assert_eq!(
+            harness
+                .canopy
+                .undo_manager()
+                .and_then(|undo| undo.undo_label()),
+            Some("move item")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn source_changes_rebuild_only_affected_rows() -> Result<()> {
+        let source = VecSource::new(vec!["a".to_string(), "b".to_string()]);
+        let mut harness = Harness::builder(List::<Text>::new()).size(20, 10).build()?;
+        harness.with_root_context(|list: &mut List<Text>, ctx| {
+            list.set_source(ctx, source.clone(), |row: &String| Text::new(row))
+        })?;
+        let first = harness.with_root_widget(|list: &mut List<Text>| list.item(0));
+
+        source.push("c".to_string());
+        source.set(1, "B".to_string());
+        source.remove(0);
+        harness.render()?;
+
+        let ids = harness.with_root_widget(|list: &mut List<Text>| {
+            (0..list.len())
+                .map(|index| list.item(index).expect("item id"))
+                .collect::<Vec<_>>()
+        });
+        let labels: Vec<String> = ids
+            .iter()
+            .map(|id| harness.with_widget::<Text, _>(*id, |text| text.raw().to_string()))
+            .collect();
+        assert_eq!(labels, ["B", "c"]);
+        assert_ne!(first, Some(ids[0]));
+
+        let second = ids[1];
+        source.set(0, "b".to_string());
+        harness.render()?;
+        let kept = harness.with_root_widget(|list: &mut List<Text>| list.item(1));
+        assert_eq!(kept, Some(second));
+        Ok(())
+    }
 }
@@ -0,0 +1,553 @@
+//! Scrolling log viewer with level filtering, search, and follow mode.
+
+use std::{collections::VecDeque, sync::mpsc, time::Duration};
+
+pub use canopy::logging::{Level, LogBuffer, LogRecord};
+use canopy::{
+    Canopy, Context, Loader, ReadContext, ScrollCommands, Scrollable, Widget, command,
+    derive_commands,
+    error::{Error, Result},
+    geom::{Line, Rect},
+    layout::{CanvasContext, Layout, Size},
+    render::Render,
+    state::NodeName,
+    text,
+};
+use regex::Regex;
+
+/// Interval between drains of the record channel.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Default number of records kept before the oldest are dropped.
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// Default bindings for the log viewer.
+const DEFAULT_BINDINGS: &str = r#"
+canopy.bind_with("j", { path = "log_view", desc = "Scroll down" }, function()
+    scrollable.down()
+end)
+canopy.bind_with("Down", { path = "log_view", desc = "Scroll down" }, function()
+    scrollable.down()
+end)
+canopy.bind_with("k", { path = "log_view", desc = "Scroll up" }, function()
+    scrollable.up()
+end)
+canopy.bind_with("Up", { path = "log_view", desc = "Scroll up" }, function()
+    scrollable.up()
+end)
+canopy.bind_with("PageDown", { path = "log_view", desc = "Page down" }, function()
+    scrollable.page_down()
+end)
+canopy.bind_with("PageUp", { path = "log_view", desc = "Page up" }, function()
+    scrollable.page_up()
+end)
+canopy.bind_with("g", { path = "log_view", desc = "Scroll to top" }, function()
+    scrollable.top()
+end)
+canopy.bind_with("G", { path = "log_view", desc = "Follow new records" }, function()
+    scrollable.bottom()
+end)
+canopy.bind_with("f", { path = "log_view", desc = "Toggle follow" }, function()
+    log_view.toggle_follow()
+end)
+canopy.bind_with("n", { path = "log_view", desc = "Next match" }, function()
+    log_view.search_next()
+end)
+canopy.bind_with("N", { path = "log_view", desc = "Previous match" }, function()
+    log_view.search_prev()
+end)
+"#;
+
+/// Return the style path for records at `level`.
+fn level_style(level: Level) -> &'static str {
+    match level {
+        Level::Trace => "logview/trace",
+        Level::Debug => "logview/debug",
+        Level::Info => "logview/info",
+        Level::Warn => "logview/warn",
+        Level::Error => "logview/error",
+    }
+}
+
+/// A scrolling log viewer.
+///
+/// Records arrive through [`LogView::push`], a channel attached with
+/// [`LogView::with_source`], or a [`LogBuffer`] attached with [`LogView::with_buffer`];
+/// channels and buffers are drained on each poll. Only records at or above the
+/// minimum level are shown. In follow mode the view sticks to the newest record;
+/// scrolling up leaves follow mode, and scrolling back to the bottom resumes it.
+/// Scrolling goes through the shared `scrollable::*` commands, which move the view by
+/// records rather than moving a viewport over a canvas.
+pub struct LogView {
+    /// Stored records, oldest first.
+    records: VecDeque<LogRecord>,
+    /// Sequence number of the oldest stored record.
+    first_seq: usize,
+    /// Sequence numbers of records passing the level filter, oldest first.
+    visible: VecDeque<usize>,
+    /// Maximum number of stored records.
+    capacity: usize,
+    /// Minimum level shown.
+    min_level: Level,
+    /// Active search pattern.
+    search: Option<Regex>,
+    /// Sequence number of the current search match.
+    current: Option<usize>,
+    /// Whether the view sticks to the newest record.
+    follow: bool,
+    /// Index into `visible` of the top row while not following.
+    top: usize,
+    /// Height of the most recent render, used for paging.
+    height: usize,
+    /// Channel of incoming records.
+    source: Option<mpsc::Receiver<LogRecord>>,
+    /// Shared buffer of incoming records, with the read cursor.
+    buffer: Option<(LogBuffer, u64)>,
+}
+
+#[derive_commands]
+impl LogView {
+    /// Construct an empty log viewer in follow mode.
+    pub fn new() -> Self {
+        Self {
+            records: VecDeque::new(),
+            first_seq: 0,
+            visible: VecDeque::new(),
+            capacity: DEFAULT_CAPACITY,
+            min_level: Level::Trace,
+            search: None,
+            current: None,
+            follow: true,
+            top: 0,
+            height: 0,
+            source: None,
+            buffer: None,
+        }
+    }
+
+    /// Read records from a channel, draining it on each poll.
+    pub fn with_source(mut self, source: mpsc::Receiver<LogRecord>) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Read records from a shared buffer, such as [`canopy::logging::log_buffer`],
+    /// starting with those it already holds.
+    pub fn with_buffer(mut self, buffer: LogBuffer) -> Self {
+        self.buffer = Some((buffer, 0));
+        self
+    }
+
+    /// Keep at most `capacity` records, dropping the oldest first.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Return the number of stored records.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Return true when no records are stored.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Return the number of records passing the level filter.
+    pub fn visible_len(&self) -> usize {
+        self.visible.len()
+    }
+
+    /// Return whether the view is following new records.
+    pub fn is_following(&self) -> bool {
+        self.follow
+    }
+
+    /// Return the minimum level shown.
+    pub fn level(&self) -> Level {
+        self.min_level
+    }
+
+    /// Append a record, dropping the oldest once the capacity is reached.
+    pub fn push(&mut self, record: LogRecord) {
+        let seq = self.first_seq + self.records.len();
+        if record.level >= self.min_level {
+            self.visible.push_back(seq);
+        }
+        self.records.push_back(record);
+        while self.records.len() > self.capacity {
+            self.records.pop_front();
+            self.first_seq += 1;
+            if self.visible.front().is_some_and(|&s| s < self.first_seq) {
+                self.visible.pop_front();
+                self.top = self.top.saturating_sub(1);
+            }
+        }
+        if self.current.is_some_and(|s| s < self.first_seq) {
+            self.current = None;
+        }
+    }
+
+    /// Show only records at or above `level`.
+    /// @param level Minimum level to show.
+    #[command]
+    pub fn set_level(&mut self, _c: &mut dyn Context, level: Level) {
+        let anchor = self.visible.get(self.top).copied();
+        self.min_level = level;
+        self.visible = self
+            .records
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.level >= level)
+            .map(|(i, _)| self.first_seq + i)
+            .collect();
+        if let Some(anchor) = anchor {
+            self.top = self.visible.partition_point(|&s| s < anchor);
+        }
+        self.clamp_top();
+    }
+
+    /// Toggle following new records.
+    #[command]
+    pub fn toggle_follow(&mut self, _c: &mut dyn Context) {
+        if self.follow {
+            self.top = self.bottom_top();
+        }
+        self.follow = !self.follow;
+    }
+
+    /// Highlight records matching a regular expression and jump to the newest match.
+    /// @param pattern Regular expression to search for.
+    #[command]
+    pub fn search(&mut self, _c: &mut dyn Context, pattern: String) -> Result<()> {
+        let regex = Regex::new(&pattern)
+            .map_err(|err| Error::Invalid(format!("invalid search pattern: {err}")))?;
+        self.search = Some(regex);
+        self.current = None;
+        self.jump_match(false);
+        Ok(())
+    }
+
+    /// Jump to the next match below the current one.
+    #[command]
+    pub fn search_next(&mut self, _c: &mut dyn Context) {
+        self.jump_match(true);
+    }
+
+    /// Jump to the previous match above the current one.
+    #[command]
+    pub fn search_prev(&mut self, _c: &mut dyn Context) {
+        self.jump_match(false);
+    }
+
+    /// Clear the search pattern.
+    #[command]
+    pub fn clear_search(&mut self, _c: &mut dyn Context) {
+        self.search = None;
+        self.current = None;
+    }
+
+    /// Remove all records.
+    #[command]
+    pub fn clear(&mut self, _c: &mut dyn Context) {
+        self.first_seq += self.records.len();
+        self.records.clear();
+        self.visible.clear();
+        self.current = None;
+        self.top = 0;
+    }
+
+    /// Return the record with sequence number `seq`.
+    fn record(&self, seq: usize) -> Option<&LogRecord> {
+        self.records.get(seq.checked_sub(self.first_seq)?)
+    }
+
+    /// Return the top row index that shows the newest records.
+    fn bottom_top(&self) -> usize {
+        self.visible.len().saturating_sub(self.height.max(1))
+    }
+
+    /// Return the top row index currently displayed.
+    fn display_top(&self) -> usize {
+        if self.follow {
+            self.bottom_top()
+        } else {
+            self.top
+        }
+    }
+
+    /// Keep the top row within range.
+    fn clamp_top(&mut self) {
+        self.top = self.top.min(self.bottom_top());
+    }
+
+    /// Scroll by a signed row count, updating follow mode. Scrolling up leaves follow
+    /// mode and reaching the bottom resumes it. Returns `true` if the top row changed.
+    fn scroll_rows(&mut self, delta: isize) -> bool {
+        let before = self.display_top();
+        let bottom = self.bottom_top();
+        let top = before.saturating_add_signed(delta).min(bottom);
+        self.top = top;
+        self.follow = top == bottom && delta >= 0;
+        top != before
+    }
+
+    /// Return the number of rows in a page.
+    fn page_rows(&self) -> isize {
+        self.height.max(1) as isize
+    }
+
+    /// Move the current match forward or backward and scroll it into view.
+    fn jump_match(&mut self, forward: bool) {
+        let Some(regex) = &self.search else {
+            return;
+        };
+        let is_match = |seq: &&usize| {
+            self.record(**seq)
+                .is_some_and(|record| regex.is_match(&record.message))
+        };
+        let current = self
+            .current
+            .map(|seq| self.visible.partition_point(|&s| s < seq));
+        let found = match (current, forward) {
+            (Some(row), true) => self.visible.iter().skip(row + 1).find(is_match),
+            (None, true) => self.visible.iter().find(is_match),
+            (Some(row), false) => self.visible.iter().take(row).rev().find(is_match),
+            (None, false) => self.visible.iter().rev().find(is_match),
+        };
+        let Some(seq) = found.copied() else {
+            return;
+        };
+        self.current = Some(seq);
+        let row = self.visible.partition_point(|&s| s < seq);
+        let top = self.display_top();
+        let height = self.height.max(1);
+        if row < top || row >= top + height {
+            self.top = row.saturating_sub(height / 2).min(self.bottom_top());
+            self.follow = false;
+        }
+    }
+
+    /// Drain pending records from the channel and buffer. Returns true if any arrived.
+    fn drain_sources(&mut self) -> bool {
+        let mut incoming: Vec<LogRecord> = Vec::new();
+        if let Some(source) = &self.source {
+            incoming.extend(source.try_iter());
+        }
+        if let Some((buffer, cursor)) = &mut self.buffer {
+            incoming.extend(buffer.read(cursor));
+        }
+        let received = !incoming.is_empty();
+        for record in incoming {
+            self.push(record);
+        }
+        received
+    }
+}
+
+impl Default for LogView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scrollable for LogView {
+    fn scroll_up(&mut self, _c: &mut dyn Context) -> bool {
+        self.scroll_rows(-1)
+    }
+
+    fn scroll_down(&mut self, _c: &mut dyn Context) -> bool {
+        self.scroll_rows(1)
+    }
+
+    fn page_up(&mut self, _c: &mut dyn Context) -> bool {
+        self.scroll_rows(-self.page_rows())
+    }
+
+    fn page_down(&mut self, _c: &mut dyn Context) -> bool {
+        self.scroll_rows(self.page_rows())
+    }
+
+    fn scroll_to(&mut self, _c: &mut dyn Context, _x: u32, y: u32) -> bool {
+        let delta = y as isize - self.display_top() as isize;
+        self.scroll_rows(delta)
+    }
+
+    fn extent(&self, c: &dyn ReadContext) -> Size {
+        Size::new(c.view().canvas.w, self.visible.len() as u32)
+    }
+}
+
+impl Widget for LogView {
+    fn scrollable(&mut self) -> Option<&mut dyn Scrollable> {
+        Some(self)
+    }
+
+    fn layout(&self) -> Layout {
+        Layout::fill()
+    }
+
+    fn canvas(&self, view: Size<u32>, _ctx: &CanvasContext) -> Size<u32> {
+        view
+    }
+
+    fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+        let size = ctx.view().content_size();
+        self.height = size.h as usize;
+        if size.w == 0 || size.h == 0 {
+            return Ok(());
+        }
+        r.fill("logview", Rect::new(0, 0, size.w, size.h), ' ')?;
+        if !self.follow {
+            self.clamp_top();
+        }
+        let top = self.display_top();
+        for (row, &seq) in self.visible.iter().skip(top).take(self.height).enumerate() {
+            let Some(record) = self.record(seq) else {
+                continue;
+            };
+            let line = Line::new(0, row as u32, size.w);
+            let style = if self.current == Some(seq) {
+                r.fill("logview/current", line.into(), ' ')?;
+                "logview/current"
+            } else if self
+                .search
+                .as_ref()
+                .is_some_and(|regex| regex.is_match(&record.message))
+            {
+                "logview/match"
+            } else {
+                level_style(record.level)
+            };
+            let full = record.to_string();
+            let (text, _) = text::slice_by_columns(&full, 0, size.w as usize);
+            r.text(style, line, text)?;
+        }
+        Ok(())
+    }
+
+    fn poll(&mut self, c: &mut dyn Context) -> Option<Duration> {
+        if self.source.is_none() && self.buffer.is_none() {
+            return None;
+        }
+        if self.drain_sources() {
+            let size = c.view().content_size();
+            c.damage(Rect::new(0, 0, size.w, size.h));
+        }
+        Some(POLL_INTERVAL)
+    }
+
+    fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+        true
+    }
+
+    fn name(&self) -> NodeName {
+        NodeName::convert("log_view")
+    }
+}
+
+impl Loader for LogView {
+    fn load(c: &mut Canopy) -> Result<()> {
+        c.add_commands::<Self>()?;
+        ScrollCommands::load(c)?;
+        c.register_default_bindings("log_view", DEFAULT_BINDINGS)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use canopy::testing::dummyctx::DummyContext;
+
+    use super::*;
+
+    fn view(count: usize) -> LogView {
+        let mut view = LogView::new();
+        view.height = 3;
+        for i in 0..count {
+            let level = if i % 2 == 0 {
+                Level::Info
+            } else {
+                Level::Debug
+            };
+            view.push(LogRecord::new(level, format!("line {i}")));
+        }
+        view
+    }
+
+    fn shown(view: &LogView) -> Vec<String> {
+        view.visible
+            .iter()
+            .skip(view.display_top())
+            .take(view.height)
+            .filter_map(|&seq| view.record(seq))
+            .map(|record| record.message.clone())
+            .collect()
+    }
+
+    #[test]
+    fn follow_sticks_to_bottom_until_scrolled_up() {
+        let mut ctx = DummyContext::default();
+        let mut view = view(6);
+        assert_eq!(shown(&view), ["line 3", "line 4", "line 5"]);
+
+        view.scroll_up(&mut ctx);
+        view.scroll_up(&mut ctx);
+        assert!(!view.is_following());
+        view.push(LogRecord::new(Level::Info, "line 6"));
+        assert_eq!(shown(&view), ["line 1", "line 2", "line 3"]);
+
+        assert!(view.page_down(&mut ctx));
+        assert!(view.is_following());
+        assert_eq!(shown(&view), ["line 4", "line 5", "line 6"]);
+    }
+
+    #[test]
+    fn level_filter_and_capacity() {
+        let mut ctx = DummyContext::default();
+        let mut view = view(6).with_capacity(4);
+        view.push(LogRecord::new(Level::Error, "boom"));
+        assert_eq!(view.len(), 4);
+        view.set_level(&mut ctx, Level::Info);
+        assert_eq!(view.visible_len(), 2);
+        assert_eq!(shown(&view), ["line 4", "boom"]);
+    }
+
+    #[test]
+    fn search_moves_between_matches() -> Result<()> {
+        let mut ctx = DummyContext::default();
+        let mut view = view(10);
+        assert!(matches!(
+            view.search(&mut ctx, "(".into()),
+            Err(Error::Invalid(_))
+        ));
+
+        view.search(&mut ctx, r"line [27]$".into())?;
+        assert_eq!(view.current, Some(7));
+        view.search_prev(&mut ctx);
+        assert_eq!(view.current, Some(2));
+        assert!(!view.is_following());
+        assert!(shown(&view).contains(&"line 2".to_string()));
+        view.search_next(&mut ctx);
+        assert_eq!(view.current, Some(7));
+        Ok(())
+    }
+
+    #[test]
+    fn drains_channel_and_shared_buffer() {
+        let (tx, rx) = mpsc::channel();
+        let buffer = LogBuffer::new(8);
+        buffer.push(LogRecord::new(Level::Info, "from buffer"));
+        let mut view = LogView::new().with_source(rx).with_buffer(buffer.clone());
+        tx.send(LogRecord::new(Level::Info, "from channel"))
+            .unwrap();
+        assert!(view.drain_sources());
+        assert_eq!(view.len(), 2);
+        assert!(!view.drain_sources());
+
+        buffer.push(LogRecord::new(Level::Warn, "later"));
+        assert!(view.drain_sources());
+        assert_eq!(view.len(), 3);
+    }
+}
@@ -0,0 +1,194 @@
+//! Page navigation for data that is fetched one page at a time.
+
+use canopy::{
+    Canopy, Context, Loader, ReadContext, Widget, command, derive_commands, error::Result,
+    geom::Line, layout::Layout, render::Render, state::NodeName, text,
+};
+
+use crate::datasource::VecSource;
+
+/// Callback run with the zero-based page index whenever the current page changes.
+type PageHandler = Box<dyn FnMut(usize) + Send>;
+
+/// A one-line "page X of Y" indicator with first, previous, next, and last commands.
+///
+/// A paginator built with [`Paginator::with_source`] loads each page into a
+/// [`VecSource`] as it is shown. A `List` bound to that source follows along, so paged
+/// data can be browsed without loading every page.
+pub struct Paginator {
+    /// Zero-based current page.
+    page: usize,
+    /// Total number of pages.
+    pages: usize,
+    /// Loader run when the page changes.
+    on_page: Option<PageHandler>,
+    /// Whether the current page has been handed to the loader.
+    loaded: bool,
+}
+
+#[derive_commands]
+impl Paginator {
+    /// Construct a paginator over `pages` pages, starting at the first.
+    pub fn new(pages: usize) -> Self {
+        Self {
+            page: 0,
+            pages,
+            on_page: None,
+            loaded: false,
+        }
+    }
+
+    /// Build a paginator that replaces the rows of `source` with `load(page)` whenever
+    /// a page is shown. The first page is loaded when the paginator mounts.
+    pub fn with_source<T: Send + 'static>(
+        mut self,
+        source: VecSource<T>,
+        mut load: impl FnMut(usize) -> Vec<T> + Send + 'static,
+    ) -> Self {
+        self.on_page = Some(Box::new(move |page| source.replace_all(load(page))));
+        self
+    }
+
+    /// Build a paginator that calls `f` with the page index whenever a page is shown.
+    pub fn with_on_page(mut self, f: impl FnMut(usize) + Send + 'static) -> Self {
+        self.on_page = Some(Box::new(f));
+        self
+    }
+
+    /// Return the zero-based current page.
+    pub fn page(&self) -> usize {
+        self.page
+    }
+
+    /// Return the total number of pages.
+    pub fn pages(&self) -> usize {
+        self.pages
+    }
+
+    /// Change the total number of pages, moving to the last page if the current one no
+    /// longer exists.
+    pub fn set_pages(&mut self, pages: usize) {
+        self.pages = pages;
+        self.show(self.page);
+    }
+
+    /// Show `page`, clamped to the available pages, and load it if it changed.
+    fn show(&mut self, page: usize) {
+        let page = page.min(self.pages.saturating_sub(1));
+        if page == self.page && self.loaded {
+            return;
+        }
+        self.page = page;
+        if self.pages == 0 {
+            return;
+        }
+        self.loaded = true;
+        if let Some(on_page) = &mut self.on_page {
+            on_page(page);
+        }
+    }
+
+    /// Text shown by the indicator.
+    fn label(&self) -> String {
+        if self.pages == 0 {
+            "no pages".to_string()
+        } else {
+            format!("page {} of {}", self.page + 1, self.pages)
+        }
+    }
+
+    /// Go to the first page.
+    #[command]
+    pub fn first(&mut self, _c: &mut dyn Context) {
+        self.show(0);
+    }
+
+    /// Go to the previous page.
+    #[command]
+    pub fn prev(&mut self, _c: &mut dyn Context) {
+        self.show(self.page.saturating_sub(1));
+    }
+
+    /// Go to the next page.
+    #[command]
+    pub fn next(&mut self, _c: &mut dyn Context) {
+        self.show(self.page.saturating_add(1));
+    }
+
+    /// Go to the last page.
+    #[command]
+    pub fn last(&mut self, _c: &mut dyn Context) {
+        self.show(self.pages.saturating_sub(1));
+    }
+}
+
+impl Widget for Paginator {
+    fn layout(&self) -> Layout {
+        Layout::fill().fixed_height(1)
+    }
+
+    fn on_mount(&mut self, _c: &mut dyn Context) -> Result<()> {
+        self.show(self.page);
+        Ok(())
+    }
+
+    fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+        let area = ctx.view().view_rect_local();
+        if area.w == 0 || area.h == 0 {
+            return Ok(());
+        }
+        r.fill("paginator", area.line(0).into(), ' ')?;
+        let label = self.label();
+        let (label, used) = text::slice_by_columns(&label, 0, area.w as usize);
+        r.text("paginator", Line::new(0, 0, used as u32), label)?;
+        Ok(())
+    }
+
+    fn name(&self) -> NodeName {
+        NodeName::convert("paginator")
+    }
+}
+
+impl Loader for Paginator {
+    fn load(c: &mut Canopy) -> Result<()> {
+        c.add_commands::<Self>()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use canopy::testing::harness::Harness;
+
+    use super::*;
+    use crate::datasource::DataSource;
+
+    #[test]
+    fn pages_load_into_the_source() -> Result<()> {
+        let source = VecSource::default();
+        let requested = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&requested);
+        let paginator = Paginator::new(3).with_source(source.clone(), move |page| {
+            log.lock().unwrap().push(page);
+            vec![page * 10, page * 10 + 1]
+        });
+        let mut h = Harness::builder(paginator).size(20, 1).build()?;
+        h.render()?;
+        h.expect_text("page 1 of 3");
+        assert_eq!(source.get(1), Some(1));
+
+        h.script("paginator.next()")?;
+        h.script("paginator.last()")?;
+        h.script("paginator.next()")?;
+        h.render()?;
+        h.expect_text("page 3 of 3");
+        assert_eq!(source.get(0), Some(20));
+
+        h.script("paginator.first()")?;
+        h.with_root_widget(|p: &mut Paginator| p.set_pages(0));
+        assert_eq!(*requested.lock().unwrap(), [0, 1, 2, 0]);
+        Ok(())
+    }
+}
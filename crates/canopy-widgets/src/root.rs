@@ -1,20 +1,25 @@
+use std::path::PathBuf;
+
 use canopy::{
-    Canopy, ChildKey, Context, Core, Loader, NodeId, ReadContext, TypedId, Widget, command,
+    Canopy, ChildKey, Context, Core, FocusCommands, Loader, NodeId, ReadContext, ScrollCommands,
+    TypedId, Widget, command,
     commands::{CommandNode, CommandSpec, FocusDirection},
     derive_commands,
     error::{Error, Result},
-    layout::{Direction, Layout, Sizing},
+    geom::{self, Size},
+    layout::{Align, Direction, Layout, Sizing},
     render::Render,
     state::NodeName,
     style::effects,
 };
 
-use crate::{help::Help, inspector::Inspector};
+use crate::{cmdline::CommandLine, help::Help, inspector::Inspector};
 
 /// Default root bindings exposed through `root.default_bindings()`.
 const DEFAULT_BINDINGS: &str = r#"
 inspector.default_bindings()
 help.default_bindings()
+command_line.default_bindings()
 
 canopy.bind_with("ctrl-Right", { path = "root", desc = "Toggle inspector" }, function()
     root.toggle_inspector()
@@ -22,9 +27,18 @@ end)
 canopy.bind_with("ctrl-/", { path = "root", desc = "Toggle help" }, function()
     root.toggle_help()
 end)
+canopy.bind_with(":", { path = "root", desc = "Command line" }, function()
+    root.command_line()
+end)
 canopy.bind_with("q", { path = "root", desc = "Quit" }, function()
     root.quit()
 end)
+canopy.bind_with("ctrl-z", { path = "root", desc = "Suspend" }, function()
+    root.suspend()
+end)
+canopy.bind_with("ctrl-e", { path = "root", desc = "Dismiss render error" }, function()
+    root.dismiss_error()
+end)
 canopy.bind_with("a", { path = "inspector", desc = "Focus app" }, function()
     root.focus_app()
 end)
@@ -36,6 +50,9 @@ canopy::key!(InspectorSlot: Inspector);
 // Typed key for the help slot
 canopy::key!(HelpSlot: Help);
 
+// Typed key for the command line slot
+canopy::key!(CommandLineSlot: CommandLine);
+
 /// Key for the application subtree under root (widget type varies).
 const KEY_APP: &str = "AppSlot";
 
@@ -48,6 +65,8 @@ pub struct Root {
     inspector_active: bool,
     /// Whether the help modal is visible.
     help_active: bool,
+    /// Size the main pane requires when the terminal is smaller than that.
+    too_small: Option<Size>,
 }
 
 #[derive_commands]
@@ -57,6 +76,7 @@ impl Root {
         Self {
             inspector_active: false,
             help_active: false,
+            too_small: None,
         }
     }
 
@@ -73,14 +93,16 @@ impl Root {
         let inspector = self.inspector_id(c)?;
         let help = self.help_id(c)?;
 
-        // Main pane uses Row for app + inspector
+        // Main pane uses Row for app + inspector, and is replaced by a placeholder when the
+        // terminal is too small to hold it
+        c.set_hidden_of(main_pane, self.too_small.is_some());
         c.set_hidden_of(inspector, !self.inspector_active);
         c.set_layout_of(main_pane, Layout::fill().direction(Direction::Row))?;
-        c.set_layout_of(app, Layout::fill())?;
-        c.set_layout_of(inspector, Layout::fill())?;
+        c.with_layout_of(app, &mut fill_axes)?;
+        c.with_layout_of(inspector, &mut fill_axes)?;
 
         // Help overlay
-        c.set_hidden_of(help, !self.help_active);
+        c.set_hidden_of(help, !self.help_active || self.too_small.is_some());
         c.set_layout_of(help, Layout::fill())?;
 
         // Dim effect on main pane when help is visible
@@ -90,8 +112,8 @@ impl Root {
             c.clear_effects(main_pane)?;
         }
 
-        // Root uses Stack layout so help overlays main pane
-        c.set_layout(Layout::fill().direction(Direction::Stack))?;
+        // Root uses Stack layout so help and the command line overlay the main pane
+        c.set_layout(root_layout())?;
 
         Ok(())
     }
@@ -138,6 +160,19 @@ impl Root {
         Ok(())
     }
 
+    #[command]
+    /// Suspend the program to the shell. The UI is restored when the program resumes.
+    pub fn suspend(&mut self, c: &mut dyn Context) -> Result<()> {
+        c.suspend()
+    }
+
+    #[command]
+    /// Dismiss the render error overlay and retry rendering the failed node.
+    pub fn dismiss_error(&mut self, c: &mut dyn Context) -> Result<()> {
+        c.dismiss_render_failure();
+        Ok(())
+    }
+
     #[command]
     /// Dump diagnostic information about the tree, focus, and bindings.
     pub fn dump_diagnostics(&mut self, c: &mut dyn Context) -> Result<()> {
@@ -146,6 +181,15 @@ impl Root {
         Ok(())
     }
 
+    /// Write the screen to a file once the current frame has rendered: plain text at
+    /// `path`, and styled copies at `path` with `ansi` and `html` appended.
+    /// @param path Destination file path.
+    #[command]
+    pub fn screenshot(&mut self, c: &mut dyn Context, path: String) -> Result<()> {
+        c.request_screenshot(PathBuf::from(path));
+        Ok(())
+    }
+
     /// Move focus in the specified direction.
     /// @param direction The direction to move focus.
     #[command]
@@ -166,8 +210,10 @@ impl Root {
     pub fn hide_inspector(&mut self, c: &mut dyn Context) -> Result<()> {
         self.inspector_active = false;
         self.sync_layout(c)?;
-        let app = self.app_id(c)?;
-        c.focus_first_in(app);
+        if !c.focus_pop() {
+            let app = self.app_id(c)?;
+            c.focus_first_in(app);
+        }
         Ok(())
     }
 
@@ -177,6 +223,7 @@ impl Root {
         self.inspector_active = true;
         self.sync_layout(c)?;
         let inspector = self.inspector_id(c)?;
+        c.focus_push();
         c.focus_first_in(inspector);
         Ok(())
     }
@@ -211,7 +258,8 @@ impl Root {
 
         self.help_active = true;
         self.sync_layout(c)?;
-        c.focus_first_in(help);
+        c.focus_push();
+        c.push_focus_scope(help);
         Ok(())
     }
 
@@ -220,11 +268,87 @@ impl Root {
     pub fn hide_help(&mut self, c: &mut dyn Context) -> Result<()> {
         self.help_active = false;
         self.sync_layout(c)?;
-        let app = self.app_id(c)?;
-        c.focus_first_in(app);
+        let help = self.help_id(c)?;
+        c.pop_focus_scope(help);
+        if !c.focus_pop() {
+            let app = self.app_id(c)?;
+            c.focus_first_in(app);
+        }
         Ok(())
     }
 
+    #[command]
+    /// Open the command line prompt at the bottom of the screen.
+    pub fn command_line(&mut self, c: &mut dyn Context) -> Result<()> {
+        c.with_child::<CommandLineSlot, _>(|cmdline, ctx| {
+            cmdline.open(ctx);
+            Ok(())
+        })
+    }
+
+    /// Start recording commands and keys into a macro register.
+    /// @param register Register name, such as `a`.
+    #[command]
+    pub fn record_macro(&mut self, c: &mut dyn Context, register: String) {
+        c.start_recording(&register);
+    }
+
+    /// Stop recording the current macro.
+    #[command]
+    pub fn stop_macro(&mut self, c: &mut dyn Context) {
+        c.stop_recording();
+    }
+
+    /// Replay a recorded macro.
+    /// @param register Register name.
+    /// @param count Number of times to replay.
+    #[command]
+    pub fn replay_macro(&mut self, c: &mut dyn Context, register: String, count: usize) {
+        c.replay(&register, count);
+    }
+
+    /// Save widget state, scroll positions, and focus as a named session.
+    /// @param name Session name.
+    #[command]
+    pub fn save_session(&mut self, c: &mut dyn Context, name: String) {
+        c.save_session(&name);
+    }
+
+    /// Restore a named session.
+    /// @param name Session name.
+    #[command]
+    pub fn load_session(&mut self, c: &mut dyn Context, name: String) {
+        c.load_session(&name);
+    }
+
+    /// Undo the most recent registered change.
+    #[command]
+    pub fn undo(&mut self, c: &mut dyn Context) -> Result<()> {
+        c.undo()?;
+        Ok(())
+    }
+
+    /// Redo the most recently undone change.
+    #[command]
+    pub fn redo(&mut self, c: &mut dyn Context) -> Result<()> {
+        c.redo()?;
+        Ok(())
+    }
+
+    /// Toggle high-contrast rendering.
+    #[command]
+    pub fn toggle_high_contrast(&mut self, c: &mut dyn Context) {
+        let enabled = !c.high_contrast();
+        c.set_high_contrast(enabled);
+    }
+
+    /// Toggle reduced motion, which asks widgets to skip animation.
+    #[command]
+    pub fn toggle_reduced_motion(&mut self, c: &mut dyn Context) {
+        let enabled = !c.reduced_motion();
+        c.set_reduced_motion(enabled);
+    }
+
     #[command]
     /// Toggle help modal visibility.
     pub fn toggle_help(&mut self, c: &mut dyn Context) -> Result<()> {
@@ -282,27 +406,42 @@ impl Root {
         let help = Help::install(core)?;
         core.set_hidden(help, true);
 
-        // Set up root with main pane and help as children
+        // Create command line (hidden by default)
+        let cmdline = core.create_detached(CommandLine::new());
+        core.set_hidden(cmdline, true);
+
+        // Set up root with main pane, help, and command line as children
         let root = Self::new().with_inspector(inspector_active);
         core.replace_subtree(core.root_id(), root)?;
         core.attach_keyed(core.root_id(), KEY_MAIN_PANE, main_pane)?;
         core.attach_keyed(core.root_id(), HelpSlot::KEY, help)?;
+        core.attach_keyed(core.root_id(), CommandLineSlot::KEY, cmdline)?;
 
         // Configure layout
         core.set_hidden(inspector, !inspector_active);
-        core.set_layout_of(core.root_id(), Layout::fill().direction(Direction::Stack))?;
-        core.with_layout_of(app, |layout| {
-            *layout = layout.width(Sizing::Flex(1)).height(Sizing::Flex(1));
-        })?;
-        core.with_layout_of(inspector, |layout| {
-            *layout = layout.width(Sizing::Flex(1)).height(Sizing::Flex(1));
-        })?;
+        core.set_layout_of(core.root_id(), root_layout())?;
+        core.with_layout_of(app, fill_axes)?;
+        core.with_layout_of(inspector, fill_axes)?;
         core.set_layout_of(help, Layout::fill())?;
+        core.with_layout_of(cmdline, |layout| *layout = layout.fixed_height(1))?;
 
         Ok(core.root_id())
     }
 }
 
+/// Root stack layout. Full-size overlays cover the main pane and the one-line command line
+/// sits at the bottom.
+fn root_layout() -> Layout {
+    Layout::fill()
+        .direction(Direction::Stack)
+        .align_vertical(Align::End)
+}
+
+/// Give a pane flex sizing on both axes, keeping its other layout constraints.
+fn fill_axes(layout: &mut Layout) {
+    *layout = layout.width(Sizing::Flex(1)).height(Sizing::Flex(1));
+}
+
 /// Simple container widget for the main pane (app + inspector).
 struct MainPane;
 
@@ -323,7 +462,28 @@ impl CommandNode for MainPane {
 }
 
 impl Widget for Root {
-    fn render(&mut self, _rndr: &mut canopy::render::Render, _ctx: &dyn ReadContext) -> Result<()> {
+    fn render(&mut self, rndr: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+        let Some(required) = self.too_small else {
+            return Ok(());
+        };
+        let area = ctx.view().view_rect_local();
+        if area.is_zero() {
+            return Ok(());
+        }
+        let message = format!("terminal too small (need {}x{})", required.w, required.h);
+        let line = area
+            .align(Size::new(message.len() as u32, 1), geom::Align::Center)
+            .line(0);
+        rndr.text("root/too_small", line, &message)
+    }
+
+    fn on_resize(&mut self, size: Size<u32>, c: &mut dyn Context) -> Result<()> {
+        let required = c.min_size_of(self.main_pane_id(c)?);
+        let too_small = (size.w < required.w || size.h < required.h).then_some(required);
+        if too_small != self.too_small {
+            self.too_small = too_small;
+            self.sync_layout(c)?;
+        }
         Ok(())
     }
 
@@ -348,6 +508,9 @@ impl Loader for Root {
         c.register_default_bindings("root", DEFAULT_BINDINGS)?;
         Inspector::load(c)?;
         Help::load(c)?;
+        CommandLine::load(c)?;
+        ScrollCommands::load(c)?;
+        FocusCommands::load(c)?;
         Ok(())
     }
 }
@@ -358,14 +521,19 @@ mod tests {
         ReadContext, Widget,
         commands::{CommandNode, CommandSpec},
         error::Result,
-        geom::Size,
+        event::key::KeyCode,
+        geom::{Point, Size},
         layout::Layout,
         render::Render,
         state::NodeName,
-        testing::render::NopBackend,
+        style::Color,
+        testing::{harness::Harness, render::NopBackend},
     };
 
+    use std::{env, fs, process};
+
     use super::*;
+    use crate::Dropdown;
 
     struct App;
 
@@ -483,4 +651,213 @@ mod tests {
 
         Ok(())
     }
+
+    fn command_line_harness() -> Result<(Harness, NodeId, NodeId, NodeId)> {
+        let (mut canopy, backend, left, right) = setup_root_tree()?;
+        run_script(&mut canopy, "root.default_bindings()")?;
+        let cmdline = canopy
+            .core()
+            .child_keyed(canopy.root_id(), CommandLineSlot::KEY)
+            .expect("command line slot");
+        let root = canopy.root_id();
+        let harness = Harness {
+            canopy,
+            backend,
+            root,
+        };
+        Ok((harness, cmdline, left, right))
+    }
+
+    #[test]
+    fn screenshot_writes_plain_and_styled_files() -> Result<()> {
+        let (mut canopy, mut backend, _left, _right) = setup_root_tree()?;
+        let path = env::temp_dir().join(format!("canopy-screenshot-{}.txt", process::id()));
+        let (ansi, html) = (
+            path.with_added_extension("ansi"),
+            path.with_added_extension("html"),
+        );
+        run_script(
+            &mut canopy,
+            &format!("root.screenshot({:?})", path.display().to_string()),
+        )?;
+        canopy.render(&mut backend)?;
+
+        let read = |path: &PathBuf| {
+            let text = fs::read_to_string(path).map_err(Error::from);
+            let _ignored = fs::remove_file(path);
+            text
+        };
+        let (plain, styled, page) = (read(&path)?, read(&ansi)?, read(&html)?);
+        assert_eq!(plain.lines().count(), 6);
+        assert!(plain.lines().all(|line| line.chars().count() == 20));
+        assert!(styled.contains("\x1b[0;"));
+        assert!(page.starts_with("<!DOCTYPE html>"));
+
+        // A path without an extension gets one for the plain text.
+        let bare = path.with_extension("");
+        canopy.screenshot(&bare)?;
+        let (plain, ansi, html) = (
+            bare.with_extension("txt"),
+            bare.with_extension("ansi"),
+            bare.with_extension("html"),
+        );
+        assert_eq!(read(&plain)?.lines().count(), 6);
+        read(&ansi)?;
+        read(&html)?;
+        Ok(())
+    }
+
+    #[test]
+    fn accessibility_toggles_via_script() -> Result<()> {
+        let (mut canopy, mut backend, _left, _right) = setup_root_tree()?;
+        run_script(
+            &mut canopy,
+            "root.toggle_high_contrast()\nroot.toggle_reduced_motion()",
+        )?;
+        assert!(canopy.high_contrast());
+        assert!(canopy.reduced_motion());
+
+        canopy.render(&mut backend)?;
+        let buf = canopy.buf().expect("rendered frame");
+        for y in 0..buf.size().h {
+            for x in 0..buf.size().w {
+                let style = buf.get(Point { x, y }).expect("cell").style;
+                assert!(matches!(style.bg, Color::Black | Color::White));
+                assert_ne!(style.fg, style.bg);
+            }
+        }
+
+        run_script(&mut canopy, "root.toggle_high_contrast()")?;
+        assert!(!canopy.high_contrast());
+        Ok(())
+    }
+
+    #[test]
+    fn command_line_runs_commands_relative_to_prior_focus() -> Result<()> {
+        let (mut h, cmdline, left, right) = command_line_harness()?;
+
+        h.key(':')?;
+        assert_eq!(h.canopy.core().focus_id(), Some(cmdline));
+        let outer = h.canopy.core().node(cmdline).map(|node| node.view().outer);
+        assert_eq!(outer.map(|r| (r.tl.y, r.h)), Some((5, 1)));
+
+        h.type_text("root::focus(Next)")?;
+        h.key(KeyCode::Enter)?;
+        assert_eq!(h.canopy.core().focus_id(), Some(right));
+        assert!(
+            h.canopy
+                .core()
+                .node(cmdline)
+                .is_some_and(|node| node.hidden())
+        );
+
+        h.key(':')?;
+        h.type_text("lua root.focus(\"Prev\")")?;
+        h.key(KeyCode::Enter)?;
+        assert_eq!(h.canopy.core().focus_id(), Some(left));
+
+        h.key(':')?;
+        h.key(KeyCode::Esc)?;
+        assert_eq!(h.canopy.core().focus_id(), Some(left));
+        Ok(())
+    }
+
+    #[test]
+    fn command_line_completes_and_recalls_history() -> Result<()> {
+        let (mut h, cmdline, _left, _right) = command_line_harness()?;
+
+        h.key(':')?;
+        h.type_text("root::toggle_h")?;
+        h.key(KeyCode::Tab)?;
+        h.with_widget(cmdline, |c: &mut CommandLine| {
+            assert_eq!(c.value(), "root::toggle_help");
+        });
+
+        h.key(KeyCode::Esc)?;
+        h.key(':')?;
+        h.type_text("nope")?;
+        h.key(KeyCode::Enter)?;
+        assert_eq!(h.canopy.core().focus_id(), Some(cmdline));
+        h.with_widget(cmdline, |c: &mut CommandLine| {
+            assert_eq!(c.value(), "nope");
+            assert!(c.message().is_some_and(|m| m.contains("nope")));
+            assert_eq!(c.history(), ["nope"]);
+        });
+
+        h.key(KeyCode::Esc)?;
+        h.key(':')?;
+        h.key(KeyCode::Up)?;
+        h.with_widget(cmdline, |c: &mut CommandLine| {
+            assert_eq!(c.value(), "nope");
+        });
+        Ok(())
+    }
+
+    #[test]
+    fn too_small_terminal_shows_placeholder() -> Result<()> {
+        let mut canopy = Canopy::new();
+        Root::load(&mut canopy)?;
+        let app = canopy.create_detached(FocusLeaf::new("app"));
+        Root::install(canopy.core_mut(), app)?;
+        canopy.core_mut().with_layout_of(app, |layout| {
+            *layout = layout.min_width(30).min_height(4);
+        })?;
+        canopy.set_root_size(Size::new(40, 3))?;
+        let mut backend = NopBackend::new();
+        canopy.render(&mut backend)?;
+
+        let screen = canopy.buf().expect("rendered frame").screen_text();
+        assert!(screen.contains("terminal too small (need 30x4)"));
+        let main_pane = canopy.core().child_keyed(canopy.root_id(), KEY_MAIN_PANE);
+        let pane_hidden = |canopy: &Canopy| {
+            main_pane
+                .and_then(|id| canopy.core().node(id))
+                .is_some_and(|node| node.hidden())
+        };
+        assert!(pane_hidden(&canopy));
+
+        canopy.set_root_size(Size::new(40, 4))?;
+        canopy.render(&mut backend)?;
+        let screen = canopy.buf().expect("rendered frame").screen_text();
+        assert!(!screen.contains("too small"));
+        assert!(!pane_hidden(&canopy));
+        Ok(())
+    }
+
+    #[test]
+    fn undo_and_redo_reverse_widget_changes() -> Result<()> {
+        let mut canopy = Canopy::new();
+        Root::load(&mut canopy)?;
+        canopy.add_commands::<Dropdown<&'static str>>()?;
+        canopy.enable_undo(8);
+        let app = canopy.create_detached(Dropdown::new(vec!["a", "b", "c"]));
+        Root::install(canopy.core_mut(), app)?;
+        canopy.set_root_size(Size::new(20, 6))?;
+        let root = canopy.root_id();
+        let mut h = Harness {
+            canopy,
+            backend: NopBackend::new(),
+            root,
+        };
+        let selected = |h: &mut Harness| {
+            h.with_widget(app, |d: &mut Dropdown<&'static str>| d.selected_index())
+        };
+
+        h.script("dropdown.select(2)")?;
+        h.script("dropdown.select(1)")?;
+        h.script("root.undo()")?;
+        assert_eq!(selected(&mut h), 2);
+        h.script("root.undo()")?;
+        assert_eq!(selected(&mut h), 0);
+        h.script("root.undo()")?;
+        assert_eq!(selected(&mut h), 0);
+        h.script("root.redo()")?;
+        assert_eq!(selected(&mut h), 2);
+
+        h.script("dropdown.select(0)")?;
+        let undo = h.canopy.undo_manager().expect("undo enabled");
+        assert_eq!(undo.undo_len(), 2);
+        assert_eq!(undo.redo_len(), 0);
+        Ok(())
+    }
 }
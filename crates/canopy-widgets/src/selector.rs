@@ -1,10 +1,11 @@
 //! Selector widget for multi-value selection with checkbox-style items.
 
 use canopy::{
-    Context, EventOutcome, ReadContext, Widget, command, derive_commands,
+    Context, EventOutcome, PersistentState, ReadContext, Widget, command, derive_commands,
     error::Result,
     event::{Event, mouse},
     layout::{MeasureConstraints, Measurement, Size},
+    persist::{Value, from_state, to_state},
     render::Render,
     state::NodeName,
 };
@@ -278,11 +279,39 @@ where
         true
     }
 
+    fn persistent(&mut self) -> Option<&mut dyn PersistentState> {
+        Some(self)
+    }
+
     fn name(&self) -> NodeName {
         NodeName::convert("selector")
     }
 }
 
+/// Selectors persist the focused item and the selection, in selection order.
+impl<T> PersistentState for Selector<T>
+where
+    T: SelectorItem + 'static,
+{
+    fn save_state(&self, _ctx: &dyn ReadContext) -> Result<Value> {
+        to_state(&(self.focused, &self.selected))
+    }
+
+    fn restore_state(&mut self, state: Value, _ctx: &mut dyn Context) -> Result<()> {
+        let (focused, selected): (usize, Vec<usize>) = from_state(state)?;
+        let len = self.items.len();
+        self.focused = focused.min(len.saturating_sub(1));
+        self.selected.clear();
+        for index in selected {
+            if index < len && !self.selected.contains(&index) {
+                self.selected.push(index);
+            }
+        }
+        debug_assert!(self.selection_invariant_holds());
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use canopy::testing::dummyctx::DummyContext;
@@ -381,4 +410,27 @@ mod tests {
         assert_eq!(selector.selected_count(), 0);
         Ok(())
     }
+
+    #[test]
+    fn state_restores_focus_and_valid_selections() -> Result<()> {
+        let items = || vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let mut ctx = DummyContext::default();
+        let mut saved = Selector::new(items());
+        saved.select_by(&mut ctx, 2)?;
+        saved.toggle(&mut ctx)?;
+        saved.select_first(&mut ctx)?;
+        saved.toggle(&mut ctx)?;
+        let state = saved.save_state(&ctx)?;
+
+        let mut restored = Selector::new(items());
+        restored.restore_state(state.clone(), &mut ctx)?;
+        assert_eq!(restored.focused_index(), 0);
+        assert_eq!(restored.selected_indices(), &[2, 0]);
+
+        let mut shorter = Selector::new(vec!["a".to_string()]);
+        shorter.restore_state(state, &mut ctx)?;
+        assert_eq!(shorter.focused_index(), 0);
+        assert_eq!(shorter.selected_indices(), &[0]);
+        Ok(())
+    }
 }
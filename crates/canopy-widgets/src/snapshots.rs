@@ -6,12 +6,13 @@ mod tests {
         Canopy, Context, Loader, Widget,
         commands::{CommandNode, CommandSpec},
         error::Result,
+        geom::Size,
         layout::Layout,
         state::NodeName,
         testing::harness::Harness,
     };
 
-    use crate::{BoxGlyphs, Button, Frame, List, ScrollGlyphs, Text};
+    use crate::{BoxGlyphs, Button, Frame, List, ScrollGlyphs, Tabs, Text};
 
     const ASCII_BOX: BoxGlyphs = BoxGlyphs {
         topleft: '+',
@@ -163,4 +164,51 @@ mod tests {
         assert_snapshot("list", &snapshot);
         Ok(())
     }
+
+    /// Terminal sizes down to nothing, including single zero dimensions.
+    const TINY_SIZES: [(u32, u32); 6] = [(0, 0), (0, 3), (3, 0), (1, 1), (2, 1), (1, 2)];
+
+    /// Render at each tiny size, then grow back, checking nothing panics.
+    fn render_tiny<W: Widget + 'static>(widget: W) -> Result<()> {
+        let mut harness = Harness::builder(SnapshotRoot::new(widget))
+            .size(10, 4)
+            .build()?;
+        harness.render()?;
+        for (w, h) in TINY_SIZES {
+            harness.resize(Size::new(w, h))?;
+        }
+        harness.resize(Size::new(10, 4))?;
+        Ok(())
+    }
+
+    #[test]
+    fn frame_renders_at_tiny_sizes() -> Result<()> {
+        render_tiny(Frame::new().with_title("Title"))
+    }
+
+    #[test]
+    fn list_renders_at_tiny_sizes() -> Result<()> {
+        let mut harness = Harness::builder(List::<Text>::new()).size(10, 4).build()?;
+        harness.with_root_context(|list: &mut List<Text>, ctx| {
+            for item in ["One", "Two", "Three"] {
+                list.append(ctx, Text::new(item))?;
+            }
+            Ok(())
+        })?;
+        for (w, h) in TINY_SIZES {
+            harness.resize(Size::new(w, h))?;
+            harness.with_root_context(|list: &mut List<Text>, ctx| {
+                list.select_by(ctx, 1)?;
+                list.page(ctx, 1)
+            })?;
+            harness.render()?;
+        }
+        harness.resize(Size::new(10, 4))?;
+        Ok(())
+    }
+
+    #[test]
+    fn tabs_render_at_tiny_sizes() -> Result<()> {
+        render_tiny(Tabs::new(["one", "two", "three"]))
+    }
 }
@@ -0,0 +1,187 @@
+use std::{collections::HashSet, ops::Range};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Number of corrections offered for a misspelled word unless configured otherwise.
+const DEFAULT_SUGGESTIONS: usize = 5;
+/// Largest edit distance between a misspelling and a [`WordList`] suggestion.
+const MAX_DISTANCE: usize = 2;
+
+/// Dictionary backend consulted by [`SpellCheck`].
+pub trait Dictionary: Send {
+    /// Whether `word` is spelled correctly.
+    fn contains(&self, word: &str) -> bool;
+
+    /// Return up to `limit` corrections for `word`, best first.
+    fn suggest(&self, word: &str, limit: usize) -> Vec<String>;
+}
+
+/// An in-memory dictionary built from a word list.
+///
+/// Lookups ignore case. Suggestions are the words closest to the misspelling, up to two
+/// single-character edits away.
+#[derive(Debug, Clone, Default)]
+pub struct WordList {
+    /// Known words, lowercased.
+    words: HashSet<String>,
+}
+
+impl WordList {
+    /// Construct a dictionary holding `words`.
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut list = Self::default();
+        for word in words {
+            list.insert(word.as_ref());
+        }
+        list
+    }
+
+    /// Construct a dictionary from text with one word per line, such as a system word
+    /// file. Blank lines are skipped.
+    pub fn from_lines(text: &str) -> Self {
+        Self::new(text.lines().map(str::trim).filter(|line| !line.is_empty()))
+    }
+
+    /// Add a word.
+    pub fn insert(&mut self, word: &str) {
+        self.words.insert(word.to_lowercase());
+    }
+}
+
+impl Dictionary for WordList {
+    fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.to_lowercase())
+    }
+
+    fn suggest(&self, word: &str, limit: usize) -> Vec<String> {
+        let word = word.to_lowercase();
+        let mut found: Vec<(usize, &String)> = self
+            .words
+            .iter()
+            .filter_map(|candidate| Some((edit_distance(&word, candidate)?, candidate)))
+            .collect();
+        found.sort();
+        found
+            .into_iter()
+            .take(limit)
+            .map(|(_, candidate)| candidate.clone())
+            .collect()
+    }
+}
+
+/// Finds misspelled words in text entry widgets and suggests corrections.
+///
+/// Widgets underline misspelled words over their text style, using the underline of the
+/// `editor/misspelled` style. Only words made of letters and apostrophes are
+/// checked, so numbers and identifiers such as `utf8` are left alone.
+pub struct SpellCheck {
+    /// Dictionary backend.
+    dictionary: Box<dyn Dictionary>,
+    /// Maximum number of corrections offered for a word.
+    limit: usize,
+}
+
+impl SpellCheck {
+    /// Construct a spell checker backed by `dictionary`.
+    pub fn new(dictionary: impl Dictionary + 'static) -> Self {
+        Self {
+            dictionary: Box::new(dictionary),
+            limit: DEFAULT_SUGGESTIONS,
+        }
+    }
+
+    /// Set the maximum number of corrections offered for a word.
+    pub fn with_suggestion_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Whether `word` is checked and missing from the dictionary.
+    pub fn is_misspelled(&self, word: &str) -> bool {
+        checkable(word) && !self.dictionary.contains(word)
+    }
+
+    /// Character ranges of the misspelled words in `text`.
+    pub fn misspelled(&self, text: &str) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+        let mut byte = 0;
+        let mut chars = 0;
+        for (offset, word) in text.unicode_word_indices() {
+            chars += text[byte..offset].chars().count();
+            byte = offset;
+            if self.is_misspelled(word) {
+                ranges.push(chars..chars + word.chars().count());
+            }
+        }
+        ranges
+    }
+
+    /// Corrections for `word`, best first, capitalized like `word`.
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let capitalized = word.chars().next().is_some_and(char::is_uppercase);
+        self.dictionary
+            .suggest(word, self.limit)
+            .into_iter()
+            .map(|suggestion| {
+                if capitalized {
+                    capitalize(&suggestion)
+                } else {
+                    suggestion
+                }
+            })
+            .collect()
+    }
+}
+
+/// Whether `word` is made only of letters and apostrophes.
+fn checkable(word: &str) -> bool {
+    word.chars().any(char::is_alphabetic) && word.chars().all(|ch| ch.is_alphabetic() || ch == '\'')
+}
+
+/// Uppercase the first character of `word`.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Levenshtein distance between `a` and `b`, or `None` if it exceeds [`MAX_DISTANCE`].
+fn edit_distance(a: &str, b: &str) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > MAX_DISTANCE {
+        return None;
+    }
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut row = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            row[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(row[j] + 1);
+        }
+        prev = row;
+    }
+    let distance = prev[b.len()];
+    (distance <= MAX_DISTANCE).then_some(distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marks_misspellings_and_suggests_corrections() {
+        let spell = SpellCheck::new(WordList::new(["the", "then", "cat", "can't"]))
+            .with_suggestion_limit(2);
+        assert_eq!(spell.misspelled("Teh cat can't x2 sta"), [0..3, 17..20]);
+        assert!(!spell.is_misspelled("CAT"));
+        assert_eq!(spell.suggest("Teh"), ["The", "Then"]);
+        assert!(spell.suggest("zzzzzz").is_empty());
+    }
+}
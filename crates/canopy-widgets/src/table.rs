@@ -0,0 +1,339 @@
+//! Table widget that renders rows from a data source.
+
+use canopy::{
+    Context, ReadContext, Scrollable, Widget, command, derive_commands,
+    error::Result,
+    geom::Line,
+    layout::{CanvasContext, Size},
+    render::Render,
+    state::NodeName,
+    text,
+};
+
+use crate::datasource::{DataChange, DataSource};
+
+/// A column of a [`Table`].
+pub struct Column {
+    /// Header text.
+    title: String,
+    /// Width in columns, excluding the separator.
+    width: u32,
+}
+
+impl Column {
+    /// Create a column with a header and a fixed width.
+    pub fn new(title: impl Into<String>, width: u32) -> Self {
+        Self {
+            title: title.into(),
+            width,
+        }
+    }
+}
+
+/// A table of rows drawn from a [`DataSource`], with a header line fixed to the top of
+/// the view and a selected row.
+///
+/// The table keeps the cell text of every row. When the source changes, only the
+/// inserted and updated rows are formatted again.
+pub struct Table<T> {
+    /// Columns, left to right.
+    columns: Vec<Column>,
+    /// Row data.
+    source: Box<dyn DataSource<T>>,
+    /// Turns a row into its cell text, one entry per column.
+    cells: Box<dyn Fn(&T) -> Vec<String> + Send>,
+    /// Formatted rows, in source order.
+    rows: Vec<String>,
+    /// Source version the rows were last synced to.
+    version: u64,
+    /// Selected row index.
+    selected: Option<usize>,
+}
+
+#[derive_commands]
+impl<T: 'static> Table<T> {
+    /// Create a table over `source`, using `cells` to produce each row's cell text.
+    pub fn new<S, F>(columns: Vec<Column>, source: S, cells: F) -> Self
+    where
+        S: DataSource<T> + 'static,
+        F: Fn(&T) -> Vec<String> + Send + 'static,
+    {
+        let mut table = Self {
+            columns,
+            source: Box::new(source),
+            cells: Box::new(cells),
+            rows: Vec::new(),
+            version: 0,
+            selected: None,
+        };
+        table.apply_changes(&[DataChange::Reset]);
+        table
+    }
+
+    /// Return the number of rows.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Return whether the table has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Return the selected row index.
+    pub fn selected_index(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Move the selection by a signed offset, clamped to the rows.
+    #[command]
+    pub fn select_by(&mut self, c: &mut dyn Context, delta: i32) -> Result<()> {
+        let Some(last) = self.rows.len().checked_sub(1) else {
+            return Ok(());
+        };
+        let current = self.selected.unwrap_or(0);
+        let next = if delta.is_negative() {
+            current.saturating_sub(delta.unsigned_abs() as usize)
+        } else {
+            current.saturating_add(delta as usize)
+        };
+        self.select(c, next.min(last));
+        Ok(())
+    }
+
+    /// Select the first row.
+    #[command]
+    pub fn select_first(&mut self, c: &mut dyn Context) -> Result<()> {
+        if !self.rows.is_empty() {
+            self.select(c, 0);
+        }
+        Ok(())
+    }
+
+    /// Select the last row.
+    #[command]
+    pub fn select_last(&mut self, c: &mut dyn Context) -> Result<()> {
+        if let Some(last) = self.rows.len().checked_sub(1) {
+            self.select(c, last);
+        }
+        Ok(())
+    }
+
+    /// Select row `index` and scroll it into view below the header.
+    fn select(&mut self, c: &mut dyn Context, index: usize) {
+        self.selected = Some(index);
+        let view = c.view().view_rect();
+        let rows = view.h.saturating_sub(1).max(1) as usize;
+        let top = view.tl.y as usize;
+        if index < top {
+            c.scroll_to(view.tl.x, index as u32);
+        } else if index >= top + rows {
+            c.scroll_to(view.tl.x, (index + 1 - rows) as u32);
+        }
+    }
+
+    /// Format a row's cells into one line, padding or cutting each cell to its column.
+    fn format_row(&self, cells: &[String]) -> String {
+        let mut line = String::new();
+        for (i, column) in self.columns.iter().enumerate() {
+            if i > 0 {
+                line.push(' ');
+            }
+            let cell = cells.get(i).map_or("", String::as_str);
+            let (cell, width) = text::slice_by_columns(cell, 0, column.width as usize);
+            line.push_str(cell);
+            line.push_str(&" ".repeat(column.width as usize - width));
+        }
+        line
+    }
+
+    /// Format the row at `index` from the source.
+    fn build(&self, index: usize) -> String {
+        self.source
+            .get(index)
+            .map(|row| self.format_row(&(self.cells)(&row)))
+            .unwrap_or_default()
+    }
+
+    /// Apply source changes to the formatted rows. Rows are moved in step with the
+    /// changes, and only inserted and updated rows are formatted again once the
+    /// structure matches the source.
+    fn apply_changes(&mut self, changes: &[DataChange]) {
+        let mut stale = vec![false; self.rows.len()];
+        let mut reset = false;
+        for change in changes {
+            match *change {
+                DataChange::Insert(index) if index <= self.rows.len() => {
+                    self.rows.insert(index, String::new());
+                    stale.insert(index, true);
+                }
+                DataChange::Remove(index) if index < self.rows.len() => {
+                    self.rows.remove(index);
+                    stale.remove(index);
+                }
+                DataChange::Update(index) if index < self.rows.len() => {
+                    stale[index] = true;
+                }
+                _ => {
+                    reset = true;
+                    break;
+                }
+            }
+        }
+        if reset || self.rows.len() != self.source.len() {
+            self.rows = vec![String::new(); self.source.len()];
+            stale = vec![true; self.rows.len()];
+        }
+        for (index, stale) in stale.into_iter().enumerate() {
+            if stale {
+                self.rows[index] = self.build(index);
+            }
+        }
+        self.version = self.source.version();
+        self.selected = match self.rows.len().checked_sub(1) {
+            Some(last) => self.selected.map(|index| index.min(last)),
+            None => None,
+        };
+    }
+
+    /// Return the width of a formatted row.
+    fn row_width(&self) -> u32 {
+        let cells: u32 = self.columns.iter().map(|column| column.width).sum();
+        cells + self.columns.len().saturating_sub(1) as u32
+    }
+}
+
+impl<T: Send + 'static> Scrollable for Table<T> {}
+
+impl<T: Send + 'static> Widget for Table<T> {
+    fn scrollable(&mut self) -> Option<&mut dyn Scrollable> {
+        Some(self)
+    }
+
+    fn on_mount(&mut self, c: &mut dyn Context) -> Result<()> {
+        let node = c.node_id();
+        c.observe_changes(node, self.source.changes());
+        let changes = self.source.changes_since(self.version);
+        self.apply_changes(&changes);
+        Ok(())
+    }
+
+    fn on_observed(&mut self, _c: &mut dyn Context) -> Result<()> {
+        let changes = self.source.changes_since(self.version);
+        self.apply_changes(&changes);
+        Ok(())
+    }
+
+    fn render(&mut self, rndr: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+        let view = ctx.view();
+        let view_rect = view.view_rect();
+        let origin = view.content_origin();
+        let x = view_rect.tl.x as usize;
+        let width = view_rect.w as usize;
+
+        let titles: Vec<String> = self.columns.iter().map(|c| c.title.clone()).collect();
+        let header = self.format_row(&titles);
+        let (header, _) = text::slice_by_columns(&header, x, width);
+        rndr.fill(
+            "table/header",
+            Line::new(origin.x, origin.y, view_rect.w).into(),
+            ' ',
+        )?;
+        rndr.text(
+            "table/header",
+            Line::new(origin.x, origin.y, view_rect.w),
+            header,
+        )?;
+
+        let top = view_rect.tl.y as usize;
+        let visible = view_rect.h.saturating_sub(1) as usize;
+        for (i, row) in self.rows.iter().enumerate().skip(top).take(visible) {
+            let y = origin.y + 1 + (i - top) as u32;
+            let line = Line::new(origin.x, y, view_rect.w);
+            let style = if self.selected == Some(i) {
+                "table/selected"
+            } else {
+                "table"
+            };
+            let (out, _) = text::slice_by_columns(row, x, width);
+            rndr.fill(style, line.into(), ' ')?;
+            rndr.text(style, line, out)?;
+        }
+        Ok(())
+    }
+
+    fn canvas(&self, view: Size<u32>, _ctx: &CanvasContext) -> Size<u32> {
+        Size::new(self.row_width().max(view.w), self.rows.len() as u32 + 1)
+    }
+
+    fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+        true
+    }
+
+    fn name(&self) -> NodeName {
+        NodeName::convert("table")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use canopy::{Canopy, Loader, testing::harness::Harness};
+
+    use super::*;
+    use crate::datasource::VecSource;
+
+    impl Loader for Table<(String, u32)> {
+        fn load(c: &mut Canopy) -> Result<()> {
+            c.add_commands::<Self>()?;
+            Ok(())
+        }
+    }
+
+    fn table(source: &VecSource<(String, u32)>) -> Table<(String, u32)> {
+        Table::new(
+            vec![Column::new("name", 5), Column::new("n", 3)],
+            source.clone(),
+            |(name, n): &(String, u32)| vec![name.clone(), n.to_string()],
+        )
+    }
+
+    #[test]
+    fn renders_rows_and_follows_source_changes() -> Result<()> {
+        let source = VecSource::new(vec![("ant".to_string(), 1), ("bee".to_string(), 2)]);
+        let mut h = Harness::builder(table(&source)).size(9, 4).build()?;
+        h.render()?;
+        assert_eq!(
+            h.buf().lines(),
+            ["name  n  ", "ant   1  ", "bee   2  ", "         "]
+        );
+
+        source.insert(0, ("cat".to_string(), 3));
+        source.insert(0, ("dog".to_string(), 4));
+        source.set(3, ("elk".to_string(), 5));
+        source.remove(2);
+        h.render()?;
+        assert_eq!(
+            h.buf().lines(),
+            ["name  n  ", "dog   4  ", "cat   3  ", "elk   5  "]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn selection_scrolls_below_the_header() -> Result<()> {
+        let rows = (0..10).map(|i| (format!("r{i}"), i)).collect();
+        let source = VecSource::new(rows);
+        let mut h = Harness::builder(table(&source)).size(9, 3).build()?;
+        h.script("table.select_last()")?;
+        h.render()?;
+        assert_eq!(h.buf().lines(), ["name  n  ", "r8    8  ", "r9    9  "]);
+
+        source.remove(9);
+        h.render()?;
+        assert_eq!(
+            h.with_root_widget(|t: &mut Table<(String, u32)>| t.selected_index()),
+            Some(8)
+        );
+        Ok(())
+    }
+}
@@ -1,5 +1,8 @@
 use canopy::{
-    Context, ReadContext, Widget, command, derive_commands, error::Result, render::Render,
+    Context, NodeId, PersistentState, ReadContext, Widget, command, derive_commands,
+    error::Result,
+    persist::{Value, from_state, to_state},
+    render::Render,
     state::NodeName,
 };
 
@@ -9,6 +12,8 @@ pub struct Tabs {
     tabs: Vec<String>,
     /// Active tab index.
     active: usize,
+    /// Content nodes shown for each tab, in tab order.
+    panes: Vec<NodeId>,
 }
 
 #[derive_commands]
@@ -22,31 +27,52 @@ impl Tabs {
         Self {
             active: 0,
             tabs: tabs.into_iter().map(|s| s.as_ref().to_string()).collect(),
+            panes: Vec::new(),
+        }
+    }
+
+    /// Attach one content node per tab. Only the active tab's pane is shown.
+    pub fn with_panes(mut self, panes: Vec<NodeId>) -> Self {
+        self.panes = panes;
+        self
+    }
+
+    /// Return the active tab index.
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Show the active pane, hide the others, and move focus into the active pane.
+    fn sync_panes(&self, c: &mut dyn Context) {
+        for (i, pane) in self.panes.iter().enumerate() {
+            c.set_hidden_of(*pane, i != self.active);
+        }
+        if let Some(pane) = self.panes.get(self.active) {
+            c.focus_first_in(*pane);
         }
     }
 
     /// Select a tab by signed offset.
     /// @param delta Signed tab delta. Positive moves forward and negative moves backward.
     #[command]
-    pub fn select_by(&mut self, _c: &mut dyn Context, delta: i32) {
+    pub fn select_by(&mut self, c: &mut dyn Context, delta: i32) {
         if self.tabs.is_empty() {
             return;
         }
         let len = self.tabs.len() as i32;
         self.active = (self.active as i32 + delta).rem_euclid(len) as usize;
+        self.sync_panes(c);
     }
 }
 
 impl Widget for Tabs {
     fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
-        if self.tabs.is_empty() {
+        let view = ctx.view().view_rect_local();
+        if self.tabs.is_empty() || view.h == 0 {
             return Ok(());
         }
 
-        let rects = ctx
-            .view()
-            .view_rect_local()
-            .split_horizontal(self.tabs.len() as u32)?;
+        let rects = view.split_horizontal(self.tabs.len() as u32)?;
         for (i, rect) in rects.iter().enumerate() {
             let styl = if i == self.active {
                 "tab/active"
@@ -60,7 +86,27 @@ impl Widget for Tabs {
         Ok(())
     }
 
+    fn persistent(&mut self) -> Option<&mut dyn PersistentState> {
+        Some(self)
+    }
+
     fn name(&self) -> NodeName {
         NodeName::convert("tabs")
     }
 }
+
+impl PersistentState for Tabs {
+    fn save_state(&self, _ctx: &dyn ReadContext) -> Result<Value> {
+        to_state(&self.active)
+    }
+
+    fn restore_state(&mut self, state: Value, ctx: &mut dyn Context) -> Result<()> {
+        let active: usize = from_state(state)?;
+        if self.tabs.is_empty() {
+            return Ok(());
+        }
+        self.active = active.min(self.tabs.len() - 1);
+        self.sync_panes(ctx);
+        Ok(())
+    }
+}
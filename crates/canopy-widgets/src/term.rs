@@ -1100,6 +1100,7 @@ fn cursor_from_state(state: &TerminalState) -> Option<cursor::Cursor> {
         },
         shape,
         blink: false,
+        color: None,
     })
 }
 
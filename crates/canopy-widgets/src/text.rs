@@ -1,18 +1,51 @@
-use std::cell::RefCell;
+use std::{borrow::Cow, cell::RefCell, fmt, ops::Range, sync::Arc};
 
 use canopy::{
-    Context, ReadContext, Widget, command, derive_commands,
-    error::Result,
+    Context, PersistentState, ReadContext, Scrollable, Widget, command, derive_commands,
+    error::{Error, Result},
     geom::{Direction, Line},
-    layout::{Constraint, MeasureConstraints, Measurement, Size},
+    layout::{Align, Constraint, Edges, MeasureConstraints, Measurement, Size},
+    persist::{Value, from_state, to_state},
     render::Render,
     state::NodeName,
     text,
 };
+use regex::Regex;
+use textwrap::{
+    Options as TextwrapOptions, WordSplitter, WrapAlgorithm as TextwrapAlgorithm,
+    core::{Word, break_words},
+    word_splitters::split_words,
+};
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
 use crate::Selectable;
 
+/// Number of wrap widths kept in the layout cache.
+const WRAP_CACHE_WIDTHS: usize = 4;
+
+/// Wrap cache key for unwrapped lines, used in ellipsis mode.
+const UNWRAPPED: usize = 0;
+
+/// Marker drawn at the end of truncated lines.
+const ELLIPSIS: &str = "\u{2026}";
+
+/// Glyph drawn in the first cell of a tab when whitespace is shown.
+const TAB_MARK: char = '\u{2192}';
+
+/// Glyph drawn for spaces when whitespace is shown.
+const SPACE_MARK: &str = "\u{b7}";
+
+/// Offset that places `len` cells within `space` cells under an alignment.
+fn align_offset(align: Align, space: u32, len: u32) -> u32 {
+    let slack = space.saturating_sub(len);
+    match align {
+        Align::Start => 0,
+        Align::Center => slack / 2,
+        Align::End => slack,
+    }
+}
+
 /// Canvas width behavior for text widgets.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CanvasWidth {
@@ -24,6 +57,197 @@ pub enum CanvasWidth {
     Fixed(u32),
 }
 
+/// Line breaking algorithm for wrapped text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WrapAlgorithm {
+    /// Put as many words on each line as fit. Fast, but can leave ragged lines.
+    FirstFit,
+    /// Choose breaks that fill lines evenly across the whole paragraph, which reads better
+    /// for prose.
+    #[default]
+    OptimalFit,
+}
+
+/// Decides where wrapped lines may break besides whitespace.
+///
+/// A line may break after whitespace and after any grapheme the predicate accepts, so
+/// code-oriented views can break paths and identifiers at punctuation. Breakers compare
+/// equal only to clones of themselves.
+#[derive(Clone)]
+pub struct WordBreaker(Arc<dyn Fn(&str) -> bool + Send + Sync>);
+
+impl WordBreaker {
+    /// Allow breaks after graphemes for which `f` returns `true`.
+    pub fn new(f: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// Allow breaks after any of the characters in `chars`, such as `"/._-"`.
+    pub fn after(chars: &str) -> Self {
+        let chars: Vec<char> = chars.chars().collect();
+        Self::new(move |grapheme| grapheme.chars().any(|c| chars.contains(&c)))
+    }
+
+    /// Whether a line may break after `grapheme`.
+    pub fn breaks_after(&self, grapheme: &str) -> bool {
+        grapheme.chars().all(char::is_whitespace) || (self.0)(grapheme)
+    }
+
+    /// Split `line` into fragments that each end at a break point. Runs of whitespace
+    /// stay with the fragment before them.
+    fn fragments<'a>(&self, line: &'a str) -> Vec<&'a str> {
+        let mut fragments = Vec::new();
+        let mut start = 0;
+        let mut graphemes = line.grapheme_indices(true).peekable();
+        while let Some((idx, grapheme)) = graphemes.next() {
+            let end = idx + grapheme.len();
+            let before_space = graphemes
+                .peek()
+                .is_some_and(|(_, next)| next.chars().all(char::is_whitespace));
+            if !before_space && self.breaks_after(grapheme) {
+                fragments.push(&line[start..end]);
+                start = end;
+            }
+        }
+        if start < line.len() {
+            fragments.push(&line[start..]);
+        }
+        fragments
+    }
+}
+
+impl fmt::Debug for WordBreaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WordBreaker(..)")
+    }
+}
+
+impl PartialEq for WordBreaker {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for WordBreaker {}
+
+/// Options controlling how text is wrapped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrapOptions {
+    /// Line breaking algorithm.
+    pub algorithm: WrapAlgorithm,
+    /// Whether words wider than the line are split. Unsplit words overflow the line.
+    pub break_words: bool,
+    /// Whether hyphenated words may break after a hyphen. Only hyphens already in the
+    /// text are used; no dictionary hyphenation is applied.
+    pub hyphenate: bool,
+    /// Extra break points. Without one, lines break only between whitespace-separated
+    /// words.
+    pub breaker: Option<WordBreaker>,
+}
+
+impl Default for WrapOptions {
+    fn default() -> Self {
+        Self {
+            algorithm: WrapAlgorithm::default(),
+            break_words: true,
+            hyphenate: true,
+            breaker: None,
+        }
+    }
+}
+
+impl WrapOptions {
+    /// Set the line breaking algorithm.
+    pub fn with_algorithm(mut self, algorithm: WrapAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Configure whether words wider than the line are split.
+    pub fn with_break_words(mut self, break_words: bool) -> Self {
+        self.break_words = break_words;
+        self
+    }
+
+    /// Configure whether hyphenated words may break after a hyphen.
+    pub fn with_hyphenate(mut self, hyphenate: bool) -> Self {
+        self.hyphenate = hyphenate;
+        self
+    }
+
+    /// Allow lines to break wherever `breaker` permits.
+    pub fn with_breaker(mut self, breaker: WordBreaker) -> Self {
+        self.breaker = Some(breaker);
+        self
+    }
+
+    /// Wrap `text` to lines at most `width` columns wide.
+    pub fn wrap(&self, text: &str, width: usize) -> Vec<String> {
+        let algorithm = match self.algorithm {
+            WrapAlgorithm::FirstFit => TextwrapAlgorithm::FirstFit,
+            WrapAlgorithm::OptimalFit => TextwrapAlgorithm::new_optimal_fit(),
+        };
+        let splitter = if self.hyphenate {
+            WordSplitter::HyphenSplitter
+        } else {
+            WordSplitter::NoHyphenation
+        };
+        if let Some(breaker) = &self.breaker {
+            return text
+                .split('\n')
+                .flat_map(|line| self.wrap_fragments(breaker, &algorithm, &splitter, line, width))
+                .collect();
+        }
+        let options = TextwrapOptions::new(width)
+            .wrap_algorithm(algorithm)
+            .break_words(self.break_words)
+            .word_splitter(splitter);
+        textwrap::wrap(text, options)
+            .into_iter()
+            .map(Cow::into_owned)
+            .collect()
+    }
+
+    /// Wrap a single line, treating each fragment allowed by `breaker` as a word.
+    fn wrap_fragments(
+        &self,
+        breaker: &WordBreaker,
+        algorithm: &TextwrapAlgorithm,
+        splitter: &WordSplitter,
+        line: &str,
+        width: usize,
+    ) -> Vec<String> {
+        let words = breaker.fragments(line).into_iter().map(Word::from);
+        let words: Vec<Word<'_>> = split_words(words, splitter).collect();
+        let words = if self.break_words {
+            break_words(words, width)
+        } else {
+            words
+        };
+        let lines: Vec<String> = algorithm
+            .wrap(&words, &[width])
+            .into_iter()
+            .map(|line| {
+                let mut out = String::new();
+                for (idx, word) in line.iter().enumerate() {
+                    out.push_str(word.word);
+                    if idx + 1 == line.len() {
+                        out.push_str(word.penalty);
+                    } else {
+                        out.push_str(word.whitespace);
+                    }
+                }
+                out
+            })
+            .collect();
+        if lines.is_empty() {
+            vec![String::new()]
+        } else {
+            lines
+        }
+    }
+}
+
 /// Multiline text widget with wrapping and scrolling.
 pub struct Text {
     /// Raw text content.
@@ -40,8 +264,29 @@ pub struct Text {
     selected: bool,
     /// Tab stop width in columns.
     tab_stop: usize,
-    /// Cached wrapped text for the last wrap width.
-    wrap_cache: RefCell<Option<WrapCache>>,
+    /// Horizontal alignment of each line.
+    align: Align,
+    /// Vertical alignment of the text block when it is shorter than the view.
+    valign: Align,
+    /// Padding around the text, in cells.
+    padding: Edges<u32>,
+    /// Whether lines are truncated with an ellipsis instead of wrapped.
+    ellipsis: bool,
+    /// Whether long lines wrap. Unwrapped text scrolls horizontally.
+    wrap: bool,
+    /// How wrapped lines are broken.
+    wrap_options: WrapOptions,
+    /// Whether tabs and spaces are drawn with visible glyphs.
+    show_whitespace: bool,
+    /// Active search pattern.
+    search: Option<Regex>,
+    /// Index of the current match in the text's list of matches.
+    current: Option<usize>,
+    /// Wrap width used by the last render, for scrolling to matches. Matches are placed
+    /// on unwrapped lines before the first render.
+    last_width: Option<usize>,
+    /// Cached tab expansion and wrapped layouts for recent widths.
+    cache: RefCell<TextCache>,
 }
 
 impl Selectable for Text {
@@ -62,14 +307,24 @@ impl Text {
             selected_style: None,
             selected: false,
             tab_stop: 4,
-            wrap_cache: RefCell::new(None),
+            align: Align::Start,
+            valign: Align::Start,
+            padding: Edges::all(0),
+            ellipsis: false,
+            wrap: true,
+            wrap_options: WrapOptions::default(),
+            show_whitespace: false,
+            search: None,
+            current: None,
+            last_width: None,
+            cache: RefCell::new(TextCache::default()),
         }
     }
 
     /// Add a fixed width for wrapping.
     pub fn with_wrap_width(mut self, width: u32) -> Self {
         self.wrap_width = Some(width);
-        self.wrap_cache.borrow_mut().take();
+        self.invalidate();
         self
     }
 
@@ -94,7 +349,56 @@ impl Text {
     /// Set the tab stop width for tab expansion.
     pub fn with_tab_stop(mut self, tab_stop: usize) -> Self {
         self.tab_stop = tab_stop.max(1);
-        self.wrap_cache.borrow_mut().take();
+        self.invalidate();
+        self
+    }
+
+    /// Set the horizontal alignment of each line within the text width.
+    pub fn with_align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Set the vertical alignment of the text when it is shorter than the view.
+    pub fn with_valign(mut self, valign: Align) -> Self {
+        self.valign = valign;
+        self
+    }
+
+    /// Set padding around the text, in cells.
+    pub fn with_padding(mut self, padding: Edges<u32>) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Truncate lines that do not fit with an ellipsis instead of wrapping them.
+    pub fn with_ellipsis(mut self) -> Self {
+        self.ellipsis = true;
+        self
+    }
+
+    /// Configure soft wrapping. Unwrapped text scrolls horizontally.
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Configure how wrapped lines are broken.
+    pub fn with_wrap_options(mut self, options: WrapOptions) -> Self {
+        self.set_wrap_options(options);
+        self
+    }
+
+    /// Change how wrapped lines are broken.
+    pub fn set_wrap_options(&mut self, options: WrapOptions) {
+        self.wrap_options = options;
+        self.invalidate();
+    }
+
+    /// Configure whether tabs and spaces are drawn with visible glyphs.
+    pub fn with_show_whitespace(mut self, show: bool) -> Self {
+        self.show_whitespace = show;
+        self.invalidate();
         self
     }
 
@@ -106,7 +410,7 @@ impl Text {
     /// Replace the raw text content.
     pub fn set_raw(&mut self, raw: impl Into<String>) {
         self.raw = raw.into();
-        self.wrap_cache.borrow_mut().take();
+        self.invalidate();
     }
 
     #[command]
@@ -139,71 +443,385 @@ impl Text {
         }
     }
 
-    /// Determine the wrapping width for the given available space.
+    /// Toggle soft wrapping. With wrapping off, long lines scroll horizontally.
+    #[command]
+    pub fn toggle_wrap(&mut self, c: &mut dyn Context) {
+        self.wrap = !self.wrap;
+        if self.wrap {
+            c.scroll_to(0, c.view().tl.y);
+        }
+    }
+
+    /// Set the tab stop width.
+    /// @param tab_stop Tab stop width in columns.
+    #[command]
+    pub fn set_tab_stop(&mut self, _c: &mut dyn Context, tab_stop: usize) {
+        self.tab_stop = tab_stop.max(1);
+        self.invalidate();
+    }
+
+    /// Toggle drawing tabs and spaces with visible glyphs.
+    #[command]
+    pub fn toggle_whitespace(&mut self, _c: &mut dyn Context) {
+        self.show_whitespace = !self.show_whitespace;
+        self.invalidate();
+    }
+
+    /// Highlight matches of a regular expression and move to the first match at or
+    /// below the top of the view.
+    /// @param pattern Regular expression to search for.
+    #[command]
+    pub fn search(&mut self, c: &mut dyn Context, pattern: String) -> Result<()> {
+        let regex = Regex::new(&pattern)
+            .map_err(|err| Error::Invalid(format!("invalid search pattern: {err}")))?;
+        self.search = Some(regex);
+        self.cache.get_mut().matches = None;
+        let top = c.view().view_rect().tl.y as usize;
+        let width = self.last_width.unwrap_or(UNWRAPPED);
+        let matches = self.matches();
+        let first = matches
+            .iter()
+            .position(|m| self.line_of(width, m.start) + self.padding.top as usize >= top)
+            .or((!matches.is_empty()).then_some(0));
+        self.jump_to(c, first);
+        Ok(())
+    }
+
+    /// Move to the next match, wrapping around at the end.
+    #[command]
+    pub fn search_next(&mut self, c: &mut dyn Context) {
+        let count = self.match_count();
+        let next = match self.current {
+            Some(index) if index + 1 < count => Some(index + 1),
+            _ => (count > 0).then_some(0),
+        };
+        self.jump_to(c, next);
+    }
+
+    /// Move to the previous match, wrapping around at the start.
+    #[command]
+    pub fn search_prev(&mut self, c: &mut dyn Context) {
+        let count = self.match_count();
+        let prev = match self.current {
+            Some(index) if index > 0 && index < count => Some(index - 1),
+            _ => count.checked_sub(1),
+        };
+        self.jump_to(c, prev);
+    }
+
+    /// Remove the search pattern and its highlights.
+    #[command]
+    pub fn clear_search(&mut self, _c: &mut dyn Context) {
+        self.search = None;
+        self.current = None;
+        self.cache.get_mut().matches = None;
+    }
+
+    /// Return the number of matches of the search pattern.
+    pub fn match_count(&self) -> usize {
+        self.matches().len()
+    }
+
+    /// Return the 1-based index of the current match, if any.
+    pub fn current_match(&self) -> Option<usize> {
+        self.current
+            .filter(|index| *index < self.match_count())
+            .map(|index| index + 1)
+    }
+
+    /// Return the byte ranges of the search pattern's matches in the tab-expanded text,
+    /// which are independent of the wrap width.
+    fn matches(&self) -> Vec<Range<usize>> {
+        if let Some(matches) = &self.cache.borrow().matches {
+            return matches.clone();
+        }
+        let matches = match &self.search {
+            Some(regex) => self.with_expanded(|expanded| {
+                regex
+                    .find_iter(&expanded.text)
+                    .filter(|m| !m.is_empty())
+                    .map(|m| m.range())
+                    .collect()
+            }),
+            None => Vec::new(),
+        };
+        self.cache.borrow_mut().matches = Some(matches.clone());
+        matches
+    }
+
+    /// Return the line, wrapped at `width`, holding byte `offset` of the expanded text.
+    fn line_of(&self, width: usize, offset: usize) -> usize {
+        self.with_wrap_cache(width, |cache| {
+            cache
+                .starts
+                .partition_point(|start| *start <= offset)
+                .saturating_sub(1)
+        })
+    }
+
+    /// Make match `index` the current match and scroll its first line into view.
+    fn jump_to(&mut self, c: &mut dyn Context, index: Option<usize>) {
+        self.current = index;
+        let Some(m) = index.and_then(|index| self.matches().get(index).cloned()) else {
+            return;
+        };
+        let line = self.line_of(self.last_width.unwrap_or(UNWRAPPED), m.start);
+        let view = c.view().view_rect();
+        let y = (line as u32).saturating_add(self.padding.top);
+        if y < view.tl.y || y >= view.tl.y + view.h {
+            c.scroll_to(view.tl.x, y.saturating_sub(view.h / 2));
+        }
+    }
+
+    /// Replace spaces with a visible glyph when whitespace is shown.
+    fn visible<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        if self.show_whitespace {
+            Cow::Owned(s.replace(' ', SPACE_MARK))
+        } else {
+            Cow::Borrowed(s)
+        }
+    }
+
+    /// Discard cached layout. Call after changing anything that affects wrapping.
+    pub fn invalidate(&mut self) {
+        *self.cache.get_mut() = TextCache::default();
+    }
+
+    /// Determine the wrapping width for the given available space, including padding.
+    /// Returns `UNWRAPPED` in ellipsis mode and when wrapping is off.
     fn wrap_width_for(&self, available_width: u32) -> usize {
-        let width = self.wrap_width.unwrap_or(available_width).max(1);
+        if self.ellipsis || !self.wrap {
+            return UNWRAPPED;
+        }
+        let width = self
+            .wrap_width
+            .unwrap_or(available_width.saturating_sub(self.padding.horizontal()))
+            .max(1);
         width as usize
     }
 
+    /// Access the tab-expanded content and its widest line, computing them once.
+    fn with_expanded<R>(&self, f: impl FnOnce(&Expanded) -> R) -> R {
+        let mut cache = self.cache.borrow_mut();
+        let expanded = cache.expanded.get_or_insert_with(|| {
+            let text = if self.show_whitespace {
+                text::expand_tabs_marked(&self.raw, self.tab_stop, TAB_MARK)
+            } else {
+                text::expand_tabs(&self.raw, self.tab_stop)
+            };
+            let width = text.lines().map(UnicodeWidthStr::width).max().unwrap_or(0) as u32;
+            Expanded { text, width }
+        });
+        f(expanded)
+    }
+
     /// Access cached wrapped lines for the provided width.
     fn with_wrap_cache<R>(&self, width: usize, f: impl FnOnce(&WrapCache) -> R) -> R {
-        let mut cache = self.wrap_cache.borrow_mut();
-        let rebuild = cache.as_ref().is_none_or(|cached| cached.width != width);
-        if rebuild {
-            let expanded = text::expand_tabs(&self.raw, self.tab_stop);
-            let lines = textwrap::wrap(&expanded, width)
-                .into_iter()
-                .map(|line| line.to_string())
-                .collect::<Vec<_>>();
-            let max_width = lines
-                .iter()
-                .map(|line| UnicodeWidthStr::width(line.as_str()))
-                .max()
-                .unwrap_or(0) as u32;
-            *cache = Some(WrapCache {
-                width,
-                lines,
-                max_width,
-            });
-        }
-        f(cache.as_ref().expect("wrap cache initialized"))
+        let cached = self
+            .cache
+            .borrow()
+            .wraps
+            .iter()
+            .position(|cached| cached.width == width);
+        let idx = match cached {
+            Some(idx) => idx,
+            None => {
+                let (lines, starts) = self.with_expanded(|expanded| {
+                    let lines: Vec<String> = if width == UNWRAPPED {
+                        expanded.text.lines().map(str::to_string).collect()
+                    } else {
+                        self.wrap_options.wrap(&expanded.text, width)
+                    };
+                    let starts = line_starts(&expanded.text, &lines);
+                    (lines, starts)
+                });
+                let max_width = lines
+                    .iter()
+                    .map(|line| UnicodeWidthStr::width(line.as_str()))
+                    .max()
+                    .unwrap_or(0) as u32;
+                let mut cache = self.cache.borrow_mut();
+                if cache.wraps.len() >= WRAP_CACHE_WIDTHS {
+                    cache.wraps.remove(0);
+                }
+                cache.wraps.push(WrapCache {
+                    width,
+                    lines,
+                    starts,
+                    max_width,
+                });
+                cache.wraps.len() - 1
+            }
+        };
+        f(&self.cache.borrow().wraps[idx])
     }
 }
 
+/// Layout computed from the text content, independent of width.
+#[derive(Default)]
+struct TextCache {
+    /// Tab-expanded content, computed on first use.
+    expanded: Option<Expanded>,
+    /// Wrapped layouts for recently used widths, oldest first.
+    wraps: Vec<WrapCache>,
+    /// Byte ranges of search matches in the expanded text, computed on first use.
+    matches: Option<Vec<Range<usize>>>,
+}
+
+/// Tab-expanded text content.
+struct Expanded {
+    /// Content with tabs expanded to spaces.
+    text: String,
+    /// Display width of the widest unwrapped line.
+    width: u32,
+}
+
 /// Cached wrapped lines for a specific width.
 struct WrapCache {
     /// Width used for wrapping.
     width: usize,
     /// Wrapped lines at the width.
     lines: Vec<String>,
+    /// Byte offset of each wrapped line in the expanded text.
+    starts: Vec<usize>,
     /// Maximum wrapped line width.
     max_width: u32,
 }
 
+/// Return the byte offset in `text` of each of `lines`, which were wrapped from it in
+/// order. Wrapping only drops whitespace at line breaks, so each line is found at or
+/// after the end of the one before.
+fn line_starts(text: &str, lines: &[String]) -> Vec<usize> {
+    let mut pos = 0;
+    lines
+        .iter()
+        .map(|line| {
+            let start = text
+                .get(pos..)
+                .and_then(|rest| rest.find(line.as_str()))
+                .map_or(pos, |at| pos + at);
+            pos = start + line.len();
+            start
+        })
+        .collect()
+}
+
+impl Scrollable for Text {}
+
+/// Text persists its scroll offset. Its content belongs to the app that built it.
+impl PersistentState for Text {
+    fn save_state(&self, ctx: &dyn ReadContext) -> Result<Value> {
+        let offset = ctx.view().tl;
+        to_state(&[offset.x, offset.y])
+    }
+
+    fn restore_state(&mut self, state: Value, ctx: &mut dyn Context) -> Result<()> {
+        let [x, y]: [u32; 2] = from_state(state)?;
+        ctx.scroll_to(x, y);
+        Ok(())
+    }
+}
+
 impl Widget for Text {
+    fn scrollable(&mut self) -> Option<&mut dyn Scrollable> {
+        Some(self)
+    }
+
+    fn persistent(&mut self) -> Option<&mut dyn PersistentState> {
+        Some(self)
+    }
+
     fn render(&mut self, rndr: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
         let view = ctx.view();
         let view_rect = view.view_rect();
         let content_origin = view.content_origin();
         let width = self.wrap_width_for(view_rect.w);
+        self.last_width = Some(width);
         let style = if self.selected {
             self.selected_style.as_deref().unwrap_or(&self.style)
         } else {
             &self.style
         };
+        let inner_width = view
+            .canvas
+            .w
+            .max(view_rect.w)
+            .saturating_sub(self.padding.horizontal());
+        let matches = self.matches();
+        let current = self.current.and_then(|index| matches.get(index));
 
         self.with_wrap_cache(width, |cache| -> Result<()> {
+            let block_height = cache.lines.len() as u32 + self.padding.vertical();
+            let top = self.padding.top + align_offset(self.valign, view_rect.h, block_height);
             for i in 0..view_rect.h {
-                let line_idx = (view_rect.tl.y + i) as usize;
-                if let Some(line) = cache.lines.get(line_idx) {
-                    let start_col = view_rect.tl.x as usize;
-                    let (out, _) = text::slice_by_columns(line, start_col, view_rect.w as usize);
-                    let line_rect = Line::new(
-                        content_origin.x,
-                        content_origin.y.saturating_add(i),
-                        view_rect.w,
-                    );
-                    rndr.text(style, line_rect, out)?;
+                let Some(line_idx) = (view_rect.tl.y + i).checked_sub(top) else {
+                    continue;
+                };
+                let Some(full) = cache.lines.get(line_idx as usize) else {
+                    continue;
+                };
+                let full_width = UnicodeWidthStr::width(full.as_str());
+                let truncated;
+                let (line, text_cols) = if self.ellipsis && full_width > inner_width as usize {
+                    let keep = (inner_width as usize).saturating_sub(1);
+                    let (head, _) = text::slice_by_columns(full, 0, keep);
+                    truncated = format!("{head}{ELLIPSIS}");
+                    (truncated.as_str(), keep)
+                } else {
+                    (full.as_str(), full_width)
+                };
+                let line_width = UnicodeWidthStr::width(line) as u32;
+                let left = self.padding.left + align_offset(self.align, inner_width, line_width);
+                let start_col = view_rect.tl.x.saturating_sub(left);
+                let x = left.saturating_sub(view_rect.tl.x);
+                if x >= view_rect.w {
+                    continue;
+                }
+                let (out, _) =
+                    text::slice_by_columns(line, start_col as usize, (view_rect.w - x) as usize);
+                let out = self.visible(out);
+                let line_rect = Line::new(
+                    content_origin.x.saturating_add(x),
+                    content_origin.y.saturating_add(i),
+                    view_rect.w - x,
+                );
+                rndr.text(style, line_rect, &out)?;
+
+                // Matches are located in the unwrapped text, so one that crosses a wrap
+                // boundary is highlighted on each line it spans.
+                let line_start = cache.starts[line_idx as usize];
+                let line_end = line_start + full.len();
+                let first = matches.partition_point(|m| m.end <= line_start);
+                let visible =
+                    start_col as usize..((start_col + view_rect.w - x) as usize).min(text_cols);
+                for m in matches[first..].iter().take_while(|m| m.start < line_end) {
+                    let from = m.start.max(line_start) - line_start;
+                    let to = m.end.min(line_end) - line_start;
+                    let (Some(before), Some(through)) = (full.get(..from), full.get(..to)) else {
+                        continue;
+                    };
+                    let start = UnicodeWidthStr::width(before).max(visible.start);
+                    let end = UnicodeWidthStr::width(through).min(visible.end);
+                    if start >= end {
+                        continue;
+                    }
+                    let match_style = if current == Some(m) {
+                        "text/match/current"
+                    } else {
+                        "text/match"
+                    };
+                    let (out, _) = text::slice_by_columns(full, start, end - start);
+                    let out = self.visible(out);
+                    let offset = (start - visible.start) as u32;
+                    rndr.text(
+                        match_style,
+                        Line::new(
+                            line_rect.tl.x + offset,
+                            line_rect.tl.y,
+                            (end - start) as u32,
+                        ),
+                        &out,
+                    )?;
                 }
             }
             Ok(())
@@ -212,20 +830,18 @@ impl Widget for Text {
     }
 
     fn measure(&self, c: MeasureConstraints) -> Measurement {
-        let expanded = text::expand_tabs(&self.raw, self.tab_stop);
-        let raw_width = expanded
-            .lines()
-            .map(UnicodeWidthStr::width)
-            .max()
-            .unwrap_or(0) as u32;
+        let raw_width = self.with_expanded(|expanded| expanded.width);
+
+        let pad_w = self.padding.horizontal();
 
         let max_width = match c.width {
-            Constraint::Exact(n) | Constraint::AtMost(n) => n,
+            Constraint::Exact(n) | Constraint::AtMost(n) => n.saturating_sub(pad_w),
             Constraint::Unbounded => self.wrap_width.unwrap_or(raw_width),
         };
 
         let wrap_width = match c.width {
             Constraint::Unbounded => self.wrap_width.unwrap_or(raw_width),
+            _ if self.ellipsis || !self.wrap => raw_width.min(max_width),
             _ => self
                 .wrap_width
                 .map(|w| w.min(max_width))
@@ -233,8 +849,16 @@ impl Widget for Text {
         }
         .max(1);
 
-        let height = self.with_wrap_cache(wrap_width as usize, |cache| cache.lines.len() as u32);
-        c.clamp(Size::new(wrap_width, height))
+        let cache_width = if self.ellipsis || !self.wrap {
+            UNWRAPPED
+        } else {
+            wrap_width as usize
+        };
+        let height = self.with_wrap_cache(cache_width, |cache| cache.lines.len() as u32);
+        c.clamp(Size::new(
+            wrap_width + pad_w,
+            height + self.padding.vertical(),
+        ))
     }
 
     fn canvas(&self, view: Size<u32>, _ctx: &canopy::layout::CanvasContext) -> Size<u32> {
@@ -243,15 +867,187 @@ impl Widget for Text {
             .with_wrap_cache(wrap_width, |cache| cache.max_width)
             .max(1);
         let canvas_width = match self.canvas_width {
+            CanvasWidth::View if !self.wrap && !self.ellipsis => {
+                (wrapped_width + self.padding.horizontal()).max(view.w.max(1))
+            }
             CanvasWidth::View => view.w.max(1),
-            CanvasWidth::Intrinsic => wrapped_width,
+            CanvasWidth::Intrinsic if self.ellipsis => view.w.max(1),
+            CanvasWidth::Intrinsic => wrapped_width + self.padding.horizontal(),
             CanvasWidth::Fixed(width) => width.max(1),
         };
         let height = self.with_wrap_cache(wrap_width, |cache| cache.lines.len() as u32);
-        Size::new(canvas_width, height)
+        Size::new(canvas_width, height + self.padding.vertical())
     }
 
     fn name(&self) -> NodeName {
         NodeName::convert("text")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use canopy::testing::{dummyctx::DummyContext, harness::Harness};
+
+    use super::*;
+
+    fn render_lines(text: Text, w: u32, h: u32) -> Result<Vec<String>> {
+        let mut harness = Harness::builder(text).size(w, h).build()?;
+        harness.render()?;
+        Ok(harness.buf().lines())
+    }
+
+    #[test]
+    fn wrap_cache_keeps_recent_widths_until_invalidated() {
+        let mut text = Text::new("one two six");
+        let narrow = text.with_wrap_cache(3, |cache| cache.lines.len());
+        let wide = text.with_wrap_cache(20, |cache| cache.lines.len());
+        assert_eq!((narrow, wide), (3, 1));
+        let _ = text.with_wrap_cache(3, |cache| cache.lines.len());
+        assert_eq!(text.cache.borrow().wraps.len(), 2);
+
+        text.set_raw("one");
+        assert!(text.cache.borrow().wraps.is_empty());
+        assert_eq!(text.with_wrap_cache(3, |cache| cache.lines.len()), 1);
+    }
+
+    #[test]
+    fn wrap_options_choose_breaks() {
+        let first_fit = WrapOptions::default().with_algorithm(WrapAlgorithm::FirstFit);
+        assert_eq!(
+            first_fit.wrap("aaa bb cc ddddd", 6),
+            ["aaa bb", "cc", "ddddd"]
+        );
+        assert_eq!(first_fit.wrap("well-known", 6), ["well-", "known"]);
+        assert_eq!(
+            first_fit
+                .clone()
+                .with_hyphenate(false)
+                .wrap("well-known", 6),
+            ["well-k", "nown"]
+        );
+        assert_eq!(
+            first_fit
+                .clone()
+                .with_hyphenate(false)
+                .with_break_words(false)
+                .wrap("well-known", 6),
+            ["well-known"]
+        );
+
+        let mut text = Text::new("well-known").with_wrap_options(first_fit.clone());
+        assert_eq!(text.with_wrap_cache(6, |cache| cache.lines.len()), 2);
+        text.set_wrap_options(first_fit.with_hyphenate(false).with_break_words(false));
+        assert_eq!(text.with_wrap_cache(6, |cache| cache.max_width), 10);
+    }
+
+    #[test]
+    fn word_breaker_splits_at_punctuation() {
+        let first_fit = WrapOptions::default().with_algorithm(WrapAlgorithm::FirstFit);
+        assert_eq!(
+            first_fit.wrap("src/core/world.rs", 10),
+            ["src/core/w", "orld.rs"]
+        );
+        let paths = first_fit.with_breaker(WordBreaker::after("/"));
+        assert_eq!(
+            paths.wrap("src/core/world.rs", 10),
+            ["src/core/", "world.rs"]
+        );
+        assert_eq!(
+            paths.wrap("see src/core/world.rs now\n", 12),
+            ["see src/", "core/", "world.rs now", ""]
+        );
+        assert_eq!(paths, paths.clone());
+        assert_ne!(paths.breaker, Some(WordBreaker::after("/")));
+    }
+
+    #[test]
+    fn alignment_padding_and_ellipsis() -> Result<()> {
+        let centered = Text::new("ab\nabcd")
+            .with_align(Align::Center)
+            .with_valign(Align::End);
+        assert_eq!(
+            render_lines(centered, 8, 4)?,
+            ["        ", "        ", "   ab   ", "  abcd  "]
+        );
+
+        let padded = Text::new("ab")
+            .with_align(Align::End)
+            .with_padding(Edges::new(1, 1, 0, 0));
+        assert_eq!(render_lines(padded, 5, 2)?, ["     ", "  ab "]);
+
+        let clipped = Text::new("abcdefgh").with_ellipsis();
+        assert_eq!(render_lines(clipped, 5, 2)?, ["abcd\u{2026}", "     "]);
+        Ok(())
+    }
+
+    #[test]
+    fn search_moves_between_matches() -> Result<()> {
+        let mut ctx = DummyContext::default();
+        let mut text = Text::new("one two\nthree one\none");
+        assert!(matches!(
+            text.search(&mut ctx, "(".into()),
+            Err(Error::Invalid(_))
+        ));
+
+        text.search(&mut ctx, "one".into())?;
+        assert_eq!(text.match_count(), 3);
+        assert_eq!(text.current_match(), Some(1));
+        text.search_next(&mut ctx);
+        assert_eq!(text.current, Some(1));
+        assert_eq!(text.line_of(UNWRAPPED, 14), 1);
+        text.search_prev(&mut ctx);
+        text.search_prev(&mut ctx);
+        assert_eq!(text.current_match(), Some(3));
+
+        text.clear_search(&mut ctx);
+        assert_eq!((text.match_count(), text.current_match()), (0, None));
+        Ok(())
+    }
+
+    #[test]
+    fn search_spans_wrapped_lines_at_any_width() -> Result<()> {
+        let mut harness = Harness::builder(Text::new("alpha beta alpha"))
+            .size(6, 3)
+            .build()?;
+        harness.render()?;
+        harness.with_root_context(|text: &mut Text, ctx| {
+            text.search(ctx, "a b".into())?;
+            assert_eq!((text.match_count(), text.current_match()), (1, Some(1)));
+            text.search(ctx, "alpha".into())?;
+            text.search_next(ctx);
+            assert_eq!(text.current_match(), Some(2));
+            assert_eq!(text.line_of(6, 11), 2);
+            Ok(())
+        })?;
+        harness.resize(Size::new(20, 3))?;
+        harness.render()?;
+        harness.with_root_context(|text: &mut Text, ctx| {
+            assert_eq!(text.current_match(), Some(2));
+            assert_eq!(text.line_of(20, 11), 0);
+            text.search_prev(ctx);
+            assert_eq!(text.current_match(), Some(1));
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn wrap_toggle_and_visible_whitespace() -> Result<()> {
+        let mut harness = Harness::builder(Text::new("abc def")).size(4, 2).build()?;
+        harness.render()?;
+        assert_eq!(harness.buf().lines(), ["abc ", "def "]);
+        harness.with_root_context(|text: &mut Text, ctx| {
+            text.toggle_wrap(ctx);
+            Ok(())
+        })?;
+        harness.render()?;
+        assert_eq!(harness.buf().lines(), ["abc ", "    "]);
+
+        let marked = Text::new("a\tb c").with_show_whitespace(true);
+        assert_eq!(
+            render_lines(marked, 8, 1)?,
+            ["a\u{2192}\u{b7}\u{b7}b\u{b7}c "]
+        );
+        Ok(())
+    }
+}
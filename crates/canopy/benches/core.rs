@@ -3,13 +3,12 @@
 use std::hint::black_box;
 
 use canopy::{
-    Canopy, NodeId, ReadContext, TermBuf, Widget,
+    Canopy, TermBuf,
     error::Result,
     geom::{FrameRects, Line, Point, Rect, Size},
-    layout::{Layout, MeasureConstraints, Measurement},
-    render::{Render, RenderBackend},
-    state::NodeName,
-    style::{AttrSet, Color, ResolvedStyle},
+    render::RenderBackend,
+    style::{AttrSet, Color, ResolvedStyle, StyleManager, solarized},
+    testing::synth,
 };
 use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
 
@@ -20,61 +19,6 @@ const TREE_DEPTH: usize = 4;
 /// Fanout used for the synthetic benchmark widget tree.
 const TREE_FANOUT: usize = 4;
 
-/// Widget used to build benchmark trees without depending on example apps.
-struct BenchNode {
-    /// Stable node name.
-    name: NodeName,
-    /// Layout returned by the widget.
-    layout: Layout,
-    /// Optional text rendered by leaf nodes.
-    label: Option<&'static str>,
-}
-
-impl BenchNode {
-    /// Build a container node.
-    fn branch(index: usize) -> Self {
-        Self {
-            name: NodeName::convert(&format!("branch_{index}")),
-            layout: Layout::column().gap(1),
-            label: None,
-        }
-    }
-
-    /// Build a leaf node.
-    fn leaf(index: usize) -> Self {
-        Self {
-            name: NodeName::convert(&format!("leaf_{index}")),
-            layout: Layout::default().fixed_width(18).fixed_height(1),
-            label: Some("leaf \u{754c} \u{1f642}"),
-        }
-    }
-}
-
-impl Widget for BenchNode {
-    fn layout(&self) -> Layout {
-        self.layout
-    }
-
-    fn measure(&self, constraints: MeasureConstraints) -> Measurement {
-        if self.label.is_some() {
-            constraints.clamp(Size::new(18, 1))
-        } else {
-            constraints.wrap()
-        }
-    }
-
-    fn render(&mut self, frame: &mut Render<'_>, _ctx: &dyn ReadContext) -> Result<()> {
-        if let Some(label) = self.label {
-            frame.text("default", Line::new(0, 0, 18), label)?;
-        }
-        Ok(())
-    }
-
-    fn name(&self) -> NodeName {
-        self.name.clone()
-    }
-}
-
 /// Render backend that counts output operations without touching a terminal.
 #[derive(Default)]
 struct CountingBackend {
@@ -123,43 +67,9 @@ impl RenderBackend for CountingBackend {
     }
 }
 
-/// Build a deterministic tree for layout and render benchmarks.
+/// Build the synthetic tree used for layout and render benchmarks.
 fn build_tree() -> Result<Canopy> {
-    let mut app = Canopy::new();
-    let root_child = app.core_mut().create_detached(BenchNode::branch(0));
-    app.set_root_child(root_child)?;
-    let mut next_index = 1;
-    add_children(&mut app, root_child, TREE_DEPTH, &mut next_index)?;
-    app.set_root_size(SCREEN)?;
-    Ok(app)
-}
-
-/// Add a fixed fanout subtree below `parent`.
-fn add_children(
-    app: &mut Canopy,
-    parent: NodeId,
-    depth: usize,
-    next_index: &mut usize,
-) -> Result<()> {
-    let mut children = Vec::with_capacity(TREE_FANOUT);
-
-    for _ in 0..TREE_FANOUT {
-        let index = *next_index;
-        *next_index += 1;
-        let child = if depth == 1 {
-            app.core_mut().create_detached(BenchNode::leaf(index))
-        } else {
-            app.core_mut().create_detached(BenchNode::branch(index))
-        };
-
-        if depth > 1 {
-            add_children(app, child, depth - 1, next_index)?;
-        }
-
-        children.push(child);
-    }
-
-    app.core_mut().set_children(parent, children)
+    synth::tree(TREE_DEPTH, TREE_FANOUT, SCREEN)
 }
 
 /// Return the solid style used in terminal buffer benchmarks.
@@ -198,7 +108,7 @@ fn bench_render_diffing(c: &mut Criterion) {
         let mut backend = CountingBackend::default();
         b.iter(|| {
             current
-                .diff(black_box(&previous), black_box(&mut backend))
+                .render_diff(black_box(&previous), black_box(&mut backend))
                 .expect("diff render should succeed");
             black_box((backend.text_bytes, backend.char_shifts, backend.line_shifts));
         });
@@ -254,12 +164,50 @@ fn bench_frame_fill(c: &mut Criterion) {
     });
 }
 
+/// Benchmark style resolution under a layer stack, with a warm cache and with a
+/// fresh manager per pass.
+fn bench_style_resolution(c: &mut Criterion) {
+    let stylemap = solarized::solarized_dark();
+    let paths = [
+        "text",
+        "frame/border",
+        "frame/focused/border",
+        "list/selected",
+        "statusbar/text",
+        "help/key",
+    ];
+    let resolve = |styl: &mut StyleManager| {
+        styl.reset();
+        styl.push();
+        styl.push_layer("frame");
+        styl.push();
+        styl.push_layer("list");
+        for path in paths {
+            black_box(styl.get(&stylemap, black_box(path)));
+        }
+        styl.pop();
+        styl.pop();
+    };
+    c.bench_function("style_resolution_warm", |b| {
+        let mut styl = StyleManager::default();
+        b.iter(|| resolve(&mut styl));
+    });
+    c.bench_function("style_resolution_cold", |b| {
+        b.iter_batched(
+            StyleManager::default,
+            |mut styl| resolve(&mut styl),
+            BatchSize::SmallInput,
+        );
+    });
+}
+
 criterion_group!(
     benches,
     bench_layout,
     bench_render_diffing,
     bench_text_buffer,
     bench_large_tree_render,
-    bench_frame_fill
+    bench_frame_fill,
+    bench_style_resolution
 );
 criterion_main!(benches);
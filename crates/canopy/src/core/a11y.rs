@@ -0,0 +1,288 @@
+//! Accessibility metadata and screen reader announcements.
+//!
+//! Widgets describe themselves through [`Widget::accessible`](crate::Widget::accessible).
+//! After each rendered frame, Canopy describes the focused node, or its nearest ancestor
+//! with metadata, and passes the text to the installed [`Announcer`] whenever it changes.
+//! An announcer is the bridge to assistive technology: [`LineAnnouncer`] writes
+//! announcements to any writer, such as a pipe read by a speech synthesizer, and other
+//! bridges implement the trait.
+
+use std::{fmt, io::Write};
+
+use crate::error::Result;
+
+/// The kind of element a node presents to assistive technology.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// A pressable button.
+    Button,
+    /// A two-state toggle.
+    Checkbox,
+    /// One option in a group of mutually exclusive options.
+    Radio,
+    /// Read-only text.
+    Text,
+    /// Editable text.
+    TextInput,
+    /// A list of items.
+    List,
+    /// One item in a list.
+    ListItem,
+    /// A hierarchical tree.
+    Tree,
+    /// One item in a tree.
+    TreeItem,
+    /// A table or grid.
+    Table,
+    /// A menu of commands.
+    Menu,
+    /// One command in a menu.
+    MenuItem,
+    /// A tab in a tab strip.
+    Tab,
+    /// A modal or transient window.
+    Dialog,
+    /// A status or progress region.
+    Status,
+    /// A group of related elements.
+    Group,
+}
+
+impl Role {
+    /// Return the spoken name of this role.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Button => "button",
+            Self::Checkbox => "checkbox",
+            Self::Radio => "radio button",
+            Self::Text => "text",
+            Self::TextInput => "edit text",
+            Self::List => "list",
+            Self::ListItem => "list item",
+            Self::Tree => "tree",
+            Self::TreeItem => "tree item",
+            Self::Table => "table",
+            Self::Menu => "menu",
+            Self::MenuItem => "menu item",
+            Self::Tab => "tab",
+            Self::Dialog => "dialog",
+            Self::Status => "status",
+            Self::Group => "group",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Accessibility metadata for a node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accessible {
+    /// Element role.
+    pub role: Role,
+    /// Short name of the element.
+    pub label: String,
+    /// Current state, such as "checked" or "3 of 10".
+    pub state: Option<String>,
+    /// Longer description or hint.
+    pub description: Option<String>,
+}
+
+impl Accessible {
+    /// Construct metadata with a role and label.
+    pub fn new(role: Role, label: impl Into<String>) -> Self {
+        Self {
+            role,
+            label: label.into(),
+            state: None,
+            description: None,
+        }
+    }
+
+    /// Set the current state.
+    pub fn with_state(mut self, state: impl Into<String>) -> Self {
+        self.state = Some(state.into());
+        self
+    }
+
+    /// Set the longer description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Return the text announced for this element: label, role, state, and
+    /// description, separated by commas.
+    pub fn describe(&self) -> String {
+        let mut parts: Vec<&str> = Vec::new();
+        if !self.label.is_empty() {
+            parts.push(&self.label);
+        }
+        parts.push(self.role.as_str());
+        parts.extend(self.state.as_deref());
+        parts.extend(self.description.as_deref());
+        parts.join(", ")
+    }
+}
+
+/// Receives announcements for assistive technology.
+pub trait Announcer: Send {
+    /// Announce a line of text.
+    fn announce(&mut self, text: &str) -> Result<()>;
+}
+
+/// An announcer that writes each announcement as a line and flushes it. The writer must
+/// not be the terminal stream the UI draws to.
+pub struct LineAnnouncer<W: Write + Send> {
+    /// Destination writer.
+    out: W,
+}
+
+impl<W: Write + Send> LineAnnouncer<W> {
+    /// Construct an announcer writing to `out`.
+    pub fn new(out: W) -> Self {
+        Self { out }
+    }
+}
+
+impl<W: Write + Send> Announcer for LineAnnouncer<W> {
+    fn announce(&mut self, text: &str) -> Result<()> {
+        writeln!(self.out, "{text}")?;
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{
+        Context, EventOutcome, Loader, ReadContext, Widget, event::Event, layout::Layout,
+        state::NodeName, testing::harness::Harness,
+    };
+
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<String>>>);
+
+    impl Announcer for Shared {
+        fn announce(&mut self, text: &str) -> Result<()> {
+            self.0.lock().unwrap().push(text.to_string());
+            Ok(())
+        }
+    }
+
+    struct Toggle {
+        label: &'static str,
+        checked: bool,
+    }
+
+    impl Widget for Toggle {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+            true
+        }
+
+        fn accessible(&self, _ctx: &dyn ReadContext) -> Option<Accessible> {
+            let state = if self.checked { "checked" } else { "unchecked" };
+            Some(Accessible::new(Role::Checkbox, self.label).with_state(state))
+        }
+
+        fn on_event(&mut self, event: &Event, _ctx: &mut dyn Context) -> Result<EventOutcome> {
+            match event {
+                Event::Key(k) if *k == ' ' => {
+                    self.checked = !self.checked;
+                    Ok(EventOutcome::Handle)
+                }
+                _ => Ok(EventOutcome::Ignore),
+            }
+        }
+
+        fn name(&self) -> NodeName {
+            NodeName::convert("toggle")
+        }
+    }
+
+    struct Panel;
+
+    impl Widget for Panel {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn on_mount(&mut self, ctx: &mut dyn Context) -> Result<()> {
+            for label in ["Alpha", "Beta"] {
+                ctx.add_child(Toggle {
+                    label,
+                    checked: false,
+                })?;
+            }
+            Ok(())
+        }
+
+        fn name(&self) -> NodeName {
+            NodeName::convert("panel")
+        }
+    }
+
+    impl Loader for Panel {}
+
+    #[test]
+    fn focus_changes_are_announced_once() -> Result<()> {
+        let mut h = Harness::builder(Panel).size(10, 2).build()?;
+        let log = Shared::default();
+        h.canopy.set_announcer(log.clone());
+        let root = h.root;
+
+        h.render()?;
+        h.render()?;
+        h.key(' ')?;
+        h.canopy.core.focus_next(root);
+        h.render()?;
+        assert_eq!(
+            h.canopy.focused_description()?.as_deref(),
+            Some("Beta, checkbox, unchecked")
+        );
+        assert_eq!(
+            *log.0.lock().unwrap(),
+            vec![
+                "Alpha, checkbox, unchecked",
+                "Alpha, checkbox, checked",
+                "Beta, checkbox, unchecked",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn describe_joins_present_parts() {
+        assert_eq!(
+            Accessible::new(Role::Button, "Save").describe(),
+            "Save, button"
+        );
+        let checkbox = Accessible::new(Role::Checkbox, "Wrap lines")
+            .with_state("checked")
+            .with_description("Wrap long lines at the view edge");
+        assert_eq!(
+            checkbox.describe(),
+            "Wrap lines, checkbox, checked, Wrap long lines at the view edge"
+        );
+        assert_eq!(Accessible::new(Role::List, "").describe(), "list");
+    }
+
+    #[test]
+    fn line_announcer_writes_lines() -> Result<()> {
+        let mut announcer = LineAnnouncer::new(Vec::new());
+        announcer.announce("Save, button")?;
+        announcer.announce("Quit, button")?;
+        assert_eq!(announcer.out, b"Save, button\nQuit, button\n");
+        Ok(())
+    }
+}
@@ -0,0 +1,418 @@
+//! Tweened values for animation.
+//!
+//! A [`Tween`] moves a value from its current position to a target over a duration,
+//! shaped by an [`Easing`] curve. Tweens read time from
+//! [`ReadContext::now`](crate::ReadContext::now), so they follow the harness's virtual
+//! clock in tests.
+//!
+//! Starting a tween arms a repeating [`FRAME_TIMER`] on the node. Each timer event
+//! marks a render pending, so the node redraws every frame while it animates. The
+//! widget reads [`Tween::value`] when rendering, and its handler for
+//! `Event::Timer(FRAME_TIMER)` calls [`frame`] with whether any of its tweens are still
+//! running, which cancels the timer once nothing is.
+//!
+//! With reduced motion enabled, tweens jump straight to their targets.
+
+use std::time::{Duration, Instant};
+
+use crate::{
+    Context,
+    event::TimerId,
+    geom::{Point, PointI32},
+    style::Color,
+};
+
+/// Timer id reserved for animation frames.
+pub const FRAME_TIMER: TimerId = TimerId::MAX;
+
+/// Timer id reserved for smooth scrolling. Canopy handles it without delivering it to the
+/// widget.
+pub const SCROLL_TIMER: TimerId = TimerId::MAX - 1;
+
+/// Interval between animation frames.
+pub const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Default tween duration.
+pub const DEFAULT_DURATION: Duration = Duration::from_millis(150);
+
+/// An easing curve mapping linear progress in `0.0..=1.0` to eased progress.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Easing {
+    /// Constant speed.
+    Linear,
+    /// Start slow and accelerate.
+    EaseIn,
+    /// Start fast and decelerate.
+    #[default]
+    EaseOut,
+    /// Accelerate, then decelerate.
+    EaseInOut,
+}
+
+impl Easing {
+    /// Apply the curve to progress `t`, clamped to `0.0..=1.0`.
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t * t,
+            Self::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Self::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// Values that can be interpolated.
+pub trait Lerp: Clone {
+    /// Interpolate from `self` toward `other`. `t` is 0.0 at `self` and 1.0 at `other`.
+    fn lerp(&self, other: &Self, t: f32) -> Self;
+}
+
+/// Interpolate between two floats.
+fn mix(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        mix(*self, *other, t)
+    }
+}
+
+impl Lerp for i32 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        mix(*self as f32, *other as f32, t).round() as Self
+    }
+}
+
+impl Lerp for u32 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        mix(*self as f32, *other as f32, t).round().max(0.0) as Self
+    }
+}
+
+impl Lerp for Point {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            x: self.x.lerp(&other.x, t),
+            y: self.y.lerp(&other.y, t),
+        }
+    }
+}
+
+impl Lerp for PointI32 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            x: self.x.lerp(&other.x, t),
+            y: self.y.lerp(&other.y, t),
+        }
+    }
+}
+
+impl Lerp for Color {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        if t >= 1.0 {
+            return *other;
+        }
+        self.blend(*other, t)
+    }
+}
+
+/// A value that animates toward a target.
+#[derive(Debug, Clone)]
+pub struct Tween<T: Lerp> {
+    /// Value at the start of the current animation.
+    from: T,
+    /// Target value.
+    to: T,
+    /// When the current animation started, or `None` when settled.
+    start: Option<Instant>,
+    /// Animation length.
+    duration: Duration,
+    /// Easing curve.
+    easing: Easing,
+}
+
+impl<T: Lerp> Tween<T> {
+    /// Construct a tween settled at `value`.
+    pub fn new(value: T) -> Self {
+        Self {
+            from: value.clone(),
+            to: value,
+            start: None,
+            duration: DEFAULT_DURATION,
+            easing: Easing::default(),
+        }
+    }
+
+    /// Set the animation length.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Set the easing curve.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Return the target value.
+    pub fn target(&self) -> &T {
+        &self.to
+    }
+
+    /// Return the value at `now`.
+    pub fn value(&self, now: Instant) -> T {
+        match self.progress(now) {
+            Some(t) => self.from.lerp(&self.to, self.easing.apply(t)),
+            None => self.to.clone(),
+        }
+    }
+
+    /// Is an animation still running at `now`?
+    pub fn is_running(&self, now: Instant) -> bool {
+        self.progress(now).is_some()
+    }
+
+    /// Jump to `value` without animating.
+    pub fn set(&mut self, value: T) {
+        self.from = value.clone();
+        self.to = value;
+        self.start = None;
+    }
+
+    /// Animate from the current value to `target` and arm the node's frame timer.
+    /// With reduced motion enabled, or a zero duration, the value jumps to `target`.
+    pub fn animate_to(&mut self, target: T, ctx: &mut dyn Context) {
+        if ctx.reduced_motion() || self.duration.is_zero() {
+            self.set(target);
+            return;
+        }
+        self.retarget(target, ctx.now());
+        ctx.set_timer(FRAME_TIMER, FRAME_INTERVAL, true);
+    }
+
+    /// Animate from the value at `now` to `target` without arming a timer.
+    pub fn retarget(&mut self, target: T, now: Instant) {
+        self.from = self.value(now);
+        self.to = target;
+        self.start = Some(now);
+    }
+
+    /// Return linear progress in `0.0..1.0` while running.
+    fn progress(&self, now: Instant) -> Option<f32> {
+        let start = self.start?;
+        let elapsed = now.saturating_duration_since(start);
+        (elapsed < self.duration).then(|| elapsed.as_secs_f32() / self.duration.as_secs_f32())
+    }
+}
+
+impl<T: Lerp + Default> Default for Tween<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// Handle a [`FRAME_TIMER`] event: cancel the timer once no animation on the node is
+/// `running`. The timer event itself marks a render pending.
+pub fn frame(ctx: &mut dyn Context, running: bool) {
+    if !running {
+        ctx.cancel_timer(FRAME_TIMER);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        EventOutcome, Loader, ReadContext, Widget,
+        error::Result,
+        event::{Event, key},
+        geom::Size,
+        layout::{CanvasContext, Layout},
+        state::NodeName,
+        testing::harness::Harness,
+    };
+
+    #[test]
+    fn easing_endpoints_are_fixed() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            assert_eq!(easing.apply(2.0), 1.0);
+        }
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn lerp_rounds_integers_and_blends_colors() {
+        assert_eq!(0u32.lerp(&10, 0.44), 4);
+        assert_eq!(10i32.lerp(&-10, 0.5), 0);
+        assert_eq!(
+            Point { x: 0, y: 10 }.lerp(&Point { x: 10, y: 0 }, 0.5),
+            Point { x: 5, y: 5 }
+        );
+        assert_eq!(Color::Black.lerp(&Color::White, 1.0), Color::White);
+    }
+
+    struct Slider {
+        offset: Tween<u32>,
+        frames: usize,
+    }
+
+    impl Widget for Slider {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+            true
+        }
+
+        fn on_event(&mut self, event: &Event, ctx: &mut dyn Context) -> Result<EventOutcome> {
+            match event {
+                Event::Key(k) if *k == key::KeyCode::Right => {
+                    let target = self.offset.target() + 10;
+                    self.offset.animate_to(target, ctx);
+                }
+                Event::Timer(FRAME_TIMER) => {
+                    self.frames += 1;
+                    frame(ctx, self.offset.is_running(ctx.now()));
+                }
+                _ => return Ok(EventOutcome::Ignore),
+            }
+            Ok(EventOutcome::Handle)
+        }
+
+        fn name(&self) -> NodeName {
+            NodeName::convert("slider")
+        }
+    }
+
+    impl Loader for Slider {}
+
+    fn slider() -> Slider {
+        Slider {
+            offset: Tween::new(0)
+                .with_duration(Duration::from_millis(100))
+                .with_easing(Easing::Linear),
+            frames: 0,
+        }
+    }
+
+    #[test]
+    fn tween_runs_on_frame_timer_until_done() -> Result<()> {
+        let mut h = Harness::builder(slider()).size(10, 1).build()?;
+        h.render()?;
+        h.press("Right")?.advance(Duration::from_millis(48))?;
+        let (value, frames) =
+            h.with_root_context(|s: &mut Slider, ctx| Ok((s.offset.value(ctx.now()), s.frames)))?;
+        assert_eq!((value, frames), (5, 3));
+
+        h.advance(Duration::from_millis(200))?;
+        let (value, frames) =
+            h.with_root_context(|s: &mut Slider, ctx| Ok((s.offset.value(ctx.now()), s.frames)))?;
+        assert_eq!(value, 10);
+        // Frames stop at the first tick after the tween settles.
+        assert_eq!(frames, 7);
+        Ok(())
+    }
+
+    #[test]
+    fn reduced_motion_jumps_to_target() -> Result<()> {
+        let mut h = Harness::builder(slider()).size(10, 1).build()?;
+        h.canopy.set_reduced_motion(true);
+        h.render()?;
+        h.press("Right")?.advance(Duration::from_millis(100))?;
+        let (value, frames) =
+            h.with_root_context(|s: &mut Slider, ctx| Ok((s.offset.value(ctx.now()), s.frames)))?;
+        assert_eq!((value, frames), (10, 0));
+        Ok(())
+    }
+
+    struct Pager;
+
+    impl Widget for Pager {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn canvas(&self, view: Size<u32>, _ctx: &CanvasContext) -> Size<u32> {
+            Size::new(view.w, 100)
+        }
+
+        fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+            true
+        }
+
+        fn on_event(&mut self, event: &Event, ctx: &mut dyn Context) -> Result<EventOutcome> {
+            match event {
+                Event::Key(k) if *k == 'n' => {
+                    ctx.page_down();
+                }
+                Event::Key(k) if *k == 'k' => {
+                    ctx.scroll_up();
+                }
+                _ => return Ok(EventOutcome::Ignore),
+            }
+            Ok(EventOutcome::Handle)
+        }
+
+        fn name(&self) -> NodeName {
+            NodeName::convert("pager")
+        }
+    }
+
+    impl Loader for Pager {}
+
+    fn scroll_y(h: &mut Harness) -> Result<u32> {
+        h.with_root_context(|_: &mut Pager, ctx| Ok(ctx.view().tl.y))
+    }
+
+    #[test]
+    fn page_scroll_animates_when_smooth() -> Result<()> {
+        let mut h = Harness::builder(Pager).size(10, 10).build()?;
+        h.canopy.set_smooth_scroll(Some(Duration::from_millis(100)));
+        h.render()?;
+        h.key('n')?;
+        assert_eq!(scroll_y(&mut h)?, 0);
+        h.advance(Duration::from_millis(48))?;
+        let mid = scroll_y(&mut h)?;
+        assert!(mid > 0 && mid < 10, "mid-animation offset {mid}");
+
+        // A second page accumulates onto the running animation's target.
+        h.key('n')?;
+        h.advance(Duration::from_millis(200))?;
+        assert_eq!(scroll_y(&mut h)?, 20);
+
+        // Direct scrolls are immediate.
+        h.key('k')?;
+        assert_eq!(scroll_y(&mut h)?, 19);
+        Ok(())
+    }
+
+    #[test]
+    fn page_scroll_is_instant_with_reduced_motion() -> Result<()> {
+        let mut h = Harness::builder(Pager).size(10, 10).build()?;
+        h.canopy.set_smooth_scroll(Some(Duration::from_millis(100)));
+        h.canopy.set_reduced_motion(true);
+        h.render()?;
+        h.key('n')?;
+        assert_eq!(scroll_y(&mut h)?, 10);
+        Ok(())
+    }
+}
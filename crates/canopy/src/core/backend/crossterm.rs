@@ -1,9 +1,16 @@
 use std::{
-    io::{self, Stderr, Write},
+    any::Any,
+    io::{self, Write},
     mem, panic,
+    path::PathBuf,
     result::Result as StdResult,
-    sync::mpsc::{self, TryRecvError},
+    sync::{
+        Arc,
+        atomic::{AtomicU16, Ordering},
+        mpsc::{self, RecvTimeoutError, TryRecvError},
+    },
     thread,
+    time::{Duration, Instant},
 };
 
 use color_backtrace::{BacktracePrinter, default_output_stream};
@@ -12,12 +19,20 @@ use unicode_segmentation::UnicodeSegmentation;
 
 use crate::{
     Canopy, NodeId,
-    backend::{BackendControl, TerminalSession},
+    backend::{
+        BackendControl, TerminalGuard, TerminalSession,
+        guard::{
+            Output, OutputStream, RESET_CURSOR_COLOR, Screen, cursor_colored, set_cursor_colored,
+            set_cursor_shaped,
+        },
+        replay::Recorder,
+    },
     core::{
         Core,
         dump::{dump, dump_with_focus},
-        text,
+        isolate, text,
     },
+    cursor,
     error::{self, Result},
     event::{Event, key, mouse},
     geom::{Point, Size},
@@ -26,18 +41,25 @@ use crate::{
 };
 /// Simple event source wrapper for receiving events.
 ///
-/// This coalesces consecutive mouse-move events so clicks are not delayed by move bursts.
+/// This coalesces consecutive mouse-move events so clicks are not delayed by move bursts,
+/// and bursts of resize events so dragging a terminal's corner triggers one relayout.
 struct EventSource {
     /// Event receiver channel.
     rx: mpsc::Receiver<Event>,
-    /// Buffered non-move event encountered while coalescing.
+    /// Buffered event that ended a coalesced burst.
     pending: Option<Event>,
+    /// How long to wait for a further resize before delivering the latest one.
+    resize_debounce: Option<Duration>,
 }
 
 impl EventSource {
     /// Construct a new event source.
-    fn new(rx: mpsc::Receiver<Event>) -> Self {
-        Self { rx, pending: None }
+    fn new(rx: mpsc::Receiver<Event>, resize_debounce: Option<Duration>) -> Self {
+        Self {
+            rx,
+            pending: None,
+            resize_debounce,
+        }
     }
 
     /// Block until the next event arrives.
@@ -46,43 +68,85 @@ impl EventSource {
             return Ok(event);
         }
 
-        let mut event = self.rx.recv()?;
-        if matches!(
-            event,
-            Event::Mouse(mouse::MouseEvent {
-                action: mouse::Action::Moved,
-                ..
-            })
-        ) {
-            loop {
-                match self.rx.try_recv() {
-                    Ok(next) => {
-                        if matches!(
-                            next,
-                            Event::Mouse(mouse::MouseEvent {
-                                action: mouse::Action::Moved,
-                                ..
-                            })
-                        ) {
-                            event = next;
-                        } else {
-                            self.pending = Some(next);
-                            break;
-                        }
-                    }
-                    Err(TryRecvError::Empty) => break,
-                    Err(TryRecvError::Disconnected) => break,
-                }
-            }
+        let event = self.rx.recv()?;
+        Ok(self.coalesce(event))
+    }
+
+    /// Block until the next event arrives or the deadline passes.
+    fn next_until(&mut self, deadline: Instant) -> StdResult<Option<Event>, mpsc::RecvError> {
+        if let Some(event) = self.pending.take() {
+            return Ok(Some(event));
         }
 
-        Ok(event)
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        match self.rx.recv_timeout(timeout) {
+            Ok(event) => Ok(Some(self.coalesce(event))),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => Err(mpsc::RecvError),
+        }
+    }
+
+    /// Collapse a burst of queued mouse-move or resize events into the latest one.
+    fn coalesce(&mut self, event: Event) -> Event {
+        if is_mouse_move(&event) {
+            self.latest(event, is_mouse_move, None)
+        } else if is_resize(&event) {
+            self.latest(event, is_resize, self.resize_debounce)
+        } else {
+            event
+        }
+    }
+
+    /// Replace `event` with later events matching `same`, stopping at the first other
+    /// event, which is kept pending. Without a `debounce`, only queued events are taken;
+    /// with one, each match waits up to that long for another.
+    fn latest(
+        &mut self,
+        mut event: Event,
+        same: fn(&Event) -> bool,
+        debounce: Option<Duration>,
+    ) -> Event {
+        loop {
+            let next = match debounce {
+                Some(wait) => match self.rx.recv_timeout(wait) {
+                    Ok(next) => next,
+                    Err(_) => break,
+                },
+                None => match self.rx.try_recv() {
+                    Ok(next) => next,
+                    Err(TryRecvError::Empty | TryRecvError::Disconnected) => break,
+                },
+            };
+            if same(&next) {
+                event = next;
+            } else {
+                self.pending = Some(next);
+                break;
+            }
+        }
+        event
     }
 }
 
-use crossterm::{
-    self, ExecutableCommand, QueueableCommand, cursor as ccursor, event as cevent, style, terminal,
-};
+/// Is this a mouse-move event?
+fn is_mouse_move(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::Mouse(mouse::MouseEvent {
+            action: mouse::Action::Moved,
+            ..
+        })
+    )
+}
+
+/// Is this a resize event?
+fn is_resize(event: &Event) -> bool {
+    matches!(event, Event::Resize(_))
+}
+
+use crossterm::{QueueableCommand, cursor as ccursor, event as cevent, style, terminal};
+#[cfg(unix)]
+use signal_hook::{consts::SIGTSTP, low_level};
 
 /// Translate a canopy color into a crossterm color.
 fn translate_color(c: Color) -> style::Color {
@@ -105,6 +169,7 @@ fn translate_color(c: Color) -> style::Color {
         Color::Grey => style::Color::Grey,
         Color::Rgb { r, g, b } => style::Color::Rgb { r, g, b },
         Color::AnsiValue(a) => style::Color::AnsiValue(a),
+        Color::Inherit => style::Color::Reset,
     }
 }
 
@@ -119,49 +184,75 @@ fn translate_result<T>(e: io::Result<T>) -> Result<T> {
 /// Crossterm-backed implementation of `BackendControl`.
 #[derive(Debug)]
 pub struct CrosstermControl {
-    /// Stderr handle used for control output.
-    fp: Stderr,
     /// Whether to enable keyboard enhancement flags on startup.
     enable_keyboard_enhancements: bool,
-    /// Track whether keyboard enhancements were pushed.
-    keyboard_enhancements_pushed: bool,
+    /// Stream that control output is written to.
+    output: OutputStream,
+    /// Where the UI is drawn.
+    screen: Screen,
+    /// Whether the terminal has been entered before. Later entries happen while the
+    /// event thread is reading input, so they cannot query the cursor position.
+    entered: bool,
+    /// Terminal guard held while the backend is started.
+    guard: Option<TerminalGuard>,
+    /// Screen origin shared with the attached render backend.
+    origin: Arc<AtomicU16>,
 }
 
 impl CrosstermControl {
     /// Build a crossterm controller with keyboard enhancements enabled or disabled.
     pub fn new(enable_keyboard_enhancements: bool) -> Self {
         Self {
-            fp: io::stderr(),
             enable_keyboard_enhancements,
-            keyboard_enhancements_pushed: false,
+            output: OutputStream::default(),
+            screen: Screen::default(),
+            entered: false,
+            guard: None,
+            origin: Arc::default(),
         }
     }
 
-    /// Enter alternate screen and raw mode.
+    /// Write control output to `output`.
+    pub fn with_output(mut self, output: OutputStream) -> Self {
+        self.output = output;
+        self
+    }
+
+    /// Draw on `screen`.
+    pub fn with_screen(mut self, screen: Screen) -> Self {
+        self.screen = screen;
+        self
+    }
+
+    /// Draw `render` on this controller's screen. The render backend writes to the
+    /// configured output stream, and its rows are offset to the screen origin each time
+    /// the terminal is entered.
+    pub fn with_render(mut self, render: &mut CrosstermRender) -> Self {
+        render.fp = self.output.writer();
+        self.origin = Arc::clone(&render.origin);
+        self
+    }
+
+    /// Enter raw mode on the configured screen.
     fn enter(&mut self) -> io::Result<()> {
-        terminal::enable_raw_mode()?;
-        self.fp.execute(terminal::EnterAlternateScreen)?;
-        self.fp.execute(cevent::EnableMouseCapture)?;
-        self.fp.execute(ccursor::Hide)?;
-        if self.enable_keyboard_enhancements {
-            self.fp.execute(cevent::PushKeyboardEnhancementFlags(
-                cevent::KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES,
-            ))?;
-            self.keyboard_enhancements_pushed = true;
-        }
+        let guard = TerminalGuard::enter_with(
+            self.output,
+            self.screen,
+            self.enable_keyboard_enhancements,
+            !self.entered,
+        )?;
+        self.origin.store(guard.origin(), Ordering::Relaxed);
+        self.guard = Some(guard);
+        self.entered = true;
         Ok(())
     }
-    /// Leave alternate screen and restore terminal state.
+
+    /// Leave the configured screen and restore terminal state.
     fn exit(&mut self) -> io::Result<()> {
-        if self.keyboard_enhancements_pushed {
-            self.fp.execute(cevent::PopKeyboardEnhancementFlags)?;
-            self.keyboard_enhancements_pushed = false;
-        }
-        self.fp.execute(terminal::LeaveAlternateScreen)?;
-        self.fp.execute(cevent::DisableMouseCapture)?;
-        self.fp.execute(ccursor::Show)?;
-        terminal::disable_raw_mode()?;
-        Ok(())
+        match self.guard.take() {
+            Some(mut guard) => guard.restore(),
+            None => Ok(()),
+        }
     }
 }
 
@@ -178,66 +269,215 @@ impl BackendControl for CrosstermControl {
     fn stop(&mut self) -> Result<()> {
         translate_result(self.exit())
     }
+    fn suspend(&mut self) -> Result<Option<Size>> {
+        translate_result(self.exit())?;
+        let stopped = stop_process();
+        translate_result(self.enter())?;
+        translate_result(stopped)?;
+        let size = translate_result(terminal::size())?;
+        Ok(Some(screen_size(
+            self.screen,
+            Size::new(size.0.into(), size.1.into()),
+        )))
+    }
+}
+
+/// Stop the current process with `SIGTSTP`, returning once it receives `SIGCONT`.
+#[cfg(unix)]
+fn stop_process() -> io::Result<()> {
+    low_level::raise(SIGTSTP)
+}
+
+/// Job control is unavailable on this platform.
+#[cfg(not(unix))]
+fn stop_process() -> io::Result<()> {
+    Ok(())
 }
 
 /// Crossterm-backed render backend.
+///
+/// Rows are offset by the screen origin that the controller attached through
+/// [`CrosstermControl::with_render`] sets, so the same backend draws on the alternate
+/// screen or into an inline region. Output is collected in a reusable buffer
+/// and written once per flush. Cursor moves to where the cursor already is, and styles
+/// equal to the one in effect, are skipped.
 pub struct CrosstermRender {
-    /// Stderr handle used for rendering output.
-    fp: Stderr,
+    /// Handle used for rendering output.
+    fp: Output,
+    /// Output queued since the last flush.
+    buf: Vec<u8>,
+    /// Terminal cursor position after the queued output, if known.
+    cursor: Option<(u16, u16)>,
+    /// Style in effect after the queued output, if known.
+    style: Option<ResolvedStyle>,
+    /// Wrap each frame in synchronized update (mode 2026) sequences. Terminals without
+    /// the mode ignore them.
+    synchronized_output: bool,
+    /// Whether a synchronized update has begun and not yet been ended.
+    sync_open: bool,
+    /// Cursor color last sent to the terminal, if any.
+    cursor_color: Option<Color>,
+    /// Whether the terminal cursor was last shown rather than hidden.
+    cursor_visible: bool,
+    /// First terminal row of the screen being drawn on.
+    origin: Arc<AtomicU16>,
 }
 
 impl CrosstermRender {
-    /// Flush pending output.
+    /// Write rendering output to `output`.
+    pub fn with_output(mut self, output: OutputStream) -> Self {
+        self.fp = output.writer();
+        self
+    }
+
+    /// Return the first terminal row of the screen being drawn on.
+    fn origin(&self) -> u16 {
+        self.origin.load(Ordering::Relaxed)
+    }
+
+    /// Translate a render row into a terminal row below the screen origin.
+    fn screen_row(&self, y: u32) -> u16 {
+        self.origin().saturating_add(y.min(u16::MAX as u32) as u16)
+    }
+
+    /// Begin a frame, opening a synchronized update if enabled. Other output may have
+    /// moved the cursor or changed the style since the last frame, so both are forgotten.
+    fn begin_frame(&mut self) -> io::Result<()> {
+        self.cursor = None;
+        self.style = None;
+        if self.synchronized_output && !self.sync_open {
+            self.buf.queue(terminal::BeginSynchronizedUpdate)?;
+            self.sync_open = true;
+        }
+        Ok(())
+    }
+
+    /// Write queued output in one call, closing any open synchronized update.
     fn flush(&mut self) -> io::Result<()> {
+        if self.sync_open {
+            self.buf.queue(terminal::EndSynchronizedUpdate)?;
+            self.sync_open = false;
+        }
+        self.cursor = None;
+        if !self.buf.is_empty() {
+            self.fp.write_all(&self.buf)?;
+            self.buf.clear();
+        }
         self.fp.flush()?;
         Ok(())
     }
 
+    /// Set the terminal cursor color with OSC 12, or reset it with OSC 112. The terminal
+    /// guard resets a changed color when the terminal is restored, so after a suspend the
+    /// color is sent again.
+    fn set_cursor_color(&mut self, color: Option<Color>) -> io::Result<()> {
+        let current = if cursor_colored() {
+            self.cursor_color
+        } else {
+            None
+        };
+        if current == color {
+            return Ok(());
+        }
+        self.cursor_color = color;
+        match color {
+            Some(color) => {
+                self.buf
+                    .queue(style::Print(format!("\x1b]12;{}\x07", color.to_hex())))?;
+            }
+            None => {
+                self.buf.write_all(RESET_CURSOR_COLOR)?;
+            }
+        }
+        set_cursor_colored(color.is_some());
+        Ok(())
+    }
+
+    /// Show the terminal cursor at a screen location with the given shape, or hide it.
+    fn show_cursor(&mut self, cursor: Option<cursor::Cursor>) -> io::Result<()> {
+        let Some(cursor) = cursor else {
+            if mem::take(&mut self.cursor_visible) {
+                self.buf.queue(ccursor::Hide)?;
+            }
+            return Ok(());
+        };
+        let x = cursor.location.x.min(u16::MAX as u32) as u16;
+        self.move_to(x, self.screen_row(cursor.location.y))?;
+        self.buf.queue(translate_cursor_style(&cursor))?;
+        self.buf.queue(ccursor::Show)?;
+        self.cursor_visible = true;
+        set_cursor_shaped(true);
+        Ok(())
+    }
+
     /// Apply a style to subsequent output.
     fn apply_style(&mut self, s: &ResolvedStyle) -> io::Result<()> {
+        if self.style == Some(*s) {
+            return Ok(());
+        }
+        self.style = Some(*s);
         // Always reset first to clear any previous attributes, then set colors and attrs.
         // Order is important: reset clears everything, so we must set colors after.
-        self.fp
+        self.buf
             .queue(style::SetAttribute(style::Attribute::Reset))?;
-        self.fp
+        self.buf
             .queue(style::SetForegroundColor(translate_color(s.fg)))?;
-        self.fp
+        self.buf
             .queue(style::SetBackgroundColor(translate_color(s.bg)))?;
 
         // Now add the desired attributes
         if s.attrs.bold {
-            self.fp.queue(style::SetAttribute(style::Attribute::Bold))?;
+            self.buf
+                .queue(style::SetAttribute(style::Attribute::Bold))?;
         }
         if s.attrs.crossedout {
-            self.fp
+            self.buf
                 .queue(style::SetAttribute(style::Attribute::CrossedOut))?;
         }
         if s.attrs.dim {
-            self.fp.queue(style::SetAttribute(style::Attribute::Dim))?;
+            self.buf.queue(style::SetAttribute(style::Attribute::Dim))?;
         }
         if s.attrs.italic {
-            self.fp
+            self.buf
                 .queue(style::SetAttribute(style::Attribute::Italic))?;
         }
         if s.attrs.overline {
-            self.fp
+            self.buf
                 .queue(style::SetAttribute(style::Attribute::OverLined))?;
         }
         if s.attrs.underline {
-            self.fp
+            self.buf
                 .queue(style::SetAttribute(style::Attribute::Underlined))?;
         }
         Ok(())
     }
 
+    /// Move the cursor unless it is already at the target.
+    fn move_to(&mut self, x: u16, y: u16) -> io::Result<()> {
+        if self.cursor != Some((x, y)) {
+            self.buf.queue(ccursor::MoveTo(x, y))?;
+        }
+        self.cursor = Some((x, y));
+        Ok(())
+    }
+
     /// Write text at a position.
+    ///
+    /// The terminal may advance a different number of columns over a wide grapheme than
+    /// we do, so the cursor position is forgotten after one and the next run is placed
+    /// with an absolute move.
     fn text(&mut self, loc: Point, txt: &str) -> io::Result<()> {
         for run in positioned_text_runs(loc, txt) {
-            self.fp.queue(ccursor::MoveTo(
-                run.location.x as u16,
-                run.location.y as u16,
-            ))?;
-            self.fp.queue(style::Print(run.text))?;
+            let (x, y) = (run.location.x as u16, self.screen_row(run.location.y));
+            self.move_to(x, y)?;
+            let width = text::display_width(&run.text).min(u16::MAX as usize) as u16;
+            let wide = run
+                .text
+                .graphemes(true)
+                .next_back()
+                .is_some_and(|grapheme| text::grapheme_width(grapheme) > 1);
+            self.buf.queue(style::Print(run.text))?;
+            self.cursor = (!wide).then_some((x.saturating_add(width), y));
         }
         Ok(())
     }
@@ -300,7 +540,17 @@ fn push_positioned_text_run(runs: &mut Vec<PositionedTextRun>, location: Point,
 
 impl Default for CrosstermRender {
     fn default() -> Self {
-        Self { fp: io::stderr() }
+        Self {
+            fp: OutputStream::default().writer(),
+            buf: Vec::new(),
+            cursor: None,
+            style: None,
+            synchronized_output: true,
+            sync_open: false,
+            cursor_color: None,
+            cursor_visible: false,
+            origin: Arc::default(),
+        }
     }
 }
 
@@ -331,13 +581,13 @@ impl RenderBackend for CrosstermRender {
         }
 
         let count_abs = count.unsigned_abs().min(u16::MAX as u32) as u16;
-        translate_result(self.fp.queue(ccursor::MoveTo(loc.x as u16, loc.y as u16)))?;
+        translate_result(self.move_to(loc.x as u16, self.screen_row(loc.y)))?;
         let seq = if count > 0 {
             format!("\x1b[{count_abs}@")
         } else {
             format!("\x1b[{count_abs}P")
         };
-        translate_result(self.fp.queue(style::Print(seq)))?;
+        translate_result(self.buf.queue(style::Print(seq)))?;
         Ok(())
     }
 
@@ -345,27 +595,53 @@ impl RenderBackend for CrosstermRender {
         if count == 0 {
             return Ok(());
         }
-        let top = _top.min(u16::MAX as u32) as u16;
-        let bottom = _bottom.min(u16::MAX as u32) as u16;
+        let top = self.screen_row(_top);
+        let bottom = self.screen_row(_bottom);
         if top > bottom {
             return Ok(());
         }
         let count_abs = count.unsigned_abs().min(u16::MAX as u32) as u16;
         let region = format!("\x1b[{};{}r", top + 1, bottom + 1);
-        translate_result(self.fp.queue(style::Print(region)))?;
-        translate_result(self.fp.queue(ccursor::MoveTo(0, top)))?;
+        translate_result(self.buf.queue(style::Print(region)))?;
+        translate_result(self.buf.queue(ccursor::MoveTo(0, top)))?;
         let seq = if count > 0 {
             format!("\x1b[{count_abs}T")
         } else {
             format!("\x1b[{count_abs}S")
         };
-        translate_result(self.fp.queue(style::Print(seq)))?;
-        translate_result(self.fp.queue(style::Print("\x1b[r")))?;
+        translate_result(self.buf.queue(style::Print(seq)))?;
+        // Resetting the scroll region homes the cursor.
+        translate_result(self.buf.queue(style::Print("\x1b[r")))?;
+        self.cursor = None;
         Ok(())
     }
 
+    fn set_synchronized_output(&mut self, enabled: bool) {
+        self.synchronized_output = enabled;
+    }
+
     fn reset(&mut self) -> Result<()> {
-        Ok(())
+        translate_result(self.begin_frame())
+    }
+
+    fn cursor_color(&mut self, color: Option<Color>) -> Result<()> {
+        translate_result(self.set_cursor_color(color))
+    }
+
+    fn show_cursor(&mut self, cursor: Option<cursor::Cursor>) -> Result<()> {
+        translate_result(self.show_cursor(cursor))
+    }
+}
+
+/// Translate a cursor's shape and blink into a terminal cursor style.
+fn translate_cursor_style(c: &cursor::Cursor) -> ccursor::SetCursorStyle {
+    match (c.shape, c.blink) {
+        (cursor::CursorShape::Block, true) => ccursor::SetCursorStyle::BlinkingBlock,
+        (cursor::CursorShape::Block, false) => ccursor::SetCursorStyle::SteadyBlock,
+        (cursor::CursorShape::Underscore, true) => ccursor::SetCursorStyle::BlinkingUnderScore,
+        (cursor::CursorShape::Underscore, false) => ccursor::SetCursorStyle::SteadyUnderScore,
+        (cursor::CursorShape::Line, true) => ccursor::SetCursorStyle::BlinkingBar,
+        (cursor::CursorShape::Line, false) => ccursor::SetCursorStyle::SteadyBar,
     }
 }
 
@@ -549,7 +825,7 @@ pub enum CtrlCBehavior {
 }
 
 /// Options for configuring the crossterm runloop behavior.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct RunloopOptions {
     /// Install a panic hook that restores the terminal before printing a backtrace.
     pub install_panic_hook: bool,
@@ -557,6 +833,22 @@ pub struct RunloopOptions {
     pub ctrl_c: CtrlCBehavior,
     /// Enable keyboard enhancement flags for disambiguated escape codes.
     pub enable_keyboard_enhancements: bool,
+    /// Wrap each rendered frame in synchronized update sequences.
+    pub synchronized_output: bool,
+    /// Maximum frames rendered per second. Events arriving faster are coalesced into one
+    /// render per frame. `None` renders after every event.
+    pub max_fps: Option<u32>,
+    /// Record terminal events to this file for later replay with
+    /// [`Recording`](super::replay::Recording).
+    pub record_events: Option<PathBuf>,
+    /// Stream the UI is written to. Writing to stderr leaves stdout free for output
+    /// that a calling script captures.
+    pub output: OutputStream,
+    /// Draw on the alternate screen or in a fixed-height region below the prompt.
+    pub screen: Screen,
+    /// After a resize, wait this long for another before relaying out, so only the
+    /// final size of a burst is handled. `None` still coalesces already queued resizes.
+    pub resize_debounce: Option<Duration>,
 }
 
 impl RunloopOptions {
@@ -575,7 +867,41 @@ impl Default for RunloopOptions {
             install_panic_hook: false,
             ctrl_c: CtrlCBehavior::Exit,
             enable_keyboard_enhancements: true,
+            synchronized_output: true,
+            max_fps: None,
+            record_events: None,
+            output: OutputStream::Stderr,
+            screen: Screen::Alternate,
+            resize_debounce: Some(Duration::from_millis(30)),
+        }
+    }
+}
+
+/// Return the root size for a terminal of `size` on `screen`.
+fn screen_size(screen: Screen, size: Size) -> Size {
+    match screen {
+        Screen::Alternate => size,
+        Screen::Inline(height) => Size::new(size.w, size.h.min(height.into())),
+    }
+}
+
+/// Map a terminal event onto `screen`, whose first terminal row is `origin`.
+///
+/// Inline mode limits resizes to the region height and moves mouse events into region
+/// coordinates, dropping those outside the region.
+fn screen_event(screen: Screen, origin: u16, event: Event) -> Option<Event> {
+    match (screen, event) {
+        (Screen::Inline(_), Event::Resize(size)) => Some(Event::Resize(screen_size(screen, size))),
+        (Screen::Inline(height), Event::Mouse(mut m)) => {
+            let y = m
+                .location
+                .y
+                .checked_sub(origin.into())
+                .filter(|y| *y < u32::from(height))?;
+            m.location.y = y;
+            Some(Event::Mouse(m))
         }
+        (_, event) => Some(event),
     }
 }
 
@@ -585,9 +911,43 @@ pub fn runloop(cnpy: Canopy) -> Result<i32> {
 }
 
 /// Run the main render/event loop using the crossterm backend with custom options.
+///
+/// Exit hooks run after the terminal is restored, and the status code is returned.
 pub fn runloop_with_options(mut cnpy: Canopy, options: RunloopOptions) -> Result<i32> {
+    run_to_exit(&mut cnpy, options)
+}
+
+/// Run the main render/event loop, returning the status code together with the value a
+/// widget passed to `exit_with`. The value is `None` if the app exited another way or
+/// the value is not a `T`.
+pub fn runloop_with_value<T: Any>(
+    mut cnpy: Canopy,
+    options: RunloopOptions,
+) -> Result<(i32, Option<T>)> {
+    let code = run_to_exit(&mut cnpy, options)?;
+    Ok((code, cnpy.take_exit_value()))
+}
+
+/// Run the loop, then the exit hooks once the terminal has been restored.
+fn run_to_exit(cnpy: &mut Canopy, options: RunloopOptions) -> Result<i32> {
+    let code = run(cnpy, options);
+    let hooks = cnpy.run_exit_hooks();
+    let code = code?;
+    hooks?;
+    Ok(code)
+}
+
+/// Run the render/event loop until the app exits. The terminal is restored on return.
+fn run(cnpy: &mut Canopy, options: RunloopOptions) -> Result<i32> {
     let mut be = CrosstermRender::default();
-    cnpy.register_backend(CrosstermControl::new(options.enable_keyboard_enhancements));
+    be.set_synchronized_output(options.synchronized_output);
+    cnpy.set_max_fps(options.max_fps);
+    cnpy.register_backend(
+        CrosstermControl::new(options.enable_keyboard_enhancements)
+            .with_output(options.output)
+            .with_screen(options.screen)
+            .with_render(&mut be),
+    );
     let mut session = {
         let backend = cnpy
             .core
@@ -600,18 +960,11 @@ pub fn runloop_with_options(mut cnpy: Canopy, options: RunloopOptions) -> Result
     let _panic_hook = if options.install_panic_hook {
         let previous = panic::take_hook();
         panic::set_hook(Box::new(|pi| {
-            let mut stderr = io::stderr();
-            #[allow(unused_must_use)]
-            {
-                crossterm::execute!(
-                    stderr,
-                    terminal::LeaveAlternateScreen,
-                    cevent::DisableMouseCapture,
-                    ccursor::Show
-                );
-                terminal::disable_raw_mode();
-                BacktracePrinter::new().print_panic_info(pi, &mut default_output_stream());
+            if isolate::active() {
+                return;
             }
+            TerminalGuard::restore_active();
+            drop(BacktracePrinter::new().print_panic_info(pi, &mut default_output_stream()));
         }));
         Some(guard(previous, |hook| {
             panic::set_hook(hook);
@@ -625,10 +978,16 @@ pub fn runloop_with_options(mut cnpy: Canopy, options: RunloopOptions) -> Result
         .take()
         .ok_or_else(|| error::Error::InvalidOperation("event loop already initialized".into()))?;
 
-    let mut events = EventSource::new(rx);
+    let mut events = EventSource::new(rx, options.resize_debounce);
     event_emitter(cnpy.event_tx.clone());
     let size = translate_result(terminal::size())?;
-    cnpy.set_root_size(Size::new(size.0.into(), size.1.into()))?;
+    let size = screen_size(options.screen, Size::new(size.0.into(), size.1.into()));
+    cnpy.set_root_size(size)?;
+    let mut recorder = options
+        .record_events
+        .as_deref()
+        .map(|path| Recorder::create(path, size))
+        .transpose()?;
     cnpy.start_poller(cnpy.event_tx.clone());
 
     if let Err(e) = cnpy.render(&mut be) {
@@ -646,31 +1005,45 @@ pub fn runloop_with_options(mut cnpy: Canopy, options: RunloopOptions) -> Result
     }
 
     loop {
-        let event = events.next()?;
+        let event = match cnpy.deferred_render_deadline(Instant::now()) {
+            Some(deadline) => events.next_until(deadline)?,
+            None => Some(events.next()?),
+        };
 
-        if matches!(
-            &event,
-            Event::Key(key::Key {
-                key: key::KeyCode::Char('c'),
-                mods: key::Mods { ctrl: true, .. },
-            })
-        ) {
-            drop(session.stop());
-            if options.ctrl_c == CtrlCBehavior::DumpTreeAndExit {
-                eprintln!("\nCtrl+C pressed - Node tree dump:");
-                match dump_with_focus(&cnpy.core, cnpy.core.root, cnpy.core.focus) {
-                    Ok(dump_str) => eprintln!("{dump_str}"),
-                    Err(dump_err) => eprintln!("Failed to dump node tree: {dump_err}"),
+        if let Some(event) =
+            event.and_then(|event| screen_event(options.screen, be.origin(), event))
+        {
+            if matches!(
+                &event,
+                Event::Key(key::Key {
+                    key: key::KeyCode::Char('c'),
+                    mods: key::Mods { ctrl: true, .. },
+                })
+            ) {
+                drop(session.stop());
+                if options.ctrl_c == CtrlCBehavior::DumpTreeAndExit {
+                    eprintln!("\nCtrl+C pressed - Node tree dump:");
+                    match dump_with_focus(&cnpy.core, cnpy.core.root, cnpy.core.focus) {
+                        Ok(dump_str) => eprintln!("{dump_str}"),
+                        Err(dump_err) => eprintln!("Failed to dump node tree: {dump_err}"),
+                    }
                 }
+
+                return Ok(130);
             }
 
-            return Ok(130);
+            if let Some(recorder) = recorder.as_mut() {
+                recorder.record(&event)?;
+            }
+            cnpy.event(event)?;
+            cnpy.service_automation();
+            if let Some(code) = cnpy.core.take_exit_request() {
+                return Ok(code);
+            }
         }
 
-        cnpy.event(event)?;
-        cnpy.service_automation();
-        if let Some(code) = cnpy.core.take_exit_request() {
-            return Ok(code);
+        if cnpy.deferred_render_deadline(Instant::now()).is_some() {
+            continue;
         }
         match cnpy.render_if_pending(&mut be) {
             Ok(rendered) => {
@@ -722,6 +1095,125 @@ mod tests {
         );
     }
 
+    #[test]
+    fn render_skips_redundant_moves_and_styles() -> io::Result<()> {
+        let mut render = CrosstermRender::default();
+        render.set_synchronized_output(false);
+        let style = ResolvedStyle::new(Color::Red, Color::Black, Default::default());
+        render.apply_style(&style)?;
+        render.text(Point { x: 0, y: 0 }, "ab")?;
+        render.apply_style(&style)?;
+        render.text(Point { x: 2, y: 0 }, "c")?;
+        render.text(Point { x: 0, y: 1 }, "d")?;
+
+        let mut expected = Vec::new();
+        expected
+            .queue(style::SetAttribute(style::Attribute::Reset))?
+            .queue(style::SetForegroundColor(style::Color::Red))?
+            .queue(style::SetBackgroundColor(style::Color::Black))?
+            .queue(ccursor::MoveTo(0, 0))?
+            .queue(style::Print("ab"))?
+            .queue(style::Print("c"))?
+            .queue(ccursor::MoveTo(0, 1))?
+            .queue(style::Print("d"))?;
+        assert_eq!(render.buf, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn render_moves_the_cursor_after_wide_graphemes() -> io::Result<()> {
+        let mut render = CrosstermRender::default();
+        render.set_synchronized_output(false);
+        render.text(Point { x: 0, y: 0 }, "a界b")?;
+        render.text(Point { x: 4, y: 0 }, "c")?;
+
+        let mut expected = Vec::new();
+        expected
+            .queue(ccursor::MoveTo(0, 0))?
+            .queue(style::Print("a"))?
+            .queue(style::Print("界"))?
+            .queue(ccursor::MoveTo(3, 0))?
+            .queue(style::Print("b"))?
+            .queue(style::Print("c"))?;
+        assert_eq!(render.buf, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn synchronized_output_toggle_wraps_frames() -> Result<()> {
+        let mut render = CrosstermRender::default();
+        render.reset()?;
+        assert_eq!(render.buf, b"\x1b[?2026h");
+
+        let mut render = CrosstermRender::default();
+        render.set_synchronized_output(false);
+        render.reset()?;
+        assert!(render.buf.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn event_source_keeps_the_latest_of_a_resize_burst() {
+        let (tx, rx) = mpsc::channel();
+        let mut events = EventSource::new(rx, None);
+        for w in 10..20 {
+            tx.send(Event::Resize(Size::new(w, 5))).unwrap();
+        }
+        tx.send(Event::FocusGained).unwrap();
+        tx.send(Event::Resize(Size::new(30, 5))).unwrap();
+
+        assert!(matches!(events.next(), Ok(Event::Resize(size)) if size.w == 19));
+        assert!(matches!(events.next(), Ok(Event::FocusGained)));
+
+        let mut events = EventSource {
+            resize_debounce: Some(Duration::from_millis(1)),
+            ..events
+        };
+        assert!(matches!(events.next(), Ok(Event::Resize(size)) if size.w == 30));
+    }
+
+    #[test]
+    fn inline_screen_limits_size_and_mouse_rows() {
+        let screen = Screen::Inline(5);
+        let Some(Event::Resize(size)) = screen_event(screen, 10, Event::Resize(Size::new(80, 40)))
+        else {
+            panic!("resize should pass through");
+        };
+        assert_eq!(size, Size::new(80, 5));
+        assert_eq!(screen_size(Screen::Alternate, size), size);
+
+        let click = |y| {
+            Event::Mouse(mouse::MouseEvent {
+                action: mouse::Action::Down,
+                button: mouse::Button::Left,
+                modifiers: key::Mods::default(),
+                location: Point { x: 3, y },
+            })
+        };
+        assert!(matches!(
+            screen_event(screen, 10, click(12)),
+            Some(Event::Mouse(m)) if m.location.y == 2
+        ));
+        assert!(screen_event(screen, 10, click(7)).is_none());
+        assert!(screen_event(screen, 10, click(15)).is_none());
+    }
+
+    #[test]
+    fn render_rows_follow_the_attached_screen_origin() -> io::Result<()> {
+        let mut render = CrosstermRender::default();
+        render.set_synchronized_output(false);
+        let control = CrosstermControl::default().with_render(&mut render);
+        control.origin.store(4, Ordering::Relaxed);
+        render.text(Point { x: 1, y: 2 }, "a")?;
+
+        let mut expected = Vec::new();
+        expected
+            .queue(ccursor::MoveTo(1, 6))?
+            .queue(style::Print("a"))?;
+        assert_eq!(render.buf, expected);
+        Ok(())
+    }
+
     #[test]
     fn positioned_text_runs_keep_combining_graphemes_in_run() {
         let runs = positioned_text_runs(Point { x: 1, y: 3 }, "e\u{0301}x");
@@ -0,0 +1,326 @@
+use std::{
+    io::{self, Stderr, Stdout, Write},
+    sync::{
+        Mutex, MutexGuard, PoisonError,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use crossterm::{
+    ExecutableCommand, QueueableCommand, cursor as ccursor, event as cevent, terminal,
+};
+
+/// What the guard currently holding the terminal has to undo. Panic hooks and signal
+/// handling restore the terminal from here, since they cannot reach the guard.
+static HELD: Mutex<Option<Held>> = Mutex::new(None);
+
+/// Whether the terminal cursor color has been changed from the terminal default.
+static CURSOR_COLORED: AtomicBool = AtomicBool::new(false);
+
+/// Sequence that resets the cursor color to the terminal default (OSC 112).
+pub(crate) const RESET_CURSOR_COLOR: &[u8] = b"\x1b]112\x07";
+
+/// Whether the terminal cursor shape has been changed from the terminal default.
+static CURSOR_SHAPED: AtomicBool = AtomicBool::new(false);
+
+/// Sequence that resets the cursor shape to the terminal default (DECSCUSR 0).
+const RESET_CURSOR_SHAPE: &[u8] = b"\x1b[0 q";
+
+/// The stream terminal output is written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputStream {
+    /// Standard output. Use this when stderr is redirected.
+    Stdout,
+    /// Standard error, leaving stdout free for the program's result.
+    #[default]
+    Stderr,
+}
+
+impl OutputStream {
+    /// Return a writer for this stream.
+    pub(crate) fn writer(self) -> Output {
+        match self {
+            Self::Stdout => Output::Stdout(io::stdout()),
+            Self::Stderr => Output::Stderr(io::stderr()),
+        }
+    }
+}
+
+/// A writer over either standard stream.
+#[derive(Debug)]
+pub(crate) enum Output {
+    /// Standard output.
+    Stdout(Stdout),
+    /// Standard error.
+    Stderr(Stderr),
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Stdout(fp) => fp.write(buf),
+            Self::Stderr(fp) => fp.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Stdout(fp) => fp.flush(),
+            Self::Stderr(fp) => fp.flush(),
+        }
+    }
+}
+
+/// Where the UI is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Screen {
+    /// The full alternate screen, leaving the shell scrollback untouched.
+    #[default]
+    Alternate,
+    /// A region of the given height below the cursor on the main screen, scrolling the
+    /// terminal up if there is not enough room. The region is cleared on exit.
+    Inline(u16),
+}
+
+/// Terminal changes made by a guard that restoring has to undo.
+#[derive(Debug, Clone, Copy)]
+struct Held {
+    /// Stream the guard writes control output to.
+    output: OutputStream,
+    /// First terminal row of the inline region, or `None` on the alternate screen.
+    inline: Option<u16>,
+    /// Whether keyboard enhancement flags were pushed.
+    keyboard_enhanced: bool,
+}
+
+/// Lock the state of the guard holding the terminal. A panic while the lock was held
+/// leaves the state usable, so poisoning is ignored.
+fn held() -> MutexGuard<'static, Option<Held>> {
+    HELD.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// RAII ownership of the terminal for a crossterm session.
+///
+/// Entering switches to raw mode with mouse capture and a hidden cursor, drawing on the
+/// alternate screen or an inline region. The terminal is restored when the guard is
+/// dropped, when [`restore`] is called, from the runloop's panic hook through
+/// [`restore_active`], and on `SIGTERM` or `SIGHUP`, so the shell is left usable
+/// however the application exits.
+///
+/// [`restore`]: TerminalGuard::restore
+/// [`restore_active`]: TerminalGuard::restore_active
+#[derive(Debug)]
+pub struct TerminalGuard {
+    /// Handle used for control output.
+    fp: Output,
+    /// Whether this guard still has to restore the terminal.
+    active: bool,
+    /// First terminal row of the screen: the top of the inline region, or 0 on the
+    /// alternate screen.
+    origin: u16,
+}
+
+impl TerminalGuard {
+    /// Take over the terminal, optionally pushing keyboard enhancement flags for
+    /// disambiguated escape codes.
+    ///
+    /// Inline mode queries the cursor position, so enter before any other thread starts
+    /// reading terminal events. If any step fails, the steps already taken are undone
+    /// before the error returns.
+    pub fn enter(
+        output: OutputStream,
+        screen: Screen,
+        keyboard_enhancements: bool,
+    ) -> io::Result<Self> {
+        Self::enter_with(output, screen, keyboard_enhancements, true)
+    }
+
+    /// Take over the terminal. With `query_cursor` false, an inline region is placed at
+    /// the bottom of the screen instead of below the cursor.
+    pub(super) fn enter_with(
+        output: OutputStream,
+        screen: Screen,
+        keyboard_enhancements: bool,
+        query_cursor: bool,
+    ) -> io::Result<Self> {
+        signals::install();
+        let mut fp = output.writer();
+        let rows = terminal::size()?.1;
+        let screen = match screen {
+            Screen::Inline(height) => Screen::Inline(height.clamp(1, rows.max(1))),
+            Screen::Alternate => Screen::Alternate,
+        };
+        if let Screen::Inline(height) = screen {
+            if !query_cursor {
+                fp.queue(ccursor::MoveTo(0, rows.saturating_sub(1)))?;
+            }
+            for _ in 1..height {
+                fp.queue(crossterm::style::Print("\n"))?;
+            }
+            if height > 1 {
+                fp.queue(ccursor::MoveUp(height - 1))?;
+            }
+            fp.execute(ccursor::MoveToColumn(0))?;
+        }
+        terminal::enable_raw_mode()?;
+        *held() = Some(Held {
+            output,
+            inline: None,
+            keyboard_enhanced: false,
+        });
+        let mut guard = Self {
+            fp,
+            active: true,
+            origin: 0,
+        };
+        match screen {
+            Screen::Alternate => {
+                guard.fp.execute(terminal::EnterAlternateScreen)?;
+            }
+            Screen::Inline(height) => {
+                guard.origin = if query_cursor {
+                    ccursor::position()?.1
+                } else {
+                    rows.saturating_sub(height)
+                };
+                if let Some(held) = held().as_mut() {
+                    held.inline = Some(guard.origin);
+                }
+            }
+        }
+        guard.fp.execute(cevent::EnableMouseCapture)?;
+        guard.fp.execute(ccursor::Hide)?;
+        if keyboard_enhancements {
+            guard.fp.execute(cevent::PushKeyboardEnhancementFlags(
+                cevent::KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES,
+            ))?;
+            if let Some(held) = held().as_mut() {
+                held.keyboard_enhanced = true;
+            }
+        }
+        Ok(guard)
+    }
+
+    /// Restore the terminal. Later calls, and the eventual drop, do nothing.
+    pub fn restore(&mut self) -> io::Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+        self.active = false;
+        restore_terminal(&mut self.fp)
+    }
+
+    /// Restore the terminal held by whichever guard is active, without access to it.
+    ///
+    /// This is for panic hooks and other code that cannot reach the guard. Errors are
+    /// ignored, and nothing happens if no guard is active.
+    pub fn restore_active() {
+        let Some(state) = *held() else {
+            return;
+        };
+        drop(restore_terminal(&mut state.output.writer()));
+    }
+
+    /// Return the first terminal row of this guard's screen: the top of the inline
+    /// region, or 0 on the alternate screen.
+    pub fn origin(&self) -> u16 {
+        self.origin
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        drop(self.restore());
+    }
+}
+
+/// Record whether the terminal cursor color differs from the terminal default, so that
+/// restoring the terminal resets it.
+pub(crate) fn set_cursor_colored(colored: bool) {
+    CURSOR_COLORED.store(colored, Ordering::SeqCst);
+}
+
+/// Whether the terminal cursor color differs from the terminal default.
+pub(crate) fn cursor_colored() -> bool {
+    CURSOR_COLORED.load(Ordering::SeqCst)
+}
+
+/// Record that the terminal cursor shape was changed, so that restoring the terminal
+/// resets it.
+pub(crate) fn set_cursor_shaped(shaped: bool) {
+    CURSOR_SHAPED.store(shaped, Ordering::SeqCst);
+}
+
+/// Undo the terminal changes made by [`TerminalGuard::enter`] if they are still in effect.
+fn restore_terminal(fp: &mut Output) -> io::Result<()> {
+    let Some(held) = held().take() else {
+        return Ok(());
+    };
+    if held.keyboard_enhanced {
+        fp.execute(cevent::PopKeyboardEnhancementFlags)?;
+    }
+    if let Some(origin) = held.inline {
+        fp.queue(ccursor::MoveTo(0, origin))?;
+        fp.execute(terminal::Clear(terminal::ClearType::FromCursorDown))?;
+    } else {
+        fp.execute(terminal::LeaveAlternateScreen)?;
+    }
+    fp.execute(cevent::DisableMouseCapture)?;
+    if CURSOR_COLORED.swap(false, Ordering::SeqCst) {
+        fp.write_all(RESET_CURSOR_COLOR)?;
+    }
+    if CURSOR_SHAPED.swap(false, Ordering::SeqCst) {
+        fp.write_all(RESET_CURSOR_SHAPE)?;
+    }
+    fp.execute(ccursor::Show)?;
+    terminal::disable_raw_mode()
+}
+
+/// Termination signal handling.
+#[cfg(unix)]
+mod signals {
+    use std::{sync::Once, thread};
+
+    use signal_hook::{
+        consts::{SIGHUP, SIGTERM},
+        iterator::Signals,
+        low_level,
+    };
+
+    use super::TerminalGuard;
+
+    /// Start a thread that restores the terminal on `SIGTERM` or `SIGHUP`, then ends the
+    /// process with the signal's default action. Installed once per process.
+    pub(super) fn install() {
+        static INSTALLED: Once = Once::new();
+        INSTALLED.call_once(|| {
+            let mut signals = match Signals::new([SIGTERM, SIGHUP]) {
+                Ok(signals) => signals,
+                Err(e) => {
+                    tracing::error!("terminal restore signal registration failed: {e}");
+                    return;
+                }
+            };
+            let spawned = thread::Builder::new()
+                .name("canopy-signals".into())
+                .spawn(move || {
+                    if let Some(signal) = signals.forever().next() {
+                        TerminalGuard::restore_active();
+                        if let Err(e) = low_level::emulate_default_handler(signal) {
+                            tracing::error!("signal {signal} default action failed: {e}");
+                        }
+                    }
+                });
+            if let Err(e) = spawned {
+                tracing::error!("terminal restore signal thread failed to start: {e}");
+            }
+        });
+    }
+}
+
+/// Termination signals are not handled on this platform.
+#[cfg(not(unix))]
+mod signals {
+    /// Do nothing.
+    pub(super) fn install() {}
+}
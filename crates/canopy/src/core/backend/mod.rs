@@ -1,8 +1,14 @@
 /// Crossterm backend implementation.
 pub mod crossterm;
+/// Terminal ownership guard shared by the runloop, panic hook, and signal handlers.
+mod guard;
+/// Event recording and deterministic replay.
+pub mod replay;
 use std::{fmt::Debug, ptr::NonNull};
 
-use crate::error::Result;
+pub use guard::{OutputStream, Screen, TerminalGuard};
+
+use crate::{error::Result, geom::Size};
 
 /// A handle for controlling our rendering back-end. The primary use is to
 /// suspend and resume rendering to permit us to fork out to another process
@@ -15,6 +21,14 @@ pub trait BackendControl: Debug {
     /// Stop the backend renderer, releasing control of the terminal.
     fn stop(&mut self) -> Result<()>;
 
+    /// Suspend the process for job control, releasing the terminal until the process is
+    /// continued. Returns the terminal size after resuming, since the terminal may have
+    /// been resized while the process was stopped. Backends that do not support job
+    /// control ignore the request and return `None`.
+    fn suspend(&mut self) -> Result<Option<Size>> {
+        Ok(None)
+    }
+
     /// Stop the render backend.
     fn exit(&mut self, _code: i32) {
         let _ = self.stop().ok();
@@ -0,0 +1,371 @@
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Canopy,
+    core::clock::Clock,
+    error::{Error, Result},
+    event::{Event, key, mouse},
+    geom::{Point, Size},
+    render::RenderBackend,
+};
+
+/// Serialized form of an event that arrives from the terminal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+enum Input {
+    /// A keystroke.
+    Key(key::Key),
+    /// A mouse action at a screen location.
+    Mouse {
+        /// Mouse action type.
+        action: mouse::Action,
+        /// Mouse button.
+        button: mouse::Button,
+        /// Keyboard modifiers.
+        modifiers: key::Mods,
+        /// Screen column.
+        x: u32,
+        /// Screen row.
+        y: u32,
+    },
+    /// Terminal resize.
+    Resize {
+        /// New width.
+        w: u32,
+        /// New height.
+        h: u32,
+    },
+    /// Terminal gained focus.
+    FocusGained,
+    /// Terminal lost focus.
+    FocusLost,
+    /// Pasted text.
+    Paste(String),
+}
+
+impl Input {
+    /// Convert a terminal event. Events Canopy generates internally are not recorded.
+    fn from_event(event: &Event) -> Option<Self> {
+        Some(match event {
+            Event::Key(k) => Self::Key(*k),
+            Event::Mouse(m) => Self::Mouse {
+                action: m.action,
+                button: m.button,
+                modifiers: m.modifiers,
+                x: m.location.x,
+                y: m.location.y,
+            },
+            Event::Resize(size) => Self::Resize {
+                w: size.w,
+                h: size.h,
+            },
+            Event::FocusGained => Self::FocusGained,
+            Event::FocusLost => Self::FocusLost,
+            Event::Paste(text) => Self::Paste(text.clone()),
+            Event::Poll(_)
+            | Event::Timers(_)
+            | Event::Timer(_)
+            | Event::Scroll(_)
+            | Event::Wake
+            | Event::App(_) => {
+                return None;
+            }
+        })
+    }
+
+    /// Convert back into an event.
+    fn into_event(self) -> Event {
+        match self {
+            Self::Key(k) => Event::Key(k),
+            Self::Mouse {
+                action,
+                button,
+                modifiers,
+                x,
+                y,
+            } => Event::Mouse(mouse::MouseEvent {
+                action,
+                button,
+                modifiers,
+                location: Point { x, y },
+            }),
+            Self::Resize { w, h } => Event::Resize(Size::new(w, h)),
+            Self::FocusGained => Event::FocusGained,
+            Self::FocusLost => Event::FocusLost,
+            Self::Paste(text) => Event::Paste(text),
+        }
+    }
+}
+
+/// One line of a recording file.
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    /// Microseconds since the start of the recording.
+    at: u64,
+    /// The recorded input.
+    input: Input,
+}
+
+/// Writes terminal events to a file as JSON lines, one event per line.
+///
+/// Each line is flushed as it is written, so a recording survives a crash.
+pub struct Recorder {
+    /// Destination file.
+    out: BufWriter<File>,
+    /// Start of the recording.
+    start: Instant,
+}
+
+impl Recorder {
+    /// Create a recording at `path`, starting with the terminal's current size.
+    pub fn create(path: &Path, size: Size) -> Result<Self> {
+        let mut recorder = Self {
+            out: BufWriter::new(File::create(path)?),
+            start: Instant::now(),
+        };
+        recorder.record(&Event::Resize(size))?;
+        Ok(recorder)
+    }
+
+    /// Record an event. Internal events such as polls and timers are skipped.
+    pub fn record(&mut self, event: &Event) -> Result<()> {
+        let Some(input) = Input::from_event(event) else {
+            return Ok(());
+        };
+        let entry = Entry {
+            at: self.start.elapsed().as_micros() as u64,
+            input,
+        };
+        let line = serde_json::to_string(&entry)
+            .map_err(|err| Error::Internal(format!("event encoding failed: {err}")))?;
+        writeln!(self.out, "{line}")?;
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+/// A sequence of recorded events with their offsets from the start of the session.
+#[derive(Debug, Clone, Default)]
+pub struct Recording {
+    /// Events in the order they arrived.
+    events: Vec<(Duration, Event)>,
+}
+
+impl Recording {
+    /// Load a recording written by [`Recorder`].
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Parse recording text. Blank lines are ignored.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut events = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: Entry = serde_json::from_str(line)
+                .map_err(|err| Error::Invalid(format!("recording line {}: {err}", index + 1)))?;
+            events.push((Duration::from_micros(entry.at), entry.input.into_event()));
+        }
+        Ok(Self { events })
+    }
+
+    /// Return the recorded events with their offsets.
+    pub fn events(&self) -> &[(Duration, Event)] {
+        &self.events
+    }
+
+    /// Feed every event to the app in order, rendering after each one.
+    ///
+    /// The app is switched to a virtual clock, which advances to each event's offset
+    /// before the event is delivered. Polls and timers due in between run as they did
+    /// while recording, and the replay does not wait on wall-clock time. Returns the exit
+    /// code if the app requested an exit, which stops the replay.
+    pub fn replay<R: RenderBackend>(&self, cnpy: &mut Canopy, be: &mut R) -> Result<Option<i32>> {
+        if cnpy.core.clock == Clock::Real {
+            cnpy.use_virtual_clock();
+        }
+        let mut elapsed = Duration::ZERO;
+        for (at, event) in &self.events {
+            cnpy.advance_clock(at.saturating_sub(elapsed))?;
+            elapsed = elapsed.max(*at);
+            cnpy.event(event.clone())?;
+            cnpy.service_automation();
+            if let Some(code) = cnpy.core.take_exit_request() {
+                return Ok(Some(code));
+            }
+            cnpy.render(be)?;
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+    use crate::{
+        Context, EventOutcome, Loader, ReadContext, Widget, layout::Layout, state::NodeName,
+        testing::harness::Harness,
+    };
+
+    struct KeyLog {
+        keys: Vec<String>,
+    }
+
+    impl Widget for KeyLog {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+            true
+        }
+
+        fn on_event(&mut self, event: &Event, _ctx: &mut dyn Context) -> Result<EventOutcome> {
+            match event {
+                Event::Key(k) => self.keys.push(k.to_string()),
+                Event::Paste(text) => self.keys.push(format!("paste:{text}")),
+                _ => return Ok(EventOutcome::Ignore),
+            }
+            Ok(EventOutcome::Handle)
+        }
+
+        fn name(&self) -> NodeName {
+            NodeName::convert("key_log")
+        }
+    }
+
+    impl Loader for KeyLog {}
+
+    struct Metronome {
+        ticks: u32,
+        ticks_at_keys: Vec<u32>,
+    }
+
+    impl Widget for Metronome {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+            true
+        }
+
+        fn poll(&mut self, ctx: &mut dyn Context) -> Option<Duration> {
+            ctx.set_timer(1, Duration::from_millis(100), true);
+            None
+        }
+
+        fn on_event(&mut self, event: &Event, _ctx: &mut dyn Context) -> Result<EventOutcome> {
+            match event {
+                Event::Timer(1) => self.ticks += 1,
+                Event::Key(_) => self.ticks_at_keys.push(self.ticks),
+                _ => return Ok(EventOutcome::Ignore),
+            }
+            Ok(EventOutcome::Handle)
+        }
+    }
+
+    impl Loader for Metronome {}
+
+    #[test]
+    fn recorded_events_round_trip() -> Result<()> {
+        let path = env::temp_dir().join(format!("canopy-replay-{}.jsonl", process::id()));
+        let mut recorder = Recorder::create(&path, Size::new(20, 4))?;
+        recorder.record(&Event::Key(key::Ctrl + 'a'))?;
+        recorder.record(&Event::Poll(Vec::new()))?;
+        recorder.record(&Event::Mouse(mouse::MouseEvent {
+            action: mouse::Action::Down,
+            button: mouse::Button::Left,
+            modifiers: key::Empty,
+            location: Point { x: 3, y: 2 },
+        }))?;
+        recorder.record(&Event::Paste("hi".into()))?;
+        drop(recorder);
+
+        let recording = Recording::load(&path);
+        let _ignored = fs::remove_file(&path);
+        let inputs: Vec<Input> = recording?
+            .events()
+            .iter()
+            .filter_map(|(_, event)| Input::from_event(event))
+            .collect();
+        assert_eq!(
+            inputs,
+            vec![
+                Input::Resize { w: 20, h: 4 },
+                Input::Key(key::Ctrl + 'a'),
+                Input::Mouse {
+                    action: mouse::Action::Down,
+                    button: mouse::Button::Left,
+                    modifiers: key::Empty,
+                    x: 3,
+                    y: 2,
+                },
+                Input::Paste("hi".into()),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn replay_feeds_events_in_order() -> Result<()> {
+        let mut harness = Harness::builder(KeyLog { keys: Vec::new() })
+            .size(10, 2)
+            .build()?;
+        let root = harness.root;
+        harness.canopy.core.set_focus(root);
+        let recording = Recording::parse(
+            "{\"at\":0,\"input\":{\"Resize\":{\"w\":12,\"h\":3}}}\n\
+             \n\
+             {\"at\":10,\"input\":{\"Key\":{\"mods\":{\"shift\":false,\"ctrl\":false,\"alt\":false},\"key\":{\"Char\":\"j\"}}}}\n\
+             {\"at\":20,\"input\":{\"Paste\":\"abc\"}}\n",
+        )?;
+        assert_eq!(
+            recording.replay(&mut harness.canopy, &mut harness.backend)?,
+            None
+        );
+        assert_eq!(harness.buf().size(), Size::new(12, 3));
+        let keys = harness.with_root_widget(|log: &mut KeyLog| log.keys.clone());
+        assert_eq!(keys, vec!["j", "paste:abc"]);
+        Ok(())
+    }
+
+    #[test]
+    fn replay_runs_timers_up_to_each_event_offset() -> Result<()> {
+        let mut harness = Harness::builder(Metronome {
+            ticks: 0,
+            ticks_at_keys: Vec::new(),
+        })
+        .size(10, 2)
+        .build()?;
+        let root = harness.root;
+        harness.canopy.core.set_focus(root);
+        let key =
+            "{\"mods\":{\"shift\":false,\"ctrl\":false,\"alt\":false},\"key\":{\"Char\":\"j\"}}";
+        let recording = Recording::parse(&format!(
+            "{{\"at\":0,\"input\":{{\"Resize\":{{\"w\":10,\"h\":2}}}}}}\n\
+             {{\"at\":250000,\"input\":{{\"Key\":{key}}}}}\n\
+             {{\"at\":1050000,\"input\":{{\"Key\":{key}}}}}\n",
+        ))?;
+        recording.replay(&mut harness.canopy, &mut harness.backend)?;
+        let ticks = harness.with_root_widget(|m: &mut Metronome| m.ticks_at_keys.clone());
+        assert_eq!(ticks, vec![2, 10]);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_reports_bad_lines() {
+        let err = Recording::parse("{\"at\":0,\"input\":\"FocusLost\"}\nnot json\n").unwrap_err();
+        assert!(err.to_string().contains("recording line 2"));
+    }
+}
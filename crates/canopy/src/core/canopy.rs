@@ -1,37 +1,54 @@
 use std::{
-    collections::{BTreeSet, HashMap},
+    any::Any,
+    collections::{BTreeMap, BTreeSet, HashMap},
     fs,
     io::Write,
-    path::Path as FsPath,
-    sync::mpsc,
-    time::Duration,
+    mem,
+    path::{Path as FsPath, PathBuf},
+    sync::{Arc, mpsc},
+    thread,
+    time::{Duration, Instant},
 };
 
 use comfy_table::{ContentArrangement, Table, presets::UTF8_FULL};
+#[cfg(feature = "dylib")]
+use libloading::Library;
 
-use super::{inputmap, poll::Poller, termbuf::TermBuf};
+use super::{anim, clock::Clock, damage::union_rect, inputmap, poll::Poller, termbuf::TermBuf};
+#[cfg(feature = "dylib")]
+use crate::core::plugin::{PLUGIN_ENTRY_SYMBOL, PluginEntry};
 use crate::{
     backend::BackendControl,
-    commands::{self, CommandDispatchKind},
+    commands::{self, CommandDispatchKind, CommandScopeFrame},
     core::{
         Core, NodeId, TypedId,
+        a11y::Announcer,
         context::CoreViewContext,
         dump::dump_with_focus,
         fixture::{Fixture, FixtureInfo},
+        graft::Graft,
         help,
+        hooks::HookFlow,
+        isolate,
+        macros::{MAX_REPLAY_DEPTH, MacroStep},
+        message::{MAX_MESSAGE_ROUNDS, MessageTarget},
+        persist::{Session, SessionRequest, session_file},
+        plugin::Plugin,
         style::Effect,
+        text,
+        undo::UndoManager,
         view::View,
         world::WidgetOperation,
     },
     cursor,
-    error::{self, Result},
-    event::{Event, key, mouse},
-    geom::{Point, Rect, RectI32, Size},
+    error::{self, RenderFailure, Result},
+    event::{AppEvent, Event, TimerId, key, mouse},
+    geom::{Line, Point, Rect, RectI32, Size},
     layout::Display,
     path::Path,
-    render::{Render, RenderBackend},
+    render::{Render, RenderBackend, export},
     script,
-    style::{ResolvedStyle, StyleManager, StyleMap, solarized},
+    style::{ResolvedStyle, StyleEffect, StyleManager, StyleMap, effects, solarized},
     widget::{EventOutcome, Widget},
 };
 
@@ -49,6 +66,17 @@ pub struct Canopy {
     /// The poller is responsible for tracking nodes that have pending poll events.
     poller: Poller,
 
+    /// Nodes that wrote outside their outer rect during the last render pass, with the
+    /// union of those writes in node-local coordinates.
+    pub(crate) overflows: Vec<(NodeId, Rect)>,
+
+    /// Whether writes outside a node's outer rect fail the render instead of being clipped.
+    strict_clipping: bool,
+
+    /// Whether a failing node render is recorded and shown as an overlay instead of
+    /// failing the frame.
+    catch_render_errors: bool,
+
     /// Root window size.
     pub(crate) root_size: Option<Size>,
 
@@ -62,6 +90,8 @@ pub struct Canopy {
     fixtures: HashMap<String, Fixture>,
     /// Input mapping table.
     pub(crate) keymap: inputmap::InputMap,
+    /// Keymap revision the binding catalog was captured at, once one has been requested.
+    binding_catalog_revision: Option<u64>,
     /// Trace for the most recent key or mouse routing pass.
     route_trace: Vec<RouteTraceEntry>,
 
@@ -69,6 +99,16 @@ pub struct Canopy {
     termbuf: Option<TermBuf>,
     /// Whether a render is pending after the most recent event.
     render_pending: bool,
+    /// Minimum interval between rendered frames, if rendering is rate limited.
+    frame_interval: Option<Duration>,
+    /// When the most recent frame was rendered.
+    last_frame: Option<Instant>,
+    /// How the focused widget's cursor is shown.
+    cursor_mode: cursor::CursorMode,
+    /// Cursors drawn into the cached terminal buffer, in screen coordinates.
+    last_cursors: Vec<cursor::Cursor>,
+    /// Cursor shown with the terminal cursor in hardware mode, in screen coordinates.
+    hardware_cursor: Option<cursor::Cursor>,
 
     /// Event sender channel.
     pub(crate) event_tx: mpsc::Sender<Event>,
@@ -81,6 +121,31 @@ pub struct Canopy {
 
     /// Style map used for rendering.
     style: StyleMap,
+    /// Style manager kept across renders so its resolution cache carries over.
+    styl: StyleManager,
+
+    /// Receiver for accessibility announcements.
+    announcer: Option<Box<dyn Announcer>>,
+    /// Description of the focused node at the most recent announcement.
+    last_announcement: Option<String>,
+
+    /// Independent applications embedded under `Graft` host nodes.
+    grafts: HashMap<NodeId, Self>,
+    /// When each graft host node is next scheduled to wake and drive its app.
+    graft_wakes: HashMap<NodeId, Instant>,
+
+    /// Named sessions saved during this run.
+    sessions: HashMap<String, Session>,
+    /// Directory that sessions are also written to and read from, if any.
+    session_dir: Option<PathBuf>,
+
+    /// Installed plugins, declared after the state they registered into so they
+    /// outlive it.
+    plugins: Vec<Box<dyn Plugin>>,
+    /// Plugin shared libraries, declared last so they stay loaded until everything
+    /// else, including the plugins themselves, has been dropped.
+    #[cfg(feature = "dylib")]
+    libraries: Vec<Library>,
 }
 
 /// A phase in key or mouse event routing.
@@ -171,6 +236,68 @@ impl AutomationHandle {
     }
 }
 
+/// Handle for pushing events into a live canopy runloop from another thread.
+///
+/// Every source, including the terminal, the poller, and automation, feeds the same
+/// channel, so events from all of them are handled in arrival order on the UI thread.
+#[derive(Clone)]
+pub struct EventSender {
+    /// Tag attached to pushed app events.
+    source: Arc<str>,
+    /// Runloop event channel.
+    tx: mpsc::Sender<Event>,
+}
+
+impl EventSender {
+    /// Return the source tag attached to pushed app events.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Push an app event carrying `payload`, tagged with this sender's source.
+    pub fn send(&self, payload: impl Any + Send + Sync) -> Result<()> {
+        self.send_event(Event::App(AppEvent {
+            source: Arc::clone(&self.source),
+            payload: Arc::new(payload),
+        }))
+    }
+
+    /// Push any event, such as a synthesized key press.
+    pub fn send_event(&self, event: Event) -> Result<()> {
+        self.tx
+            .send(event)
+            .map_err(|_| error::Error::RunLoop("event channel closed".into()))
+    }
+}
+
+/// Handle for sending messages to a node from another thread.
+///
+/// Messages are queued on the UI thread and delivered to the target's `handle_message`
+/// hook, like messages sent with `Context::send_to`.
+#[derive(Clone)]
+pub struct MessageSender {
+    /// Recipient of sent messages.
+    target: MessageTarget,
+    /// Handle used to queue messages on the UI thread.
+    automation: AutomationHandle,
+}
+
+impl MessageSender {
+    /// Return the recipient of sent messages.
+    pub fn target(&self) -> &MessageTarget {
+        &self.target
+    }
+
+    /// Send `payload` to the target. It is dropped if the target no longer resolves when
+    /// it is delivered.
+    pub fn send(&self, payload: impl Any + Send) -> Result<()> {
+        let target = self.target.clone();
+        self.automation.submit(Box::new(move |canopy| {
+            canopy.core.queue_message(target, Box::new(payload));
+        }))
+    }
+}
+
 /// Registered default binding script metadata.
 struct DefaultBindingsScript {
     /// Source text evaluated for this owner.
@@ -187,6 +314,17 @@ struct RenderTraversal<'a> {
     styl: &'a mut StyleManager,
     /// Accumulated style effects for the current subtree.
     effect_stack: &'a mut Vec<Effect>,
+    /// Screen region being rendered, which bounds unclipped nodes.
+    region: Rect,
+}
+
+impl RenderTraversal<'_> {
+    /// Unwind the style and effect state pushed for a node whose subtree is skipped.
+    fn skip_node(&mut self, saved_len: usize) {
+        self.styl.set_state_layers(&[]);
+        self.styl.pop();
+        self.effect_stack.truncate(saved_len);
+    }
 }
 
 /// No-op backend used to refresh the offscreen terminal buffer for inspection.
@@ -278,26 +416,46 @@ impl Canopy {
     pub fn new() -> Self {
         let (tx, rx) = mpsc::channel();
         let (automation_tx, automation_rx) = mpsc::channel();
-        let core = Core::new();
+        let mut core = Core::new();
+        core.event_tx = Some(tx.clone());
         Self {
             last_render_focus_gen: core.focus_gen,
             last_focus_path: Vec::new(),
             poller: Poller::new(tx.clone()),
+            overflows: Vec::new(),
+            strict_clipping: false,
+            catch_render_errors: false,
             event_tx: tx,
             event_rx: Some(rx),
             automation_tx,
             automation_rx,
             keymap: inputmap::InputMap::new(),
+            binding_catalog_revision: None,
             route_trace: Vec::new(),
             script_host: script::ScriptHost::new(),
             script_api_text: None,
             default_bindings: HashMap::new(),
             fixtures: HashMap::new(),
             style: solarized::solarized_dark(),
+            styl: StyleManager::default(),
             root_size: None,
             termbuf: None,
             render_pending: true,
+            frame_interval: None,
+            last_frame: None,
+            cursor_mode: cursor::CursorMode::default(),
+            last_cursors: Vec::new(),
+            hardware_cursor: None,
+            announcer: None,
+            last_announcement: None,
+            grafts: HashMap::new(),
+            graft_wakes: HashMap::new(),
+            sessions: HashMap::new(),
+            session_dir: None,
             core,
+            plugins: Vec::new(),
+            #[cfg(feature = "dylib")]
+            libraries: Vec::new(),
         }
     }
 
@@ -309,6 +467,35 @@ impl Canopy {
         }
     }
 
+    /// Return a sender that pushes events tagged with `source` into this app's runloop.
+    pub fn event_sender(&self, source: &str) -> EventSender {
+        EventSender {
+            source: source.into(),
+            tx: self.event_tx.clone(),
+        }
+    }
+
+    /// Return a sender that delivers messages to `target` from any thread.
+    pub fn message_sender(&self, target: impl Into<MessageTarget>) -> MessageSender {
+        MessageSender {
+            target: target.into(),
+            automation: self.automation_handle(),
+        }
+    }
+
+    /// Register an event source such as a file watcher, socket, or child process.
+    ///
+    /// `producer` runs on its own thread with a sender tagged `source`. The source ends
+    /// when `producer` returns; sends fail once the runloop has shut down.
+    pub fn add_event_source(
+        &self,
+        source: &str,
+        producer: impl FnOnce(EventSender) + Send + 'static,
+    ) -> thread::JoinHandle<()> {
+        let sender = self.event_sender(source);
+        thread::spawn(move || producer(sender))
+    }
+
     /// Return the root node ID.
     pub fn root_id(&self) -> NodeId {
         self.core.root_id()
@@ -328,6 +515,30 @@ impl Canopy {
         self.core.set_children(root, vec![child.into()])
     }
 
+    /// Embed an independent app under a new detached [`Graft`] host node. Attach the
+    /// returned node to place the app in this tree; the app is dropped once the node is
+    /// removed.
+    ///
+    /// The grafted app's clock is driven by this app: its polls, timers, and deferred
+    /// work run as this app handles events, and this app wakes when the graft next has
+    /// something due.
+    pub fn graft(&mut self, mut app: Self) -> TypedId<Graft> {
+        app.core.clock = Clock::Virtual(self.core.clock.now());
+        let id = self.create_detached(Graft);
+        self.grafts.insert(id.into(), app);
+        id
+    }
+
+    /// Return the app grafted at a host node.
+    pub fn grafted(&self, node: impl Into<NodeId>) -> Option<&Self> {
+        self.grafts.get(&node.into())
+    }
+
+    /// Return the app grafted at a host node for mutation.
+    pub fn grafted_mut(&mut self, node: impl Into<NodeId>) -> Option<&mut Self> {
+        self.grafts.get_mut(&node.into())
+    }
+
     /// Return the active style map.
     pub fn style(&self) -> &StyleMap {
         &self.style
@@ -526,11 +737,18 @@ impl Canopy {
         self.script_host.take_assertions()
     }
 
-    /// Evaluate a Luau config file from disk.
-    pub fn run_config(&mut self, path: &FsPath) -> Result<()> {
-        let source = fs::read_to_string(path)
-            .map_err(|err| error::Error::Invalid(format!("config read failed: {err}")))?;
-        self.eval_script(&source)
+    /// Evaluate a user Luau script file from disk, such as a config file.
+    ///
+    /// Functions the file defines stay reachable through any bindings it registers. Read and
+    /// evaluation errors are logged, so they appear in the inspector logs, and returned.
+    pub fn load_script_file(&mut self, path: &FsPath) -> Result<()> {
+        let result = fs::read_to_string(path)
+            .map_err(error::Error::from)
+            .and_then(|source| self.eval_script(&source));
+        if let Err(err) = &result {
+            tracing::error!("script file {} failed: {err}", path.display());
+        }
+        result
     }
 
     /// Remove a binding by ID. Returns true if a binding was removed.
@@ -599,6 +817,26 @@ impl Canopy {
         self.keymap.bindings_matching_path(mode, path)
     }
 
+    /// List every binding in every input mode, sorted by mode.
+    pub fn binding_catalog(&self) -> Vec<super::help::CatalogBinding> {
+        self.keymap
+            .bindings()
+            .into_iter()
+            .map(|b| super::help::CatalogBinding {
+                input: b.info.input,
+                mode: b.mode.to_string(),
+                path_filter: b.info.path_filter.to_string(),
+                label: super::help::binding_label(
+                    b.info.target,
+                    &self.core.commands,
+                    |sid| self.script_host.script_source(sid),
+                    |id| self.script_host.function_label(id),
+                ),
+                target: b.info.target.clone(),
+            })
+            .collect()
+    }
+
     /// Return the active input mode.
     pub fn input_mode(&self) -> &str {
         self.keymap.current_mode()
@@ -645,11 +883,79 @@ impl Canopy {
         Ok(())
     }
 
+    /// Install a plugin and run its loader. Plugins install alongside loaders, before
+    /// [`Canopy::finalize_api`], and stay alive as long as the app. A plugin whose loader
+    /// fails is not installed, but keeps anything it registered before failing.
+    pub fn install_plugin(&mut self, plugin: Box<dyn Plugin>) -> Result<()> {
+        if self.script_host.is_finalized() {
+            return Err(error::Error::InvalidOperation(
+                "plugin installation is sealed after finalize_api()".into(),
+            ));
+        }
+        let name = plugin.name();
+        if name.trim().is_empty() {
+            return Err(error::Error::Invalid("plugin name cannot be empty".into()));
+        }
+        if self
+            .plugins
+            .iter()
+            .any(|installed| installed.name() == name)
+        {
+            return Err(error::Error::Invalid(format!(
+                "plugin {name} is already installed"
+            )));
+        }
+        plugin.load(self)?;
+        self.plugins.push(plugin);
+        Ok(())
+    }
+
+    /// Load a plugin from a shared library and install it with
+    /// [`Canopy::install_plugin`]. Once opened, the library stays loaded for the life of
+    /// the app, even if its plugin fails to install, so that anything it registered
+    /// keeps valid code to run.
+    ///
+    /// # Safety
+    ///
+    /// The library must export [`PLUGIN_ENTRY_SYMBOL`] with the [`PluginEntry`]
+    /// signature and be built by the same compiler against the same version of canopy.
+    /// Loading it runs its initialization code.
+    #[cfg(feature = "dylib")]
+    pub unsafe fn install_plugin_library(&mut self, path: &FsPath) -> Result<()> {
+        let failed = |err: libloading::Error| {
+            error::Error::Invalid(format!("plugin {}: {err}", path.display()))
+        };
+        // SAFETY: the caller guarantees the library is a compatible canopy plugin.
+        let library = unsafe { Library::new(path) }.map_err(failed)?;
+        let plugin = {
+            // SAFETY: the caller guarantees the entry symbol has the `PluginEntry` type.
+            let entry = unsafe { library.get::<PluginEntry>(PLUGIN_ENTRY_SYMBOL.as_bytes()) }
+                .map_err(failed)?;
+            *entry
+        };
+        self.libraries.push(library);
+        self.install_plugin(plugin())
+    }
+
+    /// Names of installed plugins, in installation order.
+    pub fn plugin_names(&self) -> Vec<&str> {
+        self.plugins.iter().map(|plugin| plugin.name()).collect()
+    }
+
+    /// Register `name` as an alias for a command string such as `editor::save()`.
+    ///
+    /// Aliases resolve through [`Context::dispatch_str`](crate::Context::dispatch_str).
+    pub fn alias(&mut self, name: &str, target: &str) -> Result<()> {
+        self.core.commands.alias(name, target)?;
+        Ok(())
+    }
+
     /// Finalize the script API surface for this app.
     pub fn finalize_api(&mut self) -> Result<()> {
         if self.script_host.is_finalized() {
             return Ok(());
         }
+        self.collect_command_keys()?;
         let default_binding_owners = self.default_binding_owners();
         let definitions = script::defs::render_definitions(
             &self.core.commands,
@@ -697,6 +1003,46 @@ impl Canopy {
         })
     }
 
+    /// Fold the key bindings declared with `#[command(key = "...")]` into each owner's
+    /// default bindings, after any registered script.
+    fn collect_command_keys(&mut self) -> Result<()> {
+        let mut scripts: BTreeMap<&'static str, String> = BTreeMap::new();
+        let mut specs: Vec<_> = self.core.commands.iter().map(|(_, spec)| spec).collect();
+        specs.sort_by_key(|spec| spec.id.0);
+        for spec in specs {
+            let CommandDispatchKind::Node { owner } = spec.dispatch else {
+                continue;
+            };
+            let name = spec.name;
+            let desc = spec.doc.short.unwrap_or(name);
+            for key in spec.keys {
+                scripts.entry(owner).or_default().push_str(&format!(
+                    "canopy.bind_with({key:?}, {{ path = {owner:?}, desc = {desc:?} }}, \
+                     function()\n    {owner}.{name}()\nend)\n"
+                ));
+            }
+        }
+        for (owner, script) in scripts {
+            if self.owner_has_default_bindings_command(owner) {
+                return Err(error::Error::Invalid(format!(
+                    "owner {owner} already defines a command named default_bindings"
+                )));
+            }
+            let entry = self
+                .default_bindings
+                .entry(owner.to_string())
+                .or_insert_with(|| DefaultBindingsScript {
+                    source: String::new(),
+                    script_id: None,
+                });
+            if !entry.source.is_empty() && !entry.source.ends_with('\n') {
+                entry.source.push('\n');
+            }
+            entry.source.push_str(&script);
+        }
+        Ok(())
+    }
+
     /// Return the set of owners with registered default binding scripts.
     fn default_binding_owners(&self) -> BTreeSet<String> {
         self.default_bindings.keys().cloned().collect()
@@ -867,6 +1213,103 @@ impl Canopy {
         }
     }
 
+    /// Enable or disable high-contrast rendering. Every resolved style is mapped to black
+    /// and white, and the next frame repaints the whole screen.
+    pub fn set_high_contrast(&mut self, enabled: bool) {
+        self.core.set_high_contrast(enabled);
+    }
+
+    /// Is high-contrast rendering enabled?
+    pub fn high_contrast(&self) -> bool {
+        self.core.high_contrast
+    }
+
+    /// Enable or disable reduced motion. Widgets read the flag through
+    /// `ReadContext::reduced_motion` and skip animation when it is set.
+    pub fn set_reduced_motion(&mut self, enabled: bool) {
+        self.core.reduced_motion = enabled;
+    }
+
+    /// Is reduced motion enabled?
+    pub fn reduced_motion(&self) -> bool {
+        self.core.reduced_motion
+    }
+
+    /// Set the duration of smooth scroll animations, or `None` to scroll instantly.
+    /// Page and follow-the-selection scrolls in Text, List, and the editor animate when
+    /// this is set, unless reduced motion is enabled.
+    pub fn set_smooth_scroll(&mut self, duration: Option<Duration>) {
+        self.core.smooth_scroll = duration;
+    }
+
+    /// Return the smooth scroll duration, if smooth scrolling is enabled.
+    pub fn smooth_scroll(&self) -> Option<Duration> {
+        self.core.smooth_scroll
+    }
+
+    /// Install an announcer. After each rendered frame, the focused node's accessibility
+    /// description is announced whenever it differs from the last announcement.
+    pub fn set_announcer(&mut self, announcer: impl Announcer + 'static) {
+        self.announcer = Some(Box::new(announcer));
+        self.last_announcement = None;
+    }
+
+    /// Describe the focused node for assistive technology. Nodes without accessibility
+    /// metadata defer to their nearest ancestor that has it, so an item inside a list is
+    /// described by the list. Returns `None` if nothing on the focus path has metadata.
+    pub fn focused_description(&self) -> Result<Option<String>> {
+        let mut current = self.core.focus;
+        while let Some(id) = current {
+            let accessible = self.core.with_widget_read(
+                id,
+                WidgetOperation::render("accessible"),
+                |w, core| w.accessible(&CoreViewContext::new(core, id)),
+            )?;
+            if let Some(accessible) = accessible {
+                return Ok(Some(accessible.describe()));
+            }
+            current = self.core.nodes.get(id).and_then(|n| n.parent);
+        }
+        Ok(None)
+    }
+
+    /// Announce the focused node's description if it changed since the last announcement.
+    fn announce_focus(&mut self) -> Result<()> {
+        if self.announcer.is_none() {
+            return Ok(());
+        }
+        let description = self.focused_description()?;
+        if description == self.last_announcement {
+            return Ok(());
+        }
+        if let Some(text) = &description
+            && let Some(announcer) = self.announcer.as_mut()
+            && let Err(err) = announcer.announce(text)
+        {
+            tracing::error!("accessibility announcement failed: {err}");
+        }
+        self.last_announcement = description;
+        Ok(())
+    }
+
+    /// Write the last rendered frame to `path` as plain text, and with styling to copies
+    /// of `path` with `ansi` and `html` appended. A `path` without an extension gets `txt`.
+    pub fn screenshot(&self, path: &FsPath) -> Result<()> {
+        let buf = self
+            .termbuf
+            .as_ref()
+            .ok_or_else(|| error::Error::InvalidOperation("no frame has been rendered".into()))?;
+        let plain = if path.extension().is_some() {
+            path.to_path_buf()
+        } else {
+            path.with_extension("txt")
+        };
+        fs::write(&plain, buf.screen_text() + "\n")?;
+        fs::write(path.with_added_extension("ansi"), buf.ansi_text() + "\n")?;
+        fs::write(path.with_added_extension("html"), export::to_html(buf))?;
+        Ok(())
+    }
+
     /// Build a diagnostic dump with tree, focus, and binding details.
     pub fn diagnostic_dump(&self, target: NodeId) -> String {
         let mut out = String::new();
@@ -942,15 +1385,109 @@ impl Canopy {
         out
     }
 
+    /// Make widget writes outside their node's outer rect fail the render with an error,
+    /// instead of being silently clipped. Nodes marked with `Context::set_unclipped` are
+    /// exempt. Useful in tests and debug builds to catch misbehaving widgets.
+    pub fn set_strict_clipping(&mut self, strict: bool) {
+        self.strict_clipping = strict;
+    }
+
+    /// Keep the UI running when a node's render or the layout pass fails. A failing node
+    /// and its subtree are skipped, the rest of the frame renders, and an overlay shows the
+    /// node path and error until `dismiss_render_failure` is called. Layout failures are
+    /// reported against the root, with the failing node named in the message. Only the
+    /// first failure is kept.
+    pub fn set_catch_render_errors(&mut self, catch: bool) {
+        self.catch_render_errors = catch;
+    }
+
+    /// Catch panics raised by a node's callbacks: rendering, event handlers, commands,
+    /// layout, and polls. The panicking node is marked failed, its subtree renders as an
+    /// error placeholder, and the panic message is logged as an error so it shows in the
+    /// Inspector logs. Failed nodes are not called again until `dismiss_render_failure`
+    /// retries them.
+    pub fn set_isolate_panics(&mut self, isolate: bool) {
+        self.core.isolate_panics = isolate;
+    }
+
+    /// Return the panic messages of nodes currently marked failed by panic isolation.
+    pub fn failed_nodes(&self) -> Vec<(NodeId, String)> {
+        self.core
+            .nodes
+            .iter()
+            .filter_map(|(id, node)| node.failed.clone().map(|message| (id, message)))
+            .collect()
+    }
+
+    /// Return the render failure currently shown in the error overlay, if any.
+    pub fn render_failure(&self) -> Option<&RenderFailure> {
+        self.core.render_failure.as_ref()
+    }
+
+    /// Dismiss the error overlay and clear panicked nodes. The next frame repaints the
+    /// screen and retries the failed nodes.
+    pub fn dismiss_render_failure(&mut self) {
+        self.core.dismiss_render_failure();
+    }
+
+    /// Set how the focused widget's cursor is shown. The default virtual mode draws the
+    /// cursor into the frame; hardware mode moves the terminal cursor instead.
+    pub fn set_cursor_mode(&mut self, mode: cursor::CursorMode) {
+        self.cursor_mode = mode;
+        self.render_pending = true;
+    }
+
+    /// Return how the focused widget's cursor is shown.
+    pub fn cursor_mode(&self) -> cursor::CursorMode {
+        self.cursor_mode
+    }
+
+    /// Limit rendering to at most `fps` frames per second. `None` renders after every event.
+    pub fn set_max_fps(&mut self, fps: Option<u32>) {
+        self.frame_interval = fps
+            .filter(|fps| *fps > 0)
+            .map(|fps| Duration::from_secs(1) / fps);
+    }
+
+    /// Return when a pending render may run, or `None` if no render is being deferred.
+    pub(crate) fn deferred_render_deadline(&self, now: Instant) -> Option<Instant> {
+        if !self.render_pending && !self.core.has_damage() {
+            return None;
+        }
+        let deadline = self.last_frame? + self.frame_interval?;
+        (deadline > now).then_some(deadline)
+    }
+
     /// Render the tree only if a render is pending.
     pub(crate) fn render_if_pending<R: RenderBackend>(&mut self, be: &mut R) -> Result<bool> {
-        if !self.render_pending {
+        self.refresh_binding_catalog();
+        if self.core.notify_observers()? {
+            self.render_pending = true;
+        }
+        self.damage_focus_change();
+        if !self.render_pending && !self.core.full_render_requested && !self.core.has_damage() {
             return Ok(false);
         }
-        self.render(be)?;
+        let damage_only = !self.render_pending;
+        self.render_frame(be, damage_only)?;
         Ok(true)
     }
 
+    /// Damage the nodes that gained or lost focus or focus-path status since the last
+    /// render, so their focus styles redraw.
+    fn damage_focus_change(&mut self) {
+        if !self.focus_changed() {
+            return;
+        }
+        let path = self.core.focus_path_ids();
+        let state = |path: &[NodeId], id| (path.last() == Some(&id), path.contains(&id));
+        for &id in path.iter().chain(&self.last_focus_path) {
+            if state(&path, id) != state(&self.last_focus_path, id) {
+                self.core.damage_view(id);
+            }
+        }
+    }
+
     /// Refresh the cached terminal buffer without producing user-visible output.
     pub(crate) fn refresh_snapshot(&mut self) -> Result<()> {
         let mut backend = SnapshotBackend;
@@ -970,6 +1507,7 @@ impl Canopy {
 
     /// Register the poller channel.
     pub(crate) fn start_poller(&mut self, tx: mpsc::Sender<Event>) {
+        self.core.event_tx = Some(tx.clone());
         self.event_tx = tx;
     }
 
@@ -1003,12 +1541,9 @@ impl Canopy {
                 .unwrap_or(false);
             if !initialized {
                 layout_dirty = true;
-                let next = self.core.with_widget_mut(id, |w, core| {
-                    let mut ctx = crate::core::context::CoreContext::new(core, id);
-                    w.poll(&mut ctx)
-                })?;
+                let next = self.poll_node(id)?;
                 if let Some(d) = next {
-                    self.poller.schedule(id, d);
+                    self.poller.schedule(id, d, self.core.clock);
                 }
                 if let Some(node) = self.core.nodes.get_mut(id) {
                     node.initialized = true;
@@ -1021,6 +1556,8 @@ impl Canopy {
             }
         }
 
+        self.schedule_timers();
+
         if !focus_seen {
             self.core.focus_first(root);
         }
@@ -1028,7 +1565,8 @@ impl Canopy {
         Ok(layout_dirty)
     }
 
-    /// Render a single node (without children).
+    /// Render a single node (without children). Returns the union of writes the widget
+    /// made outside its outer rect.
     fn render_node(
         &self,
         dest_buf: &mut TermBuf,
@@ -1037,12 +1575,13 @@ impl Canopy {
         view: View,
         screen_clip: Rect,
         effect_slice: &[Effect],
-    ) -> Result<()> {
+    ) -> Result<Option<Rect>> {
         let local_clip = Self::outer_clip_to_local(view.outer, screen_clip);
         let screen_origin = screen_clip.tl;
 
         let mut rndr = Render::new_shared(&self.style, styl, dest_buf, local_clip, screen_origin)
-            .with_effects(effect_slice);
+            .with_effects(effect_slice)
+            .with_bounds(view.outer_rect_local());
 
         let result = self.core.with_widget_render(node_id, |widget, core| {
             let ctx = CoreViewContext::new(core, node_id);
@@ -1051,7 +1590,88 @@ impl Canopy {
         result.map_err(|error| {
             self.core
                 .widget_operation_error(WidgetOperation::render("render"), node_id, &error)
-        })
+        })?;
+        Ok(rndr.overflow())
+    }
+
+    /// Record a failed node render for the error overlay, keeping the first failure.
+    fn record_render_failure(&mut self, node_id: NodeId, error: &error::Error) {
+        if self.core.render_failure.is_none() {
+            self.core.render_failure = Some(RenderFailure {
+                path: self.core.node_path(self.core.root, node_id),
+                message: error.to_string(),
+            });
+        }
+    }
+
+    /// Draw the error placeholder that replaces a panicked node and its subtree.
+    fn render_failed_node(
+        &self,
+        traversal: &mut RenderTraversal<'_>,
+        view: View,
+        screen_clip: Rect,
+        message: &str,
+    ) -> Result<()> {
+        let local_clip = Self::outer_clip_to_local(view.outer, screen_clip);
+        let bounds = view.outer_rect_local();
+        let mut rndr = Render::new_shared(
+            &self.style,
+            traversal.styl,
+            traversal.dest_buf,
+            local_clip,
+            screen_clip.tl,
+        );
+        rndr.fill("error", bounds, ' ')?;
+        rndr.text(
+            "error",
+            Line::new(0, 0, bounds.w),
+            &format!("panic: {message}"),
+        )
+    }
+
+    /// Draw the error overlay for a recorded render failure, centered on the screen.
+    fn render_failure_overlay(
+        &self,
+        buf: &mut TermBuf,
+        styl: &mut StyleManager,
+        screen_clip: Rect,
+    ) -> Result<()> {
+        let Some(failure) = self.core.render_failure.as_ref() else {
+            return Ok(());
+        };
+        let screen = buf.rect();
+        let lines = [
+            format!("render error in {}", failure.path),
+            failure.message.clone(),
+            "dismiss to retry".to_string(),
+        ];
+        let widest = lines
+            .iter()
+            .map(|line| text::display_width(line))
+            .max()
+            .unwrap_or(0) as u32;
+        let w = (widest + 4).min(screen.w);
+        let h = (lines.len() as u32 + 2).min(screen.h);
+        let area = Rect::new(
+            screen.tl.x + (screen.w - w) / 2,
+            screen.tl.y + (screen.h - h) / 2,
+            w,
+            h,
+        );
+        let mut rndr = Render::new_shared(&self.style, styl, buf, screen_clip, Point::zero());
+        rndr.fill("error", area, ' ')?;
+        for (i, line) in lines.iter().enumerate() {
+            let y = area.tl.y + 1 + i as u32;
+            if y >= area.tl.y + area.h {
+                break;
+            }
+            rndr.text(
+                "error",
+                Line::new(area.tl.x + 2, y, area.w.saturating_sub(4)),
+                line,
+            )?;
+        }
+        Ok(())
     }
 
     /// Recursively render a node subtree.
@@ -1063,7 +1683,7 @@ impl Canopy {
         active_start: usize,
         active_len: usize,
     ) -> Result<()> {
-        let (hidden, layout, view, children, clear_inherited) = {
+        let (hidden, layout, view, children, clear_inherited, unclipped) = {
             let node = &self.core.nodes[node_id];
             (
                 node.hidden,
@@ -1071,6 +1691,7 @@ impl Canopy {
                 node.view,
                 node.children.clone(),
                 node.clear_inherited_effects,
+                node.unclipped,
             )
         };
 
@@ -1078,10 +1699,20 @@ impl Canopy {
             return Ok(());
         }
 
-        let Some(screen_clip) = view.outer.intersect_rect(parent_clip) else {
+        let draw_area = if unclipped {
+            RectI32::new(view.outer.tl.x, view.outer.tl.y, u32::MAX, u32::MAX)
+                .intersect_rect(traversal.region)
+        } else {
+            view.outer.intersect_rect(parent_clip)
+        };
+        let Some(screen_clip) = draw_area else {
             return Ok(());
         };
 
+        if let Some(message) = self.core.nodes[node_id].failed.clone() {
+            return self.render_failed_node(traversal, view, screen_clip, &message);
+        }
+
         let saved_len = traversal.effect_stack.len();
         let (base_start, base_len) = if clear_inherited {
             (saved_len, 0)
@@ -1092,24 +1723,82 @@ impl Canopy {
         if let Some(local) = self.core.nodes[node_id].effects.as_ref() {
             traversal.effect_stack.extend(local.iter().cloned());
         }
+        if self.core.drag_hover() == Some(node_id) {
+            traversal.effect_stack.push(effects::swap_fg_bg());
+        }
 
         let current_len = base_len + traversal.effect_stack.len() - saved_len;
 
         traversal.styl.push();
+        let mut state_layers = Vec::with_capacity(2);
+        if self.core.is_focused(node_id) {
+            state_layers.push("focused");
+        } else if self.core.is_on_focus_path(node_id) {
+            state_layers.push("focus-path");
+        }
+        if self.core.is_hovered(node_id) {
+            state_layers.push("hover");
+        }
+        traversal.styl.set_state_layers(&state_layers);
 
         {
+            let started = self.core.profiler.is_some().then(Instant::now);
             let effect_slice = &traversal.effect_stack[base_start..base_start + current_len];
-            self.render_node(
-                traversal.dest_buf,
-                traversal.styl,
-                node_id,
-                view,
-                screen_clip,
-                effect_slice,
-            )?;
+            let render = || {
+                self.render_node(
+                    traversal.dest_buf,
+                    traversal.styl,
+                    node_id,
+                    view,
+                    screen_clip,
+                    effect_slice,
+                )
+            };
+            let rendered = if self.core.isolate_panics {
+                isolate::catch(render)
+            } else {
+                Ok(render())
+            };
+            let overflow = match rendered {
+                Ok(Ok(overflow)) => overflow,
+                Ok(Err(error)) if self.catch_render_errors => {
+                    self.record_render_failure(node_id, &error);
+                    traversal.skip_node(saved_len);
+                    return Ok(());
+                }
+                Ok(Err(error)) => return Err(error),
+                Err(message) => {
+                    traversal.skip_node(saved_len);
+                    self.core.fail_node(node_id, "rendering", message.clone());
+                    return self.render_failed_node(traversal, view, screen_clip, &message);
+                }
+            };
+            if let Some(rect) = overflow
+                && !unclipped
+            {
+                if self.strict_clipping {
+                    return Err(error::Error::Render(format!(
+                        "{} wrote outside its rect at {rect:?}",
+                        self.core.node_path(self.core.root, node_id)
+                    )));
+                }
+                self.overflows.push((node_id, rect));
+            }
+            if let (Some(started), Some(profiler)) = (started, self.core.profiler.as_mut()) {
+                profiler.record_render(node_id, started.elapsed());
+            }
         }
+        traversal.styl.set_state_layers(&[]);
 
-        if let Some(children_clip) = view.content.intersect_rect(parent_clip) {
+        let children_bound = if unclipped {
+            traversal.region
+        } else {
+            parent_clip
+        };
+        if let Some(children_clip) = view.content.intersect_rect(children_bound) {
+            if let Some(app) = self.grafts.get_mut(&node_id) {
+                app.render_graft(traversal.dest_buf, view.content, children_clip)?;
+            }
             for child in children {
                 self.render_recursive(traversal, child, children_clip, base_start, current_len)?;
             }
@@ -1121,111 +1810,259 @@ impl Canopy {
         Ok(())
     }
 
+    /// Render this app at the size of a graft host's content rect, then copy its frame into
+    /// the host app's buffer within `clip`.
+    fn render_graft(&mut self, dest: &mut TermBuf, content: RectI32, clip: Rect) -> Result<()> {
+        let size = Size::new(content.w, content.h);
+        if self.root_size != Some(size) {
+            self.set_root_size(size)?;
+        }
+        self.render(&mut SnapshotBackend)?;
+        if let Some(frame) = &self.termbuf {
+            dest.blit(frame, content.tl, clip);
+        }
+        Ok(())
+    }
+
     /// Render the tree into an offscreen buffer.
     fn render_pass(&mut self, root_size: Size) -> Result<TermBuf> {
+        let def_style = self.default_style();
+        let mut next = TermBuf::new(root_size, ' ', def_style);
+        let screen_clip = Rect::new(0, 0, root_size.w, root_size.h);
+        let (hardware, drawn) = self.cursor_locations()?;
+        self.render_region(&mut next, screen_clip, &drawn)?;
+        self.hardware_cursor = hardware;
+        self.last_cursors = drawn;
+        Ok(next)
+    }
+
+    /// Redraw only `region` of the previous frame, leaving the rest of the screen untouched.
+    fn render_damage_pass(&mut self, prev: &TermBuf, region: Option<Rect>) -> Result<TermBuf> {
+        let mut next = prev.clone();
+        let mut region = region;
+        let (hardware, drawn) = self.cursor_locations()?;
+        if drawn != self.last_cursors {
+            for c in drawn.iter().chain(&self.last_cursors) {
+                region = union_rect(region, Rect::new(c.location.x, c.location.y, 1, 1));
+            }
+        }
+        if let Some(region) = region.and_then(|region| region.intersect(&next.rect())) {
+            let def_style = self.default_style();
+            next.fill(&def_style, region, ' ');
+            self.render_region(&mut next, region, &drawn)?;
+        }
+        self.hardware_cursor = hardware;
+        self.last_cursors = drawn;
+        Ok(next)
+    }
+
+    /// Return the default style, resolved to solid colors.
+    fn default_style(&self) -> ResolvedStyle {
         let mut styl = StyleManager::default();
         styl.reset();
-
-        let def_style = styl
-            .get(&self.style, "")
+        let mut style = styl.get(&self.style, "");
+        if self.core.high_contrast {
+            style = effects::HighContrast.apply(style);
+        }
+        style
             .resolve_solid()
-            .expect("default style resolves to solid colors");
-        let mut next = TermBuf::new(root_size, ' ', def_style);
+            .expect("default style resolves to solid colors")
+    }
 
-        let screen_clip = Rect::new(0, 0, root_size.w, root_size.h);
+    /// Render the tree and cursors into `buf`, clipped to a screen region.
+    fn render_region(
+        &mut self,
+        buf: &mut TermBuf,
+        screen_clip: Rect,
+        cursors: &[cursor::Cursor],
+    ) -> Result<()> {
+        let mut styl = mem::take(&mut self.styl);
+        styl.reset();
+        styl.set_high_contrast(self.core.high_contrast);
         let mut effect_stack: Vec<Effect> = Vec::new();
+        self.overflows.clear();
         let mut traversal = RenderTraversal {
-            dest_buf: &mut next,
+            dest_buf: buf,
             styl: &mut styl,
             effect_stack: &mut effect_stack,
+            region: screen_clip,
         };
-        self.render_recursive(&mut traversal, self.core.root, screen_clip, 0, 0)?;
-        self.post_render(&mut next)?;
-
-        Ok(next)
+        let rendered = self
+            .render_recursive(&mut traversal, self.core.root, screen_clip, 0, 0)
+            .and_then(|()| self.render_failure_overlay(buf, &mut styl, screen_clip));
+        self.styl = styl;
+        rendered?;
+        for cursor in cursors {
+            if screen_clip.contains_point(cursor.location) {
+                buf.overlay_cursor(*cursor);
+            }
+        }
+        Ok(())
     }
 
-    /// Post-render sweep of the tree.
-    pub(crate) fn post_render(&self, buf: &mut TermBuf) -> Result<()> {
+    /// Locate the cursors of the focus path, in screen coordinates. Returns the cursor to
+    /// show with the terminal cursor, which is only set in hardware mode, and the cursors
+    /// to draw into the frame.
+    fn cursor_locations(&self) -> Result<(Option<cursor::Cursor>, Vec<cursor::Cursor>)> {
         let mut current = self.core.focus;
-        let mut cursor_spec: Option<(NodeId, View, cursor::Cursor)> = None;
+        let mut cursor_spec: Option<(View, cursor::Cursor, Vec<cursor::Cursor>)> = None;
         while let Some(id) = current {
-            let cursor =
+            let cursors =
                 self.core
-                    .with_widget_read(id, WidgetOperation::render("cursor"), |w, _| w.cursor())?;
-            if let Some(node_cursor) = cursor
+                    .with_widget_read(id, WidgetOperation::render("cursor"), |w, _| {
+                        w.cursor().map(|c| (c, w.extra_cursors()))
+                    })?;
+            if let Some((primary, extra)) = cursors
                 && let Some(node) = self.core.nodes.get(id)
             {
-                cursor_spec = Some((id, node.view, node_cursor));
+                cursor_spec = Some((node.view, primary, extra));
                 break;
             }
             current = self.core.nodes.get(id).and_then(|n| n.parent);
         }
 
-        if let Some((_nid, view, c)) = cursor_spec {
+        let Some((view, primary, extra)) = cursor_spec else {
+            return Ok((None, Vec::new()));
+        };
+        let to_screen = |c: cursor::Cursor| {
             let view_rect = Rect::new(0, 0, view.content.w, view.content.h);
-            if view_rect.contains_point(c.location) {
-                let screen_x = view.content.tl.x + c.location.x as i32;
-                let screen_y = view.content.tl.y + c.location.y as i32;
-                if screen_x >= 0 && screen_y >= 0 {
-                    let screen_pos = Point {
-                        x: screen_x as u32,
-                        y: screen_y as u32,
-                    };
-                    buf.overlay_cursor(screen_pos, c.shape);
+            if !view_rect.contains_point(c.location) {
+                return None;
+            }
+            let screen_x = view.content.tl.x + c.location.x as i32;
+            let screen_y = view.content.tl.y + c.location.y as i32;
+            (screen_x >= 0 && screen_y >= 0).then_some(cursor::Cursor {
+                location: Point {
+                    x: screen_x as u32,
+                    y: screen_y as u32,
+                },
+                ..c
+            })
+        };
+        let primary = to_screen(primary);
+        let mut drawn: Vec<cursor::Cursor> = extra.into_iter().filter_map(to_screen).collect();
+        match self.cursor_mode {
+            cursor::CursorMode::Hardware => Ok((primary, drawn)),
+            cursor::CursorMode::Virtual => {
+                if let Some(primary) = primary {
+                    drawn.insert(0, primary);
                 }
+                Ok((None, drawn))
             }
         }
+    }
 
-        Ok(())
+    /// Capture the binding catalog if a widget asked for it, or if the keymap changed since
+    /// it was last captured.
+    fn refresh_binding_catalog(&mut self) {
+        let revision = self.keymap.revision();
+        let requested = mem::take(&mut self.core.binding_catalog_requested);
+        if requested
+            || self
+                .binding_catalog_revision
+                .is_some_and(|seen| seen != revision)
+        {
+            self.core.binding_catalog.set(self.binding_catalog());
+            self.binding_catalog_revision = Some(revision);
+        }
     }
 
     /// Render the widget tree. All visible nodes are rendered.
     pub fn render<R: RenderBackend>(&mut self, be: &mut R) -> Result<()> {
+        self.render_frame(be, false)
+    }
+
+    /// Render a frame. If `damage_only` is set, only regions damaged since the last frame are
+    /// redrawn, unless layout, tree, or style changes force a full redraw.
+    fn render_frame<R: RenderBackend>(&mut self, be: &mut R, damage_only: bool) -> Result<()> {
         let first_render = self.termbuf.is_none();
+        self.refresh_binding_catalog();
+        self.core.notify_observers()?;
 
         // Apply pending style change from Context::set_style
-        if let Some(new_style) = self.core.pending_style.take() {
+        let style_changed = if let Some(new_style) = self.core.pending_style.take() {
             self.style = new_style;
+            true
+        } else {
+            false
+        };
+
+        if let Some(size) = self.core.pending_resize.take() {
+            self.set_root_size(size)?;
+        }
+
+        if self.core.take_full_render_request() {
+            self.termbuf = None;
         }
 
+        self.grafts
+            .retain(|node_id, _| self.core.nodes.contains_key(*node_id));
+        self.graft_wakes
+            .retain(|node_id, _| self.grafts.contains_key(node_id));
+
         if let Some(root_size) = self.root_size {
-            self.core.update_layout(root_size)?;
+            let frame_started = Instant::now();
+            self.core.begin_profile_frame();
+            self.update_layout(root_size)?;
 
             let layout_dirty = self.pre_render()?;
+            self.install_pending_bindings();
             if layout_dirty {
-                self.core.update_layout(root_size)?;
+                self.update_layout(root_size)?;
             }
+            let views_changed = self.core.take_views_changed();
 
+            let damage = self.core.take_damage();
+            let prev = self.termbuf.take();
             let _ = self.core.take_help_snapshot_observed();
-            let mut next = self.render_pass(root_size)?;
+            let mut next = match &prev {
+                Some(prev) if damage_only && !views_changed && !layout_dirty && !style_changed => {
+                    self.render_damage_pass(prev, damage)?
+                }
+                _ => self.render_pass(root_size)?,
+            };
             if self.core.take_help_snapshot_observed() {
                 self.core.pending_help_snapshot = None;
-                self.core.update_layout(root_size)?;
+                self.update_layout(root_size)?;
                 if layout_dirty {
-                    self.core.update_layout(root_size)?;
+                    self.update_layout(root_size)?;
                 }
                 next = self.render_pass(root_size)?;
             }
 
             be.reset()?;
+            let primary = self
+                .hardware_cursor
+                .or_else(|| self.last_cursors.first().copied());
+            be.cursor_color(primary.and_then(|c| c.color))?;
 
-            if let Some(prev) = &self.termbuf {
-                next.diff(prev, be)?;
+            if let Some(prev) = &prev {
+                next.render_diff(prev, be)?;
             } else {
                 next.render(be)?;
             }
+            be.show_cursor(self.hardware_cursor)?;
+            be.flush()?;
             self.termbuf = Some(next);
+            self.core.finish_profile_frame(frame_started.elapsed());
 
             if let Some(target) = self.core.take_diagnostic_dump_request() {
                 eprintln!("{}", self.diagnostic_dump(target));
             }
+            if let Some(path) = self.core.pending_screenshot.take()
+                && let Err(err) = self.screenshot(&path)
+            {
+                tracing::error!("screenshot {} failed: {err}", path.display());
+            }
 
             self.last_render_focus_gen = self.core.focus_gen;
             self.last_focus_path = self.core.focus_path_ids();
+            self.last_frame = Some(Instant::now());
+            self.announce_focus()?;
+            self.schedule_graft_wakes();
 
             if first_render && self.run_on_start_hooks()? {
-                return self.render(be);
+                return self.render_frame(be, false);
             }
 
             self.render_pending = false;
@@ -1327,11 +2164,18 @@ impl Canopy {
                 &path,
                 format!("{event:?}"),
             );
-            let outcome = self.core.dispatch_event_on_node(id, &event)?;
+            let outcome = if self.grafts.contains_key(&id) {
+                self.deliver_to_graft(id, event)?
+            } else {
+                self.core.dispatch_event_on_node(id, &event)?
+            };
 
             match outcome {
                 EventOutcome::Handle | EventOutcome::Consume => {
                     self.trace_route(RoutePhase::Handled, Some(id), &path, format!("{outcome:?}"));
+                    if let RoutedInput::Key(key) = input {
+                        self.core.macros.record(MacroStep::Key(key));
+                    }
                     return Ok(true);
                 }
                 EventOutcome::Ignore => {
@@ -1388,22 +2232,53 @@ impl Canopy {
         Ok(true)
     }
 
-    /// Propagate a mouse event through the node under the event and all its ancestors.
-    pub(crate) fn mouse(&mut self, m: mouse::MouseEvent) -> Result<()> {
-        let (target, path) = self.mouse_route_start(m.location)?;
-        let changed = self.route_input(target, path, RoutedInput::Mouse(m))?;
-        if changed {
-            self.render_pending = true;
+    /// Advance an active drag with a mouse event. Returns `true` if the drag consumed it.
+    fn drag_mouse(&mut self, m: mouse::MouseEvent) -> Result<bool> {
+        if self.core.drag.is_none() {
+            return Ok(false);
         }
-        Ok(())
+        match m.action {
+            mouse::Action::Drag | mouse::Action::Moved => {
+                self.core.update_drag_hover(m.location)?;
+                Ok(true)
+            }
+            mouse::Action::Up => {
+                self.core.finish_drag(m.location)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Route a mouse event through event hooks and then the tree.
+    pub(crate) fn mouse(&mut self, m: mouse::MouseEvent) -> Result<()> {
+        self.event(Event::Mouse(m))
     }
 
-    /// Propagate a key event through the focus and all its ancestors.
+    /// Route a key event through event hooks and then the tree.
     pub(crate) fn key<T>(&mut self, tk: T) -> Result<()>
     where
         T: Into<key::Key>,
     {
-        let key = tk.into();
+        self.event(Event::Key(tk.into()))
+    }
+
+    /// Propagate a mouse event through the node under the event and all its ancestors.
+    /// Returns `true` if the event was handled.
+    fn route_mouse(&mut self, m: mouse::MouseEvent) -> Result<bool> {
+        self.core.update_hover(m.location)?;
+        if self.drag_mouse(m)? {
+            return Ok(true);
+        }
+        let (target, path) = self.mouse_route_start(m.location)?;
+        let changed = self.route_input(target, path, RoutedInput::Mouse(m))?;
+        self.run_deferred();
+        Ok(changed)
+    }
+
+    /// Propagate a key event through the focus and all its ancestors. Returns `true` if the
+    /// event was handled.
+    fn route_key(&mut self, key: key::Key) -> Result<bool> {
         if self.core.focus.is_none() {
             self.core.focus_first(self.core.root);
         }
@@ -1411,10 +2286,233 @@ impl Canopy {
         let start = self.core.focus.unwrap_or(self.core.root);
         let path = self.core.node_path(self.core.root, start);
         let changed = self.route_input(Some(start), path, RoutedInput::Key(key))?;
-        if changed {
-            self.render_pending = true;
+        self.run_deferred();
+        Ok(changed)
+    }
+
+    /// Report scroll changes and deliver messages, then run scripts, macro replays, and
+    /// session requests queued by widgets, logging failures.
+    fn run_deferred(&mut self) {
+        for node in self.core.take_scroll_changes() {
+            if let Err(err) = self.core.dispatch_event(node, &Event::Scroll(node)) {
+                tracing::error!("scroll event delivery failed: {err}");
+            }
+            self.core.damage_view(node);
+        }
+        for _ in 0..MAX_MESSAGE_ROUNDS {
+            let messages = self.core.take_pending_messages();
+            if messages.is_empty() {
+                break;
+            }
+            for message in messages {
+                if let Err(err) = self.core.deliver_message(message) {
+                    tracing::error!("message delivery failed: {err}");
+                }
+            }
+        }
+        self.install_pending_bindings();
+        for (node, source) in self.core.take_pending_scripts() {
+            let node = if self.core.nodes.contains_key(node) {
+                node
+            } else {
+                self.core.root
+            };
+            let result = self
+                .compile_script(&source)
+                .and_then(|sid| self.run_script(node, sid));
+            if let Err(err) = result {
+                tracing::error!("queued script failed: {err}");
+            }
+        }
+        for (register, count) in self.core.macros.take_pending() {
+            if let Err(err) = self.replay_macro(&register, count) {
+                tracing::error!("macro replay failed: {err}");
+            }
+        }
+        for request in self.core.take_pending_sessions() {
+            let result = match &request {
+                SessionRequest::Save(name) => self.save_session(name),
+                SessionRequest::Load(name) => self.load_session(name),
+            };
+            if let Err(err) = result {
+                tracing::error!("session request failed: {err}");
+            }
+        }
+    }
+
+    /// Replay the macro in `register` `count` times.
+    ///
+    /// Commands dispatch relative to the focused node at the time each step runs, and keys
+    /// route exactly as typed input does.
+    pub fn replay_macro(&mut self, register: &str, count: usize) -> Result<()> {
+        let steps = self
+            .core
+            .macros
+            .get(register)
+            .ok_or_else(|| error::Error::NotFound(format!("macro register {register:?}")))?
+            .to_vec();
+        if self.core.macros.replay_depth >= MAX_REPLAY_DEPTH {
+            return Err(error::Error::Invalid(format!(
+                "macro {register:?} exceeded the replay depth limit"
+            )));
+        }
+        self.core.macros.replay_depth += 1;
+        let result = self.replay_steps(&steps, count);
+        self.core.macros.replay_depth -= 1;
+        result
+    }
+
+    /// Execute macro steps `count` times.
+    fn replay_steps(&mut self, steps: &[MacroStep], count: usize) -> Result<()> {
+        for _ in 0..count {
+            for step in steps {
+                match step {
+                    MacroStep::Command(cmd) => {
+                        let target = self.core.focus.unwrap_or(self.core.root);
+                        let guard = self.core.push_command_scope(CommandScopeFrame::default());
+                        let result = commands::dispatch(&mut self.core, target, cmd);
+                        self.core.pop_command_scope(guard);
+                        result?;
+                        self.run_deferred();
+                    }
+                    MacroStep::Key(key) => self.key(*key)?,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Install key bindings queued by widgets, each scoped to its node's path. Bindings
+    /// for nodes removed since they were queued are dropped; failures are logged.
+    fn install_pending_bindings(&mut self) {
+        for (node, input, command) in self.core.take_pending_bindings() {
+            if !self.core.nodes.contains_key(node) {
+                continue;
+            }
+            let path = self.core.node_path(self.core.root, node).to_string();
+            let result = self.keymap.replace_binding(
+                "",
+                input,
+                &path,
+                inputmap::BindingTarget::Command(command),
+            );
+            match result {
+                Ok((_, removed)) => {
+                    self.release_removed_bindings(removed);
+                }
+                Err(err) => tracing::error!("binding for {path} failed: {err}"),
+            }
+        }
+    }
+
+    /// Run and release the exit hooks. Every hook runs even if an earlier one fails; the
+    /// first error is returned. Runloops call this after restoring the terminal.
+    pub fn run_exit_hooks(&mut self) -> Result<()> {
+        let mut result = Ok(());
+        for hook in self.core.hooks.take_exit_hooks() {
+            let outcome = self.with_root_context(hook);
+            if result.is_ok() {
+                result = outcome;
+            }
+        }
+        result
+    }
+
+    /// Take the value passed to `exit_with`, if it has type `T`.
+    pub fn take_exit_value<T: Any>(&mut self) -> Option<T> {
+        self.core
+            .exit_value
+            .take()
+            .and_then(|value| value.downcast().ok())
+            .map(|value| *value)
+    }
+
+    /// Enable app-wide undo, retaining at most `limit` changes. Existing history is discarded.
+    pub fn enable_undo(&mut self, limit: usize) {
+        self.core.undo = Some(UndoManager::new(limit));
+    }
+
+    /// Return the undo history, if undo is enabled.
+    pub fn undo_manager(&self) -> Option<&UndoManager> {
+        self.core.undo.as_ref()
+    }
+
+    /// Write all macro registers to a JSON file so they can be restored with `load_macros`.
+    pub fn save_macros(&self, path: &FsPath) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.core.macros.to_json()?)
+            .map_err(|err| error::Error::Invalid(format!("macro encode failed: {err}")))?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Replace all macro registers with those saved by `save_macros`.
+    ///
+    /// Commands must already be registered, so call this after loading widgets.
+    pub fn load_macros(&mut self, path: &FsPath) -> Result<()> {
+        let source = fs::read_to_string(path)?;
+        let json = serde_json::from_str(&source)
+            .map_err(|err| error::Error::Invalid(format!("macro decode failed: {err}")))?;
+        self.core.macros.load_json(json, &self.core.commands)?;
+        Ok(())
+    }
+
+    /// Save the state of every persistent widget in the tree to a JSON file.
+    pub fn save_state(&mut self, path: &FsPath) -> Result<()> {
+        let snapshot = self.core.capture_state()?;
+        let json = serde_json::to_string_pretty(&snapshot)
+            .map_err(|err| error::Error::Invalid(format!("state encode failed: {err}")))?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Restore widget state saved by `save_state` into the current tree.
+    ///
+    /// State is matched by node path, so build the tree before calling this. Saved
+    /// entries without a matching node are ignored.
+    pub fn load_state(&mut self, path: &FsPath) -> Result<()> {
+        let source = fs::read_to_string(path)?;
+        let snapshot = serde_json::from_str(&source)
+            .map_err(|err| error::Error::Invalid(format!("state decode failed: {err}")))?;
+        self.core.restore_state(snapshot)?;
+        self.render_pending = true;
+        Ok(())
+    }
+
+    /// Store sessions as `<name>.json` files in `dir`, so they survive restarts.
+    pub fn set_session_dir(&mut self, dir: impl Into<PathBuf>) {
+        self.session_dir = Some(dir.into());
+    }
+
+    /// Save widget state, layout, scroll offsets, and focus as session `name`.
+    pub fn save_session(&mut self, name: &str) -> Result<()> {
+        let file = session_file(name)?;
+        let session = self.core.capture_session()?;
+        if let Some(dir) = &self.session_dir {
+            let json = serde_json::to_string_pretty(&session)
+                .map_err(|err| error::Error::Invalid(format!("session encode failed: {err}")))?;
+            fs::create_dir_all(dir)?;
+            fs::write(dir.join(file), json)?;
         }
+        self.sessions.insert(name.to_string(), session);
+        Ok(())
+    }
 
+    /// Restore session `name` into the current tree.
+    ///
+    /// Sessions saved during this run are used first, then the session directory.
+    pub fn load_session(&mut self, name: &str) -> Result<()> {
+        let file = session_file(name)?;
+        let session = match (self.sessions.get(name), &self.session_dir) {
+            (Some(session), _) => session.clone(),
+            (None, Some(dir)) => {
+                let source = fs::read_to_string(dir.join(file))?;
+                serde_json::from_str(&source)
+                    .map_err(|err| error::Error::Invalid(format!("session decode failed: {err}")))?
+            }
+            (None, None) => return Err(error::Error::NotFound(format!("session {name:?}"))),
+        };
+        self.core.restore_session(session)?;
+        self.render_pending = true;
         Ok(())
     }
 
@@ -1425,69 +2523,271 @@ impl Canopy {
         }
 
         let start = self.core.focus.unwrap_or(self.core.root);
+        if self.grafts.contains_key(&start) {
+            self.deliver_to_graft(start, event.clone())?;
+            return Ok(());
+        }
         self.core.dispatch_event(start, event)?;
         Ok(())
     }
 
+    /// Deliver an event to the app grafted at `host`, marking the host damaged if the app
+    /// handled it.
+    fn deliver_to_graft(&mut self, host: NodeId, event: Event) -> Result<EventOutcome> {
+        let Some(app) = self.grafts.get_mut(&host) else {
+            return Ok(EventOutcome::Ignore);
+        };
+        let outcome = app.graft_event(event)?;
+        if outcome == EventOutcome::Handle {
+            self.core.damage_view(host);
+        }
+        Ok(outcome)
+    }
+
+    /// Poll a node's widget, returning the delay until its next poll. A node that panics
+    /// is not polled again.
+    fn poll_node(&mut self, node_id: NodeId) -> Result<Option<Duration>> {
+        let next = self.core.isolated(node_id, "polling", |core| {
+            core.with_widget_mut(node_id, |w, core| {
+                let mut ctx = crate::core::context::CoreContext::new(core, node_id);
+                w.poll(&mut ctx)
+            })
+        });
+        Ok(next.transpose()?.flatten())
+    }
+
     /// Handle poll events by executing callbacks on each node in the list.
-    fn poll(&mut self, ids: &[NodeId]) -> Result<()> {
+    ///
+    /// Returns `true` if every polled node reported damage, confining its changes.
+    fn poll(&mut self, ids: &[NodeId]) -> Result<bool> {
+        let mut confined = true;
         for id in ids {
             if self.core.nodes.contains_key(*id) {
-                let next = self.core.with_widget_mut(*id, |w, core| {
-                    let mut ctx = crate::core::context::CoreContext::new(core, *id);
-                    w.poll(&mut ctx)
-                })?;
+                let prior = self.core.take_node_damage(*id);
+                let next = self.poll_node(*id)?;
                 if let Some(d) = next {
-                    self.poller.schedule(*id, d);
+                    self.poller.schedule(*id, d, self.core.clock);
+                }
+                let reported = self.core.take_node_damage(*id);
+                confined &= reported.is_some();
+                if let Some(rect) = union_rect(prior, reported.unwrap_or_default()) {
+                    self.core.damage_node(*id, rect);
                 }
             }
         }
-        Ok(())
+        Ok(confined)
     }
 
-    /// Drain queued automation callbacks that were marshalled onto the UI thread.
+    /// Drain queued automation callbacks that were marshalled onto the UI thread, then
+    /// deliver the messages and run the work they queued.
     pub(crate) fn service_automation(&mut self) {
+        let mut serviced = false;
         while let Ok(callback) = self.automation_rx.try_recv() {
             callback(self);
+            serviced = true;
+        }
+        if serviced {
+            self.run_deferred();
         }
     }
 
-    /// Propagate an event through the tree.
+    /// Run event hooks, then propagate the event through the tree unless a hook swallowed
+    /// it.
     pub(crate) fn event(&mut self, e: Event) -> Result<()> {
-        match e {
-            Event::Key(k) => self.key(k),
-            Event::Mouse(m) => self.mouse(m),
-            Event::Resize(s) => {
-                self.render_pending = true;
-                self.set_root_size(s)
+        self.deliver_event(e).map(|_| ())
+    }
+
+    /// Deliver an event from a host app to this grafted app, reporting whether it was
+    /// handled so unhandled input keeps bubbling in the host.
+    fn graft_event(&mut self, e: Event) -> Result<EventOutcome> {
+        Ok(match self.deliver_event(e)? {
+            Some(true) => EventOutcome::Handle,
+            Some(false) => EventOutcome::Ignore,
+            None => EventOutcome::Consume,
+        })
+    }
+
+    /// Run event hooks and route the event. Returns `None` if a hook swallowed it, and
+    /// otherwise whether a key or mouse event was handled.
+    fn deliver_event(&mut self, mut e: Event) -> Result<Option<bool>> {
+        if self.core.hooks.before_event(&mut e) == HookFlow::Swallow {
+            return Ok(None);
+        }
+        let result = self.route_event(e.clone());
+        let trace: &[RouteTraceEntry] = match e {
+            Event::Key(_) | Event::Mouse(_) => self.route_trace.as_slice(),
+            _ => &[],
+        };
+        self.core.hooks.after_event(&e, trace);
+        self.schedule_timers();
+        let result = result.map(Some);
+        self.tick_grafts()?;
+        result
+    }
+
+    /// Bring every grafted app up to this app's time, then schedule a wake for each graft
+    /// with work still due. Marks the host damaged if its graft changed.
+    fn tick_grafts(&mut self) -> Result<()> {
+        let now = self.core.clock.now();
+        for (&host, app) in &mut self.grafts {
+            if app.tick_graft(now)? {
+                self.core.damage_view(host);
             }
-            Event::Poll(ids) => {
-                self.render_pending = true;
-                self.poll(&ids)
+        }
+        self.schedule_graft_wakes();
+        Ok(())
+    }
+
+    /// Advance a grafted app's clock to `now`, delivering its due polls and timers, and
+    /// run its deferred work. Returns whether the app needs a render.
+    fn tick_graft(&mut self, now: Instant) -> Result<bool> {
+        let elapsed = now.saturating_duration_since(self.core.clock.now());
+        self.advance_clock(elapsed)?;
+        self.run_deferred();
+        Ok(self.render_pending || self.core.has_damage())
+    }
+
+    /// Schedule a poll of each graft host node for when its app next has a poll or timer
+    /// due, unless an earlier wake is already pending.
+    fn schedule_graft_wakes(&mut self) {
+        let now = self.core.clock.now();
+        for (&id, app) in &self.grafts {
+            let Some(due) = app.poller.next_due() else {
+                continue;
+            };
+            if self
+                .graft_wakes
+                .get(&id)
+                .is_some_and(|wake| *wake > now && *wake <= due)
+            {
+                continue;
             }
-            Event::Paste(content) => {
-                self.render_pending = true;
-                let event = Event::Paste(content);
-                self.dispatch_focus_event(&event)
+            self.graft_wakes.insert(id, due);
+            self.poller
+                .schedule(id, due.saturating_duration_since(now), self.core.clock);
+        }
+    }
+
+    /// Drive polls and timers from a virtual clock that starts now and moves only through
+    /// [`Canopy::advance_clock`].
+    pub(crate) fn use_virtual_clock(&mut self) {
+        self.core.clock = Clock::Virtual(Instant::now());
+    }
+
+    /// Advance the virtual clock by `elapsed`, delivering due polls and timers in time
+    /// order. Entries scheduled with no delay while the clock is advancing wait for the
+    /// next advance.
+    pub(crate) fn advance_clock(&mut self, elapsed: Duration) -> Result<()> {
+        let Clock::Virtual(start) = self.core.clock else {
+            return Err(error::Error::InvalidOperation(
+                "the clock is not virtual".into(),
+            ));
+        };
+        // Timers set outside event handling, such as during a render, count from now.
+        self.schedule_timers();
+        let target = start + elapsed;
+        let mut last = None;
+        while let Some(due) = self.poller.next_due().filter(|due| *due <= target) {
+            if last == Some(due) {
+                break;
             }
-            Event::Wake => Ok(()),
-            Event::FocusGained => {
-                self.render_pending = true;
-                self.dispatch_focus_event(&Event::FocusGained)
+            last = Some(due);
+            self.core.clock = Clock::Virtual(due);
+            let (polls, timers): (Vec<_>, Vec<_>) = self
+                .poller
+                .collect_due(due)
+                .into_iter()
+                .partition(|(_, timer)| timer.is_none());
+            let ids: Vec<NodeId> = polls.into_iter().map(|(id, _)| id).collect();
+            let timers: Vec<(NodeId, TimerId)> = timers
+                .into_iter()
+                .filter_map(|(id, timer)| timer.map(|timer| (id, timer)))
+                .collect();
+            if !ids.is_empty() {
+                self.event(Event::Poll(ids))?;
+            }
+            if !timers.is_empty() {
+                self.event(Event::Timers(timers))?;
+            }
+        }
+        self.core.clock = Clock::Virtual(target);
+        Ok(())
+    }
+
+    /// Hand timers set since the last event to the poller.
+    fn schedule_timers(&mut self) {
+        for (node, id, interval) in self.core.timers.take_pending() {
+            self.poller
+                .schedule_timer(node, id, interval, self.core.clock);
+        }
+    }
+
+    /// Deliver due timers to the nodes that set them.
+    fn fire_timers(&mut self, due: &[(NodeId, TimerId)]) -> Result<()> {
+        let now = self.core.clock.now();
+        for &(node, id) in due {
+            if !self.core.nodes.contains_key(node) {
+                self.core.timers.cancel(node, id);
+                self.core.scroll_anims.remove(&node);
+                continue;
+            }
+            if !self.core.timers.fire(node, id, now) {
+                continue;
+            }
+            if id == anim::SCROLL_TIMER {
+                self.core.step_scroll(node, now);
+                self.core.damage_view(node);
+                continue;
             }
-            Event::FocusLost => {
+            self.core.dispatch_event_on_node(node, &Event::Timer(id))?;
+        }
+        self.run_deferred();
+        Ok(())
+    }
+
+    /// Propagate an event through the tree. Returns `true` if a key or mouse event was
+    /// handled.
+    fn route_event(&mut self, e: Event) -> Result<bool> {
+        match e {
+            Event::Key(k) => return self.route_key(k),
+            Event::Mouse(m) => return self.route_mouse(m),
+            Event::Resize(s) => {
                 self.render_pending = true;
-                self.dispatch_focus_event(&Event::FocusLost)
+                self.set_root_size(s)?;
+            }
+            Event::Poll(ids) => {
+                if !self.poll(&ids)? {
+                    self.render_pending = true;
+                }
             }
+            Event::Paste(content) => self.dispatch_focus_event(&Event::Paste(content))?,
+            Event::Timers(due) => self.fire_timers(&due)?,
+            Event::Timer(_) | Event::Scroll(_) | Event::Wake => {}
+            Event::App(app) => self.dispatch_focus_event(&Event::App(app))?,
+            Event::FocusGained => self.dispatch_focus_event(&Event::FocusGained)?,
+            Event::FocusLost => self.dispatch_focus_event(&Event::FocusLost)?,
         }
+        Ok(false)
     }
 
     /// Set the size on the root node.
     pub fn set_root_size(&mut self, size: Size) -> Result<()> {
         self.root_size = Some(size);
         self.render_pending = true;
-        self.core.update_layout(size)?;
-        Ok(())
+        self.update_layout(size)
+    }
+
+    /// Run layout for a root of `size`. While render errors are caught, a layout failure
+    /// is recorded for the error overlay and the frame keeps the views laid out so far.
+    fn update_layout(&mut self, size: Size) -> Result<()> {
+        match self.core.update_layout(size) {
+            Err(error) if self.catch_render_errors => {
+                let root = self.core.root;
+                self.record_render_failure(root, &error);
+                Ok(())
+            }
+            result => result,
+        }
     }
 
     /// Execute a resolved binding target on a node.
@@ -1548,22 +2848,27 @@ pub trait Loader {
 mod tests {
     use std::{
         any::Any,
-        sync::atomic::{AtomicUsize, Ordering},
+        process,
+        sync::{
+            Arc, Mutex,
+            atomic::{AtomicUsize, Ordering},
+        },
         time::Duration,
     };
 
     use super::*;
     use crate::{
-        Context, ReadContext,
-        commands::{CommandNode, CommandSpec},
+        Context, DragPayload, DropTarget, FocusCommands, ReadContext,
+        commands::{ArgValue, CommandNode, CommandSpec},
         derive_commands,
         error::{Error, Result},
         geom::{Direction, Point, RectI32},
-        layout::Layout,
+        layout::{CanvasContext, Layout},
         path::Path,
         state::NodeName,
         testing::{
             backend::{CanvasRender, TestRender},
+            harness::Harness,
             ttree::{Ba, BaLa, BaLb, OutcomeTarget, R, get_state, reset_state, run_ttree},
         },
         widget::{EventOutcome, Widget},
@@ -1571,6 +2876,28 @@ mod tests {
 
     static POLL_COUNT: AtomicUsize = AtomicUsize::new(0);
 
+    #[derive(Debug, Default)]
+    struct RecordingBackend {
+        calls: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl BackendControl for RecordingBackend {
+        fn start(&mut self) -> Result<()> {
+            self.calls.lock().unwrap().push("start");
+            Ok(())
+        }
+
+        fn stop(&mut self) -> Result<()> {
+            self.calls.lock().unwrap().push("stop");
+            Ok(())
+        }
+
+        fn suspend(&mut self) -> Result<Option<Size>> {
+            self.calls.lock().unwrap().push("suspend");
+            Ok(Some(Size::new(6, 3)))
+        }
+    }
+
     pub struct PollWidget;
 
     #[derive_commands]
@@ -1652,66 +2979,235 @@ mod tests {
         }
     }
 
-    fn set_outcome<T: Any + OutcomeTarget>(core: &mut Core, id: NodeId, outcome: EventOutcome) {
-        let _ignored = core.with_widget_mut(id, |w, _| {
-            let any = w as &mut dyn Any;
-            if let Some(node) = any.downcast_mut::<T>() {
-                node.set_outcome(outcome);
+    pub struct DragSource;
+
+    impl Widget for DragSource {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn on_event(&mut self, event: &Event, ctx: &mut dyn Context) -> Result<EventOutcome> {
+            if let Event::Mouse(mouse_event) = event
+                && mouse_event.action == mouse::Action::Down
+            {
+                ctx.start_drag(Box::new(7u32));
+                return Ok(EventOutcome::Handle);
             }
-        });
+            Ok(EventOutcome::Ignore)
+        }
     }
 
-    fn capture_drag_count(core: &mut Core, id: NodeId) -> usize {
-        core.with_widget_mut(id, |w, _| {
-            let any = w as &mut dyn Any;
-            any.downcast_mut::<CaptureWidget>()
-                .map(|widget| widget.drags)
-                .unwrap_or(0)
-        })
-        .unwrap_or(0)
+    pub struct DropBin {
+        received: Vec<(u32, Point)>,
     }
 
-    fn make_mouse_event(core: &Core, node_id: NodeId) -> mouse::MouseEvent {
-        let loc = core
-            .nodes
-            .get(node_id)
-            .map(|n| {
-                let tl = n.view.outer.tl;
-                Point {
-                    x: tl.x.max(0) as u32,
-                    y: tl.y.max(0) as u32,
-                }
-            })
-            .unwrap_or_default();
-        mouse::MouseEvent {
-            action: mouse::Action::Down,
-            button: mouse::Button::Left,
-            modifiers: key::Empty,
-            location: loc,
+    impl DropTarget for DropBin {
+        fn accepts_drop(&self, payload: &dyn Any) -> bool {
+            payload.is::<u32>()
+        }
+
+        fn on_drop(
+            &mut self,
+            payload: DragPayload,
+            location: Point,
+            _ctx: &mut dyn Context,
+        ) -> Result<()> {
+            if let Ok(value) = payload.downcast::<u32>() {
+                self.received.push((*value, location));
+            }
+            Ok(())
         }
     }
 
-    fn assert_error_context(error: &Error, operation: &str, node_id: NodeId, path: &str) {
-        let message = error.to_string();
-        assert!(
-            message.contains(operation),
-            "expected {message:?} to contain operation {operation:?}"
-        );
-        assert!(
-            message.contains(&format!("{node_id:?}")),
-            "expected {message:?} to contain node ID {node_id:?}"
-        );
-        assert!(
-            message.contains(path),
-            "expected {message:?} to contain path {path:?}"
-        );
+    impl Widget for DropBin {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn drop_target(&mut self) -> Option<&mut dyn DropTarget> {
+            Some(self)
+        }
     }
 
-    #[test]
-    fn render_errors_include_operation_node_and_path() -> Result<()> {
-        let (_, mut render) = TestRender::create();
-        let mut canopy = Canopy::new();
-        canopy
+    pub struct Ticker {
+        ticks: u32,
+    }
+
+    impl Widget for Ticker {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+            let rect = ctx.view().outer_rect_local();
+            let text = self.ticks.to_string();
+            r.text("", rect.line(0), &text)?;
+            r.text("", rect.line(1), &text)
+        }
+
+        fn poll(&mut self, ctx: &mut dyn Context) -> Option<Duration> {
+            self.ticks += 1;
+            let w = ctx.view().content.w;
+            ctx.damage(Rect::new(0, 0, w, 1));
+            None
+        }
+    }
+
+    pub struct MultiCursor;
+
+    impl Widget for MultiCursor {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+            true
+        }
+
+        fn cursor(&self) -> Option<cursor::Cursor> {
+            Some(cursor::Cursor {
+                location: Point { x: 0, y: 0 },
+                shape: cursor::CursorShape::Line,
+                blink: false,
+                color: None,
+            })
+        }
+
+        fn extra_cursors(&self) -> Vec<cursor::Cursor> {
+            vec![cursor::Cursor {
+                location: Point { x: 2, y: 1 },
+                shape: cursor::CursorShape::Block,
+                blink: false,
+                color: None,
+            }]
+        }
+    }
+
+    #[derive(Default)]
+    struct CursorBackend {
+        shown: Vec<Option<cursor::Cursor>>,
+        /// Backend calls since the last reset, by name.
+        calls: Vec<&'static str>,
+    }
+
+    impl RenderBackend for CursorBackend {
+        fn style(&mut self, _style: &ResolvedStyle) -> Result<()> {
+            Ok(())
+        }
+
+        fn text(&mut self, _loc: Point, _txt: &str) -> Result<()> {
+            self.calls.push("text");
+            Ok(())
+        }
+
+        fn supports_char_shift(&self) -> bool {
+            false
+        }
+
+        fn shift_chars(&mut self, _loc: Point, _count: i32) -> Result<()> {
+            Ok(())
+        }
+
+        fn show_cursor(&mut self, cursor: Option<cursor::Cursor>) -> Result<()> {
+            self.shown.push(cursor);
+            self.calls.push("show_cursor");
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            self.calls.push("flush");
+            Ok(())
+        }
+
+        fn reset(&mut self) -> Result<()> {
+            self.calls.clear();
+            Ok(())
+        }
+    }
+
+    pub struct Alarm {
+        fired: Vec<TimerId>,
+    }
+
+    impl Widget for Alarm {
+        fn poll(&mut self, ctx: &mut dyn Context) -> Option<Duration> {
+            ctx.set_timer(1, Duration::ZERO, false);
+            ctx.set_timer(2, Duration::from_secs(3600), true);
+            ctx.set_timer(3, Duration::from_millis(20), true);
+            None
+        }
+
+        fn on_event(&mut self, event: &Event, ctx: &mut dyn Context) -> Result<EventOutcome> {
+            if let Event::Timer(id) = event {
+                self.fired.push(*id);
+                if *id == 3 {
+                    assert!(ctx.cancel_timer(3));
+                }
+            }
+            Ok(EventOutcome::Handle)
+        }
+    }
+
+    fn set_outcome<T: Any + OutcomeTarget>(core: &mut Core, id: NodeId, outcome: EventOutcome) {
+        let _ignored = core.with_widget_mut(id, |w, _| {
+            let any = w as &mut dyn Any;
+            if let Some(node) = any.downcast_mut::<T>() {
+                node.set_outcome(outcome);
+            }
+        });
+    }
+
+    fn capture_drag_count(core: &mut Core, id: NodeId) -> usize {
+        core.with_widget_mut(id, |w, _| {
+            let any = w as &mut dyn Any;
+            any.downcast_mut::<CaptureWidget>()
+                .map(|widget| widget.drags)
+                .unwrap_or(0)
+        })
+        .unwrap_or(0)
+    }
+
+    fn make_mouse_event(core: &Core, node_id: NodeId) -> mouse::MouseEvent {
+        let loc = core
+            .nodes
+            .get(node_id)
+            .map(|n| {
+                let tl = n.view.outer.tl;
+                Point {
+                    x: tl.x.max(0) as u32,
+                    y: tl.y.max(0) as u32,
+                }
+            })
+            .unwrap_or_default();
+        mouse::MouseEvent {
+            action: mouse::Action::Down,
+            button: mouse::Button::Left,
+            modifiers: key::Empty,
+            location: loc,
+        }
+    }
+
+    fn assert_error_context(error: &Error, operation: &str, node_id: NodeId, path: &str) {
+        let message = error.to_string();
+        assert!(
+            message.contains(operation),
+            "expected {message:?} to contain operation {operation:?}"
+        );
+        assert!(
+            message.contains(&format!("{node_id:?}")),
+            "expected {message:?} to contain node ID {node_id:?}"
+        );
+        assert!(
+            message.contains(path),
+            "expected {message:?} to contain path {path:?}"
+        );
+    }
+
+    #[test]
+    fn render_errors_include_operation_node_and_path() -> Result<()> {
+        let (_, mut render) = TestRender::create();
+        let mut canopy = Canopy::new();
+        canopy
             .core
             .replace_subtree(canopy.core.root, FailRenderWidget)?;
         canopy.set_root_size(Size::new(10, 2))?;
@@ -1746,11 +3242,45 @@ mod tests {
             modifiers: key::Empty,
             location: Point { x: 1, y: 1 },
         };
+        // The first move only redraws the newly hovered node.
+        canopy.event(Event::Mouse(event))?;
+        canopy.render_if_pending(&mut render)?;
         canopy.event(Event::Mouse(event))?;
         assert!(!canopy.render_if_pending(&mut render)?);
         Ok(())
     }
 
+    #[test]
+    fn hover_tracks_node_under_mouse() -> Result<()> {
+        let mut canopy = Canopy::new();
+        let app_id = canopy
+            .core
+            .add_child_to_boxed(canopy.core.root, Box::new(StaticWidget::new()))?;
+        canopy.core.set_layout_of(app_id, Layout::fill())?;
+        canopy.set_root_size(Size::new(10, 6))?;
+
+        let (_, mut render) = TestRender::create();
+        canopy.render(&mut render)?;
+        assert!(!canopy.core.is_hovered(app_id));
+
+        let event = mouse::MouseEvent {
+            action: mouse::Action::Moved,
+            button: mouse::Button::None,
+            modifiers: key::Empty,
+            location: Point { x: 3, y: 2 },
+        };
+        canopy.event(Event::Mouse(event))?;
+        assert!(canopy.core.is_hovered(app_id));
+        assert!(!canopy.core.is_hovered(canopy.core.root));
+        assert!(!canopy.render_pending);
+        assert!(canopy.core.has_damage());
+        assert!(canopy.render_if_pending(&mut render)?);
+
+        canopy.core.remove_subtree(app_id)?;
+        assert_eq!(canopy.core.hover, None);
+        Ok(())
+    }
+
     #[test]
     fn mouse_capture_routes_drag_outside() -> Result<()> {
         let mut canopy = Canopy::new();
@@ -1775,6 +3305,8 @@ mod tests {
         canopy.event(Event::Mouse(drag))?;
 
         assert_eq!(capture_drag_count(&mut canopy.core, app_id), 1);
+        assert_eq!(canopy.core.mouse_capture, Some(app_id));
+        assert!(canopy.core.is_hovered(app_id));
 
         let up = mouse::MouseEvent {
             action: mouse::Action::Up,
@@ -1783,10 +3315,85 @@ mod tests {
             location: Point { x: 50, y: 50 },
         };
         canopy.event(Event::Mouse(up))?;
+        assert_eq!(canopy.core.mouse_capture, None);
 
         Ok(())
     }
 
+    #[test]
+    fn drag_and_drop_delivers_payload_to_target() -> Result<()> {
+        let mut canopy = Canopy::new();
+        let root = canopy.core.root;
+        canopy
+            .core
+            .set_layout_of(root, Layout::row().flex_horizontal(1).flex_vertical(1))?;
+        let source = canopy.core.add_child_to_boxed(root, Box::new(DragSource))?;
+        let bin = canopy.core.add_child_to_boxed(
+            root,
+            Box::new(DropBin {
+                received: Vec::new(),
+            }),
+        )?;
+        canopy.set_root_size(Size::new(10, 4))?;
+
+        let (_, mut render) = TestRender::create();
+        canopy.render(&mut render)?;
+
+        let at = |action, x, y| mouse::MouseEvent {
+            action,
+            button: mouse::Button::Left,
+            modifiers: key::Empty,
+            location: Point { x, y },
+        };
+
+        canopy.event(Event::Mouse(at(mouse::Action::Down, 1, 1)))?;
+        assert!(canopy.core.drag.is_some());
+        assert_eq!(canopy.core.drag_hover(), None);
+
+        canopy.event(Event::Mouse(at(mouse::Action::Drag, 7, 2)))?;
+        assert_eq!(canopy.core.drag_hover(), Some(bin));
+
+        canopy.event(Event::Mouse(at(mouse::Action::Up, 7, 2)))?;
+        assert!(canopy.core.drag.is_none());
+
+        let received = canopy.core.with_widget_mut(bin, |w, _| {
+            let any = w as &mut dyn Any;
+            any.downcast_mut::<DropBin>()
+                .map(|bin| bin.received.clone())
+                .unwrap_or_default()
+        })?;
+        assert_eq!(received, vec![(7, Point { x: 2, y: 2 })]);
+        assert!(canopy.core.nodes.contains_key(source));
+        Ok(())
+    }
+
+    #[test]
+    fn drag_released_outside_targets_is_dropped() -> Result<()> {
+        let mut canopy = Canopy::new();
+        let root = canopy.core.root;
+        let source = canopy.core.add_child_to_boxed(root, Box::new(DragSource))?;
+        canopy.set_root_size(Size::new(10, 4))?;
+
+        let (_, mut render) = TestRender::create();
+        canopy.render(&mut render)?;
+
+        let down = make_mouse_event(&canopy.core, source);
+        canopy.event(Event::Mouse(down))?;
+        assert!(canopy.core.drag.is_some());
+
+        let up = mouse::MouseEvent {
+            action: mouse::Action::Up,
+            ..down
+        };
+        canopy.event(Event::Mouse(up))?;
+        assert!(canopy.core.drag.is_none());
+
+        canopy.event(Event::Mouse(down))?;
+        canopy.core.remove_subtree(source)?;
+        assert!(canopy.core.drag.is_none());
+        Ok(())
+    }
+
     #[test]
     fn set_widget_resets_initialization() -> Result<()> {
         POLL_COUNT.store(0, Ordering::SeqCst);
@@ -1863,6 +3470,180 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn macros_replay_recorded_commands_and_keys() -> Result<()> {
+        run_ttree(|c, _, tree| {
+            c.keymap.bind(
+                "",
+                inputmap::InputSpec::Key('a'.into()),
+                "",
+                c.script_host.compile(r#"ba_la.c_leaf()"#)?,
+            )?;
+            c.core.set_focus(tree.a_a);
+
+            c.core.macros.start("q");
+            c.key('a')?;
+            set_outcome::<BaLa>(&mut c.core, tree.a_a, EventOutcome::Handle);
+            c.key('z')?;
+            assert_eq!(c.core.macros.stop().as_deref(), Some("q"));
+            let recorded = c.core.macros.get("q").map(<[_]>::to_vec);
+            assert_eq!(
+                recorded.as_ref().map(|steps| steps[1].clone()),
+                Some(MacroStep::Key('z'.into()))
+            );
+
+            reset_state();
+            set_outcome::<BaLa>(&mut c.core, tree.a_a, EventOutcome::Handle);
+            c.replay_macro("q", 1)?;
+            assert_eq!(
+                get_state().path,
+                vec!["ba_la.c_leaf()", "ba_la@key->handle"]
+            );
+            assert_eq!(c.core.macros.get("q").map(<[_]>::to_vec), recorded);
+
+            let json = c.core.macros.to_json()?;
+            c.core.macros.load_json(json, &c.core.commands)?;
+            assert_eq!(c.core.macros.get("q").map(<[_]>::to_vec), recorded);
+
+            assert!(c.replay_macro("missing", 1).is_err());
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn hooks_rewrite_and_swallow_events_and_commands() -> Result<()> {
+        run_ttree(|c, _, tree| {
+            c.keymap.bind(
+                "",
+                inputmap::InputSpec::Key('a'.into()),
+                "",
+                c.script_host.compile(r#"ba_la.c_leaf()"#)?,
+            )?;
+            c.core.set_focus(tree.a_a);
+            let _rewrite = c.core.on_event(|event| match event {
+                Event::Key(k) if *k == key::Key::from('x') => HookFlow::Swallow,
+                Event::Key(k) if *k == key::Key::from('b') => {
+                    *k = 'a'.into();
+                    HookFlow::Continue
+                }
+                _ => HookFlow::Continue,
+            });
+            let dispatched = Arc::new(Mutex::new(Vec::new()));
+            let log = Arc::clone(&dispatched);
+            let _log = c.core.after_command(move |cmd, result| {
+                log.lock().unwrap().push((cmd.id.0, result.is_ok()));
+            });
+
+            c.key('b')?;
+            assert_eq!(get_state().path, vec!["ba_la.c_leaf()"]);
+            assert_eq!(*dispatched.lock().unwrap(), [("ba_la::c_leaf", true)]);
+
+            reset_state();
+            c.key('x')?;
+            assert!(get_state().path.is_empty());
+
+            let _swallow = c.core.on_command(|cmd| {
+                if cmd.id.0 == "ba_la::c_leaf" {
+                    HookFlow::Swallow
+                } else {
+                    HookFlow::Continue
+                }
+            });
+            c.key('a')?;
+            assert!(get_state().path.is_empty());
+            assert_eq!(dispatched.lock().unwrap().len(), 1);
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn event_sources_fan_into_the_runloop_channel() -> Result<()> {
+        let mut canopy = Canopy::new();
+        let rx = canopy.event_rx.take().unwrap();
+        canopy
+            .add_event_source("watcher", |tx| tx.send(7u32).unwrap())
+            .join()
+            .unwrap();
+        canopy
+            .event_sender("socket")
+            .send_event(Event::FocusGained)?;
+
+        let Event::App(app) = rx.recv().unwrap() else {
+            panic!("expected an app event");
+        };
+        assert_eq!(&*app.source, "watcher");
+        assert_eq!(app.payload::<u32>(), Some(&7));
+        assert_eq!(app.payload::<String>(), None);
+        assert!(matches!(rx.recv().unwrap(), Event::FocusGained));
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+        let hook = canopy.core.after_event(move |event, _| {
+            if let Event::App(app) = event {
+                log.lock().unwrap().push(app.source.to_string());
+            }
+        });
+        canopy.event(Event::App(app.clone()))?;
+        assert_eq!(*seen.lock().unwrap(), ["watcher"]);
+
+        drop(hook);
+        canopy.event(Event::App(app))?;
+        assert_eq!(*seen.lock().unwrap(), ["watcher"]);
+        Ok(())
+    }
+
+    #[test]
+    fn poll_wakers_request_a_poll_of_their_node() -> Result<()> {
+        let mut canopy = Canopy::new();
+        let rx = canopy.event_rx.take().unwrap();
+        let root = canopy.core.root_id();
+        let waker = canopy.with_root_context(|ctx| Ok(ctx.poll_waker()))?;
+        thread::spawn(move || waker.wake()).join().unwrap();
+        assert!(matches!(rx.recv().unwrap(), Event::Poll(ids) if ids == [root]));
+
+        drop(rx);
+        let waker = canopy.with_root_context(|ctx| Ok(ctx.poll_waker()))?;
+        assert!(!waker.wake());
+        Ok(())
+    }
+
+    #[test]
+    fn exit_with_value_and_exit_hooks() -> Result<()> {
+        let mut canopy = Canopy::new();
+        let flushed = Arc::new(Mutex::new(Vec::new()));
+        for name in ["first", "second"] {
+            let log = Arc::clone(&flushed);
+            canopy.core.on_exit(move |_ctx| {
+                log.lock().unwrap().push(name);
+                Ok(())
+            });
+        }
+
+        canopy.with_root_context(|ctx| {
+            ctx.exit_with(String::from("picked"));
+            ctx.exit(3);
+            Ok(())
+        })?;
+        assert_eq!(canopy.core.take_exit_request(), Some(0));
+        assert_eq!(canopy.take_exit_value::<u32>(), None);
+
+        canopy.with_root_context(|ctx| {
+            ctx.exit_with(String::from("picked"));
+            Ok(())
+        })?;
+        assert_eq!(
+            canopy.take_exit_value::<String>().as_deref(),
+            Some("picked")
+        );
+
+        canopy.run_exit_hooks()?;
+        canopy.run_exit_hooks()?;
+        assert_eq!(*flushed.lock().unwrap(), ["first", "second"]);
+        Ok(())
+    }
+
     #[test]
     fn input_mode_binding_target_switches_modes() -> Result<()> {
         let mut canopy = Canopy::new();
@@ -1880,6 +3661,31 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn binding_catalog_lists_every_mode_and_tracks_keymap_changes() -> Result<()> {
+        let mut canopy = Canopy::new();
+        canopy.bind_input_mode("", inputmap::InputSpec::Key('i'.into()), "", "insert")?;
+        canopy.bind_input_mode("insert", inputmap::InputSpec::Key('n'.into()), "", "")?;
+
+        let catalog = canopy.binding_catalog();
+        let modes: Vec<&str> = catalog.iter().map(|b| b.mode.as_str()).collect();
+        assert_eq!(modes, ["", "insert"]);
+        assert_eq!(catalog[0].label, "set input mode: insert");
+        assert_eq!(catalog[1].label, "set input mode: default");
+
+        assert!(canopy.core.binding_catalog.with(Vec::is_empty));
+        canopy.core.binding_catalog_requested = true;
+        canopy.set_root_size(Size::new(10, 2))?;
+        let (_, mut render) = TestRender::create();
+        canopy.render(&mut render)?;
+        assert_eq!(canopy.core.binding_catalog.with(Vec::len), 2);
+
+        canopy.bind_input_mode("", inputmap::InputSpec::Key('v'.into()), "", "visual")?;
+        canopy.render(&mut render)?;
+        assert_eq!(canopy.core.binding_catalog.with(Vec::len), 3);
+        Ok(())
+    }
+
     #[test]
     fn route_trace_records_unhandled_key_pipeline() -> Result<()> {
         run_ttree(|c, _, tree| {
@@ -1901,18 +3707,321 @@ mod tests {
         Ok(())
     }
 
-    #[test]
-    fn register_default_bindings_is_idempotent_for_identical_scripts() -> Result<()> {
-        run_ttree(|c, _, _| {
-            c.register_default_bindings("r", "canopy.log(\"once\")")?;
-            c.register_default_bindings("r", "canopy.log(\"once\")")?;
+    #[test]
+    fn register_default_bindings_is_idempotent_for_identical_scripts() -> Result<()> {
+        run_ttree(|c, _, _| {
+            c.register_default_bindings("r", "canopy.log(\"once\")")?;
+            c.register_default_bindings("r", "canopy.log(\"once\")")?;
+
+            let err = c
+                .register_default_bindings("r", "canopy.log(\"twice\")")
+                .unwrap_err();
+            assert!(matches!(err, error::Error::Invalid(_)));
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    struct Counter {
+        count: usize,
+    }
+
+    #[derive_commands]
+    impl Counter {
+        #[command(name = "bump", key = "j", key = "k")]
+        /// Add one to the count.
+        fn increment(&mut self, _c: &mut dyn Context) -> Result<()> {
+            self.count += 1;
+            Ok(())
+        }
+    }
+
+    impl Widget for Counter {
+        fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+            true
+        }
+    }
+
+    impl Loader for Counter {
+        fn load(c: &mut Canopy) -> Result<()> {
+            c.add_commands::<Self>()
+        }
+    }
+
+    #[test]
+    fn command_keys_become_owner_default_bindings() -> Result<()> {
+        let mut h = Harness::builder(Counter { count: 0 }).size(10, 2).build()?;
+        assert!(h.canopy.core.commands.get("counter::bump").is_some());
+        h.with_root_context(|_: &mut Counter, ctx| {
+            ctx.set_focus(ctx.node_id());
+            Ok(())
+        })?;
+        h.script("counter.default_bindings()")?;
+        h.press("j k")?;
+        assert_eq!(h.with_root_widget(|w: &mut Counter| w.count), 2);
+        Ok(())
+    }
+
+    /// Root holding two counters under explicit node names.
+    struct CounterPair;
+
+    #[derive_commands]
+    impl CounterPair {}
+
+    impl Widget for CounterPair {
+        fn on_mount(&mut self, c: &mut dyn Context) -> Result<()> {
+            let parent = c.node_id();
+            c.add_named_child_to(parent, "left", Counter { count: 0 })?;
+            c.add_named_child_to(parent, "right", Counter { count: 0 })?;
+            c.add_child_to(parent, Counter { count: 0 })?;
+            Ok(())
+        }
+    }
+
+    impl Loader for CounterPair {
+        fn load(c: &mut Canopy) -> Result<()> {
+            c.add_commands::<Self>()?;
+            Counter::load(c)
+        }
+    }
+
+    #[test]
+    fn named_nodes_take_distinct_bindings() -> Result<()> {
+        let mut h = Harness::builder(CounterPair).size(10, 2).build()?;
+        let left = h.find_node("counter_pair/left").expect("left counter");
+        let right = h.find_node("counter_pair/right").expect("right counter");
+        h.script(
+            "canopy.bind_with(\"j\", { path = \"left\" }, function() counter.bump() end)\n\
+             canopy.bind_with(\"j\", { path = \"right\" }, function() \
+             counter.bump(); counter.bump() end)",
+        )?;
+        h.canopy.core.set_focus(left);
+        h.press("j")?;
+        h.canopy.core.set_focus(right);
+        h.press("j")?;
+        assert_eq!(h.with_widget(left, |w: &mut Counter| w.count), 1);
+        assert_eq!(h.with_widget(right, |w: &mut Counter| w.count), 2);
+
+        let err = h
+            .canopy
+            .core
+            .set_name_of(right, NodeName::new("left")?)
+            .unwrap_err();
+        assert!(matches!(err, Error::DuplicateNodeName(_)));
+        let err = h
+            .canopy
+            .core
+            .set_name_of(right, NodeName::new("counter")?)
+            .unwrap_err();
+        assert!(matches!(err, Error::DuplicateNodeName(_)));
+
+        h.canopy
+            .core
+            .replace_widget_keep_children(left, Counter { count: 5 })?;
+        assert_eq!(h.canopy.core.nodes[left].name(), &"left");
+        Ok(())
+    }
+
+    #[test]
+    fn node_ids_resolve_weakly() -> Result<()> {
+        let mut h = Harness::builder(CounterPair).size(10, 2).build()?;
+        let left = h.find_node("counter_pair/left").expect("left counter");
+        h.with_root_context(|_: &mut CounterPair, ctx| {
+            let location = ctx.node_location(left).expect("attached node");
+            assert_eq!(location, ctx.node_path(ctx.root_id(), left));
+            let bumped = ctx.with_node_mut(left, |widget, c| {
+                assert_eq!(c.node_id(), left);
+                let counter = (widget as &mut dyn Any)
+                    .downcast_mut::<Counter>()
+                    .expect("counter");
+                counter.increment(c)?;
+                Ok(counter.count)
+            })?;
+            assert_eq!(bumped, Some(1));
+
+            ctx.remove_subtree(left)?;
+            assert!(ctx.node_location(left).is_none());
+            assert_eq!(ctx.with_node_mut(left, |_, _| Ok(()))?, None);
+            Ok(())
+        })
+    }
+
+    /// Widget that records string messages and answers "ping" with "pong" to inbox `a`.
+    struct Inbox {
+        received: Vec<String>,
+    }
+
+    #[derive_commands]
+    impl Inbox {}
+
+    impl Widget for Inbox {
+        fn handle_message(&mut self, msg: Box<dyn Any>, ctx: &mut dyn Context) -> Result<()> {
+            if let Ok(text) = msg.downcast::<String>() {
+                if *text == "ping" {
+                    ctx.send_to("mailroom/a", Box::new("pong".to_string()));
+                }
+                self.received.push(*text);
+            }
+            Ok(())
+        }
+    }
+
+    /// Root that sends messages to its named inboxes on any key.
+    struct Mailroom;
+
+    #[derive_commands]
+    impl Mailroom {}
+
+    impl Widget for Mailroom {
+        fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+            true
+        }
+
+        fn on_mount(&mut self, c: &mut dyn Context) -> Result<()> {
+            let parent = c.node_id();
+            c.add_named_child_to(parent, "a", Inbox { received: vec![] })?;
+            c.add_named_child_to(parent, "b", Inbox { received: vec![] })?;
+            Ok(())
+        }
+
+        fn on_event(&mut self, event: &Event, c: &mut dyn Context) -> Result<EventOutcome> {
+            if !matches!(event, Event::Key(_)) {
+                return Ok(EventOutcome::Ignore);
+            }
+            let a = c.find_node("mailroom/a").expect("inbox a");
+            c.send_to(a, Box::new("hello".to_string()));
+            c.send_to("mailroom/b", Box::new("ping".to_string()));
+            c.send_to("mailroom/missing", Box::new("lost".to_string()));
+            Ok(EventOutcome::Handle)
+        }
+    }
+
+    impl Loader for Mailroom {
+        fn load(c: &mut Canopy) -> Result<()> {
+            c.add_commands::<Self>()?;
+            c.add_commands::<Inbox>()
+        }
+    }
+
+    #[test]
+    fn messages_reach_nodes_by_id_and_path() -> Result<()> {
+        let mut h = Harness::builder(Mailroom).size(10, 2).build()?;
+        let a = h.find_node("mailroom/a").expect("inbox a");
+        let b = h.find_node("mailroom/b").expect("inbox b");
+        h.canopy.core.set_focus(h.root);
+        h.press("x")?;
+        assert_eq!(
+            h.with_widget(a, |w: &mut Inbox| w.received.clone()),
+            vec!["hello".to_string(), "pong".to_string()]
+        );
+        assert_eq!(
+            h.with_widget(b, |w: &mut Inbox| w.received.clone()),
+            vec!["ping".to_string()]
+        );
+        assert!(h.canopy.core.pending_messages.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn message_senders_deliver_from_other_threads() -> Result<()> {
+        let mut h = Harness::builder(Mailroom).size(10, 2).build()?;
+        let b = h.find_node("mailroom/b").expect("inbox b");
+        let sender = h.canopy.message_sender("mailroom/b");
+        thread::spawn(move || sender.send("hello".to_string()))
+            .join()
+            .unwrap()?;
+        assert!(h.with_widget(b, |w: &mut Inbox| w.received.is_empty()));
+        h.canopy.service_automation();
+        assert_eq!(
+            h.with_widget(b, |w: &mut Inbox| w.received.clone()),
+            vec!["hello".to_string()]
+        );
+        Ok(())
+    }
+
+    /// Widget that counts the events it receives.
+    struct Tally {
+        seen: usize,
+    }
+
+    #[derive_commands]
+    impl Tally {}
+
+    impl Widget for Tally {
+        fn on_event(&mut self, _event: &Event, _c: &mut dyn Context) -> Result<EventOutcome> {
+            self.seen += 1;
+            Ok(EventOutcome::Consume)
+        }
+    }
+
+    #[test]
+    fn broadcast_reaches_every_matching_node() -> Result<()> {
+        let mut h = Harness::builder(Mailroom).size(10, 2).build()?;
+        let (first, second, other) = h.with_root_context(|_: &mut Mailroom, ctx| {
+            let parent = ctx.node_id();
+            let first = ctx.add_child_to(parent, Tally { seen: 0 })?;
+            let second = ctx.add_child_to(parent, Tally { seen: 0 })?;
+            let other = ctx.add_named_child_to(parent, "other", Tally { seen: 0 })?;
+            assert_eq!(
+                ctx.broadcast(&Event::FocusGained, "mailroom/tally")?,
+                EventOutcome::Consume
+            );
+            assert_eq!(
+                ctx.broadcast(&Event::FocusGained, "mailroom")?,
+                EventOutcome::Ignore
+            );
+            Ok((first, second, other))
+        })?;
+        assert_eq!(h.with_widget(first, |w: &mut Tally| w.seen), 1);
+        assert_eq!(h.with_widget(second, |w: &mut Tally| w.seen), 1);
+        assert_eq!(h.with_widget(other, |w: &mut Tally| w.seen), 0);
+        Ok(())
+    }
+
+    /// Focusable view over six lines that scrolls down on any key and records the
+    /// position label of each scroll event.
+    struct Scroller {
+        labels: Vec<String>,
+    }
 
-            let err = c
-                .register_default_bindings("r", "canopy.log(\"twice\")")
-                .unwrap_err();
-            assert!(matches!(err, error::Error::Invalid(_)));
-            Ok(())
-        })?;
+    #[derive_commands]
+    impl Scroller {}
+
+    impl Widget for Scroller {
+        fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+            true
+        }
+
+        fn canvas(&self, view: Size<u32>, _ctx: &CanvasContext) -> Size<u32> {
+            Size::new(view.w, 6)
+        }
+
+        fn on_event(&mut self, event: &Event, c: &mut dyn Context) -> Result<EventOutcome> {
+            match event {
+                Event::Key(_) => {
+                    c.scroll_down();
+                    Ok(EventOutcome::Handle)
+                }
+                Event::Scroll(node) if *node == c.node_id() => {
+                    self.labels.push(c.view().scroll_label());
+                    Ok(EventOutcome::Handle)
+                }
+                _ => Ok(EventOutcome::Ignore),
+            }
+        }
+    }
+
+    impl Loader for Scroller {}
+
+    #[test]
+    fn scrolling_reports_position() -> Result<()> {
+        let mut h = Harness::builder(Scroller { labels: Vec::new() })
+            .size(4, 2)
+            .build()?;
+        h.render()?;
+        h.press("j j j j j")?;
+        let labels = h.with_root_widget(|w: &mut Scroller| w.labels.clone());
+        assert_eq!(labels, ["25%", "50%", "75%", "BOT"]);
         Ok(())
     }
 
@@ -2088,6 +4197,229 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn run_external_restores_backend_on_failure() -> Result<()> {
+        let mut canopy = Canopy::new();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        canopy.register_backend(RecordingBackend {
+            calls: Arc::clone(&calls),
+        });
+
+        let result = canopy.with_root_context(|ctx| {
+            ctx.run_external(&mut process::Command::new("canopy-no-such-command"))
+        });
+
+        assert!(matches!(result, Err(Error::Io(_))));
+        assert_eq!(*calls.lock().unwrap(), vec!["stop", "start"]);
+        assert!(canopy.core.full_render_requested);
+        Ok(())
+    }
+
+    #[test]
+    fn suspend_repaints_at_the_resumed_terminal_size() -> Result<()> {
+        let mut canopy = Canopy::new();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        canopy.register_backend(RecordingBackend {
+            calls: Arc::clone(&calls),
+        });
+        canopy.set_root_size(Size::new(10, 4))?;
+        let (_, mut render) = TestRender::create();
+        canopy.render(&mut render)?;
+
+        canopy.with_root_context(|ctx| ctx.suspend())?;
+        assert_eq!(*calls.lock().unwrap(), vec!["suspend"]);
+        assert!(canopy.render_if_pending(&mut render)?);
+        assert_eq!(canopy.buf().unwrap().size(), Size::new(6, 3));
+        Ok(())
+    }
+
+    #[test]
+    fn max_fps_defers_renders_within_a_frame() -> Result<()> {
+        let mut canopy = Canopy::new();
+        canopy.set_root_size(Size::new(10, 4))?;
+        canopy.set_max_fps(Some(10));
+
+        let (_, mut render) = TestRender::create();
+        canopy.render(&mut render)?;
+        let rendered_at = Instant::now();
+        assert_eq!(canopy.deferred_render_deadline(rendered_at), None);
+
+        canopy.set_root_size(Size::new(12, 4))?;
+        let deadline = canopy
+            .deferred_render_deadline(rendered_at)
+            .expect("render should be deferred");
+        assert!(deadline > rendered_at);
+        assert_eq!(
+            canopy.deferred_render_deadline(rendered_at + Duration::from_millis(200)),
+            None
+        );
+
+        canopy.set_max_fps(None);
+        assert_eq!(canopy.deferred_render_deadline(rendered_at), None);
+        Ok(())
+    }
+
+    #[test]
+    fn timers_fire_once_on_their_node_until_cancelled() -> Result<()> {
+        let mut canopy = Canopy::new();
+        let root = canopy.core.root;
+        let alarm = canopy
+            .core
+            .add_child_to_boxed(root, Box::new(Alarm { fired: Vec::new() }))?;
+        canopy.use_virtual_clock();
+        canopy.set_root_size(Size::new(3, 2))?;
+        let (_, mut render) = TestRender::create();
+        canopy.render(&mut render)?;
+
+        canopy.advance_clock(Duration::from_millis(100))?;
+        let fired = canopy.core.with_widget_mut(alarm, |w, _| {
+            let any = w as &mut dyn Any;
+            any.downcast_mut::<Alarm>().map(|a| a.fired.clone())
+        })?;
+        assert_eq!(fired, Some(vec![1, 3]));
+        assert!(canopy.core.timers.is_active(alarm, 2));
+        assert!(!canopy.core.timers.is_active(alarm, 3));
+        Ok(())
+    }
+
+    #[test]
+    fn damaged_polls_redraw_only_the_damaged_region() -> Result<()> {
+        let mut canopy = Canopy::new();
+        let root = canopy.core.root;
+        let ticker = canopy
+            .core
+            .add_child_to_boxed(root, Box::new(Ticker { ticks: 0 }))?;
+        canopy.set_root_size(Size::new(3, 2))?;
+
+        let (_, mut render) = TestRender::create();
+        canopy.render(&mut render)?;
+        assert_eq!(canopy.buf().unwrap().screen_text(), "1  \n1  ");
+
+        canopy.event(Event::Poll(vec![ticker]))?;
+        assert!(!canopy.render_pending);
+        assert!(canopy.render_if_pending(&mut render)?);
+        assert_eq!(canopy.buf().unwrap().screen_text(), "2  \n1  ");
+
+        canopy.render(&mut render)?;
+        assert_eq!(canopy.buf().unwrap().screen_text(), "2  \n2  ");
+        Ok(())
+    }
+
+    #[test]
+    fn handled_keys_redraw_only_the_handling_leaf() -> Result<()> {
+        struct Presses(Arc<AtomicUsize>);
+
+        impl Widget for Presses {
+            fn layout(&self) -> Layout {
+                Layout::fill()
+            }
+
+            fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+                true
+            }
+
+            fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+                let text = self.0.load(Ordering::SeqCst).to_string();
+                r.text("", ctx.view().outer_rect_local().line(0), &text)
+            }
+
+            fn on_event(&mut self, event: &Event, _ctx: &mut dyn Context) -> Result<EventOutcome> {
+                if let Event::Key(_) = event {
+                    self.0.fetch_add(1, Ordering::SeqCst);
+                    return Ok(EventOutcome::Handle);
+                }
+                Ok(EventOutcome::Ignore)
+            }
+        }
+
+        let presses = Arc::new(AtomicUsize::new(0));
+        let mut canopy = Canopy::new();
+        let root = canopy.core.root;
+        canopy
+            .core
+            .set_layout_of(root, Layout::row().flex_horizontal(1).flex_vertical(1))?;
+        let left = canopy
+            .core
+            .add_child_to_boxed(root, Box::new(Presses(Arc::clone(&presses))))?;
+        let _right = canopy
+            .core
+            .add_child_to_boxed(root, Box::new(Presses(presses)))?;
+        canopy.core.set_focus(left);
+        canopy.set_root_size(Size::new(4, 1))?;
+
+        let (_, mut render) = TestRender::create();
+        canopy.render(&mut render)?;
+        assert_eq!(canopy.buf().unwrap().screen_text(), "0 0 ");
+
+        // Both leaves render the shared count, but only the one that handled the key
+        // redraws.
+        canopy.key('x')?;
+        assert!(!canopy.render_pending);
+        assert!(canopy.render_if_pending(&mut render)?);
+        assert_eq!(canopy.buf().unwrap().screen_text(), "1 0 ");
+        Ok(())
+    }
+
+    #[test]
+    fn cursor_mode_selects_drawn_and_terminal_cursors() -> Result<()> {
+        let mut canopy = Canopy::new();
+        let root = canopy.core.root;
+        let node = canopy
+            .core
+            .add_child_to_boxed(root, Box::new(MultiCursor))?;
+        canopy.core.set_focus(node);
+        canopy.set_root_size(Size::new(3, 2))?;
+        let inverted = |canopy: &Canopy, x, y| {
+            let buf = canopy.buf().unwrap();
+            buf.get(Point { x, y }).unwrap().style != buf.get(Point { x: 1, y: 0 }).unwrap().style
+        };
+
+        let mut backend = CursorBackend::default();
+        canopy.render(&mut backend)?;
+        assert_eq!(canopy.cursor_mode(), cursor::CursorMode::Virtual);
+        assert!(inverted(&canopy, 0, 0));
+        assert!(inverted(&canopy, 2, 1));
+        assert_eq!(backend.shown, [None]);
+
+        canopy.set_cursor_mode(cursor::CursorMode::Hardware);
+        assert!(canopy.render_if_pending(&mut backend)?);
+        assert!(!inverted(&canopy, 0, 0));
+        assert!(inverted(&canopy, 2, 1));
+        let shown = backend.shown.last().unwrap().unwrap();
+        assert_eq!(shown.location, Point { x: 0, y: 0 });
+        assert_eq!(shown.shape, cursor::CursorShape::Line);
+        assert!(backend.calls.contains(&"text"));
+        assert_eq!(backend.calls.iter().filter(|c| **c == "flush").count(), 1);
+        assert_eq!(
+            backend.calls[backend.calls.len() - 2..],
+            ["show_cursor", "flush"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn profiler_records_frames_and_node_timings() -> Result<()> {
+        run_ttree(|c, mut tr, tree| {
+            tr.render(c)?;
+            assert!(c.core.profiler.is_none());
+
+            c.core.set_profiling(true);
+            tr.render(c)?;
+            tr.render(c)?;
+            let profiler = c.core.profiler.as_ref().expect("profiling enabled");
+            assert_eq!(profiler.durations().len(), 2);
+            let frame = profiler.last();
+            for id in [tree.root, tree.a, tree.a_a] {
+                assert!(frame.nodes.contains_key(&id));
+            }
+            assert!(frame.duration >= frame.nodes[&tree.a_a].total());
+
+            c.core.set_profiling(false);
+            assert!(c.core.profiler.is_none());
+            Ok(())
+        })
+    }
+
     #[test]
     fn tresize() -> Result<()> {
         run_ttree(|c, mut tr, tree| {
@@ -2198,6 +4530,94 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn focus_scope_confines_traversal() -> Result<()> {
+        run_ttree(|c, _, tree| {
+            c.core.set_focus(tree.a_a);
+            c.core.push_focus_scope(tree.b);
+            assert!(c.core.is_focused(tree.b));
+
+            c.core.focus_next(c.core.root);
+            assert!(c.core.is_focused(tree.b_a));
+            c.core.focus_next(c.core.root);
+            assert!(c.core.is_focused(tree.b_b));
+            c.core.focus_next(c.core.root);
+            assert!(c.core.is_focused(tree.b));
+            c.core.focus_prev(c.core.root);
+            assert!(c.core.is_focused(tree.b_b));
+
+            assert!(c.core.pop_focus_scope(tree.b));
+            c.core.focus_next(c.core.root);
+            assert!(c.core.is_focused(tree.root));
+
+            c.core.push_focus_scope(tree.b);
+            c.core.remove_subtree(tree.b)?;
+            assert_eq!(c.core.focus_scope(), None);
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn focus_history_restores_previous_focus() -> Result<()> {
+        run_ttree(|c, _, tree| {
+            c.core.set_focus(tree.a_b);
+            c.core.focus_push();
+            c.core.set_focus(tree.b_a);
+            c.core.focus_push();
+            c.core.set_focus(tree.b_b);
+
+            c.core.remove_subtree(tree.b_a)?;
+            assert!(c.core.focus_pop());
+            assert!(c.core.is_focused(tree.a_b));
+            assert!(!c.core.focus_pop());
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn tab_index_orders_focus_traversal() -> Result<()> {
+        run_ttree(|c, _, tree| {
+            c.core.set_tab_index(tree.b_b, Some(1))?;
+            c.core.set_tab_index(tree.a_a, Some(2))?;
+
+            c.core.focus_next(c.core.root);
+            assert!(c.core.is_focused(tree.b_b));
+            c.core.focus_next(c.core.root);
+            assert!(c.core.is_focused(tree.a_a));
+            c.core.focus_next(c.core.root);
+            assert!(c.core.is_focused(tree.root));
+            c.core.focus_prev(c.core.root);
+            assert!(c.core.is_focused(tree.a_a));
+
+            c.core.set_focus(tree.b_a);
+            c.core.focus_next(c.core.root);
+            assert!(c.core.is_focused(tree.b_b));
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn focus_path_focuses_node_by_path() -> Result<()> {
+        run_ttree(|c, _, tree| {
+            assert!(c.with_root_context(|ctx| ctx.focus_path("r/bb/bb_lb"))?);
+            assert!(!c.with_root_context(|ctx| ctx.focus_path("r/missing"))?);
+            assert!(c.core.is_focused(tree.b_b));
+
+            FocusCommands::load(c)?;
+            let to = c.core.commands.get("focus::to").unwrap();
+            let focused = c.with_root_context(|ctx| {
+                Ok(ctx.dispatch_command(&to.call_with(["r/bb/bb_la"]).invocation())?)
+            })?;
+            assert_eq!(focused, ArgValue::Bool(true));
+            assert!(c.core.is_focused(tree.b_a));
+            Ok(())
+        })?;
+        Ok(())
+    }
+
     #[test]
     fn focus_prev() -> Result<()> {
         run_ttree(|c, _, tree| {
@@ -2330,6 +4750,114 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn graft_delegates_render_and_input() -> Result<()> {
+        struct Counter(u32);
+
+        impl Widget for Counter {
+            fn layout(&self) -> Layout {
+                Layout::fill()
+            }
+
+            fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+                true
+            }
+
+            fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+                let text = format!("n={}", self.0);
+                r.text("", ctx.view().outer_rect_local().line(1), &text)
+            }
+
+            fn on_event(&mut self, event: &Event, _ctx: &mut dyn Context) -> Result<EventOutcome> {
+                if let Event::Key(k) = event
+                    && *k == key::Key::from('x')
+                {
+                    self.0 += 1;
+                    return Ok(EventOutcome::Handle);
+                }
+                Ok(EventOutcome::Ignore)
+            }
+        }
+
+        let mut app = Canopy::new();
+        app.core.replace_subtree(app.core.root, Counter(0))?;
+        let mut canopy = Canopy::new();
+        let graft = canopy.graft(app);
+        canopy.set_root_child(graft)?;
+        canopy.set_root_size(Size::new(8, 3))?;
+        canopy.core.set_focus(graft.into());
+
+        let (_, mut tr) = TestRender::create();
+        canopy.render(&mut tr)?;
+        let screen = |c: &Canopy| c.buf().map(TermBuf::screen_text).unwrap_or_default();
+        assert!(screen(&canopy).contains("n=0"));
+
+        canopy.key('x')?;
+        canopy.render(&mut tr)?;
+        assert!(screen(&canopy).contains("n=1"));
+        assert!(
+            canopy
+                .grafted(graft)
+                .is_some_and(|app| app.root_size == Some(Size::new(8, 3)))
+        );
+
+        canopy.key('y')?;
+        assert_eq!(
+            canopy.route_trace().last().map(|entry| entry.phase),
+            Some(RoutePhase::Unhandled)
+        );
+
+        canopy.core.remove_subtree(graft)?;
+        canopy.render(&mut tr)?;
+        assert!(canopy.grafted(graft).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn grafted_timers_run_on_the_host_clock() -> Result<()> {
+        struct Ticker(u32);
+
+        impl Widget for Ticker {
+            fn layout(&self) -> Layout {
+                Layout::fill()
+            }
+
+            fn poll(&mut self, ctx: &mut dyn Context) -> Option<Duration> {
+                ctx.set_timer(1, Duration::from_millis(50), true);
+                None
+            }
+
+            fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+                let text = format!("n={}", self.0);
+                r.text("", ctx.view().outer_rect_local().line(0), &text)
+            }
+
+            fn on_event(&mut self, event: &Event, _ctx: &mut dyn Context) -> Result<EventOutcome> {
+                if let Event::Timer(1) = event {
+                    self.0 += 1;
+                }
+                Ok(EventOutcome::Handle)
+            }
+        }
+
+        let mut app = Canopy::new();
+        app.core.replace_subtree(app.core.root, Ticker(0))?;
+        let mut canopy = Canopy::new();
+        canopy.use_virtual_clock();
+        let graft = canopy.graft(app);
+        canopy.set_root_child(graft)?;
+        canopy.set_root_size(Size::new(8, 1))?;
+
+        let (_, mut tr) = TestRender::create();
+        canopy.render(&mut tr)?;
+        canopy.advance_clock(Duration::from_millis(120))?;
+        assert!(canopy.core.has_damage());
+        canopy.render(&mut tr)?;
+        let screen = canopy.buf().map(TermBuf::screen_text).unwrap_or_default();
+        assert!(screen.contains("n=2"), "{screen}");
+        Ok(())
+    }
+
     #[test]
     fn zero_size_child_ok() -> Result<()> {
         struct Child;
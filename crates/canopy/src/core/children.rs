@@ -6,10 +6,28 @@ use std::{
 };
 
 use crate::{
-    Context, NodeId, TypedId, Widget,
+    Canopy, Context, NodeId, TypedId, Widget,
     error::{Error, Result},
 };
 
+/// A fixed set of child widgets mounted together, in declaration order.
+///
+/// Derive it with `#[derive(ChildSet)]` on a struct whose named fields are the child
+/// widgets, then mount it from `Widget::on_mount` with `Context::mount_children`. Each
+/// field becomes a keyed child, so forgetting to add one or adding them in the wrong
+/// focus order is no longer possible.
+pub trait ChildSet {
+    /// Child keys, in mount order.
+    const KEYS: &'static [&'static str];
+
+    /// Add every child to `parent` under its key, in order, and return the new node IDs.
+    fn mount(self, c: &mut dyn Context, parent: NodeId) -> Result<Vec<NodeId>>;
+
+    /// Register the commands of every child type, running the child's own `Loader` where
+    /// the field is marked `#[child(load)]`.
+    fn load(c: &mut Canopy) -> Result<()>;
+}
+
 /// Policy for removing children that are no longer desired.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RemovePolicy {
@@ -183,3 +201,151 @@ where
         Ok(ordered)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ChildSet, Loader, ReadContext, command, derive_commands, geom::Line, layout::Layout,
+        render::Render, state::NodeName, testing::harness::Harness,
+    };
+
+    struct Label(&'static str);
+
+    #[derive_commands]
+    impl Label {
+        #[command]
+        /// Do nothing.
+        fn poke(&mut self, _c: &mut dyn Context) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Widget for Label {
+        fn render(&mut self, r: &mut Render, _ctx: &dyn ReadContext) -> Result<()> {
+            r.text("base", Line::new(0, 0, 8), self.0)
+        }
+
+        fn name(&self) -> NodeName {
+            NodeName::convert(self.0)
+        }
+    }
+
+    #[derive(ChildSet)]
+    struct PaneChildren {
+        header: Label,
+        #[child(key = "main")]
+        body: Label,
+        footer: Label,
+    }
+
+    #[derive(Loader)]
+    #[loader(children(PaneChildren))]
+    struct Pane;
+
+    #[derive_commands]
+    impl Pane {}
+
+    impl Widget for Pane {
+        fn layout(&self) -> Layout {
+            Layout::column()
+        }
+
+        fn render(&mut self, _r: &mut Render, _ctx: &dyn ReadContext) -> Result<()> {
+            Ok(())
+        }
+
+        fn on_mount(&mut self, c: &mut dyn Context) -> Result<()> {
+            c.mount_children(PaneChildren {
+                header: Label("head"),
+                body: Label("body"),
+                footer: Label("foot"),
+            })?;
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct Tree {
+        bound: Vec<NodeId>,
+    }
+
+    impl Widget for Tree {
+        fn render(&mut self, _r: &mut Render, _ctx: &dyn ReadContext) -> Result<()> {
+            Ok(())
+        }
+
+        fn on_mount(&mut self, c: &mut dyn Context) -> Result<()> {
+            crate::view!(c;
+                Label("box") {
+                    #[key = "first"]
+                    first = Label("one"),
+                    Label("two"),
+                },
+                #[layout(Layout::fill().fixed_height(1))]
+                #[bind("x", "label::poke()")]
+                last = Label("three"),
+            );
+            self.bound = vec![first.into(), last.into()];
+            Ok(())
+        }
+    }
+
+    impl Loader for Tree {
+        fn load(c: &mut Canopy) -> Result<()> {
+            c.add_commands::<Label>()
+        }
+    }
+
+    #[test]
+    fn view_builds_nested_children() -> Result<()> {
+        let mut h = Harness::builder(Tree::default()).size(10, 3).build()?;
+        h.render()?;
+        let root = h.root;
+        let children = h.canopy.core.nodes[root].children().to_vec();
+        assert_eq!(children.len(), 2);
+        let grandchildren = h.canopy.core.nodes[children[0]].children().to_vec();
+        assert_eq!(grandchildren.len(), 2);
+        assert_eq!(
+            h.canopy.core.nodes[children[1]].layout,
+            Layout::fill().fixed_height(1)
+        );
+        let bound = h.with_root_widget(|w: &mut Tree| w.bound.clone());
+        assert_eq!(bound, [grandchildren[0], children[1]]);
+        h.with_root_context(|_: &mut Tree, ctx| {
+            assert_eq!(
+                ctx.child_keyed_in(children[0], "first"),
+                Some(grandchildren[0])
+            );
+            Ok(())
+        })?;
+        let bound = h
+            .canopy
+            .binding_catalog()
+            .into_iter()
+            .find(|binding| binding.path_filter == "/tree/three")
+            .expect("view binding installed");
+        assert!(matches!(
+            bound.target,
+            crate::inputmap::BindingTarget::Command(ref command) if command.id.0 == "label::poke"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn derived_children_mount_in_field_order() -> Result<()> {
+        assert_eq!(PaneChildren::KEYS, ["header", "main", "footer"]);
+        let mut h = Harness::builder(Pane).size(10, 3).build()?;
+        assert!(h.canopy.core.commands.get("label::poke").is_some());
+        h.render()?;
+        h.with_root_context(|_: &mut Pane, ctx| {
+            let keyed: Vec<_> = PaneChildren::KEYS
+                .iter()
+                .filter_map(|key| ctx.child_keyed(key))
+                .collect();
+            assert_eq!(ctx.children(), keyed);
+            Ok(())
+        })?;
+        Ok(())
+    }
+}
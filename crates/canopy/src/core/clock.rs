@@ -0,0 +1,24 @@
+use std::time::Instant;
+
+/// Source of the current time for polls and timers.
+///
+/// A virtual clock moves only when advanced, so polls and timers fire at exact,
+/// repeatable points.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum Clock {
+    /// Wall-clock time.
+    #[default]
+    Real,
+    /// A fixed instant that moves only when advanced.
+    Virtual(Instant),
+}
+
+impl Clock {
+    /// Return the current time.
+    pub(crate) fn now(&self) -> Instant {
+        match self {
+            Self::Real => Instant::now(),
+            Self::Virtual(now) => *now,
+        }
+    }
+}
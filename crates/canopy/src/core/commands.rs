@@ -1,5 +1,6 @@
 use std::{
     any::{Any, type_name},
+    borrow::Cow,
     collections::{BTreeMap, HashMap},
     fmt,
 };
@@ -9,7 +10,7 @@ use serde_json::{Map as JsonMap, Number as JsonNumber, Value as JsonValue};
 
 use crate::{
     CommandEnum, Context,
-    core::{Core, NodeId, context::CoreContext},
+    core::{Core, NodeId, context::CoreContext, hooks::HookFlow},
     event::{Event, mouse::MouseEvent},
     geom::Direction,
 };
@@ -904,6 +905,8 @@ pub struct CommandSpec {
     pub ret: CommandReturnSpec,
     /// Documentation metadata.
     pub doc: CommandDocSpec,
+    /// Default key bindings declared with `#[command(key = "...")]`.
+    pub keys: &'static [&'static str],
     /// Erased invoke entrypoint.
     pub invoke: InvokeFn,
 }
@@ -1125,6 +1128,8 @@ impl From<&'static CommandSpec> for CommandInvocation {
 pub struct CommandSet {
     /// Registry of command specs by id.
     commands: HashMap<&'static str, &'static CommandSpec>,
+    /// Command strings keyed by alias name.
+    aliases: HashMap<String, String>,
 }
 
 impl CommandSet {
@@ -1132,6 +1137,7 @@ impl CommandSet {
     pub fn new() -> Self {
         Self {
             commands: HashMap::new(),
+            aliases: HashMap::new(),
         }
     }
 
@@ -1158,6 +1164,153 @@ impl CommandSet {
     pub fn iter(&self) -> impl Iterator<Item = (&'static str, &'static CommandSpec)> + '_ {
         self.commands.iter().map(|(k, v)| (*k, *v))
     }
+
+    /// Return the sorted names of all commands and aliases.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .commands
+            .keys()
+            .map(|id| id.to_string())
+            .chain(self.aliases.keys().cloned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Register `name` as an alias for a command string such as `editor::save()`.
+    ///
+    /// The target must parse against the registered commands. Aliases may not shadow command
+    /// ids or refer to other aliases.
+    pub fn alias(&mut self, name: &str, target: &str) -> Result<(), CommandError> {
+        let name = name.trim();
+        if name.is_empty() || name.contains(['(', ')', ',', '"']) || self.get(name).is_some() {
+            return Err(CommandError::Parse {
+                input: name.to_string(),
+                message: "invalid alias name".to_string(),
+            });
+        }
+        self.parse_command(target)?;
+        self.aliases
+            .insert(name.to_string(), target.trim().to_string());
+        Ok(())
+    }
+
+    /// Parse a command string such as `list::select(3)`, or an alias, into an invocation.
+    ///
+    /// Arguments are comma-separated literals: integers, floats, `true`, `false`, `nil`,
+    /// double-quoted strings, or bare words, which are passed as strings so they can name
+    /// enum variants such as `Up`.
+    pub fn parse(&self, text: &str) -> Result<CommandInvocation, CommandError> {
+        match self.aliases.get(text.trim()) {
+            Some(target) => self.parse_command(target),
+            None => self.parse_command(text),
+        }
+    }
+
+    /// Parse a command string without alias expansion.
+    fn parse_command(&self, text: &str) -> Result<CommandInvocation, CommandError> {
+        let text = text.trim();
+        let parse_error = |message: String| CommandError::Parse {
+            input: text.to_string(),
+            message,
+        };
+        let (name, args) = match text.find('(') {
+            Some(open) => {
+                let inner = text[open + 1..]
+                    .strip_suffix(')')
+                    .ok_or_else(|| parse_error("expected closing `)`".to_string()))?;
+                (
+                    text[..open].trim_end(),
+                    parse_command_args(inner).map_err(parse_error)?,
+                )
+            }
+            None => (text, Vec::new()),
+        };
+        let spec = self.get(name).ok_or_else(|| CommandError::UnknownCommand {
+            id: name.to_string(),
+        })?;
+        Ok(CommandInvocation {
+            id: spec.id,
+            args: CommandArgs::Positional(args),
+        })
+    }
+}
+
+/// Parse a comma-separated command argument list.
+fn parse_command_args(input: &str) -> Result<Vec<ArgValue>, String> {
+    let mut args = Vec::new();
+    let mut chars = input.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let value = match chars.peek() {
+            None if args.is_empty() => break,
+            None => return Err("expected an argument after `,`".to_string()),
+            Some('"') => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        None => return Err("unterminated string".to_string()),
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => text.push('\n'),
+                            Some('t') => text.push('\t'),
+                            Some(c @ ('"' | '\\')) => text.push(c),
+                            Some(c) => return Err(format!("unknown escape `\\{c}`")),
+                            None => return Err("unterminated string".to_string()),
+                        },
+                        Some(c) => text.push(c),
+                    }
+                }
+                ArgValue::String(text)
+            }
+            Some(_) => {
+                let mut word = String::new();
+                while let Some(c) = chars.next_if(|c| *c != ',') {
+                    word.push(c);
+                }
+                parse_command_literal(word.trim())?
+            }
+        };
+        args.push(value);
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        match chars.next() {
+            None => break,
+            Some(',') => {}
+            Some(c) => return Err(format!("unexpected `{c}` after argument")),
+        }
+    }
+    Ok(args)
+}
+
+/// Parse an unquoted command argument literal.
+fn parse_command_literal(word: &str) -> Result<ArgValue, String> {
+    match word {
+        "" => return Err("empty argument".to_string()),
+        "true" => return Ok(ArgValue::Bool(true)),
+        "false" => return Ok(ArgValue::Bool(false)),
+        "nil" => return Ok(ArgValue::Null),
+        _ => {}
+    }
+    if word.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '-' | '+' | '.')) {
+        if let Ok(value) = word.parse::<i64>() {
+            return Ok(ArgValue::Int(value));
+        }
+        if let Ok(value) = word.parse::<u64>() {
+            return Ok(ArgValue::UInt(value));
+        }
+        if let Ok(value) = word.parse::<f64>() {
+            return Ok(ArgValue::Float(value));
+        }
+        return Err(format!("invalid number `{word}`"));
+    }
+    if word
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-'))
+    {
+        return Ok(ArgValue::String(word.to_string()));
+    }
+    Err(format!("invalid argument `{word}`"))
 }
 
 /// Error type for command dispatch and conversion.
@@ -1240,6 +1393,15 @@ pub enum CommandError {
         message: String,
     },
 
+    /// Malformed command string.
+    #[error("cannot parse command `{input}`: {message}")]
+    Parse {
+        /// Input that failed to parse.
+        input: String,
+        /// Error message.
+        message: String,
+    },
+
     /// Command execution failure.
     #[error("command execution failed: {0}")]
     Exec(#[from] anyhow::Error),
@@ -1432,10 +1594,36 @@ pub fn normalize_named_args<'a>(
 }
 
 /// Dispatch a command relative to a node.
+///
+/// Command hooks run around the dispatch; a swallowed command returns `Null` without
+/// running. Outermost dispatches are captured by an active macro recording.
 pub fn dispatch(
     core: &mut Core,
     current_id: NodeId,
     inv: &CommandInvocation,
+) -> Result<ArgValue, CommandError> {
+    let inv = if !core.hooks.has_command_hooks() {
+        Cow::Borrowed(inv)
+    } else {
+        let mut inv = inv.clone();
+        if core.hooks.before_command(&mut inv) == HookFlow::Swallow {
+            return Ok(ArgValue::Null);
+        }
+        Cow::Owned(inv)
+    };
+    let register = core.macros.enter_dispatch();
+    let result = dispatch_resolved(core, current_id, &inv);
+    core.macros
+        .exit_dispatch(register.filter(|_| result.is_ok()), &inv);
+    core.hooks.after_command(&inv, &result);
+    result
+}
+
+/// Resolve and invoke a command without macro bookkeeping.
+fn dispatch_resolved(
+    core: &mut Core,
+    current_id: NodeId,
+    inv: &CommandInvocation,
 ) -> Result<ArgValue, CommandError> {
     let spec = core
         .commands
@@ -1464,18 +1652,28 @@ pub fn dispatch(
     }
 }
 
-/// Dispatch a node-routed command to a resolved node.
+/// Dispatch a node-routed command to a resolved node, marking the node damaged if the
+/// command succeeds. A command that panics on an isolated node returns `Null`.
 fn dispatch_on_node(
     core: &mut Core,
     node_id: NodeId,
     spec: &CommandSpec,
     inv: &CommandInvocation,
 ) -> Result<ArgValue, CommandError> {
-    core.with_widget_mut(node_id, |widget, core| {
-        let mut ctx = CoreContext::new(core, node_id);
-        (spec.invoke)(Some(widget as &mut dyn Any), &mut ctx, inv)
-    })
-    .map_err(|err| CommandError::Exec(err.into()))?
+    let result = core.isolated(node_id, "running a command", |core| {
+        core.with_widget_mut(node_id, |widget, core| {
+            let mut ctx = CoreContext::new(core, node_id);
+            (spec.invoke)(Some(widget as &mut dyn Any), &mut ctx, inv)
+        })
+    });
+    let result = match result {
+        Some(result) => result.map_err(|err| CommandError::Exec(err.into()))?,
+        None => return Ok(ArgValue::Null),
+    };
+    if result.is_ok() {
+        core.damage_view(node_id);
+    }
+    result
 }
 
 /// Convenience macro for building named arguments.
@@ -1499,6 +1697,27 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn command_args_parse_literals() {
+        assert_eq!(parse_command_args("").unwrap(), vec![]);
+        assert_eq!(
+            parse_command_args(r#" 3, -2, 1.5, true, nil, Up, "a, \"b\"\n" "#).unwrap(),
+            vec![
+                ArgValue::Int(3),
+                ArgValue::Int(-2),
+                ArgValue::Float(1.5),
+                ArgValue::Bool(true),
+                ArgValue::Null,
+                ArgValue::String("Up".into()),
+                ArgValue::String("a, \"b\"\n".into()),
+            ]
+        );
+        assert!(parse_command_args("1,").is_err());
+        assert!(parse_command_args(r#""open"#).is_err());
+        assert!(parse_command_args("1x").is_err());
+        assert!(parse_command_args("a b").is_err());
+    }
+
     #[test]
     fn int_range_checks() {
         let value = ArgValue::Int(i64::from(i32::MAX));
@@ -1,27 +1,44 @@
 use std::{
     any::{Any, TypeId, type_name, type_name_of_val},
     marker::PhantomData,
+    path::PathBuf,
+    process::{Command, ExitStatus},
     result::Result as StdResult,
+    sync::Arc,
+    time::{Duration, Instant},
 };
 
 use super::{
+    children::ChildSet,
     commands,
-    help::OwnedHelpSnapshot,
+    drag::DragPayload,
+    help::{CatalogBinding, OwnedHelpSnapshot},
     id::{NodeId, TypedId},
+    inputmap::InputSpec,
+    message::MessageTarget,
+    node::Node,
+    observe::{ChangeCounter, Observable},
+    persist::SessionRequest,
+    poll::PollWaker,
+    profile::Profiler,
     style::Effect,
+    undo::UndoEntry,
     view::View,
     widget_access,
     world::Core,
 };
 use crate::{
-    commands::{ArgValue, CommandError, CommandInvocation, CommandScopeFrame, ListRowContext},
-    error::{Error, Result},
-    event::{Event, mouse::MouseEvent},
+    commands::{
+        ArgValue, CommandError, CommandInvocation, CommandScopeFrame, CommandSpec, ListRowContext,
+    },
+    error::{Error, RenderFailure, Result},
+    event::{Event, TimerId, key::Key, mouse::MouseEvent},
     geom::{Direction, Point, PointI32, Rect, RectI32, Size},
     layout::Layout,
     path::{Path, PathFilter},
+    state::NodeName,
     style::StyleMap,
-    widget::Widget,
+    widget::{EventOutcome, Widget},
 };
 
 /// A typed key for keyed children.
@@ -184,6 +201,15 @@ pub trait ReadContext {
     /// Widget type identifier for a specific node.
     fn node_type_id(&self, node: NodeId) -> Option<TypeId>;
 
+    /// Read-only state for a specific node.
+    fn node(&self, node: NodeId) -> Option<&Node>;
+
+    /// Minimum outer size a node needs to lay out without clipping.
+    fn min_size_of(&self, node: NodeId) -> Size;
+
+    /// Frame profiler, if profiling is enabled.
+    fn profiler(&self) -> Option<&Profiler>;
+
     /// Canvas size for the current node.
     fn canvas(&self) -> Size {
         self.view().canvas
@@ -224,6 +250,26 @@ pub trait ReadContext {
     /// Is the specified node on the focus path?
     fn node_is_on_focus_path(&self, node: NodeId) -> bool;
 
+    /// Is the mouse pointer over the current node, rather than one of its children?
+    fn is_hovered(&self) -> bool;
+
+    /// Is the mouse pointer over the specified node, rather than one of its children?
+    fn node_is_hovered(&self, node: NodeId) -> bool;
+
+    /// Is high-contrast rendering enabled?
+    fn high_contrast(&self) -> bool;
+
+    /// Return the render failure shown in the error overlay, if any. See
+    /// `Canopy::set_catch_render_errors`.
+    fn render_failure(&self) -> Option<RenderFailure>;
+
+    /// Is reduced motion enabled? Widgets should skip animation when it is.
+    fn reduced_motion(&self) -> bool;
+
+    /// Return the current time from the app clock. Under a test harness this is a
+    /// virtual time that moves only when the harness advances it.
+    fn now(&self) -> Instant;
+
     /// Return the focus path for the subtree under `root`.
     fn focus_path(&self, root: NodeId) -> Path;
 
@@ -250,6 +296,17 @@ pub trait ReadContext {
         0
     }
 
+    /// Return a node's path from the root, or `None` if the node was removed or is
+    /// detached. Node IDs are generational, so a stale ID never resolves to a newer node.
+    fn node_location(&self, node: NodeId) -> Option<Path> {
+        let root = self.root_id();
+        let mut current = node;
+        while current != root {
+            current = self.node(current)?.parent()?;
+        }
+        Some(self.node_path(root, node))
+    }
+
     /// Find the first node whose path matches the filter, relative to the current node.
     ///
     /// The filter is normalized to match full paths.
@@ -314,6 +371,22 @@ pub trait ReadContext {
     /// This is used by help widgets to check if a snapshot is available
     /// during render, without consuming it.
     fn pending_help_snapshot(&self) -> Option<&OwnedHelpSnapshot>;
+
+    /// Return the sorted names of all registered commands and aliases.
+    fn command_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Return the specs of all registered commands, sorted by id.
+    fn command_specs(&self) -> Vec<&'static CommandSpec> {
+        Vec::new()
+    }
+
+    /// Return the binding catalog captured after `Context::request_binding_catalog`.
+    /// Widgets observe it to rebuild derived state when the keymap changes.
+    fn binding_catalog(&self) -> Observable<Vec<CatalogBinding>> {
+        Observable::new(Vec::new())
+    }
 }
 
 /// Pre-order traversal iterator over a subtree.
@@ -548,6 +621,11 @@ pub trait Context: ReadContext {
     /// Focus a node. Returns `true` if focus changed.
     fn set_focus(&mut self, node: NodeId) -> bool;
 
+    /// Focus the first node, searching from the root, whose path matches `path_filter`.
+    /// Returns `true` if focus changed. A node whose id is already known is focused with
+    /// `set_focus`.
+    fn focus_path(&mut self, path_filter: &str) -> Result<bool>;
+
     /// Move focus in a specified direction within the current node's subtree.
     fn focus_dir(&mut self, dir: Direction) {
         self.focus_dir_in(self.node_id(), dir)
@@ -600,6 +678,24 @@ pub trait Context: ReadContext {
         self.focus_prev_in(self.root_id())
     }
 
+    /// Confine focus traversal to the subtree at `node`, moving focus inside it, until the
+    /// scope is popped. Used by modals so focus cannot escape to background content.
+    fn push_focus_scope(&mut self, node: NodeId);
+
+    /// Remove a focus scope pushed with `push_focus_scope`. Returns `true` if it was active.
+    fn pop_focus_scope(&mut self, node: NodeId) -> bool;
+
+    /// Save the current focus so a later `focus_pop` can return to it.
+    fn focus_push(&mut self);
+
+    /// Return focus to the most recently saved node that still exists. Returns `true` if
+    /// focus was restored.
+    fn focus_pop(&mut self) -> bool;
+
+    /// Set a node's explicit tab index. Nodes with a tab index are visited first by
+    /// `focus_next` and `focus_prev`, in ascending order; the rest follow in tree order.
+    fn set_tab_index(&mut self, node: NodeId, index: Option<u32>) -> Result<()>;
+
     /// Move focus to the right within the current node's subtree.
     fn focus_right(&mut self) {
         self.focus_dir(Direction::Right)
@@ -666,22 +762,49 @@ pub trait Context: ReadContext {
     /// Release mouse capture if held by the current node. Returns `true` if capture changed.
     fn release_mouse(&mut self) -> bool;
 
+    /// Start a drag from the current node carrying `payload`. Returns `false` if a drag is
+    /// already active.
+    fn start_drag(&mut self, payload: DragPayload) -> bool;
+
+    /// Cancel the active drag, if any. Returns `true` if a drag was cancelled.
+    fn cancel_drag(&mut self) -> bool;
+
     /// Scroll the view to the specified position. Returns `true` if movement occurred.
     fn scroll_to(&mut self, x: u32, y: u32) -> bool;
 
     /// Scroll the view by the given offsets. Returns `true` if movement occurred.
     fn scroll_by(&mut self, x: i32, y: i32) -> bool;
 
+    /// Scroll the view to the specified position, animating the offset when smooth
+    /// scrolling is enabled and reduced motion is not. Returns `true` if the target differs
+    /// from the current offset.
+    fn smooth_scroll_to(&mut self, x: u32, y: u32) -> bool;
+
+    /// Scroll the view by the given offsets, animating as for
+    /// [`smooth_scroll_to`](Self::smooth_scroll_to). Offsets apply to the target of a
+    /// running scroll animation, so repeated calls accumulate. Returns `true` if the target
+    /// differs from the current offset.
+    fn smooth_scroll_by(&mut self, x: i32, y: i32) -> bool;
+
+    /// Scroll this node and its ancestors so `rect`, in this node's canvas coordinates, is
+    /// on screen. Scrolling animates as for [`smooth_scroll_to`](Self::smooth_scroll_to).
+    /// Returns `true` if any offset changed.
+    fn scroll_rect_into_view(&mut self, rect: Rect) -> bool;
+
+    /// Scroll the ancestors of `node` so it is on screen. Returns `true` if any offset
+    /// changed.
+    fn scroll_into_view(&mut self, node: NodeId) -> bool;
+
     /// Scroll the view up by one page. Returns `true` if movement occurred.
     fn page_up(&mut self) -> bool {
         let h = self.view().content.h as i32;
-        self.scroll_by(0, -h)
+        self.smooth_scroll_by(0, -h)
     }
 
     /// Scroll the view down by one page. Returns `true` if movement occurred.
     fn page_down(&mut self) -> bool {
         let h = self.view().content.h as i32;
-        self.scroll_by(0, h)
+        self.smooth_scroll_by(0, h)
     }
 
     /// Scroll the view up by one line. Returns `true` if movement occurred.
@@ -707,6 +830,12 @@ pub trait Context: ReadContext {
     /// Mark this node dirty so the next frame re-runs layout.
     fn invalidate_layout(&mut self);
 
+    /// Mark a region of this node, in canvas coordinates, as changed.
+    ///
+    /// A poll callback that reports damage promises its changes are confined to the damaged
+    /// region, letting the next frame redraw only that part of the screen.
+    fn damage(&mut self, rect: Rect);
+
     /// Update the layout for the current node.
     fn with_layout(&mut self, f: &mut dyn FnMut(&mut Layout)) -> Result<()> {
         let node = self.node_id();
@@ -716,10 +845,16 @@ pub trait Context: ReadContext {
     /// Update the layout for a specific node.
     fn with_layout_of(&mut self, node: NodeId, f: &mut dyn FnMut(&mut Layout)) -> Result<()>;
 
+    /// Give a node an explicit name, overriding its widget's name in paths and binding
+    /// matches. Commands still find the node by its widget name. Fails if a sibling's
+    /// path already uses the name.
+    fn set_name_of(&mut self, node: NodeId, name: NodeName) -> Result<()>;
+
     /// Create a new widget node detached from the tree.
     fn create_detached_boxed(&mut self, widget: Box<dyn Widget>) -> NodeId;
 
-    /// Execute a closure with mutable access to a widget and its node-bound context.
+    /// Execute a closure with mutable access to a widget and its node-bound context. The
+    /// node is redrawn in the next frame.
     fn with_widget_mut(
         &mut self,
         node: NodeId,
@@ -729,6 +864,27 @@ pub trait Context: ReadContext {
     /// Dispatch a command relative to this node.
     fn dispatch_command(&mut self, cmd: &CommandInvocation) -> StdResult<ArgValue, CommandError>;
 
+    /// Parse and dispatch a command string such as `list::select(3)`, or an alias, relative to
+    /// this node.
+    fn dispatch_str(&mut self, command: &str) -> StdResult<ArgValue, CommandError> {
+        let node = self.node_id();
+        self.dispatch_str_from(node, command)
+    }
+
+    /// Parse and dispatch a command string relative to `node` rather than this node.
+    fn dispatch_str_from(
+        &mut self,
+        node: NodeId,
+        command: &str,
+    ) -> StdResult<ArgValue, CommandError>;
+
+    /// Dispatch a command relative to `node` rather than this node.
+    fn dispatch_command_from(
+        &mut self,
+        node: NodeId,
+        cmd: &CommandInvocation,
+    ) -> StdResult<ArgValue, CommandError>;
+
     /// Dispatch a command with an explicit command-scope frame.
     fn dispatch_command_scoped(
         &mut self,
@@ -813,16 +969,37 @@ pub trait Context: ReadContext {
     /// Request a cooperative shutdown with the provided status code.
     fn exit(&mut self, code: i32);
 
+    /// Request a cooperative shutdown with status 0, handing `value` back to the caller of
+    /// the runloop. Prefer the typed [`exit_with`](Self::exit_with) wrapper.
+    fn exit_with_value(&mut self, value: Box<dyn Any>);
+
+    /// Suspend the process for job control. The terminal is restored while the process is
+    /// stopped, and the UI is fully repainted when it resumes.
+    fn suspend(&mut self) -> Result<()>;
+
+    /// Run an external command attached to the terminal, such as an editor. The terminal is
+    /// released while the command runs, and the UI is fully repainted when it exits.
+    fn run_external(&mut self, cmd: &mut Command) -> Result<ExitStatus>;
+
     /// Add an effect to a node that will be applied during rendering.
     /// Effects stack and inherit through the tree.
     fn push_effect(&mut self, node: NodeId, effect: Effect) -> Result<()>;
 
+    /// Remove one effect, matched by identity, from a node. Returns `false` if the node
+    /// does not carry that effect.
+    fn remove_effect(&mut self, node: NodeId, effect: &Effect) -> Result<bool>;
+
     /// Clear all effects on a node.
     fn clear_effects(&mut self, node: NodeId) -> Result<()>;
 
     /// Set whether a node should clear inherited effects before applying local ones.
     fn set_clear_inherited_effects(&mut self, node: NodeId, clear: bool) -> Result<()>;
 
+    /// Set whether a node may draw beyond its outer rect, down and to the right, over
+    /// siblings and ancestors' borders. Overlays such as drop shadows and popups use this;
+    /// other nodes are clipped to their rect.
+    fn set_unclipped(&mut self, node: NodeId, unclipped: bool) -> Result<()>;
+
     /// Set the style map to be used for rendering.
     /// The style change will be applied before the next render.
     fn set_style(&mut self, style: StyleMap);
@@ -841,8 +1018,98 @@ pub trait Context: ReadContext {
     /// no snapshot is pending.
     fn take_help_snapshot(&mut self) -> Option<OwnedHelpSnapshot>;
 
+    /// Ask Canopy to capture every binding in every mode at the start of the next render,
+    /// for reading through `ReadContext::binding_catalog`. Once captured, the catalog is
+    /// kept current as bindings are added and removed.
+    fn request_binding_catalog(&mut self);
+
     /// Request a diagnostic dump for a target node.
     fn request_diagnostic_dump(&mut self, target: NodeId);
+
+    /// Write the screen to `path` once the next render completes. See `Canopy::screenshot`.
+    fn request_screenshot(&mut self, path: PathBuf);
+
+    /// Enable or disable high-contrast rendering. See `Canopy::set_high_contrast`.
+    fn set_high_contrast(&mut self, enabled: bool);
+
+    /// Dismiss the render error overlay and retry the failed node on the next frame.
+    fn dismiss_render_failure(&mut self);
+
+    /// Enable or disable reduced motion. See `Canopy::set_reduced_motion`.
+    fn set_reduced_motion(&mut self, enabled: bool);
+
+    /// Enable or disable frame profiling. Profiling adds a clock read around each node's
+    /// layout and render.
+    fn set_profiling(&mut self, enabled: bool);
+
+    /// Set a timer that delivers `Event::Timer(id)` to this node after `interval`, and every
+    /// `interval` thereafter if `repeating`. Repeating intervals shorter than one animation
+    /// frame are raised to one frame. Setting an id that is already active replaces it.
+    fn set_timer(&mut self, id: TimerId, interval: Duration, repeating: bool);
+
+    /// Cancel a timer on this node. Returns `false` if it was not active.
+    fn cancel_timer(&mut self, id: TimerId) -> bool;
+
+    /// Return a handle that asks the runloop to poll this node, for use from another
+    /// thread.
+    fn poll_waker(&self) -> PollWaker;
+
+    /// Queue a Luau source string to run against `node` once the current input event has
+    /// been handled. Scripts cannot run while a widget is checked out, so evaluation is
+    /// deferred; failures are logged.
+    fn eval_script(&mut self, node: NodeId, source: &str);
+
+    /// Bind `key` in the default input mode to the command string `command`, active while
+    /// focus is inside `node`'s subtree. The binding is installed once the current
+    /// callback returns.
+    fn bind_key_in(&mut self, node: NodeId, key: &str, command: &str) -> Result<()>;
+
+    /// Start recording commands and widget-handled keys into a macro register. A recording
+    /// already in progress is saved first.
+    fn start_recording(&mut self, register: &str);
+
+    /// Stop recording and save the macro. Returns the register, or `None` if no recording
+    /// was active.
+    fn stop_recording(&mut self) -> Option<String>;
+
+    /// Return the register being recorded, if any.
+    fn recording_register(&self) -> Option<String>;
+
+    /// Queue a replay of the macro in `register`, run `count` times once the current input
+    /// event has been handled.
+    fn replay(&mut self, register: &str, count: usize);
+
+    /// Subscribe a node to an observable value's change counter. The node is marked for
+    /// layout and redrawn on the next frame after each change. Subscriptions end when the
+    /// node is removed.
+    fn observe_changes(&mut self, node: NodeId, changes: ChangeCounter);
+
+    /// Deliver `event` immediately to every node whose path from the root matches
+    /// `path_filter`, without bubbling. The current node and any other node mid-callback
+    /// is skipped. Outcomes aggregate to `Handle` if any node handled the event, else
+    /// `Consume` if any consumed it, else `Ignore`.
+    fn broadcast(&mut self, event: &Event, path_filter: &str) -> Result<EventOutcome>;
+
+    /// Queue a message for `target`, delivered to its `handle_message` hook once the
+    /// current event has been handled. Messages to nodes that no longer exist are dropped.
+    fn send_message(&mut self, target: MessageTarget, msg: Box<dyn Any>);
+
+    /// Queue a save of widget state, layout, scroll offsets, and focus as session `name`,
+    /// run once the current input event has been handled.
+    fn save_session(&mut self, name: &str);
+
+    /// Queue a load of session `name`, run once the current input event has been handled.
+    fn load_session(&mut self, name: &str);
+
+    /// Register a reversible change. Does nothing unless the app has enabled undo, or while
+    /// an undo or redo is being applied.
+    fn push_undo(&mut self, entry: UndoEntry);
+
+    /// Reverse the newest registered change. Returns `false` if there was nothing to undo.
+    fn undo(&mut self) -> StdResult<bool, CommandError>;
+
+    /// Reapply the newest undone change. Returns `false` if there was nothing to redo.
+    fn redo(&mut self) -> StdResult<bool, CommandError>;
 }
 
 /// Focus-related context helpers.
@@ -925,6 +1192,12 @@ pub trait StyleContext: Context {
 impl<T: Context + ?Sized> StyleContext for T {}
 
 impl dyn Context + '_ {
+    /// Request a cooperative shutdown with status 0, handing `value` back to the caller of
+    /// [`runloop_with_value`](crate::backend::crossterm::runloop_with_value).
+    pub fn exit_with<T: Any>(&mut self, value: T) {
+        self.exit_with_value(Box::new(value));
+    }
+
     /// Get a read-only view of this context.
     ///
     /// This is useful for calling methods defined on `ReadContext` that are not
@@ -1008,6 +1281,43 @@ impl dyn Context + '_ {
         Context::with_layout_of(self, node.into(), &mut |l| *l = layout)
     }
 
+    /// Subscribe the current node to an observable value, so it is laid out and redrawn
+    /// whenever the value changes.
+    pub fn observe<T>(&mut self, value: &Observable<T>) {
+        let node = self.node_id();
+        self.observe_changes(node, value.changes());
+    }
+
+    /// Send a message to a node by ID or by path filter, resolved from the root. It is
+    /// delivered to the recipient's `handle_message` hook after the current event.
+    pub fn send_to(&mut self, target: impl Into<MessageTarget>, msg: Box<dyn Any>) {
+        self.send_message(target.into(), msg);
+    }
+
+    /// Execute a closure with mutable access to any node's widget and a context bound to
+    /// that node. Returns `None` if the node has been removed, so subsystems can hold a
+    /// `NodeId` as a weak reference and resolve it on demand.
+    pub fn with_node_mut<R>(
+        &mut self,
+        node: impl Into<NodeId>,
+        f: impl FnOnce(&mut dyn Widget, &mut dyn Context) -> Result<R>,
+    ) -> Result<Option<R>> {
+        let node = node.into();
+        if self.node(node).is_none() {
+            return Ok(None);
+        }
+        let mut output = None;
+        let mut f = Some(f);
+        self.with_widget_mut(node, &mut |widget, ctx| {
+            let f = f
+                .take()
+                .ok_or_else(|| Error::Internal("missing widget closure".into()))?;
+            output = Some(f(widget, ctx)?);
+            Ok(())
+        })?;
+        Ok(output)
+    }
+
     /// Execute a closure with mutable access to a widget of type `W`.
     pub fn with_widget<W, R>(
         &mut self,
@@ -1126,6 +1436,21 @@ impl dyn Context + '_ {
         Ok(TypedId::new(id))
     }
 
+    /// Add a widget as a child of a specific parent under an explicit node name. The
+    /// name replaces the widget's own in paths, so sibling instances of the same widget
+    /// can carry distinct bindings, while commands still reach the widget by type name.
+    pub fn add_named_child_to<W: Widget + 'static>(
+        &mut self,
+        parent: impl Into<NodeId>,
+        name: &str,
+        widget: W,
+    ) -> Result<TypedId<W>> {
+        let name = NodeName::new(name)?;
+        let id = self.add_child_to(parent, widget)?;
+        self.set_name_of(id.into(), name)?;
+        Ok(id)
+    }
+
     /// Add a widget as a child of a specific parent and assign a layout.
     pub fn add_child_to_with_layout<W: Widget + 'static>(
         &mut self,
@@ -1292,6 +1617,13 @@ impl dyn Context + '_ {
         self.add_child_keyed(K::KEY, widget)
     }
 
+    /// Mount a derived set of children under the current node, in declaration order, and
+    /// return their node IDs.
+    pub fn mount_children<C: ChildSet>(&mut self, children: C) -> Result<Vec<NodeId>> {
+        let parent = self.node_id();
+        children.mount(self, parent)
+    }
+
     /// Add a typed keyed child to a specific parent and return its typed node ID.
     pub fn add_keyed_to<W: Widget + 'static>(
         &mut self,
@@ -1482,6 +1814,18 @@ impl<'a> ReadContext for CoreContext<'a> {
         self.core.nodes.get(node).map(|n| n.view)
     }
 
+    fn node(&self, node: NodeId) -> Option<&Node> {
+        self.core.nodes.get(node)
+    }
+
+    fn min_size_of(&self, node: NodeId) -> Size {
+        self.core.min_size(node)
+    }
+
+    fn profiler(&self) -> Option<&Profiler> {
+        self.core.profiler.as_ref()
+    }
+
     fn node_type_id(&self, node: NodeId) -> Option<TypeId> {
         self.core.nodes.get(node).map(|n| n.widget_type)
     }
@@ -1510,6 +1854,30 @@ impl<'a> ReadContext for CoreContext<'a> {
         self.core.is_on_focus_path(node)
     }
 
+    fn is_hovered(&self) -> bool {
+        self.core.is_hovered(self.node_id)
+    }
+
+    fn node_is_hovered(&self, node: NodeId) -> bool {
+        self.core.is_hovered(node)
+    }
+
+    fn high_contrast(&self) -> bool {
+        self.core.high_contrast
+    }
+
+    fn render_failure(&self) -> Option<RenderFailure> {
+        self.core.render_failure.clone()
+    }
+
+    fn reduced_motion(&self) -> bool {
+        self.core.reduced_motion
+    }
+
+    fn now(&self) -> Instant {
+        self.core.clock.now()
+    }
+
     fn focus_path(&self, root: NodeId) -> Path {
         self.core.focus_path(root)
     }
@@ -1549,6 +1917,20 @@ impl<'a> ReadContext for CoreContext<'a> {
         }
         snapshot
     }
+
+    fn command_names(&self) -> Vec<String> {
+        self.core.commands.names()
+    }
+
+    fn command_specs(&self) -> Vec<&'static CommandSpec> {
+        let mut specs: Vec<_> = self.core.commands.iter().map(|(_, spec)| spec).collect();
+        specs.sort_by_key(|spec| spec.id.0);
+        specs
+    }
+
+    fn binding_catalog(&self) -> Observable<Vec<CatalogBinding>> {
+        self.core.binding_catalog.clone()
+    }
 }
 
 impl<'a> Context for CoreContext<'a> {
@@ -1556,6 +1938,13 @@ impl<'a> Context for CoreContext<'a> {
         self.core.set_focus(node)
     }
 
+    fn focus_path(&mut self, path_filter: &str) -> Result<bool> {
+        let filter = PathFilter::normalized(path_filter)?;
+        let root = self.core.root_id();
+        let target = CoreViewContext::new(self.core, root).find_node_matching(&filter);
+        Ok(target.is_some_and(|node| self.core.set_focus(node)))
+    }
+
     fn focus_dir_in(&mut self, root: NodeId, dir: Direction) {
         self.core.focus_dir(root, dir);
     }
@@ -1572,6 +1961,26 @@ impl<'a> Context for CoreContext<'a> {
         self.core.focus_prev(root);
     }
 
+    fn push_focus_scope(&mut self, node: NodeId) {
+        self.core.push_focus_scope(node);
+    }
+
+    fn pop_focus_scope(&mut self, node: NodeId) -> bool {
+        self.core.pop_focus_scope(node)
+    }
+
+    fn focus_push(&mut self) {
+        self.core.focus_push();
+    }
+
+    fn focus_pop(&mut self) -> bool {
+        self.core.focus_pop()
+    }
+
+    fn set_tab_index(&mut self, node: NodeId, index: Option<u32>) -> Result<()> {
+        self.core.set_tab_index(node, index)
+    }
+
     fn capture_mouse(&mut self) -> bool {
         if self.core.mouse_capture == Some(self.node_id) {
             false
@@ -1590,7 +1999,16 @@ impl<'a> Context for CoreContext<'a> {
         }
     }
 
+    fn start_drag(&mut self, payload: DragPayload) -> bool {
+        self.core.start_drag(self.node_id, payload)
+    }
+
+    fn cancel_drag(&mut self) -> bool {
+        self.core.cancel_drag()
+    }
+
     fn scroll_to(&mut self, x: u32, y: u32) -> bool {
+        self.core.cancel_scroll(self.node_id);
         let node = self.core.nodes.get_mut(self.node_id);
         if let Some(node) = node {
             let before = node.scroll;
@@ -1603,6 +2021,7 @@ impl<'a> Context for CoreContext<'a> {
     }
 
     fn scroll_by(&mut self, x: i32, y: i32) -> bool {
+        self.core.cancel_scroll(self.node_id);
         let node = self.core.nodes.get_mut(self.node_id);
         if let Some(node) = node {
             let before = node.scroll;
@@ -1614,16 +2033,47 @@ impl<'a> Context for CoreContext<'a> {
         }
     }
 
+    fn smooth_scroll_to(&mut self, x: u32, y: u32) -> bool {
+        self.core.smooth_scroll_to(self.node_id, Point { x, y })
+    }
+
+    fn smooth_scroll_by(&mut self, x: i32, y: i32) -> bool {
+        let Some(target) = self.core.scroll_target(self.node_id) else {
+            return false;
+        };
+        self.core
+            .smooth_scroll_to(self.node_id, target.scroll(x, y))
+    }
+
+    fn scroll_rect_into_view(&mut self, rect: Rect) -> bool {
+        self.core.scroll_rect_into_view(self.node_id, rect)
+    }
+
+    fn scroll_into_view(&mut self, node: NodeId) -> bool {
+        let Some((parent, rect)) = self.core.nodes.get(node).map(|n| (n.parent, n.rect)) else {
+            return false;
+        };
+        parent.is_some_and(|parent| self.core.scroll_rect_into_view(parent, rect))
+    }
+
     fn invalidate_layout(&mut self) {
         if let Some(node) = self.core.nodes.get_mut(self.node_id) {
             node.layout_dirty = true;
         }
     }
 
+    fn damage(&mut self, rect: Rect) {
+        self.core.damage_node(self.node_id, rect);
+    }
+
     fn with_layout_of(&mut self, node: NodeId, f: &mut dyn FnMut(&mut Layout)) -> Result<()> {
         self.core.with_layout_of(node, |layout| f(layout))
     }
 
+    fn set_name_of(&mut self, node: NodeId, name: NodeName) -> Result<()> {
+        self.core.set_name_of(node, name)
+    }
+
     fn create_detached_boxed(&mut self, widget: Box<dyn Widget>) -> NodeId {
         self.core.create_detached_boxed(widget)
     }
@@ -1636,7 +2086,9 @@ impl<'a> Context for CoreContext<'a> {
         self.core.with_widget_mut(node, |widget, core| {
             let mut ctx = CoreContext::new(core, node);
             f(widget, &mut ctx)
-        })?
+        })??;
+        self.core.damage_view(node);
+        Ok(())
     }
 
     fn dispatch_command(&mut self, cmd: &CommandInvocation) -> StdResult<ArgValue, CommandError> {
@@ -1648,6 +2100,39 @@ impl<'a> Context for CoreContext<'a> {
         self.dispatch_command_scoped(frame, cmd)
     }
 
+    fn dispatch_str_from(
+        &mut self,
+        node: NodeId,
+        command: &str,
+    ) -> StdResult<ArgValue, CommandError> {
+        let cmd = self.core.commands.parse(command)?;
+        let frame = self
+            .core
+            .current_command_scope()
+            .cloned()
+            .unwrap_or_default();
+        let guard = self.core.push_command_scope(frame);
+        let result = commands::dispatch(self.core, node, &cmd);
+        self.core.pop_command_scope(guard);
+        result
+    }
+
+    fn dispatch_command_from(
+        &mut self,
+        node: NodeId,
+        cmd: &CommandInvocation,
+    ) -> StdResult<ArgValue, CommandError> {
+        let frame = self
+            .core
+            .current_command_scope()
+            .cloned()
+            .unwrap_or_default();
+        let guard = self.core.push_command_scope(frame);
+        let result = commands::dispatch(self.core, node, cmd);
+        self.core.pop_command_scope(guard);
+        result
+    }
+
     fn dispatch_command_scoped(
         &mut self,
         frame: CommandScopeFrame,
@@ -1734,6 +2219,36 @@ impl<'a> Context for CoreContext<'a> {
         self.core.request_exit(code);
     }
 
+    fn exit_with_value(&mut self, value: Box<dyn Any>) {
+        self.core.request_exit_with(value);
+    }
+
+    fn suspend(&mut self) -> Result<()> {
+        let size = self
+            .core
+            .backend
+            .as_mut()
+            .ok_or_else(|| Error::Internal("backend not set".into()))?
+            .suspend()?;
+        self.core.pending_resize = size;
+        self.core.request_full_render();
+        Ok(())
+    }
+
+    fn run_external(&mut self, cmd: &mut Command) -> Result<ExitStatus> {
+        let backend = self
+            .core
+            .backend
+            .as_mut()
+            .ok_or_else(|| Error::Internal("backend not set".into()))?;
+        backend.stop()?;
+        let status = cmd.status();
+        let restarted = backend.start();
+        self.core.request_full_render();
+        restarted?;
+        Ok(status?)
+    }
+
     fn push_effect(&mut self, node: NodeId, effect: Effect) -> Result<()> {
         let node = self
             .core
@@ -1748,6 +2263,25 @@ impl<'a> Context for CoreContext<'a> {
         Ok(())
     }
 
+    fn remove_effect(&mut self, node: NodeId, effect: &Effect) -> Result<bool> {
+        let node = self
+            .core
+            .nodes
+            .get_mut(node)
+            .ok_or(Error::NodeNotFound(node))?;
+        let Some(effects) = node.effects.as_mut() else {
+            return Ok(false);
+        };
+        let Some(index) = effects.iter().position(|e| Arc::ptr_eq(e, effect)) else {
+            return Ok(false);
+        };
+        effects.remove(index);
+        if effects.is_empty() {
+            node.effects = None;
+        }
+        Ok(true)
+    }
+
     fn clear_effects(&mut self, node: NodeId) -> Result<()> {
         let node = self
             .core
@@ -1768,6 +2302,16 @@ impl<'a> Context for CoreContext<'a> {
         Ok(())
     }
 
+    fn set_unclipped(&mut self, node: NodeId, unclipped: bool) -> Result<()> {
+        let node = self
+            .core
+            .nodes
+            .get_mut(node)
+            .ok_or(Error::NodeNotFound(node))?;
+        node.unclipped = unclipped;
+        Ok(())
+    }
+
     fn set_style(&mut self, style: StyleMap) {
         self.core.pending_style = Some(style);
     }
@@ -1781,9 +2325,112 @@ impl<'a> Context for CoreContext<'a> {
         self.core.pending_help_snapshot.take()
     }
 
+    fn request_binding_catalog(&mut self) {
+        self.core.binding_catalog_requested = true;
+    }
+
     fn request_diagnostic_dump(&mut self, target: NodeId) {
         self.core.request_diagnostic_dump(target);
     }
+
+    fn request_screenshot(&mut self, path: PathBuf) {
+        self.core.pending_screenshot = Some(path);
+    }
+
+    fn set_high_contrast(&mut self, enabled: bool) {
+        self.core.set_high_contrast(enabled);
+    }
+
+    fn dismiss_render_failure(&mut self) {
+        self.core.dismiss_render_failure();
+    }
+
+    fn set_reduced_motion(&mut self, enabled: bool) {
+        self.core.reduced_motion = enabled;
+    }
+
+    fn set_profiling(&mut self, enabled: bool) {
+        self.core.set_profiling(enabled);
+    }
+
+    fn set_timer(&mut self, id: TimerId, interval: Duration, repeating: bool) {
+        let now = self.core.clock.now();
+        self.core
+            .timers
+            .set(self.node_id, id, interval, repeating, now);
+    }
+
+    fn cancel_timer(&mut self, id: TimerId) -> bool {
+        self.core.timers.cancel(self.node_id, id)
+    }
+
+    fn poll_waker(&self) -> PollWaker {
+        PollWaker::new(self.node_id, self.core.event_tx.clone())
+    }
+
+    fn eval_script(&mut self, node: NodeId, source: &str) {
+        self.core.queue_script(node, source.to_string());
+    }
+
+    fn bind_key_in(&mut self, node: NodeId, key: &str, command: &str) -> Result<()> {
+        let key = Key::parse_spec(key).map_err(Error::Invalid)?;
+        let command = self.core.commands.parse(command)?;
+        self.core.queue_binding(node, InputSpec::Key(key), command);
+        Ok(())
+    }
+
+    fn start_recording(&mut self, register: &str) {
+        self.core.macros.start(register);
+    }
+
+    fn stop_recording(&mut self) -> Option<String> {
+        self.core.macros.stop()
+    }
+
+    fn recording_register(&self) -> Option<String> {
+        self.core.macros.recording().map(str::to_string)
+    }
+
+    fn replay(&mut self, register: &str, count: usize) {
+        self.core.macros.queue_replay(register, count);
+    }
+
+    fn observe_changes(&mut self, node: NodeId, changes: ChangeCounter) {
+        self.core.observe(node, changes);
+    }
+
+    fn broadcast(&mut self, event: &Event, path_filter: &str) -> Result<EventOutcome> {
+        let filter = PathFilter::normalized(path_filter)?;
+        self.core.broadcast(event, &filter)
+    }
+
+    fn send_message(&mut self, target: MessageTarget, msg: Box<dyn Any>) {
+        self.core.queue_message(target, msg);
+    }
+
+    fn save_session(&mut self, name: &str) {
+        self.core
+            .queue_session(SessionRequest::Save(name.to_string()));
+    }
+
+    fn load_session(&mut self, name: &str) {
+        self.core
+            .queue_session(SessionRequest::Load(name.to_string()));
+    }
+
+    fn push_undo(&mut self, entry: UndoEntry) {
+        if let Some(undo) = self.core.undo.as_mut() {
+            undo.push(entry);
+        }
+    }
+
+    fn undo(&mut self) -> StdResult<bool, CommandError> {
+        self.core.apply_undo(false)
+    }
+
+    fn redo(&mut self) -> StdResult<bool, CommandError> {
+        self.core.apply_undo(true)
+    }
 }
 
 /// Read-only context bound to a specific node.
@@ -1830,6 +2477,18 @@ impl<'a> ReadContext for CoreViewContext<'a> {
         self.core.nodes.get(node).map(|n| n.view)
     }
 
+    fn node(&self, node: NodeId) -> Option<&Node> {
+        self.core.nodes.get(node)
+    }
+
+    fn min_size_of(&self, node: NodeId) -> Size {
+        self.core.min_size(node)
+    }
+
+    fn profiler(&self) -> Option<&Profiler> {
+        self.core.profiler.as_ref()
+    }
+
     fn node_type_id(&self, node: NodeId) -> Option<TypeId> {
         self.core.nodes.get(node).map(|n| n.widget_type)
     }
@@ -1858,6 +2517,30 @@ impl<'a> ReadContext for CoreViewContext<'a> {
         self.core.is_on_focus_path(node)
     }
 
+    fn is_hovered(&self) -> bool {
+        self.core.is_hovered(self.node_id)
+    }
+
+    fn node_is_hovered(&self, node: NodeId) -> bool {
+        self.core.is_hovered(node)
+    }
+
+    fn high_contrast(&self) -> bool {
+        self.core.high_contrast
+    }
+
+    fn render_failure(&self) -> Option<RenderFailure> {
+        self.core.render_failure.clone()
+    }
+
+    fn reduced_motion(&self) -> bool {
+        self.core.reduced_motion
+    }
+
+    fn now(&self) -> Instant {
+        self.core.clock.now()
+    }
+
     fn focus_path(&self, root: NodeId) -> Path {
         self.core.focus_path(root)
     }
@@ -1897,4 +2580,18 @@ impl<'a> ReadContext for CoreViewContext<'a> {
         }
         snapshot
     }
+
+    fn command_names(&self) -> Vec<String> {
+        self.core.commands.names()
+    }
+
+    fn command_specs(&self) -> Vec<&'static CommandSpec> {
+        let mut specs: Vec<_> = self.core.commands.iter().map(|(_, spec)| spec).collect();
+        specs.sort_by_key(|spec| spec.id.0);
+        specs
+    }
+
+    fn binding_catalog(&self) -> Observable<Vec<CatalogBinding>> {
+        self.core.binding_catalog.clone()
+    }
 }
@@ -1,6 +1,6 @@
 use std::ops::Add;
 
-use crate::geom;
+use crate::{geom, style::Color};
 
 /// Cursor glyph shape variants.
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
@@ -13,8 +13,25 @@ pub enum CursorShape {
     Block,
 }
 
-/// Cursor position, shape, and blink behavior.
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+/// How the cursor of the focused widget is shown.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub enum CursorMode {
+    /// Draw cursors into the frame as styled cells and leave the terminal cursor hidden.
+    /// This works on every backend, including terminals where cursor positioning is
+    /// unreliable, and can show any number of cursors.
+    #[default]
+    Virtual,
+    /// Show the primary cursor with the terminal cursor, setting its position, shape, and
+    /// color. Extra cursors are still drawn into the frame.
+    Hardware,
+}
+
+/// Cursor position, shape, color, and blink behavior.
+///
+/// Widgets return a fresh cursor from `Widget::cursor` on every frame, so a widget with
+/// modes picks the shape and color for its current state, such as a block in normal mode
+/// and a line in insert mode.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub struct Cursor {
     /// Location of the cursor, relative to (0, 0) in the node view rect.
     pub location: geom::Point,
@@ -22,6 +39,9 @@ pub struct Cursor {
     pub shape: CursorShape,
     /// Should the cursor blink?
     pub blink: bool,
+    /// Cursor color. `None` inverts the cell under the cursor; a color fills it instead,
+    /// and is also sent to the terminal with OSC 12.
+    pub color: Option<Color>,
 }
 
 impl Add<geom::Point> for Cursor {
@@ -32,6 +52,7 @@ impl Add<geom::Point> for Cursor {
             location: self.location + other,
             shape: self.shape,
             blink: self.blink,
+            color: self.color,
         }
     }
 }
@@ -0,0 +1,84 @@
+use std::mem;
+
+use crate::{
+    core::{id::NodeId, world::Core},
+    geom::{Rect, RectI32},
+};
+
+/// Return the smallest rect covering both inputs. Zero-sized rects are ignored.
+pub(crate) fn union_rect(a: Option<Rect>, b: Rect) -> Option<Rect> {
+    match a {
+        Some(a) if !a.is_zero() => Some(a.union(&b)),
+        _ if b.is_zero() => a,
+        _ => Some(b),
+    }
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Core {
+    /// Mark a region of a node, in canvas coordinates, as needing a redraw.
+    pub(crate) fn damage_node(&mut self, id: NodeId, rect: Rect) {
+        if let Some(node) = self.nodes.get_mut(id) {
+            node.damage = Some(union_rect(node.damage, rect).unwrap_or_default());
+            self.damaged = true;
+        }
+    }
+
+    /// Mark a node's whole visible canvas region as needing a redraw.
+    pub(crate) fn damage_view(&mut self, id: NodeId) {
+        if let Some(rect) = self.nodes.get(id).map(|node| node.view.view_rect()) {
+            self.damage_node(id, rect);
+        }
+    }
+
+    /// Has any node reported damage since the last render?
+    pub(crate) fn has_damage(&self) -> bool {
+        self.damaged
+    }
+
+    /// Take the damage reported by `id` since the last render, clearing it.
+    pub(crate) fn take_node_damage(&mut self, id: NodeId) -> Option<Rect> {
+        self.nodes.get_mut(id).and_then(|node| node.damage.take())
+    }
+
+    /// Did layout move, resize, hide, or remove any node since the last call?
+    ///
+    /// Damage only covers content changes in place, so a frame that follows a layout change
+    /// is redrawn in full.
+    pub(crate) fn take_views_changed(&mut self) -> bool {
+        mem::take(&mut self.views_changed)
+    }
+
+    /// Clear all node damage and return its union in screen coordinates.
+    ///
+    /// Damage is clipped to each node's visible content; hidden nodes contribute nothing.
+    pub(crate) fn take_damage(&mut self) -> Option<Rect> {
+        if !self.damaged {
+            return None;
+        }
+        self.damaged = false;
+        let mut region = None;
+        for node in self.nodes.values_mut() {
+            let Some(damage) = node.damage.take() else {
+                continue;
+            };
+            if node.hidden {
+                continue;
+            }
+            let view = node.view;
+            let Some(visible) = damage.intersect(&view.view_rect()) else {
+                continue;
+            };
+            let screen = RectI32::new(
+                view.content.tl.x + (visible.tl.x - view.tl.x) as i32,
+                view.content.tl.y + (visible.tl.y - view.tl.y) as i32,
+                visible.w,
+                visible.h,
+            );
+            if let Some(screen) = screen.intersect_rect(Rect::new(0, 0, u32::MAX, u32::MAX)) {
+                region = union_rect(region, screen);
+            }
+        }
+        region
+    }
+}
@@ -0,0 +1,158 @@
+use std::any::Any;
+
+use crate::{
+    Context,
+    core::{context::CoreContext, id::NodeId, world::Core},
+    error::Result,
+    geom::Point,
+};
+
+/// Payload carried by an active drag.
+pub type DragPayload = Box<dyn Any + Send>;
+
+/// Widgets that accept dropped payloads.
+///
+/// Widgets opt in by implementing this trait and returning `Some(self)` from
+/// [`Widget::drop_target`](crate::Widget::drop_target).
+pub trait DropTarget {
+    /// Return `true` if this target accepts the payload.
+    fn accepts_drop(&self, payload: &dyn Any) -> bool;
+
+    /// Receive a dropped payload at a node-local content location.
+    fn on_drop(
+        &mut self,
+        payload: DragPayload,
+        location: Point,
+        ctx: &mut dyn Context,
+    ) -> Result<()>;
+}
+
+/// An in-progress drag operation.
+pub(crate) struct DragState {
+    /// Node that started the drag.
+    pub(crate) source: NodeId,
+    /// Payload delivered to the drop target.
+    pub(crate) payload: DragPayload,
+    /// Drop target currently under the pointer, if any.
+    pub(crate) hover: Option<NodeId>,
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Core {
+    /// Begin a drag from `source`. Returns `false` if a drag is already active.
+    pub(crate) fn start_drag(&mut self, source: NodeId, payload: DragPayload) -> bool {
+        if self.drag.is_some() {
+            return false;
+        }
+        self.drag = Some(DragState {
+            source,
+            payload,
+            hover: None,
+        });
+        true
+    }
+
+    /// Return the drop target currently highlighted by an active drag.
+    pub(crate) fn drag_hover(&self) -> Option<NodeId> {
+        self.drag.as_ref().and_then(|drag| drag.hover)
+    }
+
+    /// Find the nearest node at or above the node under `location` that accepts `payload`.
+    pub(crate) fn locate_drop_target(
+        &mut self,
+        location: Point,
+        payload: &dyn Any,
+    ) -> Result<Option<NodeId>> {
+        let mut current = self.locate_node(self.root, location)?;
+        while let Some(id) = current {
+            let accepts = self.with_widget_mut(id, |widget, _| {
+                widget
+                    .drop_target()
+                    .is_some_and(|target| target.accepts_drop(payload))
+            })?;
+            if accepts {
+                return Ok(Some(id));
+            }
+            current = self.nodes.get(id).and_then(|node| node.parent);
+        }
+        Ok(None)
+    }
+
+    /// Update the hovered drop target for an active drag, damaging the previous and new
+    /// targets if it changed.
+    pub(crate) fn update_drag_hover(&mut self, location: Point) -> Result<()> {
+        let Some(mut drag) = self.drag.take() else {
+            return Ok(());
+        };
+        let result = self.locate_drop_target(location, drag.payload.as_ref());
+        let prev = drag.hover;
+        if let Ok(hover) = result {
+            drag.hover = hover;
+        }
+        self.drag = Some(drag);
+        let hover = result?;
+        if hover != prev {
+            for id in [prev, hover].into_iter().flatten() {
+                self.damage_view(id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Finish an active drag at `location`, delivering the payload to an accepting target.
+    ///
+    /// Returns the node that received the drop, if any.
+    pub(crate) fn finish_drag(&mut self, location: Point) -> Result<Option<NodeId>> {
+        let Some(drag) = self.drag.take() else {
+            return Ok(None);
+        };
+        if let Some(hover) = drag.hover {
+            self.damage_view(hover);
+        }
+        let Some(target) = self.locate_drop_target(location, drag.payload.as_ref())? else {
+            return Ok(None);
+        };
+        let local = self
+            .nodes
+            .get(target)
+            .map(|node| node.view.content.to_local_point(location))
+            .unwrap_or_default();
+        let payload = drag.payload;
+        self.with_widget_mut(target, |widget, core| {
+            let mut ctx = CoreContext::new(core, target);
+            match widget.drop_target() {
+                Some(drop_target) => drop_target.on_drop(payload, local, &mut ctx),
+                None => Ok(()),
+            }
+        })??;
+        self.damage_view(target);
+        Ok(Some(target))
+    }
+
+    /// Cancel an active drag, damaging its drop target. Returns `true` if a drag was active.
+    pub(crate) fn cancel_drag(&mut self) -> bool {
+        let Some(drag) = self.drag.take() else {
+            return false;
+        };
+        if let Some(hover) = drag.hover {
+            self.damage_view(hover);
+        }
+        true
+    }
+
+    /// Ensure an active drag only references attached nodes.
+    pub fn ensure_drag_valid(&mut self) {
+        let attached =
+            |core: &Self, id: NodeId| core.nodes.contains_key(id) && core.is_attached_to_root(id);
+        let Some(drag) = self.drag.as_ref() else {
+            return;
+        };
+        let source_valid = attached(self, drag.source);
+        let hover_valid = drag.hover.is_none_or(|hover| attached(self, hover));
+        if !source_valid {
+            self.drag = None;
+        } else if !hover_valid && let Some(drag) = self.drag.as_mut() {
+            drag.hover = None;
+        }
+    }
+}
@@ -1,8 +1,8 @@
-use std::{result::Result as StdResult, sync::mpsc};
+use std::{io, result::Result as StdResult, sync::mpsc};
 
 use thiserror::Error;
 
-use crate::{commands::CommandError, core::id::NodeId, geom};
+use crate::{commands::CommandError, core::id::NodeId, geom, path::Path};
 
 /// Result type for canopy-core operations.
 pub type Result<T> = StdResult<T, Error>;
@@ -40,6 +40,16 @@ impl ParseError {
     }
 }
 
+/// A node render or layout pass that failed while render errors were being caught. See
+/// `Canopy::set_catch_render_errors`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderFailure {
+    /// Path of the node whose render failed.
+    pub path: Path,
+    /// The error message.
+    pub message: String,
+}
+
 /// Core error type.
 #[derive(Error, Debug)]
 pub enum Error {
@@ -90,6 +100,9 @@ pub enum Error {
     /// Duplicate child key under the same parent.
     #[error("duplicate child key: {0}")]
     DuplicateChildKey(String),
+    /// Duplicate explicit node name under the same parent.
+    #[error("duplicate node name: {0}")]
+    DuplicateNodeName(String),
     /// Duplicate child under the same parent.
     #[error("duplicate child {child:?} under parent {parent:?}")]
     DuplicateChild {
@@ -138,6 +151,10 @@ pub enum Error {
     /// Node not found in the arena.
     #[error("node not found: {0:?}")]
     NodeNotFound(NodeId),
+
+    /// I/O failure outside rendering.
+    #[error("io: {0}")]
+    Io(#[from] io::Error),
 }
 
 impl From<mpsc::RecvError> for Error {
@@ -1,8 +1,10 @@
 //! This module contains the core primitives to represent keyboard input.
 use std::{fmt, ops::Add};
 
+use serde::{Deserialize, Serialize};
+
 /// Modifier key state.
-#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
 pub struct Mods {
     /// Shift is active.
     pub shift: bool,
@@ -76,7 +78,7 @@ pub const Alt: Mods = Mods {
 };
 
 /// Physical modifier key codes.
-#[derive(Debug, PartialOrd, PartialEq, Hash, Eq, Clone, Copy)]
+#[derive(Debug, PartialOrd, PartialEq, Hash, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum ModifierKeyCode {
     /// Left Shift key.
     LeftShift,
@@ -109,7 +111,7 @@ pub enum ModifierKeyCode {
 }
 
 /// Media key codes.
-#[derive(Debug, PartialOrd, PartialEq, Hash, Eq, Clone, Copy)]
+#[derive(Debug, PartialOrd, PartialEq, Hash, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum MediaKeyCode {
     /// Play media key.
     Play,
@@ -140,7 +142,7 @@ pub enum MediaKeyCode {
 }
 
 /// Logical key codes.
-#[derive(Debug, PartialOrd, PartialEq, Hash, Eq, Clone, Copy)]
+#[derive(Debug, PartialOrd, PartialEq, Hash, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum KeyCode {
     /// Backspace key.
     Backspace,
@@ -212,8 +214,7 @@ impl From<char> for KeyCode {
 const LEAVE_INTACT: &[KeyCode] = &[KeyCode::Enter, KeyCode::Char(' ')];
 
 /// A keystroke along with modifiers.
-/// A keystroke along with modifiers.
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
 pub struct Key {
     /// Modifier state.
     pub mods: Mods,
@@ -320,16 +321,19 @@ impl Key {
             return Err("key specification cannot be empty".into());
         }
 
-        let parts = spec
-            .split(['-', '+'])
-            .filter(|part| !part.is_empty())
-            .collect::<Vec<_>>();
-        let Some((key_part, modifier_parts)) = parts.split_last() else {
-            return Err("key specification cannot be empty".into());
+        // The final character is always part of the key, so `-`, `ctrl--` and `ctrl++`
+        // name the separator characters themselves.
+        let last = spec.len() - spec.chars().next_back().map_or(0, char::len_utf8);
+        let (modifier_spec, key_part) = match spec[..last].rfind(['-', '+']) {
+            Some(idx) => (&spec[..idx], &spec[idx + 1..]),
+            None => ("", spec),
         };
 
         let mut mods = Empty;
-        for part in modifier_parts {
+        for part in modifier_spec
+            .split(['-', '+'])
+            .filter(|part| !part.is_empty())
+        {
             if part.eq_ignore_ascii_case("ctrl") || part.eq_ignore_ascii_case("control") {
                 mods.ctrl = true;
             } else if part.eq_ignore_ascii_case("alt") {
@@ -573,6 +577,8 @@ mod tests {
         assert_eq!(Key::parse_spec("ArrowUp"), Ok(KeyCode::Up.into()));
         assert_eq!(Key::parse_spec("A"), Ok('A'.into()));
         assert_eq!(Key::parse_spec("Space"), Ok(' '.into()));
+        assert_eq!(Key::parse_spec("-"), Ok('-'.into()));
+        assert_eq!(Key::parse_spec("Ctrl+-"), Ok(Ctrl + '-'));
         assert!(Key::parse_spec("ctrl-what").is_err());
         Ok(())
     }
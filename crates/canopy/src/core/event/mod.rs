@@ -3,8 +3,13 @@ pub mod key;
 /// Mouse event types.
 pub mod mouse;
 
+use std::{any::Any, fmt, sync::Arc};
+
 use crate::{NodeId, geom::Size};
 
+/// Identifier a widget chooses for a timer set with `Context::set_timer`.
+pub type TimerId = u64;
+
 /// This enum represents all the event types that drive the application.
 #[derive(Debug, Clone)]
 pub enum Event {
@@ -16,6 +21,13 @@ pub enum Event {
     Resize(Size),
     /// A poll event
     Poll(Vec<NodeId>),
+    /// Timers due to fire, each delivered to its node as [`Event::Timer`].
+    Timers(Vec<(NodeId, TimerId)>),
+    /// A timer set by the receiving node fired.
+    Timer(TimerId),
+    /// The scroll offset of the node changed. Delivered to that node after the event that
+    /// moved it, then bubbles towards the root until handled.
+    Scroll(NodeId),
     /// Terminal has gained focus
     FocusGained,
     /// Terminal has lost focus
@@ -24,4 +36,41 @@ pub enum Event {
     Paste(String),
     /// Internal wake event used to service queued automation work.
     Wake,
+    /// An event pushed by an application event source.
+    App(AppEvent),
+}
+
+/// An event pushed from an application thread through an `EventSender`.
+///
+/// App events are routed along the focus path like paste events, bubbling towards the
+/// root until a widget handles them.
+#[derive(Clone)]
+pub struct AppEvent {
+    /// Tag naming the source that produced the event.
+    pub source: Arc<str>,
+    /// Event payload.
+    pub payload: Arc<dyn Any + Send + Sync>,
+}
+
+impl AppEvent {
+    /// Construct an event from `source` carrying `payload`.
+    pub fn new(source: impl Into<Arc<str>>, payload: impl Any + Send + Sync) -> Self {
+        Self {
+            source: source.into(),
+            payload: Arc::new(payload),
+        }
+    }
+
+    /// Return the payload if it has type `T`.
+    pub fn payload<T: Any>(&self) -> Option<&T> {
+        self.payload.downcast_ref()
+    }
+}
+
+impl fmt::Debug for AppEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AppEvent")
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
 }
@@ -1,5 +1,7 @@
 use std::ops::Add;
 
+use serde::{Deserialize, Serialize};
+
 use crate::{event::key, geom::Point};
 
 /// An abstract specification for a mouse action.
@@ -14,7 +16,7 @@ pub struct Mouse {
 }
 
 /// Mouse button codes.
-#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
 pub enum Button {
     /// Left mouse button.
     Left,
@@ -57,7 +59,7 @@ impl Add<Action> for Button {
 }
 
 /// Mouse action kinds.
-#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
 pub enum Action {
     /// Button press.
     Down,
@@ -1,6 +1,20 @@
+use std::{mem, result::Result as StdResult};
+
 use crate::{
     ReadContext,
-    core::{context::CoreViewContext, id::NodeId, widget_access, world::Core},
+    commands::{
+        ArgValue, CommandArgs, CommandDispatchKind, CommandDocSpec, CommandError, CommandId,
+        CommandInvocation, CommandNode, CommandParamKind, CommandParamSpec, CommandReturnSpec,
+        CommandSpec, CommandTypeSpec, FromArgValue,
+    },
+    core::{
+        canopy::{Canopy, Loader},
+        context::CoreViewContext,
+        id::NodeId,
+        widget_access,
+        world::Core,
+    },
+    error::{Error, Result},
     geom::{Direction, RectI32},
     path::Path,
 };
@@ -51,7 +65,7 @@ impl Core {
         let mut parts = Vec::new();
         let mut current = self.focus;
         while let Some(id) = current {
-            parts.push(self.nodes[id].name.to_string());
+            parts.push(self.nodes[id].name().to_string());
             if id == root {
                 break;
             }
@@ -64,8 +78,77 @@ impl Core {
         Path::new(parts)
     }
 
+    /// Confine focus traversal to the subtree at `node` until the scope is popped. Scopes
+    /// nest; the most recently pushed scope is active. Focus moves into the scope if it is
+    /// currently outside.
+    pub fn push_focus_scope(&mut self, node: NodeId) {
+        self.focus_scopes.retain(|scope| *scope != node);
+        self.focus_scopes.push(node);
+        if self
+            .focus
+            .is_none_or(|focus| !is_descendant(self, node, focus))
+        {
+            self.focus_first(node);
+        }
+    }
+
+    /// Remove a focus scope. Returns `true` if the node was a scope.
+    pub fn pop_focus_scope(&mut self, node: NodeId) -> bool {
+        let before = self.focus_scopes.len();
+        self.focus_scopes.retain(|scope| *scope != node);
+        self.focus_scopes.len() != before
+    }
+
+    /// Return the active focus scope, if any.
+    pub fn focus_scope(&self) -> Option<NodeId> {
+        self.focus_scopes.last().copied()
+    }
+
+    /// Save the current focus on the focus history stack.
+    pub fn focus_push(&mut self) {
+        if let Some(focus) = self.focus {
+            self.focus_history.push(focus);
+        }
+    }
+
+    /// Restore the most recently saved focus that is still attached and focusable.
+    /// Returns `true` if focus was restored.
+    pub fn focus_pop(&mut self) -> bool {
+        while let Some(id) = self.focus_history.pop() {
+            if self.nodes.contains_key(id)
+                && self.is_attached_to_root(id)
+                && is_focus_candidate(self, id, false)
+            {
+                self.set_focus(id);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Narrow a traversal root to the active focus scope when the scope lies within it.
+    fn scoped_root(&self, root: NodeId) -> NodeId {
+        match self.focus_scope() {
+            Some(scope) if is_descendant(self, root, scope) => scope,
+            _ => root,
+        }
+    }
+
+    /// Set a node's explicit tab index. Nodes with a tab index are visited first by
+    /// `focus_next` and `focus_prev`, in ascending order; the rest follow in tree order.
+    pub fn set_tab_index(&mut self, node: NodeId, index: Option<u32>) -> Result<()> {
+        let node = self.nodes.get_mut(node).ok_or(Error::NodeNotFound(node))?;
+        node.tab_index = index;
+        Ok(())
+    }
+
     /// Focus the first node that accepts focus in the pre-order traversal of the subtree at root.
     pub fn focus_first(&mut self, root: NodeId) {
+        let root = self.scoped_root(root);
+        if let Some(order) = tab_order(self, root) {
+            self.set_focus(order[0]);
+            return;
+        }
         if let Some(target) = first_focusable(self, root) {
             self.set_focus(target);
         }
@@ -73,6 +156,15 @@ impl Core {
 
     /// Focus the next node in the pre-order traversal of root.
     pub fn focus_next(&mut self, root: NodeId) {
+        let root = self.scoped_root(root);
+        if let Some(order) = tab_order(self, root) {
+            let next = self
+                .focus
+                .and_then(|focus| order.iter().position(|id| *id == focus))
+                .map_or(0, |idx| (idx + 1) % order.len());
+            self.set_focus(order[next]);
+            return;
+        }
         if let Some(current) = self.focus
             && let Some(target) = find_next_focus(self, root, current, false)
         {
@@ -88,6 +180,15 @@ impl Core {
 
     /// Focus the previous node in the pre-order traversal of `root`.
     pub fn focus_prev(&mut self, root: NodeId) {
+        let root = self.scoped_root(root);
+        if let Some(order) = tab_order(self, root) {
+            let prev = self
+                .focus
+                .and_then(|focus| order.iter().position(|id| *id == focus))
+                .map_or(order.len() - 1, |idx| (idx + order.len() - 1) % order.len());
+            self.set_focus(order[prev]);
+            return;
+        }
         if let Some(current) = self.focus
             && let Some(target) = find_prev_focus(self, root, current)
         {
@@ -104,6 +205,7 @@ impl Core {
 
     /// Move focus in a specified direction within the subtree at root.
     pub fn focus_dir(&mut self, root: NodeId, dir: Direction) {
+        let root = self.scoped_root(root);
         let mut focusables = Vec::new();
         let ctx = CoreViewContext::new(self, root);
         let ctx = &ctx as &dyn ReadContext;
@@ -239,10 +341,29 @@ impl Core {
         }
     }
 
-    /// Ensure focus and mouse capture invariants after structural changes.
+    /// Drop focus scopes and focus history entries whose nodes are no longer attached.
+    pub fn ensure_focus_stacks_valid(&mut self) {
+        let attached = |core: &Self, id: &NodeId| {
+            core.nodes.contains_key(*id) && core.is_attached_to_root(*id)
+        };
+        let mut scopes = mem::take(&mut self.focus_scopes);
+        scopes.retain(|scope| attached(self, scope));
+        self.focus_scopes = scopes;
+        let mut history = mem::take(&mut self.focus_history);
+        history.retain(|id| attached(self, id));
+        self.focus_history = history;
+    }
+
+    /// Ensure focus, focus scope, mouse capture, hover, and drag invariants after structural changes.
+    ///
+    /// The next frame is redrawn in full, since nodes may have appeared or vanished.
     pub fn ensure_invariants(&mut self, removed_root: Option<NodeId>) {
+        self.views_changed = true;
+        self.ensure_focus_stacks_valid();
         self.ensure_focus_valid(removed_root);
         self.ensure_mouse_capture_valid();
+        self.ensure_hover_valid();
+        self.ensure_drag_valid();
         self.debug_assert_tree_invariants();
     }
 
@@ -305,6 +426,28 @@ fn first_focusable_with(core: &Core, root: NodeId, require_view: bool) -> Option
         .find(|id| is_focus_candidate(core, *id, require_view))
 }
 
+/// Return focusable nodes under `root` in tab order, or `None` if no candidate declares a
+/// tab index and plain tree order applies.
+fn tab_order(core: &Core, root: NodeId) -> Option<Vec<NodeId>> {
+    let ctx = CoreViewContext::new(core, root);
+    let ctx = &ctx as &dyn ReadContext;
+    for require_view in [true, false] {
+        let mut order: Vec<NodeId> = ctx
+            .preorder(root)
+            .filter(|id| is_focus_candidate(core, *id, require_view))
+            .collect();
+        if order.is_empty() {
+            continue;
+        }
+        if order.iter().all(|id| core.nodes[*id].tab_index.is_none()) {
+            return None;
+        }
+        order.sort_by_key(|id| core.nodes[*id].tab_index.map_or((1, 0), |index| (0, index)));
+        return Some(order);
+    }
+    None
+}
+
 /// Find next focusable node after `target`.
 /// If `skip_subtree` is true, traversal skips `target`'s children.
 fn find_next_focus(
@@ -422,3 +565,78 @@ fn is_focus_candidate(core: &Core, node_id: NodeId, require_view: bool) -> bool
     }
     widget_access::accepts_focus(core, node_id)
 }
+
+/// Read the single `path` argument of `focus::to`.
+fn path_arg(inv: &CommandInvocation) -> StdResult<String, CommandError> {
+    let value = match &inv.args {
+        CommandArgs::Positional(values) => match values.as_slice() {
+            [value] => value,
+            _ => {
+                return Err(CommandError::ArityMismatch {
+                    expected: 1,
+                    got: values.len(),
+                });
+            }
+        },
+        CommandArgs::Named(values) => {
+            values
+                .get("path")
+                .ok_or_else(|| CommandError::MissingNamedArg {
+                    name: "path".to_string(),
+                })?
+        }
+    };
+    String::from_arg_value(value).map_err(|err| err.with_param("path"))
+}
+
+/// Focus the first node whose path matches a filter.
+static TO: CommandSpec = CommandSpec {
+    id: CommandId("focus::to"),
+    name: "to",
+    dispatch: CommandDispatchKind::Free,
+    params: &[CommandParamSpec {
+        name: "path",
+        kind: CommandParamKind::User,
+        doc: Some("Path filter matched against node paths from the root."),
+        ty: CommandTypeSpec {
+            rust: "String",
+            luau: None,
+            doc: None,
+        },
+        optional: false,
+        default: None,
+    }],
+    ret: CommandReturnSpec::Value(CommandTypeSpec {
+        rust: "bool",
+        luau: None,
+        doc: None,
+    }),
+    doc: CommandDocSpec {
+        short: Some("Focus the first node whose path matches a filter."),
+        long: None,
+        hidden: false,
+    },
+    keys: &[],
+    invoke: |_, ctx, inv| {
+        let path = path_arg(inv)?;
+        let focused = ctx
+            .focus_path(&path)
+            .map_err(|err| CommandError::Exec(err.into()))?;
+        Ok(ArgValue::Bool(focused))
+    },
+};
+
+/// Registers the `focus::*` commands.
+pub struct FocusCommands;
+
+impl CommandNode for FocusCommands {
+    fn commands() -> &'static [&'static CommandSpec] {
+        &[&TO]
+    }
+}
+
+impl Loader for FocusCommands {
+    fn load(c: &mut Canopy) -> Result<()> {
+        c.add_commands::<Self>()
+    }
+}
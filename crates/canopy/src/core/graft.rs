@@ -0,0 +1,31 @@
+use crate::{
+    ReadContext, error::Result, layout::Layout, render::Render, state::NodeName, widget::Widget,
+};
+
+/// Host node for an independent application embedded with [`Canopy::graft`].
+///
+/// The grafted app keeps its own core, bindings, styles, and focus. The host node fills
+/// its slot and accepts focus; while focused, key, paste, and terminal focus events go to
+/// the grafted app, and mouse events over the node arrive in its coordinates. Bindings of
+/// the host app anchored at the graft's path still run before the event is forwarded.
+///
+/// [`Canopy::graft`]: crate::Canopy::graft
+pub struct Graft;
+
+impl Widget for Graft {
+    fn layout(&self) -> Layout {
+        Layout::fill()
+    }
+
+    fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+        true
+    }
+
+    fn render(&mut self, _frame: &mut Render, _ctx: &dyn ReadContext) -> Result<()> {
+        Ok(())
+    }
+
+    fn name(&self) -> NodeName {
+        NodeName::convert("graft")
+    }
+}
@@ -58,6 +58,21 @@ impl<'a> HelpCommand<'a> {
     }
 }
 
+/// A binding in any input mode, listed without regard to focus.
+#[derive(Debug, Clone)]
+pub struct CatalogBinding {
+    /// The input (key or mouse) that triggers this binding.
+    pub input: InputSpec,
+    /// The mode this binding belongs to.
+    pub mode: String,
+    /// The original path filter string.
+    pub path_filter: String,
+    /// Human-readable label derived from command docs or script source.
+    pub label: String,
+    /// The binding target.
+    pub target: BindingTarget,
+}
+
 /// A contextual help snapshot combining bindings and commands.
 #[derive(Debug)]
 pub struct HelpSnapshot<'a> {
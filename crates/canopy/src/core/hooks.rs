@@ -0,0 +1,209 @@
+use std::{
+    mem,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use crate::{
+    Context,
+    commands::{ArgValue, CommandError, CommandInvocation},
+    core::{canopy::RouteTraceEntry, world::Core},
+    error::Result as CanopyResult,
+    event::Event,
+};
+
+/// Decision returned by a pre-dispatch hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookFlow {
+    /// Pass the (possibly rewritten) value on to the next hook and then the tree.
+    Continue,
+    /// Drop the value. Later hooks and normal dispatch do not see it.
+    Swallow,
+}
+
+/// Hook run before an event is routed. It may rewrite the event in place.
+pub type EventHook = Box<dyn FnMut(&mut Event) -> HookFlow + Send>;
+
+/// Hook run after an event has been routed. Key and mouse events come with the route trace
+/// of their dispatch; other events get an empty trace.
+pub type AfterEventHook = Box<dyn FnMut(&Event, &[RouteTraceEntry]) + Send>;
+
+/// Hook run before a command is dispatched. It may rewrite the invocation in place.
+pub type CommandHook = Box<dyn FnMut(&mut CommandInvocation) -> HookFlow + Send>;
+
+/// Hook run after a command has been dispatched, with its result.
+pub type AfterCommandHook =
+    Box<dyn FnMut(&CommandInvocation, &Result<ArgValue, CommandError>) + Send>;
+
+/// Hook run once when the app exits, after the terminal has been restored.
+pub type ExitHook = Box<dyn FnOnce(&mut dyn Context) -> CanopyResult<()> + Send>;
+
+/// Registration of a dispatch hook. Dropping the handle unregisters the hook; call
+/// [`HookHandle::detach`] to keep it registered for the life of the app.
+#[derive(Debug)]
+#[must_use = "dropping the handle unregisters the hook"]
+pub struct HookHandle {
+    /// Cleared to unregister the hook.
+    registered: Arc<AtomicBool>,
+    /// Whether dropping the handle leaves the hook registered.
+    detached: bool,
+}
+
+impl HookHandle {
+    /// Keep the hook registered after the handle is dropped.
+    pub fn detach(mut self) {
+        self.detached = true;
+    }
+}
+
+impl Drop for HookHandle {
+    fn drop(&mut self) {
+        if !self.detached {
+            self.registered.store(false, Ordering::SeqCst);
+        }
+    }
+}
+
+/// A hook and the flag its handle clears to unregister it.
+struct Registered<H> {
+    /// Whether the hook is still registered.
+    registered: Arc<AtomicBool>,
+    /// The hook itself.
+    hook: H,
+}
+
+/// Add a hook to `hooks`, returning the handle that unregisters it.
+fn register<H>(hooks: &mut Vec<Registered<H>>, hook: H) -> HookHandle {
+    let registered = Arc::new(AtomicBool::new(true));
+    hooks.push(Registered {
+        registered: Arc::clone(&registered),
+        hook,
+    });
+    HookHandle {
+        registered,
+        detached: false,
+    }
+}
+
+/// Drop hooks whose handles have unregistered them, returning the rest.
+fn live<H>(hooks: &mut Vec<Registered<H>>) -> &mut [Registered<H>] {
+    hooks.retain(|h| h.registered.load(Ordering::SeqCst));
+    hooks
+}
+
+/// Application-registered dispatch hooks, run in registration order.
+#[derive(Default)]
+pub(crate) struct Hooks {
+    /// Hooks run before event routing.
+    before_event: Vec<Registered<EventHook>>,
+    /// Hooks run after event routing.
+    after_event: Vec<Registered<AfterEventHook>>,
+    /// Hooks run before command dispatch.
+    before_command: Vec<Registered<CommandHook>>,
+    /// Hooks run after command dispatch.
+    after_command: Vec<Registered<AfterCommandHook>>,
+    /// Hooks run on exit.
+    pub(crate) on_exit: Vec<ExitHook>,
+}
+
+impl Hooks {
+    /// Run pre-event hooks, stopping at the first that swallows the event.
+    pub(crate) fn before_event(&mut self, event: &mut Event) -> HookFlow {
+        run_before(live(&mut self.before_event), event)
+    }
+
+    /// Run post-event hooks.
+    pub(crate) fn after_event(&mut self, event: &Event, trace: &[RouteTraceEntry]) {
+        for h in live(&mut self.after_event) {
+            (h.hook)(event, trace);
+        }
+    }
+
+    /// Whether any pre-command hooks may be registered.
+    pub(crate) fn has_command_hooks(&self) -> bool {
+        !self.before_command.is_empty()
+    }
+
+    /// Run pre-command hooks, stopping at the first that swallows the command.
+    pub(crate) fn before_command(&mut self, cmd: &mut CommandInvocation) -> HookFlow {
+        run_before(live(&mut self.before_command), cmd)
+    }
+
+    /// Run post-command hooks.
+    pub(crate) fn after_command(
+        &mut self,
+        cmd: &CommandInvocation,
+        result: &Result<ArgValue, CommandError>,
+    ) {
+        for h in live(&mut self.after_command) {
+            (h.hook)(cmd, result);
+        }
+    }
+
+    /// Remove and return the exit hooks.
+    pub(crate) fn take_exit_hooks(&mut self) -> Vec<ExitHook> {
+        mem::take(&mut self.on_exit)
+    }
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Core {
+    /// Register a hook that runs before each input event is routed. Hooks run in
+    /// registration order; each may rewrite the event or swallow it.
+    pub fn on_event(
+        &mut self,
+        hook: impl FnMut(&mut Event) -> HookFlow + Send + 'static,
+    ) -> HookHandle {
+        register(&mut self.hooks.before_event, Box::new(hook))
+    }
+
+    /// Register a hook that observes each input event after it has been routed, along with
+    /// the route trace of key and mouse events.
+    pub fn after_event(
+        &mut self,
+        hook: impl FnMut(&Event, &[RouteTraceEntry]) + Send + 'static,
+    ) -> HookHandle {
+        register(&mut self.hooks.after_event, Box::new(hook))
+    }
+
+    /// Register a hook that runs before every command dispatch, including commands
+    /// dispatched by other commands. Each hook may rewrite the invocation or swallow it.
+    pub fn on_command(
+        &mut self,
+        hook: impl FnMut(&mut CommandInvocation) -> HookFlow + Send + 'static,
+    ) -> HookHandle {
+        register(&mut self.hooks.before_command, Box::new(hook))
+    }
+
+    /// Register a hook that observes every command dispatch and its result.
+    pub fn after_command(
+        &mut self,
+        hook: impl FnMut(&CommandInvocation, &Result<ArgValue, CommandError>) + Send + 'static,
+    ) -> HookHandle {
+        register(&mut self.hooks.after_command, Box::new(hook))
+    }
+
+    /// Register a hook that runs once when the app exits, after the terminal has been
+    /// restored. Exit hooks run in registration order, against the root context.
+    pub fn on_exit(
+        &mut self,
+        hook: impl FnOnce(&mut dyn Context) -> CanopyResult<()> + Send + 'static,
+    ) {
+        self.hooks.on_exit.push(Box::new(hook));
+    }
+}
+
+/// Run a chain of rewriting hooks over `value`.
+fn run_before<T: ?Sized>(
+    hooks: &mut [Registered<Box<dyn FnMut(&mut T) -> HookFlow + Send>>],
+    value: &mut T,
+) -> HookFlow {
+    for h in hooks {
+        if (h.hook)(value) == HookFlow::Swallow {
+            return HookFlow::Swallow;
+        }
+    }
+    HookFlow::Continue
+}
@@ -0,0 +1,42 @@
+use crate::{
+    core::{id::NodeId, world::Core},
+    error::Result,
+    geom::Point,
+};
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Core {
+    /// Is the mouse pointer over this node, rather than one of its children?
+    pub fn is_hovered(&self, node: NodeId) -> bool {
+        self.hover == Some(node)
+    }
+
+    /// Move the hover to the node under `location`. Returns `true` if it changed.
+    ///
+    /// While a node captures the mouse, it stays hovered wherever the pointer goes. The
+    /// previously and newly hovered nodes are marked damaged so that hover styles redraw.
+    pub(crate) fn update_hover(&mut self, location: Point) -> Result<bool> {
+        let next = match self.mouse_capture {
+            Some(capture) if self.nodes.contains_key(capture) => Some(capture),
+            _ => self.locate_node(self.root, location)?,
+        };
+        if next == self.hover {
+            return Ok(false);
+        }
+        let prev = self.hover;
+        self.hover = next;
+        for id in [prev, next].into_iter().flatten() {
+            self.damage_view(id);
+        }
+        Ok(true)
+    }
+
+    /// Ensure the hovered node, if any, is attached.
+    pub fn ensure_hover_valid(&mut self) {
+        if let Some(hover) = self.hover
+            && (!self.nodes.contains_key(hover) || !self.is_attached_to_root(hover))
+        {
+            self.hover = None;
+        }
+    }
+}
@@ -334,6 +334,8 @@ pub struct InputMap {
     current_mode: String,
     /// Next binding identifier.
     next_id: u64,
+    /// Counter bumped whenever a binding is added or removed.
+    revision: u64,
 }
 
 impl Default for InputMap {
@@ -352,9 +354,15 @@ impl InputMap {
             current_mode: DEFAULT_MODE.into(),
             modes,
             next_id: 1,
+            revision: 0,
         }
     }
 
+    /// Return a counter that changes whenever a binding is added or removed.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
     /// Set the current input mode.
     pub fn set_mode(&mut self, mode: &str) -> Result<()> {
         if mode.is_empty() {
@@ -513,6 +521,7 @@ impl InputMap {
             .entry(mode.to_string())
             .or_insert_with(InputMode::new)
             .insert(id, pathmatch, input, action);
+        self.revision += 1;
         Ok(id)
     }
 
@@ -532,7 +541,11 @@ impl InputMap {
                 targets.extend(removed_targets);
             }
         }
-        if removed { targets } else { Vec::new() }
+        if !removed {
+            return Vec::new();
+        }
+        self.revision += 1;
+        targets
     }
 
     /// Remove bindings matching an input/mode/path filter.
@@ -555,6 +568,9 @@ impl InputMap {
 
         self.modes
             .retain(|mode, actions| mode == DEFAULT_MODE || !actions.inputs.is_empty());
+        if !removed.is_empty() {
+            self.revision += 1;
+        }
         removed
     }
 
@@ -585,6 +601,7 @@ impl InputMap {
         }
         self.modes.retain(|mode, _| mode == DEFAULT_MODE);
         self.current_mode = DEFAULT_MODE.to_string();
+        self.revision += 1;
         removed
     }
 
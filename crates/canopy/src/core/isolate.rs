@@ -0,0 +1,52 @@
+//! Panic isolation for widget callbacks.
+
+use std::{
+    any::Any,
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+};
+
+thread_local! {
+    /// Number of isolated callbacks running on this thread.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Run `f`, catching a panic and returning its message instead of unwinding further.
+pub(crate) fn catch<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    DEPTH.with(|depth| depth.set(depth.get() + 1));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    DEPTH.with(|depth| depth.set(depth.get() - 1));
+    result.map_err(|payload| message(payload.as_ref()))
+}
+
+/// Will a panic raised on this thread now be caught by `catch`? Panic hooks use this to
+/// leave the terminal alone for isolated panics.
+pub(crate) fn active() -> bool {
+    DEPTH.with(|depth| depth.get() > 0)
+}
+
+/// Extract a readable message from a panic payload.
+fn message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panic with a non-string payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catch_returns_the_panic_message() {
+        assert_eq!(catch(|| 3), Ok(3));
+        assert_eq!(
+            catch::<()>(|| panic!("bad {}", 1)),
+            Err("bad 1".to_string())
+        );
+        assert!(!active());
+    }
+}
@@ -0,0 +1,267 @@
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write as _},
+    mem,
+    sync::{Arc, Mutex, OnceLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tracing::{Event as TracingEvent, Subscriber, field::Field};
+use tracing_subscriber::{Layer, field::Visit, layer::Context as LayerContext};
+
+use crate::CommandEnum;
+
+/// Number of records the shared log buffer keeps before dropping the oldest.
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// Severity of a log record, from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, CommandEnum)]
+pub enum Level {
+    /// Very verbose diagnostics.
+    Trace,
+    /// Debugging information.
+    Debug,
+    /// Normal operation.
+    Info,
+    /// Something unexpected that was handled.
+    Warn,
+    /// A failure.
+    Error,
+}
+
+impl Level {
+    /// Return a fixed-width label for aligned display.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Trace => "TRACE",
+            Self::Debug => "DEBUG",
+            Self::Info => "INFO ",
+            Self::Warn => "WARN ",
+            Self::Error => "ERROR",
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label().trim_end())
+    }
+}
+
+impl From<tracing::Level> for Level {
+    fn from(level: tracing::Level) -> Self {
+        match level {
+            tracing::Level::TRACE => Self::Trace,
+            tracing::Level::DEBUG => Self::Debug,
+            tracing::Level::INFO => Self::Info,
+            tracing::Level::WARN => Self::Warn,
+            tracing::Level::ERROR => Self::Error,
+        }
+    }
+}
+
+/// A single time-stamped log line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    /// When the record was produced.
+    pub time: SystemTime,
+    /// Record severity.
+    pub level: Level,
+    /// Module or target that produced the record, empty if unknown.
+    pub target: String,
+    /// Message text.
+    pub message: String,
+}
+
+impl LogRecord {
+    /// Construct a record without a target, stamped with the current time.
+    pub fn new(level: Level, message: impl Into<String>) -> Self {
+        Self {
+            time: SystemTime::now(),
+            level,
+            target: String::new(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Formats as `HH:MM:SS.mmm LEVEL target: message`, with the time in UTC.
+impl fmt::Display for LogRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let since_epoch = self.time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let secs = since_epoch.as_secs() % 86_400;
+        write!(
+            f,
+            "{:02}:{:02}:{:02}.{:03} {} ",
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60,
+            since_epoch.subsec_millis(),
+            self.level.label(),
+        )?;
+        if !self.target.is_empty() {
+            write!(f, "{}: ", self.target)?;
+        }
+        f.write_str(&self.message)
+    }
+}
+
+/// Records held by a [`LogBuffer`].
+struct Ring {
+    /// Stored records, oldest first.
+    records: VecDeque<LogRecord>,
+    /// Sequence number of the oldest stored record.
+    first_seq: u64,
+    /// Maximum number of stored records.
+    capacity: usize,
+}
+
+/// A shared, bounded buffer of log records.
+///
+/// Any number of readers consume the buffer independently, each keeping its own cursor.
+/// Clones share the same records.
+#[derive(Clone)]
+pub struct LogBuffer {
+    /// Shared record storage.
+    ring: Arc<Mutex<Ring>>,
+}
+
+impl LogBuffer {
+    /// Construct a buffer that keeps at most `capacity` records.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            ring: Arc::new(Mutex::new(Ring {
+                records: VecDeque::new(),
+                first_seq: 0,
+                capacity: capacity.max(1),
+            })),
+        }
+    }
+
+    /// Append a record, dropping the oldest once the capacity is reached.
+    pub fn push(&self, record: LogRecord) {
+        let mut ring = self.ring.lock().unwrap();
+        ring.records.push_back(record);
+        while ring.records.len() > ring.capacity {
+            ring.records.pop_front();
+            ring.first_seq += 1;
+        }
+    }
+
+    /// Return records added since `cursor` and advance it. Records dropped before they
+    /// were read are skipped.
+    pub fn read(&self, cursor: &mut u64) -> Vec<LogRecord> {
+        let ring = self.ring.lock().unwrap();
+        let start = cursor.saturating_sub(ring.first_seq) as usize;
+        let records: Vec<LogRecord> = ring.records.iter().skip(start).cloned().collect();
+        *cursor = ring.first_seq + ring.records.len() as u64;
+        records
+    }
+}
+
+/// Return the process-wide buffer fed by [`tracing_layer`].
+pub fn log_buffer() -> &'static LogBuffer {
+    static BUFFER: OnceLock<LogBuffer> = OnceLock::new();
+    BUFFER.get_or_init(|| LogBuffer::new(DEFAULT_CAPACITY))
+}
+
+/// A `tracing` layer that appends each event to a [`LogBuffer`].
+pub struct LogLayer {
+    /// Destination buffer.
+    buffer: LogBuffer,
+}
+
+impl LogLayer {
+    /// Construct a layer writing to `buffer`.
+    pub fn new(buffer: LogBuffer) -> Self {
+        Self { buffer }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogLayer {
+    fn on_event(&self, event: &TracingEvent<'_>, _ctx: LayerContext<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        self.buffer.push(LogRecord {
+            time: SystemTime::now(),
+            level: (*metadata.level()).into(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+        });
+    }
+}
+
+/// Collects an event's message followed by its other fields as `name=value`.
+#[derive(Default)]
+struct MessageVisitor {
+    /// Formatted message.
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let fields = mem::take(&mut self.message);
+            let _ = write!(self.message, "{value:?}{fields}");
+        } else {
+            let _ = write!(self.message, " {}={value:?}", field.name());
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.insert_str(0, value);
+        } else {
+            let _ = write!(self.message, " {}={value}", field.name());
+        }
+    }
+}
+
+/// Return a `tracing` layer that routes events into the shared [`log_buffer`].
+///
+/// Add it to the application's own subscriber so that `LogView` and the inspector can
+/// show log output without installing a global subscriber themselves.
+pub fn tracing_layer() -> LogLayer {
+    LogLayer::new(log_buffer().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn layer_routes_events_into_buffer() {
+        let buffer = LogBuffer::new(2);
+        let subscriber = tracing_subscriber::registry().with(LogLayer::new(buffer.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(count = 3, "first");
+            tracing::warn!("second");
+        });
+
+        let mut cursor = 0;
+        let records = buffer.read(&mut cursor);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].level, Level::Info);
+        assert_eq!(records[0].message, "first count=3");
+        assert!(
+            records[1]
+                .to_string()
+                .contains("WARN  canopy::core::logging::tests: second")
+        );
+        assert!(buffer.read(&mut cursor).is_empty());
+
+        buffer.push(LogRecord::new(Level::Error, "third"));
+        buffer.push(LogRecord::new(Level::Error, "fourth"));
+        buffer.push(LogRecord::new(Level::Error, "fifth"));
+        let messages: Vec<_> = buffer
+            .read(&mut cursor)
+            .into_iter()
+            .map(|r| r.message)
+            .collect();
+        assert_eq!(messages, ["fourth", "fifth"]);
+        assert_eq!(cursor, 5);
+    }
+}
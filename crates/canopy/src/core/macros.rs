@@ -0,0 +1,241 @@
+use std::{collections::BTreeMap, mem};
+
+use serde_json::{Map as JsonMap, Value as JsonValue};
+
+use crate::{
+    commands::{ArgValue, CommandArgs, CommandError, CommandInvocation, CommandSet},
+    event::key::Key,
+};
+
+/// Maximum nesting of replays, so a macro that replays itself terminates.
+pub(crate) const MAX_REPLAY_DEPTH: usize = 16;
+
+/// One recorded step of a macro.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MacroStep {
+    /// A command dispatched outside any other command.
+    Command(CommandInvocation),
+    /// A key handled by a widget rather than a binding.
+    Key(Key),
+}
+
+/// Macro registers and the in-progress recording.
+#[derive(Debug, Default)]
+pub(crate) struct Macros {
+    /// Completed recordings keyed by register name.
+    registers: BTreeMap<String, Vec<MacroStep>>,
+    /// Register being recorded and the steps captured so far.
+    recording: Option<(String, Vec<MacroStep>)>,
+    /// Depth of nested command dispatch. Only outermost dispatches are recorded.
+    dispatch_depth: usize,
+    /// Depth of nested replays. Nothing is recorded while a replay runs.
+    pub(crate) replay_depth: usize,
+    /// Replays requested from widgets, run after the current input event.
+    pending: Vec<(String, usize)>,
+}
+
+impl Macros {
+    /// Begin recording into `register`, saving any recording already in progress.
+    pub(crate) fn start(&mut self, register: &str) {
+        self.stop();
+        self.recording = Some((register.to_string(), Vec::new()));
+    }
+
+    /// Finish the current recording and return its register.
+    pub(crate) fn stop(&mut self) -> Option<String> {
+        let (register, steps) = self.recording.take()?;
+        self.registers.insert(register.clone(), steps);
+        Some(register)
+    }
+
+    /// Return the register being recorded, if any.
+    pub(crate) fn recording(&self) -> Option<&str> {
+        self.recording
+            .as_ref()
+            .map(|(register, _)| register.as_str())
+    }
+
+    /// Return the steps stored in a register.
+    pub(crate) fn get(&self, register: &str) -> Option<&[MacroStep]> {
+        self.registers.get(register).map(Vec::as_slice)
+    }
+
+    /// Append a step to the active recording unless a replay or command is running.
+    pub(crate) fn record(&mut self, step: MacroStep) {
+        if self.dispatch_depth > 0 || self.replay_depth > 0 {
+            return;
+        }
+        if let Some((_, steps)) = self.recording.as_mut() {
+            steps.push(step);
+        }
+    }
+
+    /// Note entry into command dispatch. Returns the register that should receive the
+    /// command if it completes as an outermost dispatch.
+    pub(crate) fn enter_dispatch(&mut self) -> Option<String> {
+        let outermost = self.dispatch_depth == 0 && self.replay_depth == 0;
+        self.dispatch_depth += 1;
+        if outermost {
+            self.recording().map(str::to_string)
+        } else {
+            None
+        }
+    }
+
+    /// Note exit from command dispatch, recording `cmd` if recording was active throughout.
+    ///
+    /// Commands that start or stop a recording are therefore never captured.
+    pub(crate) fn exit_dispatch(&mut self, register: Option<String>, cmd: &CommandInvocation) {
+        self.dispatch_depth = self.dispatch_depth.saturating_sub(1);
+        if register.is_some() && register.as_deref() == self.recording() {
+            self.record(MacroStep::Command(cmd.clone()));
+        }
+    }
+
+    /// Queue a replay to run after the current input event.
+    pub(crate) fn queue_replay(&mut self, register: &str, count: usize) {
+        self.pending.push((register.to_string(), count));
+    }
+
+    /// Take all queued replays in submission order.
+    pub(crate) fn take_pending(&mut self) -> Vec<(String, usize)> {
+        mem::take(&mut self.pending)
+    }
+
+    /// Serialize all registers as a JSON object keyed by register name.
+    pub(crate) fn to_json(&self) -> Result<JsonValue, CommandError> {
+        let mut out = JsonMap::new();
+        for (register, steps) in &self.registers {
+            let steps = steps
+                .iter()
+                .map(step_to_json)
+                .collect::<Result<Vec<_>, _>>()?;
+            out.insert(register.clone(), JsonValue::Array(steps));
+        }
+        Ok(JsonValue::Object(out))
+    }
+
+    /// Replace registers from JSON produced by `to_json`, resolving command ids against
+    /// `commands`.
+    pub(crate) fn load_json(
+        &mut self,
+        value: JsonValue,
+        commands: &CommandSet,
+    ) -> Result<(), CommandError> {
+        let JsonValue::Object(registers) = value else {
+            return Err(invalid("expected an object of registers"));
+        };
+        let mut loaded = BTreeMap::new();
+        for (register, steps) in registers {
+            let JsonValue::Array(steps) = steps else {
+                return Err(invalid("expected an array of steps"));
+            };
+            let steps = steps
+                .into_iter()
+                .map(|step| step_from_json(step, commands))
+                .collect::<Result<Vec<_>, _>>()?;
+            loaded.insert(register, steps);
+        }
+        self.registers = loaded;
+        Ok(())
+    }
+}
+
+/// Build a parse error for malformed macro JSON.
+fn invalid(message: &str) -> CommandError {
+    CommandError::Parse {
+        input: "macros".to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// Encode a step as `{"command": id, "args": ...}` or `{"key": spec}`.
+fn step_to_json(step: &MacroStep) -> Result<JsonValue, CommandError> {
+    let mut out = JsonMap::new();
+    match step {
+        MacroStep::Command(cmd) => {
+            let args = match &cmd.args {
+                CommandArgs::Positional(args) => ArgValue::Array(args.clone()),
+                CommandArgs::Named(args) => ArgValue::Map(args.clone()),
+            };
+            out.insert("command".into(), JsonValue::String(cmd.id.0.to_string()));
+            out.insert("args".into(), args.to_json_value()?);
+        }
+        MacroStep::Key(key) => {
+            out.insert("key".into(), JsonValue::String(key.to_string()));
+        }
+    }
+    Ok(JsonValue::Object(out))
+}
+
+/// Decode a step produced by `step_to_json`.
+fn step_from_json(value: JsonValue, commands: &CommandSet) -> Result<MacroStep, CommandError> {
+    let JsonValue::Object(mut step) = value else {
+        return Err(invalid("expected a step object"));
+    };
+    if let Some(JsonValue::String(spec)) = step.remove("key") {
+        return Key::parse_spec(&spec)
+            .map(MacroStep::Key)
+            .map_err(|message| invalid(&message));
+    }
+    let Some(JsonValue::String(id)) = step.remove("command") else {
+        return Err(invalid("step needs a `command` or `key`"));
+    };
+    let spec = commands
+        .get(&id)
+        .ok_or(CommandError::UnknownCommand { id })?;
+    let args = match step
+        .remove("args")
+        .map(ArgValue::from_json_value)
+        .transpose()?
+    {
+        None => CommandArgs::default(),
+        Some(ArgValue::Array(args)) => CommandArgs::Positional(args),
+        Some(ArgValue::Map(args)) => CommandArgs::Named(args),
+        Some(_) => return Err(invalid("command args must be an array or object")),
+    };
+    Ok(MacroStep::Command(CommandInvocation { id: spec.id, args }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandId;
+
+    fn invocation(id: &'static str) -> CommandInvocation {
+        CommandInvocation {
+            id: CommandId(id),
+            args: CommandArgs::default(),
+        }
+    }
+
+    #[test]
+    fn only_outermost_dispatches_inside_a_recording_are_captured() {
+        let mut macros = Macros::default();
+
+        let register = macros.enter_dispatch();
+        macros.start("a");
+        macros.exit_dispatch(register, &invocation("root::record_macro"));
+
+        let outer = macros.enter_dispatch();
+        let inner = macros.enter_dispatch();
+        macros.exit_dispatch(inner, &invocation("inner::cmd"));
+        macros.exit_dispatch(outer, &invocation("outer::cmd"));
+
+        macros.record(MacroStep::Key('x'.into()));
+
+        let register = macros.enter_dispatch();
+        macros.stop();
+        macros.exit_dispatch(register, &invocation("root::stop_macro"));
+
+        assert_eq!(
+            macros.get("a"),
+            Some(
+                &[
+                    MacroStep::Command(invocation("outer::cmd")),
+                    MacroStep::Key('x'.into()),
+                ][..]
+            )
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use std::{any::Any, mem};
+
+use crate::{
+    ReadContext,
+    core::{
+        context::{CoreContext, CoreViewContext},
+        id::{NodeId, TypedId},
+        world::Core,
+    },
+    error::Result,
+    widget::Widget,
+};
+
+/// Maximum delivery rounds per event, so handlers that keep replying to each other
+/// terminate. Messages still queued after the last round wait for the next event.
+pub(crate) const MAX_MESSAGE_ROUNDS: usize = 16;
+
+/// Recipient of a node-to-node message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageTarget {
+    /// A specific node.
+    Node(NodeId),
+    /// The first node matching a path filter, resolved from the root at delivery.
+    Path(String),
+}
+
+impl From<NodeId> for MessageTarget {
+    fn from(node: NodeId) -> Self {
+        Self::Node(node)
+    }
+}
+
+impl<W: Widget> From<TypedId<W>> for MessageTarget {
+    fn from(node: TypedId<W>) -> Self {
+        Self::Node(node.into())
+    }
+}
+
+impl From<&str> for MessageTarget {
+    fn from(path: &str) -> Self {
+        Self::Path(path.to_string())
+    }
+}
+
+impl From<String> for MessageTarget {
+    fn from(path: String) -> Self {
+        Self::Path(path)
+    }
+}
+
+/// A message waiting for delivery.
+pub(crate) struct PendingMessage {
+    /// Recipient of the message.
+    pub(crate) target: MessageTarget,
+    /// Message payload, downcast by the recipient.
+    pub(crate) payload: Box<dyn Any>,
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Core {
+    /// Queue a message for delivery once the current event has been handled.
+    pub(crate) fn queue_message(&mut self, target: MessageTarget, payload: Box<dyn Any>) {
+        self.pending_messages
+            .push(PendingMessage { target, payload });
+    }
+
+    /// Take all queued messages in send order.
+    pub(crate) fn take_pending_messages(&mut self) -> Vec<PendingMessage> {
+        mem::take(&mut self.pending_messages)
+    }
+
+    /// Resolve a message target to a live node.
+    fn resolve_message_target(&self, target: &MessageTarget) -> Option<NodeId> {
+        match target {
+            MessageTarget::Node(node) => self.nodes.contains_key(*node).then_some(*node),
+            MessageTarget::Path(path) => CoreViewContext::new(self, self.root).find_node(path),
+        }
+    }
+
+    /// Deliver a message to its recipient's `handle_message` hook and mark the recipient
+    /// damaged. Returns `false` if the target no longer resolves, in which case the
+    /// message is dropped.
+    pub(crate) fn deliver_message(&mut self, message: PendingMessage) -> Result<bool> {
+        let Some(node) = self.resolve_message_target(&message.target) else {
+            return Ok(false);
+        };
+        self.with_widget_mut(node, |widget, core| {
+            let mut ctx = CoreContext::new(core, node);
+            widget.handle_message(message.payload, &mut ctx)
+        })??;
+        self.damage_view(node);
+        Ok(true)
+    }
+}
@@ -2,14 +2,22 @@
 //! Core types and traits for the Canopy terminal UI library.
 
 // Core modules - public
+/// Accessibility metadata and announcements.
+pub mod a11y;
+/// Tweened values for animation.
+pub mod anim;
 /// Backend implementations.
 pub mod backend;
 /// Keyed child collection helpers.
 pub mod children;
+/// Real and virtual time sources.
+mod clock;
 /// Command definition and dispatch.
 pub mod commands;
 /// Cursor and position helpers.
 pub mod cursor;
+/// Drag-and-drop protocol.
+pub mod drag;
 /// Debug dump utilities.
 pub mod dump;
 /// Core error types.
@@ -18,14 +26,20 @@ pub mod error;
 pub mod event;
 /// Named, reproducible application fixtures.
 pub mod fixture;
+/// Embedded independent applications.
+pub mod graft;
 /// Node data and arena structures.
 pub mod node;
+/// Observable values that refresh subscribed widgets.
+pub mod observe;
 /// Path and traversal helpers.
 pub mod path;
 /// Rendering interfaces.
 pub mod render;
 /// Scripting support.
 pub mod script;
+/// Keyboard scrolling shared across widgets.
+pub mod scroll;
 /// Shared node name types.
 pub mod state;
 /// Styling and color helpers.
@@ -41,36 +55,75 @@ pub mod view;
 pub mod canopy;
 /// Core context traits and implementations.
 pub mod context;
+/// Damage-region tracking.
+mod damage;
 /// Focus management.
 pub mod focus;
 /// Help snapshot API.
 pub mod help;
+/// Event and command dispatch hooks.
+pub mod hooks;
+/// Mouse hover tracking.
+mod hover;
 /// Node ID types.
 pub mod id;
 /// Input mapping.
 pub mod inputmap;
+/// Panic isolation for widget callbacks.
+mod isolate;
+/// In-memory log capture for `tracing`.
+pub mod logging;
+/// Macro recording and replay.
+pub mod macros;
+/// Messages sent between nodes.
+pub mod message;
+/// Widget state persistence.
+pub mod persist;
+/// Extension plugins.
+pub mod plugin;
 /// Polling utilities.
 pub mod poll;
+/// Frame profiling.
+pub mod profile;
 /// Terminal buffer types.
 pub mod termbuf;
 /// Text utilities.
 pub mod text;
+/// Widget timers.
+mod timer;
+/// Undo and redo history.
+pub mod undo;
 /// Widget slot borrowing and extraction guards.
 mod widget_access;
 /// World state and layout integration.
 pub mod world;
 
 // Public exports from internal modules
+pub use a11y::{Accessible, Announcer, LineAnnouncer, Role};
 pub use canopy::{
-    AutomationCallback, AutomationHandle, Canopy, Loader, RoutePhase, RouteTraceEntry,
+    AutomationCallback, AutomationHandle, Canopy, EventSender, Loader, MessageSender, RoutePhase,
+    RouteTraceEntry,
 };
-pub use children::{KeyedChildren, RemovePolicy};
+pub use children::{ChildSet, KeyedChildren, RemovePolicy};
 pub use context::{
     ChildKey, CommandContext, Context, FocusContext, LayoutContext, Preorder, ReadContext,
     ScrollContext, Slot, StyleContext, TreeContext,
 };
+pub use drag::{DragPayload, DropTarget};
 pub use fixture::{Fixture, FixtureInfo};
+pub use focus::FocusCommands;
+pub use graft::Graft;
+pub use hooks::{HookFlow, HookHandle};
 pub use id::{NodeId, TypedId};
 pub use inputmap::{BindingId, InputMap, InputSpec};
+pub use macros::MacroStep;
+pub use message::MessageTarget;
+pub use node::Node;
+pub use observe::{ChangeCounter, Observable};
 pub use path::{Path, PathFilter};
+pub use persist::PersistentState;
+pub use poll::PollWaker;
+pub use profile::{FrameProfile, NodeTiming, Profiler};
+pub use scroll::{ScrollCommands, Scrollable};
+pub use undo::{UndoEntry, UndoManager};
 pub use world::Core;
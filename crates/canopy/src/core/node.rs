@@ -34,31 +34,48 @@ pub struct Node {
     pub(crate) canvas: Size,
     /// Scroll offset in content coordinates.
     pub(crate) scroll: Point,
+    /// Scroll offset last reported with `Event::Scroll`.
+    pub(crate) reported_scroll: Point,
     /// View information in screen coordinates.
     pub(crate) view: View,
 
     /// Node visibility.
     pub(crate) hidden: bool,
-    /// Node name for commands and paths.
+    /// Explicit position in the tab order, if any.
+    pub(crate) tab_index: Option<u32>,
+    /// Widget name, used to match command owners and, unless overridden, in paths.
     pub(crate) name: NodeName,
+    /// Explicit name that replaces the widget name in paths. It survives widget
+    /// replacement.
+    pub(crate) path_name: Option<NodeName>,
     /// Whether polling has been initialized.
     pub(crate) initialized: bool,
     /// Whether the widget mount hook has run.
     pub(crate) mounted: bool,
+    /// Content size last reported to the widget's resize hook.
+    pub(crate) reported_size: Option<Size>,
     /// Whether layout configuration should be refreshed from the widget.
     pub(crate) layout_dirty: bool,
+    /// Region changed since the last render, in canvas coordinates.
+    pub(crate) damage: Option<Rect>,
 
     /// Effects to apply to this node and descendants during rendering.
     /// None for the common case of no effects (avoids per-node Vec allocation).
     pub(crate) effects: Option<Vec<Effect>>,
     /// If true, clear inherited effects before applying local effects.
     pub(crate) clear_inherited_effects: bool,
+    /// If true, the node may draw beyond its outer rect, down and to the right, anywhere
+    /// on screen.
+    pub(crate) unclipped: bool,
+    /// Message of a panic raised by one of this node's callbacks under panic isolation.
+    /// The subtree renders as an error placeholder until the failure is dismissed.
+    pub(crate) failed: Option<String>,
 }
 
 impl Node {
-    /// Return the node's widget name.
+    /// Return the node's name in paths: its explicit name if set, else its widget name.
     pub fn name(&self) -> &NodeName {
-        &self.name
+        self.path_name.as_ref().unwrap_or(&self.name)
     }
 
     /// Return the node's parent, if any.
@@ -115,4 +132,14 @@ impl Node {
     pub fn mounted(&self) -> bool {
         self.mounted
     }
+
+    /// Return true if layout configuration is waiting to be refreshed from the widget.
+    pub fn layout_dirty(&self) -> bool {
+        self.layout_dirty
+    }
+
+    /// Return the region changed since the last render, in canvas coordinates.
+    pub fn damage(&self) -> Option<Rect> {
+        self.damage
+    }
 }
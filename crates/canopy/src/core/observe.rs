@@ -0,0 +1,228 @@
+use std::{
+    mem,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use crate::{
+    core::{context::CoreContext, id::NodeId, world::Core},
+    error::Result,
+};
+
+/// A shared value that re-lays out and redraws the widgets observing it when it
+/// changes.
+///
+/// Clones share the same value. Widgets subscribe with `Context::observe`, usually in
+/// `on_mount`. Every `set` or `update` marks subscribed nodes for layout and requests a
+/// render on the next frame, so handlers that change app state do not need to find and
+/// refresh its dependents.
+pub struct Observable<T> {
+    /// Current value.
+    value: Arc<Mutex<T>>,
+    /// Counter bumped on every change.
+    version: Arc<AtomicU64>,
+}
+
+impl<T> Clone for Observable<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: Arc::clone(&self.value),
+            version: Arc::clone(&self.version),
+        }
+    }
+}
+
+impl<T> Observable<T> {
+    /// Wrap an initial value.
+    pub fn new(value: T) -> Self {
+        Self {
+            value: Arc::new(Mutex::new(value)),
+            version: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Run a closure against the current value.
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&self.value.lock().unwrap())
+    }
+
+    /// Return a copy of the current value.
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.with(T::clone)
+    }
+
+    /// Replace the value and notify subscribers.
+    pub fn set(&self, value: T) {
+        self.update(|v| *v = value);
+    }
+
+    /// Modify the value in place and notify subscribers.
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        f(&mut self.value.lock().unwrap());
+        self.version.fetch_add(1, Ordering::Release);
+    }
+
+    /// Number of changes made so far.
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+
+    /// Type-erased change counter used to subscribe nodes.
+    pub fn changes(&self) -> ChangeCounter {
+        ChangeCounter {
+            version: Arc::clone(&self.version),
+        }
+    }
+}
+
+/// Change counter of an [`Observable`], independent of its value type.
+#[derive(Clone)]
+pub struct ChangeCounter {
+    /// Counter shared with the observable.
+    version: Arc<AtomicU64>,
+}
+
+impl ChangeCounter {
+    /// Number of changes made so far.
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+
+    /// Whether both handles track the same observable.
+    fn same_source(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.version, &other.version)
+    }
+}
+
+/// A node subscribed to an observable.
+pub(crate) struct Observer {
+    /// Subscribed node.
+    node: NodeId,
+    /// Change counter of the observed value.
+    changes: ChangeCounter,
+    /// Version seen when the node was last refreshed.
+    seen: u64,
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Core {
+    /// Subscribe `node` to an observable. Subscribing twice to the same value is a
+    /// no-op.
+    pub(crate) fn observe(&mut self, node: NodeId, changes: ChangeCounter) {
+        if self
+            .observers
+            .iter()
+            .any(|o| o.node == node && o.changes.same_source(&changes))
+        {
+            return;
+        }
+        let seen = changes.version();
+        self.observers.push(Observer {
+            node,
+            changes,
+            seen,
+        });
+    }
+
+    /// Mark nodes whose observed values changed since the last check for layout, and
+    /// drop subscriptions of removed nodes. Returns the changed nodes.
+    fn sync_observers(&mut self) -> Vec<NodeId> {
+        let mut changed = Vec::new();
+        let mut observers = mem::take(&mut self.observers);
+        observers.retain_mut(|observer| {
+            let Some(node) = self.nodes.get_mut(observer.node) else {
+                return false;
+            };
+            let version = observer.changes.version();
+            if version != observer.seen {
+                observer.seen = version;
+                node.layout_dirty = true;
+                if !changed.contains(&observer.node) {
+                    changed.push(observer.node);
+                }
+            }
+            true
+        });
+        self.observers = observers;
+        changed
+    }
+
+    /// Refresh nodes whose observed values changed and run their `on_observed` hooks.
+    /// Returns `true` if any value changed.
+    pub(crate) fn notify_observers(&mut self) -> Result<bool> {
+        let changed = self.sync_observers();
+        for node in &changed {
+            let node = *node;
+            if !self.nodes.contains_key(node) {
+                continue;
+            }
+            self.with_widget_mut(node, |widget, core| {
+                let mut ctx = CoreContext::new(core, node);
+                widget.on_observed(&mut ctx)
+            })??;
+        }
+        Ok(!changed.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Canopy, Context, Loader, ReadContext, derive_commands,
+        geom::Line,
+        render::Render,
+        testing::{backend::TestRender, harness::Harness},
+        widget::Widget,
+    };
+
+    /// Label rendering a shared string.
+    struct Status {
+        text: Observable<String>,
+    }
+
+    #[derive_commands]
+    impl Status {}
+
+    impl Widget for Status {
+        fn on_mount(&mut self, c: &mut dyn Context) -> Result<()> {
+            c.observe(&self.text);
+            Ok(())
+        }
+
+        fn render(&mut self, r: &mut Render, _ctx: &dyn ReadContext) -> Result<()> {
+            let text = self.text.get();
+            r.text("default", Line::new(0, 0, 10), &text)
+        }
+    }
+
+    impl Loader for Status {
+        fn load(c: &mut Canopy) -> Result<()> {
+            c.add_commands::<Self>()
+        }
+    }
+
+    #[test]
+    fn changes_redraw_observers() -> Result<()> {
+        let text = Observable::new("idle".to_string());
+        let mut h = Harness::builder(Status { text: text.clone() })
+            .size(10, 1)
+            .build()?;
+        let (buf, mut render) = TestRender::create();
+        h.canopy.render(&mut render)?;
+        assert!(buf.lock().unwrap().contains("idle"));
+        assert!(!h.canopy.render_if_pending(&mut render)?);
+
+        text.set("busy".to_string());
+        assert_eq!(text.version(), 1);
+        assert!(h.canopy.render_if_pending(&mut render)?);
+        assert!(buf.lock().unwrap().contains("busy"));
+        assert!(!h.canopy.render_if_pending(&mut render)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,333 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    mem,
+};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+/// JSON value holding a widget's saved state.
+pub use serde_json::Value;
+
+use crate::{
+    Context, ReadContext,
+    core::{
+        context::{CoreContext, CoreViewContext},
+        id::NodeId,
+        world::Core,
+    },
+    error::{Error, Result},
+    geom::Point,
+    layout::Layout,
+};
+
+/// Current version of the state snapshot format.
+const STATE_VERSION: u32 = 1;
+
+/// Widgets whose state survives restarts.
+///
+/// Widgets opt in by implementing this trait and returning `Some(self)` from
+/// [`Widget::persistent`](crate::Widget::persistent). State is keyed by the node's path,
+/// so it is restored into whichever node occupies the same place in the rebuilt tree.
+pub trait PersistentState {
+    /// Capture this widget's state. `ctx` exposes the node's view, so widgets can
+    /// include their scroll offset.
+    fn save_state(&self, ctx: &dyn ReadContext) -> Result<Value>;
+
+    /// Restore state previously returned by `save_state`.
+    fn restore_state(&mut self, state: Value, ctx: &mut dyn Context) -> Result<()>;
+}
+
+/// Encode a serializable value as widget state.
+pub fn to_state<T: Serialize>(value: &T) -> Result<Value> {
+    serde_json::to_value(value).map_err(|err| Error::Invalid(format!("state encode failed: {err}")))
+}
+
+/// Decode widget state into a deserializable value.
+pub fn from_state<T: DeserializeOwned>(state: Value) -> Result<T> {
+    serde_json::from_value(state)
+        .map_err(|err| Error::Invalid(format!("state decode failed: {err}")))
+}
+
+/// Serialized state for a node tree.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct StateSnapshot {
+    /// Snapshot format version.
+    version: u32,
+    /// Widget state keyed by node path.
+    nodes: BTreeMap<String, Value>,
+}
+
+impl StateSnapshot {
+    /// Check that the snapshot uses a supported format.
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.version != STATE_VERSION {
+            return Err(Error::Invalid(format!(
+                "unsupported state version {}",
+                self.version
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Named snapshot of widget state, layout, scroll offsets, and focus.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct Session {
+    /// Persistent widget state.
+    state: StateSnapshot,
+    /// Key of the focused node, if any.
+    focus: Option<String>,
+    /// Non-zero scroll offsets keyed by node.
+    scroll: BTreeMap<String, [u32; 2]>,
+    /// Layout configuration and visibility keyed by node.
+    #[serde(default)]
+    layout: BTreeMap<String, NodeLayout>,
+}
+
+/// Layout configuration and visibility of one node in a session.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct NodeLayout {
+    /// Layout configuration.
+    layout: Layout,
+    /// Whether the node is hidden.
+    hidden: bool,
+}
+
+/// A session save or load requested by a widget, run after the current input event.
+pub(crate) enum SessionRequest {
+    /// Save the current session under a name.
+    Save(String),
+    /// Load a named session.
+    Load(String),
+}
+
+/// Return the file name a named session is stored under.
+pub(crate) fn session_file(name: &str) -> Result<String> {
+    let valid = !name.is_empty() && !name.starts_with('.') && !name.contains(['/', '\\']);
+    if !valid {
+        return Err(Error::Invalid(format!("invalid session name {name:?}")));
+    }
+    Ok(format!("{name}.json"))
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Core {
+    /// Return every node in the tree in pre-order, keyed by a stable path string.
+    ///
+    /// Siblings that share a path are disambiguated with a `#n` suffix in tree order.
+    fn state_keys(&self) -> Vec<(String, NodeId)> {
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        let mut keys = Vec::new();
+        let mut stack = vec![self.root];
+        while let Some(id) = stack.pop() {
+            let Some(node) = self.nodes.get(id) else {
+                continue;
+            };
+            stack.extend(node.children.iter().rev().copied());
+            let path = self.node_path(self.root, id).to_string();
+            let count = seen.entry(path.clone()).or_default();
+            let key = match *count {
+                0 => path,
+                n => format!("{path}#{n}"),
+            };
+            *count += 1;
+            keys.push((key, id));
+        }
+        keys
+    }
+
+    /// Capture the state of every persistent widget in the tree.
+    pub(crate) fn capture_state(&mut self) -> Result<StateSnapshot> {
+        let mut nodes = BTreeMap::new();
+        for (key, id) in self.state_keys() {
+            let state = self.with_widget_mut(id, |widget, core| {
+                let ctx = CoreViewContext::new(core, id);
+                widget.persistent().map(|p| p.save_state(&ctx)).transpose()
+            })??;
+            if let Some(state) = state {
+                nodes.insert(key, state);
+            }
+        }
+        Ok(StateSnapshot {
+            version: STATE_VERSION,
+            nodes,
+        })
+    }
+
+    /// Capture widget state, layout, scroll offsets, and focus as a session.
+    pub(crate) fn capture_session(&mut self) -> Result<Session> {
+        let state = self.capture_state()?;
+        let mut focus = None;
+        let mut scroll = BTreeMap::new();
+        let mut layout = BTreeMap::new();
+        for (key, id) in self.state_keys() {
+            if self.focus == Some(id) {
+                focus = Some(key.clone());
+            }
+            let Some(node) = self.nodes.get(id) else {
+                continue;
+            };
+            if node.scroll != Point::zero() {
+                scroll.insert(key.clone(), [node.scroll.x, node.scroll.y]);
+            }
+            layout.insert(
+                key,
+                NodeLayout {
+                    layout: node.layout,
+                    hidden: node.hidden,
+                },
+            );
+        }
+        Ok(Session {
+            state,
+            focus,
+            scroll,
+            layout,
+        })
+    }
+
+    /// Restore a session captured by `capture_session`.
+    ///
+    /// Layout and visibility are restored before widget state, so widgets that manage
+    /// their children's visibility have the final say. Scroll offsets are clamped by the
+    /// next layout pass. Focus is left alone when the saved node no longer exists.
+    pub(crate) fn restore_session(&mut self, session: Session) -> Result<()> {
+        for (key, id) in self.state_keys() {
+            let Some(saved) = session.layout.get(&key).copied() else {
+                continue;
+            };
+            self.set_layout_of(id, saved.layout)?;
+            self.set_hidden(id, saved.hidden);
+        }
+        self.restore_state(session.state)?;
+        for (key, id) in self.state_keys() {
+            if let Some([x, y]) = session.scroll.get(&key).copied() {
+                self.cancel_scroll(id);
+                if let Some(node) = self.nodes.get_mut(id) {
+                    node.scroll = Point { x, y };
+                }
+            }
+            if session.focus.as_ref() == Some(&key) {
+                self.set_focus(id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Queue a session request to run after the current input event.
+    pub(crate) fn queue_session(&mut self, request: SessionRequest) {
+        self.pending_sessions.push(request);
+    }
+
+    /// Take all queued session requests in submission order.
+    pub(crate) fn take_pending_sessions(&mut self) -> Vec<SessionRequest> {
+        mem::take(&mut self.pending_sessions)
+    }
+
+    /// Restore widget state from a snapshot. Entries with no matching node are ignored.
+    pub(crate) fn restore_state(&mut self, mut snapshot: StateSnapshot) -> Result<()> {
+        snapshot.validate()?;
+        for (key, id) in self.state_keys() {
+            let Some(state) = snapshot.nodes.remove(&key) else {
+                continue;
+            };
+            if !self.nodes.contains_key(id) {
+                continue;
+            }
+            self.with_widget_mut(id, |widget, core| {
+                let mut ctx = CoreContext::new(core, id);
+                match widget.persistent() {
+                    Some(persistent) => persistent.restore_state(state, &mut ctx),
+                    None => Ok(()),
+                }
+            })??;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{layout::Sizing, widget::Widget};
+
+    struct Counter(u32);
+
+    impl Widget for Counter {
+        fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+            true
+        }
+
+        fn persistent(&mut self) -> Option<&mut dyn PersistentState> {
+            Some(self)
+        }
+    }
+
+    impl PersistentState for Counter {
+        fn save_state(&self, _ctx: &dyn ReadContext) -> Result<Value> {
+            to_state(&self.0)
+        }
+
+        fn restore_state(&mut self, state: Value, _ctx: &mut dyn Context) -> Result<()> {
+            self.0 = from_state(state)?;
+            Ok(())
+        }
+    }
+
+    fn tree(counts: [u32; 2]) -> Result<Core> {
+        let mut core = Core::new();
+        let children = counts.map(|n| core.create_detached(Counter(n)));
+        core.set_children(core.root, children.to_vec())?;
+        Ok(core)
+    }
+
+    #[test]
+    fn state_round_trips_by_path() -> Result<()> {
+        let mut source = tree([3, 4])?;
+        let snapshot = source.capture_state()?;
+        let keys: Vec<_> = snapshot.nodes.keys().cloned().collect();
+        assert_eq!(keys.len(), 2);
+        assert!(keys[1].ends_with("#1"));
+
+        let mut restored = tree([0, 0])?;
+        restored.restore_state(snapshot)?;
+        let values: Vec<_> = restored.capture_state()?.nodes.into_values().collect();
+        assert_eq!(values, [json!(3), json!(4)]);
+
+        let bad = StateSnapshot {
+            version: STATE_VERSION + 1,
+            nodes: BTreeMap::new(),
+        };
+        assert!(matches!(
+            restored.restore_state(bad),
+            Err(Error::Invalid(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn session_restores_layout_focus_and_scroll() -> Result<()> {
+        let mut source = tree([1, 2])?;
+        let [first, focused] = [0, 1].map(|i| source.nodes[source.root].children[i]);
+        source.set_focus(focused);
+        source.nodes[focused].scroll = Point { x: 0, y: 5 };
+        source.with_layout_of(focused, |layout| layout.width = Sizing::Flex(3))?;
+        source.hide(first);
+        let session = source.capture_session()?;
+
+        let mut restored = tree([0, 0])?;
+        restored.restore_session(session)?;
+        let [first, target] = [0, 1].map(|i| restored.nodes[restored.root].children[i]);
+        assert_eq!(restored.focus, Some(target));
+        assert_eq!(restored.nodes[target].scroll, Point { x: 0, y: 5 });
+        assert_eq!(restored.nodes[target].layout.width, Sizing::Flex(3));
+        assert!(restored.nodes[first].hidden);
+
+        assert!(session_file("work").is_ok());
+        for name in ["", "../up", "a/b", ".hidden"] {
+            assert!(matches!(session_file(name), Err(Error::Invalid(_))));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use std::marker::PhantomData;
+
+use super::canopy::{Canopy, Loader};
+use crate::error::Result;
+
+/// An extension that registers commands, default bindings, fixtures, or nodes into an app.
+///
+/// Plugins are trait objects, so they can be chosen at runtime and, with the `dylib`
+/// feature, loaded from shared libraries with `Canopy::install_plugin_library`. Install
+/// them with [`Canopy::install_plugin`] before the script API is finalized.
+pub trait Plugin {
+    /// Unique plugin name.
+    fn name(&self) -> &str;
+
+    /// Register this plugin's contributions.
+    fn load(&self, canopy: &mut Canopy) -> Result<()>;
+}
+
+/// Plugin that runs a static [`Loader`].
+pub struct LoaderPlugin<L> {
+    /// Plugin name.
+    name: String,
+    /// Loader type marker.
+    loader: PhantomData<fn() -> L>,
+}
+
+impl<L: Loader> LoaderPlugin<L> {
+    /// Wrap loader `L` in a plugin called `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            loader: PhantomData,
+        }
+    }
+}
+
+impl<L: Loader> Plugin for LoaderPlugin<L> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn load(&self, canopy: &mut Canopy) -> Result<()> {
+        L::load(canopy)
+    }
+}
+
+/// Symbol a plugin shared library exports as its [`PluginEntry`].
+pub const PLUGIN_ENTRY_SYMBOL: &str = "canopy_plugin";
+
+/// Entry point a plugin shared library exports under [`PLUGIN_ENTRY_SYMBOL`].
+pub type PluginEntry = fn() -> Box<dyn Plugin>;
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "dylib")]
+    use std::{env, fs, process};
+
+    use super::*;
+    use crate::{Context, command, derive_commands, error::Error, widget::Widget};
+
+    struct Greeter;
+
+    #[derive_commands]
+    impl Greeter {
+        #[command]
+        /// Greet the user.
+        pub fn greet(&self, _ctx: &mut dyn Context) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Widget for Greeter {}
+
+    impl Loader for Greeter {
+        fn load(c: &mut Canopy) -> Result<()> {
+            c.add_commands::<Self>()?;
+            c.register_default_bindings("greeter", "canopy.bind(\"g\", \"greeter::greet()\")")
+        }
+    }
+
+    #[test]
+    fn install_plugin_registers_contributions_once() -> Result<()> {
+        let mut canopy = Canopy::new();
+        canopy.install_plugin(Box::new(LoaderPlugin::<Greeter>::new("greeter")))?;
+        assert_eq!(canopy.plugin_names(), ["greeter"]);
+        assert!(canopy.core.commands.get("greeter::greet").is_some());
+
+        let duplicate = canopy.install_plugin(Box::new(LoaderPlugin::<Greeter>::new("greeter")));
+        assert!(matches!(duplicate, Err(Error::Invalid(_))));
+
+        canopy.finalize_api()?;
+        let sealed = canopy.install_plugin(Box::new(LoaderPlugin::<Greeter>::new("late")));
+        assert!(matches!(sealed, Err(Error::InvalidOperation(_))));
+        assert_eq!(canopy.plugin_names(), ["greeter"]);
+        Ok(())
+    }
+
+    #[cfg(feature = "dylib")]
+    #[test]
+    fn install_plugin_library_rejects_files_that_are_not_plugins() -> Result<()> {
+        let mut canopy = Canopy::new();
+        let dir = env::temp_dir();
+        let missing = dir.join(format!("canopy-missing-plugin-{}.so", process::id()));
+        // SAFETY: the library does not exist, so no code is loaded.
+        let result = unsafe { canopy.install_plugin_library(&missing) };
+        assert!(matches!(result, Err(Error::Invalid(_))));
+
+        let garbage = dir.join(format!("canopy-garbage-plugin-{}.so", process::id()));
+        fs::write(&garbage, b"not a shared library")?;
+        // SAFETY: the file is not a shared library, so loading it fails before any code
+        // runs.
+        let result = unsafe { canopy.install_plugin_library(&garbage) };
+        fs::remove_file(&garbage)?;
+        assert!(matches!(result, Err(Error::Invalid(_))));
+        assert!(canopy.plugin_names().is_empty());
+        Ok(())
+    }
+}
@@ -6,7 +6,37 @@ use std::{
     time::{Duration, Instant},
 };
 
-use crate::{NodeId, event::Event};
+use super::clock::Clock;
+use crate::{
+    NodeId,
+    event::{Event, TimerId},
+};
+
+/// Handle for requesting a poll of one node from any thread.
+///
+/// Widgets fed by a background thread, such as a filesystem watcher, hand a waker to that
+/// thread and are polled when it has news, instead of polling on an interval.
+#[derive(Clone, Debug)]
+pub struct PollWaker {
+    /// Node to poll.
+    node_id: NodeId,
+    /// Runloop event channel, if the node belongs to a running app.
+    tx: Option<mpsc::Sender<Event>>,
+}
+
+impl PollWaker {
+    /// Construct a waker for `node_id` that sends through `tx`.
+    pub(crate) fn new(node_id: NodeId, tx: Option<mpsc::Sender<Event>>) -> Self {
+        Self { node_id, tx }
+    }
+
+    /// Ask the runloop to poll the node. Returns `false` if no runloop is listening.
+    pub fn wake(&self) -> bool {
+        self.tx
+            .as_ref()
+            .is_some_and(|tx| tx.send(Event::Poll(vec![self.node_id])).is_ok())
+    }
+}
 
 /// A node that has a pending callback.
 #[derive(Debug)]
@@ -15,6 +45,8 @@ struct PendingNode {
     time: Instant,
     /// Node identifier to poll.
     node_id: NodeId,
+    /// Timer to fire, or `None` for a poll.
+    timer: Option<TimerId>,
 }
 
 impl PartialEq for PendingNode {
@@ -48,16 +80,17 @@ struct PendingHeap {
 
 impl PendingHeap {
     /// Add a node with an explicit time base.
-    fn _add(&mut self, now: Instant, node_id: NodeId, duration: Duration) {
+    fn _add(&mut self, now: Instant, node_id: NodeId, timer: Option<TimerId>, duration: Duration) {
         self.nodes.push(PendingNode {
             time: now + duration,
             node_id,
+            timer,
         });
     }
 
     /// Add a node with a callback duration to the heap.
-    fn add(&mut self, node_id: NodeId, duration: Duration) {
-        self._add(Instant::now(), node_id, duration);
+    fn add(&mut self, node_id: NodeId, timer: Option<TimerId>, duration: Duration) {
+        self._add(Instant::now(), node_id, timer, duration);
     }
 
     /// Calculate the wait time relative to a given timestamp.
@@ -76,12 +109,12 @@ impl PendingHeap {
         self._current_wait(Instant::now())
     }
 
-    /// Collect due node IDs relative to a given timestamp.
-    fn _collect(&mut self, now: Instant) -> Vec<NodeId> {
+    /// Collect due node IDs, with the timer each entry fires, relative to a given timestamp.
+    fn _collect(&mut self, now: Instant) -> Vec<(NodeId, Option<TimerId>)> {
         let mut v = vec![];
         while let Some(n) = self.nodes.pop() {
             if n.time <= now {
-                v.push(n.node_id);
+                v.push((n.node_id, n.timer));
             } else {
                 // Put it back on the heap.
                 self.nodes.push(n);
@@ -92,7 +125,7 @@ impl PendingHeap {
     }
 
     /// Remove and return all the pending operations .
-    pub fn collect(&mut self) -> Vec<NodeId> {
+    pub fn collect(&mut self) -> Vec<(NodeId, Option<TimerId>)> {
         self._collect(Instant::now())
     }
 }
@@ -118,12 +151,47 @@ impl Poller {
         }
     }
 
-    /// Schedule a node to be polled. This function requires us to pass in the
-    /// tx channel, which means that a lock over the global state must already
-    /// be in place.
-    pub fn schedule(&mut self, node_id: impl Into<NodeId>, duration: Duration) {
+    /// Schedule a node to be polled after `duration` on `clock`.
+    pub(crate) fn schedule(&mut self, node_id: NodeId, duration: Duration, clock: Clock) {
+        self.schedule_entry(node_id, None, duration, clock);
+    }
+
+    /// Schedule a timer to fire on a node after `duration` on `clock`.
+    pub(crate) fn schedule_timer(
+        &mut self,
+        node_id: NodeId,
+        timer: TimerId,
+        duration: Duration,
+        clock: Clock,
+    ) {
+        self.schedule_entry(node_id, Some(timer), duration, clock);
+    }
+
+    /// Return the time of the earliest pending entry.
+    pub(crate) fn next_due(&self) -> Option<Instant> {
+        self.pending.lock().unwrap().nodes.peek().map(|n| n.time)
+    }
+
+    /// Remove and return entries due at `now`. Used to drive a virtual clock.
+    pub(crate) fn collect_due(&mut self, now: Instant) -> Vec<(NodeId, Option<TimerId>)> {
+        self.pending.lock().unwrap()._collect(now)
+    }
+
+    /// Add a poll or timer entry. On the real clock, also make sure the scheduler thread
+    /// is awake. Entries on a virtual clock wait for the clock to be advanced.
+    fn schedule_entry(
+        &mut self,
+        node_id: NodeId,
+        timer: Option<TimerId>,
+        duration: Duration,
+        clock: Clock,
+    ) {
         let mut l = self.pending.lock().unwrap();
-        l.add(node_id.into(), duration);
+        if let Clock::Virtual(now) = clock {
+            l._add(now, node_id, timer, duration);
+            return;
+        }
+        l.add(node_id, timer, duration);
         if let Some(h) = self.handle.as_mut() {
             // The thread is running, let's wake it up.
             h.thread().unpark();
@@ -141,10 +209,20 @@ impl Poller {
                         // We have no current wait time, so we just park the thread.
                         thread::park();
                     };
-                    let ids = pending.lock().unwrap().collect();
+                    let due = pending.lock().unwrap().collect();
+                    let (polls, timers): (Vec<_>, Vec<_>) =
+                        due.into_iter().partition(|(_, timer)| timer.is_none());
+                    let ids: Vec<NodeId> = polls.into_iter().map(|(id, _)| id).collect();
+                    let timers: Vec<(NodeId, TimerId)> = timers
+                        .into_iter()
+                        .filter_map(|(id, timer)| timer.map(|timer| (id, timer)))
+                        .collect();
                     if !ids.is_empty() && tx.send(Event::Poll(ids)).is_err() {
                         break;
                     }
+                    if !timers.is_empty() && tx.send(Event::Timers(timers)).is_err() {
+                        break;
+                    }
                 }
             }));
         }
@@ -167,11 +245,15 @@ mod tests {
         let n2 = map.insert(());
 
         assert_eq!(ph._current_wait(now), None);
-        ph._add(now, n1, Duration::from_secs(10));
+        ph._add(now, n1, None, Duration::from_secs(10));
         assert_eq!(ph._current_wait(now).unwrap(), Duration::from_secs(10));
-        ph._add(now, n2, Duration::from_secs(100));
+        ph._add(now, n2, Some(7), Duration::from_secs(100));
         assert!(ph._current_wait(now).unwrap() <= Duration::from_secs(10));
-        assert_eq!(ph._collect(now + Duration::from_secs(11)), vec![n1]);
+        assert_eq!(ph._collect(now + Duration::from_secs(11)), vec![(n1, None)]);
+        assert_eq!(
+            ph._collect(now + Duration::from_secs(101)),
+            vec![(n2, Some(7))]
+        );
         assert!(ph._current_wait(now).unwrap() <= Duration::from_secs(100));
 
         Ok(())
@@ -0,0 +1,136 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    mem,
+    time::Duration,
+};
+
+use crate::core::{id::NodeId, world::Core};
+
+/// Number of frame durations retained by a profiler.
+const HISTORY: usize = 120;
+
+/// Time spent on one node during a frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NodeTiming {
+    /// Layout time, including measurement, excluding descendants.
+    pub layout: Duration,
+    /// Render time for the node itself.
+    pub render: Duration,
+}
+
+impl NodeTiming {
+    /// Layout and render time combined.
+    pub fn total(&self) -> Duration {
+        self.layout + self.render
+    }
+}
+
+/// Measurements for one rendered frame.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FrameProfile {
+    /// Wall time from the start of layout to the end of terminal output.
+    pub duration: Duration,
+    /// Nodes waiting for a layout refresh when the frame started.
+    pub layout_dirty: usize,
+    /// Nodes carrying damage when the frame started.
+    pub damaged: usize,
+    /// Per-node timings for nodes laid out or rendered during the frame.
+    pub nodes: HashMap<NodeId, NodeTiming>,
+}
+
+/// Per-frame layout and render timings, recorded while profiling is enabled.
+#[derive(Debug, Default)]
+pub struct Profiler {
+    /// Frame being recorded.
+    current: FrameProfile,
+    /// Most recently completed frame.
+    last: FrameProfile,
+    /// Recent frame durations, oldest first.
+    durations: VecDeque<Duration>,
+}
+
+impl Profiler {
+    /// Return the most recently completed frame.
+    pub fn last(&self) -> &FrameProfile {
+        &self.last
+    }
+
+    /// Return recent frame durations, oldest first.
+    pub fn durations(&self) -> &VecDeque<Duration> {
+        &self.durations
+    }
+
+    /// Add layout time for a node, including its descendants.
+    pub(crate) fn record_layout(&mut self, node: NodeId, elapsed: Duration) {
+        self.current.nodes.entry(node).or_default().layout += elapsed;
+    }
+
+    /// Add render time for a node.
+    pub(crate) fn record_render(&mut self, node: NodeId, elapsed: Duration) {
+        self.current.nodes.entry(node).or_default().render += elapsed;
+    }
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Core {
+    /// Enable or disable frame profiling. Enabling an active profiler keeps its history.
+    pub fn set_profiling(&mut self, enabled: bool) {
+        if !enabled {
+            self.profiler = None;
+        } else if self.profiler.is_none() {
+            self.profiler = Some(Profiler::default());
+        }
+    }
+
+    /// Start recording a frame, counting nodes that are waiting for layout or redraw.
+    pub(crate) fn begin_profile_frame(&mut self) {
+        if self.profiler.is_none() {
+            return;
+        }
+        let layout_dirty = self.nodes.values().filter(|n| n.layout_dirty).count();
+        let damaged = self.nodes.values().filter(|n| n.damage.is_some()).count();
+        if let Some(profiler) = self.profiler.as_mut() {
+            profiler.current = FrameProfile {
+                layout_dirty,
+                damaged,
+                ..FrameProfile::default()
+            };
+        }
+    }
+
+    /// Finish the frame being recorded.
+    ///
+    /// Layout is recorded per subtree, so each node's descendants are subtracted here to
+    /// leave the time spent on the node itself.
+    pub(crate) fn finish_profile_frame(&mut self, duration: Duration) {
+        let Some(profiler) = self.profiler.as_mut() else {
+            return;
+        };
+        let mut frame = mem::take(&mut profiler.current);
+        let inclusive: HashMap<NodeId, Duration> = frame
+            .nodes
+            .iter()
+            .map(|(id, timing)| (*id, timing.layout))
+            .collect();
+        for (id, timing) in &mut frame.nodes {
+            let nested: Duration = self
+                .nodes
+                .get(*id)
+                .map(|node| {
+                    node.children
+                        .iter()
+                        .filter_map(|child| inclusive.get(child))
+                        .sum()
+                })
+                .unwrap_or_default();
+            timing.layout = timing.layout.saturating_sub(nested);
+        }
+        frame.duration = duration;
+
+        profiler.durations.push_back(duration);
+        if profiler.durations.len() > HISTORY {
+            profiler.durations.pop_front();
+        }
+        profiler.last = frame;
+    }
+}
@@ -0,0 +1,227 @@
+//! Standalone HTML and SVG exports of a terminal buffer.
+//!
+//! Both formats carry each cell's resolved colors and attributes, with named and ANSI
+//! colors converted to RGB. Wide glyphs keep their column alignment.
+
+use crate::{
+    core::termbuf::TermBuf,
+    geom::Point,
+    style::{AttrSet, ResolvedStyle},
+};
+
+/// Width of one cell in SVG output, in pixels.
+const CELL_WIDTH: u32 = 9;
+/// Height of one cell in SVG output, in pixels.
+const CELL_HEIGHT: u32 = 18;
+/// Font size used in both outputs, in pixels.
+const FONT_SIZE: u32 = 15;
+/// Font stack used in both outputs.
+const FONT_FAMILY: &str = "ui-monospace, Menlo, Consolas, monospace";
+
+/// A run of adjacent cells in one row sharing a style.
+struct Run {
+    /// First column of the run.
+    x: u32,
+    /// Width of the run in columns.
+    width: u32,
+    /// Shared style.
+    style: ResolvedStyle,
+    /// Rendered text of the run.
+    text: String,
+}
+
+/// Split a row of the buffer into runs of identical style.
+fn runs(buf: &TermBuf, y: u32) -> Vec<Run> {
+    let mut out: Vec<Run> = Vec::new();
+    for x in 0..buf.size().w {
+        let Some(cell) = buf.get(Point { x, y }) else {
+            continue;
+        };
+        match out.last_mut() {
+            Some(run) if run.style == cell.style => {
+                run.width += 1;
+                run.text.push_str(&cell.rendered_text());
+            }
+            _ => out.push(Run {
+                x,
+                width: 1,
+                style: cell.style,
+                text: cell.rendered_text(),
+            }),
+        }
+    }
+    out
+}
+
+/// Escape text for use in HTML or SVG content and attribute values.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Return the CSS text decorations for a set of attributes, if any.
+fn decorations(attrs: &AttrSet) -> Option<String> {
+    let lines: Vec<&str> = [
+        (attrs.underline, "underline"),
+        (attrs.overline, "overline"),
+        (attrs.crossedout, "line-through"),
+    ]
+    .into_iter()
+    .filter_map(|(set, name)| set.then_some(name))
+    .collect();
+    (!lines.is_empty()).then(|| lines.join(" "))
+}
+
+/// Render a buffer as a standalone HTML document holding a single `pre` block.
+pub fn to_html(buf: &TermBuf) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!(
+        "<style>pre.canopy {{ font-family: {FONT_FAMILY}; font-size: {FONT_SIZE}px; \
+         line-height: {CELL_HEIGHT}px; margin: 0; }}</style>\n"
+    ));
+    out.push_str("</head>\n<body>\n<pre class=\"canopy\">");
+    for y in 0..buf.size().h {
+        if y > 0 {
+            out.push('\n');
+        }
+        for run in runs(buf, y) {
+            let style = &run.style;
+            let mut css = format!(
+                "color:{};background-color:{}",
+                style.fg.to_hex(),
+                style.bg.to_hex()
+            );
+            if style.attrs.bold {
+                css.push_str(";font-weight:bold");
+            }
+            if style.attrs.italic {
+                css.push_str(";font-style:italic");
+            }
+            if style.attrs.dim {
+                css.push_str(";opacity:0.6");
+            }
+            if let Some(lines) = decorations(&style.attrs) {
+                css.push_str(&format!(";text-decoration:{lines}"));
+            }
+            out.push_str(&format!(
+                "<span style=\"{css}\">{}</span>",
+                escape(&run.text)
+            ));
+        }
+    }
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+/// Render a buffer as a standalone SVG image with one cell per character.
+pub fn to_svg(buf: &TermBuf) -> String {
+    let size = buf.size();
+    let (width, height) = (size.w * CELL_WIDTH, size.h * CELL_HEIGHT);
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"{FONT_FAMILY}\" \
+         font-size=\"{FONT_SIZE}\">\n"
+    );
+    for y in 0..size.h {
+        let top = y * CELL_HEIGHT;
+        let rows = runs(buf, y);
+        for run in &rows {
+            out.push_str(&format!(
+                "<rect x=\"{}\" y=\"{top}\" width=\"{}\" height=\"{CELL_HEIGHT}\" fill=\"{}\"/>\n",
+                run.x * CELL_WIDTH,
+                run.width * CELL_WIDTH,
+                run.style.bg.to_hex()
+            ));
+        }
+        for run in &rows {
+            if run.text.trim().is_empty() {
+                continue;
+            }
+            let style = &run.style;
+            let mut attrs = format!("fill=\"{}\"", style.fg.to_hex());
+            if style.attrs.bold {
+                attrs.push_str(" font-weight=\"bold\"");
+            }
+            if style.attrs.italic {
+                attrs.push_str(" font-style=\"italic\"");
+            }
+            if style.attrs.dim {
+                attrs.push_str(" fill-opacity=\"0.6\"");
+            }
+            if let Some(lines) = decorations(&style.attrs) {
+                attrs.push_str(&format!(" text-decoration=\"{lines}\""));
+            }
+            // Baseline sits about four fifths of the way down the cell.
+            out.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" textLength=\"{}\" lengthAdjust=\"spacingAndGlyphs\" \
+                 xml:space=\"preserve\" {attrs}>{}</text>\n",
+                run.x * CELL_WIDTH,
+                top + CELL_HEIGHT * 4 / 5,
+                run.width * CELL_WIDTH,
+                escape(&run.text)
+            ));
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        geom::{Line, Size},
+        style::{Attr, Color},
+    };
+
+    fn sample() -> TermBuf {
+        let plain = ResolvedStyle::new(Color::White, Color::Black, AttrSet::default());
+        let loud = ResolvedStyle::new(
+            Color::Red,
+            Color::Rgb {
+                r: 0,
+                g: 0,
+                b: 0x80,
+            },
+            AttrSet::new(Attr::Bold).with(Attr::Underline),
+        );
+        let mut buf = TermBuf::new(Size::new(6, 2), ' ', plain);
+        buf.text(&plain, Line::new(0, 0, 3), "a<b");
+        buf.text(&loud, Line::new(3, 0, 3), "hot");
+        buf
+    }
+
+    #[test]
+    fn html_groups_runs_and_escapes_text() {
+        let html = to_html(&sample());
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains(
+            "<span style=\"color:#ffffff;background-color:#000000\">a&lt;b</span>\
+             <span style=\"color:#ff0000;background-color:#000080;font-weight:bold;\
+             text-decoration:underline\">hot</span>\n\
+             <span style=\"color:#ffffff;background-color:#000000\">      </span></pre>"
+        ));
+    }
+
+    #[test]
+    fn svg_draws_backgrounds_and_skips_blank_text() {
+        let svg = to_svg(&sample());
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"54\""));
+        assert!(
+            svg.contains("<rect x=\"27\" y=\"0\" width=\"27\" height=\"18\" fill=\"#000080\"/>")
+        );
+        assert!(svg.contains("font-weight=\"bold\" text-decoration=\"underline\">hot</text>"));
+        assert!(svg.contains(">a&lt;b</text>"));
+        assert_eq!(svg.matches("<text").count(), 2);
+    }
+}
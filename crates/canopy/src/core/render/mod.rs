@@ -1,13 +1,25 @@
+use std::mem;
+
 use unicode_segmentation::UnicodeSegmentation;
 
-use super::termbuf::TermBuf;
+use super::{
+    damage::union_rect,
+    termbuf::{Cell, TermBuf},
+};
 use crate::{
     core::text,
+    cursor,
     error::Result,
     geom,
-    style::{AttrSet, Color, Effect, ResolvedStyle, Style, StyleManager, StyleMap},
+    style::{
+        AttrSet, Color, Effect, ResolvedStyle, Style, StyleEffect, StyleManager, StyleMap,
+        effects::HighContrast,
+    },
 };
 
+/// HTML and SVG exports of terminal buffers.
+pub mod export;
+
 /// The trait implemented by renderers.
 pub trait RenderBackend {
     /// Apply a style to the following text output
@@ -28,6 +40,19 @@ pub trait RenderBackend {
     fn shift_lines(&mut self, _top: u32, _bottom: u32, _count: i32) -> Result<()> {
         Ok(())
     }
+    /// Set the terminal cursor color, or restore the terminal default with `None`.
+    fn cursor_color(&mut self, _color: Option<Color>) -> Result<()> {
+        Ok(())
+    }
+    /// Show the terminal cursor with the position, shape, and blink of `cursor`, in screen
+    /// coordinates, or hide it with `None`. Called after every frame; the cursor is only
+    /// shown in `CursorMode::Hardware`.
+    fn show_cursor(&mut self, _cursor: Option<cursor::Cursor>) -> Result<()> {
+        Ok(())
+    }
+    /// Enable or disable synchronized output, which has the terminal show each frame at
+    /// once instead of as it is written. Backends without the feature ignore this.
+    fn set_synchronized_output(&mut self, _enabled: bool) {}
     /// Flush output to the terminal.
     fn flush(&mut self) -> Result<()>;
     /// Reset the backend to a clean state.
@@ -71,10 +96,16 @@ pub struct Render<'a> {
     stylemap: &'a StyleMap,
     /// The rectangle in canvas coordinates that is visible for rendering.
     clip: geom::Rect,
+    /// Clip rects saved by `push_clip`, restored by `pop_clip`.
+    clip_stack: Vec<geom::Rect>,
     /// Translation offset from canvas coordinates to buffer coordinates.
     origin: Offset,
     /// Current effect stack, applied in order to resolved styles.
     effects: &'a [Effect],
+    /// Area the widget owns. Writes reaching outside it are recorded as overflow.
+    bounds: Option<geom::Rect>,
+    /// Union of writes that reached outside `bounds`.
+    overflow: Option<geom::Rect>,
 }
 
 impl<'a> Render<'a> {
@@ -90,8 +121,11 @@ impl<'a> Render<'a> {
             style,
             stylemap,
             clip: rect,
+            clip_stack: Vec::new(),
             origin: Offset::between(geom::Point::zero(), rect.tl),
             effects: &[],
+            bounds: None,
+            overflow: None,
         }
     }
 
@@ -108,8 +142,11 @@ impl<'a> Render<'a> {
             style,
             stylemap,
             clip,
+            clip_stack: Vec::new(),
             origin: Offset::between(screen_origin, clip.tl),
             effects: &[],
+            bounds: None,
+            overflow: None,
         }
     }
 
@@ -119,6 +156,27 @@ impl<'a> Render<'a> {
         self
     }
 
+    /// Record writes that reach outside `bounds`. Such writes are still clipped as usual.
+    pub(crate) fn with_bounds(mut self, bounds: geom::Rect) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Return the union of writes that reached outside the bounds, if any.
+    pub(crate) fn overflow(&self) -> Option<geom::Rect> {
+        self.overflow
+    }
+
+    /// Note a write to `r` for overflow tracking.
+    fn note_write(&mut self, r: geom::Rect) {
+        if let Some(bounds) = self.bounds
+            && !r.is_zero()
+            && !bounds.contains_rect(&r)
+        {
+            self.overflow = union_rect(self.overflow, r);
+        }
+    }
+
     /// Apply the current effect stack to a style.
     /// Use this when you have a Style from a source other than the style manager.
     pub fn apply_effects(&self, style: Style) -> Style {
@@ -126,6 +184,9 @@ impl<'a> Render<'a> {
         for effect in self.effects {
             result = effect.apply(result);
         }
+        if self.style.high_contrast() {
+            result = HighContrast.apply(result);
+        }
         result
     }
 
@@ -145,6 +206,11 @@ impl<'a> Render<'a> {
         self.resolve_style(name)
     }
 
+    /// Return the style map used to resolve style names.
+    pub fn stylemap(&self) -> &StyleMap {
+        self.stylemap
+    }
+
     /// Resolve a custom style at a point, applying the current effect stack.
     pub fn resolve_style_at(
         &self,
@@ -165,6 +231,28 @@ impl<'a> Render<'a> {
         self.resolve_style(name).resolve_at(bounds, point)
     }
 
+    /// Return the current clip rect in canvas coordinates.
+    pub fn clip(&self) -> geom::Rect {
+        self.clip
+    }
+
+    /// Restrict drawing to the intersection of the current clip and `rect`, in canvas
+    /// coordinates, until the matching `pop_clip`.
+    pub fn push_clip(&mut self, rect: geom::Rect) {
+        let next = self
+            .clip
+            .intersect(&rect)
+            .unwrap_or_else(|| geom::Rect::new(self.clip.tl.x, self.clip.tl.y, 0, 0));
+        self.clip_stack.push(mem::replace(&mut self.clip, next));
+    }
+
+    /// Restore the clip rect saved by the most recent `push_clip`.
+    pub fn pop_clip(&mut self) {
+        if let Some(clip) = self.clip_stack.pop() {
+            self.clip = clip;
+        }
+    }
+
     /// Push a style layer.
     pub fn push_layer(&mut self, name: &str) {
         self.style.push_layer(name);
@@ -172,6 +260,7 @@ impl<'a> Render<'a> {
 
     /// Fill a rectangle with a specified character. Writes out of bounds will be clipped.
     pub fn fill(&mut self, style: &str, r: geom::Rect, c: char) -> Result<()> {
+        self.note_write(r);
         if let Some(intersection) = r.intersect(&self.clip) {
             let style = self.resolve_style(style);
             if let Some(resolved) = style.resolve_solid() {
@@ -210,6 +299,7 @@ impl<'a> Render<'a> {
     /// rectangle, it will be truncated; if it is shorter, it will be padded.
     pub fn text(&mut self, style: &str, l: geom::Line, txt: &str) -> Result<()> {
         let line_rect = geom::Rect::new(l.tl.x, l.tl.y, l.w, 1);
+        self.note_write(line_rect);
         if let Some(intersection) = line_rect.intersect(&self.clip) {
             let style = self.resolve_style(style);
 
@@ -272,6 +362,7 @@ impl<'a> Render<'a> {
 
     /// Write a single cell with a resolved style.
     pub fn put_cell(&mut self, style: ResolvedStyle, p: geom::Point, ch: char) -> Result<()> {
+        self.note_write(geom::Rect::new(p.x, p.y, 1, 1));
         if self.clip.contains_point(p) {
             let adjusted = self.translate_point(p);
             self.buffer_mut().put(adjusted, ch, style);
@@ -279,6 +370,17 @@ impl<'a> Render<'a> {
         Ok(())
     }
 
+    /// Fill a rectangle with copies of a cell, without resolving a style name per write.
+    /// Wide glyphs repeat at their own width.
+    pub fn fill_region(&mut self, r: geom::Rect, cell: &Cell) -> Result<()> {
+        self.note_write(r);
+        if let Some(intersection) = r.intersect(&self.clip) {
+            let adjusted = self.translate_rect(intersection);
+            self.buffer_mut().fill_cell(adjusted, cell);
+        }
+        Ok(())
+    }
+
     /// Write a grapheme with a resolved style, including continuation cells.
     pub fn put_grapheme(
         &mut self,
@@ -291,6 +393,7 @@ impl<'a> Render<'a> {
             return Ok(());
         }
         let glyph_rect = geom::Rect::new(p.x, p.y, width as u32, 1);
+        self.note_write(glyph_rect);
         if self.clip.contains_rect(&glyph_rect) {
             let adjusted = self.translate_point(p);
             self.buffer_mut().put_grapheme(adjusted, grapheme, style);
@@ -503,6 +606,25 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn fill_region_repeats_cells_within_the_clip() {
+        let stylemap = StyleMap::new();
+        let mut style_manager = StyleManager::new();
+        let style = style_manager
+            .get(&stylemap, "")
+            .resolve_solid()
+            .expect("default style resolves to solid colors");
+        let mut render = Render::new(&stylemap, &mut style_manager, geom::Rect::new(0, 0, 6, 2));
+
+        render
+            .fill_region(geom::Rect::new(1, 0, 8, 2), &Cell::new('界', style))
+            .unwrap();
+        render
+            .fill_region(geom::Rect::new(0, 1, 3, 1), &Cell::new('.', style))
+            .unwrap();
+        assert_buffer_matches(&render, buf!("X界界 " "...界 "));
+    }
+
     #[test]
     fn put_grapheme_clips_wide_glyphs_atomically() {
         let stylemap = StyleMap::new();
@@ -524,6 +646,38 @@ mod tests {
         assert_buffer_matches(&render, buf!("界X"));
     }
 
+    #[test]
+    fn clip_stack_restricts_and_restores_drawing() {
+        let stylemap = StyleMap::new();
+        let mut style_manager = StyleManager::new();
+        let mut render = Render::new(&stylemap, &mut style_manager, geom::Rect::new(0, 0, 4, 2));
+
+        render.push_clip(geom::Rect::new(1, 0, 2, 1));
+        render.push_clip(geom::Rect::new(2, 0, 4, 4));
+        assert_eq!(render.clip(), geom::Rect::new(2, 0, 1, 1));
+        render
+            .fill("default", geom::Rect::new(0, 0, 4, 2), '#')
+            .unwrap();
+
+        render.pop_clip();
+        render
+            .text("default", geom::Line::new(0, 1, 4), "ab")
+            .unwrap();
+
+        render.pop_clip();
+        render
+            .fill("default", geom::Rect::new(0, 1, 1, 1), '*')
+            .unwrap();
+
+        assert_buffer_matches(
+            &render,
+            buf!(
+                "XX#X"
+                "*XXX"
+            ),
+        );
+    }
+
     #[test]
     fn test_part_render_fill_outside_canvas() {
         let stylemap = StyleMap::new();
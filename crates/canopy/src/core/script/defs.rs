@@ -17,15 +17,20 @@ pub(crate) fn preamble() -> &'static str {
     PREAMBLE
 }
 
-/// Group node-dispatched command specs by owner, including default-binding owners.
+/// Group command specs by owner, including default-binding owners. Free commands are
+/// grouped by the namespace prefix of their id, if any.
 pub(crate) fn owner_command_specs(
     commands: &CommandSet,
     default_binding_owners: &BTreeSet<String>,
 ) -> BTreeMap<String, Vec<&'static CommandSpec>> {
     let mut owners: BTreeMap<String, Vec<&'static CommandSpec>> = BTreeMap::new();
     for (_, spec) in commands.iter() {
-        let CommandDispatchKind::Node { owner } = spec.dispatch else {
-            continue;
+        let owner = match spec.dispatch {
+            CommandDispatchKind::Node { owner } => owner,
+            CommandDispatchKind::Free => match spec.id.0.split_once("::") {
+                Some((owner, _)) => owner,
+                None => continue,
+            },
         };
         owners.entry(owner.to_string()).or_default().push(spec);
     }
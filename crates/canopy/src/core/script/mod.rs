@@ -1366,6 +1366,29 @@ fn host_focused<'s>(
     ret_arg(scope, &focused)
 }
 
+/// `canopy.focus_path`: return the path from the root to the focused node.
+fn host_focus_path<'s>(
+    scope: &Scope<'s>,
+    _args: MultiValue<'s>,
+) -> StdResult<MultiValue<'s>, RuntimeError> {
+    let path = with_current_canopy(|canopy, _| {
+        Ok(canopy.core.focus_path(canopy.core.root_id()).to_string())
+    })
+    .map_err(|err| canopy_to_host(&err))?;
+    Ok(ret_one(ScopedValue::String(scope.create_string(&path)?)))
+}
+
+/// `canopy.root_size`: return the size of the root viewport.
+fn host_root_size<'s>(
+    scope: &Scope<'s>,
+    _args: MultiValue<'s>,
+) -> StdResult<MultiValue<'s>, RuntimeError> {
+    let size =
+        with_current_canopy(|canopy, _| Ok(size_to_arg(canopy.root_size.unwrap_or_default())))
+            .map_err(|err| canopy_to_host(&err))?;
+    ret_arg(scope, &size)
+}
+
 /// `canopy.node_info`: return the `NodeInfo` record for a node.
 fn host_node_info<'s>(
     scope: &Scope<'s>,
@@ -1874,6 +1897,8 @@ impl NativeModule for CanopyBaseModule {
         let entries: &[(&str, HostHandler)] = &[
             ("root", host_root),
             ("focused", host_focused),
+            ("focus_path", host_focus_path),
+            ("root_size", host_root_size),
             ("node_info", host_node_info),
             ("find_node", host_find_node),
             ("find_nodes", host_find_nodes),
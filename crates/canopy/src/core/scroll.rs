@@ -0,0 +1,183 @@
+use crate::{
+    Context, ReadContext,
+    commands::{
+        ArgValue, CommandArgs, CommandDispatchKind, CommandDocSpec, CommandError, CommandId,
+        CommandInvocation, CommandNode, CommandReturnSpec, CommandSpec,
+    },
+    core::canopy::{Canopy, Loader},
+    error::Result,
+    geom::Size,
+};
+
+/// Owner namespace of the shared scrolling commands.
+const OWNER: &str = "scrollable";
+
+/// Default bindings exposed through `scrollable.default_bindings()`.
+const DEFAULT_BINDINGS: &str = r#"
+canopy.bind_with("PageDown", { desc = "Page down" }, function()
+    scrollable.page_down()
+end)
+canopy.bind_with("PageUp", { desc = "Page up" }, function()
+    scrollable.page_up()
+end)
+canopy.bind_with("Home", { desc = "Scroll to top" }, function()
+    scrollable.top()
+end)
+canopy.bind_with("End", { desc = "Scroll to bottom" }, function()
+    scrollable.bottom()
+end)
+"#;
+
+/// Keyboard scrolling shared by widgets with scrollable content.
+///
+/// Every method defaults to moving the node's viewport, so a widget that scrolls its
+/// canvas needs only an empty impl and a `Widget::scrollable` override returning itself.
+/// The `scrollable::*` commands act on the nearest scrollable node at or above the node
+/// they are dispatched from, so bindings declared once work across every such widget.
+pub trait Scrollable {
+    /// Scroll up by one line. Returns `true` if the view moved.
+    fn scroll_up(&mut self, c: &mut dyn Context) -> bool {
+        c.scroll_up()
+    }
+
+    /// Scroll down by one line. Returns `true` if the view moved.
+    fn scroll_down(&mut self, c: &mut dyn Context) -> bool {
+        c.scroll_down()
+    }
+
+    /// Scroll left by one column. Returns `true` if the view moved.
+    fn scroll_left(&mut self, c: &mut dyn Context) -> bool {
+        c.scroll_left()
+    }
+
+    /// Scroll right by one column. Returns `true` if the view moved.
+    fn scroll_right(&mut self, c: &mut dyn Context) -> bool {
+        c.scroll_right()
+    }
+
+    /// Scroll up by one page. Returns `true` if the view moved.
+    fn page_up(&mut self, c: &mut dyn Context) -> bool {
+        c.page_up()
+    }
+
+    /// Scroll down by one page. Returns `true` if the view moved.
+    fn page_down(&mut self, c: &mut dyn Context) -> bool {
+        c.page_down()
+    }
+
+    /// Scroll to an absolute content position. Returns `true` if the view moved.
+    fn scroll_to(&mut self, c: &mut dyn Context, x: u32, y: u32) -> bool {
+        c.scroll_to(x, y)
+    }
+
+    /// Size of the scrollable content.
+    fn extent(&self, c: &dyn ReadContext) -> Size {
+        c.view().canvas
+    }
+}
+
+/// Registers the `scrollable::*` commands and their default bindings.
+///
+/// Widgets implementing [`Scrollable`] load this from their own loader.
+pub struct ScrollCommands;
+
+/// Run `f` on the nearest scrollable widget at or above the context node. Does nothing
+/// if no such widget exists.
+fn with_nearest(
+    c: &mut dyn Context,
+    f: impl Fn(&mut dyn Scrollable, &mut dyn Context) -> bool,
+) -> Result<(), CommandError> {
+    let mut current = Some(c.node_id());
+    while let Some(node) = current {
+        let found = c
+            .with_node_mut(node, |widget, ctx| {
+                Ok(widget.scrollable().map(|s| {
+                    f(s, ctx);
+                }))
+            })
+            .map_err(|err| CommandError::Exec(err.into()))?;
+        if let Some(Some(())) = found {
+            return Ok(());
+        }
+        current = c.parent_of(node);
+    }
+    Ok(())
+}
+
+/// Reject arguments passed to a scrolling command, none of which take any.
+fn expect_no_args(inv: &CommandInvocation) -> Result<(), CommandError> {
+    let got = match &inv.args {
+        CommandArgs::Positional(values) => values.len(),
+        CommandArgs::Named(values) => values.len(),
+    };
+    if got > 0 {
+        return Err(CommandError::ArityMismatch { expected: 0, got });
+    }
+    Ok(())
+}
+
+/// Define a `scrollable` command that runs a closure on the nearest scrollable widget.
+macro_rules! scroll_command {
+    ($spec:ident, $name:literal, $doc:literal, $f:expr) => {
+        #[doc = $doc]
+        static $spec: CommandSpec = CommandSpec {
+            id: CommandId(concat!("scrollable::", $name)),
+            name: $name,
+            dispatch: CommandDispatchKind::Free,
+            params: &[],
+            ret: CommandReturnSpec::Unit,
+            doc: CommandDocSpec {
+                short: Some($doc),
+                long: None,
+                hidden: false,
+            },
+            keys: &[],
+            invoke: |_, ctx, inv| {
+                expect_no_args(inv)?;
+                with_nearest(ctx, $f)?;
+                Ok(ArgValue::Null)
+            },
+        };
+    };
+}
+
+scroll_command!(UP, "up", "Scroll up one line.", |s, c| s.scroll_up(c));
+scroll_command!(DOWN, "down", "Scroll down one line.", |s, c| {
+    s.scroll_down(c)
+});
+scroll_command!(LEFT, "left", "Scroll left one column.", |s, c| {
+    s.scroll_left(c)
+});
+scroll_command!(RIGHT, "right", "Scroll right one column.", |s, c| {
+    s.scroll_right(c)
+});
+scroll_command!(PAGE_UP, "page_up", "Scroll up one page.", |s, c| {
+    s.page_up(c)
+});
+scroll_command!(PAGE_DOWN, "page_down", "Scroll down one page.", |s, c| {
+    s.page_down(c)
+});
+scroll_command!(TOP, "top", "Scroll to the top.", |s, c| {
+    let x = c.view().tl.x;
+    s.scroll_to(c, x, 0)
+});
+scroll_command!(BOTTOM, "bottom", "Scroll to the bottom.", |s, c| {
+    let x = c.view().tl.x;
+    let h = s.extent(c).h;
+    s.scroll_to(c, x, h)
+});
+
+impl CommandNode for ScrollCommands {
+    fn commands() -> &'static [&'static CommandSpec] {
+        &[
+            &UP, &DOWN, &LEFT, &RIGHT, &PAGE_UP, &PAGE_DOWN, &TOP, &BOTTOM,
+        ]
+    }
+}
+
+impl Loader for ScrollCommands {
+    fn load(c: &mut Canopy) -> Result<()> {
+        c.add_commands::<Self>()?;
+        c.register_default_bindings(OWNER, DEFAULT_BINDINGS)
+    }
+}
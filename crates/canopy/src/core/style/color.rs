@@ -46,6 +46,11 @@ pub enum Color {
     /// An ANSI color. See [256 colors - cheat
     /// sheet](https://jonasjacek.github.io/colors/) for more info.
     AnsiValue(u8),
+
+    /// The color of the cell being drawn over. Overlays use an inherited background to
+    /// let the underlying content show through, for drop shadows and popups that don't
+    /// look rectangular. Where nothing has been drawn yet, the terminal default is used.
+    Inherit,
 }
 
 /// Macro to create a Color from a hex string at compile time
@@ -104,63 +109,43 @@ impl Color {
     }
 
     /// Convert any color variant to RGB for transformation.
-    /// Named colors and ANSI-256 use standard palette mappings.
+    /// Named colors and ANSI-256 use standard palette mappings. `Inherit` has no color of
+    /// its own and converts to black.
     pub fn to_rgb(self) -> Self {
+        let (r, g, b) = self.channels();
+        Self::Rgb { r, g, b }
+    }
+
+    /// Return the red, green, and blue channels of the color's RGB equivalent.
+    fn channels(self) -> (u8, u8, u8) {
         match self {
-            Self::Rgb { r, g, b } => Self::Rgb { r, g, b },
-            Self::Black => Self::Rgb { r: 0, g: 0, b: 0 },
-            Self::DarkGrey => Self::Rgb {
-                r: 128,
-                g: 128,
-                b: 128,
-            },
-            Self::Red => Self::Rgb { r: 255, g: 0, b: 0 },
-            Self::DarkRed => Self::Rgb { r: 128, g: 0, b: 0 },
-            Self::Green => Self::Rgb { r: 0, g: 255, b: 0 },
-            Self::DarkGreen => Self::Rgb { r: 0, g: 128, b: 0 },
-            Self::Yellow => Self::Rgb {
-                r: 255,
-                g: 255,
-                b: 0,
-            },
-            Self::DarkYellow => Self::Rgb {
-                r: 128,
-                g: 128,
-                b: 0,
-            },
-            Self::Blue => Self::Rgb { r: 0, g: 0, b: 255 },
-            Self::DarkBlue => Self::Rgb { r: 0, g: 0, b: 128 },
-            Self::Magenta => Self::Rgb {
-                r: 255,
-                g: 0,
-                b: 255,
-            },
-            Self::DarkMagenta => Self::Rgb {
-                r: 128,
-                g: 0,
-                b: 128,
-            },
-            Self::Cyan => Self::Rgb {
-                r: 0,
-                g: 255,
-                b: 255,
-            },
-            Self::DarkCyan => Self::Rgb {
-                r: 0,
-                g: 128,
-                b: 128,
-            },
-            Self::White => Self::Rgb {
-                r: 255,
-                g: 255,
-                b: 255,
-            },
-            Self::Grey => Self::Rgb {
-                r: 192,
-                g: 192,
-                b: 192,
-            },
-            Self::AnsiValue(n) => ansi_to_rgb(n),
+            Self::Rgb { r, g, b } => (r, g, b),
+            Self::Black => (0, 0, 0),
+            Self::DarkGrey => (128, 128, 128),
+            Self::Red => (255, 0, 0),
+            Self::DarkRed => (128, 0, 0),
+            Self::Green => (0, 255, 0),
+            Self::DarkGreen => (0, 128, 0),
+            Self::Yellow => (255, 255, 0),
+            Self::DarkYellow => (128, 128, 0),
+            Self::Blue => (0, 0, 255),
+            Self::DarkBlue => (0, 0, 128),
+            Self::Magenta => (255, 0, 255),
+            Self::DarkMagenta => (128, 0, 128),
+            Self::Cyan => (0, 255, 255),
+            Self::DarkCyan => (0, 128, 128),
+            Self::White => (255, 255, 255),
+            Self::Grey => (192, 192, 192),
+            Self::AnsiValue(n) => ansi_to_rgb(n).channels(),
+            Self::Inherit => (0, 0, 0),
+        }
+    }
+
+    /// Return this color, or `below` if this color is `Inherit`.
+    pub fn over(self, below: Self) -> Self {
+        match self {
+            Self::Inherit => below,
+            color => color,
         }
     }
 
@@ -248,6 +233,27 @@ impl Color {
             b: nb,
         }
     }
+
+    /// Return the perceived luminance, from 0.0 for black to 1.0 for white.
+    pub fn luminance(self) -> f32 {
+        let (r, g, b) = self.channels();
+        (0.299 * f32::from(r) + 0.587 * f32::from(g) + 0.114 * f32::from(b)) / 255.0
+    }
+
+    /// Return black or white, whichever is closer in luminance.
+    pub fn to_extreme(self) -> Self {
+        if self.luminance() < 0.5 {
+            Self::Black
+        } else {
+            Self::White
+        }
+    }
+
+    /// Format the color as a "#rrggbb" hex string.
+    pub fn to_hex(self) -> String {
+        let (r, g, b) = self.channels();
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
 }
 
 /// Convert ANSI 256-color to RGB.
@@ -433,6 +439,12 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_to_hex_round_trips() {
+        assert_eq!(Color::rgb("#12abEF").to_hex(), "#12abef");
+        assert_eq!(Color::Red.to_hex(), "#ff0000");
+    }
+
     #[test]
     #[should_panic(expected = "Invalid hex color string: expected 6 hex digits")]
     fn test_rgb_invalid_length() {
@@ -62,6 +62,7 @@ pub fn dracula() -> StyleMap {
             StyleBuilder::new().fg(FOREGROUND).bg(CURRENT_LINE),
         )
         .style("/tab/active", StyleBuilder::new().fg(BACKGROUND).bg(PURPLE))
+        .style("/error", StyleBuilder::new().fg(FOREGROUND).bg(RED))
         .fg("/blue", CYAN)
         .fg("/red", RED)
         .fg("/magenta", PINK)
@@ -112,6 +113,28 @@ pub fn dracula() -> StyleMap {
             "/editor/prompt",
             StyleBuilder::new().fg(FOREGROUND).bg(CURRENT_LINE),
         )
+        .style(
+            "/editor/completion",
+            StyleBuilder::new().fg(FOREGROUND).bg(CURRENT_LINE),
+        )
+        .style(
+            "/editor/completion/selected",
+            StyleBuilder::new().fg(BACKGROUND).bg(PURPLE),
+        )
+        .style(
+            "/editor/hover",
+            StyleBuilder::new().fg(FOREGROUND).bg(CURRENT_LINE),
+        )
+        .attr("/editor/diagnostic", Attr::Underline)
+        .attr("/editor/misspelled", Attr::Underline)
+        .fg("/editor/diagnostic/error", RED)
+        .fg("/editor/diagnostic/warning", YELLOW)
+        .fg("/editor/diagnostic/info", CYAN)
+        .fg("/editor/diagnostic/hint", COMMENT)
+        .fg("/editor/sign/error", RED)
+        .fg("/editor/sign/warning", YELLOW)
+        .fg("/editor/sign/info", CYAN)
+        .fg("/editor/sign/hint", COMMENT)
         .apply();
     c
 }
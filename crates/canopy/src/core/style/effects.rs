@@ -5,7 +5,7 @@
 
 use std::{fmt::Debug, mem, sync::Arc};
 
-use super::{Attr, AttrSet, Color, Style};
+use super::{Attr, AttrSet, Color, Paint, Style};
 
 /// A style transformation that can be applied during rendering.
 ///
@@ -102,6 +102,51 @@ pub fn swap_fg_bg() -> Effect {
     Arc::new(SwapFgBg)
 }
 
+/// Map colors to black and white for maximum contrast.
+///
+/// A solid foreground and background are mapped as a pair: the lighter of the two
+/// becomes white and the darker black, so a highlight that is lighter than its text
+/// inverts instead of collapsing into the plain style. When both have the same
+/// luminance, or the foreground is a gradient, the background takes its nearest extreme
+/// and the foreground the other one. Over a gradient background, both paints are mapped
+/// by their own luminance.
+#[derive(Debug, Clone, Copy)]
+pub struct HighContrast;
+
+impl StyleEffect for HighContrast {
+    fn apply(&self, mut style: Style) -> Style {
+        let (fg, bg) = match (style.fg.solid_color(), style.bg.solid_color()) {
+            (Some(fg), Some(bg)) if fg.luminance() > bg.luminance() => (Color::White, Color::Black),
+            (Some(fg), Some(bg)) if fg.luminance() < bg.luminance() => (Color::Black, Color::White),
+            (_, Some(bg)) => {
+                let bg = bg.to_extreme();
+                (opposite(bg), bg)
+            }
+            (_, None) => {
+                style.bg = style.bg.map_colors(Color::to_extreme);
+                style.fg = style.fg.map_colors(Color::to_extreme);
+                return style;
+            }
+        };
+        style.fg = Paint::solid(fg);
+        style.bg = Paint::solid(bg);
+        style
+    }
+}
+
+/// Return white for black and black for anything else.
+fn opposite(color: Color) -> Color {
+    match color {
+        Color::Black => Color::White,
+        _ => Color::Black,
+    }
+}
+
+/// Create a high-contrast effect.
+pub fn high_contrast() -> Effect {
+    Arc::new(HighContrast)
+}
+
 /// Create a tint effect that blends colors toward a target.
 pub fn tint(color: Color, ratio: f32) -> Effect {
     Arc::new(ColorEffect::Tint(color, ratio))
@@ -186,7 +231,7 @@ pub fn clear_attrs() -> Effect {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::style::Paint;
+    use crate::style::{StyleManager, solarized};
 
     fn test_style() -> Style {
         Style {
@@ -235,6 +280,37 @@ mod tests {
         assert_eq!(swapped.bg, style.fg);
     }
 
+    #[test]
+    fn test_high_contrast() {
+        let style = high_contrast().apply(test_style());
+        assert_eq!(style.bg, Paint::solid(Color::Black));
+        assert_eq!(style.fg, Paint::solid(Color::White));
+
+        let light = Style {
+            bg: Paint::solid(Color::Grey),
+            ..test_style()
+        };
+        let style = high_contrast().apply(light);
+        assert_eq!(style.bg, Paint::solid(Color::White));
+        assert_eq!(style.fg, Paint::solid(Color::Black));
+    }
+
+    #[test]
+    fn high_contrast_keeps_highlights_distinct() {
+        let map = solarized::solarized_dark();
+        let styles = StyleManager::new();
+        for (plain, highlight) in [
+            ("selector", "selector/focus"),
+            ("editor/completion", "editor/completion/selected"),
+        ] {
+            let plain = high_contrast().apply(styles.get(&map, plain));
+            let highlight = high_contrast().apply(styles.get(&map, highlight));
+            assert_eq!(plain.fg, Paint::solid(Color::White));
+            assert_eq!(highlight.bg, Paint::solid(Color::White));
+            assert_ne!(plain, highlight);
+        }
+    }
+
     #[test]
     fn test_bold_effect() {
         let style = test_style();
@@ -73,6 +73,7 @@ pub fn gruvbox_dark() -> StyleMap {
         .fg("/tab", DARK4)
         .style("/tab/inactive", StyleBuilder::new().fg(LIGHT3).bg(DARK1))
         .style("/tab/active", StyleBuilder::new().fg(LIGHT0).bg(BLUE))
+        .style("/error", StyleBuilder::new().fg(LIGHT0).bg(RED))
         .fg("/blue", BLUE)
         .fg("/red", RED)
         .fg("/magenta", PURPLE)
@@ -114,6 +115,25 @@ pub fn gruvbox_dark() -> StyleMap {
         .fg("/editor/line-number", GRAY)
         .fg("/editor/line-number/current", BLUE)
         .style("/editor/prompt", StyleBuilder::new().fg(LIGHT1).bg(DARK1))
+        .style(
+            "/editor/completion",
+            StyleBuilder::new().fg(LIGHT1).bg(DARK1),
+        )
+        .style(
+            "/editor/completion/selected",
+            StyleBuilder::new().fg(DARK0).bg(BLUE),
+        )
+        .style("/editor/hover", StyleBuilder::new().fg(LIGHT1).bg(DARK1))
+        .attr("/editor/diagnostic", Attr::Underline)
+        .attr("/editor/misspelled", Attr::Underline)
+        .fg("/editor/diagnostic/error", RED)
+        .fg("/editor/diagnostic/warning", YELLOW)
+        .fg("/editor/diagnostic/info", BLUE)
+        .fg("/editor/diagnostic/hint", GRAY)
+        .fg("/editor/sign/error", RED)
+        .fg("/editor/sign/warning", YELLOW)
+        .fg("/editor/sign/info", BLUE)
+        .fg("/editor/sign/hint", GRAY)
         .apply();
     c
 }
@@ -9,12 +9,20 @@ pub mod gruvbox;
 /// Solarized theme.
 pub mod solarized;
 
-use std::collections::HashMap;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 pub use color::Color;
 pub use effects::{Effect, StyleEffect};
+use serde::{Deserialize, Serialize};
 
-use crate::geom;
+use crate::{
+    error::{Error, ParseError, Result},
+    geom,
+};
 
 /// A text attribute.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -78,6 +86,20 @@ impl AttrSet {
             || self.overline
             || self.underline)
     }
+    /// Return the names of the active attributes.
+    pub fn names(&self) -> Vec<&'static str> {
+        [
+            (self.bold, "bold"),
+            (self.crossedout, "crossedout"),
+            (self.dim, "dim"),
+            (self.italic, "italic"),
+            (self.overline, "overline"),
+            (self.underline, "underline"),
+        ]
+        .into_iter()
+        .filter_map(|(set, name)| set.then_some(name))
+        .collect()
+    }
     /// A helper for progressive construction of attribute sets.
     pub fn with(mut self, attr: Attr) -> Self {
         match attr {
@@ -239,9 +261,11 @@ impl Paint {
         }
     }
 
-    /// Map colors within this paint.
+    /// Map colors within this paint. `Color::Inherit` is left as is, since its color is
+    /// only known once it is drawn.
     pub fn map_colors(&self, f: impl Fn(Color) -> Color) -> Self {
         match self {
+            Self::Solid(Color::Inherit) => Self::Solid(Color::Inherit),
             Self::Solid(color) => Self::Solid(f(*color)),
             Self::Gradient(spec) => Self::Gradient(spec.map_colors(f)),
         }
@@ -276,6 +300,15 @@ impl ResolvedStyle {
     pub fn new(fg: Color, bg: Color, attrs: AttrSet) -> Self {
         Self { fg, bg, attrs }
     }
+
+    /// Resolve `Color::Inherit` colors against the style of the cell being drawn over.
+    pub fn over(self, below: &Self) -> Self {
+        Self {
+            fg: self.fg.over(below.fg),
+            bg: self.bg.over(below.bg),
+            attrs: self.attrs,
+        }
+    }
 }
 
 /// A paint-based style specification.
@@ -489,11 +522,22 @@ fn parse_path(path: &str) -> Vec<String> {
         .collect()
 }
 
+/// Source of style map versions.
+static NEXT_VERSION: AtomicU64 = AtomicU64::new(1);
+
+/// Return a style map version that no other map content has used.
+fn next_version() -> u64 {
+    NEXT_VERSION.fetch_add(1, Ordering::Relaxed)
+}
+
 /// Map of style paths to partial styles.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct StyleMap {
     /// Path-to-style map.
     styles: HashMap<Vec<String>, PartialStyle>,
+    /// Version of the map contents, changed on every edit. Clones share a version until
+    /// one of them is edited, so a `StyleManager` cache can tell when to discard entries.
+    version: u64,
 }
 
 impl StyleMap {
@@ -501,6 +545,7 @@ impl StyleMap {
     pub fn new() -> Self {
         let mut cs = Self {
             styles: HashMap::new(),
+            version: next_version(),
         };
         cs.insert_style(
             "/",
@@ -533,6 +578,7 @@ impl StyleMap {
 
     /// Insert a style attribute at a specified path.
     pub fn add_attr(&mut self, path: &str, attr: Attr) {
+        self.version = next_version();
         let parsed = parse_path(path);
         if let Some(ps) = self.styles.get_mut(&parsed) {
             if let Some(attrs) = ps.attrs {
@@ -546,12 +592,198 @@ impl StyleMap {
         }
     }
 
+    /// Replace the partial style at a path.
+    pub fn set(&mut self, path: &str, style: PartialStyle) {
+        self.insert_style(path, style);
+    }
+
+    /// Return the style at exactly this path, without resolution.
+    pub fn get(&self, path: &str) -> Option<&PartialStyle> {
+        self.styles.get(&parse_path(path))
+    }
+
+    /// Return every style layer as a "/"-joined path and its partial style, sorted by path.
+    pub fn entries(&self) -> Vec<(String, &PartialStyle)> {
+        let mut entries: Vec<_> = self
+            .styles
+            .iter()
+            .map(|(path, style)| (format!("/{}", path.join("/")), style))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+
+    /// Return the version of the map contents. Clones share a version until one of them
+    /// is edited, so a copy can be compared with its source to see whether it is stale.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Render the map as a TOML theme with one table per style path.
+    ///
+    /// Colors are written as "#rrggbb" strings or "inherit", gradients as tables with an
+    /// angle and a list of offset and color stops, and attributes as an array of names.
+    pub fn to_toml(&self) -> String {
+        let theme: BTreeMap<String, ThemeLayer> = self
+            .entries()
+            .into_iter()
+            .map(|(path, style)| (path, ThemeLayer::from_style(style)))
+            .collect();
+        toml::to_string(&theme).expect("themes serialize to TOML")
+    }
+
+    /// Build a style map from a TOML theme in the format written by
+    /// [`to_toml`](Self::to_toml). Layers the theme omits keep their defaults.
+    pub fn from_toml(text: &str) -> Result<Self> {
+        let theme: BTreeMap<String, ThemeLayer> =
+            toml::from_str(text).map_err(|err| Error::Parse(ParseError::new(err.to_string())))?;
+        let mut map = Self::new();
+        for (path, layer) in theme {
+            map.insert_style(&path, layer.into_style(&path)?);
+        }
+        Ok(map)
+    }
+
     /// Insert a partial style at a path.
     fn insert_style(&mut self, path: &str, style: PartialStyle) {
+        self.version = next_version();
         self.styles.insert(parse_path(path), style);
     }
 }
 
+/// One style layer of a TOML theme.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeLayer {
+    /// Foreground paint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fg: Option<ThemePaint>,
+    /// Background paint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bg: Option<ThemePaint>,
+    /// Attribute names.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    attrs: Option<Vec<String>>,
+}
+
+/// A paint in a TOML theme.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum ThemePaint {
+    /// A "#rrggbb" color or "inherit".
+    Solid(String),
+    /// A gradient.
+    Gradient {
+        /// Gradient angle in degrees.
+        angle: f32,
+        /// Offset and color of each stop.
+        stops: Vec<(f32, String)>,
+    },
+}
+
+impl ThemeLayer {
+    /// Describe a partial style as a theme layer.
+    fn from_style(style: &PartialStyle) -> Self {
+        Self {
+            fg: style.fg.as_ref().map(ThemePaint::from_paint),
+            bg: style.bg.as_ref().map(ThemePaint::from_paint),
+            attrs: style
+                .attrs
+                .map(|attrs| attrs.names().into_iter().map(String::from).collect()),
+        }
+    }
+
+    /// Convert the layer at `path` into a partial style.
+    fn into_style(self, path: &str) -> Result<PartialStyle> {
+        let attrs = match self.attrs {
+            Some(names) => {
+                let mut set = AttrSet::default();
+                for name in &names {
+                    set = set.with(theme_attr(path, name)?);
+                }
+                Some(set)
+            }
+            None => None,
+        };
+        Ok(PartialStyle {
+            fg: self.fg.map(|paint| paint.into_paint(path)).transpose()?,
+            bg: self.bg.map(|paint| paint.into_paint(path)).transpose()?,
+            attrs,
+        })
+    }
+}
+
+impl ThemePaint {
+    /// Describe a paint for a theme.
+    fn from_paint(paint: &Paint) -> Self {
+        match paint {
+            Paint::Solid(color) => Self::Solid(theme_color_name(*color)),
+            Paint::Gradient(spec) => Self::Gradient {
+                angle: spec.angle_deg,
+                stops: spec
+                    .stops
+                    .iter()
+                    .map(|stop| (stop.offset, stop.color.to_hex()))
+                    .collect(),
+            },
+        }
+    }
+
+    /// Convert a theme paint of the layer at `path` into a paint.
+    fn into_paint(self, path: &str) -> Result<Paint> {
+        Ok(match self {
+            Self::Solid(name) => Paint::Solid(theme_color(path, &name)?),
+            Self::Gradient { angle, stops } => {
+                let stops = stops
+                    .iter()
+                    .map(|(offset, name)| {
+                        theme_color(path, name).map(|color| GradientStop::new(*offset, color))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Paint::Gradient(GradientSpec::with_stops(angle, stops))
+            }
+        })
+    }
+}
+
+/// Name a color for a theme.
+fn theme_color_name(color: Color) -> String {
+    match color {
+        Color::Inherit => "inherit".to_string(),
+        color => color.to_hex(),
+    }
+}
+
+/// Parse a theme color name of the layer at `path`.
+fn theme_color(path: &str, name: &str) -> Result<Color> {
+    if name == "inherit" {
+        return Ok(Color::Inherit);
+    }
+    let invalid = || Error::Parse(ParseError::new(format!("{path}: invalid color {name:?}")));
+    let hex = name.strip_prefix('#').ok_or_else(invalid)?;
+    if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    Ok(Color::rgb(hex))
+}
+
+/// Parse a theme attribute name of the layer at `path`.
+fn theme_attr(path: &str, name: &str) -> Result<Attr> {
+    Ok(match name {
+        "bold" => Attr::Bold,
+        "crossedout" => Attr::CrossedOut,
+        "dim" => Attr::Dim,
+        "italic" => Attr::Italic,
+        "overline" => Attr::Overline,
+        "underline" => Attr::Underline,
+        _ => {
+            return Err(Error::Parse(ParseError::new(format!(
+                "{path}: unknown attribute {name:?}"
+            ))));
+        }
+    })
+}
+
 impl Default for StyleMap {
     fn default() -> Self {
         Self::new()
@@ -764,7 +996,18 @@ impl<'a> StyleRules<'a> {
 /// So given a layer stack ["foo"], and an attempt to look up "frame/selected",
 /// we try the following lookups in order: ["foo/frame/selected",
 /// "/frame/selected", "foo", ""].
-#[derive(Debug, PartialEq, Eq, Clone)]
+///
+/// While a node renders, `Canopy` also sets state layers for that node:
+/// `focused` for the focused node or `focus-path` for its ancestors, then
+/// `hover` for the node under the mouse. State layers are not inherited by
+/// children, and are always tried first on top of the current layer stack, so
+/// with layers ["foo"] on the focused node, a lookup of "frame/border" starts
+/// at "foo/focused/frame/border" and then falls back to the usual chain.
+///
+/// Each distinct layer chain is interned into a small scope id as layers are pushed, and
+/// resolved styles are memoized per scope and path until the style map changes. Keeping
+/// one manager across renders lets the cache carry over from frame to frame.
+#[derive(Debug, Clone)]
 pub struct StyleManager {
     /// Current render level.
     level: usize,
@@ -772,6 +1015,46 @@ pub struct StyleManager {
     layers: Vec<String>,
     /// Render levels corresponding to layers.
     layer_levels: Vec<usize>,
+    /// State layers for the node currently rendering.
+    state_layers: Vec<&'static str>,
+    /// Whether resolved styles are mapped to high contrast.
+    high_contrast: bool,
+    /// Interned scope ids, keyed by parent scope and layer name. Scope 0 has no layers.
+    scopes: HashMap<(usize, String), usize>,
+    /// Scope ids of the active layers, parallel to `layers`.
+    layer_scopes: Vec<usize>,
+    /// Scope of the active layers plus the state layers.
+    scope: usize,
+    /// Memoized resolutions.
+    cache: RefCell<StyleCache>,
+}
+
+impl PartialEq for StyleManager {
+    fn eq(&self, other: &Self) -> bool {
+        self.level == other.level
+            && self.layers == other.layers
+            && self.layer_levels == other.layer_levels
+            && self.state_layers == other.state_layers
+            && self.high_contrast == other.high_contrast
+    }
+}
+
+impl Eq for StyleManager {}
+
+/// Return the scope id for `name` pushed on top of scope `parent`, assigning a new id
+/// the first time the pair is seen.
+fn intern_scope(scopes: &mut HashMap<(usize, String), usize>, parent: usize, name: &str) -> usize {
+    let next = scopes.len() + 1;
+    *scopes.entry((parent, name.to_owned())).or_insert(next)
+}
+
+/// Resolved styles memoized for one style map version.
+#[derive(Debug, Clone, Default)]
+struct StyleCache {
+    /// Version of the style map the entries were resolved against.
+    version: u64,
+    /// Resolved styles by scope id and path.
+    styles: HashMap<usize, HashMap<String, Style>>,
 }
 
 impl Default for StyleManager {
@@ -787,14 +1070,34 @@ impl StyleManager {
             level: 0,
             layers: vec![],
             layer_levels: vec![],
+            state_layers: vec![],
+            high_contrast: false,
+            scopes: HashMap::new(),
+            layer_scopes: vec![],
+            scope: 0,
+            cache: RefCell::default(),
         }
     }
 
+    /// Enable or disable high-contrast output. When enabled, `Render` maps every style
+    /// through [`effects::HighContrast`] after applying node effects.
+    pub fn set_high_contrast(&mut self, enabled: bool) {
+        self.high_contrast = enabled;
+    }
+
+    /// Is high-contrast output enabled?
+    pub fn high_contrast(&self) -> bool {
+        self.high_contrast
+    }
+
     /// Reset all layers and levels.
     pub fn reset(&mut self) {
         self.level = 0;
         self.layers = vec![];
         self.layer_levels = vec![0];
+        self.state_layers.clear();
+        self.layer_scopes.clear();
+        self.scope = 0;
     }
 
     /// Increment the render level.
@@ -808,20 +1111,65 @@ impl StyleManager {
             while self.layer_levels.last() == Some(&self.level) {
                 self.layers.pop();
                 self.layer_levels.pop();
+                self.layer_scopes.pop();
             }
             self.level -= 1;
+            self.update_scope();
         }
     }
 
     /// Push onto the layer stack with the current render level.
     pub fn push_layer(&mut self, name: &str) {
+        let parent = self.layer_scopes.last().copied().unwrap_or(0);
+        let scope = intern_scope(&mut self.scopes, parent, name);
         self.layers.push(name.to_owned());
         self.layer_levels.push(self.level);
+        self.layer_scopes.push(scope);
+        self.update_scope();
+    }
+
+    /// Set the state layers applied to the node currently rendering.
+    pub(crate) fn set_state_layers(&mut self, layers: &[&'static str]) {
+        self.state_layers.clear();
+        self.state_layers.extend_from_slice(layers);
+        self.update_scope();
     }
 
-    /// Resolve a style path.
+    /// Recompute the scope of the active layers plus the state layers.
+    fn update_scope(&mut self) {
+        let mut scope = self.layer_scopes.last().copied().unwrap_or(0);
+        for layer in &self.state_layers {
+            scope = intern_scope(&mut self.scopes, scope, layer);
+        }
+        self.scope = scope;
+    }
+
+    /// Resolve a style path, reusing an earlier resolution of the same path in the same
+    /// scope if the style map has not changed since.
     pub fn get(&self, smap: &StyleMap, path: &str) -> Style {
-        self.resolve(smap, &self.layers, &parse_path(path))
+        let mut cache = self.cache.borrow_mut();
+        if cache.version != smap.version {
+            cache.styles.clear();
+            cache.version = smap.version;
+        }
+        let styles = cache.styles.entry(self.scope).or_default();
+        if let Some(style) = styles.get(path) {
+            return style.clone();
+        }
+        let style = self.resolve_path(smap, path);
+        styles.insert(path.to_owned(), style.clone());
+        style
+    }
+
+    /// Resolve a style path against the active and state layers without the cache.
+    fn resolve_path(&self, smap: &StyleMap, path: &str) -> Style {
+        let path = parse_path(path);
+        if self.state_layers.is_empty() {
+            return self.resolve(smap, &self.layers, &path);
+        }
+        let mut layers = self.layers.clone();
+        layers.extend(self.state_layers.iter().map(|layer| (*layer).to_owned()));
+        self.resolve(smap, &layers, &path)
     }
 
     /// Look up one suffix along a layer chain.
@@ -1058,6 +1406,70 @@ mod tests {
         assert_eq!(sm.layer_levels, vec![0]);
     }
 
+    #[test]
+    fn state_layers_are_tried_first_on_top_of_layers() -> Result<()> {
+        let mut smap = StyleMap::new();
+        smap.rules()
+            .fg("frame/border", Color::Red)
+            .fg("help/frame/border", Color::Green)
+            .fg("help/focused/frame/border", Color::Blue)
+            .apply();
+
+        let mut c = StyleManager::default();
+        c.reset();
+        c.push();
+        c.push_layer("help");
+        assert_eq!(
+            c.get(&smap, "frame/border").fg.solid_color(),
+            Some(Color::Green)
+        );
+
+        c.set_state_layers(&["focused"]);
+        assert_eq!(
+            c.get(&smap, "frame/border").fg.solid_color(),
+            Some(Color::Blue)
+        );
+
+        c.set_state_layers(&["focus-path", "hover"]);
+        assert_eq!(
+            c.get(&smap, "frame/border").fg.solid_color(),
+            Some(Color::Green)
+        );
+
+        c.set_state_layers(&[]);
+        assert_eq!(
+            c.get(&smap, "frame/border").fg.solid_color(),
+            Some(Color::Green)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn cached_resolutions_follow_layers_and_map_edits() {
+        let mut smap = StyleMap::new();
+        smap.rules().fg("text", Color::Red).apply();
+
+        let mut c = StyleManager::default();
+        c.reset();
+        c.push();
+        c.push_layer("bar");
+        let fg = |c: &StyleManager, smap: &StyleMap| c.get(smap, "text").fg.solid_color();
+        assert_eq!(fg(&c, &smap), Some(Color::Red));
+        let bar_scope = c.scope;
+
+        smap.rules().fg("bar/text", Color::Blue).apply();
+        assert_eq!(fg(&c, &smap), Some(Color::Blue));
+
+        c.pop();
+        assert_eq!(fg(&c, &smap), Some(Color::Red));
+
+        c.push();
+        c.push_layer("bar");
+        assert_eq!(c.scope, bar_scope);
+        assert_eq!(fg(&c, &smap), Some(Color::Blue));
+    }
+
     #[test]
     fn style_rules_later_overrides_earlier() -> Result<()> {
         let mut smap = StyleMap::new();
@@ -1076,6 +1488,43 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn stylemap_exports_toml() {
+        let mut smap = StyleMap::new();
+        smap.rules()
+            .fg("list/selected", Color::rgb("#102030"))
+            .attrs(
+                "list/selected",
+                AttrSet::new(Attr::Bold).with(Attr::Underline),
+            )
+            .apply();
+        assert_eq!(
+            smap.to_toml(),
+            "[\"/\"]\nfg = \"#ffffff\"\nbg = \"#000000\"\nattrs = []\n\n\
+             [\"/list/selected\"]\nfg = \"#102030\"\nattrs = [\"bold\", \"underline\"]\n"
+        );
+    }
+
+    #[test]
+    fn stylemap_toml_round_trips() -> Result<()> {
+        let mut smap = StyleMap::new();
+        smap.rules()
+            .fg("list/selected", Color::rgb("#102030"))
+            .bg(
+                "list/selected",
+                GradientSpec::new(90.0, Color::Black, Color::rgb("#ff8000")),
+            )
+            .attrs("list/selected", AttrSet::new(Attr::Italic))
+            .fg("text", Color::Inherit)
+            .apply();
+        let loaded = StyleMap::from_toml(&smap.to_toml())?;
+        assert_eq!(loaded.entries(), smap.entries());
+
+        assert!(StyleMap::from_toml("[\"/text\"]\nfg = \"red\"\n").is_err());
+        assert!(StyleMap::from_toml("[\"/text\"]\nattrs = [\"blink\"]\n").is_err());
+        Ok(())
+    }
+
     #[test]
     fn stylemap_default_is_complete() -> Result<()> {
         let smap = StyleMap::default();
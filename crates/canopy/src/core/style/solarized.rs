@@ -57,6 +57,7 @@ pub fn solarized_dark() -> StyleMap {
         .fg("/tab", BASE01)
         .style("/tab/inactive", StyleBuilder::new().fg(BASE1).bg(BASE02))
         .style("/tab/active", StyleBuilder::new().fg(BASE3).bg(BLUE))
+        .style("/error", StyleBuilder::new().fg(BASE3).bg(RED))
         .fg("/blue", BLUE)
         .fg("/red", RED)
         .fg("/magenta", MAGENTA)
@@ -69,6 +70,11 @@ pub fn solarized_dark() -> StyleMap {
         .attr("/text/bold", Attr::Bold)
         .attr("/text/italic", Attr::Italic)
         .attr("/text/underline", Attr::Underline)
+        .style("/text/match", StyleBuilder::new().fg(BASE03).bg(YELLOW))
+        .style(
+            "/text/match/current",
+            StyleBuilder::new().fg(BASE03).bg(ORANGE),
+        )
         .fg("/selector", BASE0)
         .fg("/selector/selected", BLUE)
         .style("/selector/focus", StyleBuilder::new().fg(BASE03).bg(BLUE))
@@ -76,6 +82,35 @@ pub fn solarized_dark() -> StyleMap {
             "/selector/focus/selected",
             StyleBuilder::new().fg(BASE03).bg(CYAN),
         )
+        .fg("/table", BASE0)
+        .style(
+            "/table/header",
+            StyleBuilder::new()
+                .fg(BASE1)
+                .attrs(AttrSet::new(Attr::Bold)),
+        )
+        .style("/table/selected", StyleBuilder::new().fg(BASE03).bg(BLUE))
+        .fg("/dirtree", BASE0)
+        .fg("/dirtree/dir", BLUE)
+        .style("/dirtree/focus", StyleBuilder::new().fg(BASE03).bg(BLUE))
+        .fg("/breadcrumb", BASE0)
+        .fg("/breadcrumb/segment", BLUE)
+        .fg("/breadcrumb/separator", BASE01)
+        .style(
+            "/breadcrumb/current",
+            StyleBuilder::new().fg(CYAN).attrs(AttrSet::new(Attr::Bold)),
+        )
+        .fg("/logview", BASE0)
+        .fg("/logview/trace", BASE01)
+        .fg("/logview/debug", BASE01)
+        .fg("/logview/info", BASE0)
+        .fg("/logview/warn", YELLOW)
+        .fg("/logview/error", RED)
+        .fg("/logview/match", MAGENTA)
+        .style(
+            "/logview/current",
+            StyleBuilder::new().fg(BASE03).bg(MAGENTA),
+        )
         .fg("/dropdown", BASE0)
         .fg("/dropdown/selected", BLUE)
         .style(
@@ -83,6 +118,10 @@ pub fn solarized_dark() -> StyleMap {
             StyleBuilder::new().fg(BASE03).bg(BLUE),
         )
         .style("/editor/text", StyleBuilder::new().fg(BASE0).bg(BASE03))
+        .style(
+            "/editor/whitespace",
+            StyleBuilder::new().fg(BASE01).bg(BASE03),
+        )
         .style(
             "/editor/selection",
             StyleBuilder::new().fg(BASE0).bg(BASE02),
@@ -98,6 +137,31 @@ pub fn solarized_dark() -> StyleMap {
         .fg("/editor/line-number", BASE01)
         .fg("/editor/line-number/current", BLUE)
         .style("/editor/prompt", StyleBuilder::new().fg(BASE0).bg(BASE02))
+        .style(
+            "/editor/completion",
+            StyleBuilder::new().fg(BASE0).bg(BASE02),
+        )
+        .style(
+            "/editor/completion/selected",
+            StyleBuilder::new().fg(BASE03).bg(BLUE),
+        )
+        .style("/editor/hover", StyleBuilder::new().fg(BASE0).bg(BASE02))
+        .attr("/editor/diagnostic", Attr::Underline)
+        .attr("/editor/misspelled", Attr::Underline)
+        .fg("/editor/diagnostic/error", RED)
+        .fg("/editor/diagnostic/warning", YELLOW)
+        .fg("/editor/diagnostic/info", BLUE)
+        .fg("/editor/diagnostic/hint", BASE01)
+        .fg("/editor/sign/error", RED)
+        .fg("/editor/sign/warning", YELLOW)
+        .fg("/editor/sign/info", BLUE)
+        .fg("/editor/sign/hint", BASE01)
+        .style("/command_line", StyleBuilder::new().fg(BASE0).bg(BASE02))
+        .style(
+            "/command_line/message",
+            StyleBuilder::new().fg(ORANGE).bg(BASE02),
+        )
+        .fg("/root/too_small", ORANGE)
         .style("/help/content", StyleBuilder::new().fg(BASE0).bg(BASE02))
         .style("/help/frame", StyleBuilder::new().bg(BASE02))
         .style("/help/frame/focused", StyleBuilder::new().bg(BASE02))
@@ -111,6 +175,14 @@ pub fn solarized_dark() -> StyleMap {
                 .attrs(AttrSet::new(Attr::Bold)),
         )
         .style("/help/label", StyleBuilder::new().fg(BASE1).bg(BASE02))
+        .style(
+            "/help/section",
+            StyleBuilder::new()
+                .fg(YELLOW)
+                .bg(BASE02)
+                .attrs(AttrSet::new(Attr::Bold)),
+        )
+        .style("/help/doc", StyleBuilder::new().fg(BASE01).bg(BASE02))
         .apply();
     c
 }
@@ -135,6 +207,7 @@ pub fn solarized_light() -> StyleMap {
         .fg("/tab", BASE1)
         .style("/tab/inactive", StyleBuilder::new().fg(BASE01).bg(BASE2))
         .style("/tab/active", StyleBuilder::new().fg(BASE3).bg(BLUE))
+        .style("/error", StyleBuilder::new().fg(BASE3).bg(RED))
         .fg("/blue", BLUE)
         .fg("/red", RED)
         .fg("/magenta", MAGENTA)
@@ -147,6 +220,11 @@ pub fn solarized_light() -> StyleMap {
         .attr("/text/bold", Attr::Bold)
         .attr("/text/italic", Attr::Italic)
         .attr("/text/underline", Attr::Underline)
+        .style("/text/match", StyleBuilder::new().fg(BASE3).bg(YELLOW))
+        .style(
+            "/text/match/current",
+            StyleBuilder::new().fg(BASE3).bg(ORANGE),
+        )
         .fg("/selector", BASE00)
         .fg("/selector/selected", BLUE)
         .style("/selector/focus", StyleBuilder::new().fg(BASE3).bg(BLUE))
@@ -154,6 +232,35 @@ pub fn solarized_light() -> StyleMap {
             "/selector/focus/selected",
             StyleBuilder::new().fg(BASE3).bg(CYAN),
         )
+        .fg("/table", BASE00)
+        .style(
+            "/table/header",
+            StyleBuilder::new()
+                .fg(BASE01)
+                .attrs(AttrSet::new(Attr::Bold)),
+        )
+        .style("/table/selected", StyleBuilder::new().fg(BASE3).bg(BLUE))
+        .fg("/dirtree", BASE00)
+        .fg("/dirtree/dir", BLUE)
+        .style("/dirtree/focus", StyleBuilder::new().fg(BASE3).bg(BLUE))
+        .fg("/breadcrumb", BASE00)
+        .fg("/breadcrumb/segment", BLUE)
+        .fg("/breadcrumb/separator", BASE1)
+        .style(
+            "/breadcrumb/current",
+            StyleBuilder::new().fg(CYAN).attrs(AttrSet::new(Attr::Bold)),
+        )
+        .fg("/logview", BASE00)
+        .fg("/logview/trace", BASE1)
+        .fg("/logview/debug", BASE1)
+        .fg("/logview/info", BASE00)
+        .fg("/logview/warn", YELLOW)
+        .fg("/logview/error", RED)
+        .fg("/logview/match", MAGENTA)
+        .style(
+            "/logview/current",
+            StyleBuilder::new().fg(BASE3).bg(MAGENTA),
+        )
         .fg("/dropdown", BASE00)
         .fg("/dropdown/selected", BLUE)
         .style(
@@ -161,6 +268,10 @@ pub fn solarized_light() -> StyleMap {
             StyleBuilder::new().fg(BASE3).bg(BLUE),
         )
         .style("/editor/text", StyleBuilder::new().fg(BASE00).bg(BASE3))
+        .style(
+            "/editor/whitespace",
+            StyleBuilder::new().fg(BASE1).bg(BASE3),
+        )
         .style(
             "/editor/selection",
             StyleBuilder::new().fg(BASE00).bg(BASE2),
@@ -176,6 +287,31 @@ pub fn solarized_light() -> StyleMap {
         .fg("/editor/line-number", BASE1)
         .fg("/editor/line-number/current", BLUE)
         .style("/editor/prompt", StyleBuilder::new().fg(BASE00).bg(BASE2))
+        .style(
+            "/editor/completion",
+            StyleBuilder::new().fg(BASE00).bg(BASE2),
+        )
+        .style(
+            "/editor/completion/selected",
+            StyleBuilder::new().fg(BASE3).bg(BLUE),
+        )
+        .style("/editor/hover", StyleBuilder::new().fg(BASE00).bg(BASE2))
+        .attr("/editor/diagnostic", Attr::Underline)
+        .attr("/editor/misspelled", Attr::Underline)
+        .fg("/editor/diagnostic/error", RED)
+        .fg("/editor/diagnostic/warning", YELLOW)
+        .fg("/editor/diagnostic/info", BLUE)
+        .fg("/editor/diagnostic/hint", BASE1)
+        .fg("/editor/sign/error", RED)
+        .fg("/editor/sign/warning", YELLOW)
+        .fg("/editor/sign/info", BLUE)
+        .fg("/editor/sign/hint", BASE1)
+        .style("/command_line", StyleBuilder::new().fg(BASE00).bg(BASE2))
+        .style(
+            "/command_line/message",
+            StyleBuilder::new().fg(ORANGE).bg(BASE2),
+        )
+        .fg("/root/too_small", ORANGE)
         .style("/help/content", StyleBuilder::new().fg(BASE00).bg(BASE2))
         .style("/help/frame", StyleBuilder::new().bg(BASE2))
         .style("/help/frame/focused", StyleBuilder::new().bg(BASE2))
@@ -189,6 +325,14 @@ pub fn solarized_light() -> StyleMap {
                 .attrs(AttrSet::new(Attr::Bold)),
         )
         .style("/help/label", StyleBuilder::new().fg(BASE01).bg(BASE2))
+        .style(
+            "/help/section",
+            StyleBuilder::new()
+                .fg(YELLOW)
+                .bg(BASE2)
+                .attrs(AttrSet::new(Attr::Bold)),
+        )
+        .style("/help/doc", StyleBuilder::new().fg(BASE1).bg(BASE2))
         .apply();
     c
 }
@@ -6,11 +6,36 @@ use crate::{
     core::text,
     cursor,
     error::Result,
-    geom::{FrameRects, Line, Point, Rect, Size},
+    geom::{FrameRects, Line, Point, PointI32, Rect, Size},
     render::RenderBackend,
     style::{Attr, AttrSet, Color, ResolvedStyle},
 };
 
+/// Return the SGR escape sequence that selects a style from a reset state.
+fn sgr(style: &ResolvedStyle) -> String {
+    let mut codes = vec!["0".to_string()];
+    for (set, code) in [
+        (style.attrs.bold, "1"),
+        (style.attrs.dim, "2"),
+        (style.attrs.italic, "3"),
+        (style.attrs.underline, "4"),
+        (style.attrs.crossedout, "9"),
+        (style.attrs.overline, "53"),
+    ] {
+        if set {
+            codes.push(code.to_string());
+        }
+    }
+    for (base, color) in [(38, style.fg), (48, style.bg)] {
+        if color != Color::Inherit
+            && let Color::Rgb { r, g, b } = color.to_rgb()
+        {
+            codes.push(format!("{base};2;{r};{g};{b}"));
+        }
+    }
+    format!("\x1b[{}m", codes.join(";"))
+}
+
 /// NULL character constant.
 const NULL: char = '\0';
 
@@ -34,7 +59,7 @@ pub struct Cell {
 
 impl Cell {
     /// Construct a cell containing a single glyph.
-    fn new(ch: char, style: ResolvedStyle) -> Self {
+    pub fn new(ch: char, style: ResolvedStyle) -> Self {
         Self {
             ch,
             suffix: String::new(),
@@ -111,6 +136,17 @@ impl Cell {
     }
 }
 
+/// A cell that differs between two buffers, as returned by [`TermBuf::diff`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CellDiff {
+    /// Cell position.
+    pub location: Point,
+    /// Cell in the first buffer, or `None` if the position is outside it.
+    pub before: Option<Cell>,
+    /// Cell in the second buffer, or `None` if the position is outside it.
+    pub after: Option<Cell>,
+}
+
 /// A 2D terminal buffer of styled cells.
 #[derive(Clone, Debug)]
 pub struct TermBuf {
@@ -206,6 +242,33 @@ impl TermBuf {
         }
     }
 
+    /// Copy cells from `src`, placed with its origin at `origin`, into the part of this
+    /// buffer inside `clip`. Inherited colors in `src` take the colors already in this buffer.
+    pub fn blit(&mut self, src: &Self, origin: PointI32, clip: Rect) {
+        let Some(clip) = self.rect().intersect(&clip) else {
+            return;
+        };
+        for y in clip.tl.y..clip.bottom() {
+            for x in clip.tl.x..clip.right() {
+                let (Ok(sx), Ok(sy)) = (
+                    u32::try_from(i64::from(x) - i64::from(origin.x)),
+                    u32::try_from(i64::from(y) - i64::from(origin.y)),
+                ) else {
+                    continue;
+                };
+                if let Some(cell) = src.get(Point { x: sx, y: sy })
+                    && let Some(i) = self.idx(Point { x, y })
+                {
+                    let style = cell.style.over(&self.cells[i].style);
+                    self.cells[i] = Cell {
+                        style,
+                        ..cell.clone()
+                    };
+                }
+            }
+        }
+    }
+
     /// Return the buffer size.
     pub fn size(&self) -> Size {
         self.size
@@ -225,10 +288,11 @@ impl TermBuf {
         }
     }
 
-    /// Write a cell at a specific point.
+    /// Write a cell at a specific point. Inherited colors take the color of the cell being
+    /// replaced.
     pub(crate) fn put(&mut self, p: Point, ch: char, style: ResolvedStyle) {
         if let Some(i) = self.idx(p) {
-            self.cells[i] = Cell::new(ch, style);
+            self.cells[i] = Cell::new(ch, style.over(&self.cells[i].style));
         }
     }
 
@@ -245,7 +309,7 @@ impl TermBuf {
             self.cells[i] = Cell {
                 ch,
                 suffix,
-                style,
+                style: style.over(&self.cells[i].style),
                 continuation: false,
             };
         }
@@ -264,7 +328,7 @@ impl TermBuf {
     /// Write a continuation cell for a wide glyph.
     fn put_continuation(&mut self, p: Point, style: ResolvedStyle) {
         if let Some(i) = self.idx(p) {
-            self.cells[i] = Cell::continuation(style);
+            self.cells[i] = Cell::continuation(style.over(&self.cells[i].style));
         }
     }
 
@@ -279,28 +343,55 @@ impl TermBuf {
         }
     }
 
+    /// Fill a rectangle with copies of a cell. Wide glyphs repeat at their own width, and
+    /// columns too narrow for another copy are filled with spaces.
+    pub fn fill_cell(&mut self, r: Rect, cell: &Cell) {
+        let Some(isec) = self.rect().intersect(&r) else {
+            return;
+        };
+        let glyph = cell.rendered_text();
+        let width = text::grapheme_width(&glyph).max(1) as u32;
+        for y in isec.tl.y..isec.bottom() {
+            let mut x = isec.tl.x;
+            while x + width <= isec.right() {
+                self.put_grapheme(Point { x, y }, &glyph, cell.style);
+                x += width;
+            }
+            for x in x..isec.right() {
+                self.put(Point { x, y }, ' ', cell.style);
+            }
+        }
+    }
+
     /// Fill all empty cells with the given character and style.
     pub fn fill_empty(&mut self, ch: char, style: &ResolvedStyle) {
         for i in 0..self.cells.len() {
             if self.cells[i].is_empty() {
-                self.cells[i] = Cell::new(ch, *style);
+                self.cells[i] = Cell::new(ch, style.over(&self.cells[i].style));
             }
         }
     }
 
     /// Overlay a cursor on a cell by adjusting its style.
-    pub fn overlay_cursor(&mut self, location: Point, shape: cursor::CursorShape) {
-        let Some(idx) = self.idx(location) else {
+    pub fn overlay_cursor(&mut self, cursor: cursor::Cursor) {
+        let Some(idx) = self.idx(cursor.location) else {
             return;
         };
         let mut cell = self.cells[idx].clone();
-        match shape {
-            cursor::CursorShape::Underscore => {
+        match (cursor.shape, cursor.color) {
+            (cursor::CursorShape::Underscore, color) => {
                 cell.style.attrs = cell.style.attrs.with(Attr::Underline);
+                if let Some(color) = color {
+                    cell.style.fg = color;
+                }
             }
-            cursor::CursorShape::Block | cursor::CursorShape::Line => {
+            (cursor::CursorShape::Block | cursor::CursorShape::Line, None) => {
                 mem::swap(&mut cell.style.fg, &mut cell.style.bg);
             }
+            (cursor::CursorShape::Block | cursor::CursorShape::Line, Some(color)) => {
+                cell.style.fg = cell.style.bg;
+                cell.style.bg = color;
+            }
         }
         if cell.is_empty() || cell.continuation {
             cell.ch = ' ';
@@ -388,10 +479,62 @@ impl TermBuf {
             .join("\n")
     }
 
+    /// Return the rendered screen as text with ANSI escapes for color and attributes.
+    ///
+    /// Colors are written as 24-bit SGR sequences, and every line ends with a reset so
+    /// the output can be viewed with `cat` or `less -R`.
+    pub fn ansi_text(&self) -> String {
+        let mut out = String::new();
+        for y in 0..self.size.h {
+            if y > 0 {
+                out.push('\n');
+            }
+            let mut current = None;
+            for x in 0..self.size.w {
+                let cell = self
+                    .get(Point { x, y })
+                    .expect("buffer coordinates should always be valid");
+                if cell.continuation {
+                    continue;
+                }
+                if current != Some(cell.style) {
+                    out.push_str(&sgr(&cell.style));
+                    current = Some(cell.style);
+                }
+                cell.push_text(&mut out);
+            }
+            out.push_str("\x1b[0m");
+        }
+        out
+    }
+
+    /// Compare this buffer cell by cell against `other`, covering the union of both
+    /// sizes. Cells are returned in row-major order.
+    pub fn diff(&self, other: &Self) -> Vec<CellDiff> {
+        let w = self.size.w.max(other.size.w);
+        let h = self.size.h.max(other.size.h);
+        let mut out = Vec::new();
+        for y in 0..h {
+            for x in 0..w {
+                let location = Point { x, y };
+                let before = self.get(location);
+                let after = other.get(location);
+                if before != after {
+                    out.push(CellDiff {
+                        location,
+                        before: before.cloned(),
+                        after: after.cloned(),
+                    });
+                }
+            }
+        }
+        out
+    }
+
     /// Diff this terminal buffer against a previous state, emitting changes
-    /// to the provided render backend.
-    pub fn diff<R: RenderBackend>(&self, prev: &Self, backend: &mut R) -> Result<()> {
-        let mut wrote = false;
+    /// to the provided render backend. The caller flushes the backend, so cursor
+    /// updates can join the same frame.
+    pub fn render_diff<R: RenderBackend>(&self, prev: &Self, backend: &mut R) -> Result<()> {
         if self.size != prev.size {
             return self.render(backend);
         }
@@ -418,7 +561,6 @@ impl TermBuf {
                     render_line_range(backend, row, y, 0, width)?;
                 }
             }
-            backend.flush()?;
             return Ok(());
         }
         if backend.supports_line_shift()
@@ -447,7 +589,6 @@ impl TermBuf {
                     render_line_range(backend, row, y, start_x, len)?;
                 }
             }
-            backend.flush()?;
             return Ok(());
         }
         let width = self.size.w as usize;
@@ -478,7 +619,6 @@ impl TermBuf {
                         let start = width.saturating_sub(gap);
                         render_line_range(backend, current_row, y, start, gap)?;
                     }
-                    wrote = true;
                     continue;
                 }
             }
@@ -508,30 +648,21 @@ impl TermBuf {
                 }
 
                 render_line_range(backend, current_row, y, start_x, end_x - start_x)?;
-                wrote = true;
                 x = end_x;
             }
         }
-        if wrote {
-            backend.flush()?;
-        }
         Ok(())
     }
 
     /// Render this terminal buffer in full using the provided backend,
-    /// batching runs of text with the same style.
+    /// batching runs of text with the same style. The caller flushes the backend.
     pub fn render<R: RenderBackend>(&self, backend: &mut R) -> Result<()> {
-        let mut wrote = false;
         let width = self.size.w as usize;
         for y in 0..self.size.h {
             let row_start = y as usize * width;
             let row_end = row_start + width;
             let row = &self.cells[row_start..row_end];
             render_line_range(backend, row, y, 0, width)?;
-            wrote = true;
-        }
-        if wrote {
-            backend.flush()?;
         }
         Ok(())
     }
@@ -903,6 +1034,69 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn cursor_overlay_uses_cursor_color() {
+        let mut tb = TermBuf::new(Size::new(3, 1), ' ', def_style());
+        let at = |x, shape, color| cursor::Cursor {
+            location: Point { x, y: 0 },
+            shape,
+            blink: false,
+            color,
+        };
+        tb.overlay_cursor(at(0, cursor::CursorShape::Block, None));
+        tb.overlay_cursor(at(1, cursor::CursorShape::Line, Some(Color::Red)));
+        tb.overlay_cursor(at(2, cursor::CursorShape::Underscore, Some(Color::Red)));
+
+        let style = |x| tb.get(Point { x, y: 0 }).unwrap().style;
+        assert_eq!((style(0).fg, style(0).bg), (Color::Black, Color::White));
+        assert_eq!((style(1).fg, style(1).bg), (Color::Black, Color::Red));
+        assert_eq!((style(2).fg, style(2).bg), (Color::Red, Color::Black));
+        assert!(style(2).attrs.underline);
+    }
+
+    #[test]
+    fn inherited_colors_take_the_underlying_cell_colors() {
+        let mut tb = TermBuf::new(Size::new(3, 1), ' ', def_style());
+        tb.fill(
+            &ResolvedStyle::new(Color::Red, Color::Blue, AttrSet::default()),
+            Rect::new(0, 0, 2, 1),
+            'x',
+        );
+        let shadow = ResolvedStyle::new(Color::Grey, Color::Inherit, AttrSet::default());
+        tb.text(&shadow, Line::new(1, 0, 2), "ab");
+
+        let style = |x| tb.get(Point { x, y: 0 }).unwrap().style;
+        assert_eq!(
+            style(0),
+            ResolvedStyle::new(Color::Red, Color::Blue, AttrSet::default())
+        );
+        assert_eq!(
+            style(1),
+            ResolvedStyle::new(Color::Grey, Color::Blue, AttrSet::default())
+        );
+        assert_eq!(
+            style(2),
+            ResolvedStyle::new(Color::Grey, def_style().bg, AttrSet::default())
+        );
+    }
+
+    #[test]
+    fn ansi_text_emits_sgr_on_style_changes() {
+        let mut tb = TermBuf::new(Size::new(3, 2), ' ', def_style());
+        let bold = ResolvedStyle {
+            fg: Color::Red,
+            bg: Color::Black,
+            attrs: AttrSet::new(Attr::Bold),
+        };
+        tb.text(&bold, Line::new(1, 0, 2), "hi");
+
+        assert_eq!(
+            tb.ansi_text(),
+            "\x1b[0;38;2;255;255;255;48;2;0;0;0m \x1b[0;1;38;2;255;0;0;48;2;0;0;0mhi\x1b[0m\n\
+             \x1b[0;38;2;255;255;255;48;2;0;0;0m   \x1b[0m"
+        );
+    }
+
     #[test]
     fn text_write() {
         let mut tb = TermBuf::new(Size::new(5, 1), ' ', def_style());
@@ -1324,13 +1518,30 @@ mod tests {
         buf
     }
 
+    #[test]
+    fn cell_diff_covers_both_sizes() {
+        let style = def_style();
+        let mut before = TermBuf::new(Size::new(3, 1), ' ', style);
+        before.text(&style, Line::new(0, 0, 3), "abc");
+        assert!(before.diff(&before.clone()).is_empty());
+
+        let mut after = TermBuf::new(Size::new(4, 1), ' ', style);
+        after.text(&style, Line::new(0, 0, 3), "abd");
+        let diffs = before.diff(&after);
+        let locations: Vec<Point> = diffs.iter().map(|d| d.location).collect();
+        assert_eq!(locations, vec![Point { x: 2, y: 0 }, Point { x: 3, y: 0 }]);
+        assert_eq!(diffs[0].before.as_ref().map(|c| c.ch), Some('c'));
+        assert_eq!(diffs[0].after.as_ref().map(|c| c.ch), Some('d'));
+        assert!(diffs[1].before.is_none());
+    }
+
     #[test]
     fn diff_no_change() {
         let style = def_style();
         let tb1 = TermBuf::new(Size::new(3, 1), ' ', style);
         let tb2 = TermBuf::new(Size::new(3, 1), ' ', style);
         let mut be = RecBackend::new();
-        tb2.diff(&tb1, &mut be).unwrap();
+        tb2.render_diff(&tb1, &mut be).unwrap();
         assert!(be.ops.is_empty());
     }
 
@@ -1348,7 +1559,7 @@ mod tests {
             }
             let prev = buf.clone();
             let mut backend = CountingBackend::default();
-            buf.diff(&prev, &mut backend).unwrap();
+            buf.render_diff(&prev, &mut backend).unwrap();
             prop_assert_eq!(backend.total_calls(), 0);
         }
 
@@ -1361,7 +1572,7 @@ mod tests {
             current.render(&mut full).unwrap();
 
             let mut diff = ReplayBackend::from_buffer(&prev);
-            current.diff(&prev, &mut diff).unwrap();
+            current.render_diff(&prev, &mut diff).unwrap();
 
             prop_assert_eq!(diff.screen_text(), full.screen_text());
         }
@@ -1372,7 +1583,7 @@ mod tests {
         let prev = buf_from_rows(&["aaa", "bbb", "ccc"]);
         let cur = buf_from_rows(&["xxx", "aaa", "bbb"]);
         let mut be = ShiftBackend::new();
-        cur.diff(&prev, &mut be).unwrap();
+        cur.render_diff(&prev, &mut be).unwrap();
         assert_eq!(be.shift, Some(1));
         assert_eq!(be.text_ops, 1);
     }
@@ -1382,7 +1593,7 @@ mod tests {
         let prev = buf_from_rows(&["#####", "#abc#", "#def#", "#ghi#", "#####"]);
         let cur = buf_from_rows(&["#####", "#xxx#", "#abc#", "#def#", "#####"]);
         let mut be = RegionShiftBackend::default();
-        cur.diff(&prev, &mut be).unwrap();
+        cur.render_diff(&prev, &mut be).unwrap();
         assert_eq!(be.shift, Some((1, 3, 1)));
         assert_eq!(be.text_ops, 1);
     }
@@ -1394,7 +1605,7 @@ mod tests {
         let mut cur = TermBuf::new(Size::new(3, 1), ' ', style);
         cur.text(&style, Line::new(0, 0, 3), "ab");
         let mut be = RecBackend::new();
-        cur.diff(&prev, &mut be).unwrap();
+        cur.render_diff(&prev, &mut be).unwrap();
         assert_eq!(be.ops.len(), 2);
         assert_eq!(be.ops[0], format!("style {style:?}"));
         assert_eq!(be.ops[1], "text 0 0 ab");
@@ -1412,7 +1623,7 @@ mod tests {
         cur.fill(&style1, Rect::new(1, 0, 1, 1), 'b');
 
         let mut be = RecBackend::new();
-        cur.diff(&prev, &mut be).unwrap();
+        cur.render_diff(&prev, &mut be).unwrap();
 
         assert_eq!(be.ops.len(), 4);
         assert_eq!(be.ops[0], format!("style {style2:?}"));
@@ -1428,7 +1639,7 @@ mod tests {
         let mut cur = TermBuf::new(Size::new(3, 2), ' ', style);
         cur.fill(&style, Rect::new(0, 1, 2, 1), 'x');
         let mut be = RecBackend::new();
-        cur.diff(&prev, &mut be).unwrap();
+        cur.render_diff(&prev, &mut be).unwrap();
         assert_eq!(be.ops.len(), 2);
         assert_eq!(be.ops[0], format!("style {style:?}"));
         assert_eq!(be.ops[1], "text 0 1 xx");
@@ -1489,7 +1700,7 @@ mod tests {
         let mut cur = TermBuf::new(Size::new(3, 1), ' ', style);
         cur.text(&style, Line::new(0, 0, 3), "abc");
         let mut be = RecBackend::new();
-        cur.diff(&prev, &mut be).unwrap();
+        cur.render_diff(&prev, &mut be).unwrap();
         assert_eq!(
             be.ops,
             vec![format!("style {style:?}"), "text 0 0 abc".to_string(),]
@@ -1,8 +1,8 @@
 //! Utilities for working with TermBufs in tests.
 use crate::{
-    core::termbuf::TermBuf,
+    core::termbuf::{Cell, TermBuf},
     geom::Point,
-    style::{Color, Paint, PartialStyle},
+    style::{AttrSet, Color, Paint, PartialStyle},
 };
 
 /// A helper macro to create buffers for the termbuf match assertions.
@@ -300,12 +300,119 @@ impl<'a> BufTest<'a> {
     }
 }
 
+/// ANSI highlight for cells that differ in the expected buffer.
+const EXPECTED_MARK: &str = "\x1b[30;41m";
+/// ANSI highlight for cells that differ in the actual buffer.
+const ACTUAL_MARK: &str = "\x1b[30;42m";
+/// ANSI reset.
+const RESET: &str = "\x1b[0m";
+/// Maximum number of differing cells listed in a report.
+const MAX_LISTED: usize = 20;
+
+/// Describe a cell for a diff report.
+fn describe(cell: Option<&Cell>) -> String {
+    let Some(cell) = cell else {
+        return "(outside)".into();
+    };
+    let style = &cell.style;
+    let mut out = format!(
+        "{:?} fg={} bg={}",
+        cell.rendered_text(),
+        style.fg.to_hex(),
+        style.bg.to_hex()
+    );
+    if style.attrs != AttrSet::default() {
+        out.push_str(&format!(" {:?}", style.attrs));
+    }
+    out
+}
+
+/// Render one row of a buffer, highlighting the columns in `changed`.
+fn diff_row(buf: &TermBuf, y: u32, changed: &[u32], mark: &str) -> String {
+    let mut out = String::new();
+    for x in 0..buf.size().w {
+        let Some(cell) = buf.get(Point { x, y }) else {
+            continue;
+        };
+        if cell.continuation {
+            continue;
+        }
+        if changed.contains(&x) {
+            out.push_str(mark);
+            out.push_str(&cell.rendered_text());
+            out.push_str(RESET);
+        } else {
+            out.push_str(&cell.rendered_text());
+        }
+    }
+    out
+}
+
+/// Format a colored before/after report of the cells that differ between two buffers.
+///
+/// The buffers are drawn side by side with differing cells highlighted, followed by a
+/// list of the differences with their styles. Returns `None` if the buffers are equal.
+pub fn diff_report(expected: &TermBuf, actual: &TermBuf) -> Option<String> {
+    let diffs = expected.diff(actual);
+    if diffs.is_empty() {
+        return None;
+    }
+    let (es, as_) = (expected.size(), actual.size());
+    let width = es.w as usize;
+    let mut out = format!(
+        "{:width$}   {}\n",
+        format!("expected {}x{}", es.w, es.h),
+        format!("actual {}x{}", as_.w, as_.h),
+        width = width + 2
+    );
+    for y in 0..es.h.max(as_.h) {
+        let changed: Vec<u32> = diffs
+            .iter()
+            .filter(|d| d.location.y == y)
+            .map(|d| d.location.x)
+            .collect();
+        let left = if y < es.h {
+            format!("│{}│", diff_row(expected, y, &changed, EXPECTED_MARK))
+        } else {
+            " ".repeat(width + 2)
+        };
+        let right = if y < as_.h {
+            format!("│{}│", diff_row(actual, y, &changed, ACTUAL_MARK))
+        } else {
+            String::new()
+        };
+        out.push_str(&format!("{left}   {right}\n"));
+    }
+    out.push_str(&format!("{} cells differ:\n", diffs.len()));
+    for d in diffs.iter().take(MAX_LISTED) {
+        out.push_str(&format!(
+            "  ({}, {}): {} -> {}\n",
+            d.location.x,
+            d.location.y,
+            describe(d.before.as_ref()),
+            describe(d.after.as_ref())
+        ));
+    }
+    if diffs.len() > MAX_LISTED {
+        out.push_str(&format!("  ... and {} more\n", diffs.len() - MAX_LISTED));
+    }
+    Some(out)
+}
+
+/// Assert that two buffers are identical, panicking with a colored diff report if not.
+#[track_caller]
+pub fn assert_buf_eq(expected: &TermBuf, actual: &TermBuf) {
+    if let Some(report) = diff_report(expected, actual) {
+        panic!("buffers differ\n{report}");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         geom::{Line, Size},
-        style::{AttrSet, Color, ResolvedStyle},
+        style::{Attr, ResolvedStyle},
     };
 
     fn test_style() -> ResolvedStyle {
@@ -448,4 +555,31 @@ mod tests {
         // Test line_text
         assert_eq!(bt.line_text(0).unwrap().trim(), "helloworld");
     }
+
+    #[test]
+    fn diff_report_highlights_changed_cells() {
+        let mut expected = TermBuf::new(Size::new(4, 1), ' ', test_style());
+        expected.text(&test_style(), Line::new(0, 0, 4), "abcd");
+        let mut actual = expected.clone();
+        assert!(diff_report(&expected, &actual).is_none());
+
+        let bold = ResolvedStyle::new(Color::White, Color::Black, AttrSet::new(Attr::Bold));
+        actual.text(&test_style(), Line::new(1, 0, 1), "x");
+        actual.text(&bold, Line::new(3, 0, 1), "d");
+        let report = diff_report(&expected, &actual).unwrap();
+        assert!(report.contains(&format!(
+            "│a{EXPECTED_MARK}b{RESET}c{EXPECTED_MARK}d{RESET}│"
+        )));
+        assert!(report.contains(&format!("│a{ACTUAL_MARK}x{RESET}c{ACTUAL_MARK}d{RESET}│")));
+        assert!(report.contains("2 cells differ"));
+        assert!(report.contains("(1, 0): \"b\" fg=#ffffff bg=#000000 -> \"x\""));
+    }
+
+    #[test]
+    #[should_panic(expected = "buffers differ")]
+    fn assert_buf_eq_panics_on_size_change() {
+        let expected = TermBuf::new(Size::new(2, 1), ' ', test_style());
+        let actual = TermBuf::new(Size::new(2, 2), ' ', test_style());
+        assert_buf_eq(&expected, &actual);
+    }
 }
@@ -1,18 +1,29 @@
-use std::{any::TypeId, result::Result as StdResult};
+use std::{
+    any::{Any, TypeId},
+    path::PathBuf,
+    process::{Command, ExitStatus},
+    result::Result as StdResult,
+    time::{Duration, Instant},
+};
 
 use slotmap::Key;
 
 use crate::{
     Context, ReadContext,
     commands::{ArgValue, CommandError, CommandInvocation, CommandScopeFrame, ListRowContext},
-    core::{NodeId, help::OwnedHelpSnapshot, style::Effect, view::View},
-    error::Result,
-    event::{Event, mouse::MouseEvent},
-    geom::{Direction, Point, PointI32, RectI32, Size},
+    core::{
+        NodeId, drag::DragPayload, help::OwnedHelpSnapshot, message::MessageTarget, node::Node,
+        observe::ChangeCounter, poll::PollWaker, profile::Profiler, style::Effect, undo::UndoEntry,
+        view::View,
+    },
+    error::{RenderFailure, Result},
+    event::{Event, TimerId, mouse::MouseEvent},
+    geom::{Direction, Point, PointI32, Rect, RectI32, Size},
     layout::Layout,
     path::Path,
+    state::NodeName,
     style::StyleMap,
-    widget::Widget,
+    widget::{EventOutcome, Widget},
 };
 
 /// Default view used by DummyContext.
@@ -69,6 +80,18 @@ impl ReadContext for DummyContext {
         None
     }
 
+    fn node(&self, _node: NodeId) -> Option<&Node> {
+        None
+    }
+
+    fn min_size_of(&self, _node: NodeId) -> Size {
+        Size::ZERO
+    }
+
+    fn profiler(&self) -> Option<&Profiler> {
+        None
+    }
+
     fn node_type_id(&self, _node: NodeId) -> Option<TypeId> {
         None
     }
@@ -93,6 +116,30 @@ impl ReadContext for DummyContext {
         false
     }
 
+    fn is_hovered(&self) -> bool {
+        false
+    }
+
+    fn node_is_hovered(&self, _node: NodeId) -> bool {
+        false
+    }
+
+    fn high_contrast(&self) -> bool {
+        false
+    }
+
+    fn render_failure(&self) -> Option<RenderFailure> {
+        None
+    }
+
+    fn reduced_motion(&self) -> bool {
+        false
+    }
+
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
     fn focus_path(&self, _root: NodeId) -> Path {
         Path::empty()
     }
@@ -131,6 +178,10 @@ impl Context for DummyContext {
         false
     }
 
+    fn focus_path(&mut self, _path_filter: &str) -> Result<bool> {
+        Ok(false)
+    }
+
     fn focus_dir_in(&mut self, _root: NodeId, _dir: Direction) {}
 
     fn focus_first_in(&mut self, _root: NodeId) {}
@@ -139,6 +190,22 @@ impl Context for DummyContext {
 
     fn focus_prev_in(&mut self, _root: NodeId) {}
 
+    fn push_focus_scope(&mut self, _node: NodeId) {}
+
+    fn pop_focus_scope(&mut self, _node: NodeId) -> bool {
+        false
+    }
+
+    fn focus_push(&mut self) {}
+
+    fn focus_pop(&mut self) -> bool {
+        false
+    }
+
+    fn set_tab_index(&mut self, _node: NodeId, _index: Option<u32>) -> Result<()> {
+        Ok(())
+    }
+
     fn capture_mouse(&mut self) -> bool {
         false
     }
@@ -147,6 +214,14 @@ impl Context for DummyContext {
         false
     }
 
+    fn start_drag(&mut self, _payload: DragPayload) -> bool {
+        false
+    }
+
+    fn cancel_drag(&mut self) -> bool {
+        false
+    }
+
     fn scroll_to(&mut self, _x: u32, _y: u32) -> bool {
         false
     }
@@ -155,12 +230,34 @@ impl Context for DummyContext {
         false
     }
 
+    fn smooth_scroll_to(&mut self, _x: u32, _y: u32) -> bool {
+        false
+    }
+
+    fn smooth_scroll_by(&mut self, _x: i32, _y: i32) -> bool {
+        false
+    }
+
+    fn scroll_rect_into_view(&mut self, _rect: Rect) -> bool {
+        false
+    }
+
+    fn scroll_into_view(&mut self, _node: NodeId) -> bool {
+        false
+    }
+
     fn invalidate_layout(&mut self) {}
 
+    fn damage(&mut self, _rect: Rect) {}
+
     fn with_layout_of(&mut self, _node: NodeId, _f: &mut dyn FnMut(&mut Layout)) -> Result<()> {
         Ok(())
     }
 
+    fn set_name_of(&mut self, _node: NodeId, _name: NodeName) -> Result<()> {
+        Ok(())
+    }
+
     fn create_detached_boxed(&mut self, _widget: Box<dyn Widget>) -> NodeId {
         NodeId::null()
     }
@@ -177,6 +274,22 @@ impl Context for DummyContext {
         Ok(ArgValue::Null)
     }
 
+    fn dispatch_str_from(
+        &mut self,
+        _node: NodeId,
+        _command: &str,
+    ) -> StdResult<ArgValue, CommandError> {
+        Ok(ArgValue::Null)
+    }
+
+    fn dispatch_command_from(
+        &mut self,
+        _node: NodeId,
+        _cmd: &CommandInvocation,
+    ) -> StdResult<ArgValue, CommandError> {
+        Ok(ArgValue::Null)
+    }
+
     fn dispatch_command_scoped(
         &mut self,
         _frame: CommandScopeFrame,
@@ -244,10 +357,24 @@ impl Context for DummyContext {
 
     fn exit(&mut self, _code: i32) {}
 
+    fn exit_with_value(&mut self, _value: Box<dyn Any>) {}
+
+    fn suspend(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn run_external(&mut self, cmd: &mut Command) -> Result<ExitStatus> {
+        Ok(cmd.status()?)
+    }
+
     fn push_effect(&mut self, _node: NodeId, _effect: Effect) -> Result<()> {
         Ok(())
     }
 
+    fn remove_effect(&mut self, _node: NodeId, _effect: &Effect) -> Result<bool> {
+        Ok(false)
+    }
+
     fn clear_effects(&mut self, _node: NodeId) -> Result<()> {
         Ok(())
     }
@@ -256,6 +383,10 @@ impl Context for DummyContext {
         Ok(())
     }
 
+    fn set_unclipped(&mut self, _node: NodeId, _unclipped: bool) -> Result<()> {
+        Ok(())
+    }
+
     fn set_style(&mut self, _style: StyleMap) {
         // DummyContext does not track styles
     }
@@ -269,7 +400,95 @@ impl Context for DummyContext {
         None
     }
 
+    fn request_binding_catalog(&mut self) {
+        // DummyContext has no bindings
+    }
+
     fn request_diagnostic_dump(&mut self, _target: NodeId) {
         // DummyContext does not track diagnostic requests
     }
+
+    fn request_screenshot(&mut self, _path: PathBuf) {
+        // DummyContext does not render frames
+    }
+
+    fn set_high_contrast(&mut self, _enabled: bool) {
+        // DummyContext does not render frames
+    }
+
+    fn dismiss_render_failure(&mut self) {
+        // DummyContext does not render frames
+    }
+
+    fn set_reduced_motion(&mut self, _enabled: bool) {
+        // DummyContext has no accessibility settings
+    }
+
+    fn set_profiling(&mut self, _enabled: bool) {
+        // DummyContext does not render frames
+    }
+
+    fn set_timer(&mut self, _id: TimerId, _interval: Duration, _repeating: bool) {
+        // DummyContext does not run timers
+    }
+
+    fn cancel_timer(&mut self, _id: TimerId) -> bool {
+        false
+    }
+
+    fn poll_waker(&self) -> PollWaker {
+        PollWaker::new(self.node_id, None)
+    }
+
+    fn eval_script(&mut self, _node: NodeId, _source: &str) {
+        // DummyContext does not run scripts
+    }
+
+    fn start_recording(&mut self, _register: &str) {
+        // DummyContext does not record macros
+    }
+
+    fn stop_recording(&mut self) -> Option<String> {
+        None
+    }
+
+    fn recording_register(&self) -> Option<String> {
+        None
+    }
+
+    fn replay(&mut self, _register: &str, _count: usize) {
+        // DummyContext does not replay macros
+    }
+
+    fn save_session(&mut self, _name: &str) {
+        // DummyContext does not save sessions
+    }
+
+    fn load_session(&mut self, _name: &str) {
+        // DummyContext does not load sessions
+    }
+
+    fn observe_changes(&mut self, _node: NodeId, _changes: ChangeCounter) {
+        // DummyContext does not track observers
+    }
+
+    fn broadcast(&mut self, _event: &Event, _path_filter: &str) -> Result<EventOutcome> {
+        Ok(EventOutcome::Ignore)
+    }
+
+    fn send_message(&mut self, _target: MessageTarget, _msg: Box<dyn Any>) {
+        // DummyContext does not deliver messages
+    }
+
+    fn push_undo(&mut self, _entry: UndoEntry) {
+        // DummyContext does not track undo history
+    }
+
+    fn undo(&mut self) -> StdResult<bool, CommandError> {
+        Ok(false)
+    }
+
+    fn redo(&mut self) -> StdResult<bool, CommandError> {
+        Ok(false)
+    }
 }
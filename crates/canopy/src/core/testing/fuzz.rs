@@ -0,0 +1,417 @@
+//! Property-based fuzzing of layouts, focus, and event handling.
+//!
+//! The strategies here generate random tree shapes, terminal sizes, and event sequences
+//! for proptest, which shrinks a failure to a minimal case. [`FuzzCase`] builds a
+//! generated tree, applies its events, and checks core invariants after every step.
+//! [`fuzz_widget`] drives a widget of your own through the same events.
+
+use std::{
+    mem,
+    panic::{self, AssertUnwindSafe},
+};
+
+use proptest::prelude::*;
+
+use super::harness::Harness;
+use crate::{
+    Context, EventOutcome, Loader, NodeId, ReadContext,
+    error::{Error, Result},
+    event::Event,
+    geom::{Direction, Point, Size},
+    layout::{Layout, Sizing},
+    render::Render,
+    state::NodeName,
+    widget::Widget,
+};
+
+/// Maximum depth of generated trees.
+const MAX_DEPTH: u32 = 4;
+/// Maximum number of children generated for one node.
+const MAX_CHILDREN: usize = 4;
+/// Maximum number of events in a generated case.
+const MAX_EVENTS: usize = 40;
+/// Largest generated terminal dimension.
+const MAX_DIMENSION: u32 = 60;
+
+/// Generate a terminal size with both dimensions in `1..=MAX_DIMENSION`.
+pub fn size() -> impl Strategy<Value = Size> {
+    (1..=MAX_DIMENSION, 1..=MAX_DIMENSION).prop_map(|(w, h)| Size::new(w, h))
+}
+
+/// Generate a point within the largest terminal.
+fn point() -> impl Strategy<Value = Point> {
+    (0..MAX_DIMENSION, 0..MAX_DIMENSION).prop_map(|(x, y)| Point { x, y })
+}
+
+/// The shape of a generated node tree.
+#[derive(Debug, Clone)]
+pub struct TreeSpec {
+    /// Layout of this node.
+    pub layout: Layout,
+    /// Whether this node accepts focus.
+    pub focusable: bool,
+    /// Child specifications, in order.
+    pub children: Vec<TreeSpec>,
+}
+
+impl TreeSpec {
+    /// Return a focusable leaf with a fill layout.
+    fn leaf() -> Self {
+        Self {
+            layout: Layout::fill(),
+            focusable: true,
+            children: Vec::new(),
+        }
+    }
+}
+
+/// Generate a node layout. Nodes use flex sizing so that a correct layout always fits
+/// its container. Overflowing containers let their content grow past the viewport.
+fn node_layout() -> impl Strategy<Value = Layout> {
+    (
+        prop_oneof![
+            Just(Layout::column()),
+            Just(Layout::row()),
+            Just(Layout::stack())
+        ],
+        1..=3u32,
+        1..=3u32,
+        prop::bool::weighted(0.15),
+        prop::bool::weighted(0.15),
+    )
+        .prop_map(|(layout, w, h, overflow_x, overflow_y)| {
+            let mut layout = layout.width(Sizing::Flex(w)).height(Sizing::Flex(h));
+            if overflow_x {
+                layout = layout.overflow_x();
+            }
+            if overflow_y {
+                layout = layout.overflow_y();
+            }
+            layout
+        })
+}
+
+/// Generate a tree at most `MAX_DEPTH` levels deep.
+pub fn tree_spec() -> impl Strategy<Value = TreeSpec> {
+    let leaf = (node_layout(), any::<bool>()).prop_map(|(layout, focusable)| TreeSpec {
+        layout,
+        focusable,
+        children: Vec::new(),
+    });
+    leaf.prop_recursive(MAX_DEPTH, 64, MAX_CHILDREN as u32, |inner| {
+        (
+            node_layout(),
+            any::<bool>(),
+            prop::collection::vec(inner, 0..=MAX_CHILDREN),
+        )
+            .prop_map(|(layout, focusable, children)| TreeSpec {
+                layout,
+                focusable,
+                children,
+            })
+    })
+}
+
+/// An input step in a fuzz case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FuzzEvent {
+    /// A character keystroke, delivered to the focused node.
+    Key(char),
+    /// Move focus to the next focusable node.
+    FocusNext,
+    /// Move focus to the previous focusable node.
+    FocusPrev,
+    /// Resize the terminal.
+    Resize(Size),
+    /// Click at a screen location.
+    Click(Point),
+    /// Scroll at a screen location.
+    Scroll(Point, Direction),
+}
+
+impl FuzzEvent {
+    /// Apply this event to a harness.
+    pub fn apply(&self, h: &mut Harness) -> Result<()> {
+        let root = h.root;
+        match *self {
+            Self::Key(c) => h.key(c)?,
+            Self::FocusNext => h.canopy.core.focus_next(root),
+            Self::FocusPrev => h.canopy.core.focus_prev(root),
+            Self::Resize(size) => {
+                h.resize(size)?;
+            }
+            Self::Click(p) => {
+                h.click(p.x, p.y)?;
+            }
+            Self::Scroll(p, dir) => {
+                h.scroll(p.x, p.y, dir)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Generate a single event.
+///
+/// Keys are drawn from `a`, `h`, `x`, and `j`, which generated nodes use to add a child,
+/// toggle a child's visibility, and remove a child.
+fn event() -> impl Strategy<Value = FuzzEvent> {
+    prop_oneof![
+        3 => prop::sample::select(vec!['a', 'h', 'x', 'j']).prop_map(FuzzEvent::Key),
+        2 => Just(FuzzEvent::FocusNext),
+        1 => Just(FuzzEvent::FocusPrev),
+        1 => size().prop_map(FuzzEvent::Resize),
+        2 => point().prop_map(FuzzEvent::Click),
+        1 => (point(), prop_oneof![Just(Direction::Up), Just(Direction::Down)])
+            .prop_map(|(p, dir)| FuzzEvent::Scroll(p, dir)),
+    ]
+}
+
+/// Wrap each event's location into the terminal size in effect when it is applied.
+fn fit_events(mut size: Size, mut events: Vec<FuzzEvent>) -> Vec<FuzzEvent> {
+    for event in &mut events {
+        match event {
+            FuzzEvent::Resize(next) => size = *next,
+            FuzzEvent::Click(p) | FuzzEvent::Scroll(p, _) => {
+                p.x %= size.w;
+                p.y %= size.h;
+            }
+            FuzzEvent::Key(_) | FuzzEvent::FocusNext | FuzzEvent::FocusPrev => {}
+        }
+    }
+    events
+}
+
+/// Generate an event sequence for a terminal that starts at `size`. Mouse events stay
+/// within the terminal as resizes change it.
+pub fn events(size: Size) -> impl Strategy<Value = Vec<FuzzEvent>> {
+    prop::collection::vec(event(), 0..=MAX_EVENTS).prop_map(move |events| fit_events(size, events))
+}
+
+/// Generate an initial terminal size with an event sequence that fits it.
+pub fn session() -> impl Strategy<Value = (Size, Vec<FuzzEvent>)> {
+    (size(), prop::collection::vec(event(), 0..=MAX_EVENTS))
+        .prop_map(|(size, events)| (size, fit_events(size, events)))
+}
+
+/// A fuzz case: a tree, a terminal size, and an event sequence.
+#[derive(Debug, Clone)]
+pub struct FuzzCase {
+    /// Initial terminal size.
+    pub size: Size,
+    /// Tree under test.
+    pub tree: TreeSpec,
+    /// Events applied in order.
+    pub events: Vec<FuzzEvent>,
+}
+
+impl FuzzCase {
+    /// Build the tree, apply every event, and check invariants after each step.
+    pub fn run(&self) -> Result<()> {
+        let root = FuzzNode {
+            spec: self.tree.clone(),
+        };
+        fuzz_widget(root, self.size, &self.events)
+    }
+}
+
+/// Generate a fuzz case.
+pub fn fuzz_case() -> impl Strategy<Value = FuzzCase> {
+    (tree_spec(), session()).prop_map(|(tree, (size, events))| FuzzCase { size, tree, events })
+}
+
+/// Drive `widget` through `events` on a terminal that starts at `size`, checking
+/// invariants after every step. Panics are converted into errors.
+pub fn fuzz_widget<W: Widget + Loader + 'static>(
+    widget: W,
+    size: Size,
+    events: &[FuzzEvent],
+) -> Result<()> {
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| -> Result<()> {
+        let mut h = Harness::builder(widget).size(size.w, size.h).build()?;
+        let root = h.root;
+        h.canopy.core.focus_first(root);
+        h.render()?;
+        check_invariants(&h).map_err(|err| step_error("initial render", &err))?;
+        for (index, event) in events.iter().enumerate() {
+            event
+                .apply(&mut h)
+                .and_then(|()| h.render())
+                .and_then(|()| check_invariants(&h))
+                .map_err(|err| step_error(&format!("step {index} {event:?}"), &err))?;
+        }
+        Ok(())
+    }));
+    outcome.unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| (*s).to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        Err(Error::Invariant(format!("panic: {message}")))
+    })
+}
+
+/// Annotate a failure with the step that produced it.
+fn step_error(step: &str, err: &Error) -> Error {
+    let detail = match err {
+        Error::Invariant(message) => message.clone(),
+        other => other.to_string(),
+    };
+    Error::Invariant(format!("{step}: {detail}"))
+}
+
+/// Check core invariants against the harness's current state.
+///
+/// - No node wrote outside its outer rect during the last render.
+/// - No node's layout escapes its container.
+/// - The focused node, if any, exists, is attached to the root, and is not hidden.
+pub fn check_invariants(h: &Harness) -> Result<()> {
+    let overflows = h.overflows();
+    if !overflows.is_empty() {
+        return Err(Error::Invariant(format!(
+            "writes outside node rects: {overflows:?}"
+        )));
+    }
+    let escapes = h.layout_escapes();
+    if !escapes.is_empty() {
+        return Err(Error::Invariant(format!("layout escapes: {escapes:?}")));
+    }
+    let core = &h.canopy.core;
+    let Some(focus) = core.focus_id() else {
+        return Ok(());
+    };
+    let mut current = Some(focus);
+    while let Some(id) = current {
+        let Some(node) = core.nodes.get(id) else {
+            return Err(Error::Invariant(format!(
+                "focus {focus:?} has a missing ancestor"
+            )));
+        };
+        if node.hidden {
+            return Err(Error::Invariant(format!(
+                "focus {} is inside a hidden node",
+                core.node_path(h.root, focus)
+            )));
+        }
+        if id == h.root {
+            return Ok(());
+        }
+        current = node.parent;
+    }
+    Err(Error::Invariant(format!(
+        "focus {focus:?} is detached from the root"
+    )))
+}
+
+/// A node built from a [`TreeSpec`].
+struct FuzzNode {
+    /// Specification for this node. Children are taken on mount.
+    spec: TreeSpec,
+}
+
+impl Widget for FuzzNode {
+    fn layout(&self) -> Layout {
+        self.spec.layout
+    }
+
+    fn on_mount(&mut self, ctx: &mut dyn Context) -> Result<()> {
+        for spec in mem::take(&mut self.spec.children) {
+            ctx.add_child(Self { spec })?;
+        }
+        Ok(())
+    }
+
+    fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+        self.spec.focusable
+    }
+
+    fn render(&mut self, r: &mut Render, ctx: &dyn ReadContext) -> Result<()> {
+        r.fill("", ctx.outer_rect_local(), '.')
+    }
+
+    fn on_event(&mut self, event: &Event, ctx: &mut dyn Context) -> Result<EventOutcome> {
+        let Event::Key(key) = event else {
+            return Ok(EventOutcome::Ignore);
+        };
+        let first: Option<NodeId> = ctx.children().first().copied();
+        if *key == 'a' {
+            ctx.add_child(Self {
+                spec: TreeSpec::leaf(),
+            })?;
+        } else if *key == 'x'
+            && let Some(child) = first
+        {
+            ctx.remove_subtree(child)?;
+        } else if *key == 'h'
+            && let Some(child) = first
+        {
+            let hidden = ctx.node(child).is_some_and(|n| n.hidden());
+            ctx.set_hidden_of(child, !hidden);
+        } else {
+            return Ok(EventOutcome::Ignore);
+        }
+        Ok(EventOutcome::Handle)
+    }
+
+    fn name(&self) -> NodeName {
+        NodeName::convert("fuzz_node")
+    }
+}
+
+impl Loader for FuzzNode {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::Rect;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(48))]
+
+        #[test]
+        fn generated_cases_hold_invariants(case in fuzz_case()) {
+            let result = case.run();
+            prop_assert!(result.is_ok(), "{result:?}");
+        }
+
+        #[test]
+        fn generated_events_stay_on_screen((size, events) in session()) {
+            let mut current = size;
+            for event in events {
+                match event {
+                    FuzzEvent::Resize(next) => current = next,
+                    FuzzEvent::Click(p) | FuzzEvent::Scroll(p, _) => {
+                        prop_assert!(p.x < current.w && p.y < current.h);
+                    }
+                    FuzzEvent::Key(_) | FuzzEvent::FocusNext | FuzzEvent::FocusPrev => {}
+                }
+            }
+        }
+    }
+
+    struct Wide;
+
+    impl Widget for Wide {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn render(&mut self, r: &mut Render, _ctx: &dyn ReadContext) -> Result<()> {
+            r.fill("", Rect::new(0, 0, 80, 1), '#')
+        }
+
+        fn name(&self) -> NodeName {
+            NodeName::convert("wide")
+        }
+    }
+
+    impl Loader for Wide {}
+
+    #[test]
+    fn fuzz_widget_reports_the_failing_step() {
+        let err = fuzz_widget(Wide, Size::new(10, 2), &[FuzzEvent::FocusNext]).unwrap_err();
+        assert!(err.to_string().contains("initial render"));
+        assert!(err.to_string().contains("writes outside node rects"));
+    }
+}
@@ -121,7 +121,7 @@ impl Grid {
     pub fn find_leaf_at(&self, core: &Core, x: u32, y: u32) -> Option<String> {
         let point = Point { x, y };
         let id = core.locate_node(self.root, point).ok().flatten()?;
-        let name = core.nodes.get(id)?.name.to_string();
+        let name = core.nodes.get(id)?.name().to_string();
         if name.starts_with("cell_") || name.starts_with("container_") {
             Some(name)
         } else {
@@ -1,4 +1,4 @@
-use std::any::Any;
+use std::{any::Any, time::Duration};
 
 use super::{buf::BufTest, render::NopBackend};
 use crate::{
@@ -7,16 +7,20 @@ use crate::{
         context::{CoreContext, CoreViewContext},
         termbuf::TermBuf,
     },
-    error::Result,
-    event::{key, mouse},
-    geom::Size,
+    error::{Error, Result},
+    event::{Event, key, mouse},
+    geom::{Direction, Point, Rect, Size},
     layout::Sizing,
+    path::{Path, PathMatcher},
     widget::Widget,
 };
 
 /// A simple harness that holds a [`Canopy`], a [`NopBackend`] backend and a
 /// root node ID. Tests drive the UI by sending key events and triggering renders
 /// and can then inspect the render buffer.
+///
+/// Polls and timers run on a virtual clock that moves only through
+/// [`Harness::advance`].
 pub struct Harness {
     /// The Canopy instance that manages the node tree and rendering.
     pub canopy: Canopy,
@@ -53,6 +57,7 @@ impl<W: Widget + Loader + 'static> HarnessBuilder<W> {
     pub fn build(self) -> Result<Harness> {
         let render = NopBackend::new();
         let mut canopy = Canopy::new();
+        canopy.use_virtual_clock();
 
         <W as Loader>::load(&mut canopy)?;
         canopy.finalize_api()?;
@@ -83,6 +88,7 @@ impl Harness {
     pub fn with_size<W: Widget + Loader + 'static>(root: W, size: Size) -> Result<Self> {
         let render = NopBackend::new();
         let mut canopy = Canopy::new();
+        canopy.use_virtual_clock();
         <W as Loader>::load(&mut canopy)?;
         canopy.finalize_api()?;
         canopy
@@ -157,6 +163,223 @@ impl Harness {
         self.canopy.render(&mut self.backend)
     }
 
+    /// Press a whitespace-separated sequence of key specifications such as
+    /// `"j j ctrl-s Enter"`, rendering after each key.
+    pub fn press(&mut self, keys: &str) -> Result<&mut Self> {
+        for spec in keys.split_whitespace() {
+            let k = key::Key::parse_spec(spec)
+                .map_err(|err| Error::Invalid(format!("key {spec:?}: {err}")))?;
+            self.key(k)?;
+        }
+        Ok(self)
+    }
+
+    /// Send a mouse action such as `"LeftDown"` or `"ScrollUp"` at a screen location and
+    /// render.
+    pub fn mouse_at(&mut self, spec: &str, location: Point) -> Result<&mut Self> {
+        let m = mouse::Mouse::parse_spec(spec)
+            .map_err(|err| Error::Invalid(format!("mouse {spec:?}: {err}")))?;
+        self.mouse(mouse::MouseEvent {
+            action: m.action,
+            button: m.button,
+            modifiers: m.modifiers,
+            location,
+        })?;
+        Ok(self)
+    }
+
+    /// Send an unmodified mouse action at a screen location and render.
+    fn mouse_action(
+        &mut self,
+        action: mouse::Action,
+        button: mouse::Button,
+        location: Point,
+    ) -> Result<()> {
+        self.mouse(mouse::MouseEvent {
+            action,
+            button,
+            modifiers: key::Empty,
+            location,
+        })
+    }
+
+    /// Click the left button at a screen location: a press followed by a release.
+    pub fn click(&mut self, x: u32, y: u32) -> Result<&mut Self> {
+        let at = Point { x, y };
+        self.mouse_action(mouse::Action::Down, mouse::Button::Left, at)?;
+        self.mouse_action(mouse::Action::Up, mouse::Button::Left, at)?;
+        Ok(self)
+    }
+
+    /// Drag with the left button from one screen location to another. A drag event is
+    /// sent for every cell along the way, then the button is released at `to`.
+    pub fn drag(&mut self, from: Point, to: Point) -> Result<&mut Self> {
+        self.mouse_action(mouse::Action::Down, mouse::Button::Left, from)?;
+        let (dx, dy) = (
+            i64::from(to.x) - i64::from(from.x),
+            i64::from(to.y) - i64::from(from.y),
+        );
+        let steps = dx.abs().max(dy.abs());
+        for step in 1..=steps {
+            let at = Point {
+                x: (i64::from(from.x) + dx * step / steps) as u32,
+                y: (i64::from(from.y) + dy * step / steps) as u32,
+            };
+            self.mouse_action(mouse::Action::Drag, mouse::Button::Left, at)?;
+        }
+        self.mouse_action(mouse::Action::Up, mouse::Button::Left, to)?;
+        Ok(self)
+    }
+
+    /// Scroll the wheel once in a direction at a screen location.
+    pub fn scroll(&mut self, x: u32, y: u32, dir: Direction) -> Result<&mut Self> {
+        let action = match dir {
+            Direction::Up => mouse::Action::ScrollUp,
+            Direction::Down => mouse::Action::ScrollDown,
+            Direction::Left => mouse::Action::ScrollLeft,
+            Direction::Right => mouse::Action::ScrollRight,
+        };
+        self.mouse_action(action, mouse::Button::None, Point { x, y })?;
+        Ok(self)
+    }
+
+    /// Resize the terminal as a resize event would, re-running root sizing and layout,
+    /// then render.
+    pub fn resize(&mut self, size: Size) -> Result<&mut Self> {
+        self.canopy.event(Event::Resize(size))?;
+        self.render()?;
+        Ok(self)
+    }
+
+    /// Advance the virtual clock, firing due polls and timers in time order, then render.
+    pub fn advance(&mut self, elapsed: Duration) -> Result<&mut Self> {
+        self.canopy.advance_clock(elapsed)?;
+        self.render()?;
+        Ok(self)
+    }
+
+    /// Poll every node matching a path filter now, as if its poll interval had elapsed,
+    /// then render.
+    pub fn poll(&mut self, path_filter: &str) -> Result<&mut Self> {
+        let ids = self.find_nodes(path_filter);
+        if ids.is_empty() {
+            return Err(Error::NotFound(path_filter.into()));
+        }
+        self.canopy.event(Event::Poll(ids))?;
+        self.render()?;
+        Ok(self)
+    }
+
+    /// Assert that the focused node's path ends with a match for `path_filter`.
+    #[track_caller]
+    pub fn expect_focus(&mut self, path_filter: &str) -> &mut Self {
+        let matcher = PathMatcher::new(path_filter).expect("invalid path filter");
+        let path = self
+            .canopy
+            .core
+            .focus_id()
+            .map(|id| self.canopy.core.node_path(self.root, id));
+        let matched = path
+            .as_ref()
+            .and_then(|path| matcher.check_match(path))
+            .is_some_and(|m| m.anchored_end);
+        assert!(
+            matched,
+            "expected focus at {path_filter}, found {}",
+            path.map_or_else(|| "no focus".to_string(), |p| p.to_string())
+        );
+        self
+    }
+
+    /// Assert that the rendered text inside `rect` equals `expected`, with rows joined by
+    /// newlines.
+    #[track_caller]
+    pub fn expect_text_at(&mut self, rect: Rect, expected: &str) -> &mut Self {
+        let buf = self.buf();
+        let rows: Vec<String> = (rect.tl.y..rect.tl.y + rect.h)
+            .map(|y| {
+                (rect.tl.x..rect.tl.x + rect.w)
+                    .filter_map(|x| buf.get(Point { x, y }))
+                    .map(|cell| cell.rendered_text())
+                    .collect()
+            })
+            .collect();
+        let actual = rows.join("\n");
+        assert_eq!(actual, expected, "text at {rect:?}");
+        self
+    }
+
+    /// Return nodes that wrote outside their outer rect during the last render pass, with
+    /// the union of those writes in node-local coordinates.
+    pub fn overflows(&self) -> Vec<(Path, Rect)> {
+        self.canopy
+            .overflows
+            .iter()
+            .map(|(id, rect)| (self.canopy.core.node_path(self.root, *id), *rect))
+            .collect()
+    }
+
+    /// Return visible nodes whose layout escapes its container: a child placed outside
+    /// its parent's canvas, or a scroll viewport extending past the node's own canvas.
+    pub fn layout_escapes(&self) -> Vec<(Path, String)> {
+        let core = &self.canopy.core;
+        let mut out = Vec::new();
+        let mut stack = vec![self.root];
+        while let Some(id) = stack.pop() {
+            let Some(node) = core.nodes.get(id) else {
+                continue;
+            };
+            if node.hidden {
+                continue;
+            }
+            stack.extend(node.children.iter().rev());
+            let path = || core.node_path(self.root, id);
+            if let Some(parent) = node.parent.and_then(|p| core.nodes.get(p)) {
+                let canvas = Rect::new(0, 0, parent.canvas.w, parent.canvas.h);
+                if !node.rect.is_zero() && !canvas.contains_rect(&node.rect) {
+                    out.push((
+                        path(),
+                        format!("rect {:?} outside parent canvas {canvas:?}", node.rect),
+                    ));
+                }
+            }
+            let view = node.view.view_rect();
+            let canvas = Rect::new(0, 0, node.canvas.w.max(view.w), node.canvas.h.max(view.h));
+            if !canvas.contains_rect(&view) {
+                out.push((
+                    path(),
+                    format!("viewport {view:?} outside canvas {canvas:?}"),
+                ));
+            }
+        }
+        out
+    }
+
+    /// Assert that no node's layout escapes its container and that no node wrote outside
+    /// its outer rect in the last render pass.
+    #[track_caller]
+    pub fn expect_contained(&mut self) -> &mut Self {
+        let escapes = self.layout_escapes();
+        assert!(escapes.is_empty(), "layout escapes: {escapes:?}");
+        let overflows = self.overflows();
+        assert!(
+            overflows.is_empty(),
+            "writes outside node rects: {overflows:?}"
+        );
+        self
+    }
+
+    /// Assert that the rendered buffer contains `text`.
+    #[track_caller]
+    pub fn expect_text(&mut self, text: &str) -> &mut Self {
+        assert!(
+            self.tbuf().contains_text(text),
+            "expected {text:?} in:\n{}",
+            self.tbuf().snapshot()
+        );
+        self
+    }
+
     /// Execute a closure with mutable access to a widget by node id.
     pub fn with_widget<W, R>(
         &mut self,
@@ -227,8 +450,8 @@ impl Harness {
 mod tests {
     use super::*;
     use crate::{
-        ReadContext, derive_commands, error::Result, geom::Line, layout::Layout, render::Render,
-        state::NodeName, widget::Widget,
+        EventOutcome, ReadContext, derive_commands, error::Result, geom::Line, layout::Layout,
+        render::Render, state::NodeName, widget::Widget,
     };
 
     struct TestNode;
@@ -280,6 +503,396 @@ mod tests {
         assert!(h.tbuf().contains_text("test"));
     }
 
+    struct Ticker {
+        keys: usize,
+        polls: usize,
+    }
+
+    impl Widget for Ticker {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn render(&mut self, r: &mut Render, _ctx: &dyn ReadContext) -> Result<()> {
+            let text = format!("k{} p{}", self.keys, self.polls);
+            r.text("base", Line::new(0, 0, 10), &text)?;
+            Ok(())
+        }
+
+        fn poll(&mut self, _ctx: &mut dyn Context) -> Option<Duration> {
+            self.polls += 1;
+            Some(Duration::from_secs(60))
+        }
+
+        fn on_event(&mut self, event: &Event, _ctx: &mut dyn Context) -> Result<EventOutcome> {
+            if matches!(event, Event::Key(_)) {
+                self.keys += 1;
+                return Ok(EventOutcome::Handle);
+            }
+            Ok(EventOutcome::Ignore)
+        }
+
+        fn accept_focus(&self, _ctx: &dyn ReadContext) -> bool {
+            true
+        }
+
+        fn name(&self) -> NodeName {
+            NodeName::convert("ticker")
+        }
+    }
+
+    impl Loader for Ticker {}
+
+    #[test]
+    fn fluent_script_drives_and_checks_the_ui() -> Result<()> {
+        let mut h = Harness::builder(Ticker { keys: 0, polls: 0 })
+            .size(10, 2)
+            .build()?;
+        h.render()?;
+        let root = h.root;
+        h.canopy.core.set_focus(root);
+
+        h.press("j j ctrl-s Enter")?
+            .expect_focus("/ticker")
+            .expect_text_at(Rect::new(0, 0, 6, 1), "k4 p1 ");
+        h.poll("ticker")?
+            .expect_text("k4 p2")
+            .resize(Size::new(6, 3))?
+            .expect_text_at(Rect::new(0, 0, 6, 3), "k4 p2 \n      \n      ");
+        assert!(h.press("ctrl-bogus").is_err());
+        assert!(h.poll("missing").is_err());
+        Ok(())
+    }
+
+    struct MouseLog {
+        seen: Vec<String>,
+    }
+
+    impl Widget for MouseLog {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn on_event(&mut self, event: &Event, _ctx: &mut dyn Context) -> Result<EventOutcome> {
+            let Event::Mouse(m) = event else {
+                return Ok(EventOutcome::Ignore);
+            };
+            let (x, y) = (m.location.x, m.location.y);
+            self.seen.push(format!("{:?}@{x},{y}", m.action));
+            Ok(EventOutcome::Handle)
+        }
+
+        fn name(&self) -> NodeName {
+            NodeName::convert("mouse_log")
+        }
+    }
+
+    impl Loader for MouseLog {}
+
+    #[test]
+    fn mouse_helpers_run_the_dispatch_path() -> Result<()> {
+        let mut h = Harness::builder(MouseLog { seen: Vec::new() })
+            .size(10, 5)
+            .build()?;
+        h.render()?;
+        h.click(1, 1)?
+            .drag(Point { x: 0, y: 0 }, Point { x: 2, y: 1 })?
+            .scroll(3, 4, Direction::Down)?;
+        let seen = h.with_root_widget(|log: &mut MouseLog| log.seen.clone());
+        assert_eq!(
+            seen,
+            vec![
+                "Down@1,1",
+                "Up@1,1",
+                "Down@0,0",
+                "Drag@1,0",
+                "Drag@2,1",
+                "Up@2,1",
+                "ScrollDown@3,4",
+            ]
+        );
+        Ok(())
+    }
+
+    struct Blinker {
+        log: Vec<String>,
+    }
+
+    impl Widget for Blinker {
+        fn poll(&mut self, ctx: &mut dyn Context) -> Option<Duration> {
+            if self.log.is_empty() {
+                ctx.set_timer(7, Duration::from_millis(250), false);
+            }
+            self.log.push("poll".into());
+            Some(Duration::from_millis(100))
+        }
+
+        fn on_event(&mut self, event: &Event, _ctx: &mut dyn Context) -> Result<EventOutcome> {
+            if let Event::Timer(id) = event {
+                self.log.push(format!("timer {id}"));
+            }
+            Ok(EventOutcome::Handle)
+        }
+    }
+
+    impl Loader for Blinker {}
+
+    #[test]
+    fn advance_fires_polls_and_timers_in_order() -> Result<()> {
+        let mut h = Harness::builder(Blinker { log: Vec::new() })
+            .size(4, 1)
+            .build()?;
+        h.render()?;
+        h.advance(Duration::from_millis(99))?;
+        assert_eq!(h.with_root_widget(|b: &mut Blinker| b.log.len()), 1);
+
+        h.advance(Duration::from_millis(201))?;
+        let log = h.with_root_widget(|b: &mut Blinker| b.log.clone());
+        assert_eq!(log, vec!["poll", "poll", "poll", "timer 7", "poll"]);
+        Ok(())
+    }
+
+    struct Banner;
+
+    impl Widget for Banner {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn render(&mut self, r: &mut Render, _ctx: &dyn ReadContext) -> Result<()> {
+            r.text("base", Line::new(0, 0, 8), "banner")
+        }
+
+        fn name(&self) -> NodeName {
+            NodeName::convert("banner")
+        }
+    }
+
+    impl Loader for Banner {}
+
+    #[test]
+    fn resize_exposes_writes_outside_the_node() -> Result<()> {
+        let mut h = Harness::builder(Banner).size(10, 2).build()?;
+        h.render()?;
+        h.expect_contained();
+
+        h.resize(Size::new(5, 2))?
+            .expect_text_at(Rect::new(0, 0, 5, 1), "banne");
+        assert!(h.layout_escapes().is_empty());
+        let overflows = h.overflows();
+        assert_eq!(overflows.len(), 1);
+        assert_eq!(overflows[0].0.to_string(), "/banner");
+        assert_eq!(overflows[0].1, Rect::new(0, 0, 8, 1));
+        Ok(())
+    }
+
+    #[test]
+    fn strict_clipping_rejects_writes_outside_the_node() -> Result<()> {
+        let mut h = Harness::builder(Banner).size(5, 2).build()?;
+        h.canopy.set_strict_clipping(true);
+        assert!(h.render().is_err());
+
+        h.with_root_context(|_: &mut Banner, ctx| {
+            let id = ctx.node_id();
+            ctx.set_unclipped(id, true)
+        })?;
+        h.render()?;
+        assert!(h.overflows().is_empty());
+        h.expect_text_at(Rect::new(0, 0, 5, 1), "banne");
+        Ok(())
+    }
+
+    struct Flaky {
+        fail: bool,
+    }
+
+    impl Widget for Flaky {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn render(&mut self, r: &mut Render, _ctx: &dyn ReadContext) -> Result<()> {
+            if self.fail {
+                return Err(crate::error::Error::Render("boom".into()));
+            }
+            r.text("base", Line::new(0, 0, 8), "healthy")
+        }
+
+        fn name(&self) -> NodeName {
+            NodeName::convert("flaky")
+        }
+    }
+
+    impl Loader for Flaky {}
+
+    #[test]
+    fn caught_render_errors_show_an_overlay_until_dismissed() -> Result<()> {
+        let mut h = Harness::builder(Flaky { fail: true }).size(70, 6).build()?;
+        assert!(h.render().is_err());
+
+        h.canopy.set_catch_render_errors(true);
+        h.render()?;
+        let failure = h
+            .canopy
+            .render_failure()
+            .cloned()
+            .expect("failure recorded");
+        assert_eq!(failure.path.to_string(), "/flaky");
+        assert!(failure.message.contains("boom"));
+        assert!(h.tbuf().contains_text("render error in /flaky"));
+        assert!(h.tbuf().contains_text("boom"));
+
+        h.with_root_widget(|w: &mut Flaky| w.fail = false);
+        h.canopy.dismiss_render_failure();
+        h.render()?;
+        assert!(h.canopy.render_failure().is_none());
+        assert!(h.tbuf().contains_text("healthy"));
+        assert!(!h.tbuf().contains_text("boom"));
+        Ok(())
+    }
+
+    struct Cramped {
+        min_width: u32,
+    }
+
+    impl Widget for Cramped {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn on_resize(&mut self, size: Size<u32>, _ctx: &mut dyn Context) -> Result<()> {
+            if size.w < self.min_width {
+                return Err(crate::error::Error::Layout("too narrow".into()));
+            }
+            Ok(())
+        }
+
+        fn render(&mut self, r: &mut Render, _ctx: &dyn ReadContext) -> Result<()> {
+            r.text("base", Line::new(0, 0, 8), "roomy")
+        }
+
+        fn name(&self) -> NodeName {
+            NodeName::convert("cramped")
+        }
+    }
+
+    impl Loader for Cramped {}
+
+    #[test]
+    fn caught_layout_errors_show_an_overlay() -> Result<()> {
+        let mut h = Harness::builder(Cramped { min_width: 40 })
+            .size(70, 6)
+            .build()?;
+        h.render()?;
+        assert!(h.resize(Size::new(30, 6)).is_err());
+
+        h.canopy.set_catch_render_errors(true);
+        h.resize(Size::new(20, 6))?;
+        let failure = h
+            .canopy
+            .render_failure()
+            .cloned()
+            .expect("failure recorded");
+        assert_eq!(failure.path.to_string(), "/cramped");
+        assert!(failure.message.contains("on_resize"));
+        assert!(failure.message.contains("too narrow"));
+        assert!(h.tbuf().contains_text("too narrow"));
+        Ok(())
+    }
+
+    struct Fragile {
+        panic: bool,
+        renders: usize,
+    }
+
+    impl Widget for Fragile {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn render(&mut self, r: &mut Render, _ctx: &dyn ReadContext) -> Result<()> {
+            self.renders += 1;
+            if self.panic {
+                panic!("kaboom");
+            }
+            r.text("base", Line::new(0, 0, 8), "intact")
+        }
+
+        fn name(&self) -> NodeName {
+            NodeName::convert("fragile")
+        }
+    }
+
+    impl Loader for Fragile {}
+
+    #[test]
+    fn isolated_panics_render_a_placeholder_until_dismissed() -> Result<()> {
+        let widget = Fragile {
+            panic: true,
+            renders: 0,
+        };
+        let mut h = Harness::builder(widget).size(30, 3).build()?;
+        h.canopy.set_isolate_panics(true);
+        h.render()?;
+        let failed = h.canopy.failed_nodes();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].1, "kaboom");
+        assert!(h.tbuf().contains_text("panic: kaboom"));
+
+        h.render()?;
+        assert_eq!(h.with_root_widget(|w: &mut Fragile| w.renders), 1);
+
+        h.with_root_widget(|w: &mut Fragile| w.panic = false);
+        h.canopy.dismiss_render_failure();
+        h.render()?;
+        assert!(h.canopy.failed_nodes().is_empty());
+        assert!(h.tbuf().contains_text("intact"));
+        Ok(())
+    }
+
+    struct Jumpy {
+        events: usize,
+    }
+
+    impl Widget for Jumpy {
+        fn layout(&self) -> Layout {
+            Layout::fill()
+        }
+
+        fn on_event(&mut self, _event: &Event, _ctx: &mut dyn Context) -> Result<EventOutcome> {
+            self.events += 1;
+            panic!("jumped");
+        }
+
+        fn render(&mut self, r: &mut Render, _ctx: &dyn ReadContext) -> Result<()> {
+            r.text("base", Line::new(0, 0, 8), "steady")
+        }
+
+        fn name(&self) -> NodeName {
+            NodeName::convert("jumpy")
+        }
+    }
+
+    impl Loader for Jumpy {}
+
+    #[test]
+    fn isolated_panics_in_event_handlers_fail_the_node() -> Result<()> {
+        let mut h = Harness::builder(Jumpy { events: 0 }).size(30, 3).build()?;
+        h.canopy.set_isolate_panics(true);
+        let root = h.root;
+        let outcome = h.canopy.core.dispatch_event(root, &Event::FocusGained)?;
+        assert_eq!(outcome, EventOutcome::Ignore);
+        let failed = h.canopy.failed_nodes();
+        assert_eq!(failed, vec![(root, "jumped".to_string())]);
+
+        h.render()?;
+        assert!(h.tbuf().contains_text("panic: jumped"));
+
+        h.canopy.core.dispatch_event(root, &Event::FocusGained)?;
+        assert_eq!(h.with_root_widget(|w: &mut Jumpy| w.events), 1);
+        Ok(())
+    }
+
     #[test]
     fn test_harness_with_size() {
         let mut h = Harness::with_size(TestNode::new(), Size::new(15, 4)).unwrap();
@@ -4,12 +4,16 @@ pub mod backend;
 pub mod buf;
 /// Dummy context for tests.
 pub mod dummyctx;
+/// Property-based layout and event fuzzing.
+pub mod fuzz;
 /// Grid test helpers.
 pub mod grid;
 /// Harness for node testing.
 pub mod harness;
 /// Render helpers for tests.
 pub mod render;
+/// Synthetic widget trees for benchmarks.
+pub mod synth;
 /// Test tree helpers.
 pub mod ttree;
 
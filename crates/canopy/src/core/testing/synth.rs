@@ -0,0 +1,126 @@
+//! Deterministic synthetic widget trees for benchmarks and stress tests.
+
+use crate::{
+    Canopy, NodeId, ReadContext,
+    error::Result,
+    geom::{Line, Size},
+    layout::{Layout, MeasureConstraints, Measurement},
+    render::Render,
+    state::NodeName,
+    widget::Widget,
+};
+
+/// Width of a leaf label.
+const LEAF_WIDTH: u32 = 18;
+
+/// Label rendered by every leaf, mixing narrow, wide, and emoji graphemes.
+const LEAF_LABEL: &str = "leaf \u{754c} \u{1f642}";
+
+/// A node in a synthetic tree: a column container, or a one-line leaf label.
+struct SynthNode {
+    /// Stable node name.
+    name: NodeName,
+    /// Layout returned by the widget.
+    layout: Layout,
+    /// Whether this node is a leaf that renders a label.
+    leaf: bool,
+}
+
+impl SynthNode {
+    /// Build a container node.
+    fn branch(index: usize) -> Self {
+        Self {
+            name: NodeName::convert(&format!("branch_{index}")),
+            layout: Layout::column().gap(1),
+            leaf: false,
+        }
+    }
+
+    /// Build a leaf node.
+    fn leaf(index: usize) -> Self {
+        Self {
+            name: NodeName::convert(&format!("leaf_{index}")),
+            layout: Layout::default().fixed_width(LEAF_WIDTH).fixed_height(1),
+            leaf: true,
+        }
+    }
+}
+
+impl Widget for SynthNode {
+    fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    fn measure(&self, constraints: MeasureConstraints) -> Measurement {
+        if self.leaf {
+            constraints.clamp(Size::new(LEAF_WIDTH, 1))
+        } else {
+            constraints.wrap()
+        }
+    }
+
+    fn render(&mut self, frame: &mut Render<'_>, _ctx: &dyn ReadContext) -> Result<()> {
+        if self.leaf {
+            frame.text("default", Line::new(0, 0, LEAF_WIDTH), LEAF_LABEL)?;
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> NodeName {
+        self.name.clone()
+    }
+}
+
+/// Build an app whose root holds a complete tree of column containers, `depth` levels
+/// deep with `fanout` children per container and labelled leaves at the bottom, laid
+/// out at `size`.
+///
+/// The same arguments always produce the same tree, so timings are comparable across
+/// runs.
+pub fn tree(depth: usize, fanout: usize, size: Size) -> Result<Canopy> {
+    let mut app = Canopy::new();
+    let root_child = app.core_mut().create_detached(SynthNode::branch(0));
+    app.set_root_child(root_child)?;
+    let mut next_index = 1;
+    add_children(&mut app, root_child, depth, fanout, &mut next_index)?;
+    app.set_root_size(size)?;
+    Ok(app)
+}
+
+/// Add a fixed fanout subtree below `parent`.
+fn add_children(
+    app: &mut Canopy,
+    parent: NodeId,
+    depth: usize,
+    fanout: usize,
+    next_index: &mut usize,
+) -> Result<()> {
+    let mut children = Vec::with_capacity(fanout);
+    for _ in 0..fanout {
+        let index = *next_index;
+        *next_index += 1;
+        let child = if depth <= 1 {
+            app.core_mut().create_detached(SynthNode::leaf(index))
+        } else {
+            let child = app.core_mut().create_detached(SynthNode::branch(index));
+            add_children(app, child, depth - 1, fanout, next_index)?;
+            child
+        };
+        children.push(child);
+    }
+    app.core_mut().set_children(parent, children)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tree_has_the_requested_shape() -> Result<()> {
+        let app = tree(3, 2, Size::new(40, 20))?;
+        let base = Canopy::new().core.nodes.len();
+        // The top branch, then 2 + 4 + 8 nodes below it.
+        assert_eq!(app.core.nodes.len() - base, 15);
+        Ok(())
+    }
+}
@@ -64,13 +64,29 @@ pub fn grapheme_width(grapheme: &str) -> usize {
 
 /// Expand tabs into spaces using the configured tab stop.
 pub fn expand_tabs(s: &str, tab_stop: usize) -> String {
+    expand(s, tab_stop, None)
+}
+
+/// Expand tabs like [`expand_tabs`], drawing `marker` in the first cell of each tab.
+pub fn expand_tabs_marked(s: &str, tab_stop: usize, marker: char) -> String {
+    expand(s, tab_stop, Some(marker))
+}
+
+/// Expand tabs into spaces, optionally starting each tab with a marker glyph.
+fn expand(s: &str, tab_stop: usize, marker: Option<char>) -> String {
     let tab_stop = tab_stop.max(1);
     let mut out = String::new();
     let mut col = 0usize;
     for grapheme in s.graphemes(true) {
         if grapheme == "\t" {
             let width = tab_width(col, tab_stop);
-            out.extend(repeat_n(' ', width));
+            match marker {
+                Some(marker) => {
+                    out.push(marker);
+                    out.extend(repeat_n(' ', width - 1));
+                }
+                None => out.extend(repeat_n(' ', width)),
+            }
             col = col.saturating_add(width);
             continue;
         }
@@ -97,7 +113,6 @@ fn tab_width(column: usize, tab_stop: usize) -> usize {
 }
 
 /// Return the display width of a string in terminal cells.
-#[cfg(test)]
 pub fn display_width(s: &str) -> usize {
     s.graphemes(true).map(grapheme_width).sum()
 }
@@ -154,4 +169,9 @@ mod tests {
     fn expand_tabs_handles_wide_graphemes() {
         assert_eq!(expand_tabs("界\tb", 4), "界  b");
     }
+
+    #[test]
+    fn expand_tabs_marked_starts_each_tab_with_marker() {
+        assert_eq!(expand_tabs_marked("a\tb\t", 4, '>'), "a>  b>  ");
+    }
 }
@@ -0,0 +1,125 @@
+use std::{
+    collections::HashMap,
+    mem,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    core::{anim::FRAME_INTERVAL, id::NodeId},
+    event::TimerId,
+};
+
+/// An active timer.
+#[derive(Debug)]
+struct Timer {
+    /// Delay between firings.
+    interval: Duration,
+    /// Whether the timer rearms after firing.
+    repeating: bool,
+    /// Earliest instant the timer may fire. Poller entries that arrive earlier are stale.
+    due: Instant,
+}
+
+/// Timers set by widgets, keyed by node and widget-chosen id.
+#[derive(Debug, Default)]
+pub(crate) struct Timers {
+    /// Active timers.
+    active: HashMap<(NodeId, TimerId), Timer>,
+    /// Timers waiting to be handed to the poller, with their delay.
+    pending: Vec<(NodeId, TimerId, Duration)>,
+}
+
+impl Timers {
+    /// Set or replace a timer, counting its interval from `now`. Repeating intervals are
+    /// at least one animation frame, so a zero interval cannot spin the event loop.
+    pub(crate) fn set(
+        &mut self,
+        node: NodeId,
+        id: TimerId,
+        interval: Duration,
+        repeating: bool,
+        now: Instant,
+    ) {
+        let interval = if repeating {
+            interval.max(FRAME_INTERVAL)
+        } else {
+            interval
+        };
+        self.active.insert(
+            (node, id),
+            Timer {
+                interval,
+                repeating,
+                due: now + interval,
+            },
+        );
+        self.pending.push((node, id, interval));
+    }
+
+    /// Cancel a timer. Returns `false` if it was not active.
+    pub(crate) fn cancel(&mut self, node: NodeId, id: TimerId) -> bool {
+        self.active.remove(&(node, id)).is_some()
+    }
+
+    /// Return whether a timer is active.
+    pub(crate) fn is_active(&self, node: NodeId, id: TimerId) -> bool {
+        self.active.contains_key(&(node, id))
+    }
+
+    /// Take timers waiting to be scheduled.
+    pub(crate) fn take_pending(&mut self) -> Vec<(NodeId, TimerId, Duration)> {
+        mem::take(&mut self.pending)
+    }
+
+    /// Handle a poller entry for a timer at `now`. Returns `true` if the timer should be
+    /// delivered. Repeating timers are rearmed; one-shot timers are removed.
+    pub(crate) fn fire(&mut self, node: NodeId, id: TimerId, now: Instant) -> bool {
+        let Some(timer) = self.active.get_mut(&(node, id)) else {
+            return false;
+        };
+        if now < timer.due {
+            return false;
+        }
+        if timer.repeating {
+            timer.due = now + timer.interval;
+            self.pending.push((node, id, timer.interval));
+        } else {
+            self.active.remove(&(node, id));
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use slotmap::SlotMap;
+
+    use super::*;
+
+    #[test]
+    fn stale_and_cancelled_entries_do_not_fire() {
+        let mut nodes: SlotMap<NodeId, ()> = SlotMap::with_key();
+        let node = nodes.insert(());
+        let mut timers = Timers::default();
+        let start = Instant::now();
+
+        timers.set(node, 1, Duration::from_secs(10), true, start);
+        assert!(!timers.fire(node, 1, start));
+        let later = start + Duration::from_secs(11);
+        assert!(timers.fire(node, 1, later));
+        assert!(timers.is_active(node, 1));
+        assert!(!timers.fire(node, 1, later));
+        assert_eq!(timers.take_pending().len(), 2);
+
+        timers.set(node, 2, Duration::ZERO, false, later);
+        assert!(timers.fire(node, 2, later));
+        assert!(!timers.is_active(node, 2));
+
+        timers.set(node, 3, Duration::ZERO, true, later);
+        assert!(!timers.fire(node, 3, later));
+        assert!(timers.fire(node, 3, later + FRAME_INTERVAL));
+
+        assert!(timers.cancel(node, 1));
+        assert!(!timers.fire(node, 1, later + Duration::from_secs(20)));
+    }
+}
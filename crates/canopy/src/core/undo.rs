@@ -0,0 +1,174 @@
+use crate::{
+    commands::{self, CommandError, CommandInvocation},
+    core::{id::NodeId, world::Core},
+};
+
+/// A reversible change registered by a widget.
+///
+/// Both commands dispatch relative to `node`, so node-routed commands reach the widget that
+/// made the change.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UndoEntry {
+    /// Node the commands dispatch from.
+    pub node: NodeId,
+    /// Short description of the change.
+    pub label: String,
+    /// Command that reverses the change.
+    pub undo: CommandInvocation,
+    /// Command that reapplies the change.
+    pub redo: CommandInvocation,
+}
+
+impl UndoEntry {
+    /// Construct an entry from a pair of inverse commands.
+    pub fn new(
+        node: NodeId,
+        label: impl Into<String>,
+        undo: CommandInvocation,
+        redo: CommandInvocation,
+    ) -> Self {
+        Self {
+            node,
+            label: label.into(),
+            undo,
+            redo,
+        }
+    }
+}
+
+/// Undo and redo history shared by all widgets in an app.
+#[derive(Debug)]
+pub struct UndoManager {
+    /// Changes that can be undone, oldest first.
+    undo: Vec<UndoEntry>,
+    /// Undone changes that can be reapplied, oldest first.
+    redo: Vec<UndoEntry>,
+    /// Maximum number of undo entries retained.
+    limit: usize,
+    /// Set while an entry's command runs, so the command does not register itself.
+    applying: bool,
+}
+
+impl UndoManager {
+    /// Construct a manager retaining at most `limit` undo entries.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            limit,
+            applying: false,
+        }
+    }
+
+    /// Record a change. This clears the redo history. Entries pushed while an undo or redo
+    /// is being applied are ignored.
+    pub fn push(&mut self, entry: UndoEntry) {
+        if self.applying {
+            return;
+        }
+        self.redo.clear();
+        self.undo.push(entry);
+        if self.undo.len() > self.limit {
+            let excess = self.undo.len() - self.limit;
+            self.undo.drain(..excess);
+        }
+    }
+
+    /// Label of the change `undo` would reverse.
+    pub fn undo_label(&self) -> Option<&str> {
+        self.undo.last().map(|entry| entry.label.as_str())
+    }
+
+    /// Label of the change `redo` would reapply.
+    pub fn redo_label(&self) -> Option<&str> {
+        self.redo.last().map(|entry| entry.label.as_str())
+    }
+
+    /// Number of changes that can be undone.
+    pub fn undo_len(&self) -> usize {
+        self.undo.len()
+    }
+
+    /// Number of changes that can be redone.
+    pub fn redo_len(&self) -> usize {
+        self.redo.len()
+    }
+
+    /// Discard all history.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+#[allow(clippy::multiple_inherent_impl)]
+impl Core {
+    /// Apply the newest undo entry, or the newest redo entry if `redo` is set.
+    ///
+    /// The entry's command dispatches relative to its node with registration suppressed, and
+    /// the entry moves to the opposite stack on success. Returns `false` if undo is disabled
+    /// or there is nothing to apply.
+    pub(crate) fn apply_undo(&mut self, redo: bool) -> Result<bool, CommandError> {
+        let Some(manager) = self.undo.as_mut() else {
+            return Ok(false);
+        };
+        let source = if redo {
+            &mut manager.redo
+        } else {
+            &mut manager.undo
+        };
+        let Some(entry) = source.pop() else {
+            return Ok(false);
+        };
+        manager.applying = true;
+        let cmd = if redo { &entry.redo } else { &entry.undo };
+        let frame = self.current_command_scope().cloned().unwrap_or_default();
+        let guard = self.push_command_scope(frame);
+        let result = commands::dispatch(self, entry.node, cmd);
+        self.pop_command_scope(guard);
+        if let Some(manager) = self.undo.as_mut() {
+            manager.applying = false;
+            match (&result, redo) {
+                (Ok(_), false) | (Err(_), true) => manager.redo.push(entry),
+                (Ok(_), true) | (Err(_), false) => manager.undo.push(entry),
+            }
+        }
+        result.map(|_| true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{CommandArgs, CommandId};
+
+    fn entry(label: &str) -> UndoEntry {
+        let inv = CommandInvocation {
+            id: CommandId("test::cmd"),
+            args: CommandArgs::default(),
+        };
+        UndoEntry::new(NodeId::default(), label, inv.clone(), inv)
+    }
+
+    #[test]
+    fn push_trims_to_limit_and_clears_redo() {
+        let mut undo = UndoManager::new(2);
+        undo.push(entry("a"));
+        undo.push(entry("b"));
+        undo.push(entry("c"));
+        assert_eq!(undo.undo_len(), 2);
+        assert_eq!(undo.undo_label(), Some("c"));
+
+        let undone = undo.undo.pop();
+        undo.redo.extend(undone);
+        undo.applying = true;
+        undo.push(entry("ignored"));
+        undo.applying = false;
+        assert_eq!(undo.undo_label(), Some("b"));
+        assert_eq!(undo.redo_label(), Some("c"));
+
+        undo.push(entry("d"));
+        assert_eq!(undo.redo_len(), 0);
+        assert_eq!(undo.undo_label(), Some("d"));
+    }
+}
@@ -65,6 +65,17 @@ impl View {
         }
     }
 
+    /// Return the scroll offset that brings `rect`, in content coordinates, into view with
+    /// the least movement. A rect larger than the view aligns its top-left corner with the
+    /// view's. The offset is clamped to the canvas.
+    pub fn ensure_visible(&self, rect: Rect) -> Point {
+        let view = self.view_rect();
+        Point {
+            x: ensure_span(view.tl.x, view.w, rect.tl.x, rect.w, self.canvas.w),
+            y: ensure_span(view.tl.y, view.h, rect.tl.y, rect.h, self.canvas.h),
+        }
+    }
+
     /// Calculates the (pre, active, post) rectangles needed to draw a vertical
     /// scroll bar for this view in the specified margin rect.
     pub fn vactive(&self, margin: Rect) -> Result<Option<(Rect, Rect, Rect)>> {
@@ -100,6 +111,42 @@ impl View {
             )))
         }
     }
+
+    /// Vertical scroll position as a fraction from 0.0 at the top to 1.0 at the bottom, or
+    /// `None` if the canvas fits in the view.
+    pub fn scroll_fraction(&self) -> Option<f64> {
+        let max = self.canvas.h.saturating_sub(self.content.h);
+        (max > 0).then(|| f64::from(self.tl.y.min(max)) / f64::from(max))
+    }
+
+    /// Label for the vertical scroll position, as shown in status lines: `ALL` if the
+    /// canvas fits, `TOP` or `BOT` at either end, and otherwise the percentage scrolled,
+    /// such as `43%`.
+    pub fn scroll_label(&self) -> String {
+        match self.scroll_fraction() {
+            None => "ALL".into(),
+            Some(f) if f <= 0.0 => "TOP".into(),
+            Some(f) if f >= 1.0 => "BOT".into(),
+            Some(f) => format!("{}%", ((f * 100.0).round() as u32).clamp(1, 99)),
+        }
+    }
+}
+
+/// Return the offset of a window of `len` starting at `start` moved as little as possible
+/// to show the span `target..target + target_len`, clamped to `canvas`.
+fn ensure_span(start: u32, len: u32, target: u32, target_len: u32, canvas: u32) -> u32 {
+    if len == 0 {
+        return 0;
+    }
+    let end = target.saturating_add(target_len);
+    let offset = if target < start || target_len > len {
+        target
+    } else if end > start.saturating_add(len) {
+        end - len
+    } else {
+        start
+    };
+    offset.min(canvas.saturating_sub(len))
 }
 
 #[cfg(test)]
@@ -193,10 +240,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ensure_visible_moves_least() {
+        let view = view_for_sizes(Size::new(10, 5), Size::new(30, 20), Point { x: 0, y: 5 });
+        // Already visible.
+        assert_eq!(
+            view.ensure_visible(Rect::new(2, 6, 3, 2)),
+            Point { x: 0, y: 5 }
+        );
+        // Above and below the view.
+        assert_eq!(
+            view.ensure_visible(Rect::new(0, 2, 1, 1)),
+            Point { x: 0, y: 2 }
+        );
+        assert_eq!(
+            view.ensure_visible(Rect::new(0, 12, 1, 2)),
+            Point { x: 0, y: 9 }
+        );
+        // Right of the view.
+        assert_eq!(
+            view.ensure_visible(Rect::new(12, 6, 2, 1)),
+            Point { x: 4, y: 5 }
+        );
+        // Too tall to fit aligns the top, and offsets clamp to the canvas.
+        assert_eq!(
+            view.ensure_visible(Rect::new(0, 1, 1, 8)),
+            Point { x: 0, y: 1 }
+        );
+        assert_eq!(
+            view.ensure_visible(Rect::new(0, 18, 1, 8)),
+            Point { x: 0, y: 15 }
+        );
+    }
+
     #[test]
     fn scrollbars_are_absent_when_canvas_matches_view() {
         let view = view_for_sizes(Size::new(10, 5), Size::new(10, 5), Point::zero());
         assert!(view.vactive(Rect::new(0, 0, 1, 5)).unwrap().is_none());
         assert!(view.hactive(Rect::new(0, 0, 10, 1)).unwrap().is_none());
     }
+
+    #[test]
+    fn scroll_position_labels() {
+        let at = |y| view_for_sizes(Size::new(10, 5), Size::new(10, 305), Point { x: 0, y });
+        assert_eq!(at(0).scroll_fraction(), Some(0.0));
+        assert_eq!(at(150).scroll_fraction(), Some(0.5));
+        assert_eq!(at(0).scroll_label(), "TOP");
+        assert_eq!(at(129).scroll_label(), "43%");
+        assert_eq!(at(1).scroll_label(), "1%");
+        assert_eq!(at(299).scroll_label(), "99%");
+        assert_eq!(at(300).scroll_label(), "BOT");
+        let fits = view_for_sizes(Size::new(10, 5), Size::new(10, 5), Point::zero());
+        assert_eq!(fits.scroll_fraction(), None);
+        assert_eq!(fits.scroll_label(), "ALL");
+    }
 }
@@ -1,14 +1,30 @@
 use std::{
-    any::TypeId,
+    any::{Any, TypeId},
     cell::{Cell, RefCell},
     collections::{HashMap, HashSet},
+    mem,
+    path::PathBuf,
+    sync::mpsc,
+    time::{Duration, Instant},
 };
 
 use slotmap::SlotMap;
 
 use super::{
+    anim::{self, Tween},
+    clock::Clock,
+    drag::DragState,
     focus::FocusRecoveryHint,
-    help::OwnedHelpSnapshot,
+    help::{CatalogBinding, OwnedHelpSnapshot},
+    hooks::Hooks,
+    isolate,
+    macros::Macros,
+    message::PendingMessage,
+    observe::{Observable, Observer},
+    persist::SessionRequest,
+    profile::Profiler,
+    timer::Timers,
+    undo::UndoManager,
     widget_access::{
         WidgetMutGuard, WidgetReadGuard, WidgetSlotGuard, WidgetSlotPolicy, validate_slot,
     },
@@ -16,16 +32,22 @@ use super::{
 use crate::{
     ReadContext,
     backend::BackendControl,
-    commands::{CommandScopeFrame, CommandSet},
-    core::{context::CoreContext, id::NodeId, node::Node, view::View},
-    error::{Error, Result},
+    commands::{CommandInvocation, CommandScopeFrame, CommandSet},
+    core::{
+        context::{CoreContext, CoreViewContext},
+        id::NodeId,
+        inputmap::InputSpec,
+        node::Node,
+        view::View,
+    },
+    error::{Error, RenderFailure, Result},
     event::Event,
     geom::{Point, Rect, RectI32, Size},
     layout::{
         Align, CanvasChild, CanvasContext, Constraint, Direction as LayoutDirection, Display,
         Layout, MeasureConstraints, Measurement, Sizing,
     },
-    path::Path,
+    path::{Path, PathFilter},
     render::Render,
     state::NodeName,
     style::StyleMap,
@@ -46,10 +68,41 @@ pub struct Core {
     pub(crate) backend: Option<Box<dyn BackendControl>>,
     /// Exit code requested by a widget or command, if any.
     pub(crate) exit_requested: Option<i32>,
+    /// Value handed back to the runloop caller by the exit request, if any.
+    pub(crate) exit_value: Option<Box<dyn Any>>,
+    /// Whether the next render must repaint the full terminal.
+    pub(crate) full_render_requested: bool,
+    /// Whether any node has reported damage since the last render.
+    pub(crate) damaged: bool,
+    /// Whether layout or tree changes moved any node's view since the last render.
+    pub(crate) views_changed: bool,
     /// Pending style map to be applied before next render.
     pub(crate) pending_style: Option<StyleMap>,
+    /// Terminal size reported by the backend after resuming, applied before next render.
+    pub(crate) pending_resize: Option<Size>,
+    /// Whether styles render in high contrast.
+    pub(crate) high_contrast: bool,
+    /// The first node render that failed while render errors were caught, shown as an
+    /// overlay until dismissed.
+    pub(crate) render_failure: Option<RenderFailure>,
+    /// Whether panics in widget callbacks are caught and the panicking node marked failed.
+    pub(crate) isolate_panics: bool,
+    /// Whether widgets should avoid motion such as animations.
+    pub(crate) reduced_motion: bool,
+    /// Duration of smooth scroll animations, or `None` to scroll instantly.
+    pub(crate) smooth_scroll: Option<Duration>,
+    /// Running scroll animations by node.
+    pub(crate) scroll_anims: HashMap<NodeId, Tween<Point>>,
+    /// Focus scopes confining focus traversal, innermost last.
+    pub(crate) focus_scopes: Vec<NodeId>,
+    /// Previously focused nodes saved by `focus_push`, most recent last.
+    pub(crate) focus_history: Vec<NodeId>,
     /// Node that captures mouse events regardless of cursor position.
     pub(crate) mouse_capture: Option<NodeId>,
+    /// Deepest node under the mouse pointer.
+    pub(crate) hover: Option<NodeId>,
+    /// Active drag-and-drop operation.
+    pub(crate) drag: Option<DragState>,
     /// Focus recovery hint for the most recent structural removal.
     pub(crate) focus_hint: Option<FocusRecoveryHint>,
     /// Active structural transaction for rollback on failure.
@@ -64,8 +117,39 @@ pub struct Core {
     pub(crate) pending_help_snapshot: Option<OwnedHelpSnapshot>,
     /// Tracks whether a pending help snapshot was observed during render.
     pending_help_snapshot_observed: Cell<bool>,
+    /// Whether a widget asked for the binding catalog to be refreshed.
+    pub(crate) binding_catalog_requested: bool,
+    /// Bindings in every mode, captured on request at the start of a render and
+    /// recaptured whenever the keymap changes after that.
+    pub(crate) binding_catalog: Observable<Vec<CatalogBinding>>,
     /// Pending diagnostic dump request.
     pub(crate) pending_diagnostic_dump: Option<NodeId>,
+    /// Destination of a screenshot requested for the end of the next render.
+    pub(crate) pending_screenshot: Option<PathBuf>,
+    /// Luau sources queued by widgets, with the node each runs against.
+    pub(crate) pending_scripts: Vec<(NodeId, String)>,
+    /// Key bindings queued by widgets, with the node whose subtree each is scoped to.
+    pub(crate) pending_bindings: Vec<(NodeId, InputSpec, CommandInvocation)>,
+    /// Session saves and loads queued by widgets.
+    pub(crate) pending_sessions: Vec<SessionRequest>,
+    /// Messages queued between nodes, delivered after the current event.
+    pub(crate) pending_messages: Vec<PendingMessage>,
+    /// Nodes subscribed to observable values.
+    pub(crate) observers: Vec<Observer>,
+    /// Macro registers and recording state.
+    pub(crate) macros: Macros,
+    /// Undo history, present once an app enables undo.
+    pub(crate) undo: Option<UndoManager>,
+    /// Application-registered event and command hooks.
+    pub(crate) hooks: Hooks,
+    /// Timers set by widgets.
+    pub(crate) timers: Timers,
+    /// Time source for polls and timers.
+    pub(crate) clock: Clock,
+    /// Runloop event channel handed to poll wakers, set once the core belongs to an app.
+    pub(crate) event_tx: Option<mpsc::Sender<Event>>,
+    /// Frame profiler, present while profiling is enabled.
+    pub(crate) profiler: Option<Profiler>,
 }
 
 #[derive(Default)]
@@ -155,14 +239,21 @@ impl Core {
             content_size: Size::default(),
             canvas: Size::default(),
             scroll: Point::zero(),
+            reported_scroll: Point::zero(),
             view: View::default(),
             hidden: false,
+            tab_index: None,
             name: root_name,
+            path_name: None,
             initialized: false,
             mounted: false,
+            reported_size: None,
             layout_dirty: false,
+            damage: None,
             effects: None,
             clear_inherited_effects: false,
+            unclipped: false,
+            failed: None,
         });
 
         Self {
@@ -172,8 +263,23 @@ impl Core {
             focus_gen: 1,
             backend: None,
             exit_requested: None,
+            exit_value: None,
+            full_render_requested: false,
+            damaged: false,
+            views_changed: false,
             pending_style: None,
+            pending_resize: None,
+            high_contrast: false,
+            render_failure: None,
+            isolate_panics: false,
+            reduced_motion: false,
+            smooth_scroll: None,
+            scroll_anims: HashMap::new(),
+            focus_scopes: Vec::new(),
+            focus_history: Vec::new(),
             mouse_capture: None,
+            hover: None,
+            drag: None,
             focus_hint: None,
             transaction: None,
             commands: CommandSet::new(),
@@ -181,7 +287,22 @@ impl Core {
             pending_help_request: None,
             pending_help_snapshot: None,
             pending_help_snapshot_observed: Cell::new(false),
+            binding_catalog_requested: false,
+            binding_catalog: Observable::new(Vec::new()),
             pending_diagnostic_dump: None,
+            pending_screenshot: None,
+            pending_scripts: Vec::new(),
+            pending_bindings: Vec::new(),
+            pending_sessions: Vec::new(),
+            pending_messages: Vec::new(),
+            observers: Vec::new(),
+            macros: Macros::default(),
+            undo: None,
+            hooks: Hooks::default(),
+            timers: Timers::default(),
+            clock: Clock::Real,
+            event_tx: None,
+            profiler: None,
         }
     }
 
@@ -202,11 +323,212 @@ impl Core {
         }
     }
 
+    /// Request a cooperative exit with status 0 that hands `value` back to the caller.
+    pub(crate) fn request_exit_with(&mut self, value: Box<dyn Any>) {
+        if self.exit_requested.is_none() {
+            self.exit_requested = Some(0);
+            self.exit_value = Some(value);
+        }
+    }
+
     /// Take the pending exit request, if any.
     pub(crate) fn take_exit_request(&mut self) -> Option<i32> {
         self.exit_requested.take()
     }
 
+    /// Request a full repaint, discarding the previous terminal buffer.
+    pub(crate) fn request_full_render(&mut self) {
+        self.full_render_requested = true;
+    }
+
+    /// Enable or disable high-contrast rendering, repainting the screen on a change.
+    pub(crate) fn set_high_contrast(&mut self, enabled: bool) {
+        if self.high_contrast != enabled {
+            self.high_contrast = enabled;
+            self.request_full_render();
+        }
+    }
+
+    /// Clear the recorded render failure and any panicked nodes, repainting the screen so
+    /// the failed nodes render again.
+    pub(crate) fn dismiss_render_failure(&mut self) {
+        let mut dismissed = self.render_failure.take().is_some();
+        for node in self.nodes.values_mut() {
+            dismissed |= node.failed.take().is_some();
+        }
+        if dismissed {
+            self.request_full_render();
+        }
+    }
+
+    /// Run a callback on `node_id`'s widget, catching a panic while panic isolation is on.
+    /// A panicking node is marked failed. Returns `None` if the callback panicked or the
+    /// node had already failed, so failed nodes are not called again until the failure is
+    /// dismissed.
+    pub(crate) fn isolated<R>(
+        &mut self,
+        node_id: NodeId,
+        phase: &str,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> Option<R> {
+        if !self.isolate_panics {
+            return Some(f(self));
+        }
+        if self
+            .nodes
+            .get(node_id)
+            .is_some_and(|node| node.failed.is_some())
+        {
+            return None;
+        }
+        match isolate::catch(|| f(self)) {
+            Ok(result) => Some(result),
+            Err(message) => {
+                self.fail_node(node_id, phase, message);
+                None
+            }
+        }
+    }
+
+    /// Mark a node failed after a panic in one of its callbacks, logging the message at
+    /// error level.
+    pub(crate) fn fail_node(&mut self, node_id: NodeId, phase: &str, message: String) {
+        tracing::error!(
+            "widget at {} panicked while {phase}: {message}",
+            self.node_path(self.root, node_id)
+        );
+        if let Some(node) = self.nodes.get_mut(node_id) {
+            node.failed = Some(message);
+        }
+        self.damage_view(node_id);
+    }
+
+    /// Deliver an event to a single node's widget. A node that panics ignores the event.
+    fn deliver_event(&mut self, node_id: NodeId, event: &Event) -> Result<EventOutcome> {
+        let outcome = self.isolated(node_id, "handling an event", |core| {
+            core.with_widget_mut(node_id, |w, core| {
+                let mut ctx = CoreContext::new(core, node_id);
+                w.on_event(event, &mut ctx)
+            })
+        });
+        let outcome = match outcome {
+            Some(outcome) => outcome??,
+            None => EventOutcome::Ignore,
+        };
+        if outcome == EventOutcome::Handle {
+            self.damage_view(node_id);
+        }
+        Ok(outcome)
+    }
+
+    /// Return the offset a node is scrolling toward: the target of a running animation, or
+    /// the current offset.
+    pub(crate) fn scroll_target(&self, node_id: NodeId) -> Option<Point> {
+        let node = self.nodes.get(node_id)?;
+        Some(
+            self.scroll_anims
+                .get(&node_id)
+                .map_or(node.scroll, |tween| *tween.target()),
+        )
+    }
+
+    /// Scroll a node toward `target`, clamped to its canvas. The offset animates when smooth
+    /// scrolling is enabled and reduced motion is not, and jumps otherwise. Returns `true` if
+    /// the clamped target differs from the current offset.
+    pub(crate) fn smooth_scroll_to(&mut self, node_id: NodeId, mut target: Point) -> bool {
+        let now = self.clock.now();
+        let Some(node) = self.nodes.get_mut(node_id) else {
+            return false;
+        };
+        clamp_scroll(&mut target, node.content_size, node.canvas);
+        let current = node.scroll;
+        let duration = self
+            .smooth_scroll
+            .filter(|d| !d.is_zero() && !self.reduced_motion);
+        let Some(duration) = duration else {
+            node.scroll = target;
+            return current != target;
+        };
+        if let Some(tween) = self.scroll_anims.get_mut(&node_id) {
+            if *tween.target() != target {
+                tween.retarget(target, now);
+            }
+        } else if current != target {
+            let mut tween = Tween::new(current).with_duration(duration);
+            tween.retarget(target, now);
+            self.scroll_anims.insert(node_id, tween);
+            self.timers
+                .set(node_id, anim::SCROLL_TIMER, anim::FRAME_INTERVAL, true, now);
+        }
+        current != target
+    }
+
+    /// Scroll a node and its ancestors so `rect`, in the node's canvas coordinates, is on
+    /// screen. Offsets move through [`Self::smooth_scroll_to`]. Returns `true` if any
+    /// offset changes.
+    pub(crate) fn scroll_rect_into_view(&mut self, node_id: NodeId, mut rect: Rect) -> bool {
+        let mut changed = false;
+        let mut current = node_id;
+        loop {
+            let (Some(node), Some(tl)) = (self.nodes.get(current), self.scroll_target(current))
+            else {
+                break;
+            };
+            let content = RectI32::new(0, 0, node.content_size.w, node.content_size.h);
+            let view = View::new(content, content, tl, node.canvas);
+            let (parent, outer, padding) = (node.parent, node.rect, node.layout.padding);
+            let offset = view.ensure_visible(rect);
+            if offset != tl {
+                changed |= self.smooth_scroll_to(current, offset);
+            }
+            let Some(parent) = parent else {
+                break;
+            };
+            let visible = Rect::new(offset.x, offset.y, view.content.w, view.content.h);
+            let Some(shown) = rect.intersect(&visible) else {
+                break;
+            };
+            // Re-express the visible part in the parent's canvas coordinates.
+            rect = Rect::new(
+                outer.tl.x + padding.left + shown.tl.x - offset.x,
+                outer.tl.y + padding.top + shown.tl.y - offset.y,
+                shown.w,
+                shown.h,
+            );
+            current = parent;
+        }
+        changed
+    }
+
+    /// Stop a node's scroll animation, leaving the offset where it is.
+    pub(crate) fn cancel_scroll(&mut self, node_id: NodeId) {
+        if self.scroll_anims.remove(&node_id).is_some() {
+            self.timers.cancel(node_id, anim::SCROLL_TIMER);
+        }
+    }
+
+    /// Advance a node's scroll animation to `now`, ending it once it settles.
+    pub(crate) fn step_scroll(&mut self, node_id: NodeId, now: Instant) {
+        let Some(tween) = self.scroll_anims.get(&node_id) else {
+            self.timers.cancel(node_id, anim::SCROLL_TIMER);
+            return;
+        };
+        let value = tween.value(now);
+        let running = tween.is_running(now);
+        if let Some(node) = self.nodes.get_mut(node_id) {
+            node.scroll = value;
+            clamp_scroll(&mut node.scroll, node.content_size, node.canvas);
+        }
+        if !running {
+            self.cancel_scroll(node_id);
+        }
+    }
+
+    /// Take the pending full repaint request.
+    pub(crate) fn take_full_render_request(&mut self) -> bool {
+        mem::take(&mut self.full_render_requested)
+    }
+
     /// Request a diagnostic dump for a target node.
     pub(crate) fn request_diagnostic_dump(&mut self, target: NodeId) {
         self.pending_diagnostic_dump = Some(target);
@@ -217,6 +539,31 @@ impl Core {
         self.pending_diagnostic_dump.take()
     }
 
+    /// Queue a Luau source string to run against `node` after the current input event.
+    pub(crate) fn queue_script(&mut self, node: NodeId, source: String) {
+        self.pending_scripts.push((node, source));
+    }
+
+    /// Take all queued scripts in submission order.
+    pub(crate) fn take_pending_scripts(&mut self) -> Vec<(NodeId, String)> {
+        mem::take(&mut self.pending_scripts)
+    }
+
+    /// Queue a key binding scoped to `node`, installed after the current callback.
+    pub(crate) fn queue_binding(
+        &mut self,
+        node: NodeId,
+        input: InputSpec,
+        command: CommandInvocation,
+    ) {
+        self.pending_bindings.push((node, input, command));
+    }
+
+    /// Take all queued key bindings in submission order.
+    pub(crate) fn take_pending_bindings(&mut self) -> Vec<(NodeId, InputSpec, CommandInvocation)> {
+        mem::take(&mut self.pending_bindings)
+    }
+
     /// Return the current command-scope frame, if any.
     pub(crate) fn current_command_scope(&self) -> Option<&CommandScopeFrame> {
         self.command_scope.last()
@@ -271,14 +618,21 @@ impl Core {
             content_size: Size::default(),
             canvas: Size::default(),
             scroll: Point::zero(),
+            reported_scroll: Point::zero(),
             view: View::default(),
             hidden: false,
+            tab_index: None,
             name,
+            path_name: None,
             initialized: false,
             mounted: false,
+            reported_size: None,
             layout_dirty: false,
+            damage: None,
             effects: None,
             clear_inherited_effects: false,
+            unclipped: false,
+            failed: None,
         });
         self.record_created(node_id);
         node_id
@@ -308,6 +662,33 @@ impl Core {
         self.with_layout_of(node, |l| *l = layout)
     }
 
+    /// Give a node an explicit name, overriding the widget's own name in paths. Commands
+    /// still find the node by its widget name. The name survives widget replacement and
+    /// must differ from every sibling's name in paths, so a path can address the node
+    /// unambiguously.
+    pub fn set_name_of(&mut self, node: impl Into<NodeId>, name: NodeName) -> Result<()> {
+        let node_id = node.into();
+        let parent = self
+            .nodes
+            .get(node_id)
+            .ok_or(Error::NodeNotFound(node_id))?
+            .parent;
+        if let Some(parent) = parent.and_then(|p| self.nodes.get(p)) {
+            let taken = parent.children.iter().any(|child| {
+                *child != node_id && self.nodes.get(*child).is_some_and(|c| c.name() == &name)
+            });
+            if taken {
+                return Err(Error::DuplicateNodeName(name.to_string()));
+            }
+        }
+        let node = self
+            .nodes
+            .get_mut(node_id)
+            .ok_or(Error::NodeNotFound(node_id))?;
+        node.path_name = Some(name);
+        Ok(())
+    }
+
     /// Replace the widget stored at a node.
     pub fn replace_widget_keep_children<W>(
         &mut self,
@@ -331,6 +712,7 @@ impl Core {
         node.widget_type = widget_type;
         node.mounted = false;
         node.initialized = false;
+        node.reported_size = None;
         self.ensure_invariants(None);
         Ok(())
     }
@@ -675,7 +1057,7 @@ impl Core {
         Ok(())
     }
 
-    /// Validate focus and mouse capture targets.
+    /// Validate focus, mouse capture, and hover targets.
     fn validate_focus_and_capture(&self) -> Result<()> {
         if let Some(focus) = self.focus {
             self.validate_attached_target("focus", focus)?;
@@ -683,6 +1065,9 @@ impl Core {
         if let Some(capture) = self.mouse_capture {
             self.validate_attached_target("mouse capture", capture)?;
         }
+        if let Some(hover) = self.hover {
+            self.validate_attached_target("hover", hover)?;
+        }
         Ok(())
     }
 
@@ -1200,6 +1585,52 @@ impl Core {
         self.set_hidden(node_id, false)
     }
 
+    /// Minimum outer size a node needs to lay out without clipping. Derived from the
+    /// `min_width`/`min_height` bounds and padding of the node and its visible descendants;
+    /// axes that allow overflow scroll instead, so children do not contribute to them.
+    pub fn min_size(&self, node_id: impl Into<NodeId>) -> Size<u32> {
+        let Some(node) = self.nodes.get(node_id.into()) else {
+            return Size::ZERO;
+        };
+        let layout = node.layout;
+        let direction = layout.direction;
+        let (mut main, mut cross, mut count) = (0u32, 0u32, 0u32);
+        for child in &node.children {
+            let Some(child_node) = self.nodes.get(*child) else {
+                continue;
+            };
+            if child_node.hidden || child_node.layout.display == Display::None {
+                continue;
+            }
+            let size = self.min_size(*child);
+            main = if direction == LayoutDirection::Stack {
+                main.max(direction.main_size(size))
+            } else {
+                main.saturating_add(direction.main_size(size))
+            };
+            cross = cross.max(direction.cross_size(size));
+            count += 1;
+        }
+        if direction != LayoutDirection::Stack && count > 1 {
+            main = main.saturating_add(layout.gap.saturating_mul(count - 1));
+        }
+        let content = direction.size_from_main_cross(main, cross);
+        let content_w = if layout.overflow_x { 0 } else { content.w };
+        let content_h = if layout.overflow_y { 0 } else { content.h };
+        Size::new(
+            clamp_axis(
+                content_w.saturating_add(layout.padding.horizontal()),
+                layout.min_width,
+                layout.max_width,
+            ),
+            clamp_axis(
+                content_h.saturating_add(layout.padding.vertical()),
+                layout.min_height,
+                layout.max_height,
+            ),
+        )
+    }
+
     /// Run layout computation and synchronize views.
     pub fn update_layout(&mut self, screen_size: Size) -> Result<()> {
         refresh_layouts(self)?;
@@ -1213,6 +1644,28 @@ impl Core {
             screen_size,
         );
         pass.update_views(root, screen_view)?;
+        let resized = pass.resized;
+
+        for (node_id, size) in resized {
+            if !self.nodes.contains_key(node_id) {
+                continue;
+            }
+            let resized = self.isolated(node_id, "resizing", |core| {
+                core.with_widget_mut(node_id, |widget, core| {
+                    let mut ctx = CoreContext::new(core, node_id);
+                    widget.on_resize(size, &mut ctx)
+                })
+            });
+            if let Some(resized) = resized {
+                resized?.map_err(|error| {
+                    self.widget_operation_error(
+                        WidgetOperation::layout("on_resize"),
+                        node_id,
+                        &error,
+                    )
+                })?;
+            }
+        }
 
         self.ensure_focus_valid(None);
         self.validate_invariants()?;
@@ -1334,14 +1787,24 @@ impl Core {
         outcome
     }
 
+    /// Take the nodes whose scroll offset changed since it was last reported, recording
+    /// the new offsets as reported.
+    pub(crate) fn take_scroll_changes(&mut self) -> Vec<NodeId> {
+        let mut changed = Vec::new();
+        for (id, node) in &mut self.nodes {
+            if node.scroll != node.reported_scroll {
+                node.reported_scroll = node.scroll;
+                changed.push(id);
+            }
+        }
+        changed
+    }
+
     /// Dispatch an event to a node and bubble until handled.
     fn dispatch_event_inner(&mut self, start: NodeId, event: &Event) -> Result<EventOutcome> {
         let mut target = Some(start);
         while let Some(id) = target {
-            let outcome = self.with_widget_mut(id, |w, core| {
-                let mut ctx = CoreContext::new(core, id);
-                w.on_event(event, &mut ctx)
-            })??;
+            let outcome = self.deliver_event(id, event)?;
             match outcome {
                 EventOutcome::Handle | EventOutcome::Consume => return Ok(outcome),
                 EventOutcome::Ignore => {
@@ -1360,13 +1823,36 @@ impl Core {
     ) -> Result<EventOutcome> {
         let node_id = node_id.into();
         let depth = self.push_command_scope(self.command_scope_for_event(event));
-        let outcome = self.with_widget_mut(node_id, |w, core| {
-            let mut ctx = CoreContext::new(core, node_id);
-            w.on_event(event, &mut ctx)
-        });
+        let outcome = self.deliver_event(node_id, event);
         self.pop_command_scope(depth);
-        let outcome = outcome??;
-        Ok(outcome)
+        outcome
+    }
+
+    /// Deliver an event to every node whose path from the root matches `path_filter`,
+    /// in pre-order and without bubbling. Nodes whose widgets are mid-callback, such as
+    /// the sender, are skipped. Returns `Handle` if any node handled the event, else
+    /// `Consume` if any consumed it, else `Ignore`.
+    pub fn broadcast(&mut self, event: &Event, path_filter: &PathFilter) -> Result<EventOutcome> {
+        let targets = CoreViewContext::new(self, self.root).find_nodes_matching(path_filter);
+        let mut result = EventOutcome::Ignore;
+        for id in targets {
+            let available = self.nodes.get(id).is_some_and(|node| {
+                node.widget
+                    .try_borrow()
+                    .is_ok_and(|widget| widget.is_some())
+            });
+            if !available {
+                continue;
+            }
+            match self.dispatch_event_on_node(id, event)? {
+                EventOutcome::Handle => result = EventOutcome::Handle,
+                EventOutcome::Consume if result == EventOutcome::Ignore => {
+                    result = EventOutcome::Consume;
+                }
+                EventOutcome::Consume | EventOutcome::Ignore => {}
+            }
+        }
+        Ok(result)
     }
 
     /// Return the path for a node relative to a root.
@@ -1377,7 +1863,7 @@ impl Core {
         let mut current = Some(node_id);
         while let Some(id) = current {
             if let Some(node) = self.nodes.get(id) {
-                parts.push(node.name.to_string());
+                parts.push(node.name().to_string());
                 if id == root {
                     break;
                 }
@@ -1427,11 +1913,18 @@ fn refresh_layouts(core: &mut Core) -> Result<()> {
         .filter_map(|(node_id, node)| node.layout_dirty.then_some(node_id))
         .collect::<Vec<_>>();
     for node_id in dirty {
-        let layout = core.with_widget_read(
-            node_id,
-            WidgetOperation::layout("layout refresh"),
-            |widget, _core| widget.layout(),
-        )?;
+        let layout = core
+            .isolated(node_id, "laying out", |core| {
+                core.with_widget_read(
+                    node_id,
+                    WidgetOperation::layout("layout refresh"),
+                    |widget, _core| widget.layout(),
+                )
+            })
+            .transpose()?;
+        let Some(layout) = layout else {
+            continue;
+        };
         if let Some(node) = core.nodes.get_mut(node_id) {
             node.layout = layout;
             node.layout_dirty = false;
@@ -1455,6 +1948,8 @@ struct LayoutPass<'a> {
     core: &'a mut Core,
     /// Cached measurements for this pass.
     measure_cache: HashMap<MeasureKey, Measurement>,
+    /// Mounted nodes whose content size changed, with the new size.
+    resized: Vec<(NodeId, Size)>,
 }
 
 #[derive(Clone, Copy)]
@@ -1487,6 +1982,7 @@ impl<'a> LayoutPass<'a> {
         Self {
             core,
             measure_cache: HashMap::new(),
+            resized: Vec::new(),
         }
     }
 
@@ -1503,6 +1999,7 @@ impl<'a> LayoutPass<'a> {
             self.clear_layout(node_id, position)?;
             return Ok(Size::ZERO);
         }
+        let started = self.core.profiler.is_some().then(Instant::now);
 
         let mut effective_layout = layout;
         if parent_overflow.x {
@@ -1532,6 +2029,10 @@ impl<'a> LayoutPass<'a> {
         let canvas = self.compute_canvas(node_id, content_size)?;
         self.update_canvas(node_id, content_size, canvas);
 
+        if let (Some(started), Some(profiler)) = (started, self.core.profiler.as_mut()) {
+            profiler.record_layout(node_id, started.elapsed());
+        }
+
         Ok(outer)
     }
 
@@ -1555,8 +2056,11 @@ impl<'a> LayoutPass<'a> {
         };
 
         if hidden || layout.display == Display::None {
-            if let Some(node) = self.core.nodes.get_mut(node_id) {
+            if let Some(node) = self.core.nodes.get_mut(node_id)
+                && node.view != View::default()
+            {
                 node.view = View::default();
+                self.core.views_changed = true;
             }
             return Ok(());
         }
@@ -1581,10 +2085,18 @@ impl<'a> LayoutPass<'a> {
         );
 
         let view = View::new(outer, content, scroll, canvas);
-        if let Some(node) = self.core.nodes.get_mut(node_id) {
+        let node = self
+            .core
+            .nodes
+            .get_mut(node_id)
+            .ok_or(Error::NodeNotFound(node_id))?;
+        if node.view != view {
             node.view = view;
-        } else {
-            return Err(Error::NodeNotFound(node_id));
+            self.core.views_changed = true;
+        }
+        if node.mounted && node.reported_size != Some(content_size) {
+            node.reported_size = Some(content_size);
+            self.resized.push((node_id, content_size));
         }
 
         for child in children {
@@ -1996,7 +2508,7 @@ impl<'a> LayoutPass<'a> {
     }
 
     /// Compute the scrollable canvas size for a node.
-    fn compute_canvas(&self, node_id: NodeId, view_size: Size<u32>) -> Result<Size<u32>> {
+    fn compute_canvas(&mut self, node_id: NodeId, view_size: Size<u32>) -> Result<Size<u32>> {
         let children = self.visible_children(node_id).map_err(|error| {
             self.core
                 .widget_operation_error(WidgetOperation::layout("canvas"), node_id, &error)
@@ -2019,11 +2531,17 @@ impl<'a> LayoutPass<'a> {
             canvas_children.push(CanvasChild::new(node.rect, child_canvas));
         }
         let ctx = CanvasContext::new(&canvas_children);
-        let canvas = self.core.with_widget_read(
-            node_id,
-            WidgetOperation::layout("canvas"),
-            |widget, _core| widget.canvas(view_size, &ctx),
-        )?;
+        let canvas = self
+            .core
+            .isolated(node_id, "computing its canvas", |core| {
+                core.with_widget_read(
+                    node_id,
+                    WidgetOperation::layout("canvas"),
+                    |widget, _core| widget.canvas(view_size, &ctx),
+                )
+            })
+            .transpose()?
+            .unwrap_or(view_size);
         Ok(Size::new(
             canvas.w.max(view_size.w),
             canvas.h.max(view_size.h),
@@ -2087,11 +2605,17 @@ impl<'a> LayoutPass<'a> {
         if let Some(m) = self.measure_cache.get(&key) {
             return Ok(*m);
         }
-        let measured = self.core.with_widget_read(
-            node_id,
-            WidgetOperation::layout("measure"),
-            |widget, _core| widget.measure(constraints),
-        )?;
+        let measured = self
+            .core
+            .isolated(node_id, "measuring", |core| {
+                core.with_widget_read(
+                    node_id,
+                    WidgetOperation::layout("measure"),
+                    |widget, _core| widget.measure(constraints),
+                )
+            })
+            .transpose()?
+            .unwrap_or_else(|| constraints.wrap());
         self.measure_cache.insert(key, measured);
         Ok(measured)
     }
@@ -2406,6 +2930,17 @@ mod tests {
         }
     }
 
+    struct ResizeWidget {
+        sizes: Arc<Mutex<Vec<Size>>>,
+    }
+
+    impl Widget for ResizeWidget {
+        fn on_resize(&mut self, size: Size<u32>, _ctx: &mut dyn Context) -> Result<()> {
+            self.sizes.lock().unwrap().push(size);
+            Ok(())
+        }
+    }
+
     struct MountFailWidget;
 
     impl Widget for MountFailWidget {
@@ -2637,6 +3172,26 @@ mod tests {
         core.validate_invariants()
     }
 
+    #[test]
+    fn min_size_aggregates_visible_children() -> Result<()> {
+        let mut core = Core::new();
+        let parent = core.create_detached(simple_widget());
+        let a = core.create_detached(simple_widget());
+        let b = core.create_detached(simple_widget());
+        let hidden = core.create_detached(simple_widget());
+        core.set_children(parent, vec![a, b, hidden])?;
+        core.set_layout_of(parent, Layout::row().gap(1).padding(Edges::symmetric(1, 2)))?;
+        core.set_layout_of(a, Layout::fill().min_width(10).min_height(3))?;
+        core.set_layout_of(b, Layout::fill().min_width(5).min_height(4))?;
+        core.set_layout_of(hidden, Layout::fill().min_width(100))?;
+        core.set_hidden(hidden, true);
+        assert_eq!(core.min_size(parent), Size::new(20, 6));
+
+        core.set_layout_of(parent, Layout::row().overflow_x().min_height(10))?;
+        assert_eq!(core.min_size(parent), Size::new(0, 10));
+        Ok(())
+    }
+
     #[test]
     fn validate_invariants_rejects_detached_focus() {
         let mut core = Core::new();
@@ -2789,7 +3344,7 @@ mod tests {
 
         let error = core
             .with_widget_mut(child, |_widget, core| {
-                let pass = LayoutPass::new(core);
+                let mut pass = LayoutPass::new(core);
                 pass.compute_canvas(child, Size::new(1, 1))
             })?
             .expect_err("canvas should fail while the widget is extracted");
@@ -3021,6 +3576,30 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn resize_hook_reports_content_size_changes() -> Result<()> {
+        let mut core = Core::new();
+        let sizes = Arc::new(Mutex::new(Vec::new()));
+        let child = core.add_boxed(Box::new(ResizeWidget {
+            sizes: Arc::clone(&sizes),
+        }));
+        attach_root_child(&mut core, child)?;
+        core.with_layout_of(child, |layout| {
+            *layout = Layout::fill().padding(Edges::all(1));
+        })?;
+
+        core.update_layout(Size::new(10, 5))?;
+        core.update_layout(Size::new(10, 5))?;
+        core.update_layout(Size::new(20, 5))?;
+        core.hide(child);
+        core.update_layout(Size::new(30, 5))?;
+        core.show(child);
+        core.update_layout(Size::new(20, 5))?;
+
+        assert_eq!(*sizes.lock().unwrap(), [Size::new(8, 3), Size::new(18, 3)]);
+        Ok(())
+    }
+
     #[test]
     fn leaf_padding_consumes_all() -> Result<()> {
         let mut core = Core::new();
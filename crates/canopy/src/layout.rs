@@ -2,12 +2,13 @@
 
 use std::result::Result;
 
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::geom::{Rect, Size as GeomSize};
 
 /// Stack direction for children.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Direction {
     /// Stack children vertically (column).
     #[default]
@@ -19,7 +20,7 @@ pub enum Direction {
 }
 
 /// Alignment along an axis.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Align {
     /// Align to the start of the axis.
     #[default]
@@ -31,7 +32,7 @@ pub enum Align {
 }
 
 /// Display mode for layout participation.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Display {
     /// Node participates in layout and rendering.
     Block,
@@ -40,7 +41,7 @@ pub enum Display {
 }
 
 /// Sizing strategy for a single axis.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Sizing {
     /// Size derives from `measure()` or wrapping children.
     Measure,
@@ -76,7 +77,7 @@ pub enum LayoutValidationError {
 }
 
 /// Edge insets for padding.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Edges<T> {
     /// Top edge.
     pub top: T,
@@ -162,7 +163,7 @@ impl Direction {
 }
 
 /// Layout configuration for a node.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Layout {
     /// Whether this node participates in layout/render.
     pub display: Display,
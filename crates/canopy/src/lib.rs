@@ -29,25 +29,37 @@ pub mod layout;
 pub mod prelude;
 pub(crate) mod widget;
 
-pub use core::termbuf::TermBuf;
+pub use core::logging::{self, tracing_layer};
+pub use core::persist;
+pub use core::plugin;
+pub use core::termbuf::{Cell, CellDiff, TermBuf};
 #[cfg(any(test, feature = "testing"))]
 pub use core::testing;
 // Stable app-author surface.
 pub use core::{
-    AutomationCallback, AutomationHandle, BindingId, Canopy, ChildKey, CommandContext, Context,
-    Fixture, FixtureInfo, FocusContext, KeyedChildren, LayoutContext, Loader, NodeId, Path,
-    PathFilter, ReadContext, RemovePolicy, ScrollContext, Slot, StyleContext, TreeContext, TypedId,
+    Accessible, Announcer, AutomationCallback, AutomationHandle, BindingId, Canopy, ChangeCounter,
+    ChildKey, ChildSet, CommandContext, Context, DragPayload, DropTarget, EventSender, Fixture,
+    FixtureInfo, FocusCommands, FocusContext, Graft, HookFlow, HookHandle, KeyedChildren,
+    LayoutContext, LineAnnouncer, Loader, MacroStep, MessageSender, MessageTarget, NodeId,
+    Observable, Path, PathFilter, PersistentState, PollWaker, ReadContext, RemovePolicy, Role,
+    ScrollCommands, ScrollContext, Scrollable, Slot, StyleContext, TreeContext, TypedId, UndoEntry,
+    UndoManager,
 };
 // Lower-level runtime exports retained for internal crates and diagnostics.
 #[doc(hidden)]
-pub use core::{Core, InputMap, InputSpec, Preorder, RoutePhase, RouteTraceEntry};
+pub use core::{
+    Core, FrameProfile, InputMap, InputSpec, Node, NodeTiming, Preorder, Profiler, RoutePhase,
+    RouteTraceEntry,
+};
 #[doc(hidden)]
 pub use core::{
-    backend, commands, cursor, error, event, help, inputmap, path, render, script, state, style,
-    text, view,
+    anim, backend, commands, cursor, error, event, help, inputmap, path, render, script, state,
+    style, text, view,
 };
 
 // Re-export derive macros
-pub use canopy_derive::{CommandArg, CommandEnum, command, derive_commands};
+pub use canopy_derive::{
+    ChildSet, CommandArg, CommandEnum, Loader, command, derive_commands, view,
+};
 // Re-export widget trait and event outcome
 pub use widget::{EventOutcome, Widget};
@@ -13,6 +13,7 @@ pub use crate::{
     render::Render,
     state::NodeName,
     style::{StyleBuilder, StyleMap},
+    view,
 };
 
 /// Common result alias for Canopy operations.
@@ -6,8 +6,8 @@ use std::{
 };
 
 use crate::{
-    Context,
-    core::context::ReadContext,
+    Context, DropTarget, PersistentState, Scrollable,
+    core::{a11y::Accessible, context::ReadContext},
     cursor,
     error::Result,
     event::Event,
@@ -56,6 +56,24 @@ pub trait Widget: Any + Send {
         Ok(EventOutcome::Ignore)
     }
 
+    /// Receive a message sent to this node with `Context::send_to`. Widgets downcast the
+    /// payload to the types they understand; unhandled messages are dropped.
+    fn handle_message(&mut self, _msg: Box<dyn Any>, _ctx: &mut dyn Context) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called before a frame when a value this node observes has changed, so the widget
+    /// can rebuild state derived from it.
+    fn on_observed(&mut self, _ctx: &mut dyn Context) -> Result<()> {
+        Ok(())
+    }
+
+    /// Keyboard scrolling for this widget, used by the `scrollable::*` commands. Widgets
+    /// implementing [`Scrollable`] return `Some(self)`.
+    fn scrollable(&mut self) -> Option<&mut dyn Scrollable> {
+        None
+    }
+
     /// Attempt to focus this widget.
     ///
     /// Widgets can use the provided context to query their tree state (e.g., whether they have
@@ -64,16 +82,38 @@ pub trait Widget: Any + Send {
         false
     }
 
+    /// Accessibility metadata, announced when this widget holds focus.
+    fn accessible(&self, _ctx: &dyn ReadContext) -> Option<Accessible> {
+        None
+    }
+
     /// Cursor specification for focused widgets.
     fn cursor(&self) -> Option<cursor::Cursor> {
         None
     }
 
+    /// Additional cursors for focused widgets with several insertion points, such as a
+    /// multi-cursor editor. Only read from the widget whose `cursor` is shown, and always
+    /// drawn into the frame.
+    fn extra_cursors(&self) -> Vec<cursor::Cursor> {
+        Vec::new()
+    }
+
     /// Scheduled poll endpoint.
     fn poll(&mut self, _ctx: &mut dyn Context) -> Option<Duration> {
         None
     }
 
+    /// Drop target interface for widgets that accept dragged payloads.
+    fn drop_target(&mut self) -> Option<&mut dyn DropTarget> {
+        None
+    }
+
+    /// Persistent state interface for widgets saved by `Canopy::save_state`.
+    fn persistent(&mut self) -> Option<&mut dyn PersistentState> {
+        None
+    }
+
     /// Called exactly once when the widget is first mounted in the tree, before the first render.
     ///
     /// The framework guarantees single invocation via an internal `mounted` flag on each node.
@@ -92,6 +132,12 @@ pub trait Widget: Any + Send {
     /// Called exactly once immediately before the node is removed from the arena.
     fn on_unmount(&mut self, _ctx: &mut dyn Context) {}
 
+    /// Called after layout when the content size of a mounted, visible node changes,
+    /// including the first layout after mount.
+    fn on_resize(&mut self, _size: Size<u32>, _ctx: &mut dyn Context) -> Result<()> {
+        Ok(())
+    }
+
     /// Name used for commands and paths.
     fn name(&self) -> NodeName {
         let name = type_name::<Self>();
@@ -85,6 +85,47 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn dispatch_str_resolves_commands_and_aliases() -> Result<()> {
+        reset_state();
+
+        let mut canopy = Canopy::new();
+        canopy.add_commands::<TestLeaf>()?;
+        let leaf_id = canopy.core_mut().create_detached(TestLeaf);
+        let branch_id = canopy.core_mut().create_detached(TestBranch);
+        canopy.core_mut().set_children(branch_id, vec![leaf_id])?;
+        let root_id = canopy.root_id();
+        canopy.core_mut().set_children(root_id, vec![branch_id])?;
+
+        canopy.alias("leaf", "test_leaf::c_leaf()")?;
+        assert!(canopy.alias("bad", "test_leaf::missing").is_err());
+        assert!(
+            canopy
+                .alias("test_leaf::c_leaf", "test_leaf::c_leaf")
+                .is_err()
+        );
+
+        canopy.with_root_context(|ctx| {
+            assert_eq!(ctx.dispatch_str("test_leaf::c_leaf")?, ArgValue::Null);
+            assert_eq!(ctx.dispatch_str(" leaf ")?, ArgValue::Null);
+            assert!(matches!(
+                ctx.dispatch_str("test_leaf::c_leaf(1"),
+                Err(CommandError::Parse { .. })
+            ));
+            assert!(matches!(
+                ctx.dispatch_str("test_leaf::c_leaf(1)"),
+                Err(CommandError::ArityMismatch { .. })
+            ));
+            Ok(())
+        })?;
+        assert_eq!(
+            state_path(),
+            vec!["test_leaf.c_leaf()", "test_leaf.c_leaf()"]
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn duplicate_command_ids_are_deduplicated() -> Result<()> {
         reset_state();
@@ -2,15 +2,18 @@
 
 #[cfg(test)]
 mod tests {
+    use std::fs;
+
     use canopy::{
         Canopy, Context, EventOutcome, Loader, NodeId, ReadContext, Widget, command,
         derive_commands,
-        error::Result,
+        error::{Error, Result},
         event::{Event, mouse},
         layout::Layout,
         render::Render,
         testing::harness::Harness,
     };
+    use tempfile::TempDir;
 
     struct ApiLeaf {
         value: i32,
@@ -140,6 +143,12 @@ mod tests {
             canopy.set_focus(first)
             canopy.assert(canopy.focused() == first, "focus should move to the first leaf")
             canopy.assert(api_leaf.get() == 0, "focused dispatch should hit the first leaf")
+            canopy.assert(
+                canopy.focus_path() == "/api_root/api_leaf",
+                "focus_path should name the focused leaf"
+            )
+            local size = canopy.root_size()
+            canopy.assert(size.w == 20 and size.h == 5, "root_size should match the harness")
 
             canopy.cmd_on(second, "api_leaf::set", 9)
             canopy.assert(canopy.cmd_on(second, "api_leaf::get") == 9, "cmd_on should target a node")
@@ -270,6 +279,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn script_files_define_functions_for_bindings() -> Result<()> {
+        let mut harness = Harness::builder(ApiRoot).size(20, 5).build()?;
+        harness.render()?;
+
+        let dir = TempDir::new()?;
+        let path = dir.path().join("bindings.luau");
+        fs::write(
+            &path,
+            r#"
+            local function set_both(value: number)
+                for _, leaf in canopy.find_nodes("api_root/api_leaf") do
+                    canopy.cmd_on(leaf, "api_leaf::set", value)
+                end
+            end
+            canopy.bind("s", function() set_both(5) end)
+        "#,
+        )?;
+        harness.canopy.load_script_file(&path)?;
+
+        harness.script(r#"canopy.send_key("s")"#)?;
+        assert_eq!(leaf_values(&mut harness), vec![5, 5]);
+
+        let err = harness
+            .canopy
+            .load_script_file(&dir.path().join("missing.luau"))
+            .expect_err("missing script file should fail");
+        assert!(matches!(err, Error::Io(_)), "{err}");
+        Ok(())
+    }
+
     #[test]
     fn luau_can_switch_input_modes() -> Result<()> {
         let mut harness = Harness::builder(ApiRoot).size(20, 5).build()?;
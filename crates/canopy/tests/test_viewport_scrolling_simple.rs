@@ -6,7 +6,7 @@ mod tests {
         Canopy, Context, Loader, ReadContext, Widget, command, derive_commands,
         error::Result,
         event::key,
-        geom::Line,
+        geom::{Line, Point, Rect},
         layout::{CanvasContext, Size},
         render::Render,
         state::NodeName,
@@ -93,4 +93,32 @@ end)
 
         Ok(())
     }
+
+    #[test]
+    fn test_scroll_rect_into_view() -> Result<()> {
+        let mut harness = Harness::builder(ScrollTest::new()).size(30, 10).build()?;
+        harness.render()?;
+
+        let reveal = |harness: &mut Harness, rect: Rect| {
+            harness.with_root_context(|_: &mut ScrollTest, ctx| {
+                ctx.scroll_rect_into_view(rect);
+                Ok(ctx.view().tl)
+            })
+        };
+        assert_eq!(
+            reveal(&mut harness, Rect::new(2, 40, 4, 2))?,
+            Point { x: 0, y: 32 }
+        );
+        // Already visible, so nothing moves.
+        assert_eq!(
+            reveal(&mut harness, Rect::new(0, 35, 1, 1))?,
+            Point { x: 0, y: 32 }
+        );
+        assert_eq!(
+            reveal(&mut harness, Rect::new(50, 3, 1, 1))?,
+            Point { x: 21, y: 3 }
+        );
+
+        Ok(())
+    }
 }
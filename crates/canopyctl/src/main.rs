@@ -12,6 +12,7 @@ use std::{
 };
 
 use anyhow::{Context, Result, anyhow, bail};
+use canopy::tracing_layer;
 use canopy_mcp::{ApplyFixtureRequest, ScriptEvalOutcome, ScriptEvalRequest};
 use clap::{Args, Parser, Subcommand};
 use serde::{Deserialize, Serialize};
@@ -22,6 +23,7 @@ use tokio::{
     sync::Mutex,
     time::sleep,
 };
+use tracing_subscriber::prelude::*;
 
 /// MCP client name reported to spawned or connected servers.
 const CLIENT_NAME: &str = "canopyctl";
@@ -654,6 +656,7 @@ impl CanopyctlMcpServer {
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    tracing_subscriber::registry().with(tracing_layer()).init();
     let config = LoadedConfig::load()?;
 
     match cli.command {
@@ -8,7 +8,7 @@ use canopy::{
 };
 use canopy_examples::{
     cedit::{Ed, setup_bindings},
-    print_luau_api,
+    install_tracing, print_luau_api,
 };
 use canopy_widgets::Root;
 use clap::Parser;
@@ -50,6 +50,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     let contents = fs::read_to_string(&filename)?;
     let extension = file_extension(&filename);
     Root::install_app_with_inspector(&mut cnpy, Ed::new(&contents, &extension), false)?;
+    install_tracing();
     let exit_code = runloop_with_options(cnpy, RunloopOptions::ctrlc_dump())?;
     if exit_code != 0 {
         process::exit(exit_code);
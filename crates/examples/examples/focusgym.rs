@@ -8,7 +8,7 @@ use canopy::{
 };
 use canopy_examples::{
     focusgym::{FocusGym, setup_bindings},
-    print_luau_api,
+    install_tracing, print_luau_api,
 };
 use canopy_widgets::Root;
 use clap::Parser;
@@ -40,6 +40,7 @@ fn main() -> Result<()> {
     }
 
     Root::install_app_with_inspector(&mut cnpy, FocusGym::new(), args.inspector)?;
+    install_tracing();
     let exit_code = runloop_with_options(cnpy, RunloopOptions::ctrlc_dump())?;
     if exit_code != 0 {
         process::exit(exit_code);
@@ -8,7 +8,7 @@ use canopy::{
 };
 use canopy_examples::{
     imgview::{create_app, setup_bindings},
-    print_luau_api,
+    install_tracing, print_luau_api,
 };
 use canopy_widgets::{ImageView, Root};
 use clap::Parser;
@@ -46,6 +46,7 @@ fn main() -> Result<()> {
         }
     };
     let cnpy = create_app(&path)?;
+    install_tracing();
     let exit_code = runloop_with_options(cnpy, RunloopOptions::ctrlc_dump())?;
     if exit_code != 0 {
         process::exit(exit_code);
@@ -7,6 +7,7 @@ use canopy::{
     prelude::*,
 };
 use canopy_examples::{
+    install_tracing,
     intervals::{Intervals, setup_bindings},
     print_luau_api,
 };
@@ -37,6 +38,7 @@ fn main() -> Result<()> {
     }
 
     Root::install_app(&mut cnpy, Intervals::new())?;
+    install_tracing();
     let exit_code = runloop_with_options(cnpy, RunloopOptions::ctrlc_dump())?;
     if exit_code != 0 {
         process::exit(exit_code);
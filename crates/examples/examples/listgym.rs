@@ -7,6 +7,7 @@ use canopy::{
     prelude::*,
 };
 use canopy_examples::{
+    install_tracing,
     listgym::{ListGym, setup_bindings},
     print_luau_api,
 };
@@ -40,6 +41,7 @@ fn main() -> Result<()> {
     }
 
     Root::install_app_with_inspector(&mut cnpy, ListGym::new(), args.inspector)?;
+    install_tracing();
     let exit_code = runloop_with_options(cnpy, RunloopOptions::ctrlc_dump())?;
     if exit_code != 0 {
         process::exit(exit_code);
@@ -7,6 +7,7 @@ use canopy::{
     prelude::*,
 };
 use canopy_examples::{
+    install_tracing,
     pager::{Pager, setup_bindings},
     print_luau_api,
 };
@@ -49,6 +50,7 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     let contents = fs::read_to_string(filename)?;
     Root::install_app(&mut cnpy, Pager::new(&contents))?;
+    install_tracing();
     let exit_code = runloop_with_options(cnpy, RunloopOptions::ctrlc_dump())?;
     if exit_code != 0 {
         process::exit(exit_code);
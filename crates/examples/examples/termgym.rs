@@ -7,7 +7,7 @@ use canopy::{
     prelude::*,
 };
 use canopy_examples::{
-    print_luau_api,
+    install_tracing, print_luau_api,
     termgym::{TermGym, setup_bindings},
 };
 use canopy_widgets::Root;
@@ -40,6 +40,7 @@ fn main() -> Result<()> {
     }
 
     Root::install_app_with_inspector(&mut cnpy, TermGym::new(), args.inspector)?;
+    install_tracing();
     let exit_code = runloop_with_options(cnpy, RunloopOptions::ctrlc_dump())?;
     if exit_code != 0 {
         process::exit(exit_code);
@@ -6,7 +6,7 @@ use canopy::{
     backend::crossterm::{RunloopOptions, runloop_with_options},
     prelude::*,
 };
-use canopy_examples::{print_luau_api, textgym::TextGym};
+use canopy_examples::{install_tracing, print_luau_api, textgym::TextGym};
 use canopy_widgets::Root;
 use clap::Parser;
 
@@ -44,6 +44,7 @@ end)
     }
 
     Root::install_app(&mut cnpy, TextGym::new())?;
+    install_tracing();
     let exit_code = runloop_with_options(cnpy, RunloopOptions::ctrlc_dump())?;
     if exit_code != 0 {
         process::exit(exit_code);
@@ -12,7 +12,7 @@ use canopy::{
     prelude::*,
 };
 use canopy_examples::{
-    print_luau_api,
+    install_tracing, print_luau_api,
     widget::{DemoHost, DemoSize, FontDemo, FontSource, ListDemo, TermDemo},
     widget_editor::{WidgetEditor, setup_bindings},
 };
@@ -228,6 +228,7 @@ fn main() -> Result<()> {
         }
     };
     Root::install_app_with_inspector(&mut cnpy, demo, args.inspector)?;
+    install_tracing();
     let exit_code = runloop_with_options(cnpy, RunloopOptions::ctrlc_dump())?;
     if exit_code != 0 {
         process::exit(exit_code);
@@ -219,9 +219,7 @@ impl EditorGym {
             column_id,
             "Single line (text)",
             single_line,
-            EditorConfig::new()
-                .with_multiline(false)
-                .with_wrap(WrapMode::None),
+            EditorConfig::single_line(),
             Some(3),
             None,
         )?;
@@ -267,9 +267,12 @@ impl Widget for FocusGym {
 
     fn on_mount(&mut self, c: &mut dyn Context) -> Result<()> {
         c.set_layout(Layout::fill())?;
-        let root_block = c.add_child(Block::new(true))?;
-        c.add_child_to(root_block, Block::new(false))?;
-        c.add_child_to(root_block, Block::new(false))?;
+        view!(c;
+            Block::new(true) {
+                Block::new(false),
+                Block::new(false),
+            },
+        );
         Ok(())
     }
 }
@@ -670,6 +670,7 @@ impl Widget for FontGymInput {
             },
             shape: CursorShape::Block,
             blink: true,
+            color: None,
         })
     }
 
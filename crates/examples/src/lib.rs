@@ -1,6 +1,7 @@
 //! Example widgets used by canopy demos.
 
-use canopy::{Canopy, error::Result};
+use canopy::{Canopy, error::Result, tracing_layer};
+use tracing_subscriber::prelude::*;
 
 /// Cedit example nodes.
 pub mod cedit;
@@ -40,5 +41,11 @@ pub fn print_luau_api(cnpy: &mut Canopy) -> Result<()> {
     Ok(())
 }
 
+/// Install a global `tracing` subscriber that feeds canopy's log buffer, so the inspector's
+/// Logs panel shows the demo's log output.
+pub fn install_tracing() {
+    tracing_subscriber::registry().with(tracing_layer()).init();
+}
+
 #[cfg(test)]
 mod tests;
@@ -1,4 +1,4 @@
-use canopy::{derive_commands, prelude::*};
+use canopy::{command, derive_commands, prelude::*};
 use canopy_widgets::{Frame, Text};
 
 /// Default bindings for the pager demo.
@@ -45,15 +45,29 @@ end)
 canopy.bind_with("PageUp", { path = "pager", desc = "Page up" }, function()
     text.page(-1)
 end)
+canopy.bind_with("n", { path = "pager", desc = "Next match" }, function()
+    pager.search_next()
+end)
+canopy.bind_with("N", { path = "pager", desc = "Previous match" }, function()
+    pager.search_prev()
+end)
+canopy.bind_with("Esc", { path = "pager", desc = "Clear search" }, function()
+    pager.clear_search()
+end)
 canopy.bind_with("q", { path = "root", desc = "Quit" }, function()
     root.quit()
 end)
 "#;
 
 /// Simple pager widget for file contents.
+///
+/// Search with `:pager::search("pattern")`, then step through matches with `n` and `N`.
+/// The frame title shows the match position and count.
 pub struct Pager {
     /// Contents to display.
     contents: String,
+    /// Whether a search is active.
+    searching: bool,
 }
 
 #[derive_commands]
@@ -62,8 +76,65 @@ impl Pager {
     pub fn new(contents: &str) -> Self {
         Self {
             contents: contents.to_string(),
+            searching: false,
         }
     }
+
+    /// Highlight matches of a regular expression and jump to the first one.
+    /// @param pattern Regular expression to search for.
+    #[command]
+    pub fn search(&mut self, c: &mut dyn Context, pattern: String) -> Result<()> {
+        c.with_first_descendant::<Text, _>(|text, ctx| text.search(ctx, pattern))?;
+        self.searching = true;
+        self.sync_status(c)
+    }
+
+    /// Jump to the next match.
+    #[command]
+    pub fn search_next(&mut self, c: &mut dyn Context) -> Result<()> {
+        c.with_first_descendant::<Text, _>(|text, ctx| {
+            text.search_next(ctx);
+            Ok(())
+        })?;
+        self.sync_status(c)
+    }
+
+    /// Jump to the previous match.
+    #[command]
+    pub fn search_prev(&mut self, c: &mut dyn Context) -> Result<()> {
+        c.with_first_descendant::<Text, _>(|text, ctx| {
+            text.search_prev(ctx);
+            Ok(())
+        })?;
+        self.sync_status(c)
+    }
+
+    /// Clear the search highlights.
+    #[command]
+    pub fn clear_search(&mut self, c: &mut dyn Context) -> Result<()> {
+        c.with_first_descendant::<Text, _>(|text, ctx| {
+            text.clear_search(ctx);
+            Ok(())
+        })?;
+        self.searching = false;
+        self.sync_status(c)
+    }
+
+    /// Show the current match and match count in the frame title.
+    fn sync_status(&self, c: &mut dyn Context) -> Result<()> {
+        let status = c.with_first_descendant::<Text, _>(|text, _| {
+            let count = text.match_count();
+            Ok(match text.current_match() {
+                Some(current) => Some(format!("match {current}/{count}")),
+                None if self.searching => Some("no matches".to_string()),
+                None => None,
+            })
+        })?;
+        c.with_first_descendant::<Frame, _>(|frame, _| {
+            frame.set_title(status);
+            Ok(())
+        })
+    }
 }
 
 impl Widget for Pager {
@@ -86,6 +157,7 @@ impl Widget for Pager {
 
 impl Loader for Pager {
     fn load(c: &mut Canopy) -> Result<()> {
+        c.add_commands::<Self>()?;
         c.add_commands::<Text>()?;
         Ok(())
     }
@@ -7,8 +7,8 @@ use canopy::{
     style::{Attr, AttrSet, solarized},
 };
 use canopy_widgets::{
-    Box, Button, Center, Frame, List, ROUND_THICK, SINGLE, Selectable, Terminal, TerminalConfig,
-    Text, VStack,
+    Box, Button, Center, Frame, List, ROUND_THICK, SINGLE, Selectable, Text, VStack,
+    term::{Terminal, TerminalConfig},
 };
 use unicode_width::UnicodeWidthStr;
 
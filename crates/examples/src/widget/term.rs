@@ -10,7 +10,10 @@ use canopy::{
     state::NodeName,
     style::{Color, Paint, StyleMap},
 };
-use canopy_widgets::{Button, Frame, ROUND, Terminal, TerminalConfig};
+use canopy_widgets::{
+    Button, Frame, ROUND,
+    term::{Terminal, TerminalConfig},
+};
 
 /// Tab labels shown in the demo.
 const TAB_LABELS: [&str; 3] = ["claude", "codex", "gemini"];
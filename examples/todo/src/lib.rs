@@ -523,7 +523,7 @@ pub fn setup_app_with_config(cnpy: &mut Canopy, config: Option<&Path>) -> Result
     cnpy.finalize_api()?;
     cnpy.run_default_script(DEFAULT_BINDINGS)?;
     if let Some(config) = config {
-        cnpy.run_config(config)?;
+        cnpy.load_script_file(config)?;
     }
     Ok(())
 }